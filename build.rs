@@ -0,0 +1,11 @@
+/// Vygeneruje gRPC kód z `proto/store.proto`.
+///
+/// Používa `protoc` z balíka `protoc-bin-vendored`, takže na stroji nemusí byť
+/// nainštalovaný; premenná `PROTOC` ho môže nahradiť.
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::var_os("PROTOC").is_none() {
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    }
+    tonic_prost_build::compile_protos("proto/store.proto")?;
+    Ok(())
+}
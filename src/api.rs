@@ -1,45 +1,844 @@
+use std::{collections::BTreeMap, fmt::Display, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize};
+use tower::{Layer, ServiceExt};
+use tower_http::normalize_path::NormalizePathLayer;
+
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Html,
-    routing::{delete, get, post, put},
-    Json, Router,
+    body::{Body, Bytes},
+    extract::{multipart::MultipartError, DefaultBodyLimit, Extension, FromRef, Multipart, OriginalUri, Path, Query, State},
+    handler::Handler,
+    middleware,
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Router,
 };
 
 use crate::{
-    db::StoreDB,
-    structs::{Employee, Product},
+    barcode::{self, Ean, RenderOptions},
+    api_version::{self, ApiVersion},
+    auth::{self, AuthUser, JwtVerifier, Role},
+    batch,
+    body_log,
+    cache::{QueryCache, QueryKind},
+    client::Table,
+    config::Config,
+    db::{is_read_only_error, StoreDB, DEFAULT_COVERAGE_DAYS, DEFAULT_SALARY_DEVIATION, MAX_COMPARE_PRODUCTS},
+    db_filler::{DBFiller, EntitySelection},
+    error::StoreError,
+    export::{self, ExportRequest},
+    graphql,
+    health::{self, Readiness},
+    maintenance::{self, Maintenance},
+    read_only,
+    route_table::{delete, get, post, put, RouteTable},
+    stores::{self, StoreRegistry},
+    import,
+    json::{self, Json},
+    list_shape,
+    pdf,
+    phone,
+    pricing::{MarginPolicy, PriceRounding},
+    qr::{self, Qr},
+    range,
+    sku,
+    slow_query,
+    telemetry,
+    tx_session::{self, TxSessions},
+    web,
+    structs::{
+        AgingBucket, Alert, ApiKey, ApiKeyRequest, AuditFilter, AverageCost, AttendanceEntry, CreatedApiKey, BarcodeError, BarcodeValidationReport, BasketStats, BarcodeQuery, BreakEven, BatchError, BatchOp, BatchRequest, BatchStepResult, Bundle, BundleComponent, BulkCreateQuery, BulkCreateResult, BulkUpdate, BulkUpdateResult, CategoryDaysOfInventory, CategoryReorderBudget, DataImportReport, DeltaApplyReport, DiscountReport, CategoryLimit, CategoryLimitRequest, CategoryRemap, CategoryMargin, CoPurchase, DailyReport, DashboardSummary, MarginViolation, NetMargin, DateRange, DeleteByFilter, DeletionReport, Employee, EmployeeBatch, EmployeeHours, EmployeeMerge, EmployeeProfit, ExpiringBatch, Gmroi, ImportDiff, HeadcountReport, ImportReport, IntegrityReport, InventoryTurnover, JsonBodyError, LaborRatio, MaintenanceRequest, MaxStockRequest, MinStockRequest, MaintenanceStatus, ReadOnlyRequest, ReadOnlyStatus, RetentionReport, ShrinkageReport, SellThroughReport, SupplierScore, ShiftAssignment, ShiftAssignmentRequest, Order, OrderFilter, OrderReturn, OrgNode, Pagination, PayrollSummary, PriceListReport, Product, ProductBatch, PriceAnomaly, PriceElasticity, ProductComparison, ProductDelta, ProductDetail, ProductImage, ProductLookup, ProfitReport, QrQuery, Reservation, ReservationRequest, SaleRequest, ScheduledChangesQuery, ScheduledPriceChange, PriceScheduleRequest, SalaryAdjustment, SalaryAdjustmentReport, SalaryOutlier, SavedSearch, SavedSearchQuery, Scorecard, SearchEntity, Seasonality, ShiftCoverage, TagByFilter, TaggingReport, StatsSnapshot, StatusByFilter, StatusChangeReport, StockCoverage, StockCount, StockDeltaReport, StockMovement, StockSnapshot, StocktakeReport, SupplierOrderSheet, TxSessionInfo, TxStarted, UploadImportReport,
+        PriceAdjustment, PriceChange, PriceRoundingReport, ProductTranslation, SupplierReactivation, TranslationSearch,
+        PurchaseOrder, PurchaseOrderDiscrepancy, PurchaseOrderItem, PurchaseOrderReceipt,
+        PurchaseOrderReceiptResult, PurchaseOrderStatus, RemovedProducts, ReorderSuggestion, StockAdjustment, StockStatus,
+        ValuationQuery, ValuationReport,
+    },
 };
 
+/// Hlavička s celkovým počtom záznamov vyhovujúcich filtru.
+pub(crate) const X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
+
+/// Hlavička oznamujúca, že odpoveď bola skrátená na maximálny počet riadkov.
+const X_RESULT_TRUNCATED: HeaderName = HeaderName::from_static("x-result-truncated");
+
+/// Hlavička zoznamov produktov s použitou konvenciou k vyradeným produktom
+/// (`excluded` alebo `included`).
+const X_REMOVED_PRODUCTS: HeaderName = HeaderName::from_static("x-removed-products");
+
+/// Zdieľaný stav HTTP handlerov.
+#[derive(Clone)]
+pub struct AppState {
+    pub db: StoreDB,
+    pub config: Arc<Config>,
+    pub readiness: Readiness,
+    pub maintenance: Maintenance,
+    pub cache: QueryCache,
+    /// Overovač JWT (`None` = prihlasovanie tokenmi je vypnuté)
+    pub jwt: Option<JwtVerifier>,
+    /// Otvorené transakcie z `POST /tx`
+    pub tx: TxSessions,
+    /// Ďalšie obchody pod `/stores/{id}/...` (v routeroch samotných obchodov prázdny)
+    pub stores: StoreRegistry,
+}
+
+impl FromRef<AppState> for StoreDB {
+    fn from_ref(state: &AppState) -> Self {
+        state.db.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Config> {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+impl FromRef<AppState> for Readiness {
+    fn from_ref(state: &AppState) -> Self {
+        state.readiness.clone()
+    }
+}
+
+impl FromRef<AppState> for Maintenance {
+    fn from_ref(state: &AppState) -> Self {
+        state.maintenance.clone()
+    }
+}
+
+impl FromRef<AppState> for QueryCache {
+    fn from_ref(state: &AppState) -> Self {
+        state.cache.clone()
+    }
+}
+
+impl FromRef<AppState> for TxSessions {
+    fn from_ref(state: &AppState) -> Self {
+        state.tx.clone()
+    }
+}
+
+impl FromRef<AppState> for StoreRegistry {
+    fn from_ref(state: &AppState) -> Self {
+        state.stores.clone()
+    }
+}
+
+impl FromRef<AppState> for Option<JwtVerifier> {
+    fn from_ref(state: &AppState) -> Self {
+        state.jwt.clone()
+    }
+}
+
 /// Vytvorí a nakonfiguruje HTTP router aplikácie.
-pub fn create_router(db: StoreDB) -> Router {
-    Router::new()
-        .route("/", get(index_page))
+///
+/// Trasy sú dostupné aj pod `/api/v1` (pozri `api_version`), ich zoznam vráti
+/// `GET /routes` (pozri `route_table`).
+///
+/// Lomka na konci cesty sa pred smerovaním odstráni (`/products/` je `/products`).
+/// Veľkosť písmen v ceste sa rozlišuje: `/Products` je neznáma trasa (404), lebo
+/// parametre v ceste (SKU, názvy uložených vyhľadávaní, …) veľkosť písmen rozlišujú.
+pub fn create_router(state: AppState) -> Router {
+    let router = routes(&state)
+        .route("/tx", post(begin_tx))
+        .route("/tx/{id}/commit", post(commit_tx))
+        .route("/tx/{id}/rollback", post(rollback_tx))
+        .route("/batch", post(run_batch))
+        .into_router_with_listing("/routes")
+        .layer(middleware::from_fn_with_state(state.clone(), tx_session::forward))
+        .layer(middleware::from_fn_with_state(state.clone(), list_shape::negotiate))
+        .layer(middleware::from_fn_with_state(state.clone(), read_only::guard))
+        .layer(middleware::from_fn_with_state(state.clone(), auth::authorize))
+        .layer(middleware::from_fn_with_state(state.clone(), maintenance::guard))
+        .layer(middleware::from_fn_with_state(state.clone(), body_log::log_bodies))
+        .layer(middleware::from_fn(telemetry::trace_requests))
+        .layer(middleware::from_fn_with_state(state.clone(), stores::dispatch))
+        .with_state(state);
+    let router = router.map_request(api_version::strip_prefix);
+    Router::new().fallback_service(NormalizePathLayer::trim_trailing_slash().layer(router))
+}
+
+/// Trasy aplikácie bez middleware – zdieľa ich hlavný router aj transakcie z `POST /tx`.
+///
+/// # Arguments
+/// * `state` – stav, nad ktorým beží GraphQL schéma
+fn routes(state: &AppState) -> RouteTable<AppState> {
+    // vyžadujú rolu admin (`auth::required_role`)
+    let admin = RouteTable::new()
+        .route("/admin/purge-removed", post(purge_removed))
+        .route("/admin/import-ndjson", post(import_ndjson))
+        .route("/admin/config", get(admin_config))
+        .route("/admin/export", get(export_data))
+        .route("/admin/expire-sales", post(expire_sales))
+        .route("/admin/apply-price-changes", post(apply_price_changes))
+        .route("/admin/import", post(import_data))
+        .route("/admin/import-delta", post(import_delta))
+        .route("/admin/integrity-check", get(integrity_check).post(repair_integrity))
+        .route("/admin/keys", get(list_api_keys).post(create_api_key))
+        .route("/admin/keys/{id}", delete(revoke_api_key))
+        .route("/admin/maintenance", get(get_maintenance).post(set_maintenance))
+        .route("/admin/price-anomalies", get(price_anomalies))
+        .route("/admin/read-only", get(get_read_only).post(set_read_only))
+        .route("/admin/retention", get(retention_preview))
+        .route("/admin/schema.sql", get(schema_sql))
+        .route("/admin/stats/snapshot", post(take_stats_snapshot))
+        .route("/admin/stock-snapshot", post(take_stock_snapshot))
+        .route("/admin/tx-sessions", get(list_tx_sessions));
+
+    // GraphiQL len vo vývojovom režime
+    let graphql_route = if state.config.dev_mode {
+        post(graphql::execute).get(graphql::graphiql)
+    } else {
+        post(graphql::execute)
+    };
+    let graphql = RouteTable::new()
+        .route("/graphql", graphql_route)
+        .with_state(graphql::schema(state.db.clone(), state.config.clone()));
+
+    // vývojový režim: súbory rozhrania z disku a automatické obnovenie stránky
+    let dev = if state.config.dev_mode {
+        RouteTable::new()
+            .route("/__reload", get(web::reload_token))
+            .route("/web/{file}", get(web::asset))
+    } else {
+        RouteTable::new()
+    };
+
+    RouteTable::new()
+        .route("/", get(web::index_page))
+        .route("/livez", get(health::livez))
+        .route("/readyz", get(health::readyz))
+        .route("/metrics", get(metrics))
+        .route("/stores", get(list_stores))
         .route("/employees", get(list_employees).post(add_employee))
         .route("/employees/search", post(search_employees))
+        .route("/employees/by-email", get(get_employee_by_email))
+        .route("/employees/batch", post(get_employees_batch))
+        .route("/employees/bulk-update", post(bulk_update_employees))
+        .route("/employees/merge", post(merge_employees))
+        .route("/employees/salary-adjust", post(adjust_salaries))
+        .route("/employees/saved/{name}", get(saved_employees))
+        .route("/employees/org-chart", get(org_chart))
         .route("/employees/{id}", delete(delete_employee).put(update_employee))
+        .route("/employees/{id}/restore", post(restore_employee))
+        .route("/employees/{id}/scorecard", get(employee_scorecard))
+        .route("/employees/{id}/reports", get(employee_reports))
+        .route("/employees/{id}/chain", get(employee_chain))
+        .route("/employees/{id}/clock-in", post(clock_in))
+        .route("/employees/{id}/clock-out", post(clock_out))
+        .route("/employees/{id}/hours", get(employee_hours))
+        .route("/schedule", get(employees_on_shift).post(assign_shift))
+        .route("/schedule/{id}", delete(unassign_shift))
         .route("/products", get(list_products).post(add_product))
         .route("/products/search", post(search_products))
-        .route("/products/{id}", delete(delete_product).put(update_product))
-        .with_state(db)
+        .route("/products/export", post(export_products))
+        .route("/products/export.ndjson", get(export_products_ndjson))
+        .route("/export", post(export_records))
+        .route("/products/sample", get(sample_products))
+        .route("/products/recent", get(recent_products))
+        .route("/products/compare", get(compare_products))
+        .route("/products/reorder-list", get(reorder_list))
+        .route("/products/coverage", get(low_coverage))
+        .route("/products/overstocked", get(overstocked_products))
+        .route("/products/modified-since", get(products_modified_since))
+        .route("/products/sku/{sku}", get(get_product_by_sku))
+        .route("/products/lookup", get(lookup_product))
+        .route("/products/index", get(product_index))
+        .route("/products/saved/{name}", get(saved_products))
+        .route("/products/reactivate-by-supplier", post(reactivate_by_supplier))
+        .route("/products/adjust-prices", post(adjust_prices))
+        .route("/products/round-prices", post(round_prices))
+        .route("/products/bulk", post(bulk_create_products))
+        .route("/products/bulk-update", post(bulk_update_products))
+        .route("/products/tag-by-filter", post(tag_products_by_filter))
+        .route("/products/delete-by-filter", post(delete_products_by_filter))
+        .route("/products/set-status-by-filter", post(set_status_by_filter))
+        .route("/products/remap-categories", post(remap_categories))
+        .route("/products/on-sale", get(products_on_sale))
+        .route("/products/scheduled-changes", get(scheduled_price_changes))
+        .route("/products/{id}", get(get_product).delete(delete_product).put(update_product))
+        .route("/products/{id}/stock-status", get(get_stock_status))
+        .route("/products/{id}/coverage", get(stock_coverage))
+        .route("/products/{id}/avg-cost", get(average_cost))
+        .route("/products/{id}/net-margin", get(net_margin))
+        .route("/products/{id}/break-even", get(break_even))
+        .route("/products/{id}/movements", get(stock_movements))
+        .route("/products/{id}/elasticity", get(price_elasticity))
+        .route("/products/{id}/seasonality", get(product_seasonality))
+        .route("/products/{id}/cross-sell", get(cross_sell))
+        .route("/products/{id}/min-stock", put(set_min_stock))
+        .route("/products/{id}/max-stock", put(set_max_stock))
+        .route("/products/{id}/sale", post(set_sale))
+        .route("/products/{id}/schedule-price", post(schedule_price_change))
+        .route("/products/{id}/clone", post(clone_product))
+        .route("/products/{id}/barcode.png", get(barcode_png))
+        .route("/products/{id}/barcode.svg", get(barcode_svg))
+        .route("/products/{id}/qr.png", get(qr_png))
+        .route("/products/{id}/adjust-stock", post(adjust_stock))
+        .route("/products/reconcile", post(reconcile_stock))
+        .route("/products/{id}/reserve", post(add_reservation))
+        .route("/products/{id}/batches", get(list_batches).post(add_batch))
+        .route("/products/{id}/batches/{batch_id}", put(update_batch))
+        .route("/products/{id}/components", get(get_bundle).put(set_bundle_components))
+        .route("/products/{id}/images", get(list_images).post(add_images))
+        .route("/products/{id}/images/{image_id}/primary", put(set_primary_image))
+        .route("/products/{id}/tags", get(list_tags))
+        .route("/products/{id}/translations", get(list_translations))
+        .route("/products/{id}/translations/{lang}", get(get_translation).put(set_translation))
+        .route("/categories/{name}/limit", get(get_category_limit).put(set_category_limit))
+        .route("/saved-searches", get(list_saved_searches).post(add_saved_search))
+        .route("/saved-searches/{id}", delete(delete_saved_search))
+        .route("/meta/brands", get(meta_brands))
+        .route("/meta/categories", get(meta_categories))
+        .route("/meta/suppliers", get(meta_suppliers))
+        .route("/import/diff", post(import_diff))
+        .route("/import/upload", post(import_upload.layer(DefaultBodyLimit::max(state.config.max_upload_size))))
+        .route(
+            "/import/validate-barcodes",
+            post(validate_import_barcodes.layer(DefaultBodyLimit::max(state.config.max_upload_size))),
+        )
+        .route(
+            "/import/price-list/preview",
+            post(preview_price_list.layer(DefaultBodyLimit::max(state.config.max_upload_size))),
+        )
+        .route(
+            "/import/price-list/apply",
+            post(apply_price_list.layer(DefaultBodyLimit::max(state.config.max_upload_size))),
+        )
+        .route("/orders", get(list_orders).post(add_order))
+        .route("/reservations", get(list_reservations))
+        .route("/reservations/{id}/cancel", post(cancel_reservation))
+        .route("/reservations/{id}/fulfill", post(fulfill_reservation))
+        .route("/orders/{id}", get(get_order))
+        .route("/orders/{id}/returns", post(add_order_return))
+        .route("/purchase-orders", get(list_purchase_orders).post(add_purchase_order))
+        .route("/purchase-orders/from-suggestions", post(purchase_orders_from_suggestions))
+        .route(
+            "/purchase-orders/{id}",
+            get(get_purchase_order).put(update_purchase_order).delete(delete_purchase_order),
+        )
+        .route("/purchase-orders/{id}/receive", post(receive_purchase_order))
+        .route("/purchase-orders/{id}/discrepancies", get(purchase_order_discrepancies))
+        .route("/reports/reorder-suggestions", get(reorder_suggestions))
+        .route("/suppliers/{name}/order-sheet", get(supplier_order_sheet))
+        .route("/suppliers/{name}/purchase-order", post(supplier_purchase_order))
+        .route("/reports/inventory-value", get(inventory_value_report))
+        .route("/reports/inventory.pdf", get(inventory_pdf))
+        .route("/reports/low-stock", get(low_stock_report))
+        .route("/reports/dead-stock", get(dead_stock_report))
+        .route("/reports/margin-violations", get(margin_violations))
+        .route("/reports/hours", get(hours_report))
+        .route("/reports/daily", get(daily_report))
+        .route("/reports/expiring-batches", get(expiring_batches))
+        .route("/dashboard", get(dashboard))
+        .route("/stats/profit", get(profit_report))
+        .route("/stats/profit-by-employee", get(profit_by_employee))
+        .route("/stats/discounts", get(discounts_given))
+        .route("/stats/baskets", get(basket_stats))
+        .route("/stats/turnover", get(inventory_turnover))
+        .route("/stats/gmroi", get(gmroi))
+        .route("/stats/days-of-inventory", get(days_of_inventory))
+        .route("/stats/reorder-budget", get(reorder_budget))
+        .route("/stats/aging", get(inventory_aging))
+        .route("/stats/shrinkage", get(shrinkage_report))
+        .route("/stats/sell-through", get(sell_through))
+        .route("/stats/supplier-scores", get(supplier_scores))
+        .route("/stats/valuation", get(stock_valuation))
+        .route("/stats/category-margins", get(category_margins))
+        .route("/stats/headcount", get(headcount_over_time))
+        .route("/stats/payroll", get(total_payroll))
+        .route("/stats/labor-ratio", get(labor_ratio))
+        .route("/stats/salary-outliers", get(salary_outliers))
+        .route("/stats/shift-coverage", get(shift_coverage))
+        .route("/stats/history", get(stats_history))
+        .route("/stats/stock-delta", get(stock_delta))
+        .route("/stats/seasonality", get(category_seasonality))
+        .route("/audit/export.csv", get(export_audit_csv))
+        .route("/alerts", get(list_alerts))
+        .route("/alerts/{id}/resolve", post(resolve_alert))
+        .merge(admin)
+        .merge(dev)
+        .merge(graphql)
+}
+
+/// Začne transakciu, v ktorej sa vykonajú požiadavky s hlavičkou `X-Transaction-Id`.
+///
+/// Transakcia drží spojenie a zámok zápisu databázy, kým sa neukončí
+/// (pozri `tx_session`); po `tx_timeout` sekundách nečinnosti sa vráti späť.
+///
+/// # Arguments
+/// * `state` – stav aplikácie
+///
+/// # Returns
+/// `201 Created` s tokenom transakcie
+///
+/// # Errors
+/// 409, ak je otvorená iná transakcia alebo prebieha zápis
+async fn begin_tx(State(state): State<AppState>) -> Result<(StatusCode, Json<TxStarted>), (StatusCode, String)> {
+    let timeout = state.config.tx_timeout;
+    let session_state = state.clone();
+    let id = state
+        .tx
+        .begin(&state.db, Duration::from_secs(timeout), |db| {
+            let cache = QueryCache::disabled(db.generations());
+            let session_state = AppState { db, cache, ..session_state };
+            routes(&session_state).into_router().with_state(session_state)
+        })
+        .await
+        .map_err(store_error("Chyba pri začatí transakcie"))?;
+    Ok((StatusCode::CREATED, Json(TxStarted { id, timeout })))
+}
+
+/// Potvrdí zmeny transakcie z `POST /tx`.
+///
+/// # Arguments
+/// * `sessions` – otvorené transakcie
+/// * `id` – token transakcie
+///
+/// # Returns
+/// `204 No Content`
+///
+/// # Errors
+/// 404, ak transakcia neexistuje alebo vypršala
+async fn commit_tx(State(sessions): State<TxSessions>, Path(id): Path<String>) -> Result<StatusCode, (StatusCode, String)> {
+    sessions
+        .finish(&id, true)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(store_error("Chyba pri potvrdení transakcie"))
+}
+
+/// Zahodí zmeny transakcie z `POST /tx`.
+///
+/// # Arguments
+/// * `sessions` – otvorené transakcie
+/// * `id` – token transakcie
+///
+/// # Returns
+/// `204 No Content`
+///
+/// # Errors
+/// 404, ak transakcia neexistuje alebo vypršala
+async fn rollback_tx(State(sessions): State<TxSessions>, Path(id): Path<String>) -> Result<StatusCode, (StatusCode, String)> {
+    sessions
+        .finish(&id, false)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(store_error("Chyba pri vrátení transakcie späť"))
+}
+
+/// Vráti otvorené transakcie z `POST /tx` (napr. pri hľadaní klienta, ktorý drží zámok).
+///
+/// # Arguments
+/// * `sessions` – otvorené transakcie
+///
+/// # Returns
+/// Transakcie od najstaršej s časom nečinnosti a zostávajúcim časom do vrátenia späť
+async fn list_tx_sessions(State(sessions): State<TxSessions>) -> Json<Vec<TxSessionInfo>> {
+    Json(sessions.list())
+}
+
+/// Vykoná dávku operácií v jednej transakcii (pozri `batch`).
+///
+/// Operácie sa vykonajú v poradí; ak niektorá zlyhá, nezapíše sa nič a odpoveď
+/// má stav chyby tejto operácie a jej poradie v `index`. Kým dávka beží, drží
+/// zámok zápisu databázy ako transakcia z `POST /tx`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+/// * `role` – rola volajúceho (každá operácia vyžaduje rolu svojho volania REST API)
+/// * `request` – operácie v poradí vykonania
+///
+/// # Returns
+/// Výsledky operácií v poradí
+///
+/// # Errors
+/// 400 pri prázdnej alebo príliš veľkej dávke či neplatnom odkaze, 403 pri
+/// nedostatočnej role, 409 ak prebieha iná transakcia, inak stav zlyhanej operácie
+async fn run_batch(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Extension(role): Extension<Role>,
+    Json(request): Json<BatchRequest>,
+) -> Result<Json<Vec<BatchStepResult>>, (StatusCode, Json<BatchError>)> {
+    let batch_error = |status: StatusCode, index: Option<usize>, op: Option<BatchOp>, error: String| {
+        (status, Json(BatchError { index, op, error }))
+    };
+    let count = request.operations.len();
+    if count == 0 || count > batch::MAX_OPERATIONS {
+        let error = format!("dávka musí mať 1 až {} operácií, má {count}", batch::MAX_OPERATIONS);
+        return Err(batch_error(StatusCode::BAD_REQUEST, None, None, error));
+    }
+    for (index, operation) in request.operations.iter().enumerate() {
+        let required = batch::required_role(operation.op);
+        if role < required {
+            let error = format!("vyžaduje sa rola {required}, volajúci má rolu {role}");
+            return Err(batch_error(StatusCode::FORBIDDEN, Some(index), Some(operation.op), error));
+        }
+    }
+
+    let session = db.begin_session().await.map_err(|e| {
+        let (status, error) = store_error("Chyba pri začatí dávky")(e);
+        batch_error(status, None, None, error)
+    })?;
+    let mut results = Vec::with_capacity(count);
+    let mut references = Vec::with_capacity(count);
+    for (index, operation) in request.operations.into_iter().enumerate() {
+        match batch_step(&session, &config, operation.op, operation.id, operation.body, &references).await {
+            Ok((id, result)) => {
+                references.push(result.clone().unwrap_or_else(|| serde_json::json!({ "id": id })));
+                results.push(BatchStepResult { index, op: operation.op, id, result });
+            }
+            Err((status, error)) => {
+                if let Err(e) = session.rollback_session().await {
+                    eprintln!("Nepodarilo sa vrátiť späť dávku: {e}");
+                }
+                return Err(batch_error(status, Some(index), Some(operation.op), error));
+            }
+        }
+    }
+    session.commit_session().await.map_err(|e| {
+        let (status, error) = store_error("Chyba pri potvrdení dávky")(e);
+        batch_error(status, None, None, error)
+    })?;
+    Ok(Json(results))
+}
+
+/// Vykoná jednu operáciu dávky v relácii dávky.
+///
+/// # Arguments
+/// * `db` – databáza relácie dávky
+/// * `config` – konfigurácia aplikácie
+/// * `op` – druh operácie
+/// * `id` – ID záznamu, môže byť odkazom
+/// * `body` – telo, môže obsahovať odkazy
+/// * `references` – výsledky skorších operácií na nahradenie odkazov
+///
+/// # Returns
+/// ID záznamu a záznam po zmene (pri mazaní `None`)
+async fn batch_step(
+    db: &StoreDB,
+    config: &Config,
+    op: BatchOp,
+    mut id: Option<serde_json::Value>,
+    body: Option<serde_json::Value>,
+    references: &[serde_json::Value],
+) -> Result<(u32, Option<serde_json::Value>), (StatusCode, String)> {
+    let invalid = |e: String| (StatusCode::BAD_REQUEST, e);
+    let mut body = body.unwrap_or(serde_json::Value::Null);
+    if let Some(id) = &mut id {
+        batch::resolve(id, references).map_err(invalid)?;
+    }
+    batch::resolve(&mut body, references).map_err(invalid)?;
+    let id = batch::record_id(op, id.as_ref()).map_err(invalid)?;
+    let (method, path) = batch::endpoint(op, id);
+    let context = format!("Chyba pri dávke ({method} {path})");
+    let not_found = || (StatusCode::NOT_FOUND, format!("{path} neexistuje"));
+    let to_value = |v: Result<serde_json::Value, serde_json::Error>| v.map_err(|e| store_error(&context)(e.into()));
+
+    let (id, result) = match op {
+        BatchOp::CreateProduct | BatchOp::UpdateProduct => {
+            let mut product: Product = batch_body(&body, config)?;
+            product.id = id;
+            prepare_product(&mut product, config)?;
+            let id = match id {
+                None => db.add_product_to_store_db(&product).await.map_err(store_error(&context))?,
+                Some(id) => {
+                    let policy = Some(&config.margin_policy);
+                    if !db.update_product(&product, policy).await.map_err(store_error(&context))? {
+                        return Err(not_found());
+                    }
+                    id
+                }
+            };
+            let product = db.get_product(id).await.map_err(|e| store_error(&context)(e.into()))?.ok_or_else(not_found)?;
+            (id, Some(to_value(serde_json::to_value(product))?))
+        }
+        BatchOp::CreateEmployee | BatchOp::UpdateEmployee => {
+            let mut employee: Employee = batch_body(&body, config)?;
+            employee.id = id;
+            prepare_employee(&mut employee, config)?;
+            let id = match id {
+                None => db.add_employee_to_store_db(&employee).await.map_err(store_error(&context))?,
+                Some(id) => {
+                    if !db.update_employee(&employee).await.map_err(store_error(&context))? {
+                        return Err(not_found());
+                    }
+                    id
+                }
+            };
+            let employee = db.get_employee(id).await.map_err(|e| store_error(&context)(e.into()))?.ok_or_else(not_found)?;
+            (id, Some(to_value(serde_json::to_value(employee))?))
+        }
+        BatchOp::AdjustStock => {
+            let id = id.ok_or_else(not_found)?;
+            let adjustment: StockAdjustment = batch_body(&body, config)?;
+            let product = db.adjust_stock(id, adjustment.delta, adjustment.unit).await.map_err(store_error(&context))?;
+            (id, Some(to_value(serde_json::to_value(product))?))
+        }
+        BatchOp::DeleteProduct | BatchOp::DeleteEmployee => {
+            let id = id.ok_or_else(not_found)?;
+            let deleted = if op == BatchOp::DeleteProduct {
+                db.delete_product(id).await
+            } else {
+                db.delete_employee(id).await
+            };
+            if !deleted.map_err(store_error(&context))? {
+                return Err(not_found());
+            }
+            (id, None)
+        }
+    };
+    Ok((id, result))
+}
+
+/// Prevedie telo operácie dávky ako extraktor `Json` (`422` s popisom chybného poľa).
+fn batch_body<T: DeserializeOwned>(body: &serde_json::Value, config: &Config) -> Result<T, (StatusCode, String)> {
+    json::from_value(body, config.strict_json).map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, e.message))
+}
+
+/// Zostaví hlavičku `Link` (rel `first`, `prev`, `next`, `last`) pre stránkovaný zoznam.
+///
+/// Zachová ostatné parametre dotazu (napr. filtre) a nahradí len `offset` a `limit`.
+///
+/// # Arguments
+/// * `uri` – URI pôvodnej požiadavky
+/// * `offset` – aktuálny posun
+/// * `limit` – veľkosť stránky
+/// * `total` – celkový počet záznamov vyhovujúcich filtru
+///
+/// # Returns
+/// Hodnota hlavičky `Link`
+fn build_link_header(uri: &Uri, offset: u32, limit: u32, total: u64) -> Option<HeaderValue> {
+    let limit = limit.max(1);
+    let params: Vec<&str> = uri
+        .query()
+        .unwrap_or("")
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .filter(|p| {
+            let key = p.split('=').next().unwrap_or("");
+            key != "offset" && key != "limit"
+        })
+        .collect();
+
+    let link = |rel: &str, offset: u64| {
+        let mut query = params.join("&");
+        if !query.is_empty() {
+            query.push('&');
+        }
+        format!("<{}?{query}offset={offset}&limit={limit}>; rel=\"{rel}\"", uri.path())
+    };
+
+    let offset = offset as u64;
+    let limit_u64 = limit as u64;
+    let last = if total == 0 { 0 } else { (total - 1) / limit_u64 * limit_u64 };
+
+    let mut links = vec![link("first", 0)];
+    if offset > 0 {
+        links.push(link("prev", offset.saturating_sub(limit_u64).min(last)));
+    }
+    if offset + limit_u64 < total {
+        links.push(link("next", offset + limit_u64));
+    }
+    links.push(link("last", last));
+
+    HeaderValue::from_str(&links.join(", ")).ok()
+}
+
+/// Zostaví hlavičky stránkovanej odpovede.
+///
+/// Hlavička `Link` sa pridá, ak klient zadal `limit` alebo bola odpoveď skrátená.
+/// Ak bol výsledok orezaný na `max_rows`, pridá sa `X-Result-Truncated: true`.
+///
+/// # Arguments
+/// * `uri` – URI pôvodnej požiadavky
+/// * `page` – stránkovanie požadované klientom
+/// * `limit` – skutočne použitá veľkosť stránky
+/// * `total` – celkový počet záznamov vyhovujúcich filtru
+/// * `max_rows` – maximálny počet riadkov v odpovedi
+///
+/// # Returns
+/// Hlavičky odpovede
+fn page_headers(uri: &Uri, page: &Pagination, limit: u32, total: u64, max_rows: u32) -> HeaderMap {
+    let offset = page.offset.unwrap_or(0);
+    let truncated = page.limit.is_none_or(|l| l > max_rows) && total > offset as u64 + limit as u64;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(X_TOTAL_COUNT, HeaderValue::from(total));
+    if truncated {
+        headers.insert(X_RESULT_TRUNCATED, HeaderValue::from_static("true"));
+    }
+    if page.limit.is_some() || truncated {
+        if let Some(link) = build_link_header(uri, offset, limit, total) {
+            headers.insert(header::LINK, link);
+        }
+    }
+    headers
+}
+
+/// Vráti subjekt prihláseného volajúceho pre audit log.
+fn actor(user: &Option<AuthUser>) -> Option<&str> {
+    user.as_ref().map(|u| u.subject.as_str())
+}
+
+/// Zaloguje chybu a prevedie ju na `500 Internal Server Error`.
+///
+/// # Arguments
+/// * `context` – popis operácie pre log
+///
+/// # Returns
+/// Funkcia vhodná pre `map_err`
+fn internal_error<E: Display>(context: &str) -> impl FnOnce(E) -> StatusCode + '_ {
+    move |e| {
+        eprintln!("{context}: {e}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// Zistí jazyky z hlavičky `Accept-Language` v poradí podľa preferencie (`q`).
+///
+/// Pri regionálnej variante (`hu-HU`) sa za ňou skúsi aj samotný jazyk (`hu`).
+///
+/// # Arguments
+/// * `headers` – hlavičky požiadavky
+///
+/// # Returns
+/// Kódy jazykov malými písmenami; prázdny zoznam, ak hlavička chýba
+fn preferred_languages(headers: &HeaderMap) -> Vec<String> {
+    let Some(value) = headers.get(header::ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok()) else {
+        return Vec::new();
+    };
+
+    let mut weighted: Vec<(String, f32)> = value
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let tag = parts.next()?.trim().to_ascii_lowercase();
+            let q = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse().ok())?;
+            (!tag.is_empty() && tag != "*" && q > 0.0).then_some((tag, q))
+        })
+        .collect();
+    weighted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut langs: Vec<String> = Vec::new();
+    for (tag, _) in weighted {
+        let primary = tag.split('-').next().unwrap_or_default().to_string();
+        for lang in [tag, primary] {
+            if !langs.contains(&lang) {
+                langs.push(lang);
+            }
+        }
+    }
+    langs
 }
 
+/// Prevedie chybu z databázovej vrstvy na HTTP odpoveď.
+///
+/// `StoreError` sa mapuje na 404 / 400 / 409 so správou v tele, ostatné chyby
+/// sa zalogujú a vrátia ako `500 Internal Server Error`.
+///
+/// # Arguments
+/// * `context` – popis operácie pre log
+///
+/// # Returns
+/// Funkcia vhodná pre `map_err`
+fn store_error(context: &str) -> impl FnOnce(anyhow::Error) -> (StatusCode, String) + '_ {
+    move |e| match e.downcast_ref::<StoreError>() {
+        Some(StoreError::NotFound(_)) => (StatusCode::NOT_FOUND, e.to_string()),
+        Some(StoreError::Invalid(_)) => (StatusCode::BAD_REQUEST, e.to_string()),
+        Some(StoreError::Conflict(_)) => (StatusCode::CONFLICT, e.to_string()),
+        Some(StoreError::PolicyViolation(_) | StoreError::TooLong { .. }) => {
+            (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+        }
+        None if is_read_only_error(&e) => (StatusCode::SERVICE_UNAVAILABLE, read_only::MESSAGE.to_string()),
+        None => {
+            eprintln!("{context}: {e}");
+            (StatusCode::INTERNAL_SERVER_ERROR, String::new())
+        }
+    }
+}
 
+/// Parameter vyhľadania zamestnanca podľa e-mailu.
+#[derive(Debug, Deserialize)]
+struct EmailQuery {
+    email: String,
+}
 
-/// Vráti hlavnú HTML stránku.
+/// Vráti zamestnanca podľa e-mailu (bez ohľadu na veľkosť písmen a okolité medzery).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?email=...`
 ///
 /// # Returns
-/// HTML obsah stránky
-async fn index_page() -> Html<&'static str> {
-    Html(include_str!("web/index.html"))
+/// Zamestnanec v JSONe alebo `404 Not Found`
+///
+/// # Errors
+/// 400 pri prázdnom e-maile
+async fn get_employee_by_email(
+    State(db): State<StoreDB>,
+    Query(query): Query<EmailQuery>,
+) -> Result<Json<Employee>, StatusCode> {
+    if query.email.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    db.get_employee_by_email(&query.email)
+        .await
+        .map_err(internal_error("Chyba pri načítaní zamestnanca"))?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
 }
 
+/// Telo dávkového načítania zamestnancov.
+#[derive(Debug, Deserialize)]
+struct EmployeeIds {
+    ids: Vec<u32>,
+}
 
+/// Vráti zamestnancov so zadanými ID naraz (napr. zamestnancov, na ktorých
+/// odkazuje zoznam produktov). Opakované ID sa berú raz.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `body` – `{"ids": [1, 2]}`
+///
+/// # Returns
+/// Nájdení zamestnanci v poradí `ids` a ID, ktoré sa nenašli
+async fn get_employees_batch(
+    State(db): State<StoreDB>,
+    Json(body): Json<EmployeeIds>,
+) -> Result<Json<EmployeeBatch>, (StatusCode, String)> {
+    let mut ids: Vec<u32> = Vec::with_capacity(body.ids.len());
+    for id in body.ids {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    let employees = db
+        .get_employees_by_ids(&ids)
+        .await
+        .map_err(|e| store_error("Chyba pri načítaní zamestnancov")(e.into()))?;
+    let not_found = ids.into_iter().filter(|&id| !employees.iter().any(|e| e.id == Some(id))).collect();
+    Ok(Json(EmployeeBatch { employees, not_found }))
+}
 
-/// Vráti všetkých zamestnancov.
+/// Vráti zamestnancov, voliteľne filtrovaných a stránkovaných cez parametre dotazu.
+///
+/// Pri zadanom `limit` odpoveď obsahuje hlavičku `Link` na susedné stránky.
+/// Počet riadkov je vždy obmedzený na `max_result_rows` z konfigurácie.
+/// Telefón sa porovnáva v normalizovanom tvare (E.164). Vyradení zamestnanci
+/// sa vynechávajú, kým filter nemá `include_removed=true`.
 ///
 /// # Arguments
 /// * `db` – databázový stav aplikácie
+/// * `config` – konfigurácia aplikácie
+/// * `page` – parametre stránkovania
+/// * `filter` – filter z parametrov dotazu
+/// * `uri` – URI požiadavky (pre hlavičku `Link`)
 ///
 /// # Returns
 /// Zoznam zamestnancov v JSONe
@@ -48,20 +847,37 @@ async fn index_page() -> Html<&'static str> {
 /// Ak zlyhá čítanie z databázy
 async fn list_employees(
     State(db): State<StoreDB>,
-) -> Result<Json<Vec<Employee>>, StatusCode> {
-    db.get_employees(Employee::new_empty())
+    State(config): State<Arc<Config>>,
+    Query(page): Query<Pagination>,
+    Query(mut filter): Query<Employee>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<(HeaderMap, Json<Vec<Employee>>), StatusCode> {
+    normalize_phone_filter(&mut filter, &config.phone_country);
+    filter.include_removed.get_or_insert(false);
+    let max_rows = config.max_result_rows;
+    let limit = page.limit.unwrap_or(max_rows).min(max_rows);
+
+    let total = db
+        .count_employees(filter.clone())
         .await
-        .map(Json)
-        .map_err(|e| {
-            eprintln!("Chyba pri načítaní zamestnancov: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })
+        .map_err(internal_error("Chyba pri načítaní zamestnancov"))?;
+    let employees = db
+        .get_employees_page(filter, limit, page.offset.unwrap_or(0))
+        .await
+        .map_err(internal_error("Chyba pri načítaní zamestnancov"))?;
+
+    Ok((page_headers(&uri, &page, limit, total, max_rows), Json(employees)))
 }
 
 /// Vyhľadá zamestnancov podľa filtra.
 ///
+/// Počet riadkov je obmedzený na `max_result_rows` z konfigurácie. Telefón
+/// sa porovnáva v normalizovanom tvare (E.164). Vyradení zamestnanci sa
+/// vynechávajú, kým filter nemá `include_removed: true`.
+///
 /// # Arguments
 /// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
 /// * `filter` – vyhľadávacie kritériá
 ///
 /// # Returns
@@ -71,51 +887,139 @@ async fn list_employees(
 /// Ak zlyhá vyhľadávanie
 async fn search_employees(
     State(db): State<StoreDB>,
-    Json(filter): Json<Employee>,
-) -> Result<Json<Vec<Employee>>, StatusCode> {
-    db.get_employees(filter)
+    State(config): State<Arc<Config>>,
+    Json(mut filter): Json<Employee>,
+) -> Result<(HeaderMap, Json<Vec<Employee>>), StatusCode> {
+    normalize_phone_filter(&mut filter, &config.phone_country);
+    filter.include_removed.get_or_insert(false);
+    let max_rows = config.max_result_rows;
+
+    let total = db
+        .count_employees(filter.clone())
         .await
-        .map(Json)
-        .map_err(|e| {
-            eprintln!("Chyba pri vyhľadávaní zamestnancov: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })
+        .map_err(internal_error("Chyba pri vyhľadávaní zamestnancov"))?;
+    let employees = db
+        .get_employees_page(filter, max_rows, 0)
+        .await
+        .map_err(internal_error("Chyba pri vyhľadávaní zamestnancov"))?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(X_TOTAL_COUNT, HeaderValue::from(total));
+    if total > max_rows as u64 {
+        headers.insert(X_RESULT_TRUNCATED, HeaderValue::from_static("true"));
+    }
+    Ok((headers, Json(employees)))
+}
+
+/// Pripraví zamestnanca na zápis: oreže textové polia, overí ich dĺžku a doplní
+/// telefón v tvare E.164 podľa zadaného `phone_number`.
+///
+/// Prázdne číslo nemá normalizovaný tvar.
+///
+/// # Arguments
+/// * `emp` – zamestnanec
+/// * `config` – konfigurácia aplikácie (dĺžky polí, krajina telefónnych čísel)
+///
+/// # Errors
+/// `422 Unprocessable Entity` pri príliš dlhom poli, `400 Bad Request` ak sa
+/// číslo nedá rozpoznať
+fn prepare_employee(emp: &mut Employee, config: &Config) -> Result<(), (StatusCode, String)> {
+    emp.trim_text();
+    emp.validate(&config.text_limits()).map_err(too_long)?;
+    phone::normalize_employee(emp, &config.phone_country).map_err(|e| (StatusCode::BAD_REQUEST, e))
+}
+
+/// Pripraví produkt na zápis: oreže textové polia, overí ich dĺžku a formát SKU.
+///
+/// # Arguments
+/// * `prod` – produkt
+/// * `config` – konfigurácia aplikácie (dĺžky polí, maska predpony SKU)
+///
+/// # Errors
+/// `422 Unprocessable Entity` pri príliš dlhom poli, `400 Bad Request` pri SKU
+/// v nesprávnom tvare
+fn prepare_product(prod: &mut Product, config: &Config) -> Result<(), (StatusCode, String)> {
+    prod.trim_text();
+    prod.validate(&config.text_limits()).map_err(too_long)?;
+    if let Some(sku) = &mut prod.sku {
+        *sku = sku::normalize(sku);
+        sku::validate(sku, &config.sku_prefix_pattern).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    }
+    Ok(())
+}
+
+/// Prevedie chybu príliš dlhého poľa na `422 Unprocessable Entity`.
+fn too_long(e: StoreError) -> (StatusCode, String) {
+    (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+}
+
+/// Upraví filter tak, aby sa telefón porovnával v normalizovanom tvare.
+///
+/// Číslo, ktoré sa nedá rozpoznať, sa porovnáva tak, ako bolo zadané.
+///
+/// # Arguments
+/// * `filter` – filter zamestnancov
+/// * `country` – krajina pre čísla bez medzinárodnej predvoľby
+fn normalize_phone_filter(filter: &mut Employee, country: &str) {
+    phone::normalize_filter(filter, country);
 }
 
 /// Pridá nového zamestnanca.
 ///
 /// # Arguments
 /// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (krajina telefónnych čísel, dĺžky polí)
 /// * `emp` – dáta zamestnanca
 ///
 /// # Returns
 /// HTTP status kód výsledku
+///
+/// # Errors
+/// 400 pri nerozpoznateľnom telefónnom čísle alebo neznámom nadriadenom,
+/// 422 pri príliš dlhom textovom poli
 async fn add_employee(
     State(db): State<StoreDB>,
-    Json(emp): Json<Employee>,
-) -> StatusCode {
-    match db.add_employee_to_store_db(&emp).await {
-        Ok(_) => StatusCode::CREATED,
-        Err(e) => {
-            eprintln!("Chyba pri pridávaní zamestnanca: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        }
-    }
+    State(config): State<Arc<Config>>,
+    Json(mut emp): Json<Employee>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    prepare_employee(&mut emp, &config)?;
+    db.add_employee_to_store_db(&emp)
+        .await
+        .map_err(store_error("Chyba pri pridávaní zamestnanca"))?;
+    Ok(StatusCode::CREATED)
 }
 
-/// Vymaže zamestnanca podľa ID.
+/// Parametre mazania zamestnanca.
+#[derive(Debug, Deserialize, Default)]
+struct DeleteEmployeeQuery {
+    /// `true` zamestnanca len vyradí (`status = 0`, `date_remove`) namiesto vymazania
+    soft: Option<bool>,
+}
+
+/// Vymaže zamestnanca podľa ID, s `?soft=true` ho len vyradí.
+///
+/// Vyradený zamestnanec zostáva v databáze (produkty a objednávky naňho naďalej
+/// odkazujú), len ho predvolené zoznamy vynechávajú; obnoví ho
+/// `POST /employees/{id}/restore`.
 ///
 /// # Arguments
 /// * `db` – databáza
 /// * `id` – ID zamestnanca
+/// * `query` – `?soft=true` pre vyradenie
 ///
 /// # Returns
 /// HTTP status kód výsledku
 async fn delete_employee(
     State(db): State<StoreDB>,
     Path(id): Path<u32>,
+    Query(query): Query<DeleteEmployeeQuery>,
 ) -> StatusCode {
-    match db.delete_employee(id).await {
+    let result = if query.soft.unwrap_or(false) {
+        db.soft_delete_employee(id).await
+    } else {
+        db.delete_employee(id).await
+    };
+    match result {
         Ok(true) => StatusCode::NO_CONTENT,
         Ok(false) => StatusCode::NOT_FOUND,
         Err(e) => {
@@ -125,143 +1029,5163 @@ async fn delete_employee(
     }
 }
 
+/// Obnoví vyradeného zamestnanca (`status = 1`, bez `date_remove`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID zamestnanca
+///
+/// # Returns
+/// Obnovený zamestnanec
+///
+/// # Errors
+/// 404 ak zamestnanec neexistuje
+async fn restore_employee(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Employee>, (StatusCode, String)> {
+    db.restore_employee(id)
+        .await
+        .map_err(store_error("Chyba pri obnove zamestnanca"))?
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, format!("zamestnanec {id} neexistuje")))
+}
+
 /// Aktualizuje zamestnanca.
 ///
 /// # Arguments
 /// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (krajina telefónnych čísel, dĺžky polí)
 /// * `id` – ID zamestnanca
 /// * `emp` – nové dáta
 ///
 /// # Returns
 /// HTTP status kód výsledku
+///
+/// # Errors
+/// 400 pri nerozpoznateľnom telefónnom čísle alebo neznámom nadriadenom,
+/// 422 pri príliš dlhom textovom poli alebo cykle v hierarchii nadriadených
 async fn update_employee(
     State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
     Path(id): Path<u32>,
     Json(mut emp): Json<Employee>,
-) -> StatusCode {
+) -> Result<StatusCode, (StatusCode, String)> {
     emp.id = Some(id);
-    match db.update_employee(&emp).await {
-        Ok(true) => StatusCode::OK,
-        Ok(false) => StatusCode::NOT_FOUND,
-        Err(e) => {
-            eprintln!("Chyba pri updatovaní zamestnanca: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        }
+    prepare_employee(&mut emp, &config)?;
+    match db.update_employee(&emp).await.map_err(store_error("Chyba pri updatovaní zamestnanca"))? {
+        true => Ok(StatusCode::OK),
+        false => Ok(StatusCode::NOT_FOUND),
     }
 }
 
-
-
-/// Vráti všetky produkty.
+/// Nastaví rovnaké polia viacerým zamestnancom naraz.
 ///
 /// # Arguments
 /// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (krajina telefónnych čísel, dĺžky polí)
+/// * `request` – `{"ids": [...], "set": {...}}`
 ///
 /// # Returns
-/// Zoznam produktov
+/// Výsledok (`updated` / `not_found`) pre každé ID
 ///
 /// # Errors
-/// Ak zlyhá čítanie z databázy
-async fn list_products(
+/// 400 pri prázdnom zozname ID alebo zmene, zadanom `id` či `phone_e164`
+/// a nerozpoznateľnom telefónnom čísle, 422 pri príliš dlhom textovom poli
+async fn bulk_update_employees(
     State(db): State<StoreDB>,
-) -> Result<Json<Vec<Product>>, StatusCode> {
-    db.get_products(Product::new_empty())
+    State(config): State<Arc<Config>>,
+    Json(mut request): Json<BulkUpdate<Employee>>,
+) -> Result<Json<Vec<BulkUpdateResult>>, (StatusCode, String)> {
+    request.set.trim_text();
+    request.set.validate(&config.text_limits()).map_err(too_long)?;
+    db.bulk_update_employees(&request.ids, &request.set, &config.phone_country)
         .await
         .map(Json)
-        .map_err(|e| {
-            eprintln!("Chyba pri načítaní produktov: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
+        .map_err(store_error("Chyba pri hromadnej úprave zamestnancov"))
+}
+
+/// Zlúči duplicitný záznam zamestnanca do iného.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `request` – `{"keep_id": ..., "merge_id": ...}`
+///
+/// # Returns
+/// Počet produktov presunutých na ponechaného zamestnanca
+///
+/// # Errors
+/// 400 pri zlúčení zamestnanca do seba, 404 pri neznámom zamestnancovi,
+/// 409 ak majú obaja zamestnanci otvorenú dochádzku
+async fn merge_employees(
+    State(db): State<StoreDB>,
+    Json(request): Json<EmployeeMerge>,
+) -> Result<Json<u64>, (StatusCode, String)> {
+    db.merge_employees(request.keep_id, request.merge_id)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zlučovaní zamestnancov"))
+}
+
+/// Hromadne upraví platy aktívnych zamestnancov oddelenia (`salary * factor + flat`).
+///
+/// Zamestnanci bez platu sa nezmenia, ich ID sú v `skipped_without_salary`.
+/// Úprava sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `request` – oddelenie, násobok a pevná suma
+///
+/// # Returns
+/// Počet upravených zamestnancov a zamestnanci bez platu
+///
+/// # Errors
+/// 400 pri prázdnom oddelení alebo zápornom násobku, 422 ak by niektorý plat bol záporný
+async fn adjust_salaries(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Json(request): Json<SalaryAdjustment>,
+) -> Result<Json<SalaryAdjustmentReport>, (StatusCode, String)> {
+    let department = request.department.trim();
+    let (factor, flat) = (request.factor.unwrap_or(1.0), request.flat.unwrap_or(0.0));
+    let adjusted = db
+        .adjust_salaries(department, factor, flat)
+        .await
+        .map_err(store_error("Chyba pri úprave platov"))?;
+    let skipped_without_salary = db
+        .employees_without_salary(department)
+        .await
+        .map_err(|e| store_error("Chyba pri načítaní zamestnancov")(e.into()))?;
+    let details = format!("department={department}, factor={factor}, flat={flat}, employees={adjusted}");
+    db.record_audit("adjust_salaries", Some(details), actor(&user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    Ok(Json(SalaryAdjustmentReport { department: department.to_string(), adjusted, skipped_without_salary }))
+}
+
+/// Vráti prehľad výkonu zamestnanca (pridané produkty a zaevidované predaje).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID zamestnanca
+///
+/// # Returns
+/// Prehľad výkonu; nulové hodnoty, ak zamestnanec nemá aktivitu
+///
+/// # Errors
+/// 404 pri neznámom zamestnancovi
+async fn employee_scorecard(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Scorecard>, (StatusCode, String)> {
+    db.employee_scorecard(id)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení prehľadu zamestnanca"))
+}
+
+/// Parametre zoznamu podriadených.
+#[derive(Debug, Deserialize)]
+struct ReportsQuery {
+    /// Aj nepriami podriadení (celý podstrom), predvolene len priami
+    all: Option<bool>,
+}
+
+/// Vráti podriadených zamestnanca.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID nadriadeného
+/// * `query` – `all=true` vráti aj nepriamych podriadených
+///
+/// # Returns
+/// Zoznam podriadených zoradený podľa úrovne a ID
+///
+/// # Errors
+/// 404 pri neznámom zamestnancovi
+async fn employee_reports(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<ReportsQuery>,
+) -> Result<Json<Vec<Employee>>, (StatusCode, String)> {
+    db.get_reports(id, query.all.unwrap_or(false))
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní podriadených"))
+}
+
+/// Vráti líniu nadriadených zamestnanca od priameho nadriadeného po vrchol.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID zamestnanca
+///
+/// # Returns
+/// Zoznam nadriadených; prázdny, ak zamestnanec nadriadeného nemá
+///
+/// # Errors
+/// 404 pri neznámom zamestnancovi
+async fn employee_chain(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Vec<Employee>>, (StatusCode, String)> {
+    db.get_management_chain(id)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní nadriadených"))
+}
+
+
+/// Vráti organizačnú štruktúru zamestnancov podľa nadriadených.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Vnorené stromy od zamestnancov bez nadriadeného
+async fn org_chart(State(db): State<StoreDB>) -> Result<Json<Vec<OrgNode>>, (StatusCode, String)> {
+    db.org_chart()
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení organizačnej štruktúry"))
+}
+
+
+/// Čas príchodu alebo odchodu (dodatočná oprava dochádzky).
+#[derive(Debug, Deserialize)]
+struct ClockQuery {
+    /// Čas udalosti (`None` = teraz)
+    at: Option<NaiveDateTime>,
+}
+
+/// Zaeviduje príchod zamestnanca do práce.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID zamestnanca
+/// * `query` – `?at=` čas príchodu, inak teraz
+///
+/// # Returns
+/// `201 Created` s otvoreným záznamom dochádzky
+///
+/// # Errors
+/// 404 pri neznámom zamestnancovi, 409 ak je už prihlásený
+async fn clock_in(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<ClockQuery>,
+) -> Result<(StatusCode, Json<AttendanceEntry>), (StatusCode, String)> {
+    let at = query.at.unwrap_or_else(|| Local::now().naive_local());
+    db.clock_in(id, at)
+        .await
+        .map(|entry| (StatusCode::CREATED, Json(entry)))
+        .map_err(store_error("Chyba pri zápise príchodu"))
+}
+
+/// Zaeviduje odchod zamestnanca; s `?at=` aj opravu chýbajúceho odchodu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID zamestnanca
+/// * `query` – `?at=` čas odchodu, inak teraz
+///
+/// # Returns
+/// Uzavretý záznam dochádzky
+///
+/// # Errors
+/// 409 ak zamestnanec nie je prihlásený, 400 ak je odchod pred príchodom
+async fn clock_out(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<ClockQuery>,
+) -> Result<Json<AttendanceEntry>, (StatusCode, String)> {
+    let at = query.at.unwrap_or_else(|| Local::now().naive_local());
+    db.clock_out(id, at)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zápise odchodu"))
+}
+
+/// Vráti detail dochádzky zamestnanca za obdobie (záznamy, dni, týždne).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (týždenná norma hodín)
+/// * `id` – ID zamestnanca
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Detail dochádzky s nadčasmi po týždňoch
+///
+/// # Errors
+/// 400 pri neplatnom období, 404 pri neznámom zamestnancovi
+async fn employee_hours(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<u32>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<EmployeeHours>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.employee_hours(id, range.from, range.to, config.weekly_hours_norm)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení dochádzky zamestnanca"))
+}
+
+/// Deň a zmena rozpisu služieb (`?date=2024-06-01&shift=morning`).
+#[derive(Debug, Deserialize)]
+struct ScheduleQuery {
+    date: NaiveDate,
+    shift: String,
+}
+
+/// Vráti aktívnych zamestnancov rozpísaných na zmenu v daný deň.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – deň a zmena
+///
+/// # Returns
+/// Zamestnanci na zmene (prázdny zoznam, ak nie je nikto rozpísaný)
+async fn employees_on_shift(
+    State(db): State<StoreDB>,
+    Query(query): Query<ScheduleQuery>,
+) -> Result<Json<Vec<Employee>>, (StatusCode, String)> {
+    db.employees_on_shift(query.date, &query.shift)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní rozpisu služieb"))
+}
+
+/// Priradí zamestnanca na zmenu v daný deň.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `request` – zamestnanec, deň a zmena
+///
+/// # Returns
+/// `201 Created` s priradením
+///
+/// # Errors
+/// 400 pri prázdnej zmene, 404 pri neznámom zamestnancovi, 409 pri neaktívnom
+/// zamestnancovi alebo ak už na zmene je
+async fn assign_shift(
+    State(db): State<StoreDB>,
+    Json(request): Json<ShiftAssignmentRequest>,
+) -> Result<(StatusCode, Json<ShiftAssignment>), (StatusCode, String)> {
+    db.assign_shift(&request)
+        .await
+        .map(|assignment| (StatusCode::CREATED, Json(assignment)))
+        .map_err(store_error("Chyba pri priradení na zmenu"))
+}
+
+/// Zruší priradenie na zmenu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID priradenia
+///
+/// # Returns
+/// `204 No Content` alebo `404 Not Found`
+async fn unassign_shift(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    match db.unassign_shift(id).await.map_err(store_error("Chyba pri rušení priradenia na zmenu"))? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err((StatusCode::NOT_FOUND, format!("priradenie {id} neexistuje"))),
+    }
+}
+
+/// Doplní do filtra predvolené správanie k vyradeným produktom podľa verzie API.
+///
+/// Pod `/api/v1` sa produkty s `date_remove` pred dneškom vynechávajú, kým
+/// filter nemá `include_removed=true`; pôvodné cesty ich vracajú ako doteraz.
+///
+/// # Arguments
+/// * `version` – verzia API požiadavky
+/// * `filter` – filter produktov
+///
+/// # Returns
+/// Hodnota hlavičky `X-Removed-Products`
+fn removed_products(version: ApiVersion, filter: &mut Product) -> HeaderValue {
+    if version == ApiVersion::V1 {
+        filter.include_removed.get_or_insert(false);
+    }
+    if filter.include_removed == Some(false) {
+        HeaderValue::from_static("excluded")
+    } else {
+        HeaderValue::from_static("included")
+    }
+}
+
+/// Vráti produkty, voliteľne filtrované a stránkované cez parametre dotazu.
+///
+/// Pri zadanom `limit` odpoveď obsahuje hlavičku `Link` na susedné stránky.
+/// Počet riadkov je vždy obmedzený na `max_result_rows` z konfigurácie.
+/// Hlavička `X-Removed-Products` uvádza, či zoznam obsahuje vyradené produkty.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+/// * `version` – verzia API (pod `/api/v1` sa vyradené produkty vynechávajú)
+/// * `page` – parametre stránkovania
+/// * `filter` – filter z parametrov dotazu
+/// * `search` – či sa názov a popis hľadajú aj v prekladoch
+/// * `uri` – URI požiadavky (pre hlavičku `Link`)
+/// * `headers` – hlavičky požiadavky (`Accept-Language`)
+///
+/// # Returns
+/// Zoznam produktov, názov a popis v preferovanom jazyku, ak je preklad
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy
+#[allow(clippy::too_many_arguments)]
+async fn list_products(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    State(cache): State<QueryCache>,
+    version: ApiVersion,
+    Query(page): Query<Pagination>,
+    Query(mut filter): Query<Product>,
+    Query(search): Query<TranslationSearch>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<(HeaderMap, Json<Vec<Product>>), StatusCode> {
+    let removed = removed_products(version, &mut filter);
+    let max_rows = config.max_result_rows;
+    let limit = page.limit.unwrap_or(max_rows).min(max_rows);
+    let offset = page.offset.unwrap_or(0);
+    let translations = search.translations.unwrap_or(false);
+    let languages = preferred_languages(&headers);
+
+    let key = (&filter, translations, limit, offset, &languages);
+    let (total, products) = cache
+        .get_or_load(QueryKind::Products, &key, || async {
+            let total = db
+                .count_products(filter.clone(), translations)
+                .await
+                .map_err(internal_error("Chyba pri načítaní produktov"))?;
+            let mut products = db
+                .get_products_page(filter.clone(), translations, limit, offset)
+                .await
+                .map_err(internal_error("Chyba pri načítaní produktov"))?;
+            db.localize_products(&mut products, &languages)
+                .await
+                .map_err(internal_error("Chyba pri načítaní prekladov"))?;
+            Ok::<_, StatusCode>((total, products))
         })
+        .await?;
+
+    let mut headers = page_headers(&uri, &page, limit, total, max_rows);
+    headers.insert(X_REMOVED_PRODUCTS, removed);
+    Ok((headers, Json(products)))
 }
 
 /// Vyhľadá produkty podľa filtra.
 ///
+/// Počet riadkov je obmedzený na `max_result_rows` z konfigurácie. Vyradené
+/// produkty sa vracajú podľa `removed_products` (`include_removed` v tele alebo
+/// v parametroch dotazu).
+///
 /// # Arguments
 /// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+/// * `version` – verzia API
+/// * `search` – či sa názov a popis hľadajú aj v prekladoch
+/// * `removed` – `?include_removed` (má prednosť pred telom)
+/// * `request_headers` – hlavičky požiadavky (`Accept-Language`)
 /// * `filter` – vyhľadávacie kritériá
 ///
 /// # Returns
-/// Zoznam nájdených produktov
+/// Zoznam nájdených produktov, názov a popis v preferovanom jazyku, ak je preklad
 ///
 /// # Errors
 /// Ak zlyhá vyhľadávanie
 async fn search_products(
     State(db): State<StoreDB>,
-    Json(filter): Json<Product>,
-) -> Result<Json<Vec<Product>>, StatusCode> {
-    db.get_products(filter)
+    State(config): State<Arc<Config>>,
+    version: ApiVersion,
+    Query(search): Query<TranslationSearch>,
+    Query(removed): Query<RemovedProducts>,
+    request_headers: HeaderMap,
+    Json(mut filter): Json<Product>,
+) -> Result<(HeaderMap, Json<Vec<Product>>), StatusCode> {
+    if removed.include_removed.is_some() {
+        filter.include_removed = removed.include_removed;
+    }
+    let removed = removed_products(version, &mut filter);
+    let max_rows = config.max_result_rows;
+    let translations = search.translations.unwrap_or(false);
+
+    let total = db
+        .count_products(filter.clone(), translations)
         .await
-        .map(Json)
-        .map_err(|e| {
-            eprintln!("Chyba pri vyhľadávaní produktov: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })
+        .map_err(internal_error("Chyba pri vyhľadávaní produktov"))?;
+    let mut products = db
+        .get_products_page(filter, translations, max_rows, 0)
+        .await
+        .map_err(internal_error("Chyba pri vyhľadávaní produktov"))?;
+    db.localize_products(&mut products, &preferred_languages(&request_headers))
+        .await
+        .map_err(internal_error("Chyba pri načítaní prekladov"))?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(X_TOTAL_COUNT, HeaderValue::from(total));
+    if total > max_rows as u64 {
+        headers.insert(X_RESULT_TRUNCATED, HeaderValue::from_static("true"));
+    }
+    headers.insert(X_REMOVED_PRODUCTS, removed);
+    Ok((headers, Json(products)))
 }
 
-/// Pridá nový produkt.
+/// Exportuje produkty vyhovujúce filtru do JSON súboru na stiahnutie.
+///
+/// Na rozdiel od vyhľadávania sa počet riadkov neobmedzuje. Prerušené
+/// sťahovanie sa dá dokončiť hlavičkou `Range` (s `If-Range` podľa `ETag`).
 ///
 /// # Arguments
 /// * `db` – databáza
-/// * `prod` – dáta produktu
+/// * `request_headers` – hlavičky požiadavky (`Range`, `If-Range`)
+/// * `filter` – vyhľadávacie kritériá
 ///
 /// # Returns
-/// HTTP status kód výsledku
-async fn add_product(
+/// JSON pole produktov s hlavičkou `Content-Disposition: attachment`,
+/// pri `Range` `206 Partial Content` alebo `416 Range Not Satisfiable`
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy alebo serializácia
+async fn export_products(
     State(db): State<StoreDB>,
-    Json(prod): Json<Product>,
-) -> StatusCode {
-    match db.add_product_to_store_db(&prod).await {
-        Ok(_) => StatusCode::CREATED,
-        Err(e) => {
-            eprintln!("Chyba pri pridávaní produktu: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        }
-    }
+    request_headers: HeaderMap,
+    Json(filter): Json<Product>,
+) -> Result<Response, StatusCode> {
+    let context = "Chyba pri exporte produktov";
+    let products = db.get_products(filter).await.map_err(internal_error(context))?;
+    let body = serde_json::to_vec_pretty(&products).map_err(internal_error(context))?;
+
+    let filename = format!("products-{}.json", Local::now().date_naive());
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!("attachment; filename=\"{filename}\"")).map_err(internal_error(context))?,
+    );
+    Ok(range::ranged_response(&request_headers, headers, body))
 }
 
-/// Vymaže produkt podľa ID.
+/// Exportuje produkty alebo zamestnancov vyhovujúcich filtru vo zvolenom formáte.
+///
+/// Vráti len vybrané polia (v poradí `fields`) ako CSV, JSON, NDJSON alebo XLSX.
+/// Filter má tvar tela `POST /products/search`, resp. `POST /employees/search`;
+/// počet riadkov sa neobmedzuje.
 ///
 /// # Arguments
 /// * `db` – databáza
-/// * `id` – ID produktu
+/// * `config` – konfigurácia aplikácie (krajina telefónnych čísel)
+/// * `request` – druh záznamov, filter, polia a formát
 ///
 /// # Returns
-/// HTTP status kód výsledku
-async fn delete_product(
+/// Súbor s hlavičkami `Content-Type` a `Content-Disposition: attachment`
+///
+/// # Errors
+/// 400 pri neznámom, opakovanom alebo prázdnom zozname polí, 422 pri neplatnom filtri
+async fn export_records(
     State(db): State<StoreDB>,
-    Path(id): Path<u32>,
-) -> StatusCode {
-    match db.delete_product(id).await {
-        Ok(true) => StatusCode::NO_CONTENT,
-        Ok(false) => StatusCode::NOT_FOUND,
-        Err(e) => {
-            eprintln!("Chyba pri mazaní produktu: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
+    State(config): State<Arc<Config>>,
+    Json(request): Json<ExportRequest>,
+) -> Result<Response, (StatusCode, String)> {
+    let context = "Chyba pri exporte";
+    let fields = export::select_fields(request.entity, request.fields.as_deref()).map_err(store_error(context))?;
+    let filter = serde_json::Value::Object(request.filter);
+    let invalid_filter = |e: Box<JsonBodyError>| (StatusCode::UNPROCESSABLE_ENTITY, e.message);
+    let rows = match request.entity {
+        SearchEntity::Products => {
+            let filter: Product = json::from_value(&filter, true).map_err(invalid_filter)?;
+            let products = db.get_products(filter).await.map_err(|e| store_error(context)(e.into()))?;
+            export::project(&products, &fields)
+        }
+        SearchEntity::Employees => {
+            let mut filter: Employee = json::from_value(&filter, true).map_err(invalid_filter)?;
+            normalize_phone_filter(&mut filter, &config.phone_country);
+            filter.include_removed.get_or_insert(false);
+            let employees = db.get_employees(filter).await.map_err(|e| store_error(context)(e.into()))?;
+            export::project(&employees, &fields)
         }
     }
+    .map_err(store_error(context))?;
+    let body = export::render(request.format, request.entity, &fields, &rows).map_err(store_error(context))?;
+
+    let filename = format!("{}-{}.{}", request.entity.as_str(), Local::now().date_naive(), request.format.extension());
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(request.format.content_type()));
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!("attachment; filename=\"{filename}\""))
+            .map_err(|e| store_error(context)(e.into()))?,
+    );
+    Ok((headers, body).into_response())
 }
 
-/// Aktualizuje produkt.
+/// Počet produktov načítaných naraz pri streamovanom exporte.
+const NDJSON_PAGE_SIZE: u32 = 500;
+
+/// Streamuje produkty vyhovujúce filtru ako NDJSON (jeden produkt na riadok).
+///
+/// Produkty sa z databázy čítajú po stránkach a každá stránka sa hneď odošle,
+/// pamäť servera preto nerastie s počtom produktov. Ak čítanie zlyhá uprostred
+/// exportu, telo odpovede sa ukončí chybou (spojenie sa preruší), takže klient
+/// nedostane neúplný súbor ako úspešný. Vyradené produkty sa vracajú podľa
+/// `removed_products`.
 ///
 /// # Arguments
 /// * `db` – databáza
-/// * `id` – ID produktu
-/// * `prod` – nové dáta
+/// * `version` – verzia API
+/// * `filter` – filter z parametrov dotazu
 ///
 /// # Returns
-/// HTTP status kód výsledku
-async fn update_product(
+/// Telo `application/x-ndjson` s hlavičkou `Content-Disposition: attachment`
+async fn export_products_ndjson(
     State(db): State<StoreDB>,
-    Path(id): Path<u32>,
-    Json(mut prod): Json<Product>,
-) -> StatusCode {
-    prod.id = Some(id);
-    match db.update_product(&prod).await {
-        Ok(true) => StatusCode::OK,
-        Ok(false) => StatusCode::NOT_FOUND,
-        Err(e) => {
-            eprintln!("Chyba pri updatovaní produktu: {e}");
-            StatusCode::INTERNAL_SERVER_ERROR
+    version: ApiVersion,
+    Query(mut filter): Query<Product>,
+) -> Response {
+    let removed = removed_products(version, &mut filter);
+    let pages = futures_util::stream::unfold(Some(0u32), move |offset| {
+        let db = db.clone();
+        let filter = filter.clone();
+        async move {
+            let offset = offset?;
+            let page = match db.get_products_page(filter, false, NDJSON_PAGE_SIZE, offset).await {
+                Ok(page) => page,
+                Err(e) => {
+                    eprintln!("Chyba pri exporte produktov (od riadku {offset}): {e}");
+                    return Some((Err(std::io::Error::other(e)), None));
+                }
+            };
+            if page.is_empty() {
+                return None;
+            }
+            let next = (page.len() == NDJSON_PAGE_SIZE as usize).then(|| offset + NDJSON_PAGE_SIZE);
+            let mut chunk = Vec::new();
+            for product in &page {
+                if let Err(e) = serde_json::to_writer(&mut chunk, product) {
+                    eprintln!("Chyba pri exporte produktov: {e}");
+                    return Some((Err(std::io::Error::other(e)), None));
+                }
+                chunk.push(b'\n');
+            }
+            Some((Ok(Bytes::from(chunk)), next))
         }
+    });
+
+    let filename = format!("products-{}.ndjson", Local::now().date_naive());
+    (
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\"")),
+        ],
+        [(X_REMOVED_PRODUCTS, removed)],
+        Body::from_stream(pages),
+    )
+        .into_response()
+}
+
+/// Počet záznamov audit logu načítaných naraz pri exporte do CSV.
+const AUDIT_PAGE_SIZE: u32 = 500;
+
+/// Streamuje audit log vyhovujúci filtru ako CSV (od najstaršieho záznamu).
+///
+/// Záznamy sa čítajú po stránkach ako pri `export_products_ndjson`; chyba
+/// uprostred exportu preruší telo odpovede. Stĺpce sú `export::AUDIT_CSV_COLUMNS`,
+/// podrobnosti záznamu sú v stĺpci `changed_fields` ako kompaktný JSON.
+/// Vyžaduje rolu admin.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `filter` – `?from=YYYY-MM-DD&to=YYYY-MM-DD&entity=...&action=...`
+///
+/// # Returns
+/// Telo `text/csv` s hlavičkou `Content-Disposition: attachment`
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn export_audit_csv(
+    State(db): State<StoreDB>,
+    Query(filter): Query<AuditFilter>,
+) -> Result<Response, (StatusCode, String)> {
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        validate_range(&DateRange { from, to })?;
     }
+    // prvá stránka (`after_id` 0) nesie hlavičku CSV aj bez záznamov
+    let pages = futures_util::stream::unfold(Some(0u32), move |after_id| {
+        let db = db.clone();
+        let filter = filter.clone();
+        async move {
+            let after_id = after_id?;
+            let page = match db.get_audit_page(&filter, AUDIT_PAGE_SIZE, after_id).await {
+                Ok(page) => page,
+                Err(e) => {
+                    eprintln!("Chyba pri exporte audit logu (po zázname {after_id}): {e}");
+                    return Some((Err(std::io::Error::other(e)), None));
+                }
+            };
+            if page.is_empty() && after_id > 0 {
+                return None;
+            }
+            let next = (page.len() == AUDIT_PAGE_SIZE as usize).then(|| page.last().and_then(|e| e.id)).flatten();
+            match export::audit_csv(&page, after_id == 0) {
+                Ok(chunk) => Some((Ok(Bytes::from(chunk)), next)),
+                Err(e) => {
+                    eprintln!("Chyba pri exporte audit logu: {e}");
+                    Some((Err(std::io::Error::other(e)), None))
+                }
+            }
+        }
+    });
+
+    let filename = format!("audit-{}.csv", Local::now().date_naive());
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{filename}\"")),
+        ],
+        Body::from_stream(pages),
+    )
+        .into_response())
+}
+
+/// Parametre náhodnej vzorky produktov.
+#[derive(Debug, Deserialize)]
+struct SampleQuery {
+    /// Veľkosť vzorky, predvolene 10 (najviac `MAX_SAMPLE_SIZE`)
+    n: Option<u32>,
+}
+
+/// Vráti náhodnú vzorku produktov na kontrolu fyzického stavu zásob.
+///
+/// Väčšia požadovaná vzorka sa obmedzí na `MAX_SAMPLE_SIZE`; menší sklad vráti
+/// všetky produkty.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?n=N`
+///
+/// # Returns
+/// Náhodne vybrané produkty
+async fn sample_products(
+    State(db): State<StoreDB>,
+    Query(query): Query<SampleQuery>,
+) -> Result<Json<Vec<Product>>, StatusCode> {
+    db.random_products(query.n.unwrap_or(10))
+        .await
+        .map(Json)
+        .map_err(internal_error("Chyba pri výbere vzorky produktov"))
+}
+
+/// Parametre zoznamu novo pridaných produktov.
+#[derive(Debug, Deserialize)]
+struct RecentQuery {
+    /// Počet produktov, predvolene 10 (najviac `MAX_RECENT_PRODUCTS`)
+    limit: Option<u32>,
+}
+
+/// Vráti naposledy pridané produkty zoradené podľa `date_added` od najnovšieho.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?limit=N`
+///
+/// # Returns
+/// Najviac `limit` produktov; produkty bez dátumu pridania sa nevracajú
+async fn recent_products(
+    State(db): State<StoreDB>,
+    Query(query): Query<RecentQuery>,
+) -> Result<Json<Vec<Product>>, StatusCode> {
+    db.recently_added_products(query.limit.unwrap_or(10))
+        .await
+        .map(Json)
+        .map_err(internal_error("Chyba pri načítaní nových produktov"))
+}
+
+/// Parametre porovnania produktov.
+#[derive(Debug, Deserialize)]
+struct CompareQuery {
+    /// ID produktov oddelené čiarkou (`1,2,3`)
+    ids: String,
+}
+
+/// Porovná produkty vedľa seba (napr. pri rozhodovaní o nákupe).
+///
+/// Prvé ID je základ: pre každý ďalší nájdený produkt sa vypočíta rozdiel
+/// predajnej a nákupnej ceny, marže a zásob oproti nemu (pozri `ProductDelta`).
+/// Ak prvý produkt neexistuje, základom je prvý nájdený. Opakované ID sa berú raz.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?ids=1,2`
+///
+/// # Returns
+/// Produkty, rozdiely a ID, ktoré sa nenašli
+///
+/// # Errors
+/// 400 pri neplatnom ID, menej ako dvoch alebo viac ako `MAX_COMPARE_PRODUCTS` produktoch
+async fn compare_products(
+    State(db): State<StoreDB>,
+    Query(query): Query<CompareQuery>,
+) -> Result<Json<ProductComparison>, (StatusCode, String)> {
+    let mut ids: Vec<u32> = Vec::new();
+    for part in query.ids.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let id = part.parse().map_err(|_| (StatusCode::BAD_REQUEST, format!("neplatné ID produktu {part:?}")))?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if !(2..=MAX_COMPARE_PRODUCTS).contains(&ids.len()) {
+        return Err((StatusCode::BAD_REQUEST, format!("porovnať sa dá 2 až {MAX_COMPARE_PRODUCTS} produktov")));
+    }
+
+    let products = db
+        .get_products_by_ids(&ids)
+        .await
+        .map_err(|e| store_error("Chyba pri načítaní produktov na porovnanie")(e.into()))?;
+    let not_found = ids.into_iter().filter(|&id| !products.iter().any(|p| p.id == Some(id))).collect();
+    let deltas = match products.split_first() {
+        Some((base, rest)) => rest.iter().map(|other| ProductDelta::between(base, other)).collect(),
+        None => Vec::new(),
+    };
+    Ok(Json(ProductComparison { products, deltas, not_found }))
+}
+
+/// Vráti detail produktu vrátane stavu zásob.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (hranica nízkeho stavu)
+/// * `id` – ID produktu
+/// * `headers` – hlavičky požiadavky (`Accept-Language`)
+///
+/// # Returns
+/// Detail produktu (názov a popis v preferovanom jazyku, ak je preklad) alebo `404 Not Found`
+async fn get_product(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<Json<ProductDetail>, StatusCode> {
+    let product = db
+        .get_product(id)
+        .await
+        .map_err(internal_error("Chyba pri načítaní produktu"))?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    product_detail(&db, &config, product, &headers).await
+}
+
+/// Vráti detail produktu podľa SKU (napr. z etikety v regáli).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (hranica nízkeho stavu)
+/// * `sku` – SKU produktu (na veľkosti písmen nezáleží)
+/// * `headers` – hlavičky požiadavky (`Accept-Language`)
+///
+/// # Returns
+/// Detail produktu ako `GET /products/{id}` alebo `404 Not Found`
+async fn get_product_by_sku(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Path(sku): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<ProductDetail>, StatusCode> {
+    let product = db
+        .get_product_by_sku(&sku)
+        .await
+        .map_err(internal_error("Chyba pri načítaní produktu"))?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    product_detail(&db, &config, product, &headers).await
+}
+
+/// Vráti kompaktný index aktívnych produktov (ID, názov, čiarový kód, kategória,
+/// cena) na vyhľadávanie priamo u klienta.
+///
+/// Serializovaný index sa drží v cache dotazov, kým sa nezmení niektorý produkt.
+/// `ETag` sa počíta z obsahu, takže klient s aktuálnym indexom dostane pri
+/// `If-None-Match` odpoveď `304 Not Modified` bez tela.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `cache` – cache dotazov
+/// * `headers` – hlavičky požiadavky (`If-None-Match`)
+///
+/// # Returns
+/// JSON pole `SearchIndexEntry` s hlavičkami `ETag` a `Cache-Control`
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy
+async fn product_index(
+    State(db): State<StoreDB>,
+    State(cache): State<QueryCache>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let (etag, body) = cache
+        .get_or_load(QueryKind::SearchIndex, &(), || async {
+            let index = db.search_index().await.map_err(internal_error("Chyba pri načítaní indexu produktov"))?;
+            let body = serde_json::to_vec(&index).map_err(internal_error("Chyba pri serializácii indexu produktov"))?;
+            Ok::<_, StatusCode>((range::etag(&body), Bytes::from(body)))
+        })
+        .await?;
+
+    let mut response_headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-cache"));
+    let matches = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"));
+    if matches {
+        return Ok((StatusCode::NOT_MODIFIED, response_headers).into_response());
+    }
+    response_headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    Ok((response_headers, body).into_response())
+}
+
+/// Nájde produkt podľa ID, čiarového kódu alebo názvu (`?id=`, `?barcode=`, `?name=`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (hranica nízkeho stavu)
+/// * `lookup` – práve jeden identifikátor
+/// * `headers` – hlavičky požiadavky (`Accept-Language`)
+///
+/// # Returns
+/// Detail produktu ako `GET /products/{id}`
+///
+/// # Errors
+/// 400 bez identifikátora alebo pri viacerých, 404 ak produkt neexistuje,
+/// 409 ak čiarovému kódu alebo názvu zodpovedá viac produktov
+async fn lookup_product(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(lookup): Query<ProductLookup>,
+    headers: HeaderMap,
+) -> Result<Json<ProductDetail>, (StatusCode, String)> {
+    let product = db
+        .lookup_product(&lookup)
+        .await
+        .map_err(store_error("Chyba pri vyhľadaní produktu"))?;
+    product_detail(&db, &config, product, &headers)
+        .await
+        .map_err(|status| (status, "Chyba pri načítaní produktu".to_string()))
+}
+
+/// Doplní k produktu preklad, obrázky a stav zásob.
+async fn product_detail(
+    db: &StoreDB,
+    config: &Config,
+    mut product: Product,
+    headers: &HeaderMap,
+) -> Result<Json<ProductDetail>, StatusCode> {
+    db.localize_products(std::slice::from_mut(&mut product), &preferred_languages(headers))
+        .await
+        .map_err(internal_error("Chyba pri načítaní prekladov"))?;
+    let id = product.id.unwrap_or_default();
+    let images = db.list_product_images(id).await.map_err(internal_error("Chyba pri načítaní obrázkov"))?;
+
+    Ok(Json(ProductDetail {
+        stock_status: product.stock_status(config.low_stock_threshold),
+        product,
+        images,
+    }))
+}
+
+/// Vráti stav zásob produktu (`InStock`, `LowStock`, `OutOfStock`, `Discontinued`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (hranica nízkeho stavu)
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Stav zásob alebo `404 Not Found`
+async fn get_stock_status(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<u32>,
+) -> Result<Json<StockStatus>, StatusCode> {
+    db.get_product(id)
+        .await
+        .map_err(internal_error("Chyba pri načítaní produktu"))?
+        .map(|p| Json(p.stock_status(config.low_stock_threshold)))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Vráti, na koľko dní vystačia zásoby produktu pri súčasnom tempe predaja.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Pokrytie v dňoch s denným predajom; bez predaja `coverage_days: null` a `message`
+///
+/// # Errors
+/// 404 ak produkt neexistuje
+async fn stock_coverage(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<StockCoverage>, (StatusCode, String)> {
+    db.stock_coverage(id)
+        .await
+        .map_err(store_error("Chyba pri výpočte pokrytia zásob"))?
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")))
+}
+
+/// Poplatok z predaja pre výpočet čistej marže (`?fee=0.02`).
+#[derive(Debug, Deserialize)]
+struct NetMarginQuery {
+    /// Podiel z ceny (`None` = bez poplatkov)
+    fee: Option<f64>,
+}
+
+/// Vráti čistú maržu produktu: dnešná predajná cena (počas akcie akciová)
+/// mínus nákupná cena a poplatky.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `query` – `?fee=` poplatok ako podiel z ceny
+///
+/// # Returns
+/// Čistá marža; pri chýbajúcej cene `net_margin: null`
+///
+/// # Errors
+/// 400 pri poplatku mimo intervalu 0 – 1, 404 ak produkt neexistuje
+async fn net_margin(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<NetMarginQuery>,
+) -> Result<Json<NetMargin>, (StatusCode, String)> {
+    let fee_rate = query.fee.unwrap_or(0.0);
+    if !(0.0..1.0).contains(&fee_rate) {
+        return Err((StatusCode::BAD_REQUEST, format!("poplatok {fee_rate} musí byť v intervale 0 až 1")));
+    }
+    let product = db
+        .get_product(id)
+        .await
+        .map_err(|e| store_error("Chyba pri načítaní produktu")(e.into()))?
+        .ok_or((StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")))?;
+
+    let today = Local::now().date_naive();
+    let price = product.price_on(today);
+    let net_margin = price.and_then(|p| product.net_margin(p, fee_rate));
+    Ok(Json(NetMargin {
+        product_id: id,
+        price,
+        on_sale: product.on_sale(today),
+        cost_price: product.cost_price,
+        fee_rate,
+        fees: price.map(|p| p * fee_rate),
+        net_margin_percent: net_margin.zip(price.filter(|&p| p != 0.0)).map(|(m, p)| m / p * 100.0),
+        net_margin,
+    }))
+}
+
+/// Fixné náklady pre výpočet bodu zvratu (`?fixed=1000`).
+#[derive(Debug, Deserialize)]
+struct BreakEvenQuery {
+    fixed: f64,
+}
+
+/// Vráti počet kusov, ktoré treba predať, aby marža pokryla fixné náklady.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `query` – `?fixed=` fixné náklady
+///
+/// # Returns
+/// Marža na kus a bod zvratu (pozri `Product::break_even_quantity`)
+///
+/// # Errors
+/// 400 pri záporných fixných nákladoch, 404 ak produkt neexistuje, 422 ak
+/// produkt nemá ceny alebo sa predáva za nákupnú cenu či so stratou
+async fn break_even(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<BreakEvenQuery>,
+) -> Result<Json<BreakEven>, (StatusCode, String)> {
+    let fixed_costs = query.fixed;
+    if !fixed_costs.is_finite() || fixed_costs < 0.0 {
+        return Err((StatusCode::BAD_REQUEST, format!("fixné náklady {fixed_costs} musia byť nezáporné")));
+    }
+    let product = db
+        .get_product(id)
+        .await
+        .map_err(|e| store_error("Chyba pri načítaní produktu")(e.into()))?
+        .ok_or((StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")))?;
+
+    let unit_margin = product.margin().ok_or((
+        StatusCode::UNPROCESSABLE_ENTITY,
+        format!("produkt {id} nemá predajnú alebo nákupnú cenu"),
+    ))?;
+    let quantity = product.break_even_quantity(fixed_costs).ok_or_else(|| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            format!("produkt {id} sa nepredáva so ziskom (marža {unit_margin:.2}), bod zvratu neexistuje"),
+        )
+    })?;
+    Ok(Json(BreakEven { product_id: id, fixed_costs, unit_margin, quantity }))
+}
+
+/// Obdobie knihy skladových pohybov.
+#[derive(Debug, Deserialize)]
+struct MovementsQuery {
+    /// Prvý deň obdobia
+    from: Option<NaiveDate>,
+    /// Posledný deň obdobia
+    to: Option<NaiveDate>,
+}
+
+/// Vráti knihu skladových pohybov produktu (predaje, vrátenia, príjmy, odpisy).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `query` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Pohyby od najstaršieho
+///
+/// # Errors
+/// 400 pri neplatnom období, 404 ak produkt neexistuje
+async fn stock_movements(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<MovementsQuery>,
+) -> Result<Json<Vec<StockMovement>>, (StatusCode, String)> {
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        validate_range(&DateRange { from, to })?;
+    }
+    db.movements(id, query.from, query.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri čítaní pohybov skladu"))
+}
+
+/// Parametre prehľadu produktov s krátkym pokrytím zásob.
+#[derive(Debug, Deserialize)]
+struct CoverageQuery {
+    /// Hranica pokrytia v dňoch, predvolene `DEFAULT_COVERAGE_DAYS`
+    days: Option<u32>,
+}
+
+/// Vráti aktívne produkty, ktorých zásoby pri súčasnom tempe predaja nevydržia `days` dní.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?days=N`
+///
+/// # Returns
+/// Produkty od najkratšieho pokrytia
+///
+/// # Errors
+/// 400 pri `days=0`
+async fn low_coverage(
+    State(db): State<StoreDB>,
+    Query(query): Query<CoverageQuery>,
+) -> Result<Json<Vec<StockCoverage>>, (StatusCode, String)> {
+    let days = query.days.unwrap_or(DEFAULT_COVERAGE_DAYS);
+    if days == 0 {
+        return Err((StatusCode::BAD_REQUEST, "hranica pokrytia musí mať aspoň 1 deň".into()));
+    }
+    db.low_coverage(days)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte pokrytia zásob"))
+}
+
+/// Vráti čiarový kód produktu ako PNG (napr. pre tlačiareň etikiet).
+///
+/// Pozri `barcode_image`.
+async fn barcode_png(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<BarcodeQuery>,
+    headers: HeaderMap,
+) -> Response {
+    barcode_image(&db, id, query, &headers, false).await
+}
+
+/// Vráti čiarový kód produktu ako SVG.
+///
+/// Pozri `barcode_image`.
+async fn barcode_svg(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<BarcodeQuery>,
+    headers: HeaderMap,
+) -> Response {
+    barcode_image(&db, id, query, &headers, true).await
+}
+
+/// Vykreslí čiarový kód produktu (EAN-13 alebo EAN-8 podľa počtu číslic).
+///
+/// Obrázok sa mení len so zmenou kódu, `ETag` sa preto odvodí z kódu
+/// a parametrov; pri zhode s `If-None-Match` sa vráti `304 Not Modified`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `query` – šírka modulu, výška čiar a či vypísať číslice
+/// * `headers` – hlavičky požiadavky (`If-None-Match`)
+/// * `svg` – SVG namiesto PNG
+///
+/// # Returns
+/// Obrázok s hlavičkami `ETag` a `Cache-Control`
+///
+/// # Errors
+/// 404 s `BarcodeError` pri neznámom produkte alebo produkte bez kódu, 422 s
+/// `BarcodeError` pri neplatnom uloženom kóde, 400 pri parametroch mimo rozsahu
+async fn barcode_image(db: &StoreDB, id: u32, query: BarcodeQuery, headers: &HeaderMap, svg: bool) -> Response {
+    let options = RenderOptions {
+        module_width: query.module_width.unwrap_or(barcode::DEFAULT_MODULE_WIDTH),
+        height: query.height.unwrap_or(barcode::DEFAULT_HEIGHT),
+        text: query.text.unwrap_or(true),
+    };
+    if let Err(e) = options.validate() {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    let error = |status: StatusCode, error: &str, bar_code: Option<i64>, message: String| {
+        (status, Json(BarcodeError { error: error.into(), product_id: id, bar_code, message })).into_response()
+    };
+
+    let product = match db.get_product(id).await {
+        Ok(Some(product)) => product,
+        Ok(None) => return error(StatusCode::NOT_FOUND, "product_not_found", None, format!("produkt {id} neexistuje")),
+        Err(e) => return internal_error("Chyba pri načítaní produktu")(e).into_response(),
+    };
+    let code = match product.bar_code {
+        Some(code) if code != 0 => code,
+        _ => return error(StatusCode::NOT_FOUND, "no_barcode", None, format!("produkt {id} nemá čiarový kód")),
+    };
+    let ean = match Ean::from_code(code) {
+        Ok(ean) => ean,
+        Err(e) => return error(StatusCode::UNPROCESSABLE_ENTITY, "invalid_barcode", Some(code), e),
+    };
+
+    let extension = if svg { "svg" } else { "png" };
+    let etag = format!(
+        "\"{}-{}-{}-{}.{extension}\"",
+        ean.text(),
+        options.module_width,
+        options.height,
+        u8::from(options.text)
+    );
+    let mut response_headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("public, no-cache"));
+    let matches = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"));
+    if matches {
+        return (StatusCode::NOT_MODIFIED, response_headers).into_response();
+    }
+
+    let body = if svg {
+        response_headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/svg+xml"));
+        ean.to_svg(&options).into_bytes()
+    } else {
+        response_headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/png"));
+        match ean.to_png(&options) {
+            Ok(png) => png,
+            Err(e) => return internal_error("Chyba pri vykreslení čiarového kódu")(e).into_response(),
+        }
+    };
+    (response_headers, body).into_response()
+}
+
+/// Vráti QR kód produktu ako PNG (napr. pre tabuľku s odkazom na informácie o produkte).
+///
+/// Kód obsahuje ID produktu, alebo odkaz z `?url=`, v ktorom sa `{id}` nahradí ID.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `query` – zakódovaný odkaz a strana obrázka (`size`, predvolene 256 px)
+///
+/// # Returns
+/// Štvorcový obrázok `image/png`
+///
+/// # Errors
+/// 404 pri neznámom produkte, 400 pri veľkosti mimo rozsahu alebo príliš dlhom odkaze
+async fn qr_png(State(db): State<StoreDB>, Path(id): Path<u32>, Query(query): Query<QrQuery>) -> Response {
+    match db.get_product(id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")).into_response(),
+        Err(e) => return internal_error("Chyba pri načítaní produktu")(e).into_response(),
+    }
+    let text = match &query.url {
+        Some(url) => url.replace("{id}", &id.to_string()),
+        None => id.to_string(),
+    };
+    let size = query.size.unwrap_or(qr::DEFAULT_SIZE);
+    let code = match Qr::new(&text).and_then(|code| code.validate_size(size).map(|_| code)) {
+        Ok(code) => code,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    match code.to_png(size) {
+        Ok(png) => ([(header::CONTENT_TYPE, "image/png")], png).into_response(),
+        Err(e) => internal_error("Chyba pri vykreslení QR kódu")(e).into_response(),
+    }
+}
+
+/// Nastaví alebo zruší (`null`) vlastnú hranicu nízkeho stavu zásob produktu.
+///
+/// Produkt s hranicou sa v správe o nízkom stave, návrhoch na doobjednanie
+/// a upozorneniach porovnáva s ňou namiesto `low_stock_threshold`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `request` – nová hranica
+///
+/// # Returns
+/// Upravený produkt
+///
+/// # Errors
+/// 404 pri neznámom produkte, 400 ak hranica nezodpovedá jednotke produktu
+async fn set_min_stock(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(request): Json<MinStockRequest>,
+) -> Result<Json<Product>, (StatusCode, String)> {
+    db.set_min_stock(id, request.min_stock)
+        .await
+        .map_err(store_error("Chyba pri nastavení hranice zásob"))?
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")))
+}
+
+/// Nastaví alebo zruší (`null`) najvyšší žiadaný stav zásob produktu.
+///
+/// Produkt nad hranicou sa objaví v `GET /products/overstocked` a príjem
+/// tovaru, ktorý ju prekročí, vráti varovanie (alebo sa odmietne pri `block_overstock`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `request` – nová hranica
+///
+/// # Returns
+/// Upravený produkt
+///
+/// # Errors
+/// 404 pri neznámom produkte, 400 ak hranica nezodpovedá jednotke produktu
+async fn set_max_stock(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(request): Json<MaxStockRequest>,
+) -> Result<Json<Product>, (StatusCode, String)> {
+    db.set_max_stock(id, request.max_stock)
+        .await
+        .map_err(store_error("Chyba pri nastavení hranice zásob"))?
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")))
+}
+
+/// Vráti aktívne produkty so zásobami nad ich `max_stock` (viazaný kapitál a miesto).
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Produkty od najväčšieho prebytku
+async fn overstocked_products(State(db): State<StoreDB>) -> Result<Json<Vec<Product>>, (StatusCode, String)> {
+    db.get_overstocked()
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri hľadaní produktov nad hranicou zásob"))
+}
+
+/// Nastaví alebo zruší (`"sale_price": null`) akciu produktu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `request` – akciová cena a posledný deň akcie
+///
+/// # Returns
+/// Upravený produkt
+///
+/// # Errors
+/// 404 pri neznámom produkte, 400 pri chýbajúcom konci akcie, akciovej cene,
+/// ktorá nie je nižšia ako predajná, alebo konci akcie v minulosti
+async fn set_sale(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(request): Json<SaleRequest>,
+) -> Result<Json<Product>, (StatusCode, String)> {
+    let product = match (request.sale_price, request.ends) {
+        (Some(price), Some(ends)) => db.set_sale(id, price, ends).await,
+        (Some(_), None) => return Err((StatusCode::BAD_REQUEST, "chýba koniec akcie (ends)".into())),
+        (None, _) => db.clear_sale(id).await,
+    };
+    product
+        .map_err(store_error("Chyba pri nastavení akcie"))?
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")))
+}
+
+/// Naplánuje zmenu predajnej ceny produktu od daného dňa.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `request` – nová cena a prvý deň jej platnosti
+///
+/// # Returns
+/// `201 Created` s naplánovanou zmenou
+///
+/// # Errors
+/// 404 pri neznámom produkte, 400 pri nekladnej cene alebo dni platnosti v minulosti
+async fn schedule_price_change(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(request): Json<PriceScheduleRequest>,
+) -> Result<(StatusCode, Json<ScheduledPriceChange>), (StatusCode, String)> {
+    db.schedule_price_change(id, request.new_price, request.effective_date)
+        .await
+        .map(|change| (StatusCode::CREATED, Json(change)))
+        .map_err(store_error("Chyba pri plánovaní zmeny ceny"))
+}
+
+/// Vráti naplánované (a uplatnené) zmeny cien (`?product_id=`, `?pending=true`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – filter produktu a stavu
+///
+/// # Returns
+/// Zmeny zoradené podľa dňa platnosti
+async fn scheduled_price_changes(
+    State(db): State<StoreDB>,
+    Query(query): Query<ScheduledChangesQuery>,
+) -> Result<Json<Vec<ScheduledPriceChange>>, (StatusCode, String)> {
+    db.scheduled_price_changes(query.product_id, query.pending)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní naplánovaných zmien cien"))
+}
+
+/// Vráti produkty s dnes platnou akciou.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Produkty zoradené podľa konca akcie
+async fn products_on_sale(State(db): State<StoreDB>) -> Result<Json<Vec<Product>>, (StatusCode, String)> {
+    db.products_on_sale(Local::now().date_naive())
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní akciových produktov"))
+}
+
+/// Vráti šarže produktu zoradené podľa expirácie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Zoznam šarží
+async fn list_batches(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Vec<ProductBatch>>, StatusCode> {
+    db.get_batches(id)
+        .await
+        .map(Json)
+        .map_err(internal_error("Chyba pri načítaní šarží"))
+}
+
+/// Naskladní kusy do šarže produktu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `batch` – kód šarže, expirácia a množstvo
+///
+/// # Returns
+/// `201 Created`
+///
+/// # Errors
+/// 404 ak produkt neexistuje, 400 pri nulovom množstve
+async fn add_batch(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(batch): Json<ProductBatch>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    db.add_batch(id, &batch)
+        .await
+        .map(|_| StatusCode::CREATED)
+        .map_err(store_error("Chyba pri pridávaní šarže"))
+}
+
+/// Voľby inventúry.
+#[derive(Debug, Deserialize, Default)]
+struct ReconcileQuery {
+    /// Zosúladiť stav zásob so spočítaným množstvom (inak len správa o rozdieloch)
+    apply: Option<bool>,
+}
+
+/// Porovná spočítané množstvá z inventúry so stavom zásob.
+///
+/// S `?apply=true` sa stav zásob nastaví na spočítané množstvá a rozdiely sa
+/// zapíšu do knihy pohybov ako `stocktake`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – voľba zosúladenia
+/// * `counts` – `[{"id": 1, "counted": 8}, ...]`
+///
+/// # Returns
+/// Rozdiely oproti evidencii a hodnota manka
+///
+/// # Errors
+/// 404 pri neznámom produkte, 400 pri prázdnom zozname, opakovanom produkte,
+/// sade alebo neprípustnom množstve
+async fn reconcile_stock(
+    State(db): State<StoreDB>,
+    Query(query): Query<ReconcileQuery>,
+    Json(counts): Json<Vec<StockCount>>,
+) -> Result<Json<StocktakeReport>, (StatusCode, String)> {
+    db.reconcile(&counts, query.apply.unwrap_or(false))
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri inventúre"))
+}
+
+/// Zmení stav zásob produktu o zadané množstvo (inventúrna úprava).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `adjustment` – zmena množstva a voliteľná jednotka
+///
+/// # Returns
+/// Produkt s novým stavom zásob
+///
+/// # Errors
+/// 404 ak produkt neexistuje, 400 pri nulovom množstve alebo sade,
+/// 409 ak na sklade nie je dosť tovaru na odpis
+async fn adjust_stock(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(adjustment): Json<StockAdjustment>,
+) -> Result<Json<Product>, (StatusCode, String)> {
+    db.adjust_stock(id, adjustment.delta, adjustment.unit)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri úprave zásob"))
+}
+
+/// Odloží tovar pre zákazníka; rezervované množstvo sa nedá predať inému.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `request` – množstvo, zákazník a čas, do ktorého rezervácia platí
+///
+/// # Returns
+/// `201 Created` s rezerváciou
+///
+/// # Errors
+/// 404 ak produkt neexistuje, 400 pri neplatnej požiadavke alebo sade,
+/// 409 ak nie je dosť voľného tovaru
+async fn add_reservation(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(request): Json<ReservationRequest>,
+) -> Result<(StatusCode, Json<Reservation>), (StatusCode, String)> {
+    db.add_reservation(id, &request)
+        .await
+        .map(|reservation| (StatusCode::CREATED, Json(reservation)))
+        .map_err(store_error("Chyba pri rezervácii tovaru"))
+}
+
+/// Upraví šaržu produktu (kód, expirácia, množstvo).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `batch_id` – ID šarže
+/// * `batch` – nové dáta šarže
+///
+/// # Returns
+/// HTTP status kód výsledku
+async fn update_batch(
+    State(db): State<StoreDB>,
+    Path((id, batch_id)): Path<(u32, u32)>,
+    Json(mut batch): Json<ProductBatch>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    batch.id = Some(batch_id);
+    match db.update_batch(id, &batch).await {
+        Ok(true) => Ok(StatusCode::OK),
+        Ok(false) => Ok(StatusCode::NOT_FOUND),
+        Err(e) => Err(store_error("Chyba pri úprave šarže")(e)),
+    }
+}
+
+/// Vráti zloženie sady a počet sád zostaviteľných zo zásob.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID sady
+///
+/// # Returns
+/// Sada alebo `404 Not Found`, ak produkt neexistuje alebo nie je sada
+async fn get_bundle(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Bundle>, (StatusCode, String)> {
+    match db.get_bundle(id).await {
+        Ok(Some(bundle)) => Ok(Json(bundle)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, format!("sada {id} neexistuje"))),
+        Err(e) => Err(store_error("Chyba pri načítaní sady")(e)),
+    }
+}
+
+/// Nastaví zloženie sady (prázdny zoznam sadu zruší).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `components` – zložky a ich množstvá v jednej sade
+///
+/// # Returns
+/// Nové zloženie sady
+///
+/// # Errors
+/// 404 pri neznámom produkte alebo zložke, 400 pri neplatnej zložke,
+/// 409 ak má produkt vlastné zásoby alebo je sám zložkou inej sady
+async fn set_bundle_components(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(components): Json<Vec<BundleComponent>>,
+) -> Result<Json<Option<Bundle>>, (StatusCode, String)> {
+    let context = "Chyba pri nastavení zloženia sady";
+    if !db.set_bundle_components(id, &components).await.map_err(store_error(context))? {
+        return Err((StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")));
+    }
+    db.get_bundle(id).await.map(Json).map_err(store_error(context))
+}
+
+/// Vráti limit množstva tovaru v kategórii a jej aktuálny stav.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `name` – názov kategórie
+///
+/// # Returns
+/// Limit (`null` = bez limitu) a súčet množstiev produktov kategórie
+async fn get_category_limit(
+    State(db): State<StoreDB>,
+    Path(name): Path<String>,
+) -> Result<Json<CategoryLimit>, (StatusCode, String)> {
+    db.get_category_limit(&name)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní limitu kategórie"))
+}
+
+/// Nastaví alebo zruší (`null`) limit množstva tovaru v kategórii.
+///
+/// Naskladnenie, ktoré by limit prekročilo, sa odmietne s `409 Conflict`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `name` – názov kategórie
+/// * `request` – nový limit
+///
+/// # Returns
+/// Limit a aktuálny stav kategórie
+///
+/// # Errors
+/// 400 pri zápornom limite
+async fn set_category_limit(
+    State(db): State<StoreDB>,
+    Path(name): Path<String>,
+    Json(request): Json<CategoryLimitRequest>,
+) -> Result<Json<CategoryLimit>, (StatusCode, String)> {
+    db.set_category_limit(&name, request.max_total_quantity)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri nastavení limitu kategórie"))
+}
+
+/// Uloží pomenovaný filter produktov alebo zamestnancov.
+///
+/// Filter sa overí podľa aktuálnej schémy filtra; neznáme polia sa odmietnu
+/// bez ohľadu na `strict_json`, aby sa preklep neuložil ako filter, ktorý nič nefiltruje.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – prihlásený používateľ (vlastník vyhľadávania)
+/// * `search` – názov, druh záznamov (`products`, `employees`) a filter
+///
+/// # Returns
+/// `201 Created` a uložené vyhľadávanie
+///
+/// # Errors
+/// 422 s `JsonBodyError` pri neplatnom filtri, 400 pri prázdnom názve,
+/// 409 ak vyhľadávanie s rovnakým názvom a druhom už existuje
+async fn add_saved_search(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Json(search): Json<SavedSearch>,
+) -> Result<(StatusCode, Json<SavedSearch>), Response> {
+    if let Some(e) = saved_filter_error(&search) {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, Json(*e)).into_response());
+    }
+    let saved = db
+        .add_saved_search(&search, actor(&user))
+        .await
+        .map_err(|e| store_error("Chyba pri ukladaní vyhľadávania")(e).into_response())?;
+    Ok((StatusCode::CREATED, Json(saved)))
+}
+
+/// Vráti uložené vyhľadávania.
+///
+/// Vyhľadávania, ktorých filtru aktuálna verzia nerozumie (napr. po odstránení
+/// poľa), majú v `stale` popis chyby; spustiť sa nedajú.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?entity=products|employees`
+///
+/// # Returns
+/// Vyhľadávania zoradené podľa druhu a názvu
+async fn list_saved_searches(
+    State(db): State<StoreDB>,
+    Query(query): Query<SavedSearchQuery>,
+) -> Result<Json<Vec<SavedSearch>>, (StatusCode, String)> {
+    let mut searches = db
+        .list_saved_searches(query.entity)
+        .await
+        .map_err(store_error("Chyba pri načítaní vyhľadávaní"))?;
+    for search in &mut searches {
+        search.stale = saved_filter_error(search).map(|e| e.message);
+    }
+    Ok(Json(searches))
+}
+
+/// Odstráni uložené vyhľadávanie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID vyhľadávania
+///
+/// # Returns
+/// `204 No Content` alebo `404 Not Found`
+async fn delete_saved_search(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    match db.delete_saved_search(id).await.map_err(store_error("Chyba pri mazaní vyhľadávania"))? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Err((StatusCode::NOT_FOUND, format!("vyhľadávanie {id} neexistuje"))),
+    }
+}
+
+/// Začiatok obdobia zmien (`?ts=2024-06-01T08:00:00.000`).
+#[derive(Debug, Deserialize)]
+struct ModifiedSinceQuery {
+    /// Miestny čas v tvare `updated_at`
+    ts: NaiveDateTime,
+}
+
+/// Vráti produkty zmenené po danom čase, od naposledy zmeneného.
+///
+/// Klient si tak po poslednom načítaní obnoví len zmenené produkty bez úplnej
+/// synchronizácie. Stránkovanie a hlavičky ako pri `GET /products`; vyradené
+/// produkty sa vracajú (okrem `/api/v1`), aby ich klient mohol skryť.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+/// * `version` – verzia API
+/// * `page` – parametre stránkovania
+/// * `query` – čas poslednej kontroly
+/// * `uri` – URI požiadavky (pre hlavičku `Link`)
+///
+/// # Returns
+/// Zmenené produkty
+///
+/// # Errors
+/// 400 pri chýbajúcom alebo nesprávnom `ts`
+async fn products_modified_since(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    version: ApiVersion,
+    Query(page): Query<Pagination>,
+    Query(query): Query<ModifiedSinceQuery>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<(HeaderMap, Json<Vec<Product>>), StatusCode> {
+    let mut filter = Product { updated_after: Some(query.ts), ..Default::default() };
+    let removed = removed_products(version, &mut filter);
+    let max_rows = config.max_result_rows;
+    let limit = page.limit.unwrap_or(max_rows).min(max_rows);
+    let offset = page.offset.unwrap_or(0);
+
+    let include_removed = filter.include_removed;
+    let total = db
+        .count_products(filter, false)
+        .await
+        .map_err(internal_error("Chyba pri načítaní produktov"))?;
+    let products = db
+        .get_products_modified_since(query.ts, include_removed, limit, offset)
+        .await
+        .map_err(internal_error("Chyba pri načítaní produktov"))?;
+
+    let mut headers = page_headers(&uri, &page, limit, total, max_rows);
+    headers.insert(X_REMOVED_PRODUCTS, removed);
+    Ok((headers, Json(products)))
+}
+
+/// Spustí uložené vyhľadávanie produktov ako `GET /products` s jeho filtrom.
+///
+/// Stránkovanie (`offset`, `limit`) a `translations` sa berú z aktuálnej požiadavky.
+///
+/// # Arguments
+/// * `name` – názov uloženého vyhľadávania
+/// * ostatné ako pri `list_products`
+///
+/// # Returns
+/// Nájdené produkty s hlavičkami ako pri `GET /products`
+///
+/// # Errors
+/// 404 pri neznámom vyhľadávaní, 409 ak je jeho filter zastaraný
+#[allow(clippy::too_many_arguments)]
+async fn saved_products(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    State(cache): State<QueryCache>,
+    version: ApiVersion,
+    Query(page): Query<Pagination>,
+    Query(search): Query<TranslationSearch>,
+    Path(name): Path<String>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
+) -> Result<(HeaderMap, Json<Vec<Product>>), (StatusCode, String)> {
+    let filter = saved_filter(&db, SearchEntity::Products, &name).await?;
+    list_products(
+        State(db),
+        State(config),
+        State(cache),
+        version,
+        Query(page),
+        Query(filter),
+        Query(search),
+        OriginalUri(uri),
+        headers,
+    )
+        .await
+        .map_err(|status| (status, String::new()))
+}
+
+/// Spustí uložené vyhľadávanie zamestnancov ako `GET /employees` s jeho filtrom.
+///
+/// # Arguments
+/// * `name` – názov uloženého vyhľadávania
+/// * ostatné ako pri `list_employees`
+///
+/// # Returns
+/// Nájdení zamestnanci s hlavičkami ako pri `GET /employees`
+///
+/// # Errors
+/// 404 pri neznámom vyhľadávaní, 409 ak je jeho filter zastaraný
+async fn saved_employees(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(page): Query<Pagination>,
+    Path(name): Path<String>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<(HeaderMap, Json<Vec<Employee>>), (StatusCode, String)> {
+    let filter = saved_filter(&db, SearchEntity::Employees, &name).await?;
+    list_employees(State(db), State(config), Query(page), Query(filter), OriginalUri(uri))
+        .await
+        .map_err(|status| (status, String::new()))
+}
+
+/// Načíta filter uloženého vyhľadávania.
+///
+/// # Errors
+/// 404 ak vyhľadávanie neexistuje, 409 ak mu aktuálna schéma filtra nerozumie
+async fn saved_filter<T: DeserializeOwned>(
+    db: &StoreDB,
+    entity: SearchEntity,
+    name: &str,
+) -> Result<T, (StatusCode, String)> {
+    let search = db
+        .get_saved_search(entity, name)
+        .await
+        .map_err(store_error("Chyba pri načítaní vyhľadávania"))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("vyhľadávanie {name} neexistuje")))?;
+    json::from_value(&search.filter, true)
+        .map_err(|e| (StatusCode::CONFLICT, format!("vyhľadávanie {name} je zastarané: {}", e.message)))
+}
+
+/// Overí filter uloženého vyhľadávania podľa aktuálnej schémy (neznáme polia sú chybou).
+fn saved_filter_error(search: &SavedSearch) -> Option<Box<JsonBodyError>> {
+    match search.entity {
+        SearchEntity::Products => json::from_value::<Product>(&search.filter, true).err(),
+        SearchEntity::Employees => json::from_value::<Employee>(&search.filter, true).err(),
+    }
+}
+
+/// Vráti obrázky produktu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Obrázky zoradené podľa poradia alebo `404 Not Found`, ak produkt neexistuje
+async fn list_images(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Vec<ProductImage>>, StatusCode> {
+    let context = "Chyba pri načítaní obrázkov";
+    db.get_product(id).await.map_err(internal_error(context))?.ok_or(StatusCode::NOT_FOUND)?;
+    db.list_product_images(id).await.map(Json).map_err(internal_error(context))
+}
+
+/// Pridá produktu obrázky (hromadne, všetky alebo žiadny).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `images` – pridávané obrázky
+///
+/// # Returns
+/// `201 Created` a všetky obrázky produktu
+///
+/// # Errors
+/// 404 ak produkt neexistuje, 400 pri neplatnej adrese alebo viacerých hlavných obrázkoch
+async fn add_images(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(images): Json<Vec<ProductImage>>,
+) -> Result<(StatusCode, Json<Vec<ProductImage>>), (StatusCode, String)> {
+    let context = "Chyba pri pridávaní obrázkov";
+    if !db.add_product_images(id, &images).await.map_err(store_error(context))? {
+        return Err((StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")));
+    }
+    let images = db.list_product_images(id).await.map_err(|e| store_error(context)(e.into()))?;
+    Ok((StatusCode::CREATED, Json(images)))
+}
+
+/// Nastaví hlavný obrázok produktu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `image_id` – ID obrázka
+///
+/// # Returns
+/// Všetky obrázky produktu
+///
+/// # Errors
+/// 404 ak produkt taký obrázok nemá
+async fn set_primary_image(
+    State(db): State<StoreDB>,
+    Path((id, image_id)): Path<(u32, u32)>,
+) -> Result<Json<Vec<ProductImage>>, (StatusCode, String)> {
+    let context = "Chyba pri nastavení hlavného obrázka";
+    if !db.set_primary_image(id, image_id).await.map_err(store_error(context))? {
+        return Err((StatusCode::NOT_FOUND, format!("produkt {id} nemá obrázok {image_id}")));
+    }
+    db.list_product_images(id).await.map(Json).map_err(|e| store_error(context)(e.into()))
+}
+
+/// Vráti štítky produktu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Štítky v abecednom poradí alebo `404 Not Found`, ak produkt neexistuje
+async fn list_tags(State(db): State<StoreDB>, Path(id): Path<u32>) -> Result<Json<Vec<String>>, StatusCode> {
+    let context = "Chyba pri načítaní štítkov";
+    db.get_product(id).await.map_err(internal_error(context))?.ok_or(StatusCode::NOT_FOUND)?;
+    db.list_product_tags(id).await.map(Json).map_err(internal_error(context))
+}
+
+/// Pridá štítok všetkým produktom, ktoré zodpovedajú filtru (v jednej transakcii).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `request` – filter produktov a štítok
+///
+/// # Returns
+/// Štítok a počet označených produktov
+///
+/// # Errors
+/// 400 pri prázdnom štítku
+async fn tag_products_by_filter(
+    State(db): State<StoreDB>,
+    Json(request): Json<TagByFilter>,
+) -> Result<Json<TaggingReport>, (StatusCode, String)> {
+    db.tag_products(request.filter, &request.tag)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri pridávaní štítku produktom"))
+}
+
+/// Vymaže všetky produkty, ktoré zodpovedajú filtru (v jednej transakcii), a zapíše to do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – prihlásený používateľ (pre audit log)
+/// * `request` – filter produktov a potvrdenie prázdneho filtra
+///
+/// # Returns
+/// Počet a ID vymazaných produktov
+///
+/// # Errors
+/// 400 pri prázdnom filtri bez `confirm_all: true`, 409 ak je niektorý produkt
+/// zložkou sady, ktorá sa nemaže
+async fn delete_products_by_filter(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Json(request): Json<DeleteByFilter>,
+) -> Result<Json<DeletionReport>, (StatusCode, String)> {
+    let report = db
+        .delete_products_by_filter(request.filter, request.confirm_all)
+        .await
+        .map_err(store_error("Chyba pri mazaní produktov podľa filtra"))?;
+    let ids = report.ids.iter().map(u32::to_string).collect::<Vec<_>>().join(",");
+    db.record_audit("delete_products_by_filter", Some(format!("deleted={}, ids={ids}", report.deleted)), actor(&user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    Ok(Json(report))
+}
+
+/// Vyradí alebo obnoví všetky produkty, ktoré zodpovedajú filtru (v jednej
+/// transakcii), a zapíše to do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – prihlásený používateľ (pre audit log)
+/// * `request` – filter produktov a nový stav
+///
+/// # Returns
+/// Počet vybraných a ID zmenených produktov
+///
+/// # Errors
+/// 400 pri prázdnom filtri
+async fn set_status_by_filter(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Json(request): Json<StatusByFilter>,
+) -> Result<Json<StatusChangeReport>, (StatusCode, String)> {
+    let report = db
+        .set_status_by_filter(request.filter, request.active)
+        .await
+        .map_err(store_error("Chyba pri zmene stavu produktov podľa filtra"))?;
+    let ids = report.ids.iter().map(u32::to_string).collect::<Vec<_>>().join(",");
+    let detail = format!("active={}, changed={}, ids={ids}", request.active, report.changed);
+    db.record_audit("set_status_by_filter", Some(detail), actor(&user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    Ok(Json(report))
+}
+
+/// Premenuje kategórie produktov podľa mapy `{"stará": "nová", ...}` v jednej
+/// transakcii; do existujúcej kategórie sa produkty zlúčia. Každé premenovanie
+/// sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – prihlásený používateľ (pre audit log)
+/// * `mapping` – pôvodná kategória → nová kategória
+///
+/// # Returns
+/// Počet preradených produktov pre každú položku mapy
+///
+/// # Errors
+/// 400 pri prázdnej mape alebo prázdnom názve kategórie
+async fn remap_categories(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Json(mapping): Json<BTreeMap<String, String>>,
+) -> Result<Json<Vec<CategoryRemap>>, (StatusCode, String)> {
+    let report = db
+        .remap_categories(&mapping)
+        .await
+        .map_err(store_error("Chyba pri premenovaní kategórií"))?;
+    for remap in &report {
+        let detail = format!("from={}, to={}, products={}", remap.from, remap.to, remap.products);
+        db.record_audit("remap_category", Some(detail), actor(&user))
+            .await
+            .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    }
+    Ok(Json(report))
+}
+
+/// Vráti všetky preklady produktu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Preklady zoradené podľa jazyka alebo `404 Not Found`, ak produkt neexistuje
+async fn list_translations(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Vec<ProductTranslation>>, StatusCode> {
+    let context = "Chyba pri načítaní prekladov";
+    db.get_product(id).await.map_err(internal_error(context))?.ok_or(StatusCode::NOT_FOUND)?;
+    db.get_product_translations(Some(id)).await.map(Json).map_err(internal_error(context))
+}
+
+/// Vráti preklad produktu do jazyka.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `lang` – kód jazyka
+///
+/// # Returns
+/// Preklad alebo `404 Not Found`, ak produkt alebo preklad neexistuje
+async fn get_translation(
+    State(db): State<StoreDB>,
+    Path((id, lang)): Path<(u32, String)>,
+) -> Result<Json<ProductTranslation>, StatusCode> {
+    db.get_product_translations(Some(id))
+        .await
+        .map_err(internal_error("Chyba pri načítaní prekladu"))?
+        .into_iter()
+        .find(|t| t.lang.eq_ignore_ascii_case(&lang))
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Nastaví preklad názvu a popisu produktu do jazyka.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `lang` – kód jazyka (`sk`, `hu`, ...)
+/// * `translation` – preložený názov a popis
+///
+/// # Returns
+/// Uložený preklad
+///
+/// # Errors
+/// 404 pri neznámom produkte, 400 pri neplatnom jazyku alebo prázdnom preklade
+async fn set_translation(
+    State(db): State<StoreDB>,
+    Path((id, lang)): Path<(u32, String)>,
+    Json(mut translation): Json<ProductTranslation>,
+) -> Result<Json<ProductTranslation>, (StatusCode, String)> {
+    let context = "Chyba pri ukladaní prekladu";
+    translation.product_id = id;
+    translation.lang = lang.clone();
+    if !db.set_product_translation(&translation).await.map_err(store_error(context))? {
+        return Err((StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")));
+    }
+
+    db.get_product_translations(Some(id))
+        .await
+        .map_err(store_error(context))?
+        .into_iter()
+        .find(|t| t.lang.eq_ignore_ascii_case(&lang))
+        .map(Json)
+        .ok_or((StatusCode::INTERNAL_SERVER_ERROR, String::new()))
+}
+
+/// Znovu aktivuje všetky produkty dodávateľa a voliteľne nastaví ich množstvo.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `req` – dodávateľ a voliteľné nové množstvo
+///
+/// # Returns
+/// Počet aktivovaných produktov
+///
+/// # Errors
+/// 400 pri prázdnom dodávateľovi
+async fn reactivate_by_supplier(
+    State(db): State<StoreDB>,
+    Json(req): Json<SupplierReactivation>,
+) -> Result<Json<u64>, (StatusCode, String)> {
+    if req.supplier.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "dodávateľ nesmie byť prázdny".into()));
+    }
+
+    db.reactivate_by_supplier(&req.supplier, req.quantity)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri aktivácii produktov dodávateľa"))
+}
+
+/// Pridá nový produkt.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (dĺžky textových polí)
+/// * `prod` – dáta produktu
+///
+/// # Returns
+/// HTTP status kód výsledku
+///
+/// # Errors
+/// 422 pri príliš dlhom textovom poli
+async fn add_product(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Json(mut prod): Json<Product>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    prepare_product(&mut prod, &config)?;
+    db.add_product_to_store_db(&prod)
+        .await
+        .map_err(store_error("Chyba pri pridávaní produktu"))?;
+    Ok(StatusCode::CREATED)
+}
+
+/// Vytvorí kópiu produktu (variant) s novým ID, nulovým množstvom a dnešným dátumom pridania.
+///
+/// Telo je nepovinné: polia produktu v ňom (napr. `name`, `sell_price`,
+/// `bar_code`) nahradia hodnoty zdroja. Bez `bar_code` kópia čiarový kód nemá (`0`).
+/// Kópia sa zapíše do audit logu s odkazom na zdrojový produkt.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (dĺžky textových polí)
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `id` – ID kopírovaného produktu
+/// * `body` – voliteľné zmenené polia
+///
+/// # Returns
+/// `201 Created` s vytvoreným produktom
+///
+/// # Errors
+/// 404 ak zdrojový produkt neexistuje, 409 ak čiarový kód už má iný produkt,
+/// 400 pri neplatnom tele alebo sade, 422 pri príliš dlhom textovom poli
+async fn clone_product(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Path(id): Path<u32>,
+    body: Bytes,
+) -> Result<(StatusCode, Json<Product>), (StatusCode, String)> {
+    let mut overrides = if body.iter().all(u8::is_ascii_whitespace) {
+        Product::new_empty()
+    } else {
+        serde_json::from_slice(&body).map_err(|e| (StatusCode::BAD_REQUEST, format!("neplatné telo: {e}")))?
+    };
+    prepare_product(&mut overrides, &config)?;
+
+    let clone_id = db
+        .clone_product(id, &overrides)
+        .await
+        .map_err(store_error("Chyba pri kopírovaní produktu"))?
+        .ok_or((StatusCode::NOT_FOUND, format!("produkt {id} neexistuje")))?;
+    let details = format!("source_id={id}, clone_id={clone_id}");
+    db.record_entity_audit("clone_product", Some(("product", clone_id)), Some(details), actor(&user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+
+    let product = db
+        .get_product(clone_id)
+        .await
+        .map_err(|e| store_error("Chyba pri načítaní produktu")(e.into()))?
+        .ok_or((StatusCode::NOT_FOUND, format!("produkt {clone_id} neexistuje")))?;
+    Ok((StatusCode::CREATED, Json(product)))
+}
+
+/// Vymaže produkt podľa ID.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// HTTP status kód výsledku
+///
+/// # Errors
+/// 409 ak je produkt zložkou sady
+async fn delete_product(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    match db.delete_product(id).await.map_err(store_error("Chyba pri mazaní produktu"))? {
+        true => Ok(StatusCode::NO_CONTENT),
+        false => Ok(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Voľby pri úprave produktu.
+#[derive(Debug, Deserialize, Default)]
+struct ProductUpdateOptions {
+    /// Zaokrúhli novú predajnú cenu podľa pravidla z konfigurácie
+    round_price: Option<bool>,
+}
+
+/// Voľba, ktorá pri zmene cien vynechá kontrolu minimálnej marže.
+#[derive(Debug, Deserialize, Default)]
+struct MarginOverride {
+    /// Ceny sa zapíšu aj pod minimálnou maržou; použitie sa zapíše do audit logu
+    override_margin_check: Option<bool>,
+}
+
+impl MarginOverride {
+    /// Či požiadavka výslovne vynecháva kontrolu marže.
+    fn active(&self) -> bool {
+        self.override_margin_check.unwrap_or(false)
+    }
+
+    /// Politika, podľa ktorej sa majú kontrolovať ceny (`None` pri vynechaní kontroly).
+    fn policy<'a>(&self, config: &'a Config) -> Option<&'a MarginPolicy> {
+        (!self.active()).then_some(&config.margin_policy)
+    }
+
+    /// Zapíše vynechanie kontroly marže do audit logu (len ak bola vynechaná).
+    ///
+    /// # Arguments
+    /// * `db` – databáza (audit log)
+    /// * `user` – volajúci prihlásený tokenom
+    /// * `details` – čo sa zmenilo
+    async fn record(&self, db: &StoreDB, user: &Option<AuthUser>, details: String) -> Result<(), (StatusCode, String)> {
+        if !self.active() {
+            return Ok(());
+        }
+        db.record_audit("margin_override", Some(details), actor(user))
+            .await
+            .map_err(store_error("Chyba pri zápise do audit logu"))
+    }
+}
+
+/// Aktualizuje produkt.
+///
+/// S `?round_price=true` sa nová predajná cena zaokrúhli podľa konfigurácie
+/// a odpoveď obsahuje zadanú aj zaokrúhlenú cenu. Nová predajná cena musí
+/// spĺňať minimálnu maržu, ak požiadavka nemá `?override_margin_check=true`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (pravidlo zaokrúhľovania, minimálna marža)
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `id` – ID produktu
+/// * `options` – voľby úpravy
+/// * `margin` – vynechanie kontroly marže
+/// * `prod` – nové dáta
+///
+/// # Returns
+/// HTTP status kód výsledku, pri zaokrúhlení aj novú cenu
+///
+/// # Errors
+/// 400 ak sa má zaokrúhliť cena, ktorá nie je zadaná, 422 ak je nová cena pod minimálnou maržou
+/// alebo je textové pole príliš dlhé
+async fn update_product(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Path(id): Path<u32>,
+    Query(options): Query<ProductUpdateOptions>,
+    Query(margin): Query<MarginOverride>,
+    Json(mut prod): Json<Product>,
+) -> Result<Response, (StatusCode, String)> {
+    let context = "Chyba pri updatovaní produktu";
+    prod.id = Some(id);
+    prepare_product(&mut prod, &config)?;
+
+    let change = match (options.round_price.unwrap_or(false), prod.sell_price) {
+        (false, _) => None,
+        (true, None) => return Err((StatusCode::BAD_REQUEST, "chýba predajná cena na zaokrúhlenie".into())),
+        (true, Some(raw_price)) => {
+            let current = db.get_product(id).await.map_err(|e| store_error(context)(e.into()))?;
+            let price = config.price_rounding.apply(raw_price);
+            prod.sell_price = Some(price);
+            Some(PriceChange {
+                product_id: id,
+                name: current.as_ref().and_then(|p| p.name.clone()),
+                old_price: current.and_then(|p| p.sell_price),
+                raw_price,
+                price,
+            })
+        }
+    };
+
+    if !db.update_product(&prod, margin.policy(&config)).await.map_err(store_error(context))? {
+        return Ok(StatusCode::NOT_FOUND.into_response());
+    }
+    if let Some(price) = prod.sell_price {
+        margin.record(&db, &user, format!("product_id={id}, sell_price={price}")).await?;
+    }
+    Ok(match change {
+        Some(change) => Json(change).into_response(),
+        None => StatusCode::OK.into_response(),
+    })
+}
+
+/// Vytvorí viacero produktov naraz.
+///
+/// Predvolene platí všetko alebo nič: pri prvej chybnej položke sa nevytvorí nič
+/// a odpoveď má stav tej položky. S `?best_effort=true` sa platné položky vytvoria
+/// (každá vo vlastnom savepointe) a odpoveď je `207 Multi-Status` s výsledkom
+/// každej položky.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (dĺžky textových polí, formát SKU)
+/// * `query` – režim `best_effort`
+/// * `products` – pole produktov
+///
+/// # Returns
+/// `201 Created` (alebo `207 Multi-Status`) s výsledkom každej položky
+///
+/// # Errors
+/// 400 pri prázdnom poli; v prísnom režime stav prvej chybnej položky
+/// (400, 409, 422) so správou začínajúcou jej poradím
+async fn bulk_create_products(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<BulkCreateQuery>,
+    Json(products): Json<Vec<Product>>,
+) -> Result<(StatusCode, Json<Vec<BulkCreateResult>>), (StatusCode, String)> {
+    if products.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "prázdny zoznam produktov".to_string()));
+    }
+    let failed = |index: usize, (status, message): (StatusCode, String)| {
+        if query.best_effort {
+            Ok(BulkCreateResult { index: index as u32, status: status.as_u16(), id: None, error: Some(message) })
+        } else {
+            Err((status, format!("položka {index}: {message}")))
+        }
+    };
+
+    // položky, ktoré neprešli kontrolou, sa do databázy neposielajú
+    let mut results = Vec::with_capacity(products.len());
+    let mut positions = Vec::new();
+    let mut batch = Vec::new();
+    for (index, mut product) in products.into_iter().enumerate() {
+        match prepare_product(&mut product, &config) {
+            Ok(()) => {
+                positions.push(index);
+                batch.push(product);
+            }
+            Err(e) => results.push(failed(index, e)?),
+        }
+    }
+
+    let context = "Chyba pri hromadnom pridávaní produktov";
+    let inserted = db.add_products(&batch, query.best_effort).await.map_err(store_error(context))?;
+    for (index, result) in positions.into_iter().zip(inserted) {
+        results.push(match result {
+            Ok(id) => BulkCreateResult { index: index as u32, status: 201, id: Some(id), error: None },
+            Err(e) => failed(index, store_error(context)(e))?,
+        });
+    }
+    results.sort_by_key(|r| r.index);
+
+    let status = if query.best_effort { StatusCode::MULTI_STATUS } else { StatusCode::CREATED };
+    Ok((status, Json(results)))
+}
+
+/// Nastaví rovnaké polia viacerým produktom naraz jedným `UPDATE` v transakcii.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (dĺžky textových polí)
+/// * `request` – `{"ids": [...], "set": {...}}`, `set` je čiastočný produkt
+///
+/// # Returns
+/// Výsledok (`updated` / `not_found`) pre každé ID
+///
+/// # Errors
+/// 400 pri prázdnom zozname ID alebo zmene, pri zadanom `id`, `is_bundle`
+/// či `quantity` a ak zmena nezodpovedá niektorému produktu (nič sa nezmení),
+/// 422 pri príliš dlhom textovom poli
+async fn bulk_update_products(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Json(mut request): Json<BulkUpdate<Product>>,
+) -> Result<Json<Vec<BulkUpdateResult>>, (StatusCode, String)> {
+    prepare_product(&mut request.set, &config)?;
+    db.bulk_update_products(&request.ids, &request.set)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri hromadnej úprave produktov"))
+}
+
+/// Pravidlo zaokrúhlenia cien (`POST /products/round-prices`).
+#[derive(Debug, Deserialize)]
+struct RoundPricesRequest {
+    /// Pravidlo v tvare konfigurácie (`0.99`, `0.05`, `0.10`, `x9`, `ceil`)
+    rule: String,
+}
+
+/// Voľby zaokrúhlenia cien.
+#[derive(Debug, Deserialize, Default)]
+struct RoundPricesQuery {
+    /// Len náhľad zmien, ceny sa nezapíšu
+    dry_run: Option<bool>,
+}
+
+/// Zaokrúhli predajné ceny všetkých produktov podľa zadaného pravidla.
+///
+/// Ceny sa menia v jednej transakcii; ak by niektorá nová cena nespĺňala
+/// minimálnu maržu, nezmení sa nič (okrem `?override_margin_check=true`).
+/// S `?dry_run=true` sa vráti len náhľad zmien.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (minimálna marža)
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `query` – voľba náhľadu
+/// * `margin` – vynechanie kontroly marže
+/// * `request` – pravidlo zaokrúhlenia
+///
+/// # Returns
+/// Počet zmenených cien a ich pôvodné a nové hodnoty
+///
+/// # Errors
+/// 400 pri neznámom pravidle alebo pravidle `none`, 422 ak by niektorá cena
+/// klesla pod minimálnu maržu
+async fn round_prices(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Query(query): Query<RoundPricesQuery>,
+    Query(margin): Query<MarginOverride>,
+    Json(request): Json<RoundPricesRequest>,
+) -> Result<Json<PriceRoundingReport>, (StatusCode, String)> {
+    let rule: PriceRounding = request.rule.parse().map_err(|e: String| (StatusCode::BAD_REQUEST, e))?;
+    let dry_run = query.dry_run.unwrap_or(false);
+    let changes = db
+        .apply_price_rounding(rule, dry_run, margin.policy(&config))
+        .await
+        .map_err(store_error("Chyba pri zaokrúhľovaní cien"))?;
+    if !dry_run {
+        margin.record(&db, &user, format!("round_prices rule={rule}, products={}", changes.len())).await?;
+    }
+    Ok(Json(PriceRoundingReport {
+        rule: rule.to_string(),
+        dry_run,
+        changed: changes.len() as u64,
+        changes,
+    }))
+}
+
+/// Hromadne upraví predajné ceny o percento a zaokrúhli ich podľa konfigurácie.
+///
+/// Ak by niektorá nová cena nespĺňala minimálnu maržu, nezmení sa nič
+/// (okrem `?override_margin_check=true`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (pravidlo zaokrúhľovania, minimálna marža)
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `margin` – vynechanie kontroly marže
+/// * `adjustment` – percento a voliteľný filter kategórie a dodávateľa
+///
+/// # Returns
+/// Pôvodné, vypočítané a zaokrúhlené ceny upravených produktov
+///
+/// # Errors
+/// 400 pri zmene o -100 % a menej, 422 ak by niektorá cena klesla pod minimálnu maržu
+async fn adjust_prices(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Query(margin): Query<MarginOverride>,
+    Json(adjustment): Json<PriceAdjustment>,
+) -> Result<Json<Vec<PriceChange>>, (StatusCode, String)> {
+    let changes = db
+        .adjust_prices(&adjustment, config.price_rounding, margin.policy(&config))
+        .await
+        .map_err(store_error("Chyba pri úprave cien"))?;
+    let details = format!(
+        "adjust_prices percent={}, category={}, supplier={}, products={}",
+        adjustment.percent,
+        adjustment.category.as_deref().unwrap_or("-"),
+        adjustment.supplier.as_deref().unwrap_or("-"),
+        changes.len()
+    );
+    margin.record(&db, &user, details).await?;
+    Ok(Json(changes))
+}
+
+
+
+/// Voľby pri zápise objednávky.
+#[derive(Debug, Deserialize, Default)]
+struct OrderOptions {
+    /// Povolí spätný zápis do dňa uzavretého Z-reportom
+    override_closed: Option<bool>,
+}
+
+/// Zaeviduje predaj (objednávku) a odpíše kusy zo skladu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `options` – `?override_closed=true` povolí zápis do uzavretého dňa
+/// * `order` – objednávka s položkami
+///
+/// # Returns
+/// `201 Created` s ID objednávky
+///
+/// # Errors
+/// 400 pri prázdnej objednávke, 404 pri neznámom produkte alebo rezervácii,
+/// 409 pri nedostatku voľného tovaru, neplatnej rezervácii alebo uzavretom dni
+async fn add_order(
+    State(db): State<StoreDB>,
+    Query(options): Query<OrderOptions>,
+    Json(order): Json<Order>,
+) -> Result<(StatusCode, Json<u32>), (StatusCode, String)> {
+    db.add_order(&order, options.override_closed.unwrap_or(false))
+        .await
+        .map(|id| (StatusCode::CREATED, Json(id)))
+        .map_err(store_error("Chyba pri pridávaní objednávky"))
+}
+
+/// Vráti objednávky s položkami a vráteniami, voliteľne len zmenené po `updated_after`.
+///
+/// Počet riadkov je obmedzený na `max_result_rows` z konfigurácie; pri zadanom
+/// `limit` odpoveď obsahuje hlavičku `Link` na susedné stránky.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+/// * `page` – parametre stránkovania
+/// * `filter` – filter podľa času zmeny
+/// * `uri` – URI požiadavky (pre hlavičku `Link`)
+///
+/// # Returns
+/// Zoznam objednávok v JSONe
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy
+async fn list_orders(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(page): Query<Pagination>,
+    Query(filter): Query<OrderFilter>,
+    OriginalUri(uri): OriginalUri,
+) -> Result<(HeaderMap, Json<Vec<Order>>), (StatusCode, String)> {
+    let max_rows = config.max_result_rows;
+    let limit = page.limit.unwrap_or(max_rows).min(max_rows);
+
+    let total = db.count_orders(filter).await.map_err(store_error("Chyba pri načítaní objednávok"))?;
+    let orders = db
+        .get_orders_page(filter, limit, page.offset.unwrap_or(0))
+        .await
+        .map_err(store_error("Chyba pri načítaní objednávok"))?;
+
+    Ok((page_headers(&uri, &page, limit, total, max_rows), Json(orders)))
+}
+
+/// Vráti objednávku s položkami a vráteniami.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID objednávky
+///
+/// # Returns
+/// Objednávka alebo `404 Not Found`
+async fn get_order(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Order>, (StatusCode, String)> {
+    match db.get_order(id).await {
+        Ok(Some(order)) => Ok(Json(order)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, format!("objednávka {id} neexistuje"))),
+        Err(e) => Err(store_error("Chyba pri načítaní objednávky")(e)),
+    }
+}
+
+/// Zaeviduje vrátenie tovaru k objednávke.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID objednávky
+/// * `ret` – vracané položky a množstvá
+///
+/// # Returns
+/// `201 Created` s ID dokladu o vrátení
+///
+/// # Errors
+/// 404 ak objednávka neexistuje, 400 ak sa vracia viac, než bolo predané
+async fn add_order_return(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(ret): Json<OrderReturn>,
+) -> Result<(StatusCode, Json<u32>), (StatusCode, String)> {
+    db.add_order_return(id, &ret)
+        .await
+        .map(|id| (StatusCode::CREATED, Json(id)))
+        .map_err(store_error("Chyba pri vrátení tovaru"))
+}
+
+
+
+/// Filter zoznamu rezervácií.
+#[derive(Debug, Deserialize, Default)]
+struct ReservationQuery {
+    product_id: Option<u32>,
+    /// `true` len platné, `false` len ukončené rezervácie
+    active: Option<bool>,
+}
+
+/// Vráti rezervácie od najnovšej.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – voliteľný filter `product_id` a `active`
+///
+/// # Returns
+/// Zoznam rezervácií (expirované majú stav `expired`)
+async fn list_reservations(
+    State(db): State<StoreDB>,
+    Query(query): Query<ReservationQuery>,
+) -> Result<Json<Vec<Reservation>>, (StatusCode, String)> {
+    db.get_reservations(query.product_id, query.active)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní rezervácií"))
+}
+
+/// Zruší rezerváciu a uvoľní tovar na predaj.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID rezervácie
+///
+/// # Returns
+/// Zrušená rezervácia
+///
+/// # Errors
+/// 404 ak rezervácia neexistuje, 409 ak už nie je platná
+async fn cancel_reservation(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Reservation>, (StatusCode, String)> {
+    db.cancel_reservation(id)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zrušení rezervácie"))
+}
+
+/// Zamestnanec, ktorý eviduje vyzdvihnutie rezervácie.
+#[derive(Debug, Deserialize, Default)]
+struct FulfillQuery {
+    employee_id: Option<u32>,
+}
+
+/// Vyzdvihne rezerváciu – zaeviduje predaj rezervovaného tovaru.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID rezervácie
+/// * `query` – `?employee_id=N`
+///
+/// # Returns
+/// Vyzdvihnutá rezervácia s ID objednávky
+///
+/// # Errors
+/// 404 ak rezervácia neexistuje, 409 ak už nie je platná alebo je deň uzavretý
+async fn fulfill_reservation(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(query): Query<FulfillQuery>,
+) -> Result<Json<Reservation>, (StatusCode, String)> {
+    db.fulfill_reservation(id, query.employee_id)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri vyzdvihnutí rezervácie"))
+}
+
+/// Filter zoznamu objednávok u dodávateľov.
+#[derive(Debug, Deserialize, Default)]
+struct PurchaseOrderQuery {
+    supplier: Option<String>,
+    status: Option<PurchaseOrderStatus>,
+}
+
+/// Vráti objednávky u dodávateľov.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – voliteľný filter `supplier` a `status`
+///
+/// # Returns
+/// Zoznam objednávok s položkami
+async fn list_purchase_orders(
+    State(db): State<StoreDB>,
+    Query(query): Query<PurchaseOrderQuery>,
+) -> Result<Json<Vec<PurchaseOrder>>, (StatusCode, String)> {
+    db.get_purchase_orders(query.supplier, query.status)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní objednávok u dodávateľov"))
+}
+
+/// Vytvorí novú objednávku u dodávateľa v stave `draft`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `po` – objednávka s položkami
+///
+/// # Returns
+/// `201 Created` s ID objednávky
+///
+/// # Errors
+/// 400 ak je zadaný iný stav ako `draft`, 404 pri neznámom produkte
+async fn add_purchase_order(
+    State(db): State<StoreDB>,
+    Json(po): Json<PurchaseOrder>,
+) -> Result<(StatusCode, Json<u32>), (StatusCode, String)> {
+    if po.status.is_some_and(|s| s != PurchaseOrderStatus::Draft) {
+        return Err((StatusCode::BAD_REQUEST, "nová objednávka musí byť v stave draft".into()));
+    }
+
+    db.add_purchase_order(&po)
+        .await
+        .map(|id| (StatusCode::CREATED, Json(id)))
+        .map_err(store_error("Chyba pri pridávaní objednávky u dodávateľa"))
+}
+
+/// Vráti objednávku u dodávateľa.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID objednávky
+///
+/// # Returns
+/// Objednávka alebo `404 Not Found`
+async fn get_purchase_order(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<PurchaseOrder>, (StatusCode, String)> {
+    match db.get_purchase_order(id).await {
+        Ok(Some(po)) => Ok(Json(po)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, format!("objednávka u dodávateľa {id} neexistuje"))),
+        Err(e) => Err(store_error("Chyba pri načítaní objednávky u dodávateľa")(e)),
+    }
+}
+
+/// Aktualizuje objednávku u dodávateľa (dodávateľ, termín, stav, položky).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID objednávky
+/// * `po` – nové dáta
+///
+/// # Returns
+/// HTTP status kód výsledku
+///
+/// # Errors
+/// 409 pri nepovolenom prechode stavu alebo zmene položiek mimo `draft`
+async fn update_purchase_order(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Json(mut po): Json<PurchaseOrder>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    po.id = Some(id);
+    match db.update_purchase_order(&po).await {
+        Ok(true) => Ok(StatusCode::OK),
+        Ok(false) => Ok(StatusCode::NOT_FOUND),
+        Err(e) => Err(store_error("Chyba pri updatovaní objednávky u dodávateľa")(e)),
+    }
+}
+
+/// Voľby príjmu tovaru.
+#[derive(Debug, Deserialize, Default)]
+struct ReceiveOptions {
+    /// Povolí prijať viac kusov, než bolo objednané
+    allow_over_delivery: Option<bool>,
+}
+
+/// Zapíše príjem tovaru k objednávke u dodávateľa (aj čiastočný).
+///
+/// Prijaté množstvá sú kumulatívne; opakované odoslanie naskladní len rozdiel.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – `average_cost_on_restock` prepočíta nákupné ceny prijatých produktov,
+///   `block_overstock` odmietne príjem nad `max_stock`
+/// * `id` – ID objednávky
+/// * `options` – `?allow_over_delivery=true` povolí nadodávku
+/// * `receipt` – prijaté množstvá položiek
+///
+/// # Returns
+/// Nový stav objednávky, porovnanie objednaného a prijatého množstva a varovania
+/// o prekročení `max_stock`
+///
+/// # Errors
+/// 404 ak objednávka neexistuje, 409 pri zlom stave, nadodávke alebo zablokovanom
+/// prekročení `max_stock`, 400 pri neplatných položkách
+async fn receive_purchase_order(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<u32>,
+    Query(options): Query<ReceiveOptions>,
+    Json(receipt): Json<PurchaseOrderReceipt>,
+) -> Result<Json<PurchaseOrderReceiptResult>, (StatusCode, String)> {
+    let allow_over_delivery = options.allow_over_delivery.unwrap_or(false);
+    db.receive_purchase_order(
+        id,
+        &receipt,
+        allow_over_delivery,
+        config.average_cost_on_restock,
+        config.block_overstock,
+    )
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri príjme tovaru"))
+}
+
+/// Vráti porovnanie objednaného a prijatého množstva objednávky u dodávateľa.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID objednávky
+///
+/// # Returns
+/// Rozdiely po položkách alebo `404 Not Found`
+async fn purchase_order_discrepancies(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Vec<PurchaseOrderDiscrepancy>>, (StatusCode, String)> {
+    match db.purchase_order_discrepancies(id).await {
+        Ok(Some(lines)) => Ok(Json(lines)),
+        Ok(None) => Err((StatusCode::NOT_FOUND, format!("objednávka u dodávateľa {id} neexistuje"))),
+        Err(e) => Err(store_error("Chyba pri načítaní rozdielov objednávky")(e)),
+    }
+}
+
+/// Vymaže objednávku u dodávateľa.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID objednávky
+///
+/// # Returns
+/// HTTP status kód výsledku
+async fn delete_purchase_order(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> StatusCode {
+    match db.delete_purchase_order(id).await {
+        Ok(true) => StatusCode::NO_CONTENT,
+        Ok(false) => StatusCode::NOT_FOUND,
+        Err(e) => {
+            eprintln!("Chyba pri mazaní objednávky u dodávateľa: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+/// Vráti návrhy na doobjednanie produktov s nízkym stavom zásob.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (hranica nízkeho stavu a cieľový stav)
+///
+/// # Returns
+/// Zoznam návrhov
+async fn reorder_suggestions(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    State(cache): State<QueryCache>,
+) -> Result<Json<Vec<ReorderSuggestion>>, (StatusCode, String)> {
+    let key = (config.low_stock_threshold, config.reorder_target);
+    cache
+        .get_or_load(QueryKind::ReorderSuggestions, &key, || async {
+            db.reorder_suggestions(config.low_stock_threshold, config.reorder_target)
+                .await
+                .map_err(store_error("Chyba pri zostavení návrhov na doobjednanie"))
+        })
+        .await
+        .map(Json)
+}
+
+/// Vráti produkty, ktoré treba doobjednať hneď, od najnaliehavejšieho.
+///
+/// Na rozdiel od `/reports/reorder-suggestions` (spoločná hranica a cieľový
+/// stav) počíta bod doobjednania pre každý produkt z jeho `min_stock`,
+/// dodacej lehoty a priemerného denného predaja (pozri `StoreDB::reorder_list`).
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Návrhy s navrhovaným množstvom a odhadovanou cenou
+async fn reorder_list(State(db): State<StoreDB>) -> Result<Json<Vec<ReorderSuggestion>>, (StatusCode, String)> {
+    db.reorder_list()
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení zoznamu na doobjednanie"))
+}
+
+/// Vráti odhadované výdavky na doobjednanie po kategóriách (súhrn `/products/reorder-list`).
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Kategórie od najväčšej odhadovanej ceny; kategórie bez potreby doobjednania s nulou
+async fn reorder_budget(
+    State(db): State<StoreDB>,
+) -> Result<Json<Vec<CategoryReorderBudget>>, (StatusCode, String)> {
+    let budgets = db
+        .reorder_budget_by_category()
+        .await
+        .map_err(store_error("Chyba pri výpočte rozpočtu na doobjednanie"))?;
+    Ok(Json(budgets.into_iter().map(|(category, budget)| CategoryReorderBudget { category, budget }).collect()))
+}
+
+/// Vráti podklad objednávky u dodávateľa pripravený na odoslanie.
+///
+/// Obsahuje produkty dodávateľa na bode doobjednania alebo pod ním (pozri
+/// `StoreDB::reorder_list`) s navrhovaným množstvom a odhadovanou cenou.
+/// Ak netreba nič objednať (alebo dodávateľ nie je známy), podklad je prázdny.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `supplier` – názov dodávateľa
+///
+/// # Returns
+/// Riadky objednávky a odhadovaná cena spolu
+async fn supplier_order_sheet(
+    State(db): State<StoreDB>,
+    Path(supplier): Path<String>,
+) -> Result<Json<SupplierOrderSheet>, (StatusCode, String)> {
+    db.supplier_order_sheet(&supplier)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení objednávky u dodávateľa"))
+}
+
+/// Formát dokumentu objednávky u dodávateľa.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum DocumentFormat {
+    #[default]
+    Pdf,
+    Csv,
+}
+
+/// Parametre dokumentu objednávky u dodávateľa.
+#[derive(Debug, Deserialize)]
+struct DocumentQuery {
+    /// `pdf` (predvolene) alebo `csv`
+    #[serde(default)]
+    format: DocumentFormat,
+}
+
+/// Vráti objednávku u dodávateľa ako dokument na odoslanie (PDF alebo CSV).
+///
+/// Riadky sú z podkladu `GET /suppliers/{name}/order-sheet`, PDF má v hlavičke
+/// názov obchodu z konfigurácie (`shop_name`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (názov obchodu)
+/// * `supplier` – názov dodávateľa
+/// * `query` – `?format=pdf|csv`
+///
+/// # Returns
+/// Dokument na stiahnutie
+///
+/// # Errors
+/// 422 ak u dodávateľa netreba nič objednať
+async fn supplier_purchase_order(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Path(supplier): Path<String>,
+    Query(query): Query<DocumentQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let context = "Chyba pri zostavení objednávky u dodávateľa";
+    let sheet = db.supplier_order_sheet(&supplier).await.map_err(store_error(context))?;
+    if sheet.lines.is_empty() {
+        return Err((StatusCode::UNPROCESSABLE_ENTITY, format!("u dodávateľa {supplier} netreba nič objednať")));
+    }
+
+    let date = Local::now().date_naive();
+    let slug: String = supplier.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    match query.format {
+        DocumentFormat::Pdf => {
+            let body = pdf::purchase_order(&sheet, &config.shop_name, date).map_err(store_error(context))?;
+            Ok(pdf_response(&format!("purchase-order-{slug}-{date}.pdf"), body))
+        }
+        DocumentFormat::Csv => {
+            let disposition = format!("attachment; filename=\"purchase-order-{slug}-{date}.csv\"");
+            let body = Table::purchase_order(&sheet.lines).to_csv();
+            Ok(([(header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()), (header::CONTENT_DISPOSITION, disposition)], body)
+                .into_response())
+        }
+    }
+}
+
+/// Parametre našepkávača rôznych hodnôt.
+#[derive(Debug, Deserialize)]
+struct MetaQuery {
+    /// Začiatok hľadanej hodnoty
+    prefix: Option<String>,
+    /// Maximálny počet hodnôt (najviac `max_result_rows`)
+    limit: Option<u32>,
+}
+
+impl MetaQuery {
+    fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref().filter(|p| !p.is_empty())
+    }
+
+    fn limit(&self, config: &Config) -> u32 {
+        self.limit.unwrap_or(config.max_result_rows).min(config.max_result_rows)
+    }
+}
+
+/// Vráti rôzne značky produktov, voliteľne začínajúce na `prefix`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (maximálny počet riadkov)
+/// * `query` – `?prefix=so&limit=10`
+///
+/// # Returns
+/// Abecedne zoradené značky
+async fn meta_brands(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<MetaQuery>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    db.distinct_brands(query.prefix(), query.limit(&config))
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní značiek"))
+}
+
+/// Vráti rôzne kategórie produktov, voliteľne začínajúce na `prefix`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (maximálny počet riadkov)
+/// * `query` – `?prefix=so&limit=10`
+///
+/// # Returns
+/// Abecedne zoradené kategórie
+async fn meta_categories(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<MetaQuery>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    db.distinct_categories(query.prefix(), query.limit(&config))
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní kategórií"))
+}
+
+/// Vráti rôznych dodávateľov produktov, voliteľne začínajúcich na `prefix`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (maximálny počet riadkov)
+/// * `query` – `?prefix=so&limit=10`
+///
+/// # Returns
+/// Abecedne zoradení dodávatelia
+async fn meta_suppliers(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<MetaQuery>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    db.distinct_suppliers(query.prefix(), query.limit(&config))
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní dodávateľov"))
+}
+
+/// Výstupný formát zostáv.
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ReportFormat {
+    #[default]
+    Json,
+    /// Tlačová zostava A4
+    Pdf,
+}
+
+/// Parametre zostáv s voliteľným PDF výstupom.
+#[derive(Debug, Deserialize)]
+struct ReportQuery {
+    /// `json` (predvolene) alebo `pdf`
+    #[serde(default)]
+    format: ReportFormat,
+}
+
+/// Vráti hodnotu zásob aktívnych produktov v nákupných cenách.
+///
+/// S `?format=pdf` vráti tlačovú zostavu na stiahnutie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (názov obchodu v hlavičke PDF)
+/// * `query` – výstupný formát
+///
+/// # Returns
+/// Zostava v JSON alebo PDF
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy alebo zostavenie PDF
+async fn inventory_value_report(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let context = "Chyba pri zostavení hodnoty zásob";
+    let report = db.inventory_value().await.map_err(store_error(context))?;
+    match query.format {
+        ReportFormat::Json => Ok(Json(report).into_response()),
+        ReportFormat::Pdf => {
+            let date = Local::now().date_naive();
+            let body = pdf::inventory_value(&report, &config.shop_name, date).map_err(store_error(context))?;
+            Ok(pdf_response(&format!("inventory-value-{date}.pdf"), body))
+        }
+    }
+}
+
+/// Vráti inventúrny súpis aktívnych produktov ako tlačovú zostavu v PDF.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (názov obchodu v hlavičke PDF)
+///
+/// # Returns
+/// PDF na stiahnutie
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy alebo zostavenie PDF
+async fn inventory_pdf(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<Response, (StatusCode, String)> {
+    let context = "Chyba pri zostavení inventúrneho súpisu";
+    let report = db.inventory_value().await.map_err(store_error(context))?;
+    let date = Local::now().date_naive();
+    let body = pdf::inventory(&report, &config.shop_name, date).map_err(store_error(context))?;
+    Ok(pdf_response(&format!("inventory-{date}.pdf"), body))
+}
+
+/// Vráti súhrn pre nástenku: zásoby, nízky stav, najhodnotnejšie produkty,
+/// počet zamestnancov a posledné pohyby skladu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (hranica nízkeho stavu zásob)
+///
+/// # Returns
+/// Súhrn všetkých častí v jednej odpovedi
+async fn dashboard(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<DashboardSummary>, (StatusCode, String)> {
+    db.dashboard(config.low_stock_threshold)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení nástenky"))
+}
+
+/// Vráti aktívne produkty s nízkym stavom zásob (hranica `low_stock_threshold`).
+///
+/// S `?format=pdf` vráti tlačovú zostavu na stiahnutie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (hranica nízkeho stavu, názov obchodu)
+/// * `query` – výstupný formát
+///
+/// # Returns
+/// Zostava v JSON alebo PDF
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy alebo zostavenie PDF
+async fn low_stock_report(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let context = "Chyba pri zostavení nízkeho stavu zásob";
+    let report = db.low_stock(config.low_stock_threshold).await.map_err(store_error(context))?;
+    match query.format {
+        ReportFormat::Json => Ok(Json(report).into_response()),
+        ReportFormat::Pdf => {
+            let date = Local::now().date_naive();
+            let body = pdf::low_stock(&report, &config.shop_name, date).map_err(store_error(context))?;
+            Ok(pdf_response(&format!("low-stock-{date}.pdf"), body))
+        }
+    }
+}
+
+/// Vráti aktívne produkty, ktorých ceny nespĺňajú minimálnu maržu z konfigurácie.
+///
+/// Zdraženie nákupu sa neblokuje, produkty s takto zníženou maržou sa
+/// objavia v tejto zostave.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (politika minimálnej marže)
+///
+/// # Returns
+/// Produkty od najnižšej marže
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy
+async fn margin_violations(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<Vec<MarginViolation>>, (StatusCode, String)> {
+    db.margin_violations(&config.margin_policy)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení produktov pod minimálnou maržou"))
+}
+
+/// Vráti aktívne produkty s podozrivou predajnou cenou (pravdepodobné chyby pri zadávaní).
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Produkty s dôvodmi (prirážka mimo `MIN_PRICE_MARKUP` – `MAX_PRICE_MARKUP`,
+/// extrémna cena v kategórii), zoradené podľa ID
+async fn price_anomalies(State(db): State<StoreDB>) -> Result<Json<Vec<PriceAnomaly>>, (StatusCode, String)> {
+    db.price_anomalies()
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri hľadaní podozrivých cien"))
+}
+
+/// Parametre zostavy nepredajných zásob.
+#[derive(Debug, Deserialize)]
+struct DeadStockQuery {
+    /// Dĺžka obdobia bez pohybu v dňoch, predvolene 90
+    days: Option<u32>,
+    /// `json` (predvolene) alebo `csv`
+    #[serde(default)]
+    format: CsvFormat,
+}
+
+/// Vráti aktívne produkty na sklade bez predaja a príjmu za posledných `days` dní.
+///
+/// Produkty sú zoradené od najvyššej viazanej hodnoty v nákupných cenách.
+/// S `?format=csv` vráti riadky ako CSV na stiahnutie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – dĺžka obdobia a formát
+///
+/// # Returns
+/// Zostava v JSON alebo CSV
+///
+/// # Errors
+/// 400 pri `days=0`
+async fn dead_stock_report(
+    State(db): State<StoreDB>,
+    Query(query): Query<DeadStockQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let days = query.days.unwrap_or(90);
+    if days == 0 {
+        return Err((StatusCode::BAD_REQUEST, "obdobie musí mať aspoň 1 deň".into()));
+    }
+    let report = db.dead_stock(days).await.map_err(store_error("Chyba pri zostavení nepredajných zásob"))?;
+    match query.format {
+        CsvFormat::Json => Ok(Json(report).into_response()),
+        CsvFormat::Csv => {
+            let disposition = format!("attachment; filename=\"dead-stock-{days}d-{}.csv\"", Local::now().date_naive());
+            let body = Table::dead_stock(&report.lines).to_csv();
+            Ok(([(header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()), (header::CONTENT_DISPOSITION, disposition)], body)
+                .into_response())
+        }
+    }
+}
+
+/// Výstupný formát zostáv s exportom do CSV (dochádzka, nepredajné zásoby).
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum CsvFormat {
+    #[default]
+    Json,
+    /// Riadky zostavy ako CSV (napr. týždne pre mzdový systém)
+    Csv,
+}
+
+/// Parametre zostavy odpracovaných hodín.
+#[derive(Debug, Deserialize)]
+struct HoursQuery {
+    from: NaiveDate,
+    to: NaiveDate,
+    /// Len jeden zamestnanec
+    employee_id: Option<u32>,
+    /// `json` (predvolene) alebo `csv`
+    #[serde(default)]
+    format: CsvFormat,
+}
+
+/// Vráti odpracované hodiny a nadčasy zamestnancov po týždňoch.
+///
+/// Otvorené záznamy dochádzky sa nezapočítajú (varovanie v `warnings`),
+/// zabudnuté odchody sú v `missing_clock_outs`. S `?format=csv` vráti len
+/// týždne ako CSV na stiahnutie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (týždenná norma hodín)
+/// * `query` – obdobie, voliteľný zamestnanec a formát
+///
+/// # Returns
+/// Zostava v JSON alebo CSV
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn hours_report(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<HoursQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    validate_range(&DateRange { from: query.from, to: query.to })?;
+    let report = db
+        .hours_report(query.from, query.to, query.employee_id, config.weekly_hours_norm)
+        .await
+        .map_err(store_error("Chyba pri zostavení dochádzky"))?;
+    match query.format {
+        CsvFormat::Json => Ok(Json(report).into_response()),
+        CsvFormat::Csv => {
+            let disposition = format!("attachment; filename=\"hours-{}-{}.csv\"", query.from, query.to);
+            let body = Table::weekly_hours(&report.weeks).to_csv();
+            Ok(([(header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()), (header::CONTENT_DISPOSITION, disposition)], body)
+                .into_response())
+        }
+    }
+}
+
+/// Odpoveď s PDF súborom na stiahnutie.
+fn pdf_response(filename: &str, body: Vec<u8>) -> Response {
+    let disposition = format!("attachment; filename=\"{filename}\"");
+    (
+        [(header::CONTENT_TYPE, "application/pdf".to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        body,
+    )
+        .into_response()
+}
+
+/// Vytvorí z návrhov na doobjednanie jednu `draft` objednávku pre každého dodávateľa.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (hranica nízkeho stavu a cieľový stav)
+///
+/// # Returns
+/// `201 Created` so zoznamom vytvorených objednávok
+async fn purchase_orders_from_suggestions(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<(StatusCode, Json<Vec<PurchaseOrder>>), (StatusCode, String)> {
+    let suggestions = db
+        .reorder_suggestions(config.low_stock_threshold, config.reorder_target)
+        .await
+        .map_err(store_error("Chyba pri zostavení návrhov na doobjednanie"))?;
+
+    let mut by_supplier: BTreeMap<Option<String>, Vec<PurchaseOrderItem>> = BTreeMap::new();
+    for s in suggestions {
+        by_supplier.entry(s.supplier).or_default().push(PurchaseOrderItem {
+            product_id: s.product_id,
+            quantity: s.suggested_quantity,
+            unit_cost: s.unit_cost,
+            received_quantity: None,
+        });
+    }
+
+    let mut created = Vec::new();
+    for (supplier, items) in by_supplier {
+        let po = PurchaseOrder {
+            id: None,
+            supplier,
+            status: Some(PurchaseOrderStatus::Draft),
+            created_at: None,
+            expected_date: None,
+            items: Some(items),
+        };
+        let id = db
+            .add_purchase_order(&po)
+            .await
+            .map_err(store_error("Chyba pri vytváraní objednávky u dodávateľa"))?;
+        if let Some(po) = db
+            .get_purchase_order(id)
+            .await
+            .map_err(store_error("Chyba pri načítaní objednávky u dodávateľa"))?
+        {
+            created.push(po);
+        }
+    }
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+
+
+/// Parametre dennej uzávierky.
+#[derive(Debug, Deserialize, Default)]
+struct DailyReportQuery {
+    /// Deň uzávierky, predvolene dnes
+    date: Option<NaiveDate>,
+    /// Ak je `true`, deň sa zároveň uzavrie
+    close: Option<bool>,
+}
+
+/// Vráti dennú uzávierku (Z-report).
+///
+/// S `?close=true` deň zároveň uzavrie, takže ďalšie spätné predaje
+/// do tohto dňa budú odmietnuté (ak nie je zadané `override_closed`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – deň uzávierky a voľba uzavretia
+///
+/// # Returns
+/// Súhrn dňa
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy
+async fn daily_report(
+    State(db): State<StoreDB>,
+    State(cache): State<QueryCache>,
+    Query(query): Query<DailyReportQuery>,
+) -> Result<Json<DailyReport>, (StatusCode, String)> {
+    let date = query.date.unwrap_or_else(|| Local::now().date_naive());
+
+    if query.close.unwrap_or(false) {
+        db.close_day(date)
+            .await
+            .map_err(store_error("Chyba pri uzatváraní dňa"))?;
+    }
+
+    cache
+        .get_or_load(QueryKind::DailyReport, &date, || async {
+            db.daily_report(date)
+                .await
+                .map_err(store_error("Chyba pri zostavení dennej uzávierky"))
+        })
+        .await
+        .map(Json)
+}
+
+/// Parametre prehľadu expirujúcich šarží.
+#[derive(Debug, Deserialize)]
+struct ExpiringQuery {
+    /// Počet dní dopredu, predvolene 7
+    days: Option<u32>,
+}
+
+/// Vráti šarže, ktoré expirujú do `days` dní (vrátane už expirovaných).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?days=N`
+///
+/// # Returns
+/// Zoznam šarží zoradených podľa expirácie
+async fn expiring_batches(
+    State(db): State<StoreDB>,
+    Query(query): Query<ExpiringQuery>,
+) -> Result<Json<Vec<ExpiringBatch>>, (StatusCode, String)> {
+    db.expiring_batches(query.days.unwrap_or(7))
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri načítaní expirujúcich šarží"))
+}
+
+/// Overí, že obdobie nezačína po svojom konci.
+///
+/// # Arguments
+/// * `range` – obdobie od–do
+///
+/// # Errors
+/// `400 Bad Request` ak `from` je po `to`
+fn validate_range(range: &DateRange) -> Result<(), (StatusCode, String)> {
+    if range.from > range.to {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("neplatné obdobie: {} je po {}", range.from, range.to),
+        ));
+    }
+    Ok(())
+}
+
+/// Vráti hrubý zisk za obdobie s rozpadom podľa kategórií.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Správa o zisku
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn profit_report(
+    State(db): State<StoreDB>,
+    State(cache): State<QueryCache>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<ProfitReport>, (StatusCode, String)> {
+    validate_range(&range)?;
+    cache
+        .get_or_load(QueryKind::Profit, &range, || async {
+            db.profit_report(range.from, range.to)
+                .await
+                .map_err(store_error("Chyba pri zostavení správy o zisku"))
+        })
+        .await
+        .map(Json)
+}
+
+/// Vráti hrubý zisk z predajov jednotlivých zamestnancov za obdobie (podklad pre provízie).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Zamestnanci od najvyššieho zisku; aktívni bez predaja so ziskom 0
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn profit_by_employee(
+    State(db): State<StoreDB>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<Vec<EmployeeProfit>>, (StatusCode, String)> {
+    validate_range(&range)?;
+    let profits = db
+        .profit_by_employee(range.from, range.to)
+        .await
+        .map_err(store_error("Chyba pri výpočte zisku zamestnancov"))?;
+    Ok(Json(
+        profits
+            .into_iter()
+            .map(|(employee, profit)| EmployeeProfit {
+                employee_id: employee.id.unwrap_or_default(),
+                name: employee.name,
+                surname: employee.surname,
+                position: employee.position,
+                profit,
+            })
+            .collect(),
+    ))
+}
+
+/// Vráti zľavy poskytnuté pri predajoch za obdobie s rozpadom podľa kategórií.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Súčet zliav (bežná mínus účtovaná cena × množstvo) a rozpad podľa kategórií
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn discounts_given(
+    State(db): State<StoreDB>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<DiscountReport>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.discount_given(range.from, range.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte zliav"))
+}
+
+/// Vráti priemernú veľkosť a hodnotu nákupu (objednávky) za obdobie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Priemerný počet položiek, množstvo a hodnota na objednávku; obdobie bez
+/// predajov má nuly a `has_sales = false`
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn basket_stats(
+    State(db): State<StoreDB>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<BasketStats>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.basket_stats(range.from, range.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte priemerného nákupu"))
+}
+
+/// Parametre návrhov produktov kupovaných spolu.
+#[derive(Debug, Deserialize)]
+struct CrossSellQuery {
+    /// Počet produktov, predvolene 10 (najviac `max_result_rows`)
+    limit: Option<u32>,
+}
+
+/// Vráti produkty najčastejšie kupované spolu s produktom (napr. na návrhy k nákupu).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (maximálny počet riadkov)
+/// * `id` – ID produktu
+/// * `query` – `?limit=N`
+///
+/// # Returns
+/// Produkty s podporou a spoľahlivosťou; prázdny zoznam, ak sa produkt
+/// s ničím nekupoval
+///
+/// # Errors
+/// 404 pri neznámom produkte
+async fn cross_sell(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Path(id): Path<u32>,
+    Query(query): Query<CrossSellQuery>,
+) -> Result<Json<Vec<CoPurchase>>, (StatusCode, String)> {
+    let limit = query.limit.unwrap_or(10).min(config.max_result_rows);
+    db.frequently_bought_together(id, limit)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri hľadaní produktov kupovaných spolu"))
+}
+
+/// Vráti hrubý odhad cenovej elasticity dopytu po produkte za obdobie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Cenové body a elasticita; `elasticity` je `null` s vysvetlením v `note`,
+/// ak sa v období predávalo za menej ako dve ceny
+///
+/// # Errors
+/// 400 pri neplatnom období, 404 pri neznámom produkte
+async fn price_elasticity(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<PriceElasticity>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.price_elasticity(id, range.from, range.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri odhade elasticity"))
+}
+
+/// Vráti priemerný predaj produktu v jednotlivých kalendárnych mesiacoch naprieč rokmi.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Priemery mesiacov 1–12; `months` je prázdne s vysvetlením v `note`, ak
+/// história predajov nepokrýva aspoň rok
+///
+/// # Errors
+/// 404 pri neznámom produkte
+async fn product_seasonality(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Seasonality>, (StatusCode, String)> {
+    db.seasonality(id).await.map(Json).map_err(store_error("Chyba pri výpočte sezónnosti"))
+}
+
+/// Parameter sezónnosti kategórie.
+#[derive(Debug, Deserialize)]
+struct SeasonalityQuery {
+    category: String,
+}
+
+/// Vráti sezónnosť predaja všetkých produktov kategórie (ako `/products/{id}/seasonality`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?category=...`
+///
+/// # Returns
+/// Priemery mesiacov 1–12 alebo vysvetlenie v `note`
+async fn category_seasonality(
+    State(db): State<StoreDB>,
+    Query(query): Query<SeasonalityQuery>,
+) -> Result<Json<Seasonality>, (StatusCode, String)> {
+    db.category_seasonality(&query.category)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte sezónnosti"))
+}
+
+/// Vráti obrátku zásob za obdobie (náklady predaného tovaru / priemerná hodnota zásob).
+///
+/// Priemer zásob sa počíta zo snímok štatistík v období, bez nich z aktuálnych zásob.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Obrátka celkom a po kategóriách; `turnover` je `null`, ak nebolo čo obracať
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn inventory_turnover(
+    State(db): State<StoreDB>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<InventoryTurnover>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.inventory_turnover(range.from, range.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte obrátky zásob"))
+}
+
+/// Vráti hrubý výnos z investície do zásob (GMROI) za obdobie.
+///
+/// GMROI = hrubý zisk / priemerná hodnota zásob v nákupných cenách; priemer
+/// zásob sa počíta ako pri `/stats/turnover`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// GMROI celkom a po kategóriách; `gmroi` je `null` s vysvetlením v `note`,
+/// ak boli zásoby nulové
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn gmroi(
+    State(db): State<StoreDB>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<Gmroi>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.gmroi(range.from, range.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte GMROI"))
+}
+
+/// Vráti, na koľko dní vystačia zásoby jednotlivých kategórií pri súčasnom predaji.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Kategórie zoradené od najpomalšie sa predávajúcich; `days` je `null`, ak sa
+/// kategória nepredávala
+async fn days_of_inventory(
+    State(db): State<StoreDB>,
+) -> Result<Json<Vec<CategoryDaysOfInventory>>, (StatusCode, String)> {
+    let days = db
+        .days_of_inventory_by_category()
+        .await
+        .map_err(store_error("Chyba pri výpočte pokrytia kategórií"))?;
+    Ok(Json(
+        days.into_iter()
+            .map(|(category, days)| CategoryDaysOfInventory { category, days: days.is_finite().then_some(days) })
+            .collect(),
+    ))
+}
+
+/// Vráti počet a hodnotu produktov na sklade po vekových skupinách podľa dátumu pridania.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – hranice skupín (`aging_buckets`)
+///
+/// # Returns
+/// Skupiny od najmladšej; produkty bez dátumu pridania sú v skupine `unknown`
+async fn inventory_aging(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<Vec<AgingBucket>>, (StatusCode, String)> {
+    db.inventory_aging(&config.aging_buckets)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte veku zásob"))
+}
+
+/// Vráti straty zásob za obdobie (odpisy, ktoré nie sú predajom) po kategóriách.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Straty v kusoch a v nákupných cenách, predané množstvo a podiel strát
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn shrinkage_report(
+    State(db): State<StoreDB>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<ShrinkageReport>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.shrinkage_report(range.from, range.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri zostavení správy o stratách"))
+}
+
+/// Vráti mieru predaja (predané / prijaté kusy) za obdobie po kategóriách.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Miera predaja celkom a po kategóriách; `rate` je `null`, ak sa nič neprijalo
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn sell_through(
+    State(db): State<StoreDB>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<SellThroughReport>, (StatusCode, String)> {
+    validate_range(&range)?;
+    db.sell_through(range.from, range.to)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte miery predaja"))
+}
+
+/// Vráti hodnotenie dodávateľov (včasnosť dodávok, stálosť cien, dostupnosť tovaru).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – váhy zložiek skóre (`supplier_score_weights`)
+///
+/// # Returns
+/// Dodávatelia zoradení podľa skóre od najlepšieho
+async fn supplier_scores(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<Vec<SupplierScore>>, (StatusCode, String)> {
+    db.supplier_scores(&config.supplier_score_weights)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri hodnotení dodávateľov"))
+}
+
+/// Ocení zásoby podľa nákupných dávok (`?method=fifo`, predvolene `average`).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – metóda oceňovania
+///
+/// # Returns
+/// Ocenenie zásob po produktoch so súčtami
+async fn stock_valuation(
+    State(db): State<StoreDB>,
+    Query(query): Query<ValuationQuery>,
+) -> Result<Json<ValuationReport>, (StatusCode, String)> {
+    db.valuation(query.method.unwrap_or_default())
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri oceňovaní zásob"))
+}
+
+/// Vráti vážený priemer nákupných cien zásoby produktu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID produktu
+///
+/// # Returns
+/// Priemerná cena (`null` bez zásoby alebo ceny) spolu s uloženou `cost_price`
+///
+/// # Errors
+/// 404 ak produkt neexistuje
+async fn average_cost(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<AverageCost>, (StatusCode, String)> {
+    db.average_cost(id)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte priemernej nákupnej ceny"))
+}
+
+/// Vráti rebríček kategórií podľa marže aktívnych produktov.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Kategórie zoradené podľa celkovej marže zostupne
+async fn category_margins(
+    State(db): State<StoreDB>,
+    State(cache): State<QueryCache>,
+) -> Result<Json<Vec<CategoryMargin>>, (StatusCode, String)> {
+    cache
+        .get_or_load(QueryKind::CategoryMargins, &(), || async {
+            db.category_margins()
+                .await
+                .map_err(store_error("Chyba pri výpočte marží kategórií"))
+        })
+        .await
+        .map(Json)
+}
+
+/// Parametre prehľadu platov mimo mediánu pozície.
+#[derive(Debug, Deserialize)]
+struct SalaryOutlierQuery {
+    /// Povolená odchýlka ako podiel mediánu, predvolene `DEFAULT_SALARY_DEVIATION`
+    deviation: Option<f64>,
+}
+
+/// Vráti aktívnych zamestnancov, ktorých plat sa výrazne líši od mediánu ich pozície.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?deviation=0.2` (±20 % od mediánu)
+///
+/// # Returns
+/// Zamestnanci s platom, mediánom pozície a odchýlkou, od najväčšej odchýlky
+///
+/// # Errors
+/// 400 pri zápornej odchýlke
+async fn salary_outliers(
+    State(db): State<StoreDB>,
+    Query(query): Query<SalaryOutlierQuery>,
+) -> Result<Json<Vec<SalaryOutlier>>, (StatusCode, String)> {
+    db.salary_outliers(query.deviation.unwrap_or(DEFAULT_SALARY_DEVIATION))
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri porovnaní platov"))
+}
+
+/// Vráti počet zamestnancov ku koncu každého mesiaca obdobia.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `range` – `?from=YYYY-MM-DD&to=YYYY-MM-DD`
+///
+/// # Returns
+/// Počty zamestnancov po mesiacoch
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn headcount_over_time(
+    State(db): State<StoreDB>,
+    State(cache): State<QueryCache>,
+    Query(range): Query<DateRange>,
+) -> Result<Json<HeadcountReport>, (StatusCode, String)> {
+    validate_range(&range)?;
+    cache
+        .get_or_load(QueryKind::Headcount, &range, || async {
+            db.headcount_over_time(range.from, range.to)
+                .await
+                .map_err(store_error("Chyba pri výpočte počtu zamestnancov"))
+        })
+        .await
+        .map(Json)
+}
+
+/// Vráti mesačné a ročné mzdové náklady aktívnych zamestnancov.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Súčty miezd a počty zahrnutých a vynechaných zamestnancov (pozri `PayrollSummary`)
+async fn total_payroll(State(db): State<StoreDB>) -> Result<Json<PayrollSummary>, (StatusCode, String)> {
+    db.total_payroll()
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte mzdových nákladov"))
+}
+
+/// Vráti mesačné mzdové náklady ako podiel z hodnoty zásob.
+///
+/// Pri nulovej hodnote zásob pomer nie je definovaný: odpoveď má `ratio`
+/// a `percent` `null` a dôvod v `note`.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Pomer a percento (pozri `LaborRatio`)
+async fn labor_ratio(State(db): State<StoreDB>) -> Result<Json<LaborRatio>, (StatusCode, String)> {
+    let ratio = db
+        .labor_to_inventory_ratio()
+        .await
+        .map_err(store_error("Chyba pri výpočte pomeru miezd k zásobám"))?;
+    Ok(Json(LaborRatio {
+        ratio,
+        percent: ratio.map(|r| r * 100.0),
+        note: ratio.is_none().then(|| "hodnota zásob je nulová, pomer nie je definovaný".to_string()),
+    }))
+}
+
+/// Vráti počty aktívnych zamestnancov na zmenách po oddeleniach.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Matica oddelenie × zmena s neobsadenými zmenami v `uncovered`
+async fn shift_coverage(State(db): State<StoreDB>) -> Result<Json<Vec<ShiftCoverage>>, (StatusCode, String)> {
+    db.shift_coverage()
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri výpočte obsadenia zmien"))
+}
+
+/// Filter zoznamu upozornení.
+#[derive(Debug, Deserialize)]
+struct AlertQuery {
+    /// `true` len otvorené, `false` len uzavreté upozornenia
+    open: Option<bool>,
+}
+
+/// Vráti upozornenia na stav zásob od najnovšieho.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (maximálny počet riadkov)
+/// * `query` – `?open=true`
+///
+/// # Returns
+/// Zoznam upozornení
+async fn list_alerts(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<AlertQuery>,
+) -> Result<Json<Vec<Alert>>, (StatusCode, String)> {
+    db.get_alerts(query.open, config.max_result_rows)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri čítaní upozornení"))
+}
+
+/// Ručne uzavrie upozornenie.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `id` – ID upozornenia
+///
+/// # Returns
+/// Uzavreté upozornenie
+///
+/// # Errors
+/// 404, ak upozornenie neexistuje; 409, ak už je uzavreté
+async fn resolve_alert(
+    State(db): State<StoreDB>,
+    Path(id): Path<u32>,
+) -> Result<Json<Alert>, (StatusCode, String)> {
+    db.resolve_alert(id)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri uzavretí upozornenia"))
+}
+
+/// Parametre histórie štatistík.
+#[derive(Debug, Deserialize)]
+struct StatsHistoryQuery {
+    /// Prvý deň obdobia
+    from: Option<NaiveDate>,
+    /// Posledný deň obdobia
+    to: Option<NaiveDate>,
+    /// Maximálny počet najnovších snímok (najviac `max_result_rows`)
+    limit: Option<u32>,
+}
+
+/// Vráti uložené snímky štatistík, z ktorých sa dá sledovať vývoj súhrnov.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia (maximálny počet riadkov)
+/// * `query` – `?from=YYYY-MM-DD&to=YYYY-MM-DD&limit=N`
+///
+/// # Returns
+/// Snímky od najstaršej
+///
+/// # Errors
+/// 400 pri neplatnom období
+async fn stats_history(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    Query(query): Query<StatsHistoryQuery>,
+) -> Result<Json<Vec<StatsSnapshot>>, (StatusCode, String)> {
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        validate_range(&DateRange { from, to })?;
+    }
+    let limit = query.limit.unwrap_or(config.max_result_rows).min(config.max_result_rows);
+    db.stats_history(query.from, query.to, limit)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri čítaní histórie štatistík"))
+}
+
+/// Okamžite vypočíta a uloží snímku štatistík (mimo plánovača).
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// 201 s uloženou snímkou
+async fn take_stats_snapshot(
+    State(db): State<StoreDB>,
+) -> Result<(StatusCode, Json<StatsSnapshot>), (StatusCode, String)> {
+    let snapshot = db.snapshot_stats().await.map_err(store_error("Chyba pri ukladaní snímky štatistík"))?;
+    Ok((StatusCode::CREATED, Json(snapshot)))
+}
+
+/// Parameter snímky zásob.
+#[derive(Debug, Deserialize)]
+struct StockSnapshotQuery {
+    /// Označenie snímky (predvolene čas snímky)
+    label: Option<String>,
+}
+
+/// Uloží aktuálne množstvá všetkých produktov ako snímku zásob.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?label=...`
+///
+/// # Returns
+/// 201 s hlavičkou uloženej snímky
+async fn take_stock_snapshot(
+    State(db): State<StoreDB>,
+    Query(query): Query<StockSnapshotQuery>,
+) -> Result<(StatusCode, Json<StockSnapshot>), (StatusCode, String)> {
+    let snapshot = db
+        .snapshot_stock(query.label.as_deref())
+        .await
+        .map_err(store_error("Chyba pri ukladaní snímky zásob"))?;
+    Ok((StatusCode::CREATED, Json(snapshot)))
+}
+
+/// Parametre porovnania snímok zásob.
+#[derive(Debug, Deserialize)]
+struct StockDeltaQuery {
+    /// ID staršej snímky
+    from_snapshot: u32,
+    /// ID novšej snímky
+    to_snapshot: u32,
+}
+
+/// Vráti zmeny zásob produktov medzi dvoma snímkami.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – `?from_snapshot=ID&to_snapshot=ID`
+///
+/// # Returns
+/// Zmenené produkty s množstvami v oboch snímkach
+///
+/// # Errors
+/// 404, ak niektorá snímka neexistuje
+async fn stock_delta(
+    State(db): State<StoreDB>,
+    Query(query): Query<StockDeltaQuery>,
+) -> Result<Json<StockDeltaReport>, (StatusCode, String)> {
+    db.stock_delta(query.from_snapshot, query.to_snapshot)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri porovnaní snímok zásob"))
+}
+
+/// Vráti náhľad toho, čo by ďalší beh politiky uchovávania osobných údajov
+/// (`retention` v konfigurácii) anonymizoval alebo vymazal; nič nezmení.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (politika uchovávania)
+///
+/// # Returns
+/// Zamestnanci a počty záznamov, ktoré by sa dnes odstránili
+async fn retention_preview(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<RetentionReport>, (StatusCode, String)> {
+    db.purge_personal_data(&config.retention, Local::now().date_naive(), true)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri náhľade politiky uchovávania"))
+}
+
+/// Zruší akcie produktov, ktoré skončili pred dneškom.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Počet produktov so zrušenou akciou
+async fn expire_sales(State(db): State<StoreDB>) -> Result<Json<u64>, (StatusCode, String)> {
+    db.expire_sales(Local::now().date_naive())
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri rušení skončených akcií"))
+}
+
+/// Uplatní naplánované zmeny cien platné od dneška a skôr (inak ich uplatní plánovač).
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Uplatnené zmeny s pôvodnými cenami
+async fn apply_price_changes(State(db): State<StoreDB>) -> Result<Json<Vec<ScheduledPriceChange>>, (StatusCode, String)> {
+    db.apply_due_price_changes(Local::now().date_naive())
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri uplatnení zmien cien"))
+}
+
+/// Skontroluje referenčnú integritu databázy (napr. po importe); nič nezmení.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Odkazy na neexistujúce záznamy zoskupené podľa tabuľky a stĺpca
+async fn integrity_check(State(db): State<StoreDB>) -> Result<Json<IntegrityReport>, (StatusCode, String)> {
+    db.integrity_check()
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri kontrole integrity"))
+}
+
+/// Vynuluje odkazy na neexistujúce záznamy v stĺpcoch, ktoré pripúšťajú `NULL`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – prihlásený používateľ (do audit logu)
+///
+/// # Returns
+/// Nájdené neplatné odkazy a počet vynulovaných; povinné odkazy zostanú nezmenené
+async fn repair_integrity(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+) -> Result<Json<IntegrityReport>, (StatusCode, String)> {
+    let report = db.repair_integrity().await.map_err(store_error("Chyba pri oprave integrity"))?;
+    if report.nulled > 0 {
+        db.record_audit("repair_integrity", Some(format!("nulled={}", report.nulled)), actor(&user))
+            .await
+            .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    }
+    Ok(Json(report))
+}
+
+/// Vráti ID ďalších obchodov servera dostupných pod `/stores/{id}/...`.
+///
+/// # Arguments
+/// * `stores` – ďalšie obchody servera
+///
+/// # Returns
+/// Zoznam ID obchodov (bez hlavného obchodu)
+async fn list_stores(State(stores): State<StoreRegistry>) -> Json<Vec<String>> {
+    Json(stores.ids())
+}
+
+/// Vráti metriky aplikácie vo formáte Prometheus (zásahy a výpadky cache dotazov,
+/// pomalé príkazy, otvorené transakcie).
+///
+/// # Arguments
+/// * `cache` – cache dotazov
+/// * `sessions` – otvorené transakcie
+///
+/// # Returns
+/// Textové metriky s `Content-Type: text/plain; version=0.0.4`
+async fn metrics(State(cache): State<QueryCache>, State(sessions): State<TxSessions>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        cache.render_metrics() + &slow_query::render_metrics() + &sessions.render_metrics(),
+    )
+}
+
+
+
+/// Parametre čistenia vyradených produktov.
+#[derive(Debug, Deserialize)]
+struct PurgeQuery {
+    /// Vymažú sa produkty vyradené pred viac ako `days` dňami
+    days: u32,
+    /// Potvrdenie nevratnej operácie, musí byť `true`
+    confirm: Option<bool>,
+}
+
+/// Natrvalo vymaže produkty vyradené pred viac ako `days` dňami.
+///
+/// Vyžaduje administrátorský API kľúč a `?confirm=true`. Akcia sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `query` – `?days=N&confirm=true`
+///
+/// # Returns
+/// Počet vymazaných produktov
+///
+/// # Errors
+/// 400 bez potvrdenia
+async fn purge_removed(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Query(query): Query<PurgeQuery>,
+) -> Result<Json<u64>, (StatusCode, String)> {
+    if query.confirm != Some(true) {
+        return Err((StatusCode::BAD_REQUEST, "operáciu je potrebné potvrdiť cez ?confirm=true".into()));
+    }
+
+    let cutoff = Local::now().date_naive() - chrono::Days::new(query.days as u64);
+    let purged = db
+        .purge_removed_before(cutoff)
+        .await
+        .map_err(store_error("Chyba pri čistení vyradených produktov"))?;
+
+    db.record_audit("purge_removed", Some(format!("cutoff={cutoff}, deleted={purged}")), actor(&user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+
+    Ok(Json(purged))
+}
+
+/// Parametre NDJSON importu.
+#[derive(Debug, Deserialize)]
+struct ImportQuery {
+    /// Chybné riadky sa preskočia namiesto zastavenia importu
+    lenient: Option<bool>,
+    /// Importovať len `products` alebo `employees`
+    #[serde(default)]
+    only: EntitySelection,
+}
+
+/// Hromadne importuje zamestnancov a produkty z NDJSON (jeden záznam na riadok).
+///
+/// Každý riadok je JSON objekt s poľom `type` (`employee` alebo `product`).
+/// Telo sa spracúva priebežne po dávkach, takže veľkosť súboru neovplyvňuje
+/// spotrebu pamäte. Vyžaduje administrátorský API kľúč; import sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (krajina pre telefóny)
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `query` – `?lenient=true` preskočí chybné riadky, `?only=products` importuje len produkty
+/// * `margin` – vynechanie kontroly marže (produkt pod maržou je inak chybný riadok)
+/// * `body` – NDJSON telo
+///
+/// # Returns
+/// Výsledok importu; `400 Bad Request` s výsledkom, ak sa import v prísnom
+/// režime zastavil na chybnom riadku
+async fn import_ndjson(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Query(query): Query<ImportQuery>,
+    Query(margin): Query<MarginOverride>,
+    body: Body,
+) -> Result<(StatusCode, Json<ImportReport>), (StatusCode, String)> {
+    let context = "Chyba pri importe";
+    let lenient = query.lenient.unwrap_or(false);
+    let report = import::import_ndjson(
+        &db,
+        body,
+        lenient,
+        query.only,
+        &config.phone_country,
+        config.text_limits(),
+        margin.policy(&config),
+    )
+    .await
+    .map_err(store_error(context))?;
+    margin.record(&db, &user, format!("import_ndjson products={}", report.products)).await?;
+
+    db.record_audit(
+        "import_ndjson",
+        Some(format!(
+            "only={}, employees={}, products={}, skipped={}, ignored={}, completed={}",
+            query.only, report.employees, report.products, report.skipped, report.ignored, report.completed
+        )),
+        actor(&user),
+    )
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+
+    let status = if report.completed { StatusCode::OK } else { StatusCode::BAD_REQUEST };
+    Ok((status, Json(report)))
+}
+
+/// Formát súboru nahraného na import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UploadFormat {
+    Json,
+    Ndjson,
+    Csv,
+}
+
+impl UploadFormat {
+    /// Určí formát podľa prípony názvu súboru, bez nej podľa typu obsahu časti formulára.
+    fn detect(file_name: Option<&str>, content_type: Option<&str>) -> Option<Self> {
+        let extension = file_name
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("json") => return Some(Self::Json),
+            Some("ndjson" | "jsonl") => return Some(Self::Ndjson),
+            Some("csv") => return Some(Self::Csv),
+            _ => {}
+        }
+        let mime = content_type?.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/json" => Some(Self::Json),
+            "application/x-ndjson" | "application/jsonl" => Some(Self::Ndjson),
+            "text/csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Prečíta súbor z poľa `file` formulára `multipart/form-data`.
+///
+/// # Returns
+/// Názov súboru, rozpoznaný formát (`None`, ak ho nemožno určiť) a obsah
+///
+/// # Errors
+/// 400 bez poľa `file`, 413 pri prekročení limitu veľkosti tela
+async fn read_upload(
+    multipart: &mut Multipart,
+) -> Result<(Option<String>, Option<UploadFormat>, Bytes), (StatusCode, String)> {
+    let upload_error = |e: MultipartError| (e.status(), e.body_text());
+    let field = loop {
+        match multipart.next_field().await.map_err(upload_error)? {
+            Some(field) if field.name() == Some("file") => break field,
+            Some(_) => continue,
+            None => return Err((StatusCode::BAD_REQUEST, "formulár neobsahuje súbor (pole file)".into())),
+        }
+    };
+    let file_name = field.file_name().map(str::to_string);
+    let format = UploadFormat::detect(file_name.as_deref(), field.content_type());
+    let data = field.bytes().await.map_err(upload_error)?;
+    Ok((file_name, format, data))
+}
+
+/// Importuje súbor nahraný ako `multipart/form-data` (pole `file`).
+///
+/// Formát sa určí podľa prípony (`.json`, `.ndjson`/`.jsonl`, `.csv`), inak podľa
+/// typu obsahu: JSON súbor vo formáte `store_data.json` sa importuje ako
+/// `POST /admin/import`, NDJSON ako `POST /admin/import-ndjson` a CSV s produktmi
+/// po riadkoch rovnakou cestou. Veľkosť tela obmedzuje `max_upload_size`.
+/// Import sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (krajina pre telefóny, dĺžky textov, marža)
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `query` – `?lenient=true` preskočí chybné riadky, `?only=products` importuje len produkty
+/// * `margin` – vynechanie kontroly marže
+/// * `multipart` – formulár so súborom
+///
+/// # Returns
+/// Výsledok importu s formátom súboru; `400 Bad Request` s výsledkom, ak sa
+/// riadkový import v prísnom režime zastavil na chybnom riadku
+///
+/// # Errors
+/// 400 bez poľa `file` alebo pri neplatnom súbore, 413 pri prekročení
+/// `max_upload_size`, 415 pri nerozpoznanom formáte
+async fn import_upload(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Query(query): Query<ImportQuery>,
+    Query(margin): Query<MarginOverride>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<UploadImportReport>), (StatusCode, String)> {
+    let context = "Chyba pri importe nahraného súboru";
+    let (file_name, format, data) = read_upload(&mut multipart).await?;
+    let format = format.ok_or_else(|| {
+        (StatusCode::UNSUPPORTED_MEDIA_TYPE, "podporované sú súbory .json, .ndjson a .csv".to_string())
+    })?;
+
+    let lenient = query.lenient.unwrap_or(false);
+    let report = match format {
+        UploadFormat::Json => UploadImportReport::Json(
+            DBFiller::load_from_slice(&db, &data, query.only, margin.policy(&config), false)
+                .await
+                .map_err(store_error(context))?,
+        ),
+        UploadFormat::Ndjson => UploadImportReport::Ndjson(
+            import::import_ndjson(
+                &db,
+                Body::from(data),
+                lenient,
+                query.only,
+                &config.phone_country,
+                config.text_limits(),
+                margin.policy(&config),
+            )
+            .await
+            .map_err(store_error(context))?,
+        ),
+        UploadFormat::Csv => UploadImportReport::Csv(
+            import::import_csv(&db, &data, lenient, query.only, config.text_limits(), margin.policy(&config))
+                .await
+                .map_err(store_error(context))?,
+        ),
+    };
+
+    let (employees, products, completed) = match &report {
+        UploadImportReport::Json(r) => (r.employees, r.products, true),
+        UploadImportReport::Ndjson(r) | UploadImportReport::Csv(r) => (r.employees, r.products, r.completed),
+    };
+    margin.record(&db, &user, format!("import_upload products={products}")).await?;
+    db.record_audit(
+        "import_upload",
+        Some(format!(
+            "file={}, only={}, employees={employees}, products={products}, completed={completed}",
+            file_name.unwrap_or_default(),
+            query.only
+        )),
+        actor(&user),
+    )
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+
+    let status = if completed { StatusCode::OK } else { StatusCode::BAD_REQUEST };
+    Ok((status, Json(report)))
+}
+
+/// Parametre exportu a importu dát vo formáte `store_data.json`.
+#[derive(Debug, Deserialize)]
+struct DataQuery {
+    /// Len `products` (katalóg bez zamestnancov) alebo `employees`
+    #[serde(default)]
+    only: EntitySelection,
+    /// Export: nahradí osobné údaje zamestnancov pseudonymami
+    #[serde(default)]
+    anonymize: bool,
+    /// Import: povolí anonymizovaný súbor (inak `409`)
+    #[serde(default)]
+    allow_anonymized: bool,
+    /// Export: len zmeny od tohto času (rozdielový export)
+    since: Option<DateTime<Utc>>,
+}
+
+/// Exportuje dáta vo formáte `store_data.json` (napr. na prenos katalógu do inej predajne).
+///
+/// S `?only=products` alebo `?only=employees` obsahuje súbor len vybrané
+/// entity; ostatné polia sú prázdne a uvedené v poli `omitted`. S `?anonymize=true`
+/// sú osobné údaje zamestnancov nahradené pseudonymami a súbor má príznak
+/// `anonymized` (na zdieľanie mimo firmy).
+///
+/// S `?since=2024-06-01T00:00:00Z` vráti rozdielový export: zamestnancov
+/// a produkty zmenené od daného času a ID zmazaných záznamov s poľom `version`
+/// (formát `DBFiller::export_changes_since`). Pole `until` je `since` ďalšieho
+/// rozdielového exportu; premieta ho `POST /admin/import-delta`.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `query` – výber entít, anonymizácia a začiatok rozdielového exportu
+///
+/// # Returns
+/// JSON súbor na stiahnutie
+///
+/// # Errors
+/// 400 pri rozdielovom exporte s `anonymize`
+async fn export_data(
+    State(db): State<StoreDB>,
+    Query(query): Query<DataQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    if let Some(since) = query.since {
+        if query.anonymize {
+            return Err((StatusCode::BAD_REQUEST, "Rozdielový export nemôže byť anonymizovaný".into()));
+        }
+        let body = DBFiller::changes_to_vec(&db, since, query.only)
+            .await
+            .map_err(store_error("Chyba pri exporte dát"))?;
+        let disposition = format!("attachment; filename=\"store_data-delta-{}.json\"", query.only);
+        return Ok((
+            [(header::CONTENT_TYPE, "application/json".to_string()), (header::CONTENT_DISPOSITION, disposition)],
+            body,
+        )
+            .into_response());
+    }
+
+    let body = DBFiller::export_to_vec(&db, query.only, query.anonymize)
+        .await
+        .map_err(store_error("Chyba pri exporte dát"))?;
+    let suffix = if query.anonymize { "-anonymized" } else { "" };
+    let disposition = format!("attachment; filename=\"store_data-{}{suffix}.json\"", query.only);
+    Ok(([(header::CONTENT_TYPE, "application/json".to_string()), (header::CONTENT_DISPOSITION, disposition)], body)
+        .into_response())
+}
+
+/// Importuje dáta vo formáte `store_data.json`; záznamy sa pridajú k existujúcim.
+///
+/// S `?only=` sa nevybrané polia súboru ignorujú, aj keď nie sú prázdne.
+/// Anonymizovaný export sa načíta len s `?allow_anonymized=true`.
+/// Import sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `query` – výber entít a povolenie anonymizovaného súboru
+/// * `margin` – vynechanie kontroly marže importovaných produktov
+/// * `body` – obsah JSON súboru
+///
+/// # Returns
+/// Počty importovaných záznamov a ignorované polia
+///
+/// # Errors
+/// 400 pri neplatnom JSON súbore, 422 ak je niektorý produkt pod minimálnou maržou
+/// (nevloží sa nič), 409 pri anonymizovanom súbore bez `allow_anonymized`
+async fn import_data(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Query(query): Query<DataQuery>,
+    Query(margin): Query<MarginOverride>,
+    body: Bytes,
+) -> Result<Json<DataImportReport>, (StatusCode, String)> {
+    let report = DBFiller::load_from_slice(&db, &body, query.only, margin.policy(&config), query.allow_anonymized)
+        .await
+        .map_err(store_error("Chyba pri importe dát"))?;
+    margin.record(&db, &user, format!("import_data products={}", report.products)).await?;
+
+    db.record_audit(
+        "import_data",
+        Some(format!(
+            "only={}, employees={}, products={}, ignored={}, anonymized={}",
+            query.only,
+            report.employees,
+            report.products,
+            report.ignored.join(","),
+            report.anonymized
+        )),
+        actor(&user),
+    )
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    Ok(Json(report))
+}
+
+/// Premietne rozdielový export inej inštancie (`GET /admin/export?since=`).
+///
+/// Zmenené záznamy sa prepíšu alebo vložia pod ID zo zdroja, zmazané sa zmažú.
+/// Import sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `body` – obsah rozdielového exportu
+///
+/// # Returns
+/// Počty zapísaných a zmazaných záznamov
+///
+/// # Errors
+/// 400 pri neplatnom súbore alebo nepodporovanej verzii formátu
+async fn import_delta(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    body: Bytes,
+) -> Result<Json<DeltaApplyReport>, (StatusCode, String)> {
+    let report = DBFiller::apply_changes_from_slice(&db, &body)
+        .await
+        .map_err(store_error("Chyba pri importe zmien"))?;
+
+    db.record_audit(
+        "import_delta",
+        Some(format!(
+            "employees={}, products={}, deleted_employees={}, deleted_products={}",
+            report.employees, report.products, report.deleted_employees, report.deleted_products
+        )),
+        actor(&user),
+    )
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    Ok(Json(report))
+}
+
+/// Skontroluje čiarové kódy produktov v nahranom súbore pred importom; nič nezapíše.
+///
+/// Súbor sa nahráva ako pri `POST /import/upload` (pole `file`, formát CSV
+/// alebo NDJSON). Pre každý riadok s produktom sa overí kontrolná číslica
+/// EAN-13/UPC/EAN-8, opakovanie kódu v súbore a zhoda s existujúcim produktom.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `multipart` – formulár so súborom
+///
+/// # Returns
+/// Výsledok kontroly každého riadku
+///
+/// # Errors
+/// 400 bez poľa `file` alebo pri CSV bez hlavičky, 413 pri prekročení
+/// `max_upload_size`, 415 pri inom formáte ako CSV a NDJSON
+async fn validate_import_barcodes(
+    State(db): State<StoreDB>,
+    mut multipart: Multipart,
+) -> Result<Json<BarcodeValidationReport>, (StatusCode, String)> {
+    let context = "Chyba pri kontrole čiarových kódov";
+    let rows = match read_upload(&mut multipart).await? {
+        (_, Some(UploadFormat::Csv), data) => import::csv_products(&data).map_err(store_error(context))?,
+        (_, Some(UploadFormat::Ndjson), data) => import::ndjson_products(&data),
+        _ => return Err((StatusCode::UNSUPPORTED_MEDIA_TYPE, "podporované sú súbory .ndjson a .csv".to_string())),
+    };
+    import::validate_barcodes(&db, rows).await.map(Json).map_err(store_error(context))
+}
+
+/// Prečíta nahraný cenník dodávateľa a vypočíta jeho dopad na marže.
+///
+/// # Errors
+/// 400 bez poľa `file` alebo pri CSV bez hlavičky, 413 pri prekročení
+/// `max_upload_size`, 415 pri inom formáte ako CSV a NDJSON
+async fn read_price_list(
+    db: &StoreDB,
+    config: &Config,
+    multipart: &mut Multipart,
+) -> Result<PriceListReport, (StatusCode, String)> {
+    let context = "Chyba pri načítaní cenníka";
+    let rows = match read_upload(multipart).await? {
+        (_, Some(UploadFormat::Csv), data) => import::csv_price_list(&data).map_err(store_error(context))?,
+        (_, Some(UploadFormat::Ndjson), data) => import::ndjson_price_list(&data),
+        _ => return Err((StatusCode::UNSUPPORTED_MEDIA_TYPE, "podporované sú súbory .ndjson a .csv".to_string())),
+    };
+    import::price_list_preview(db, rows, Some(&config.margin_policy)).await.map_err(store_error(context))
+}
+
+/// Ukáže dopad cenníka dodávateľa na nákupné ceny a marže; nič nezapíše.
+///
+/// Súbor sa nahráva ako pri `POST /import/upload` (pole `file`): CSV so stĺpcami
+/// `barcode` (alebo `bar_code`) a `new_cost`, alebo NDJSON s rovnakými poľami.
+/// Riadky sa párujú s produktmi podľa čiarového kódu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (minimálna marža)
+/// * `multipart` – formulár so súborom
+///
+/// # Returns
+/// Stará a nová nákupná cena, predajná cena a zmena marže každého riadku
+///
+/// # Errors
+/// 400 bez poľa `file` alebo pri CSV bez hlavičky, 413 pri prekročení
+/// `max_upload_size`, 415 pri inom formáte ako CSV a NDJSON
+async fn preview_price_list(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    mut multipart: Multipart,
+) -> Result<Json<PriceListReport>, (StatusCode, String)> {
+    read_price_list(&db, &config, &mut multipart).await.map(Json)
+}
+
+/// Zapíše nákupné ceny z cenníka dodávateľa spárovaným produktom.
+///
+/// Súbor a párovanie sú rovnaké ako pri `POST /import/price-list/preview`.
+/// Všetky spárované ceny sa zapíšu v jednej transakcii; nespárované a chybné
+/// riadky sa preskočia. Ak by nová nákupná cena porušila minimálnu maržu,
+/// nezapíše sa nič, pokiaľ požiadavka nemá `?override_margin_check=true`.
+/// Import sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie (minimálna marža)
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `margin` – vynechanie kontroly marže
+/// * `multipart` – formulár so súborom
+///
+/// # Returns
+/// Dopad každého riadku a počet zapísaných cien
+///
+/// # Errors
+/// 400 bez poľa `file` alebo pri CSV bez hlavičky, 413 pri prekročení
+/// `max_upload_size`, 415 pri inom formáte ako CSV a NDJSON, 422 pri
+/// porušení minimálnej marže
+async fn apply_price_list(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+    user: Option<AuthUser>,
+    Query(margin): Query<MarginOverride>,
+    mut multipart: Multipart,
+) -> Result<Json<PriceListReport>, (StatusCode, String)> {
+    let context = "Chyba pri zápise cenníka";
+    let mut report = read_price_list(&db, &config, &mut multipart).await?;
+
+    if !margin.active() {
+        let violations: Vec<String> = report
+            .lines
+            .iter()
+            .filter_map(|l| {
+                let shortfall = l.below_minimum.as_ref()?;
+                Some(format!("{} ({}): {shortfall}", l.name.as_deref().unwrap_or("?"), l.product_id?))
+            })
+            .collect();
+        if !violations.is_empty() {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("{} produktov by kleslo pod minimálnu maržu: {}", violations.len(), violations.join("; ")),
+            ));
+        }
+    }
+
+    let prices: Vec<(u32, f64)> = report.lines.iter().filter_map(|l| Some((l.product_id?, l.new_cost?))).collect();
+    report.applied = db.set_cost_prices(&prices).await.map_err(store_error(context))?;
+
+    db.record_audit(
+        "import_price_list",
+        Some(format!("applied={}, unmatched={}, invalid={}", report.applied, report.unmatched, report.invalid)),
+        actor(&user),
+    )
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    margin.record(&db, &user, format!("import_price_list products={}", report.applied)).await?;
+    Ok(Json(report))
+}
+
+/// Porovná JSON súbor (formát `store_data.json`) s databázou bez zápisu.
+///
+/// Náhľad ukazuje skutočné zmeny dát, ktoré by import spôsobil: pridané,
+/// zmenené (so zmenenými poľami) a zmazané produkty a zamestnancov.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `body` – obsah JSON súboru
+///
+/// # Returns
+/// Rozdiel medzi súborom a databázou
+///
+/// # Errors
+/// 400 pri neplatnom JSONe
+async fn import_diff(State(db): State<StoreDB>, body: Bytes) -> Result<Json<ImportDiff>, (StatusCode, String)> {
+    DBFiller::diff_against_slice(&db, &body)
+        .await
+        .map(Json)
+        .map_err(store_error("Chyba pri porovnaní importu"))
+}
+
+/// Vráti schému databázy ako SQL DDL (tabuľky, indexy, triggery).
+///
+/// Slúži na znovuvytvorenie štruktúry v inej databáze; dáta neobsahuje.
+/// Vyžaduje administrátorský API kľúč.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Príkazy `CREATE` s typom obsahu `application/sql`
+///
+/// # Errors
+/// Ak zlyhá čítanie `sqlite_master`
+async fn schema_sql(State(db): State<StoreDB>) -> Result<Response, (StatusCode, String)> {
+    let schema = db.dump_schema().await.map_err(store_error("Chyba pri výpise schémy"))?;
+    Ok(([(header::CONTENT_TYPE, "application/sql; charset=utf-8")], schema).into_response())
+}
+
+/// Vráti platnú konfiguráciu servera na diagnostiku bez prístupu k serveru.
+///
+/// Obsahuje všetky polia konfigurácie po uplatnení súboru a premenných prostredia
+/// (tajomstvá sú zamaskované, pozri `Config::sanitized`), aktuálny režim len na
+/// čítanie a režim žurnálu databázy. Vyžaduje administrátorský API kľúč.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+///
+/// # Returns
+/// Konfigurácia ako JSON objekt
+///
+/// # Errors
+/// Ak zlyhá zistenie režimu žurnálu
+async fn admin_config(
+    State(db): State<StoreDB>,
+    State(config): State<Arc<Config>>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let journal_mode = db.journal_mode().await.map_err(store_error("Chyba pri načítaní konfigurácie"))?;
+    let mut value = config.sanitized();
+    if let Some(fields) = value.as_object_mut() {
+        fields.insert("read_only".into(), db.is_read_only().into());
+        fields.insert("journal_mode".into(), journal_mode.into());
+    }
+    Ok(Json(value))
+}
+
+/// Vydá zamestnancovi API kľúč; celý kľúč je len v tejto odpovedi, uloží sa hash.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `request` – zamestnanec, rola a voliteľný názov kľúča
+///
+/// # Returns
+/// 201 so záznamom kľúča a celým kľúčom
+///
+/// # Errors
+/// 404 ak zamestnanec neexistuje, 409 ak je vyradený
+async fn create_api_key(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Json(request): Json<ApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), (StatusCode, String)> {
+    let created = db.create_api_key(&request).await.map_err(store_error("Chyba pri vydaní API kľúča"))?;
+    let details = format!("key_id={}, employee_id={}, role={}", created.info.id, request.employee_id, created.info.role);
+    db.record_entity_audit("create_api_key", Some(("api_key", created.info.id)), Some(details), actor(&user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// Vráti API kľúče zamestnancov bez tajomstiev a hashov.
+///
+/// # Arguments
+/// * `db` – databáza
+///
+/// # Returns
+/// Kľúče vrátane odvolaných
+async fn list_api_keys(State(db): State<StoreDB>) -> Result<Json<Vec<ApiKey>>, (StatusCode, String)> {
+    db.list_api_keys().await.map(Json).map_err(store_error("Chyba pri čítaní API kľúčov"))
+}
+
+/// Odvolá API kľúč zamestnanca; ďalšie požiadavky s ním sa odmietnu.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `id` – ID kľúča
+///
+/// # Returns
+/// 204 po odvolaní, 404 ak kľúč neexistuje
+async fn revoke_api_key(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Path(id): Path<u32>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let context = "Chyba pri odvolaní API kľúča";
+    if !db.revoke_api_key(id).await.map_err(store_error(context))? {
+        return Ok(StatusCode::NOT_FOUND);
+    }
+    db.record_entity_audit("revoke_api_key", Some(("api_key", id)), Some(format!("key_id={id}")), actor(&user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Vráti stav režimu údržby.
+///
+/// # Arguments
+/// * `maintenance` – zdieľaný stav údržby
+///
+/// # Returns
+/// `{"maintenance": bool, "retry_after": N}`
+async fn get_maintenance(State(maintenance): State<Maintenance>) -> Json<MaintenanceStatus> {
+    Json(maintenance.status())
+}
+
+/// Zapne alebo vypne režim údržby bez reštartu aplikácie.
+///
+/// Počas údržby vracajú všetky endpointy okrem health checkov `503` s hlavičkou
+/// `Retry-After`. Vyžaduje administrátorský API kľúč; zmena sa zapíše do audit logu.
+///
+/// # Arguments
+/// * `db` – databáza (audit log)
+/// * `maintenance` – zdieľaný stav údržby
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `request` – `{"enabled": bool, "retry_after": N}`
+///
+/// # Returns
+/// Nový stav údržby
+///
+/// # Errors
+/// Ak zlyhá zápis do audit logu
+async fn set_maintenance(
+    State(db): State<StoreDB>,
+    State(maintenance): State<Maintenance>,
+    user: Option<AuthUser>,
+    Json(request): Json<MaintenanceRequest>,
+) -> Result<Json<MaintenanceStatus>, (StatusCode, String)> {
+    maintenance.set(request.enabled, request.retry_after);
+    let status = maintenance.status();
+
+    db.record_audit(
+        "maintenance",
+        Some(format!("enabled={}, retry_after={}", status.maintenance, status.retry_after)),
+        actor(&user),
+    )
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))?;
+
+    Ok(Json(status))
+}
+
+/// Vráti stav režimu len na čítanie.
+///
+/// # Arguments
+/// * `db` – databáza (nesie stav režimu)
+///
+/// # Returns
+/// `{"read_only": bool}`
+async fn get_read_only(State(db): State<StoreDB>) -> Json<ReadOnlyStatus> {
+    Json(ReadOnlyStatus { read_only: db.is_read_only() })
+}
+
+/// Zapne alebo vypne režim len na čítanie bez reštartu aplikácie.
+///
+/// V režime len na čítanie vracajú požiadavky meniace dáta `503`, čítanie, exporty
+/// a health checky fungujú ďalej. Databáza sa zároveň prepne na spojenia s príznakom
+/// read-only. Zmena sa zapíše do audit logu – pri zapínaní ešte pred prepnutím,
+/// nastavenie už platného režimu sa nezapisuje.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `user` – volajúci prihlásený tokenom (audit log)
+/// * `request` – `{"enabled": bool}`
+///
+/// # Returns
+/// Nový stav režimu
+///
+/// # Errors
+/// Ak zlyhá zápis do audit logu
+async fn set_read_only(
+    State(db): State<StoreDB>,
+    user: Option<AuthUser>,
+    Json(request): Json<ReadOnlyRequest>,
+) -> Result<Json<ReadOnlyStatus>, (StatusCode, String)> {
+    if request.enabled != db.is_read_only() {
+        if request.enabled {
+            record_read_only(&db, true, &user).await?;
+            db.set_read_only(true);
+        } else {
+            db.set_read_only(false);
+            record_read_only(&db, false, &user).await?;
+        }
+    }
+    Ok(Json(ReadOnlyStatus { read_only: db.is_read_only() }))
+}
+
+/// Zapíše prepnutie režimu len na čítanie do audit logu.
+async fn record_read_only(db: &StoreDB, enabled: bool, user: &Option<AuthUser>) -> Result<(), (StatusCode, String)> {
+    db.record_audit("read_only", Some(format!("enabled={enabled}")), actor(user))
+        .await
+        .map_err(store_error("Chyba pri zápise do audit logu"))
 }
@@ -0,0 +1,63 @@
+//! Verzie HTTP API.
+//!
+//! Všetky trasy sú dostupné bez predpony (pôvodné správanie) aj pod `/api/v1/...`.
+//! Predpona sa odstráni ešte pred smerovaním, takže obe cesty zdieľajú trasy aj
+//! middleware; handler zistí verziu extraktorom `ApiVersion`. Zmeny predvoleného
+//! správania platia len pre `/api/v1`, pôvodné cesty zostávajú bez zmeny.
+
+use std::convert::Infallible;
+
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{request::Parts, Uri},
+};
+
+/// Predpona ciest verzie 1.
+pub const V1_PREFIX: &str = "/api/v1";
+
+/// Verzia API, cez ktorú prišla požiadavka.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApiVersion {
+    /// Cesty bez predpony
+    #[default]
+    Legacy,
+    /// Cesty pod `/api/v1`
+    V1,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ApiVersion {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts.extensions.get::<ApiVersion>().copied().unwrap_or_default())
+    }
+}
+
+/// Odstráni z cesty predponu `/api/v1` a označí požiadavku verziou `ApiVersion::V1`.
+///
+/// Požiadavky bez predpony (aj napr. `/api/v10`) prejdú bez zmeny.
+///
+/// # Arguments
+/// * `req` – prichádzajúca požiadavka
+///
+/// # Returns
+/// Požiadavka s cestou bez predpony
+pub fn strip_prefix(mut req: Request) -> Request {
+    let Some(rest) = req.uri().path().strip_prefix(V1_PREFIX) else {
+        return req;
+    };
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return req;
+    }
+
+    let path = if rest.is_empty() { "/" } else { rest };
+    let path_and_query = match req.uri().query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    if let Ok(uri) = path_and_query.parse::<Uri>() {
+        *req.uri_mut() = uri;
+        req.extensions_mut().insert(ApiVersion::V1);
+    }
+    req
+}
@@ -0,0 +1,354 @@
+use std::{fmt, sync::Arc};
+
+use anyhow::Context;
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use axum::{
+    extract::{FromRequestParts, OptionalFromRequestParts, Request, State},
+    http::{header, request::Parts, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+
+use crate::{config::Config, db::StoreDB};
+
+/// Hlavička s API kľúčom.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Predpona API kľúčov vydaných zamestnancom (`smk_<identifikátor>_<tajomstvo>`).
+pub const EMPLOYEE_KEY_PREFIX: &str = "smk_";
+
+/// Endpointy volané metódou `POST`, ktoré dáta len čítajú (stačí rola `viewer`).
+pub const READ_ONLY_POSTS: &[&str] = &["/products/search", "/employees/search", "/employees/batch", "/products/export", "/export", "/import/diff", "/import/validate-barcodes", "/import/price-list/preview"];
+
+/// Nároky (claims) JWT, ktoré aplikácia číta.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    /// Subjekt – používateľ alebo služba
+    pub sub: String,
+    /// Rola volajúceho (`viewer`, `editor`, `admin`; bez nej `viewer`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Čas expirácie (Unix sekundy)
+    pub exp: u64,
+}
+
+/// Rola volajúceho; vyššia rola zahŕňa oprávnenia nižších.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Len čítanie
+    Viewer,
+    /// Čítanie, pridávanie a úpravy
+    Editor,
+    /// Všetko vrátane mazania a `/admin/*`
+    Admin,
+}
+
+impl Role {
+    /// Rola z nároku tokenu; neznáma alebo chýbajúca rola je `viewer`.
+    pub fn from_claim(role: Option<&str>) -> Self {
+        match role.map(str::to_ascii_lowercase).as_deref() {
+            Some("admin") => Role::Admin,
+            Some("editor") => Role::Editor,
+            _ => Role::Viewer,
+        }
+    }
+
+    /// Názov roly v nárokoch tokenu a chybových hláseniach.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Editor => "editor",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Volajúci overený platným JWT.
+///
+/// Ako extraktor vráti `401`, ak požiadavka token nemala; `Option<AuthUser>`
+/// vráti `None`. Token overuje middleware `authorize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthUser {
+    /// Subjekt tokenu (`sub`)
+    pub subject: String,
+    /// Rola z nároku `role`
+    pub role: Role,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthUser>()
+            .cloned()
+            .ok_or_else(|| unauthorized("chýba prístupový token"))
+    }
+}
+
+impl<S: Send + Sync> OptionalFromRequestParts<S> for AuthUser {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Option<Self>, Self::Rejection> {
+        Ok(parts.extensions.get::<AuthUser>().cloned())
+    }
+}
+
+/// Overovač JWT (podpis HS256 alebo RS256, expirácia).
+#[derive(Clone)]
+pub struct JwtVerifier {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+impl JwtVerifier {
+    /// Overovač tokenov podpísaných HS256 zdieľaným tajomstvom.
+    ///
+    /// # Arguments
+    /// * `secret` – tajomstvo
+    pub fn hs256(secret: &[u8]) -> Self {
+        Self { key: DecodingKey::from_secret(secret), validation: Validation::new(Algorithm::HS256) }
+    }
+
+    /// Overovač tokenov podpísaných RS256.
+    ///
+    /// # Arguments
+    /// * `pem` – verejný kľúč RSA vo formáte PEM
+    ///
+    /// # Errors
+    /// Ak kľúč nie je platný PEM
+    pub fn rs256(pem: &[u8]) -> anyhow::Result<Self> {
+        let key = DecodingKey::from_rsa_pem(pem).context("neplatný verejný kľúč pre JWT")?;
+        Ok(Self { key, validation: Validation::new(Algorithm::RS256) })
+    }
+
+    /// Vytvorí overovač podľa konfigurácie (`jwt_secret` alebo `jwt_public_key_path`).
+    ///
+    /// # Arguments
+    /// * `config` – konfigurácia aplikácie
+    ///
+    /// # Returns
+    /// `None`, ak prihlasovanie tokenmi nie je zapnuté
+    ///
+    /// # Errors
+    /// Ak sa nepodarí načítať verejný kľúč
+    pub fn from_config(config: &Config) -> anyhow::Result<Option<Self>> {
+        if let Some(secret) = &config.jwt_secret {
+            return Ok(Some(Self::hs256(secret.as_bytes())));
+        }
+        match &config.jwt_public_key_path {
+            Some(path) => {
+                let pem = std::fs::read(path).with_context(|| format!("nepodarilo sa načítať kľúč {path}"))?;
+                Self::rs256(&pem).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Overí podpis a expiráciu tokenu.
+    ///
+    /// # Arguments
+    /// * `token` – JWT bez predpony `Bearer`
+    ///
+    /// # Returns
+    /// Volajúci podľa nárokov `sub` a `role`
+    ///
+    /// # Errors
+    /// Ak je token poškodený, podpísaný iným kľúčom alebo expirovaný
+    pub fn verify(&self, token: &str) -> jsonwebtoken::errors::Result<AuthUser> {
+        let data = jsonwebtoken::decode::<Claims>(token, &self.key, &self.validation)?;
+        Ok(AuthUser { role: Role::from_claim(data.claims.role.as_deref()), subject: data.claims.sub })
+    }
+}
+
+/// Middleware prihlásenia a autorizácie všetkých požiadaviek.
+///
+/// Rola volajúceho sa určí z `Authorization: Bearer <JWT>` (nárok `role`) alebo
+/// z API kľúča v hlavičke `x-api-key` a porovná sa s `required_role`. Overený
+/// volajúci sa uloží do požiadavky (`AuthUser` pri tokene, vždy `Role` – rola,
+/// ktorú smie volajúci uplatniť, napr. v operáciách dávky `POST /batch`).
+///
+/// API kľúč, ktorý nie je v konfigurácii, sa overí voči kľúčom zamestnancov
+/// (`POST /admin/keys`); volajúci je potom `employee:<id>` s rolou kľúča.
+///
+/// Bez prihlásenia smie volajúci čítať; ak nie je nastavený token ani kľúče
+/// `editor`/`viewer`, smie aj zapisovať a mazať (spätná kompatibilita).
+/// `/admin/*` a `/audit/*` vždy vyžaduje administrátorský kľúč alebo token s rolou `admin`.
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie
+/// * `verifier` – overovač JWT (`None` = tokeny sú vypnuté)
+/// * `db` – databáza (kľúče zamestnancov)
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď handlera; `401 Unauthorized` pri neplatnom tokene či kľúči alebo
+/// chýbajúcom prihlásení, `403 Forbidden` pri nedostatočnej role
+pub async fn authorize(
+    State(config): State<Arc<Config>>,
+    State(verifier): State<Option<JwtVerifier>>,
+    State(db): State<StoreDB>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let required = required_role(req.method(), req.uri().path());
+    let admin_path = is_admin_path(req.uri().path());
+    if admin_path && config.admin_api_key.is_none() && verifier.is_none() {
+        return (StatusCode::FORBIDDEN, "administrátorské API je vypnuté").into_response();
+    }
+
+    let role = if let Some(token) = bearer_token(&req) {
+        let Some(verifier) = &verifier else {
+            return unauthorized("prihlasovanie tokenmi nie je zapnuté");
+        };
+        match verifier.verify(token) {
+            Ok(user) => {
+                let role = user.role;
+                req.extensions_mut().insert(user);
+                role
+            }
+            Err(e) => return unauthorized(&format!("neplatný prístupový token: {e}")),
+        }
+    } else if let Some(key) = req.headers().get(API_KEY_HEADER) {
+        let key = key.to_str().unwrap_or_default().to_string();
+        if let Some(role) = api_key_role(&config, &key) {
+            role
+        } else {
+            match db.verify_api_key(&key).await {
+                Ok(Some(api_key)) => {
+                    let user = AuthUser { subject: format!("employee:{}", api_key.employee_id), role: api_key.role };
+                    req.extensions_mut().insert(user);
+                    api_key.role
+                }
+                Ok(None) => return unauthorized("neplatný API kľúč"),
+                Err(e) => {
+                    eprintln!("Chyba pri overení API kľúča: {e}");
+                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                }
+            }
+        }
+    } else {
+        let enforced = verifier.is_some() || config.editor_api_key.is_some() || config.viewer_api_key.is_some();
+        if admin_path {
+            return unauthorized("vyžaduje sa administrátorský API kľúč alebo token");
+        }
+        if enforced && required > Role::Viewer {
+            return unauthorized("zápis vyžaduje prihlásenie");
+        }
+        // bez vynúteného prihlásenia smie neprihlásený všetko okrem `/admin/*` a `/audit/*` (odmietnuté vyššie)
+        if enforced { Role::Viewer } else { Role::Admin }
+    };
+
+    if role < required {
+        return (StatusCode::FORBIDDEN, format!("vyžaduje sa rola {required}, volajúci má rolu {role}")).into_response();
+    }
+    req.extensions_mut().insert(role);
+    next.run(req).await
+}
+
+/// Minimálna rola potrebná pre požiadavku – jediné miesto, kde sa mapujú cesty na roly.
+///
+/// * `viewer` – čítanie (`GET`, `HEAD`, `OPTIONS` a `READ_ONLY_POSTS`)
+/// * `editor` – pridávanie a úpravy (`POST`, `PUT`, `PATCH`)
+/// * `admin` – mazanie (`DELETE`) a všetko pod `/admin/*` a `/audit/*`
+///
+/// # Arguments
+/// * `method` – HTTP metóda
+/// * `path` – cesta požiadavky
+pub fn required_role(method: &Method, path: &str) -> Role {
+    if is_admin_path(path) {
+        return Role::Admin;
+    }
+    match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => Role::Viewer,
+        Method::POST if READ_ONLY_POSTS.contains(&path) => Role::Viewer,
+        Method::DELETE => Role::Admin,
+        _ => Role::Editor,
+    }
+}
+
+/// Cesty administrátorského API (`/admin/*` a export audit logu `/audit/*`).
+fn is_admin_path(path: &str) -> bool {
+    path.starts_with("/admin/") || path.starts_with("/audit/")
+}
+
+/// Rola podľa API kľúča (`None` pri neznámom kľúči).
+fn api_key_role(config: &Config, key: &str) -> Option<Role> {
+    [
+        (&config.admin_api_key, Role::Admin),
+        (&config.editor_api_key, Role::Editor),
+        (&config.viewer_api_key, Role::Viewer),
+    ]
+    .into_iter()
+    .find_map(|(expected, role)| (expected.as_deref() == Some(key)).then_some(role))
+}
+
+/// Vygeneruje nový API kľúč zamestnanca.
+///
+/// # Returns
+/// Verejný identifikátor kľúča (na vyhľadanie v databáze) a celý kľúč
+pub fn generate_api_key() -> (String, String) {
+    let id = format!("{:08x}", rand::random::<u32>());
+    let secret = format!("{:032x}{:032x}", rand::random::<u128>(), rand::random::<u128>());
+    let key = format!("{EMPLOYEE_KEY_PREFIX}{id}_{secret}");
+    (id, key)
+}
+
+/// Identifikátor kľúča zamestnanca (`None`, ak kľúč nemá tvar `smk_<id>_<tajomstvo>`).
+///
+/// # Arguments
+/// * `key` – API kľúč z hlavičky
+pub fn api_key_id(key: &str) -> Option<&str> {
+    let (id, secret) = key.strip_prefix(EMPLOYEE_KEY_PREFIX)?.split_once('_')?;
+    (!id.is_empty() && !secret.is_empty()).then_some(id)
+}
+
+/// Zahashuje API kľúč algoritmom Argon2id s náhodnou soľou.
+///
+/// # Arguments
+/// * `key` – celý API kľúč
+///
+/// # Returns
+/// Hash vo formáte PHC (`$argon2id$...`)
+///
+/// # Errors
+/// Ak hashovanie zlyhá
+pub fn hash_api_key(key: &str) -> anyhow::Result<String> {
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>()).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let hash = Argon2::default().hash_password(key.as_bytes(), &salt).map_err(|e| anyhow::anyhow!("{e}"))?;
+    Ok(hash.to_string())
+}
+
+/// Overí API kľúč voči uloženému hashu (poškodený hash kľúč neoverí).
+///
+/// # Arguments
+/// * `key` – API kľúč z hlavičky
+/// * `hash` – hash vo formáte PHC
+pub fn verify_api_key_hash(key: &str, hash: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|hash| Argon2::default().verify_password(key.as_bytes(), &hash).is_ok())
+}
+
+/// Vráti token z hlavičky `Authorization: Bearer <token>`.
+fn bearer_token(req: &Request) -> Option<&str> {
+    let value = req.headers().get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+}
+
+fn unauthorized(message: &str) -> Response {
+    (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, "Bearer")], message.to_string()).into_response()
+}
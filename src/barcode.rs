@@ -0,0 +1,300 @@
+//! Vykreslenie čiarového kódu EAN-13 / EAN-8 do PNG a SVG (pre tlač etikiet).
+
+/// Predvolená šírka modulu (najužšej čiary) v pixeloch.
+pub const DEFAULT_MODULE_WIDTH: u32 = 2;
+/// Najväčšia povolená šírka modulu v pixeloch.
+pub const MAX_MODULE_WIDTH: u32 = 10;
+/// Predvolená výška čiar v pixeloch.
+pub const DEFAULT_HEIGHT: u32 = 60;
+/// Najmenšia povolená výška čiar v pixeloch.
+pub const MIN_HEIGHT: u32 = 10;
+/// Najväčšia povolená výška čiar v pixeloch.
+pub const MAX_HEIGHT: u32 = 1000;
+
+/// Kódovanie číslic ľavej polovice s nepárnou paritou (sada A); sada C je jeho doplnok.
+const L_CODES: [u8; 10] = [
+    0b0001101, 0b0011001, 0b0010011, 0b0111101, 0b0100011, 0b0110001, 0b0101111, 0b0111011, 0b0110111, 0b0001011,
+];
+/// Kódovanie číslic ľavej polovice s párnou paritou (sada B).
+const G_CODES: [u8; 10] = [
+    0b0100111, 0b0110011, 0b0011011, 0b0100001, 0b0011101, 0b0111001, 0b0000101, 0b0010001, 0b0001001, 0b0010111,
+];
+/// Parita šiestich číslic ľavej polovice EAN-13 podľa prvej číslice (bit 1 = sada B).
+const PARITY: [u8; 10] = [
+    0b000000, 0b001011, 0b001101, 0b001110, 0b010011, 0b011001, 0b011100, 0b010101, 0b010110, 0b011010,
+];
+
+/// Číslice 5×7 pre popis pod kódom (riadky zhora, bit 4 = ľavý stĺpec).
+const DIGIT_FONT: [[u8; 7]; 10] = [
+    [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+    [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+    [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+    [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+    [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+    [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+    [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+];
+
+/// Výška pásu s číslicami pod čiarami (v moduloch): medzera, 7 riadkov písma, medzera.
+const TEXT_MODULES: u32 = 9;
+/// O koľko modulov siahajú ochranné čiary do pásu s číslicami.
+const GUARD_EXTENSION: u32 = 5;
+
+/// Parametre vykreslenia čiarového kódu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Šírka modulu v pixeloch
+    pub module_width: u32,
+    /// Výška čiar v pixeloch (bez pásu s číslicami)
+    pub height: u32,
+    /// Či sa pod kódom vypíšu číslice
+    pub text: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self { module_width: DEFAULT_MODULE_WIDTH, height: DEFAULT_HEIGHT, text: true }
+    }
+}
+
+impl RenderOptions {
+    /// Overí rozsah parametrov.
+    ///
+    /// # Errors
+    /// Popis chyby pri šírke modulu mimo `1..=MAX_MODULE_WIDTH` alebo výške mimo `MIN_HEIGHT..=MAX_HEIGHT`
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=MAX_MODULE_WIDTH).contains(&self.module_width) {
+            return Err(format!("šírka modulu musí byť 1 až {MAX_MODULE_WIDTH} px"));
+        }
+        if !(MIN_HEIGHT..=MAX_HEIGHT).contains(&self.height) {
+            return Err(format!("výška musí byť {MIN_HEIGHT} až {MAX_HEIGHT} px"));
+        }
+        Ok(())
+    }
+}
+
+/// Čiarový kód EAN-13 alebo EAN-8 s overenou kontrolnou číslicou.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ean {
+    digits: Vec<u8>,
+}
+
+/// Modul (najužší pruh) kódu.
+#[derive(Debug, Clone, Copy)]
+struct Module {
+    dark: bool,
+    /// Patrí ochrannému vzoru (čiary sa predlžujú do pásu s číslicami)
+    guard: bool,
+}
+
+impl Ean {
+    /// Prevedie uložený čiarový kód na EAN.
+    ///
+    /// Kódy do 8 číslic sú EAN-8, dlhšie (do 13) EAN-13; úvodné nuly, ktoré
+    /// sa v čísle nezachovajú, sa doplnia.
+    ///
+    /// # Arguments
+    /// * `code` – čiarový kód produktu
+    ///
+    /// # Errors
+    /// Popis chyby pri nekladnom alebo príliš dlhom kóde či nesprávnej kontrolnej číslici
+    pub fn from_code(code: i64) -> Result<Self, String> {
+        if code <= 0 {
+            return Err(format!("čiarový kód {code} nie je kladné číslo"));
+        }
+        let text = code.to_string();
+        let len = match text.len() {
+            0..=8 => 8,
+            9..=13 => 13,
+            _ => return Err(format!("čiarový kód {code} má viac ako 13 číslic")),
+        };
+        let digits: Vec<u8> = format!("{text:0>len$}").bytes().map(|b| b - b'0').collect();
+        let (data, check) = digits.split_at(len - 1);
+        let expected = check_digit(data);
+        if check[0] != expected {
+            return Err(format!(
+                "čiarový kód {} má nesprávnu kontrolnú číslicu {} (očakávaná {expected})",
+                digits.iter().map(|d| char::from(b'0' + d)).collect::<String>(),
+                check[0]
+            ));
+        }
+        Ok(Self { digits })
+    }
+
+    /// Číslice kódu vrátane doplnených úvodných núl.
+    pub fn text(&self) -> String {
+        self.digits.iter().map(|d| char::from(b'0' + d)).collect()
+    }
+
+    /// Či ide o EAN-13 (inak EAN-8).
+    pub fn is_ean13(&self) -> bool {
+        self.digits.len() == 13
+    }
+
+    /// Tichá zóna vľavo a vpravo (v moduloch).
+    fn quiet_zone(&self) -> (u32, u32) {
+        if self.is_ean13() { (11, 7) } else { (7, 7) }
+    }
+
+    /// Moduly kódu bez tichých zón.
+    fn modules(&self) -> Vec<Module> {
+        let mut modules = Vec::with_capacity(95);
+        let mut push = |bits: u8, width: u32, guard: bool| {
+            for i in (0..width).rev() {
+                modules.push(Module { dark: bits >> i & 1 == 1, guard });
+            }
+        };
+        let (left, right, parity) = if self.is_ean13() {
+            (&self.digits[1..7], &self.digits[7..], PARITY[usize::from(self.digits[0])])
+        } else {
+            (&self.digits[..4], &self.digits[4..], 0)
+        };
+
+        push(0b101, 3, true);
+        for (i, &d) in left.iter().enumerate() {
+            let even = parity >> (left.len() - 1 - i) & 1 == 1;
+            push(if even { G_CODES[usize::from(d)] } else { L_CODES[usize::from(d)] }, 7, false);
+        }
+        push(0b01010, 5, true);
+        for &d in right {
+            push(!L_CODES[usize::from(d)] & 0x7f, 7, false);
+        }
+        push(0b101, 3, true);
+        modules
+    }
+
+    /// Číslice popisu s pozíciou začiatku ich 7-modulového poľa (vrátane tichej zóny).
+    fn text_slots(&self) -> Vec<(u8, u32)> {
+        let (quiet, _) = self.quiet_zone();
+        let mut slots = Vec::with_capacity(self.digits.len());
+        let rest = if self.is_ean13() {
+            slots.push((self.digits[0], quiet - 7));
+            &self.digits[1..]
+        } else {
+            &self.digits[..]
+        };
+        let half = rest.len() as u32 / 2;
+        for (i, &d) in rest.iter().enumerate() {
+            let i = i as u32;
+            let x = if i < half { quiet + 3 + 7 * i } else { quiet + 3 + 7 * half + 5 + 7 * (i - half) };
+            slots.push((d, x));
+        }
+        slots
+    }
+
+    /// Rozmery obrázka v pixeloch.
+    fn size(&self, options: &RenderOptions) -> (u32, u32) {
+        let (left, right) = self.quiet_zone();
+        let width = (left + self.modules().len() as u32 + right) * options.module_width;
+        let text = if options.text { TEXT_MODULES * options.module_width } else { 0 };
+        (width, options.height + text)
+    }
+
+    /// Vykreslí kód do odtieňov sivej (riadok po riadku, 0 = čierna, 255 = biela).
+    fn raster(&self, options: &RenderOptions) -> (u32, u32, Vec<u8>) {
+        let (width, height) = self.size(options);
+        let mw = options.module_width;
+        let mut pixels = vec![255u8; (width * height) as usize];
+        let mut fill = |x: u32, y: u32, w: u32, h: u32| {
+            for row in y..(y + h).min(height) {
+                let start = (row * width + x) as usize;
+                pixels[start..start + w as usize].fill(0);
+            }
+        };
+
+        let (quiet, _) = self.quiet_zone();
+        for (i, module) in self.modules().iter().enumerate().filter(|(_, m)| m.dark) {
+            let extension = if options.text && module.guard { GUARD_EXTENSION * mw } else { 0 };
+            fill((quiet + i as u32) * mw, 0, mw, options.height + extension);
+        }
+        if options.text {
+            for (digit, slot) in self.text_slots() {
+                for (row, bits) in DIGIT_FONT[usize::from(digit)].iter().enumerate() {
+                    for col in 0..5 {
+                        if bits >> (4 - col) & 1 == 1 {
+                            fill((slot + 1 + col) * mw, options.height + (1 + row as u32) * mw, mw, mw);
+                        }
+                    }
+                }
+            }
+        }
+        (width, height, pixels)
+    }
+
+    /// Vykreslí kód ako PNG (8-bitové odtiene sivej).
+    ///
+    /// # Errors
+    /// Chyba kódovania PNG
+    pub fn to_png(&self, options: &RenderOptions) -> Result<Vec<u8>, png::EncodingError> {
+        let (width, height, pixels) = self.raster(options);
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        writer.finish()?;
+        Ok(out)
+    }
+
+    /// Vykreslí kód ako SVG (súvislé čiary spojené do jedného obdĺžnika).
+    pub fn to_svg(&self, options: &RenderOptions) -> String {
+        let (width, height) = self.size(options);
+        let mw = options.module_width;
+        let (quiet, _) = self.quiet_zone();
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        svg.push_str(&format!(r##"<rect width="{width}" height="{height}" fill="#fff"/><g fill="#000">"##));
+
+        let modules = self.modules();
+        let mut i = 0;
+        while i < modules.len() {
+            if !modules[i].dark {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < modules.len() && modules[i].dark && modules[i].guard == modules[start].guard {
+                i += 1;
+            }
+            let extension = if options.text && modules[start].guard { GUARD_EXTENSION * mw } else { 0 };
+            svg.push_str(&format!(
+                r#"<rect x="{}" y="0" width="{}" height="{}"/>"#,
+                (quiet + start as u32) * mw,
+                (i - start) as u32 * mw,
+                options.height + extension
+            ));
+        }
+        svg.push_str("</g>");
+
+        if options.text {
+            let font_size = 9 * mw;
+            let baseline = options.height + 8 * mw;
+            svg.push_str(&format!(
+                r##"<g font-family="monospace" font-size="{font_size}" text-anchor="middle" fill="#000">"##
+            ));
+            for (digit, slot) in self.text_slots() {
+                let center = slot as f32 * mw as f32 + 3.5 * mw as f32;
+                svg.push_str(&format!(r#"<text x="{center}" y="{baseline}">{digit}</text>"#));
+            }
+            svg.push_str("</g>");
+        }
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+/// Kontrolná číslica EAN pre číslice bez nej (váhy 3 a 1 sprava).
+fn check_digit(data: &[u8]) -> u8 {
+    let sum: u32 = data
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| u32::from(d) * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
@@ -0,0 +1,104 @@
+//! Dávka operácií vykonaná v jednej transakcii (`POST /batch`).
+//!
+//! Operácie sa vykonajú v poradí v relácii `StoreDB::begin_session`; ak niektorá
+//! zlyhá, celá dávka sa vráti späť. Neskoršia operácia môže odkazovať na výsledok
+//! skoršej reťazcom `"$<index>.<pole>"` v `id` alebo kdekoľvek v tele, napr.
+//! produkt s `"employee_id": "$0.id"` za operáciou `create_employee`.
+//!
+//! Povolené sú len operácie `BatchOp` a najviac `MAX_OPERATIONS` v dávke.
+//! Každá operácia vyžaduje rovnakú rolu ako zodpovedajúce volanie REST API.
+
+use axum::http::Method;
+use serde_json::Value;
+
+use crate::auth::{self, Role};
+use crate::structs::BatchOp;
+
+/// Najväčší počet operácií v jednej dávke.
+pub const MAX_OPERATIONS: usize = 50;
+
+/// Metóda a cesta volania REST API, ktorému operácia zodpovedá.
+///
+/// # Arguments
+/// * `op` – druh operácie
+/// * `id` – ID záznamu (pri `create_*` sa ignoruje)
+pub fn endpoint(op: BatchOp, id: Option<u32>) -> (Method, String) {
+    let id = id.map(|id| id.to_string()).unwrap_or_else(|| "{id}".into());
+    match op {
+        BatchOp::CreateProduct => (Method::POST, "/products".into()),
+        BatchOp::UpdateProduct => (Method::PUT, format!("/products/{id}")),
+        BatchOp::DeleteProduct => (Method::DELETE, format!("/products/{id}")),
+        BatchOp::AdjustStock => (Method::POST, format!("/products/{id}/adjust-stock")),
+        BatchOp::CreateEmployee => (Method::POST, "/employees".into()),
+        BatchOp::UpdateEmployee => (Method::PUT, format!("/employees/{id}")),
+        BatchOp::DeleteEmployee => (Method::DELETE, format!("/employees/{id}")),
+    }
+}
+
+/// Rola potrebná na operáciu (podľa `auth::required_role` jej volania REST API).
+pub fn required_role(op: BatchOp) -> Role {
+    let (method, path) = endpoint(op, None);
+    auth::required_role(&method, &path)
+}
+
+/// Či operácia vytvára nový záznam (nemá `id`).
+pub fn creates(op: BatchOp) -> bool {
+    matches!(op, BatchOp::CreateProduct | BatchOp::CreateEmployee)
+}
+
+/// Nahradí odkazy `"$<index>.<pole>"` hodnotami z výsledkov skorších operácií.
+///
+/// # Arguments
+/// * `value` – `id` alebo telo operácie
+/// * `results` – výsledky už vykonaných operácií v poradí
+///
+/// # Errors
+/// Popis chyby pri odkaze na neexistujúcu (alebo ešte nevykonanú) operáciu či pole
+pub fn resolve(value: &mut Value, results: &[Value]) -> Result<(), String> {
+    match value {
+        Value::String(s) => {
+            if let Some((index, field)) = reference(s) {
+                let step = results
+                    .get(index)
+                    .ok_or_else(|| format!("odkaz {s} smeruje na operáciu, ktorá ešte neprebehla"))?;
+                *value = step
+                    .get(field)
+                    .filter(|v| !v.is_null())
+                    .cloned()
+                    .ok_or_else(|| format!("výsledok operácie {index} nemá pole `{field}`"))?;
+            }
+            Ok(())
+        }
+        Value::Array(items) => items.iter_mut().try_for_each(|v| resolve(v, results)),
+        Value::Object(fields) => fields.values_mut().try_for_each(|v| resolve(v, results)),
+        _ => Ok(()),
+    }
+}
+
+/// Rozloží odkaz `$<index>.<pole>`; iný reťazec nie je odkazom.
+fn reference(s: &str) -> Option<(usize, &str)> {
+    let (index, field) = s.strip_prefix('$')?.split_once('.')?;
+    if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) || field.is_empty() {
+        return None;
+    }
+    Some((index.parse().ok()?, field))
+}
+
+/// Prevedie (už nahradené) `id` operácie na číslo.
+///
+/// # Errors
+/// Popis chyby pri `id` pri vytváraní, chýbajúcom `id` pri úprave či mazaní
+/// alebo `id`, ktoré nie je kladné celé číslo
+pub fn record_id(op: BatchOp, id: Option<&Value>) -> Result<Option<u32>, String> {
+    match (creates(op), id) {
+        (true, None) => Ok(None),
+        (true, Some(_)) => Err("nový záznam nemôže mať zadané `id`".into()),
+        (false, None) => Err("chýba `id` upravovaného záznamu".into()),
+        (false, Some(id)) => id
+            .as_u64()
+            .and_then(|id| u32::try_from(id).ok())
+            .filter(|&id| id > 0)
+            .map(Some)
+            .ok_or_else(|| format!("neplatné `id` {id}")),
+    }
+}
@@ -0,0 +1,242 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use store_manager::client::{ApiClient, Table};
+use store_manager::config::Config;
+use store_manager::db_filler::EntitySelection;
+use store_manager::structs::{Employee, Product, StockAdjustment, Unit};
+
+/// Klient HTTP API skladu pre skripty a prácu v termináli.
+///
+/// Adresa servera sa berie z `--url`, premennej `STORE_URL` alebo z `listen_addr`
+/// v konfigurácii servera; API kľúč z `admin_api_key` (`STORE_ADMIN_API_KEY`).
+#[derive(Parser)]
+#[command(name = "store-cli", version)]
+struct Cli {
+    /// Adresa servera, napr. http://127.0.0.1:8000
+    #[arg(long, global = true)]
+    url: Option<String>,
+    /// Konfiguračný súbor servera (predvolene config.toml, ak existuje)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Formát výpisu
+    #[arg(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Produkty
+    #[command(subcommand)]
+    Products(ProductsCommand),
+    /// Zamestnanci
+    #[command(subcommand)]
+    Employees(EmployeesCommand),
+    /// Uloží dáta skladu do súboru vo formáte store_data.json
+    Export {
+        /// Cieľový súbor
+        path: PathBuf,
+        /// Len products alebo employees
+        #[arg(long, default_value_t)]
+        only: EntitySelection,
+        /// Nahradí osobné údaje zamestnancov pseudonymami (na zdieľanie mimo firmy)
+        #[arg(long)]
+        anonymize: bool,
+    },
+    /// Nahrá dáta zo súboru vo formáte store_data.json
+    Import {
+        /// Zdrojový súbor
+        path: PathBuf,
+        /// Len products alebo employees; ostatné polia súboru sa ignorujú
+        #[arg(long, default_value_t)]
+        only: EntitySelection,
+        /// Povolí import anonymizovaného exportu (nie do produkcie)
+        #[arg(long)]
+        allow_anonymized: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProductsCommand {
+    /// Vypíše produkty podľa filtra
+    List {
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        category: Option<String>,
+        #[arg(long)]
+        supplier: Option<String>,
+        #[arg(long)]
+        brand: Option<String>,
+        /// Začiatok SKU (napr. `DRK` pre všetky nápoje)
+        #[arg(long)]
+        sku: Option<String>,
+        /// Najviac toľko produktov
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+    /// Pridá produkt
+    Add {
+        #[arg(long)]
+        name: String,
+        /// Predajná cena
+        #[arg(long)]
+        price: f64,
+        /// Nákupná cena
+        #[arg(long)]
+        cost_price: f64,
+        #[arg(long)]
+        category: String,
+        /// Čiarový kód
+        #[arg(long)]
+        bar_code: i64,
+        /// Interné SKU (bez hodnoty ho pridelí server)
+        #[arg(long)]
+        sku: Option<String>,
+        /// Počiatočný stav zásob
+        #[arg(long, default_value_t = 0.0)]
+        quantity: f64,
+        /// Jednotka (piece, kg, g, l, ml, pack)
+        #[arg(long, value_parser = parse_unit)]
+        unit: Option<Unit>,
+        #[arg(long)]
+        supplier: Option<String>,
+        #[arg(long)]
+        brand: Option<String>,
+    },
+    /// Zmení stav zásob produktu (inventúrna úprava)
+    Adjust {
+        /// ID produktu
+        id: u32,
+        /// Zmena množstva (záporná = odpis)
+        #[arg(long, allow_hyphen_values = true)]
+        delta: f64,
+        /// Jednotka zmeny (predvolene jednotka produktu)
+        #[arg(long, value_parser = parse_unit)]
+        unit: Option<Unit>,
+    },
+}
+
+#[derive(Subcommand)]
+enum EmployeesCommand {
+    /// Vyhľadá zamestnancov
+    Search {
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        surname: Option<String>,
+        #[arg(long)]
+        position: Option<String>,
+        #[arg(long)]
+        department: Option<String>,
+    },
+}
+
+/// Vstupný bod klienta; chyby servera sa vypíšu na stderr s nenulovým kódom.
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Chyba: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let config = Config::load(cli.config.as_deref())?;
+    let url = cli.url.or_else(|| std::env::var("STORE_URL").ok().filter(|v| !v.is_empty()));
+    let client = ApiClient::from_config(&config, url.as_deref());
+
+    match cli.command {
+        Command::Products(ProductsCommand::List { name, category, supplier, brand, sku, limit }) => {
+            let filter = Product { name, category, supplier, brand, sku, ..Product::default() };
+            let products = client.list_products(&filter, limit)?;
+            print(cli.format, &products, Table::products)
+        }
+        Command::Products(ProductsCommand::Add {
+            name,
+            price,
+            cost_price,
+            category,
+            bar_code,
+            sku,
+            quantity,
+            unit,
+            supplier,
+            brand,
+        }) => {
+            let product = Product {
+                name: Some(name),
+                sell_price: Some(price),
+                cost_price: Some(cost_price),
+                category: Some(category),
+                bar_code: Some(bar_code),
+                sku,
+                quantity: Some(quantity),
+                unit,
+                supplier,
+                brand,
+                status: Some(true),
+                ..Product::default()
+            };
+            client.add_product(&product)?;
+            println!("Produkt pridaný.");
+            Ok(())
+        }
+        Command::Products(ProductsCommand::Adjust { id, delta, unit }) => {
+            let product = client.adjust_stock(id, &StockAdjustment { delta, unit })?;
+            print(cli.format, std::slice::from_ref(&product), Table::products)
+        }
+        Command::Employees(EmployeesCommand::Search { name, surname, position, department }) => {
+            let filter = Employee { name, surname, position, department, ..Employee::default() };
+            let employees = client.search_employees(&filter)?;
+            print(cli.format, &employees, Table::employees)
+        }
+        Command::Export { path, only, anonymize } => {
+            let data = client.export_data(only, anonymize)?;
+            std::fs::write(&path, data).with_context(|| format!("nepodarilo sa zapísať {}", path.display()))?;
+            println!("Export ({only}) uložený do {}.", path.display());
+            Ok(())
+        }
+        Command::Import { path, only, allow_anonymized } => {
+            let data = std::fs::read(&path).with_context(|| format!("nepodarilo sa načítať {}", path.display()))?;
+            let report = client.import_data(data, only, allow_anonymized)?;
+            if report.anonymized {
+                println!("Varovanie: importované dáta sú anonymizované.");
+            }
+            for field in &report.ignored {
+                println!("Pole {field} zo súboru sa ignoruje.");
+            }
+            println!("Importované: {} zamestnancov, {} produktov.", report.employees, report.products);
+            Ok(())
+        }
+    }
+}
+
+/// Vypíše záznamy v zvolenom formáte.
+fn print<T: Serialize>(format: Format, items: &[T], table: fn(&[T]) -> Table) -> Result<()> {
+    match format {
+        Format::Table => print!("{}", table(items).to_text()),
+        Format::Csv => print!("{}", table(items).to_csv()),
+        Format::Json => println!("{}", serde_json::to_string_pretty(items)?),
+    }
+    Ok(())
+}
+
+fn parse_unit(value: &str) -> Result<Unit, String> {
+    Unit::parse(value).ok_or_else(|| format!("neznáma jednotka {value:?} (piece, kg, g, l, ml, pack)"))
+}
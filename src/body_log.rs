@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::Value;
+
+use crate::config::Config;
+
+/// Polia, ktorých hodnoty sa v logu nahradia maskou.
+pub const REDACTED_FIELDS: &[&str] = &["salary", "phone_number", "phone_e164", "api_key"];
+
+/// Náhrada hodnoty citlivého poľa.
+pub const REDACTED: &str = "***";
+
+/// Najväčšie telo, ktoré sa kvôli logovaniu načíta do pamäte.
+///
+/// Väčšie telá a telá bez známej dĺžky (stream) sa posielajú ďalej bez zmeny
+/// a zaloguje sa len ich veľkosť.
+const MAX_BUFFERED_BODY: u64 = 1024 * 1024;
+
+/// Middleware, ktorý na úrovni debug zaloguje telo požiadavky a odpovede.
+///
+/// Zapína sa v konfigurácii (`log_bodies`), predvolene je vypnutý. Hodnoty polí
+/// z `REDACTED_FIELDS` sa maskujú a dĺžka záznamu je obmedzená `log_body_limit`.
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď handlera s nezmeneným telom
+pub async fn log_bodies(
+    State(config): State<Arc<Config>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if !config.log_bodies {
+        return next.run(req).await;
+    }
+
+    let target = format!("{} {}", req.method(), req.uri().path());
+    let (parts, body) = req.into_parts();
+    let (body, logged) = buffer(body, config.log_body_limit).await;
+    eprintln!("[debug] {target} požiadavka: {logged}");
+
+    let res = next.run(Request::from_parts(parts, body)).await;
+
+    let status = res.status();
+    let (parts, body) = res.into_parts();
+    let (body, logged) = buffer(body, config.log_body_limit).await;
+    eprintln!("[debug] {target} odpoveď {status}: {logged}");
+
+    Response::from_parts(parts, body)
+}
+
+/// Načíta telo do pamäte, ak má známu a dostatočne malú dĺžku.
+///
+/// # Arguments
+/// * `body` – telo požiadavky alebo odpovede
+/// * `limit` – maximálny počet zalogovaných znakov
+///
+/// # Returns
+/// Telo na poslanie ďalej a jeho popis do logu
+async fn buffer(body: Body, limit: usize) -> (Body, String) {
+    let size = match body.size_hint().exact() {
+        Some(size) if size <= MAX_BUFFERED_BODY => size,
+        Some(size) => return (body, format!("<telo {size} B nezalogované>")),
+        None => return (body, "<stream nezalogovaný>".into()),
+    };
+
+    match axum::body::to_bytes(body, size as usize).await {
+        Ok(bytes) => {
+            let logged = describe_body(&bytes, limit);
+            (Body::from(bytes), logged)
+        }
+        Err(e) => (Body::empty(), format!("<telo sa nepodarilo načítať: {e}>")),
+    }
+}
+
+/// Pripraví telo na zalogovanie.
+///
+/// JSON sa zaloguje s maskovanými citlivými poľami (aj vo vnorených objektoch
+/// a poliach) a skrátený na `limit` znakov. Iné telá sa nelogujú, keďže v nich
+/// citlivé údaje nevieme spoľahlivo nájsť.
+///
+/// # Arguments
+/// * `bytes` – telo
+/// * `limit` – maximálny počet znakov
+///
+/// # Returns
+/// Text do logu
+pub fn describe_body(bytes: &[u8], limit: usize) -> String {
+    if bytes.is_empty() {
+        return "<prázdne>".into();
+    }
+
+    let mut value: Value = match serde_json::from_slice(bytes) {
+        Ok(v) => v,
+        Err(_) => return format!("<ne-JSON telo {} B>", bytes.len()),
+    };
+    redact(&mut value);
+
+    let text = value.to_string();
+    match text.char_indices().nth(limit) {
+        Some((end, _)) => format!("{}… ({} B)", &text[..end], bytes.len()),
+        None => text,
+    }
+}
+
+/// Nahradí hodnoty citlivých polí maskou.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if REDACTED_FIELDS.contains(&key.as_str()) {
+                    *v = Value::String(REDACTED.into());
+                } else {
+                    redact(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::Write,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::config::Config;
+
+/// Najväčší počet položiek v cache; pri zaplnení sa najprv odstránia neplatné.
+const MAX_ENTRIES: usize = 1024;
+
+/// Skupina dát, ktorej zmena zneplatní závislé dotazy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entity {
+    /// Produkty, šarže, sady a preklady
+    Products,
+    /// Zamestnanci
+    Employees,
+    /// Objednávky, vrátenia a uzavreté dni
+    Orders,
+    /// Objednávky u dodávateľov
+    PurchaseOrders,
+}
+
+/// Počítadlá zmien (generácie) jednotlivých entít.
+///
+/// Každá úspešná zmena v `StoreDB` zvýši generáciu dotknutých entít; položka
+/// cache je platná, len kým sa generácie, od ktorých závisí, nezmenia.
+#[derive(Debug, Default)]
+pub struct Generations {
+    counters: [AtomicU64; 4],
+}
+
+impl Generations {
+    /// Zvýši generáciu zmenených entít.
+    pub fn bump(&self, entities: &[Entity]) {
+        for entity in entities {
+            self.counters[*entity as usize].fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    /// Vráti súčet generácií entít – zmení sa pri každej zmene ktorejkoľvek z nich.
+    fn stamp(&self, entities: &[Entity]) -> u64 {
+        entities.iter().map(|e| self.counters[*e as usize].load(Ordering::Acquire)).sum()
+    }
+}
+
+/// Druh dotazu uloženého v cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryKind {
+    /// Stránka zoznamu produktov (`GET /products`)
+    Products,
+    /// Správa o zisku
+    Profit,
+    /// Rebríček kategórií podľa marže
+    CategoryMargins,
+    /// Počet zamestnancov po mesiacoch
+    Headcount,
+    /// Denná uzávierka
+    DailyReport,
+    /// Návrhy na doobjednanie
+    ReorderSuggestions,
+    /// Index katalógu na vyhľadávanie u klienta (`GET /products/index`)
+    SearchIndex,
+}
+
+impl QueryKind {
+    /// Všetky druhy dotazov v poradí výpisu metrík.
+    pub const ALL: [QueryKind; 7] = [
+        QueryKind::Products,
+        QueryKind::Profit,
+        QueryKind::CategoryMargins,
+        QueryKind::Headcount,
+        QueryKind::DailyReport,
+        QueryKind::ReorderSuggestions,
+        QueryKind::SearchIndex,
+    ];
+
+    /// Názov druhu v metrikách (label `query`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QueryKind::Products => "products",
+            QueryKind::Profit => "profit",
+            QueryKind::CategoryMargins => "category_margins",
+            QueryKind::Headcount => "headcount",
+            QueryKind::DailyReport => "daily_report",
+            QueryKind::ReorderSuggestions => "reorder_suggestions",
+            QueryKind::SearchIndex => "search_index",
+        }
+    }
+
+    /// Entity, ktorých zmena zneplatní výsledok dotazu.
+    fn depends_on(self) -> &'static [Entity] {
+        match self {
+            QueryKind::Products | QueryKind::CategoryMargins | QueryKind::SearchIndex => &[Entity::Products],
+            QueryKind::Headcount => &[Entity::Employees],
+            QueryKind::Profit => &[Entity::Orders, Entity::Products],
+            QueryKind::DailyReport => &[Entity::Orders, Entity::Products, Entity::Employees],
+            QueryKind::ReorderSuggestions => &[Entity::Products, Entity::PurchaseOrders],
+        }
+    }
+}
+
+/// Počty zásahov a výpadkov cache pre jeden druh dotazu.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Cache výsledkov častých dotazov, zdieľaná v stave aplikácie.
+///
+/// Položky sa kľúčujú druhom dotazu a normalizovaným filtrom (JSON parametrov).
+/// Platnosť končí po `cache_ttl` sekundách alebo pri zmene entít, od ktorých
+/// dotaz závisí – nikdy sa teda nevráti výsledok spred úspešného zápisu.
+#[derive(Clone)]
+pub struct QueryCache {
+    inner: Arc<CacheInner>,
+}
+
+struct CacheInner {
+    enabled: bool,
+    ttl: Duration,
+    generations: Arc<Generations>,
+    entries: Mutex<HashMap<(QueryKind, String), CacheEntry>>,
+    hits: [AtomicU64; QueryKind::ALL.len()],
+    misses: [AtomicU64; QueryKind::ALL.len()],
+}
+
+struct CacheEntry {
+    stamp: u64,
+    stored_at: Instant,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+impl QueryCache {
+    /// Vytvorí cache podľa konfigurácie (`cache_enabled`, `cache_ttl`).
+    ///
+    /// # Arguments
+    /// * `config` – konfigurácia aplikácie
+    /// * `generations` – počítadlá zmien databázy (`StoreDB::generations`)
+    pub fn new(config: &Config, generations: Arc<Generations>) -> Self {
+        Self::with_settings(config.cache_enabled, Duration::from_secs(config.cache_ttl), generations)
+    }
+
+    /// Vytvorí vypnutú cache (napr. pre transakciu z `POST /tx`, ktorej nepotvrdené
+    /// dáta nesmú byť viditeľné mimo nej).
+    ///
+    /// # Arguments
+    /// * `generations` – počítadlá zmien databázy (`StoreDB::generations`)
+    pub fn disabled(generations: Arc<Generations>) -> Self {
+        Self::with_settings(false, Duration::ZERO, generations)
+    }
+
+    fn with_settings(enabled: bool, ttl: Duration, generations: Arc<Generations>) -> Self {
+        Self {
+            inner: Arc::new(CacheInner {
+                enabled,
+                ttl,
+                generations,
+                entries: Mutex::new(HashMap::new()),
+                hits: Default::default(),
+                misses: Default::default(),
+            }),
+        }
+    }
+
+    /// Zistí, či je cache zapnutá.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.enabled
+    }
+
+    /// Vráti výsledok dotazu z cache, alebo ho načíta a uloží.
+    ///
+    /// Generácie entít sa čítajú pred načítaním, takže výsledok, počas ktorého
+    /// načítania prebehol zápis, sa pri ďalšom čítaní považuje za neplatný.
+    /// Chyby sa do cache neukladajú. Pri vypnutej cache sa vždy volá `load`.
+    ///
+    /// # Arguments
+    /// * `kind` – druh dotazu
+    /// * `filter` – parametre dotazu, z ktorých sa zostaví kľúč
+    /// * `load` – načítanie výsledku z databázy
+    ///
+    /// # Errors
+    /// Chyba z `load`
+    pub async fn get_or_load<T, E, F, Fut>(&self, kind: QueryKind, filter: &impl Serialize, load: F) -> Result<T, E>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if !self.inner.enabled {
+            return load().await;
+        }
+
+        let key = (kind, serde_json::to_string(filter).unwrap_or_default());
+        let stamp = self.inner.generations.stamp(kind.depends_on());
+        if let Some(value) = self.lookup(&key, stamp) {
+            self.inner.hits[kind as usize].fetch_add(1, Ordering::Relaxed);
+            return Ok(value);
+        }
+
+        self.inner.misses[kind as usize].fetch_add(1, Ordering::Relaxed);
+        let value = load().await?;
+        self.store(key, stamp, value.clone());
+        Ok(value)
+    }
+
+    /// Vráti počty zásahov a výpadkov pre druh dotazu.
+    pub fn stats(&self, kind: QueryKind) -> CacheStats {
+        CacheStats {
+            hits: self.inner.hits[kind as usize].load(Ordering::Relaxed),
+            misses: self.inner.misses[kind as usize].load(Ordering::Relaxed),
+        }
+    }
+
+    /// Vráti počet uložených položiek (vrátane ešte neodstránených neplatných).
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Zistí, či je cache prázdna.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Vypíše metriky cache vo formáte Prometheus.
+    pub fn render_metrics(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# HELP store_cache_enabled Whether the query cache is enabled.");
+        let _ = writeln!(out, "# TYPE store_cache_enabled gauge");
+        let _ = writeln!(out, "store_cache_enabled {}", u8::from(self.inner.enabled));
+        let _ = writeln!(out, "# HELP store_cache_entries Number of entries in the query cache.");
+        let _ = writeln!(out, "# TYPE store_cache_entries gauge");
+        let _ = writeln!(out, "store_cache_entries {}", self.len());
+        for (name, help, pick) in [
+            ("store_cache_hits_total", "Query cache hits.", (|s: CacheStats| s.hits) as fn(CacheStats) -> u64),
+            ("store_cache_misses_total", "Query cache misses.", |s: CacheStats| s.misses),
+        ] {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            for kind in QueryKind::ALL {
+                let _ = writeln!(out, "{name}{{query=\"{}\"}} {}", kind.as_str(), pick(self.stats(kind)));
+            }
+        }
+        out
+    }
+
+    fn lookup<T: Clone + 'static>(&self, key: &(QueryKind, String), stamp: u64) -> Option<T> {
+        let entries = self.entries();
+        let entry = entries.get(key)?;
+        if entry.stamp != stamp || entry.stored_at.elapsed() >= self.inner.ttl {
+            return None;
+        }
+        entry.value.downcast_ref::<T>().cloned()
+    }
+
+    fn store<T: Send + Sync + 'static>(&self, key: (QueryKind, String), stamp: u64, value: T) {
+        let mut entries = self.entries();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            let generations = &self.inner.generations;
+            let ttl = self.inner.ttl;
+            entries.retain(|(kind, _), e| {
+                e.stamp == generations.stamp(kind.depends_on()) && e.stored_at.elapsed() < ttl
+            });
+            if entries.len() >= MAX_ENTRIES {
+                entries.clear();
+            }
+        }
+        entries.insert(key, CacheEntry { stamp, stored_at: Instant::now(), value: Arc::new(value) });
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<(QueryKind, String), CacheEntry>> {
+        self.inner.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
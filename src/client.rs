@@ -0,0 +1,351 @@
+use std::fmt;
+
+use anyhow::{Context, Result};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    auth::API_KEY_HEADER,
+    config::Config,
+    db_filler::EntitySelection,
+    list_shape,
+    structs::{DataImportReport, DeadStockLine, Employee, Product, ReorderSuggestion, StockAdjustment, WeeklyHours},
+};
+
+/// Chybová odpoveď servera (status mimo 2xx).
+#[derive(Debug)]
+pub struct ApiError {
+    /// HTTP status kód
+    pub status: u16,
+    /// Text chyby zo servera (pri JSON tele jednotlivé polia)
+    pub message: String,
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = reqwest::StatusCode::from_u16(self.status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .unwrap_or("");
+        write!(f, "server vrátil {} {reason}", self.status)?;
+        if !self.message.is_empty() {
+            write!(f, ": {}", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Klient HTTP API skladu (používa ho `store-cli`).
+///
+/// Posiela a prijíma tie isté štruktúry ako server, takže sa formát dát
+/// nemôže rozísť s API.
+pub struct ApiClient {
+    http: Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl ApiClient {
+    /// Vytvorí klienta pre server na danej adrese.
+    ///
+    /// # Arguments
+    /// * `base_url` – adresa servera, napr. `http://127.0.0.1:8000`
+    /// * `api_key` – API kľúč posielaný v hlavičke `x-api-key`
+    pub fn new(base_url: &str, api_key: Option<String>) -> Self {
+        Self { http: Client::new(), base_url: base_url.trim_end_matches('/').to_string(), api_key }
+    }
+
+    /// Vytvorí klienta podľa konfigurácie servera.
+    ///
+    /// Adresa sa odvodí z `listen_addr` (`0.0.0.0` sa nahradí `127.0.0.1`),
+    /// kľúč z `admin_api_key`.
+    ///
+    /// # Arguments
+    /// * `config` – konfigurácia aplikácie
+    /// * `base_url` – adresa servera, ktorá má prednosť pred `listen_addr`
+    pub fn from_config(config: &Config, base_url: Option<&str>) -> Self {
+        let url = match base_url {
+            Some(url) => url.to_string(),
+            None => format!("http://{}", config.listen_addr.replacen("0.0.0.0", "127.0.0.1", 1)),
+        };
+        Self::new(&url, config.admin_api_key.clone())
+    }
+
+    /// Vráti produkty podľa filtra (parametre dotazu ako `GET /products`).
+    ///
+    /// # Errors
+    /// Ak zlyhá spojenie alebo server vráti chybu
+    pub fn list_products(&self, filter: &Product, limit: Option<u32>) -> Result<Vec<Product>> {
+        let mut request = self.request(reqwest::Method::GET, "/products").query(filter);
+        if let Some(limit) = limit {
+            request = request.query(&[("limit", limit)]);
+        }
+        json(send(request)?)
+    }
+
+    /// Pridá produkt.
+    ///
+    /// # Errors
+    /// Ak zlyhá spojenie alebo server produkt odmietne
+    pub fn add_product(&self, product: &Product) -> Result<()> {
+        send(self.request(reqwest::Method::POST, "/products").json(product))?;
+        Ok(())
+    }
+
+    /// Zmení stav zásob produktu (`POST /products/{id}/adjust-stock`).
+    ///
+    /// # Returns
+    /// Produkt s novým stavom zásob
+    ///
+    /// # Errors
+    /// Ak zlyhá spojenie alebo server úpravu odmietne
+    pub fn adjust_stock(&self, id: u32, adjustment: &StockAdjustment) -> Result<Product> {
+        json(send(self.request(reqwest::Method::POST, &format!("/products/{id}/adjust-stock")).json(adjustment))?)
+    }
+
+    /// Vyhľadá zamestnancov podľa filtra (`POST /employees/search`).
+    ///
+    /// # Errors
+    /// Ak zlyhá spojenie alebo server vráti chybu
+    pub fn search_employees(&self, filter: &Employee) -> Result<Vec<Employee>> {
+        json(send(self.request(reqwest::Method::POST, "/employees/search").json(filter))?)
+    }
+
+    /// Stiahne export dát vo formáte `store_data.json` (`GET /admin/export`).
+    ///
+    /// # Arguments
+    /// * `only` – výber entít
+    /// * `anonymize` – nahradí osobné údaje zamestnancov pseudonymami
+    ///
+    /// # Errors
+    /// Ak zlyhá spojenie alebo server vráti chybu
+    pub fn export_data(&self, only: EntitySelection, anonymize: bool) -> Result<Vec<u8>> {
+        let request = self
+            .request(reqwest::Method::GET, "/admin/export")
+            .query(&[("only", only.as_str()), ("anonymize", if anonymize { "true" } else { "false" })]);
+        Ok(send(request)?.bytes().context("neplatná odpoveď servera")?.to_vec())
+    }
+
+    /// Nahrá súbor vo formáte `store_data.json` (`POST /admin/import`).
+    ///
+    /// # Arguments
+    /// * `data` – obsah súboru
+    /// * `only` – výber entít; ostatné polia súboru server ignoruje
+    /// * `allow_anonymized` – povolí import anonymizovaného exportu
+    ///
+    /// # Errors
+    /// Ak zlyhá spojenie alebo server import odmietne
+    pub fn import_data(&self, data: Vec<u8>, only: EntitySelection, allow_anonymized: bool) -> Result<DataImportReport> {
+        let request = self
+            .request(reqwest::Method::POST, "/admin/import")
+            .query(&[("only", only.as_str()), ("allow_anonymized", if allow_anonymized { "true" } else { "false" })])
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(data);
+        json(send(request)?)
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> RequestBuilder {
+        // zoznamy ako holé pole bez ohľadu na `list_shape` servera
+        let request = self
+            .http
+            .request(method, format!("{}{path}", self.base_url))
+            .header(reqwest::header::ACCEPT, list_shape::ACCEPT_ARRAY);
+        match &self.api_key {
+            Some(key) => request.header(API_KEY_HEADER, key),
+            None => request,
+        }
+    }
+}
+
+/// Odošle požiadavku a odpoveď mimo 2xx prevedie na `ApiError`.
+fn send(request: RequestBuilder) -> Result<Response> {
+    let response = request.send().context("nepodarilo sa spojiť so serverom")?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().unwrap_or_default();
+    Err(ApiError { status: status.as_u16(), message: error_message(&body) }.into())
+}
+
+fn json<T: DeserializeOwned>(response: Response) -> Result<T> {
+    response.json().context("neplatná odpoveď servera")
+}
+
+/// Pripraví text chyby z tela odpovede; JSON objekt sa vypíše ako `pole: hodnota`.
+fn error_message(body: &str) -> String {
+    match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(fields)) => fields
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(s) => format!("{key}: {s}"),
+                other => format!("{key}: {other}"),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => body.trim().to_string(),
+    }
+}
+
+/// Tabuľka na výpis v termináli alebo ako CSV.
+pub struct Table {
+    pub headers: Vec<&'static str>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Tabuľka produktov.
+    pub fn products(products: &[Product]) -> Self {
+        Self {
+            headers: vec!["id", "name", "category", "quantity", "unit", "sell_price", "supplier", "sku"],
+            rows: products
+                .iter()
+                .map(|p| {
+                    vec![
+                        cell(p.id),
+                        cell(p.name.as_deref()),
+                        cell(p.category.as_deref()),
+                        cell(p.quantity),
+                        cell(p.unit.map(|u| u.as_str())),
+                        cell(p.sell_price),
+                        cell(p.supplier.as_deref()),
+                        cell(p.sku.as_deref()),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Tabuľka zamestnancov.
+    pub fn employees(employees: &[Employee]) -> Self {
+        Self {
+            headers: vec!["id", "name", "surname", "position", "department", "phone_number", "email"],
+            rows: employees
+                .iter()
+                .map(|e| {
+                    vec![
+                        cell(e.id),
+                        cell(e.name.as_deref()),
+                        cell(e.surname.as_deref()),
+                        cell(e.position.as_deref()),
+                        cell(e.department.as_deref()),
+                        cell(e.phone_number.as_deref()),
+                        cell(e.email.as_deref()),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Tabuľka odpracovaných hodín po týždňoch (export pre mzdy).
+    pub fn weekly_hours(weeks: &[WeeklyHours]) -> Self {
+        Self {
+            headers: vec!["employee_id", "name", "week", "week_start", "hours", "overtime"],
+            rows: weeks
+                .iter()
+                .map(|w| {
+                    vec![
+                        w.employee_id.to_string(),
+                        w.name.clone(),
+                        w.week.clone(),
+                        w.week_start.to_string(),
+                        format!("{:.2}", w.hours),
+                        format!("{:.2}", w.overtime),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Tabuľka nepredajných zásob.
+    pub fn dead_stock(lines: &[DeadStockLine]) -> Self {
+        Self {
+            headers: vec!["product_id", "name", "category", "quantity", "unit", "value", "last_movement", "days_since_movement", "employee"],
+            rows: lines
+                .iter()
+                .map(|l| {
+                    vec![
+                        l.product_id.to_string(),
+                        cell(l.name.as_deref()),
+                        cell(l.category.as_deref()),
+                        l.quantity.to_string(),
+                        l.unit.as_str().to_string(),
+                        format!("{:.2}", l.value),
+                        cell(l.last_movement),
+                        cell(l.days_since_movement),
+                        cell(l.employee_name.as_deref()),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Tabuľka riadkov objednávky u dodávateľa.
+    pub fn purchase_order(lines: &[ReorderSuggestion]) -> Self {
+        Self {
+            headers: vec!["product_id", "name", "quantity", "unit", "unit_cost", "total"],
+            rows: lines
+                .iter()
+                .map(|l| {
+                    vec![
+                        l.product_id.to_string(),
+                        cell(l.name.as_deref()),
+                        l.suggested_quantity.to_string(),
+                        l.unit.as_str().to_string(),
+                        cell(l.unit_cost.map(|c| format!("{c:.2}"))),
+                        cell(l.estimated_cost.map(|c| format!("{c:.2}"))),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Vypíše tabuľku so stĺpcami zarovnanými podľa najdlhšej hodnoty.
+    pub fn to_text(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (width, value) in widths.iter_mut().zip(row) {
+                *width = (*width).max(value.chars().count());
+            }
+        }
+        let line = |values: Vec<&str>| {
+            let cells: Vec<String> = values.iter().zip(&widths).map(|(v, w)| format!("{v:<w$}")).collect();
+            cells.join("  ").trim_end().to_string() + "\n"
+        };
+
+        let mut out = line(self.headers.clone());
+        let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        out += &line(rule.iter().map(String::as_str).collect());
+        for row in &self.rows {
+            out += &line(row.iter().map(String::as_str).collect());
+        }
+        out
+    }
+
+    /// Vypíše tabuľku ako CSV (RFC 4180, hodnoty s čiarkou alebo úvodzovkami sa uzavrú).
+    pub fn to_csv(&self) -> String {
+        let line = |values: &mut dyn Iterator<Item = &str>| {
+            values.map(csv_field).collect::<Vec<_>>().join(",") + "\r\n"
+        };
+        let mut out = line(&mut self.headers.iter().copied());
+        for row in &self.rows {
+            out += &line(&mut row.iter().map(String::as_str));
+        }
+        out
+    }
+}
+
+fn cell(value: Option<impl ToString>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,779 @@
+use std::{collections::BTreeMap, env, fmt::Display, path::Path, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::body_log::REDACTED;
+
+use crate::list_shape::ListShape;
+use crate::phone;
+use crate::pricing::{MarginPolicy, PriceRounding};
+use crate::retention::RetentionPolicy;
+use crate::sku;
+
+/// Predvolený konfiguračný súbor, ak nie je zadaný `--config`.
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+/// Polia s tajomstvami, ktorých hodnoty `Config::sanitized` nahradí maskou.
+pub const SECRET_FIELDS: &[&str] = &["admin_api_key", "editor_api_key", "viewer_api_key", "jwt_secret"];
+
+/// Predvolený maximálny počet riadkov v jednej odpovedi.
+const DEFAULT_MAX_RESULT_ROWS: u32 = 1000;
+
+/// Predvolená hranica nízkeho stavu zásob.
+const DEFAULT_LOW_STOCK_THRESHOLD: u32 = 5;
+
+/// Predvolený cieľový stav zásob pri doobjednaní.
+const DEFAULT_REORDER_TARGET: u32 = 20;
+
+/// Predvolený maximálny počet zalogovaných znakov jedného tela požiadavky/odpovede.
+const DEFAULT_LOG_BODY_LIMIT: usize = 2048;
+
+/// Predvolená najväčšia veľkosť súboru nahraného na import (10 MiB).
+const DEFAULT_MAX_UPLOAD_SIZE: usize = 10 * 1024 * 1024;
+
+/// Predvolený maximálny počet vlákien pre blokujúce operácie.
+///
+/// Blokujúce vlákna používa len súborové I/O pri importe a exporte JSONu,
+/// takže namiesto predvolených 512 z tokia stačí niekoľko.
+const DEFAULT_MAX_BLOCKING_THREADS: usize = 8;
+
+/// Predvolená hodnota `Retry-After` počas údržby (sekundy).
+const DEFAULT_MAINTENANCE_RETRY_AFTER: u64 = 120;
+
+/// Predvolený čas na dokončenie rozbehnutých požiadaviek pri vypínaní (sekundy).
+const DEFAULT_SHUTDOWN_TIMEOUT: u64 = 30;
+
+/// Predvolená doba platnosti položiek cache dotazov (sekundy).
+const DEFAULT_CACHE_TTL: u64 = 30;
+
+/// Predvolený názov obchodu v hlavičke zostáv.
+const DEFAULT_SHOP_NAME: &str = "Obchod";
+
+/// Predvolený interval ukladania snímok štatistík (raz za deň, sekundy).
+const DEFAULT_STATS_SNAPSHOT_INTERVAL: u64 = 24 * 60 * 60;
+
+/// Predvolený interval vyhodnocovania upozornení na stav zásob (sekundy).
+const DEFAULT_ALERT_CHECK_INTERVAL: u64 = 5 * 60;
+
+/// Predvolený interval uplatňovania politiky uchovávania osobných údajov (raz za deň, sekundy).
+const DEFAULT_RETENTION_INTERVAL: u64 = 24 * 60 * 60;
+
+/// Predvolený interval rušenia skončených akcií produktov (raz za hodinu, sekundy).
+const DEFAULT_SALE_EXPIRY_INTERVAL: u64 = 60 * 60;
+
+/// Predvolený interval uplatňovania naplánovaných zmien cien (raz za hodinu, sekundy).
+const DEFAULT_PRICE_CHANGE_INTERVAL: u64 = 60 * 60;
+
+/// Predvolená týždenná norma odpracovaných hodín (nad ňu sa počíta nadčas).
+const DEFAULT_WEEKLY_HOURS_NORM: f64 = 40.0;
+
+/// Predvolený čas nečinnosti, po ktorom sa transakcia z `POST /tx` zruší (sekundy).
+const DEFAULT_TX_TIMEOUT: u64 = 60;
+
+/// Predvolený počet ponechaných záloh JSON súboru.
+const DEFAULT_BACKUP_KEEP: usize = 7;
+
+/// Predvolený maximálny počet spojení do databázy.
+const DEFAULT_DB_MAX_CONNECTIONS: u32 = 5;
+
+/// Predvolený čas SQL príkazu, od ktorého sa príkaz zaloguje ako pomalý (milisekundy).
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: u64 = 200;
+
+/// Predvolená najväčšia dĺžka krátkeho textového poľa (znaky).
+pub const DEFAULT_MAX_TEXT_LENGTH: usize = 255;
+
+/// Predvolená najväčšia dĺžka popisu produktu a poznámky k zamestnancovi (znaky).
+pub const DEFAULT_MAX_LONG_TEXT_LENGTH: usize = 4000;
+
+/// Najväčšie dĺžky textových polí produktov a zamestnancov v znakoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextLimits {
+    /// Krátke polia (názov, kategória, značka, dodávateľ, meno, e-mail, ...)
+    pub text: usize,
+    /// Dlhé polia (`description`, `note`)
+    pub long_text: usize,
+}
+
+impl Default for TextLimits {
+    fn default() -> Self {
+        Self { text: DEFAULT_MAX_TEXT_LENGTH, long_text: DEFAULT_MAX_LONG_TEXT_LENGTH }
+    }
+}
+
+/// Váhy zložiek skóre dodávateľa (sekcia `[supplier_score_weights]`).
+///
+/// Na absolútnej veľkosti nezáleží, skóre je vážený priemer zložiek, ktoré
+/// dodávateľ má (pozri `StoreDB::supplier_scores`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SupplierScoreWeights {
+    /// Dodávky prijaté najneskôr v očakávaný deň
+    pub on_time:         f64,
+    /// Stálosť nákupných cien
+    pub price_stability: f64,
+    /// Výdaje zo skladu, po ktorých tovar nedošiel
+    pub availability:    f64,
+}
+
+impl Default for SupplierScoreWeights {
+    fn default() -> Self {
+        Self { on_time: 0.4, price_stability: 0.3, availability: 0.3 }
+    }
+}
+
+/// Hranice vekových skupín zásob v dňoch (`[30, 60, 90]` → 0–30, 31–60, 61–90, 91+).
+///
+/// V TOML je to pole čísel, v premennej prostredia čísla oddelené čiarkou.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct AgingBuckets(pub Vec<u32>);
+
+impl Default for AgingBuckets {
+    fn default() -> Self {
+        Self(vec![30, 60, 90])
+    }
+}
+
+impl FromStr for AgingBuckets {
+    type Err = String;
+
+    /// Prevedie hodnotu v tvare `30,60,90` na hranice.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        value
+            .split(',')
+            .map(|part| part.trim().parse::<u32>().map_err(|e| format!("{part:?}: {e}")))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Konfigurácia aplikácie.
+///
+/// Načítava sa zo súboru `config.toml`, premenné prostredia `STORE_*` prepisujú
+/// jednotlivé polia. Chýbajúce polia majú predvolené hodnoty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Maximálny počet riadkov, ktoré môže vrátiť jedna odpoveď
+    pub max_result_rows: u32,
+    /// Množstvo, pri ktorom (a pod ktorým) sa produkt považuje za nízky stav zásob
+    pub low_stock_threshold: u32,
+    /// Cieľové množstvo na sklade, na ktoré sa dopĺňa pri návrhoch doobjednania
+    pub reorder_target: u32,
+    /// API kľúč pre administrátorské endpointy (`None` = endpointy sú vypnuté)
+    pub admin_api_key: Option<String>,
+    /// API kľúč s rolou `editor` (čítanie, pridávanie a úpravy)
+    pub editor_api_key: Option<String>,
+    /// API kľúč s rolou `viewer` (len čítanie)
+    pub viewer_api_key: Option<String>,
+    /// Tajomstvo na overenie JWT podpísaných HS256 (zapne prihlasovanie tokenmi)
+    pub jwt_secret: Option<String>,
+    /// Súbor s verejným kľúčom (PEM) na overenie JWT podpísaných RS256
+    pub jwt_public_key_path: Option<String>,
+    /// Logovanie tiel požiadaviek a odpovedí (ladenie, citlivé polia sa maskujú)
+    pub log_bodies: bool,
+    /// Maximálny počet zalogovaných znakov jedného tela
+    pub log_body_limit: usize,
+    /// Najväčšia veľkosť tela (bajty) pri nahrávaní súboru na import (`POST /import/upload`)
+    pub max_upload_size: usize,
+    /// Pravidlo zaokrúhľovania predajných cien pri ich úprave
+    #[serde(deserialize_with = "deserialize_parsed", serialize_with = "serialize_display")]
+    pub price_rounding: PriceRounding,
+    /// Minimálna marža pri zmene predajných cien (sekcia `[margin_policy]`: `default`
+    /// a tabuľka `categories`; hodnoty `"15%"` alebo `"0.20"`). Prázdna politika nič nekontroluje.
+    pub margin_policy: MarginPolicy,
+    /// Krajina pre telefónne čísla zadané bez medzinárodnej predvoľby (napr. `SK`)
+    pub phone_country: String,
+    /// Najväčšia dĺžka krátkych textových polí produktov a zamestnancov (znaky);
+    /// dlhší text sa odmietne s `422`
+    pub max_text_length: usize,
+    /// Najväčšia dĺžka popisu produktu a poznámky k zamestnancovi (znaky)
+    pub max_long_text_length: usize,
+    /// Maska predpony zadaného SKU (`A` = písmeno, `9` = číslica, napr. `AAA` pre `DRK-0042`);
+    /// SKU v inom tvare sa odmietne s `400`. Automaticky pridelené SKU majú predponu
+    /// kategórie – prvé zadané SKU kategórie alebo tri písmená jej názvu.
+    pub sku_prefix_pattern: String,
+    /// Počet worker vlákien tokio runtime (`None` = počet jadier CPU)
+    pub worker_threads: Option<usize>,
+    /// Maximálny počet vlákien pre blokujúce operácie (súborové I/O)
+    pub max_blocking_threads: usize,
+    /// Súbor databázy SQLite
+    pub db_path: String,
+    /// Maximálny počet spojení do databázy
+    pub db_max_connections: u32,
+    /// Čas SQL príkazu v milisekundách, od ktorého sa zaloguje ako pomalý
+    /// a započíta do `store_db_slow_queries_total` (`0` = každý príkaz)
+    pub slow_query_threshold: u64,
+    /// Ďalšie obchody obsluhované tým istým serverom (sekcia `[stores]`: `id = "cesta.db"`).
+    ///
+    /// Obchod je dostupný pod `/stores/{id}/...` s vlastnou databázou; hlavný obchod
+    /// z `db_path` zostáva na cestách bez predpony. JSON sa synchronizuje len s hlavným.
+    pub stores: BTreeMap<String, String>,
+    /// Režim len na čítanie (napr. počas migrácie alebo nad obnovenou zálohou).
+    ///
+    /// Zmeny cez API vracajú `503`, databáza sa otvorí s príznakom read-only
+    /// (bez migrácií schémy) a JSON sa pri štarte ani vypnutí nesynchronizuje.
+    /// Za behu sa prepína cez `/admin/read-only`.
+    pub read_only: bool,
+    /// JSON súbor, z ktorého sa naplní nová databáza a do ktorého sa ukladá pri vypnutí
+    pub json_path: String,
+    /// Synchronizácia databázy s `json_path` pri štarte a vypnutí.
+    ///
+    /// `false` = zdrojom pravdy je len súbor SQLite: JSON sa nečíta ani nezapisuje,
+    /// štart a vypnutie sú rýchlejšie a vypnutie neprepíše JSON neúplnými dátami.
+    /// Zálohu však treba robiť zo súboru databázy (alebo cez `/admin/export`).
+    pub json_sync: bool,
+    /// Počet časovo označených záloh `json_path`, ktoré sa ponechajú pri jeho
+    /// prepísaní pri vypnutí (`json_path.RRRRMMDDTHHMMSSffffff.bak`); staršie sa
+    /// zmažú. 0 = zálohy sa nerobia
+    pub backup_keep: usize,
+    /// Adresa, na ktorej počúva HTTP server
+    pub listen_addr: String,
+    /// Vývojový režim: webové rozhranie sa číta z disku a stránka sa sama obnoví po zmene
+    pub dev_mode: bool,
+    /// Adresár so súbormi webového rozhrania pre vývojový režim
+    pub web_dir: String,
+    /// Predvolená hodnota `Retry-After` (sekundy) počas režimu údržby
+    pub maintenance_retry_after: u64,
+    /// Spustí gRPC rozhranie na samostatnom porte
+    pub grpc_enabled: bool,
+    /// Adresa, na ktorej počúva gRPC server
+    pub grpc_addr: String,
+    /// Čas (sekundy), ktorý majú rozbehnuté požiadavky pri vypínaní, kým sa server ukončí násilne
+    pub shutdown_timeout: u64,
+    /// Zapne cache výsledkov častých dotazov (vypnutie pomáha pri ladení)
+    pub cache_enabled: bool,
+    /// Doba platnosti (sekundy) položky cache dotazov
+    pub cache_ttl: u64,
+    /// Odmietne JSON telá s neznámymi poľami (`false` = tolerantný režim pre staršie klienty)
+    pub strict_json: bool,
+    /// Predvolený tvar odpovedí zoznamov: `array` (holé pole) alebo `envelope`
+    /// (`{"items": [...], "total": N}`). Klient si tvar vyberie hlavičkou
+    /// `Accept: application/json; profile="array"` / `profile="envelope"` (pozri `list_shape`).
+    pub list_shape: ListShape,
+    /// Názov obchodu v hlavičke tlačových zostáv (PDF)
+    pub shop_name: String,
+    /// Interval (sekundy) ukladania snímok štatistík do histórie; 0 plánovač vypne
+    pub stats_snapshot_interval: u64,
+    /// Interval (sekundy) vyhodnocovania upozornení na nízky stav zásob; 0 monitor vypne
+    pub alert_check_interval: u64,
+    /// Doby uchovávania osobných údajov (sekcia `[retention]`: `employee_years`,
+    /// `attendance_years`, `action` = `anonymize`/`delete`). Prázdna politika nič nemaže;
+    /// pred zapnutím skontrolujte náhľad na `GET /admin/retention`.
+    pub retention: RetentionPolicy,
+    /// Interval (sekundy) uplatňovania politiky uchovávania; 0 plánovač vypne
+    pub retention_interval: u64,
+    /// Interval (sekundy) rušenia skončených akcií produktov; 0 plánovač vypne
+    pub sale_expiry_interval: u64,
+    /// Pri príjme tovaru k objednávke u dodávateľa nastaví `cost_price` produktu
+    /// na vážený priemer nákupných dávok na sklade (`GET /products/{id}/avg-cost`)
+    pub average_cost_on_restock: bool,
+    /// Odmietne príjem tovaru, po ktorom by zásoby produktu prekročili jeho `max_stock`;
+    /// inak sa príjem zapíše a prekročenie sa vráti vo `warnings`
+    pub block_overstock: bool,
+    /// Interval (sekundy) uplatňovania naplánovaných zmien cien; 0 plánovač vypne
+    /// (zmeny potom uplatní len `POST /admin/apply-price-changes`)
+    pub price_change_interval: u64,
+    /// Týždenná norma hodín v zostave dochádzky; odpracované hodiny nad ňu sú nadčas
+    pub weekly_hours_norm: f64,
+    /// Najdlhší čas (sekundy) od posledného volania, počas ktorého môže byť transakcia
+    /// z `POST /tx` otvorená; potom sa vráti späť a uvoľní spojenie
+    pub tx_timeout: u64,
+    /// Váhy zložiek skóre dodávateľov v `GET /stats/supplier-scores`
+    pub supplier_score_weights: SupplierScoreWeights,
+    /// Horné hranice (dni) vekových skupín zásob v `GET /stats/aging`, rastúce
+    pub aging_buckets: AgingBuckets,
+}
+
+impl Config {
+    /// Načíta konfiguráciu zo súboru a premenných prostredia.
+    ///
+    /// Bez zadanej cesty sa použije `config.toml`, ak existuje; inak sa začína
+    /// z predvolených hodnôt. Premenné prostredia (pozri `apply_overrides`)
+    /// majú prednosť pred súborom.
+    ///
+    /// # Arguments
+    /// * `path` – cesta ku konfiguračnému súboru (`--config`)
+    ///
+    /// # Returns
+    /// Načítaná a skontrolovaná konfigurácia
+    ///
+    /// # Errors
+    /// Ak zadaný súbor neexistuje alebo niektoré pole nie je platné; chyba
+    /// obsahuje názov poľa (alebo premennej) a dôvod
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let mut config = match path {
+            Some(path) => Self::from_file(path)?,
+            None if Path::new(DEFAULT_CONFIG_PATH).exists() => Self::from_file(Path::new(DEFAULT_CONFIG_PATH))?,
+            None => Self::default(),
+        };
+        config.apply_overrides(|key| env::var(key).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Načíta konfiguráciu z TOML súboru (bez premenných prostredia).
+    ///
+    /// # Arguments
+    /// * `path` – cesta k súboru
+    ///
+    /// # Returns
+    /// Konfigurácia zo súboru
+    ///
+    /// # Errors
+    /// Ak sa súbor nedá prečítať alebo nie je platný
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("konfiguračný súbor {} sa nepodarilo prečítať", path.display()))?;
+        Self::from_toml(&text).with_context(|| format!("neplatná konfigurácia v {}", path.display()))
+    }
+
+    /// Sparsuje konfiguráciu z TOML textu.
+    ///
+    /// # Arguments
+    /// * `text` – obsah konfiguračného súboru
+    ///
+    /// # Returns
+    /// Konfigurácia; chýbajúce polia majú predvolené hodnoty
+    ///
+    /// # Errors
+    /// Ak text nie je platný TOML, obsahuje neznáme pole alebo hodnotu
+    /// nesprávneho typu; chyba uvádza cestu k poľu
+    pub fn from_toml(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| {
+            let field = e.span().and_then(|span| {
+                let table = toml::de::DeTable::parse(text).ok()?;
+                field_path(table.get_ref(), span.start)
+            });
+            match field {
+                Some(field) => anyhow!("pole `{field}`: {}", e.message()),
+                None => anyhow!("{}", e.message().trim()),
+            }
+        })
+    }
+
+    /// Prepíše polia hodnotami premenných prostredia.
+    ///
+    /// * `STORE_MAX_RESULT_ROWS` – maximálny počet riadkov v odpovedi
+    /// * `STORE_LOW_STOCK_THRESHOLD` – hranica nízkeho stavu zásob
+    /// * `STORE_REORDER_TARGET` – cieľový stav zásob pri doobjednaní
+    /// * `STORE_ADMIN_API_KEY` – API kľúč pre `/admin/*`
+    /// * `STORE_EDITOR_API_KEY` – API kľúč s rolou `editor`
+    /// * `STORE_VIEWER_API_KEY` – API kľúč s rolou `viewer`
+    /// * `STORE_JWT_SECRET` – tajomstvo pre JWT podpísané HS256
+    /// * `STORE_JWT_PUBLIC_KEY_PATH` – verejný kľúč (PEM) pre JWT podpísané RS256
+    /// * `STORE_LOG_BODIES` – `true` zapne logovanie tiel požiadaviek a odpovedí
+    /// * `STORE_LOG_BODY_LIMIT` – maximálny počet zalogovaných znakov tela
+    /// * `STORE_PRICE_ROUNDING` – zaokrúhľovanie cien (`none`, `0.05`, `0.10`, `x9`, `0.99`, `ceil`)
+    /// * `STORE_MIN_MARGIN` – globálna minimálna marža (`15%` alebo `0.20`)
+    /// * `STORE_CATEGORY_MIN_MARGINS` – marže kategórií (`Pečivo=10%,Nápoje=0.30`), nahradia tie zo súboru
+    /// * `STORE_PHONE_COUNTRY` – predvolená krajina telefónnych čísel
+    /// * `STORE_MAX_TEXT_LENGTH` – najväčšia dĺžka krátkych textových polí
+    /// * `STORE_MAX_LONG_TEXT_LENGTH` – najväčšia dĺžka popisu a poznámky
+    /// * `STORE_SKU_PREFIX_PATTERN` – maska predpony SKU (`AAA`)
+    /// * `STORE_WORKER_THREADS` – počet worker vlákien runtime (`0` = počet jadier CPU)
+    /// * `STORE_MAX_BLOCKING_THREADS` – maximálny počet blokujúcich vlákien
+    /// * `STORE_DB_PATH` – súbor databázy
+    /// * `STORE_DB_MAX_CONNECTIONS` – maximálny počet spojení do databázy
+    /// * `STORE_SLOW_QUERY_THRESHOLD` – prah pomalého SQL príkazu (ms)
+    /// * `STORE_STORES` – ďalšie obchody (`pobocka1=pobocka1.db,pobocka2=pobocka2.db`), nahradia tie zo súboru
+    /// * `STORE_READ_ONLY` – `true` zapne režim len na čítanie
+    /// * `STORE_JSON_PATH` – JSON súbor pre import a export dát
+    /// * `STORE_JSON_SYNC` – `false` vypne načítanie a ukladanie JSONu pri štarte a vypnutí
+    /// * `STORE_LISTEN_ADDR` – adresa HTTP servera
+    /// * `STORE_DEV_MODE` – `true` zapne vývojový režim webového rozhrania
+    /// * `STORE_WEB_DIR` – adresár webového rozhrania pre vývojový režim
+    /// * `STORE_MAINTENANCE_RETRY_AFTER` – `Retry-After` v sekundách počas údržby
+    /// * `STORE_GRPC_ENABLED` – `true` zapne gRPC rozhranie
+    /// * `STORE_GRPC_ADDR` – adresa gRPC servera
+    /// * `STORE_SHUTDOWN_TIMEOUT` – čas na dokončenie požiadaviek pri vypínaní (sekundy)
+    /// * `STORE_CACHE_ENABLED` – `false` vypne cache dotazov
+    /// * `STORE_CACHE_TTL` – doba platnosti položky cache dotazov (sekundy)
+    /// * `STORE_STRICT_JSON` – `false` povolí neznáme polia v JSON telách
+    /// * `STORE_LIST_SHAPE` – predvolený tvar zoznamov (`array`, `envelope`)
+    /// * `STORE_SHOP_NAME` – názov obchodu v hlavičke PDF zostáv
+    /// * `STORE_STATS_SNAPSHOT_INTERVAL` – interval snímok štatistík (sekundy, 0 = vypnuté)
+    /// * `STORE_ALERT_CHECK_INTERVAL` – interval kontroly upozornení na zásoby (sekundy, 0 = vypnuté)
+    /// * `STORE_RETENTION_EMPLOYEE_YEARS` – roky uchovávania údajov bývalých zamestnancov (`0` = bez obmedzenia)
+    /// * `STORE_RETENTION_ATTENDANCE_YEARS` – roky uchovávania dochádzky (`0` = bez obmedzenia)
+    /// * `STORE_RETENTION_ACTION` – `anonymize` alebo `delete`
+    /// * `STORE_RETENTION_INTERVAL` – interval uplatňovania politiky uchovávania (sekundy, 0 = vypnuté)
+    /// * `STORE_PRICE_CHANGE_INTERVAL` – interval uplatňovania naplánovaných zmien cien (sekundy, 0 = vypnuté)
+    /// * `STORE_AVERAGE_COST_ON_RESTOCK` – `true` prepočíta nákupnú cenu na vážený priemer pri príjme tovaru
+    /// * `STORE_WEEKLY_HOURS_NORM` – týždenná norma hodín pre výpočet nadčasov
+    /// * `STORE_TX_TIMEOUT` – čas nečinnosti transakcie z `POST /tx` pred zrušením (sekundy)
+    /// * `STORE_SUPPLIER_WEIGHT_ON_TIME` – váha včasných dodávok v skóre dodávateľa
+    /// * `STORE_SUPPLIER_WEIGHT_PRICE_STABILITY` – váha stálosti cien v skóre dodávateľa
+    /// * `STORE_SUPPLIER_WEIGHT_AVAILABILITY` – váha dostupnosti tovaru v skóre dodávateľa
+    /// * `STORE_AGING_BUCKETS` – hranice vekových skupín zásob v dňoch (napr. `30,60,90`)
+    ///
+    /// Prázdne premenné sa ignorujú.
+    ///
+    /// # Arguments
+    /// * `lookup` – zdroj premenných (v `load` prostredie procesu)
+    ///
+    /// # Errors
+    /// Ak premenná nemá platnú hodnotu pre svoje pole
+    pub fn apply_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<()> {
+        let var = |key: &str| lookup(key).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+        override_parsed(&mut self.max_result_rows, "STORE_MAX_RESULT_ROWS", "max_result_rows", &var)?;
+        override_parsed(&mut self.low_stock_threshold, "STORE_LOW_STOCK_THRESHOLD", "low_stock_threshold", &var)?;
+        override_parsed(&mut self.reorder_target, "STORE_REORDER_TARGET", "reorder_target", &var)?;
+        if let Some(key) = var("STORE_ADMIN_API_KEY") {
+            self.admin_api_key = Some(key);
+        }
+        if let Some(key) = var("STORE_EDITOR_API_KEY") {
+            self.editor_api_key = Some(key);
+        }
+        if let Some(key) = var("STORE_VIEWER_API_KEY") {
+            self.viewer_api_key = Some(key);
+        }
+        if let Some(secret) = var("STORE_JWT_SECRET") {
+            self.jwt_secret = Some(secret);
+        }
+        if let Some(path) = var("STORE_JWT_PUBLIC_KEY_PATH") {
+            self.jwt_public_key_path = Some(path);
+        }
+        override_parsed(&mut self.log_bodies, "STORE_LOG_BODIES", "log_bodies", &var)?;
+        override_parsed(&mut self.log_body_limit, "STORE_LOG_BODY_LIMIT", "log_body_limit", &var)?;
+        override_parsed(&mut self.max_upload_size, "STORE_MAX_UPLOAD_SIZE", "max_upload_size", &var)?;
+        override_parsed(&mut self.price_rounding, "STORE_PRICE_ROUNDING", "price_rounding", &var)?;
+        if let Some(value) = var("STORE_MIN_MARGIN") {
+            let rule = value
+                .parse()
+                .map_err(|e| anyhow!("premenná STORE_MIN_MARGIN (pole `margin_policy.default`): {e}"))?;
+            self.margin_policy.default = Some(rule);
+        }
+        if let Some(value) = var("STORE_CATEGORY_MIN_MARGINS") {
+            self.margin_policy.categories = MarginPolicy::parse_categories(&value)
+                .map_err(|e| anyhow!("premenná STORE_CATEGORY_MIN_MARGINS (pole `margin_policy.categories`): {e}"))?;
+        }
+        override_parsed(&mut self.phone_country, "STORE_PHONE_COUNTRY", "phone_country", &var)?;
+        override_parsed(&mut self.max_text_length, "STORE_MAX_TEXT_LENGTH", "max_text_length", &var)?;
+        override_parsed(&mut self.max_long_text_length, "STORE_MAX_LONG_TEXT_LENGTH", "max_long_text_length", &var)?;
+        override_parsed(&mut self.sku_prefix_pattern, "STORE_SKU_PREFIX_PATTERN", "sku_prefix_pattern", &var)?;
+        let mut workers = self.worker_threads.unwrap_or(0);
+        override_parsed(&mut workers, "STORE_WORKER_THREADS", "worker_threads", &var)?;
+        self.worker_threads = Some(workers).filter(|n| *n > 0);
+        override_parsed(&mut self.max_blocking_threads, "STORE_MAX_BLOCKING_THREADS", "max_blocking_threads", &var)?;
+        override_parsed(&mut self.db_path, "STORE_DB_PATH", "db_path", &var)?;
+        override_parsed(&mut self.db_max_connections, "STORE_DB_MAX_CONNECTIONS", "db_max_connections", &var)?;
+        override_parsed(&mut self.slow_query_threshold, "STORE_SLOW_QUERY_THRESHOLD", "slow_query_threshold", &var)?;
+        if let Some(value) = var("STORE_STORES") {
+            self.stores = parse_stores(&value).map_err(|e| anyhow!("premenná STORE_STORES (pole `stores`): {e}"))?;
+        }
+        override_parsed(&mut self.read_only, "STORE_READ_ONLY", "read_only", &var)?;
+        override_parsed(&mut self.json_path, "STORE_JSON_PATH", "json_path", &var)?;
+        override_parsed(&mut self.json_sync, "STORE_JSON_SYNC", "json_sync", &var)?;
+        override_parsed(&mut self.backup_keep, "STORE_BACKUP_KEEP", "backup_keep", &var)?;
+        override_parsed(&mut self.listen_addr, "STORE_LISTEN_ADDR", "listen_addr", &var)?;
+        override_parsed(&mut self.dev_mode, "STORE_DEV_MODE", "dev_mode", &var)?;
+        override_parsed(&mut self.web_dir, "STORE_WEB_DIR", "web_dir", &var)?;
+        override_parsed(
+            &mut self.maintenance_retry_after,
+            "STORE_MAINTENANCE_RETRY_AFTER",
+            "maintenance_retry_after",
+            &var,
+        )?;
+        override_parsed(&mut self.grpc_enabled, "STORE_GRPC_ENABLED", "grpc_enabled", &var)?;
+        override_parsed(&mut self.grpc_addr, "STORE_GRPC_ADDR", "grpc_addr", &var)?;
+        override_parsed(&mut self.shutdown_timeout, "STORE_SHUTDOWN_TIMEOUT", "shutdown_timeout", &var)?;
+        override_parsed(&mut self.cache_enabled, "STORE_CACHE_ENABLED", "cache_enabled", &var)?;
+        override_parsed(&mut self.cache_ttl, "STORE_CACHE_TTL", "cache_ttl", &var)?;
+        override_parsed(&mut self.strict_json, "STORE_STRICT_JSON", "strict_json", &var)?;
+        override_parsed(&mut self.list_shape, "STORE_LIST_SHAPE", "list_shape", &var)?;
+        override_parsed(&mut self.shop_name, "STORE_SHOP_NAME", "shop_name", &var)?;
+        override_parsed(
+            &mut self.stats_snapshot_interval,
+            "STORE_STATS_SNAPSHOT_INTERVAL",
+            "stats_snapshot_interval",
+            &var,
+        )?;
+        override_parsed(&mut self.alert_check_interval, "STORE_ALERT_CHECK_INTERVAL", "alert_check_interval", &var)?;
+        let mut years = self.retention.employee_years.unwrap_or(0);
+        override_parsed(&mut years, "STORE_RETENTION_EMPLOYEE_YEARS", "retention.employee_years", &var)?;
+        self.retention.employee_years = Some(years).filter(|n| *n > 0);
+        let mut years = self.retention.attendance_years.unwrap_or(0);
+        override_parsed(&mut years, "STORE_RETENTION_ATTENDANCE_YEARS", "retention.attendance_years", &var)?;
+        self.retention.attendance_years = Some(years).filter(|n| *n > 0);
+        override_parsed(&mut self.retention.action, "STORE_RETENTION_ACTION", "retention.action", &var)?;
+        override_parsed(&mut self.retention_interval, "STORE_RETENTION_INTERVAL", "retention_interval", &var)?;
+        override_parsed(&mut self.sale_expiry_interval, "STORE_SALE_EXPIRY_INTERVAL", "sale_expiry_interval", &var)?;
+        override_parsed(&mut self.price_change_interval, "STORE_PRICE_CHANGE_INTERVAL", "price_change_interval", &var)?;
+        override_parsed(
+            &mut self.average_cost_on_restock,
+            "STORE_AVERAGE_COST_ON_RESTOCK",
+            "average_cost_on_restock",
+            &var,
+        )?;
+        override_parsed(&mut self.block_overstock, "STORE_BLOCK_OVERSTOCK", "block_overstock", &var)?;
+        override_parsed(&mut self.weekly_hours_norm, "STORE_WEEKLY_HOURS_NORM", "weekly_hours_norm", &var)?;
+        override_parsed(&mut self.tx_timeout, "STORE_TX_TIMEOUT", "tx_timeout", &var)?;
+        let weights = &mut self.supplier_score_weights;
+        override_parsed(&mut weights.on_time, "STORE_SUPPLIER_WEIGHT_ON_TIME", "supplier_score_weights.on_time", &var)?;
+        override_parsed(
+            &mut weights.price_stability,
+            "STORE_SUPPLIER_WEIGHT_PRICE_STABILITY",
+            "supplier_score_weights.price_stability",
+            &var,
+        )?;
+        override_parsed(
+            &mut weights.availability,
+            "STORE_SUPPLIER_WEIGHT_AVAILABILITY",
+            "supplier_score_weights.availability",
+            &var,
+        )?;
+        override_parsed(&mut self.aging_buckets, "STORE_AGING_BUCKETS", "aging_buckets", &var)?;
+        Ok(())
+    }
+
+    /// Vráti najväčšie dĺžky textových polí.
+    pub fn text_limits(&self) -> TextLimits {
+        TextLimits { text: self.max_text_length, long_text: self.max_long_text_length }
+    }
+
+    /// Vráti konfiguráciu ako JSON so zamaskovanými tajomstvami.
+    ///
+    /// Nastavené polia zo `SECRET_FIELDS` sa nahradia maskou, nenastavené zostanú
+    /// `null`, takže je vidieť, či je tajomstvo zadané. Cesta k verejnému kľúču
+    /// JWT tajomstvom nie je.
+    pub fn sanitized(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = value.as_object_mut() {
+            for field in SECRET_FIELDS {
+                if let Some(secret) = fields.get_mut(*field).filter(|v| !v.is_null()) {
+                    *secret = Value::from(REDACTED);
+                }
+            }
+        }
+        value
+    }
+
+    /// Skontroluje hodnoty, ktoré typ poľa sám neobmedzuje.
+    ///
+    /// # Errors
+    /// Názov prvého neplatného poľa a dôvod
+    pub fn validate(&self) -> Result<()> {
+        if self.max_result_rows == 0 {
+            bail!("pole `max_result_rows`: musí byť aspoň 1");
+        }
+        if self.worker_threads == Some(0) {
+            bail!("pole `worker_threads`: musí byť aspoň 1 (bez hodnoty = počet jadier CPU)");
+        }
+        if self.max_blocking_threads == 0 {
+            bail!("pole `max_blocking_threads`: musí byť aspoň 1");
+        }
+        if self.db_max_connections == 0 {
+            bail!("pole `db_max_connections`: musí byť aspoň 1");
+        }
+        if !self.weekly_hours_norm.is_finite() || self.weekly_hours_norm <= 0.0 {
+            bail!("pole `weekly_hours_norm`: musí byť kladné číslo");
+        }
+        if self.tx_timeout == 0 {
+            bail!("pole `tx_timeout`: musí byť aspoň 1");
+        }
+        let weights = self.supplier_score_weights;
+        for (name, weight) in [
+            ("on_time", weights.on_time),
+            ("price_stability", weights.price_stability),
+            ("availability", weights.availability),
+        ] {
+            if !weight.is_finite() || weight < 0.0 {
+                bail!("pole `supplier_score_weights.{name}`: musí byť nezáporné číslo");
+            }
+        }
+        if weights.on_time + weights.price_stability + weights.availability <= 0.0 {
+            bail!("pole `supplier_score_weights`: aspoň jedna váha musí byť kladná");
+        }
+        let buckets = &self.aging_buckets.0;
+        if buckets.is_empty() || buckets[0] == 0 || buckets.windows(2).any(|w| w[0] >= w[1]) {
+            bail!("pole `aging_buckets`: musí obsahovať kladné hranice v rastúcom poradí");
+        }
+        if self.retention.employee_years == Some(0) {
+            bail!("pole `retention.employee_years`: musí byť aspoň 1 (bez hodnoty = bez obmedzenia)");
+        }
+        if self.retention.attendance_years == Some(0) {
+            bail!("pole `retention.attendance_years`: musí byť aspoň 1 (bez hodnoty = bez obmedzenia)");
+        }
+        if self.max_text_length == 0 {
+            bail!("pole `max_text_length`: musí byť aspoň 1");
+        }
+        if self.max_long_text_length == 0 {
+            bail!("pole `max_long_text_length`: musí byť aspoň 1");
+        }
+        if let Err(e) = sku::check_pattern(&self.sku_prefix_pattern) {
+            bail!("pole `sku_prefix_pattern`: {e}");
+        }
+        if phone::country_code(&self.phone_country).is_none() {
+            bail!("pole `phone_country`: nepodporovaná krajina {:?}", self.phone_country);
+        }
+        if self.db_path.trim().is_empty() {
+            bail!("pole `db_path`: nesmie byť prázdne");
+        }
+        for (id, path) in &self.stores {
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                bail!("pole `stores`: neplatné ID obchodu {id:?} (povolené sú písmená, číslice, `-` a `_`)");
+            }
+            if path.trim().is_empty() {
+                bail!("pole `stores.{id}`: nesmie byť prázdne");
+            }
+            if *path == self.db_path || self.stores.iter().any(|(other, p)| other < id && p == path) {
+                bail!("pole `stores.{id}`: databázu {path:?} už používa iný obchod");
+            }
+        }
+        if self.json_sync && self.json_path.trim().is_empty() {
+            bail!("pole `json_path`: nesmie byť prázdne");
+        }
+        if self.listen_addr.rsplit_once(':').is_none_or(|(_, port)| port.parse::<u16>().is_err()) {
+            bail!("pole `listen_addr`: očakáva sa adresa v tvare host:port");
+        }
+        if self.jwt_secret.is_some() && self.jwt_public_key_path.is_some() {
+            bail!("polia `jwt_secret` a `jwt_public_key_path`: nastaviť sa dá len jedno (HS256 alebo RS256)");
+        }
+        if self.grpc_enabled && self.grpc_addr.rsplit_once(':').is_none_or(|(_, port)| port.parse::<u16>().is_err()) {
+            bail!("pole `grpc_addr`: očakáva sa adresa v tvare host:port");
+        }
+        Ok(())
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            max_result_rows: DEFAULT_MAX_RESULT_ROWS,
+            low_stock_threshold: DEFAULT_LOW_STOCK_THRESHOLD,
+            reorder_target: DEFAULT_REORDER_TARGET,
+            admin_api_key: None,
+            editor_api_key: None,
+            viewer_api_key: None,
+            jwt_secret: None,
+            jwt_public_key_path: None,
+            log_bodies: false,
+            log_body_limit: DEFAULT_LOG_BODY_LIMIT,
+            max_upload_size: DEFAULT_MAX_UPLOAD_SIZE,
+            price_rounding: PriceRounding::None,
+            margin_policy: MarginPolicy::default(),
+            phone_country: phone::DEFAULT_COUNTRY.into(),
+            max_text_length: DEFAULT_MAX_TEXT_LENGTH,
+            max_long_text_length: DEFAULT_MAX_LONG_TEXT_LENGTH,
+            sku_prefix_pattern: sku::DEFAULT_PREFIX_PATTERN.into(),
+            worker_threads: None,
+            max_blocking_threads: DEFAULT_MAX_BLOCKING_THREADS,
+            db_path: "store.db".into(),
+            db_max_connections: DEFAULT_DB_MAX_CONNECTIONS,
+            slow_query_threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+            stores: BTreeMap::new(),
+            read_only: false,
+            json_path: "store_data.json".into(),
+            json_sync: true,
+            backup_keep: DEFAULT_BACKUP_KEEP,
+            listen_addr: "0.0.0.0:8000".into(),
+            dev_mode: false,
+            web_dir: "src/web".into(),
+            maintenance_retry_after: DEFAULT_MAINTENANCE_RETRY_AFTER,
+            grpc_enabled: false,
+            grpc_addr: "0.0.0.0:50051".into(),
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            cache_enabled: true,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            strict_json: true,
+            list_shape: ListShape::Array,
+            shop_name: DEFAULT_SHOP_NAME.into(),
+            stats_snapshot_interval: DEFAULT_STATS_SNAPSHOT_INTERVAL,
+            alert_check_interval: DEFAULT_ALERT_CHECK_INTERVAL,
+            retention: RetentionPolicy::default(),
+            retention_interval: DEFAULT_RETENTION_INTERVAL,
+            sale_expiry_interval: DEFAULT_SALE_EXPIRY_INTERVAL,
+            price_change_interval: DEFAULT_PRICE_CHANGE_INTERVAL,
+            average_cost_on_restock: false,
+            block_overstock: false,
+            weekly_hours_norm: DEFAULT_WEEKLY_HOURS_NORM,
+            tx_timeout: DEFAULT_TX_TIMEOUT,
+            supplier_score_weights: SupplierScoreWeights::default(),
+            aging_buckets: AgingBuckets::default(),
+        }
+    }
+}
+
+/// Prevedie zoznam obchodov `id=cesta.db,iné=iná.db` (premenná prostredia) na mapu.
+///
+/// # Errors
+/// Ak položka nemá tvar `id=cesta`
+fn parse_stores(value: &str) -> Result<BTreeMap<String, String>, String> {
+    value
+        .split(',')
+        .filter(|item| !item.trim().is_empty())
+        .map(|item| {
+            let (id, path) = item.split_once('=').ok_or_else(|| format!("očakáva sa id=cesta, nie {item:?}"))?;
+            Ok((id.trim().to_string(), path.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Prepíše pole hodnotou premennej prostredia, ak je nastavená.
+///
+/// # Arguments
+/// * `field` – prepisované pole
+/// * `key` – názov premennej
+/// * `name` – názov poľa v konfigurácii (do chybovej správy)
+/// * `var` – zdroj premenných
+///
+/// # Errors
+/// Ak sa hodnota nedá sparsovať
+fn override_parsed<T>(field: &mut T, key: &str, name: &str, var: &impl Fn(&str) -> Option<String>) -> Result<()>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    if let Some(value) = var(key) {
+        *field = value
+            .parse()
+            .map_err(|e| anyhow!("premenná {key} (pole `{name}`): neplatná hodnota {value:?}: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Deserializuje pole z textu rovnako ako z premennej prostredia (`FromStr`).
+fn deserialize_parsed<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+}
+
+/// Serializuje pole ako text v tvare konfigurácie (`Display`).
+fn serialize_display<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    serializer.collect_str(value)
+}
+
+/// Nájde pole TOML tabuľky, ktorého kľúč alebo hodnota obsahuje danú pozíciu.
+///
+/// # Arguments
+/// * `table` – sparsovaná tabuľka so spanmi
+/// * `pos` – pozícia chyby v texte
+///
+/// # Returns
+/// Cesta k poľu (`sekcia.pole`) alebo `None`
+fn field_path(table: &toml::de::DeTable<'_>, pos: usize) -> Option<String> {
+    table.iter().find_map(|(key, value)| {
+        let key_span = key.span();
+        let value_span = value.span();
+        if !key_span.contains(&pos) && !value_span.contains(&pos) {
+            return None;
+        }
+        let nested = value.get_ref().as_table().and_then(|t| field_path(t, pos));
+        Some(match nested {
+            Some(inner) => format!("{}.{inner}", key.get_ref()),
+            None => key.get_ref().to_string(),
+        })
+    })
+}
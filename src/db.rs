@@ -1,32 +1,352 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
-use chrono::NaiveDate;
-use sqlx::{sqlite::SqlitePoolOptions, Arguments, Row, SqlitePool};
-use crate::structs::{Employee, Product};
+use log::LevelFilter;
+use sqlx::{
+    sqlite::{SqliteArguments, SqliteConnectOptions, SqliteConnection, SqlitePoolOptions, SqliteRow, SqliteTransactionManager},
+    Acquire, Arguments, ConnectOptions, Encode, Row, Sqlite, SqlitePool, TransactionManager, Type,
+};
+use chrono::{Datelike, Local, Months, NaiveDate, NaiveDateTime, NaiveTime};
+use tracing::{field::Empty, instrument, Span};
+use crate::auth::{self, Role};
+use crate::cache::{Entity, Generations};
+use crate::config::{AgingBuckets, Config, SupplierScoreWeights, DEFAULT_SLOW_QUERY_THRESHOLD};
+use crate::error::StoreError;
+use crate::phone;
+use crate::pricing::{self, MarginPolicy, PriceRounding};
+use crate::retention::{RetentionAction, RetentionPolicy};
+use crate::sku;
+use crate::structs::{
+    AgingBucket, Alert, AlertEvaluation, ApiKey, AverageCost, ApiKeyRequest, CreatedApiKey, AttendanceEntry, AuditEntry, AuditFilter, BasketStats, Bundle, BundleComponent, BulkUpdateResult, BulkUpdateStatus, CategoryLimit, CategoryRemap, CategoryMargin, CategoryProfit, CategoryTurnover, CategoryDiscount, CategoryGmroi, CoPurchase, DailyHours, DailyReport, DashboardInventory, DashboardSummary, DeletedIds, DeltaApplyReport, DiscountReport, Employee, EmployeeHours, EmployeeRevenue, ExpiringBatch, Gmroi, HeadcountReport, HoursReport, PayrollSummary,
+    ImportLineError, ImportRecord, IntegrityReport, InventoryTurnover, OrderFilter, InventoryValueLine, InventoryValueReport, DeadStockLine, DeadStockReport, DeletionReport, LowStockLine, LowStockReport, MarginViolation, MissingClockOut, MonthlyAvg, MonthlyHeadcount, MovementDirection, Order, OrderItem, OrderReturn, OrgNode, OrphanedReferences, PriceAdjustment, PriceAnomaly, PriceAnomalyReason, PriceChange, PriceElasticity, PricePoint, Product, ProductBatch, ProductImage, ProductLookup, ProductSales, ProductTranslation,
+    ProfitReport, PurchaseOrder, SalaryOutlier, PurchaseOrderDiscrepancy, PurchaseOrderItem, PurchaseOrderReceipt,
+    PurchaseOrderReceiptResult, PurchaseOrderStatus, ReorderSuggestion, Reservation, ReservationRequest, ReservationStatus, RetentionCandidate, RetentionReport, ReturnItem, SavedSearch, ScheduledPriceChange, SearchIndexEntry, Scorecard, Seasonality, SearchEntity, SellThroughReport, CategorySellThrough, SupplierScore, ShiftAssignment, ShiftAssignmentRequest, ShiftCoverage, ShrinkageReport, CategoryShrinkage, StatsSnapshot, StatusChangeReport, StockCoverage, StockCount, StockDeltaReport, StockDelta, StockDiscrepancy, StockSnapshot, StockMovement, StocktakeReport, SupplierOrderSheet, SyncConflict, SyncCounts, SyncLogEntry, TaggingReport, Unit, ValuationLine, ValuationMethod, ValuationReport, WeeklyHours,
+};
+
+/// Kód šarže pre tovar bez zadanej šarže (a pre stav zásob spred evidencie šarží).
+pub const LEGACY_LOT: &str = "legacy";
+
+/// Zistí, či chyba vznikla pokusom o zápis do databázy otvorenej len na čítanie
+/// (`SQLITE_READONLY` vrátane rozšírených kódov).
+pub fn is_read_only_error(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<sqlx::Error>()
+        .and_then(|e| e.as_database_error())
+        .and_then(|e| e.code())
+        .and_then(|code| code.parse::<i32>().ok())
+        .is_some_and(|code| code & 0xff == 8)
+}
+
+/// Druh upozornenia na nízky stav zásob v tabuľke `alerts`.
+pub const ALERT_LOW_STOCK: &str = "low_stock";
+
+/// Najväčší počet produktov v náhodnej vzorke (`random_products`).
+pub const MAX_SAMPLE_SIZE: u32 = 100;
+
+/// Najväčší počet produktov v zozname novo pridaných (`recently_added_products`).
+pub const MAX_RECENT_PRODUCTS: u32 = 100;
+
+/// Najviac väzieb produkt–štítok v jednom `INSERT` (limit parametrov SQLite).
+const TAG_INSERT_CHUNK: usize = 500;
+
+/// Najviac ID v jednom dotaze `WHERE id IN (...)` (limit parametrov SQLite).
+const ID_QUERY_CHUNK: usize = 500;
+
+/// Najväčší počet produktov v jednom porovnaní (`/products/compare`).
+pub const MAX_COMPARE_PRODUCTS: usize = 10;
+
+/// Najväčšia hĺbka hierarchie nadriadených, ktorú rekurzívne dotazy prejdú
+/// (poistka proti cyklom v starších dátach).
+const MAX_CHAIN_DEPTH: u32 = 100;
+
+/// Dodacia lehota produktu bez vlastnej `lead_time_days` (dni).
+pub const DEFAULT_LEAD_TIME_DAYS: u32 = 7;
+
+/// Obdobie, z ktorého sa počíta priemerný denný predaj pre `reorder_list` (dni);
+/// objednávka z `reorder_list` pokryje predaj na rovnako dlhé obdobie.
+pub const SALES_VELOCITY_DAYS: u32 = 30;
+
+/// Predvolená hranica pokrytia zásob (dni), pod ktorou `low_coverage` produkt vráti.
+pub const DEFAULT_COVERAGE_DAYS: u32 = 14;
+
+/// Predvolená povolená odchýlka platu od mediánu pozície pre `salary_outliers` (podiel).
+pub const DEFAULT_SALARY_DEVIATION: f64 = 0.2;
+
+/// Počet najhodnotnejších produktov a posledných pohybov na nástenke.
+pub const DASHBOARD_ITEMS: u32 = 5;
+
+/// Najvyšší bežný pomer predajnej a nákupnej ceny; vyšší je podozrivý (`price_anomalies`).
+pub const MAX_PRICE_MARKUP: f64 = 5.0;
+
+/// Najnižší bežný pomer predajnej a nákupnej ceny; nižší je podozrivý (`price_anomalies`).
+pub const MIN_PRICE_MARKUP: f64 = 1.1;
+
+/// Odchýlka ceny od kategórie (v smerodajných odchýlkach), od ktorej je cena podozrivá.
+pub const PRICE_OUTLIER_Z: f64 = 3.0;
+
+/// Najdlhšia zmena (hodiny); otvorený záznam dochádzky starší ako toto je chýbajúci odchod.
+const MAX_SHIFT_HOURS: i64 = 16;
+
+/// Tabuľky s časom poslednej zmeny (`updated_at`), ktoré sa dajú synchronizovať z inej inštancie.
+const SYNCED_TABLES: [&str; 3] = ["employees", "products", "orders"];
+
+/// Tabuľky v rozdielovom exporte; zmazané záznamy sa evidujú v `deleted_records`.
+const DELTA_TABLES: [&str; 2] = ["employees", "products"];
+
+/// Aktuálny miestny čas s milisekundami v tvare stĺpca `updated_at`.
+const NOW_MILLIS: &str = "strftime('%Y-%m-%d %H:%M:%f', 'now', 'localtime')";
+
+/// Formát `updated_at` pri porovnávaní v SQL (rovnaký ako `NOW_MILLIS`).
+const UPDATED_AT_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+/// Tolerancia pri porovnávaní desatinných množstiev (kg, l).
+const QUANTITY_EPSILON: f64 = 1e-9;
+
+/// Podmienka platnej rezervácie: aktívna a ešte neexpirovaná (expirované sa uvoľnia pri čítaní).
+const ACTIVE_RESERVATION: &str = "status = 'active' AND expires_at > datetime('now', 'localtime')";
+
+/// Stav rezervácie; platnosť aktívnych sa vyhodnocuje pri čítaní.
+const RESERVATION_STATUS: &str =
+    "CASE WHEN status = 'active' AND expires_at <= datetime('now', 'localtime') THEN 'expired' ELSE status END";
+
+/// Výber produktov vrátane počtu zostaviteľných sád (`buildable`, pri sadách nahrádza množstvo)
+/// a množstva v platných rezerváciách (`reserved`).
+const PRODUCT_SELECT: &str = r#"
+    SELECT *, (
+        SELECT MIN(CAST(cp.quantity / c.quantity + 1e-9 AS INTEGER))
+        FROM bundle_components c
+        JOIN products cp ON cp.id = c.component_product_id
+        WHERE c.bundle_id = products.id
+    ) AS buildable, (
+        SELECT COALESCE(SUM(r.quantity), 0.0)
+        FROM reservations r
+        WHERE r.product_id = products.id
+          AND r.status = 'active' AND r.expires_at > datetime('now', 'localtime')
+    ) AS reserved
+    FROM products"#;
+
+/// Stĺpce a hodnoty pre `UPDATE ... SET` z polí čiastočného záznamu, ktoré nie sú `None`.
+#[derive(Default)]
+struct Assignments<'q> {
+    columns: Vec<&'static str>,
+    args: SqliteArguments<'q>,
+}
+
+impl<'q> Assignments<'q> {
+    /// Pridá stĺpec, ak je hodnota zadaná.
+    fn set<T>(&mut self, column: &'static str, value: Option<T>)
+    where
+        T: 'q + Encode<'q, Sqlite> + Type<Sqlite>,
+    {
+        if let Some(value) = value {
+            self.columns.push(column);
+            let _ = self.args.add(value);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Časť `SET` príkazu `UPDATE` (`a = ?, b = ?`).
+    fn sql(&self) -> String {
+        self.columns.iter().map(|c| format!("{c} = ?")).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Záznam prevzatý synchronizáciou, nájdený podľa zdroja a kľúča.
+struct SyncedRow {
+    id: u32,
+    /// ID záznamu vo vzdialenej inštancii
+    source_id: Option<u32>,
+    updated_at: Option<NaiveDateTime>,
+    /// Záznam sa od poslednej synchronizácie zmenil miestne (`updated_at > synced_at`)
+    changed_locally: bool,
+}
+
+impl SyncedRow {
+    /// Konflikt s verziou zo vzdialenej inštancie.
+    fn conflict(&self, key: &str, remote_updated_at: Option<NaiveDateTime>) -> SyncConflict {
+        SyncConflict { key: key.to_string(), local_updated_at: self.updated_at, remote_updated_at }
+    }
+}
+
+/// Nastavenia spojení zdieľané klonmi `StoreDB`.
+///
+/// Po zmene nastavení (`StoreDB::reconfigure`) pool otvára nové spojenia s novými
+/// voľbami a spojenia otvorené pred zmenou zahodí pri ďalšom vypožičaní.
+#[derive(Default)]
+struct ConnectionSettings {
+    read_only: AtomicBool,
+    changed_at: Mutex<Option<Instant>>,
+}
+
+impl ConnectionSettings {
+    /// Zaznamená zmenu nastavení.
+    fn changed(&self) {
+        *self.changed_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Zistí, či bolo spojenie s daným vekom otvorené až po poslednej zmene.
+    fn is_current(&self, age: std::time::Duration) -> bool {
+        self.changed_at.lock().unwrap().is_none_or(|at| age < at.elapsed())
+    }
+}
 
 /// Wrapper nad SQLite databázou obchodu.
 #[derive(Clone)]
 pub struct StoreDB {
     m_pool: SqlitePool,
+    m_generations: Arc<Generations>,
+    m_settings: Arc<ConnectionSettings>,
 }
 
 impl StoreDB {
-    /// Vytvorí alebo otvorí databázu a pripraví tabuľky.
+    /// Vytvorí alebo otvorí databázu podľa konfigurácie a pripraví tabuľky.
+    ///
+    /// # Arguments
+    /// * `config` – konfigurácia aplikácie (`db_path`, `db_max_connections`)
+    ///
+    /// # Returns
+    /// Inicializovaná inštancia `StoreDB`
+    ///
+    /// Pri zapnutom `read_only` sa existujúca databáza len otvorí na čítanie.
+    /// Príkazy pomalšie ako `slow_query_threshold` sa zalogujú (`slow_query`).
+    ///
+    /// # Errors
+    /// Ak zlyhá vytvorenie súboru alebo pripojenie k databáze
+    pub async fn new(config: &Config) -> Result<Self> {
+        let db = if config.read_only {
+            Self::open_read_only(&config.db_path, config.db_max_connections).await?
+        } else {
+            Self::open_with(&config.db_path, config.db_max_connections).await?
+        };
+        db.set_slow_query_threshold(Duration::from_millis(config.slow_query_threshold));
+        Ok(db)
+    }
+
+    /// Otvorí existujúcu databázu s príznakom read-only (napr. obnovenú zálohu).
+    ///
+    /// Súbor sa nevytvára a schéma sa nemigruje – po prepnutí do zápisu cez
+    /// `set_read_only(false)` preto musí databáza už mať aktuálnu schému.
+    ///
+    /// # Arguments
+    /// * `path` – cesta k súboru databázy
+    /// * `max_connections` – maximálny počet spojení v poole
+    ///
+    /// # Returns
+    /// `StoreDB` v režime len na čítanie
+    ///
+    /// # Errors
+    /// Ak súbor neexistuje alebo zlyhá pripojenie k databáze
+    pub async fn open_read_only(path: &str, max_connections: u32) -> Result<Self> {
+        let m_settings = Arc::new(ConnectionSettings::default());
+        m_settings.read_only.store(true, Ordering::Release);
+        let options = Self::connect_options(path)?.read_only(true);
+        let m_pool = Self::connect_pool(options, max_connections, &m_settings).await?;
+
+        println!("Databáza otvorená len na čítanie.");
+        Ok(Self { m_pool, m_generations: Arc::default(), m_settings })
+    }
+
+    /// Voľby spojenia k súboru databázy; príkazy pomalšie ako predvolený prah
+    /// (`DEFAULT_SLOW_QUERY_THRESHOLD`) sqlx zaloguje s úrovňou WARN, ostatné nezaloguje.
+    fn connect_options(path: &str) -> Result<SqliteConnectOptions> {
+        let threshold = Duration::from_millis(DEFAULT_SLOW_QUERY_THRESHOLD);
+        Ok(SqliteConnectOptions::from_str(&format!("sqlite:{path}"))?
+            .log_statements(LevelFilter::Off)
+            .log_slow_statements(LevelFilter::Warn, threshold))
+    }
+
+    /// Vytvorí pool, ktorý po zmene nastavení (`reconfigure`) nahradí staršie spojenia.
+    async fn connect_pool(
+        options: SqliteConnectOptions,
+        max_connections: u32,
+        settings: &Arc<ConnectionSettings>,
+    ) -> Result<SqlitePool> {
+        let settings = settings.clone();
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .before_acquire(move |_, meta| {
+                let current = settings.is_current(meta.age);
+                Box::pin(async move { Ok(current) })
+            })
+            .connect_with(options)
+            .await?;
+        Ok(pool)
+    }
+
+    /// Zmení voľby, s ktorými pool otvára spojenia; staršie spojenia sa zatvoria
+    /// pri ďalšom vypožičaní.
+    fn reconfigure(&self, change: impl FnOnce(SqliteConnectOptions) -> SqliteConnectOptions) {
+        let options = change((*self.m_pool.connect_options()).clone());
+        self.m_pool.set_connect_options(options);
+        self.m_settings.changed();
+    }
+
+    /// Zapne alebo vypne režim len na čítanie.
+    ///
+    /// Nové spojenia sa otvárajú s príznakom `SQLITE_OPEN_READONLY`, takže zápis
+    /// zlyhá aj mimo HTTP vrstvy (gRPC, GraphQL, úlohy na pozadí). Spojenia otvorené
+    /// pred prepnutím sa zatvoria pri ďalšom vypožičaní z poolu.
+    ///
+    /// # Arguments
+    /// * `read_only` – `true` zapne režim len na čítanie
+    pub fn set_read_only(&self, read_only: bool) {
+        self.reconfigure(|options| options.read_only(read_only));
+        self.m_settings.read_only.store(read_only, Ordering::Release);
+    }
+
+    /// Nastaví čas, od ktorého sa SQL príkaz zaloguje ako pomalý (`slow_query`).
+    ///
+    /// # Arguments
+    /// * `threshold` – prah trvania príkazu (nula = každý príkaz)
+    pub fn set_slow_query_threshold(&self, threshold: Duration) {
+        self.reconfigure(|options| options.log_slow_statements(LevelFilter::Warn, threshold));
+    }
+
+    /// Zistí, či je databáza v režime len na čítanie.
+    pub fn is_read_only(&self) -> bool {
+        self.m_settings.read_only.load(Ordering::Acquire)
+    }
+
+    /// Vytvorí alebo otvorí databázu v zadanom súbore a pripraví tabuľky.
+    ///
+    /// # Arguments
+    /// * `path` – cesta k súboru databázy
+    ///
+    /// # Returns
+    /// Inicializovaná inštancia `StoreDB`
+    ///
+    /// # Errors
+    /// Ak zlyhá vytvorenie súboru alebo pripojenie k databáze
+    pub async fn open(path: &str) -> Result<Self> {
+        Self::open_with(path, 5).await
+    }
+
+    /// Vytvorí alebo otvorí databázu so zadaným počtom spojení a pripraví tabuľky.
+    ///
+    /// # Arguments
+    /// * `path` – cesta k súboru databázy
+    /// * `max_connections` – maximálny počet spojení v poole
     ///
     /// # Returns
     /// Inicializovaná inštancia `StoreDB`
     ///
     /// # Errors
     /// Ak zlyhá vytvorenie súboru alebo pripojenie k databáze
-    pub async fn new() -> Result<Self> {
+    pub async fn open_with(path: &str, max_connections: u32) -> Result<Self> {
         std::fs::OpenOptions::new()
             .create(true)
+            .truncate(false)
             .write(true)
-            .open("store.db")?;
+            .open(path)?;
 
-        let m_pool = SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect("sqlite:store.db")
-            .await?;
+        let m_settings = Arc::new(ConnectionSettings::default());
+        let m_pool = Self::connect_pool(Self::connect_options(path)?, max_connections, &m_settings).await?;
 
         // employees
         sqlx::query(
@@ -40,16 +360,24 @@ impl StoreDB {
                 shift TEXT,
                 salary REAL,
                 phone_number TEXT,
+                phone_e164 TEXT,
                 email TEXT,
                 status INTEGER,
                 note TEXT,
-                hire_date TEXT
+                hire_date TEXT,
+                manager_id INTEGER REFERENCES employees(id),
+                date_remove TEXT
             );
             "#,
         )
             .execute(&m_pool)
             .await?;
 
+        // vyhľadanie podľa e-mailu bez ohľadu na veľkosť písmen (`get_employee_by_email`)
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_employees_email ON employees (lower(trim(email)))")
+            .execute(&m_pool)
+            .await?;
+
         // products
         sqlx::query(
             r#"
@@ -57,11 +385,20 @@ impl StoreDB {
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 name TEXT NOT NULL,
                 category TEXT NOT NULL,
-                quantity INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                unit TEXT NOT NULL DEFAULT 'piece',
+                pack_size INTEGER,
+                is_bundle INTEGER NOT NULL DEFAULT 0,
                 status INTEGER,
                 bar_code INTEGER NOT NULL,
+                sku TEXT,
+                min_stock REAL,
+                max_stock REAL,
+                lead_time_days INTEGER,
                 cost_price REAL NOT NULL,
                 sell_price REAL NOT NULL,
+                sale_price REAL,
+                sale_ends TEXT,
                 description TEXT,
                 brand TEXT,
                 supplier TEXT,
@@ -75,275 +412,10615 @@ impl StoreDB {
             .execute(&m_pool)
             .await?;
 
-        println!("Databáza pripravená.");
-        Ok(Self { m_pool })
-    }
-
-    // ==========================
-    // Employees
-    // ==========================
-
-    /// Pridá zamestnanca do databázy.
-    ///
-    /// # Arguments
-    /// * `employee` – dáta zamestnanca
-    ///
-    /// # Errors
-    /// Ak zlyhá zápis do databázy
-    pub async fn add_employee_to_store_db(&self, employee: &Employee) -> Result<()> {
+        // orders
         sqlx::query(
             r#"
-            INSERT INTO employees (
-                name, surname, position, department, shift, salary,
-                phone_number, email, status, note, hire_date
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            CREATE TABLE IF NOT EXISTS orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                employee_id INTEGER,
+                status TEXT NOT NULL DEFAULT 'open',
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (employee_id) REFERENCES employees(id)
+            );
             "#,
         )
-            .bind(employee.name.clone())
-            .bind(employee.surname.clone())
-            .bind(employee.position.clone())
-            .bind(employee.department.clone())
-            .bind(employee.shift.clone())
-            .bind(employee.salary.clone())
-            .bind(employee.phone_number.clone())
-            .bind(employee.email.clone())
-            .bind(employee.status.clone())
-            .bind(employee.note.clone())
-            .bind(employee.hire_date.clone())
-            .execute(&self.m_pool)
+            .execute(&m_pool)
             .await?;
 
-        Ok(())
-    }
-
-    /// Vymaže zamestnanca podľa ID.
-    ///
-    /// # Arguments
-    /// * `id` – ID zamestnanca
-    ///
-    /// # Returns
-    /// `true` ak bol záznam vymazaný
-    pub async fn delete_employee(&self, id: u32) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM employees WHERE id = ?")
-            .bind(id)
-            .execute(&self.m_pool)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS order_items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                product_id INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                unit_price REAL NOT NULL,
+                regular_price REAL,
+                FOREIGN KEY (order_id) REFERENCES orders(id),
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
             .await?;
-        Ok(result.rows_affected() > 0)
-    }
-
-    /// Aktualizuje zamestnanca podľa ID.
-    ///
-    /// Aktualizujú sa len polia, ktoré nie sú `None`.
-    ///
-    /// # Arguments
-    /// * `employee` – nové dáta (musí obsahovať `id`)
-    ///
-    /// # Returns
-    /// `true` ak bol záznam zmenený
-    pub async fn update_employee(&self, employee: &Employee) -> Result<bool> {
-        let id = match employee.id {
-            Some(id) => id,
-            None => return Ok(false),
-        };
-
-        let mut query = String::from("UPDATE employees SET ");
-        let mut args = sqlx::sqlite::SqliteArguments::default();
-        let mut updates = Vec::new();
-
-        if let Some(v) = &employee.name { updates.push("name = ?"); args.add(v); }
-        if let Some(v) = &employee.surname { updates.push("surname = ?"); args.add(v); }
-        if let Some(v) = &employee.position { updates.push("position = ?"); args.add(v); }
-        if let Some(v) = &employee.department { updates.push("department = ?"); args.add(v); }
-        if let Some(v) = &employee.shift { updates.push("shift = ?"); args.add(v); }
-        if let Some(v) = &employee.salary { updates.push("salary = ?"); args.add(v); }
-        if let Some(v) = &employee.phone_number { updates.push("phone_number = ?"); args.add(v); }
-        if let Some(v) = &employee.email { updates.push("email = ?"); args.add(v); }
-        if let Some(v) = &employee.status { updates.push("status = ?"); args.add(v); }
-        if let Some(v) = &employee.note { updates.push("note = ?"); args.add(v); }
-        if let Some(v) = &employee.hire_date { updates.push("hire_date = ?"); args.add(v); }
-
-        if updates.is_empty() {
-            return Ok(false);
-        }
-
-        query.push_str(&updates.join(", "));
-        query.push_str(" WHERE id = ?");
-        args.add(id);
-
-        let result = sqlx::query_with(&query, args).execute(&self.m_pool).await?;
-        Ok(result.rows_affected() > 0)
-    }
-
-    /// Vráti zoznam zamestnancov podľa filtra.
-    ///
-    /// # Arguments
-    /// * `employee` – filter (polia `None` sa ignorujú)
-    ///
-    /// # Returns
-    /// Zoznam zamestnancov
-    pub async fn get_employees(&self, employee: Employee) -> Result<Vec<Employee>, sqlx::Error> {
-        let mut query = String::from("SELECT * FROM Employees WHERE 1=1");
-        let mut args = sqlx::sqlite::SqliteArguments::default();
-
-        if let Some(id) = employee.id { query.push_str(" AND id = ?"); args.add(id); }
-        if let Some(name) = employee.name {
-            if !name.is_empty() { query.push_str(" AND name LIKE ?"); args.add(format!("%{}%", name)); }
-        }
-        if let Some(surname) = employee.surname {
-            if !surname.is_empty() { query.push_str(" AND surname LIKE ?"); args.add(format!("%{}%", surname)); }
-        }
-        if let Some(position) = employee.position { query.push_str(" AND position = ?"); args.add(position); }
-        if let Some(department) = employee.department { query.push_str(" AND department = ?"); args.add(department); }
-        if let Some(shift) = employee.shift { query.push_str(" AND shift = ?"); args.add(shift); }
-        if let Some(salary) = employee.salary { query.push_str(" AND salary = ?"); args.add(salary); }
-        if let Some(phone) = employee.phone_number { query.push_str(" AND phone_number = ?"); args.add(phone); }
-        if let Some(email) = employee.email { query.push_str(" AND email = ?"); args.add(email); }
-        if let Some(status) = employee.status { query.push_str(" AND status = ?"); args.add(status); }
-        if let Some(note) = employee.note {
-            if !note.is_empty() { query.push_str(" AND note LIKE ?"); args.add(format!("%{}%", note)); }
-        }
-        if let Some(date) = employee.hire_date { query.push_str(" AND hire_date = ?"); args.add(date); }
-
-        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
-
-        Ok(rows.into_iter().map(|row| Employee {
-            id: row.get::<Option<i64>, _>("id").map(|v| v as u32),
-            name: row.get("name"),
-            surname: row.get("surname"),
-            position: row.get("position"),
-            department: row.get("department"),
-            shift: row.get("shift"),
-            salary: row.get("salary"),
-            phone_number: row.get("phone_number"),
-            email: row.get("email"),
-            status: row.get::<Option<i64>, _>("status").map(|v| v == 1),
-            note: row.get("note"),
-            hire_date: row.get("hire_date"),
-        }).collect())
-    }
-
-    // ==========================
-    // Products
-    // ==========================
 
-    /// Pridá produkt do databázy.
-    pub async fn add_product_to_store_db(&self, product: &Product) -> Result<()> {
+        // returns
         sqlx::query(
             r#"
-            INSERT INTO products (
-                name, category, quantity, status, bar_code, cost_price, sell_price,
-                description, brand, supplier, employee_id, date_added, date_remove
-            )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            CREATE TABLE IF NOT EXISTS order_returns (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                order_id INTEGER NOT NULL,
+                employee_id INTEGER,
+                note TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (order_id) REFERENCES orders(id)
+            );
             "#,
         )
-            .bind(product.name.clone())
-            .bind(product.category.clone())
-            .bind(product.quantity.clone())
-            .bind(product.status.clone())
-            .bind(product.bar_code.clone())
-            .bind(product.cost_price.clone())
-            .bind(product.sell_price.clone())
-            .bind(product.description.clone())
-            .bind(product.brand.clone())
-            .bind(product.supplier.clone())
-            .bind(product.employee_id.clone())
-            .bind(product.date_added.clone())
-            .bind(product.date_remove.clone())
-            .execute(&self.m_pool)
+            .execute(&m_pool)
             .await?;
 
-        Ok(())
-    }
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS return_items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                return_id INTEGER NOT NULL,
+                product_id INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                unit_price REAL NOT NULL,
+                FOREIGN KEY (return_id) REFERENCES order_returns(id),
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
 
-    /// Vymaže produkt podľa ID.
-    pub async fn delete_product(&self, id: u32) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM products WHERE id = ?")
-            .bind(id)
-            .execute(&self.m_pool)
+        // purchase orders
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS purchase_orders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                supplier TEXT,
+                status TEXT NOT NULL DEFAULT 'draft',
+                created_at TEXT NOT NULL,
+                expected_date TEXT
+            );
+            "#,
+        )
+            .execute(&m_pool)
             .await?;
-        Ok(result.rows_affected() > 0)
-    }
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS purchase_order_items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                purchase_order_id INTEGER NOT NULL,
+                product_id INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                unit_cost REAL NOT NULL,
+                received_quantity REAL NOT NULL DEFAULT 0,
+                FOREIGN KEY (purchase_order_id) REFERENCES purchase_orders(id),
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // uzavreté dni (Z-report)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS closed_days (
+                date TEXT PRIMARY KEY,
+                closed_at TEXT NOT NULL
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // stock movements
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS stock_movements (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                product_id INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                reason TEXT NOT NULL,
+                reference TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_stock_movements_product ON stock_movements (product_id, created_at)")
+            .execute(&m_pool)
+            .await?;
+
+        // šarže produktov
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS product_batches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                product_id INTEGER NOT NULL,
+                lot_code TEXT NOT NULL,
+                expiry_date TEXT,
+                quantity REAL NOT NULL,
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // šarže sa hľadajú podľa produktu pri každom príjme a výdaji (aj pri hromadnom importe)
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_product_batches_product ON product_batches (product_id)")
+            .execute(&m_pool)
+            .await?;
+
+        // nákupné dávky (príjmy s nákupnou cenou) pre oceňovanie zásob FIFO a priemerom
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS stock_lots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                product_id INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                unit_cost REAL NOT NULL,
+                received_at TEXT NOT NULL,
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_stock_lots_product ON stock_lots (product_id)")
+            .execute(&m_pool)
+            .await?;
+
+        // zloženie sád
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS bundle_components (
+                bundle_id INTEGER NOT NULL,
+                component_product_id INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                PRIMARY KEY (bundle_id, component_product_id),
+                FOREIGN KEY (bundle_id) REFERENCES products(id),
+                FOREIGN KEY (component_product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // preklady názvov a popisov produktov
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS product_translations (
+                product_id INTEGER NOT NULL,
+                lang TEXT NOT NULL,
+                name TEXT,
+                description TEXT,
+                PRIMARY KEY (product_id, lang),
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // obrázky produktov
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS product_images (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                product_id INTEGER NOT NULL,
+                url TEXT NOT NULL,
+                is_primary INTEGER NOT NULL DEFAULT 0,
+                sort_order INTEGER NOT NULL,
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // najviac jeden hlavný obrázok na produkt
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_product_images_primary ON product_images (product_id) WHERE is_primary = 1",
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // štítky produktov (napr. `clearance`)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS product_tags (
+                product_id INTEGER NOT NULL,
+                tag TEXT NOT NULL,
+                PRIMARY KEY (product_id, tag),
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // naplánované zmeny predajných cien; uplatnené zostávajú ako história cien
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS scheduled_price_changes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                product_id INTEGER NOT NULL,
+                new_price REAL NOT NULL,
+                effective_date TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                applied_at TEXT,
+                old_price REAL,
+                FOREIGN KEY (product_id) REFERENCES products(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_scheduled_price_changes_due ON scheduled_price_changes (effective_date) WHERE applied_at IS NULL",
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // maximálne množstvo tovaru v kategórii (kapacita skladu)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS category_limits (
+                category TEXT PRIMARY KEY,
+                max_total_quantity REAL NOT NULL
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // uložené vyhľadávania (pomenované filtre produktov a zamestnancov)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS saved_searches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                entity TEXT NOT NULL,
+                filter TEXT NOT NULL,
+                owner TEXT,
+                created_at TEXT NOT NULL,
+                UNIQUE (entity, name)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // API kľúče zamestnancov (ukladá sa len hash)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS api_keys (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                employee_id INTEGER NOT NULL,
+                name TEXT,
+                role TEXT NOT NULL,
+                key_id TEXT NOT NULL UNIQUE,
+                key_hash TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                revoked_at TEXT
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // audit log
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                action TEXT NOT NULL,
+                details TEXT,
+                actor TEXT,
+                created_at TEXT NOT NULL
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // tovar odložený pre zákazníkov
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS reservations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                product_id INTEGER NOT NULL,
+                quantity REAL NOT NULL,
+                customer TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'active',
+                order_id INTEGER,
+                FOREIGN KEY (product_id) REFERENCES products(id) ON DELETE CASCADE,
+                FOREIGN KEY (order_id) REFERENCES orders(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_reservations_product ON reservations (product_id)")
+            .execute(&m_pool)
+            .await?;
+
+        // snímky súhrnných štatistík (história vývoja)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS stats_snapshot (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                taken_at TEXT NOT NULL,
+                products INTEGER NOT NULL,
+                total_quantity REAL NOT NULL,
+                inventory_value REAL NOT NULL,
+                employees INTEGER NOT NULL,
+                categories TEXT NOT NULL
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // snímky stavu zásob jednotlivých produktov (porovnanie období);
+        // položky nemajú cudzí kľúč, aby prežili vymazanie produktu
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS stock_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                label TEXT NOT NULL,
+                taken_at TEXT NOT NULL
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS stock_snapshot_items (
+                snapshot_id INTEGER NOT NULL,
+                product_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                quantity REAL NOT NULL,
+                PRIMARY KEY (snapshot_id, product_id),
+                FOREIGN KEY (snapshot_id) REFERENCES stock_snapshots(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // dochádzka zamestnancov; zamestnanec má otvorený najviac jeden záznam
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS attendance (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                employee_id INTEGER NOT NULL,
+                clock_in TEXT NOT NULL,
+                clock_out TEXT,
+                FOREIGN KEY (employee_id) REFERENCES employees(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS shift_assignments (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                employee_id INTEGER NOT NULL,
+                date TEXT NOT NULL,
+                shift TEXT NOT NULL,
+                UNIQUE (employee_id, date, shift),
+                FOREIGN KEY (employee_id) REFERENCES employees(id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_shift_assignments_date ON shift_assignments (date, shift)")
+            .execute(&m_pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_attendance_employee ON attendance (employee_id, clock_in)")
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_attendance_open ON attendance (employee_id) WHERE clock_out IS NULL",
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // deň odchodu (deaktivácie) zamestnanca pre politiku uchovávania osobných údajov;
+        // vedie sa mimo tabuľky zamestnancov, aby neposúval `updated_at` synchronizácie
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS employee_departures (
+                employee_id INTEGER PRIMARY KEY,
+                departed_on TEXT NOT NULL,
+                purged_at TEXT,
+                FOREIGN KEY (employee_id) REFERENCES employees(id) ON DELETE CASCADE
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS employees_departure_insert AFTER INSERT ON employees
+            WHEN NEW.status = 0
+            BEGIN
+                INSERT OR IGNORE INTO employee_departures (employee_id, departed_on)
+                VALUES (NEW.id, date('now', 'localtime'));
+            END
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS employees_departure_update AFTER UPDATE OF status ON employees
+            WHEN NEW.status IS NOT OLD.status
+            BEGIN
+                DELETE FROM employee_departures WHERE employee_id = NEW.id AND NEW.status IS NOT 0;
+                INSERT OR IGNORE INTO employee_departures (employee_id, departed_on)
+                SELECT NEW.id, date('now', 'localtime') WHERE NEW.status = 0;
+            END
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        // neaktívni zamestnanci spred evidencie odchodov: posledná dochádzka, inak dnešok
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO employee_departures (employee_id, departed_on)
+            SELECT e.id, COALESCE(
+                (SELECT date(MAX(COALESCE(a.clock_out, a.clock_in))) FROM attendance a WHERE a.employee_id = e.id),
+                date('now', 'localtime')
+            )
+            FROM employees e
+            WHERE e.status = 0
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // upozornenia na stav zásob; pre produkt a druh je otvorené najviac jedno
+        // (`dismissed` = ručne uzavreté, kým sa zásoby nedoplnia, nevznikne nové)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS alerts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                product_id INTEGER NOT NULL,
+                type TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                resolved_at TEXT,
+                message TEXT NOT NULL,
+                dismissed INTEGER NOT NULL DEFAULT 0
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_alerts_open_product ON alerts (product_id, type) WHERE resolved_at IS NULL",
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // stĺpce pridané do existujúcich tabuliek
+        // (staršie databázy majú množstvá v stĺpcoch INTEGER; SQLite do nich desatinné
+        // hodnoty uloží bez straty, čítajú sa cez `quantity_of`)
+        Self::ensure_column(&m_pool, "purchase_order_items", "received_quantity", "INTEGER NOT NULL DEFAULT 0").await?;
+        Self::ensure_column(&m_pool, "products", "unit", "TEXT NOT NULL DEFAULT 'piece'").await?;
+        Self::ensure_column(&m_pool, "products", "pack_size", "INTEGER").await?;
+        Self::ensure_column(&m_pool, "products", "is_bundle", "INTEGER NOT NULL DEFAULT 0").await?;
+        Self::ensure_column(&m_pool, "products", "sku", "TEXT").await?;
+        Self::ensure_column(&m_pool, "products", "min_stock", "REAL").await?;
+        Self::ensure_column(&m_pool, "products", "max_stock", "REAL").await?;
+        Self::ensure_column(&m_pool, "products", "lead_time_days", "INTEGER").await?;
+        Self::ensure_column(&m_pool, "products", "sale_price", "REAL").await?;
+        Self::ensure_column(&m_pool, "products", "sale_ends", "TEXT").await?;
+        Self::ensure_column(&m_pool, "order_items", "regular_price", "REAL").await?;
+        Self::ensure_column(&m_pool, "employees", "phone_e164", "TEXT").await?;
+        Self::ensure_column(&m_pool, "employees", "manager_id", "INTEGER REFERENCES employees(id)").await?;
+        Self::ensure_column(&m_pool, "employees", "date_remove", "TEXT").await?;
+        Self::ensure_column(&m_pool, "audit_log", "actor", "TEXT").await?;
+        Self::ensure_column(&m_pool, "audit_log", "entity_type", "TEXT").await?;
+        Self::ensure_column(&m_pool, "audit_log", "entity_id", "INTEGER").await?;
+        Self::ensure_column(&m_pool, "stats_snapshot", "category_values", "TEXT NOT NULL DEFAULT '{}'").await?;
+        Self::ensure_column(&m_pool, "stock_movements", "employee_id", "INTEGER").await?;
+
+        // čas poslednej zmeny (filter `updated_after`) a pôvod záznamov prevzatých synchronizáciou
+        for table in SYNCED_TABLES {
+            Self::ensure_column(&m_pool, table, "updated_at", "TEXT").await?;
+            Self::ensure_column(&m_pool, table, "source_instance", "TEXT").await?;
+            Self::ensure_column(&m_pool, table, "source_id", "INTEGER").await?;
+            Self::ensure_column(&m_pool, table, "synced_at", "TEXT").await?;
+            sqlx::query(&format!("UPDATE {table} SET updated_at = {NOW_MILLIS} WHERE updated_at IS NULL"))
+                .execute(&m_pool)
+                .await?;
+            sqlx::query(&format!(
+                "CREATE INDEX IF NOT EXISTS idx_{table}_updated ON {table} (updated_at, id)"
+            ))
+                .execute(&m_pool)
+                .await?;
+            // každá zmena mimo synchronizácie posunie `updated_at`; synchronizácia ho nastavuje
+            // sama spolu so `synced_at` (aj keď vyjde na rovnakú milisekundu ako pri vložení)
+            sqlx::query(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {table}_touch_insert AFTER INSERT ON {table}
+                WHEN NEW.updated_at IS NULL
+                BEGIN UPDATE {table} SET updated_at = {NOW_MILLIS} WHERE id = NEW.id; END
+                "#
+            ))
+                .execute(&m_pool)
+                .await?;
+            sqlx::query(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {table}_touch_update AFTER UPDATE ON {table}
+                WHEN NEW.updated_at IS OLD.updated_at AND NEW.synced_at IS OLD.synced_at
+                BEGIN UPDATE {table} SET updated_at = {NOW_MILLIS} WHERE id = NEW.id; END
+                "#
+            ))
+                .execute(&m_pool)
+                .await?;
+        }
+        // zmazané záznamy pre rozdielový export (`DBFiller::export_changes_since`)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS deleted_records (
+                entity TEXT NOT NULL,
+                record_id INTEGER NOT NULL,
+                deleted_at TEXT NOT NULL,
+                PRIMARY KEY (entity, record_id)
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_deleted_records_at ON deleted_records (entity, deleted_at)")
+            .execute(&m_pool)
+            .await?;
+        for table in DELTA_TABLES {
+            sqlx::query(&format!(
+                r#"
+                CREATE TRIGGER IF NOT EXISTS {table}_tombstone AFTER DELETE ON {table}
+                BEGIN
+                    INSERT OR REPLACE INTO deleted_records (entity, record_id, deleted_at)
+                    VALUES ('{table}', OLD.id, {NOW_MILLIS});
+                END
+                "#
+            ))
+                .execute(&m_pool)
+                .await?;
+        }
+        // podriadení odchádzajúceho (zmazaného alebo deaktivovaného) zamestnanca prejdú
+        // pod jeho nadriadeného, bez neho zostanú bez nadriadeného; trigger beží v tej
+        // istej transakcii ako zmazanie či deaktivácia, nech ju spustí ktorákoľvek cesta
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_employees_manager ON employees (manager_id)")
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS employees_reassign_on_delete BEFORE DELETE ON employees
+            BEGIN UPDATE employees SET manager_id = OLD.manager_id WHERE manager_id = OLD.id; END
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS employees_unschedule_on_delete BEFORE DELETE ON employees
+            BEGIN DELETE FROM shift_assignments WHERE employee_id = OLD.id; END
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS employees_reassign_on_termination AFTER UPDATE OF status ON employees
+            WHEN NEW.status = 0 AND OLD.status IS NOT 0
+            BEGIN UPDATE employees SET manager_id = NEW.manager_id WHERE manager_id = NEW.id; END
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_orders_source ON orders (source_instance, source_id) WHERE source_instance IS NOT NULL",
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // synchronizácie z iných inštancií (`store_manager sync`)
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS sync_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                source TEXT NOT NULL,
+                entity TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                created INTEGER NOT NULL,
+                updated INTEGER NOT NULL,
+                skipped INTEGER NOT NULL,
+                conflicts TEXT NOT NULL DEFAULT '[]',
+                high_water_mark TEXT
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+
+        // produkty bez šarží dostanú jednu šaržu "legacy" s celým stavom zásob
+        sqlx::query(
+            r#"
+            INSERT INTO product_batches (product_id, lot_code, expiry_date, quantity)
+            SELECT id, ?, NULL, quantity FROM products
+            WHERE quantity > 0 AND id NOT IN (SELECT product_id FROM product_batches)
+            "#,
+        )
+            .bind(LEGACY_LOT)
+            .execute(&m_pool)
+            .await?;
+
+        // interné SKU: jedinečné, poradové čísla sa prideľujú po kategóriách
+        sqlx::query("CREATE UNIQUE INDEX IF NOT EXISTS idx_products_sku ON products (sku)")
+            .execute(&m_pool)
+            .await?;
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS sku_sequences (
+                category TEXT PRIMARY KEY,
+                prefix TEXT NOT NULL,
+                last INTEGER NOT NULL DEFAULT 0
+            );
+            "#,
+        )
+            .execute(&m_pool)
+            .await?;
+        // produkty spred zavedenia SKU dostanú SKU v poradí ID
+        let unassigned = sqlx::query("SELECT id, category FROM products WHERE sku IS NULL ORDER BY id")
+            .fetch_all(&m_pool)
+            .await?;
+        if !unassigned.is_empty() {
+            let mut tx = m_pool.begin().await?;
+            for row in &unassigned {
+                let id = row.get::<i64, _>("id") as u32;
+                Self::assign_sku(&mut tx, id, row.get("category")).await?;
+            }
+            tx.commit().await?;
+        }
+
+        println!("Databáza pripravená.");
+        Ok(Self { m_pool, m_generations: Arc::default(), m_settings })
+    }
+
+    /// Vráti počítadlá zmien entít (generácie), podľa ktorých sa zneplatňuje cache dotazov.
+    pub fn generations(&self) -> Arc<Generations> {
+        self.m_generations.clone()
+    }
+
+    /// Začne reláciu s transakciou drženou naprieč viacerými volaniami API (`POST /tx`).
+    ///
+    /// Relácia má vlastný pool s jediným spojením, na ktorom beží `BEGIN IMMEDIATE`.
+    /// Transakcie vnútri metód `StoreDB` sa v relácii vnoria ako savepointy, takže
+    /// sa všetky zmeny potvrdia alebo zahodia naraz v `commit_session` / `rollback_session`.
+    ///
+    /// Relácia drží spojenie a zámok zápisu celej databázy až do ukončenia – ostatné
+    /// zápisy medzitým čakajú (najviac `busy_timeout` SQLite, 5 s) a potom zlyhajú.
+    ///
+    /// # Returns
+    /// `StoreDB` nad spojením relácie
+    ///
+    /// # Errors
+    /// `StoreError::Conflict`, ak zámok zápisu drží iná relácia alebo zápis,
+    /// inak ak zlyhá pripojenie k databáze
+    #[instrument(skip_all, fields(db.operation.name = "begin_session", db.rows = Empty))]
+    pub async fn begin_session(&self) -> Result<StoreDB> {
+        let options = (*self.m_pool.connect_options()).clone();
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .acquire_timeout(std::time::Duration::from_secs(5))
+            // nové spojenie (napr. po páde pôvodného) by bolo mimo transakcie relácie
+            .before_acquire(|conn, _| {
+                Box::pin(async move {
+                    match SqliteTransactionManager::get_transaction_depth(conn) {
+                        0 => Err(sqlx::Error::Protocol("spojenie relácie už nie je v transakcii".into())),
+                        _ => Ok(true),
+                    }
+                })
+            })
+            .connect_lazy_with(options);
+
+        let mut conn = pool.acquire().await?;
+        if let Err(e) = SqliteTransactionManager::begin(&mut conn, Some("BEGIN IMMEDIATE".into())).await {
+            drop(conn);
+            pool.close().await;
+            return match e.as_database_error().and_then(|d| d.code()) {
+                Some(code) if code == "5" => {
+                    Err(StoreError::Conflict("databázu práve zapisuje iná transakcia".into()).into())
+                }
+                _ => Err(e.into()),
+            };
+        }
+        drop(conn);
+
+        Self::record_rows(0);
+        Ok(Self { m_pool: pool, m_generations: self.m_generations.clone(), m_settings: self.m_settings.clone() })
+    }
+
+    /// Potvrdí zmeny relácie začatej cez `begin_session` a uvoľní jej spojenie.
+    ///
+    /// # Errors
+    /// Ak relácia už nie je v transakcii alebo zlyhá `COMMIT`
+    #[instrument(skip_all, fields(db.operation.name = "commit_session", db.rows = Empty))]
+    pub async fn commit_session(&self) -> Result<()> {
+        self.end_session(true).await
+    }
+
+    /// Zahodí zmeny relácie začatej cez `begin_session` a uvoľní jej spojenie.
+    ///
+    /// # Errors
+    /// Ak relácia už nie je v transakcii alebo zlyhá `ROLLBACK`
+    #[instrument(skip_all, fields(db.operation.name = "rollback_session", db.rows = Empty))]
+    pub async fn rollback_session(&self) -> Result<()> {
+        self.end_session(false).await
+    }
+
+    /// Vykoná zápis v relácii (`begin_session`) vo vlastnom savepointe.
+    ///
+    /// Ak zápis zlyhá, relácia sa vráti po savepoint a pokračuje bez jeho zmien;
+    /// inak sa savepoint uvoľní a zmeny zostanú v transakcii relácie.
+    ///
+    /// # Arguments
+    /// * `write` – zápis nad touto reláciou
+    ///
+    /// # Returns
+    /// Výsledok zápisu
+    ///
+    /// # Errors
+    /// Chyba zápisu (po návrate k savepointu), alebo ak sa savepoint nepodarí
+    /// vytvoriť či ukončiť
+    pub async fn savepoint<T>(&self, write: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+        let mut conn = self.m_pool.acquire().await?;
+        SqliteTransactionManager::begin(&mut conn, None).await?;
+        // spojenie relácie je jediné, zápis si ho musí vziať znova
+        drop(conn);
+
+        let result = write.await;
+        let mut conn = self.m_pool.acquire().await?;
+        match result {
+            Ok(_) => SqliteTransactionManager::commit(&mut conn).await?,
+            Err(_) => SqliteTransactionManager::rollback(&mut conn).await?,
+        }
+        result
+    }
+
+    /// Ukončí transakciu relácie, zatvorí jej pool a zneplatní cache všetkých entít
+    /// (čítania mimo relácie mohli medzitým uložiť stav spred potvrdenia).
+    async fn end_session(&self, commit: bool) -> Result<()> {
+        let result = async {
+            let mut conn = self.m_pool.acquire().await?;
+            if commit {
+                SqliteTransactionManager::commit(&mut conn).await?;
+            } else {
+                SqliteTransactionManager::rollback(&mut conn).await?;
+            }
+            Ok::<_, sqlx::Error>(())
+        }
+        .await;
+        self.m_pool.close().await;
+        if commit {
+            self.changed(&[Entity::Products, Entity::Employees, Entity::Orders, Entity::PurchaseOrders]);
+        }
+        Self::record_rows(0);
+        Ok(result?)
+    }
+
+    /// Oznámi úspešnú zmenu entít – zvýši ich generáciu, čím zneplatní závislé položky cache.
+    fn changed(&self, entities: &[Entity]) {
+        self.m_generations.bump(entities);
+    }
+
+    /// Zapíše počet vrátených alebo zmenených riadkov do spanu aktuálnej operácie (`db.rows`).
+    fn record_rows(rows: usize) {
+        Span::current().record("db.rows", rows as u64);
+    }
+
+    /// Pridá stĺpec do tabuľky, ak v nej ešte nie je (migrácia starších databáz).
+    ///
+    /// # Arguments
+    /// * `pool` – pripojenie k databáze
+    /// * `table` – názov tabuľky
+    /// * `column` – názov stĺpca
+    /// * `definition` – typ a obmedzenia stĺpca
+    ///
+    /// # Errors
+    /// Ak zlyhá čítanie schémy alebo `ALTER TABLE`
+    async fn ensure_column(pool: &SqlitePool, table: &str, column: &str, definition: &str) -> Result<()> {
+        let exists = sqlx::query(&format!("SELECT 1 FROM pragma_table_info('{table}') WHERE name = ?"))
+            .bind(column)
+            .fetch_optional(pool)
+            .await?
+            .is_some();
+
+        if !exists {
+            sqlx::query(&format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"))
+                .execute(pool)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Načíta množstvo zo stĺpca bez ohľadu na to, či je uložené ako celé alebo desatinné číslo.
+    ///
+    /// # Arguments
+    /// * `row` – riadok výsledku
+    /// * `column` – názov stĺpca
+    ///
+    /// # Returns
+    /// Množstvo (`NULL` = 0)
+    fn quantity_of(row: &SqliteRow, column: &str) -> f64 {
+        row.try_get_unchecked::<Option<f64>, _>(column).ok().flatten().unwrap_or(0.0)
+    }
+
+    /// Načíta jednotku zo stĺpca `unit` (chýbajúci stĺpec alebo neznáma hodnota = `piece`).
+    fn unit_of(row: &SqliteRow) -> Unit {
+        row.try_get::<Option<String>, _>("unit")
+            .ok()
+            .flatten()
+            .and_then(|u| Unit::parse(&u))
+            .unwrap_or_default()
+    }
+
+    /// Ošetrí znaky `\`, `%` a `_`, aby ich `LIKE ? ESCAPE '\'` hľadal doslovne.
+    ///
+    /// # Arguments
+    /// * `value` – hľadaný text zadaný používateľom
+    ///
+    /// # Returns
+    /// Text na vloženie do vzoru `LIKE`
+    fn escape_like(value: &str) -> String {
+        value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+    }
+
+    // ==========================
+    // Employees
+    // ==========================
+
+    /// Pridá zamestnanca do databázy.
+    ///
+    /// # Arguments
+    /// * `employee` – dáta zamestnanca
+    ///
+    /// # Returns
+    /// ID nového zamestnanca
+    ///
+    /// # Errors
+    /// Ak zlyhá zápis do databázy
+    #[instrument(skip_all, fields(db.operation.name = "add_employee_to_store_db", db.rows = Empty))]
+    pub async fn add_employee_to_store_db(&self, employee: &Employee) -> Result<u32> {
+        let mut tx = self.m_pool.begin().await?;
+        let id = Self::insert_employee(&mut tx, employee).await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Employees]);
+        Ok(id)
+    }
+
+    /// Vloží zamestnanca v rámci otvorenej transakcie a vráti jeho ID.
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak zadaný nadriadený neexistuje
+    async fn insert_employee(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, employee: &Employee) -> Result<u32> {
+        let manager_id = employee.manager_id.filter(|&m| m != 0);
+        if let Some(manager) = manager_id {
+            Self::check_manager(tx, None, manager).await?;
+        }
+        let id = sqlx::query(
+            r#"
+            INSERT INTO employees (
+                name, surname, position, department, shift, salary,
+                phone_number, phone_e164, email, status, note, hire_date, manager_id, date_remove
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+            .bind(employee.name.clone())
+            .bind(employee.surname.clone())
+            .bind(employee.position.clone())
+            .bind(employee.department.clone())
+            .bind(employee.shift.clone())
+            .bind(employee.salary)
+            .bind(employee.phone_number.clone())
+            .bind(employee.phone_e164.clone())
+            .bind(employee.email.clone())
+            .bind(employee.status)
+            .bind(employee.note.clone())
+            .bind(employee.hire_date)
+            .bind(manager_id)
+            .bind(employee.date_remove)
+            .execute(&mut **tx)
+            .await?
+            .last_insert_rowid();
+
+        Ok(id as u32)
+    }
+
+    /// Overí, že nadriadený existuje a že jeho priradením nevznikne cyklus.
+    ///
+    /// # Arguments
+    /// * `conn` – spojenie (napr. otvorená transakcia)
+    /// * `id` – ID zamestnanca (`None` pri novom zamestnancovi)
+    /// * `manager_id` – ID nového nadriadeného
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak nadriadený neexistuje, `StoreError::PolicyViolation`
+    /// ak by bol zamestnanec svojím vlastným (aj nepriamym) nadriadeným
+    async fn check_manager(conn: &mut SqliteConnection, id: Option<u32>, manager_id: u32) -> Result<()> {
+        let chain = Self::chain_ids(conn, manager_id).await?;
+        if chain.is_empty() {
+            return Err(StoreError::Invalid(format!("nadriadený {manager_id} neexistuje")).into());
+        }
+        match id {
+            Some(id) if chain.contains(&id) => Err(StoreError::PolicyViolation(format!(
+                "zamestnanec {id} by bol svojím vlastným nadriadeným (cez zamestnanca {manager_id})"
+            ))
+                .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Vráti ID zamestnanca a všetkých jeho nadriadených až po najvyššieho.
+    ///
+    /// # Arguments
+    /// * `conn` – spojenie
+    /// * `id` – ID zamestnanca
+    ///
+    /// # Returns
+    /// ID od zamestnanca nahor; prázdne, ak zamestnanec neexistuje
+    async fn chain_ids(conn: &mut SqliteConnection, id: u32) -> Result<Vec<u32>, sqlx::Error> {
+        let ids: Vec<i64> = sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE chain(id, depth) AS (
+                SELECT id, 0 FROM employees WHERE id = ?
+                UNION
+                SELECT e.manager_id, c.depth + 1
+                FROM employees e JOIN chain c ON e.id = c.id
+                WHERE e.manager_id IS NOT NULL AND c.depth < ?
+            )
+            SELECT id FROM chain ORDER BY depth
+            "#,
+        )
+            .bind(id)
+            .bind(MAX_CHAIN_DEPTH)
+            .fetch_all(conn)
+            .await?;
+        Ok(ids.into_iter().map(|id| id as u32).collect())
+    }
+
+    /// Zostaví prehľad výkonu zamestnanca z pridaných produktov a zaevidovaných predajov.
+    ///
+    /// # Arguments
+    /// * `id` – ID zamestnanca
+    ///
+    /// # Returns
+    /// Prehľad; zamestnanec bez aktivity má všetky hodnoty nulové
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak zamestnanec neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "employee_scorecard", db.rows = Empty))]
+    pub async fn employee_scorecard(&self, id: u32) -> Result<Scorecard> {
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM employees WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        if exists.is_none() {
+            return Err(StoreError::NotFound(format!("zamestnanec {id}")).into());
+        }
+
+        let products = sqlx::query(
+            r#"
+            SELECT COUNT(*) AS products, COALESCE(SUM(quantity * sell_price), 0.0) AS value
+            FROM products
+            WHERE employee_id = ?
+            "#,
+        )
+            .bind(id)
+            .fetch_one(&self.m_pool)
+            .await?;
+
+        let sales = sqlx::query(
+            r#"
+            SELECT COUNT(DISTINCT o.id) AS orders, COALESCE(SUM(i.quantity * i.unit_price), 0.0) AS revenue
+            FROM orders o
+            JOIN order_items i ON i.order_id = o.id
+            WHERE o.employee_id = ?
+            "#,
+        )
+            .bind(id)
+            .fetch_one(&self.m_pool)
+            .await?;
+
+        Ok(Scorecard {
+            employee_id: id,
+            products_added: products.get::<i64, _>("products") as u32,
+            catalog_value: products.get("value"),
+            orders: sales.get::<i64, _>("orders") as u32,
+            revenue: sales.get("revenue"),
+        })
+    }
+
+    /// Vymaže zamestnanca podľa ID.
+    ///
+    /// Jeho podriadení prejdú pod jeho nadriadeného (trigger `employees_reassign_on_delete`)
+    /// a jeho rozpis služieb sa vymaže (trigger `employees_unschedule_on_delete`).
+    ///
+    /// # Arguments
+    /// * `id` – ID zamestnanca
+    ///
+    /// # Returns
+    /// `true` ak bol záznam vymazaný
+    #[instrument(skip_all, fields(db.operation.name = "delete_employee", db.rows = Empty))]
+    pub async fn delete_employee(&self, id: u32) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM employees WHERE id = ?")
+            .bind(id)
+            .execute(&self.m_pool)
+            .await?;
+        self.changed(&[Entity::Employees]);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Vyradí zamestnanca bez vymazania: nastaví `status = 0` a `date_remove` na dnešok.
+    ///
+    /// Záznam zostáva v databáze, takže produkty, objednávky a dochádzka naňho
+    /// naďalej odkazujú; predvolené zoznamy ho vynechávajú. Opakované vyradenie
+    /// pôvodný dátum nezmení.
+    ///
+    /// # Arguments
+    /// * `id` – ID zamestnanca
+    ///
+    /// # Returns
+    /// `true` ak zamestnanec existuje
+    #[instrument(skip_all, fields(db.operation.name = "soft_delete_employee", db.rows = Empty))]
+    pub async fn soft_delete_employee(&self, id: u32) -> Result<bool> {
+        let result = sqlx::query("UPDATE employees SET status = 0, date_remove = COALESCE(date_remove, ?) WHERE id = ?")
+            .bind(Local::now().date_naive())
+            .bind(id)
+            .execute(&self.m_pool)
+            .await?;
+        self.changed(&[Entity::Employees]);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Obnoví vyradeného zamestnanca: nastaví `status = 1` a vymaže `date_remove`.
+    ///
+    /// # Arguments
+    /// * `id` – ID zamestnanca
+    ///
+    /// # Returns
+    /// Obnovený zamestnanec alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "restore_employee", db.rows = Empty))]
+    pub async fn restore_employee(&self, id: u32) -> Result<Option<Employee>> {
+        let result = sqlx::query("UPDATE employees SET status = 1, date_remove = NULL WHERE id = ?")
+            .bind(id)
+            .execute(&self.m_pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.changed(&[Entity::Employees]);
+        Ok(self.get_employee(id).await?)
+    }
+
+    /// Zlúči duplicitný záznam zamestnanca do iného v jednej transakcii.
+    ///
+    /// Produkty, objednávky, vrátenia, dochádzka a podriadení zlúčeného zamestnanca
+    /// prejdú na ponechaného, prázdne polia ponechaného sa doplnia zo zlúčeného
+    /// (telefón spolu s tvarom E.164) a zlúčený záznam sa vymaže. Nadriadený sa
+    /// doplní, len ak tým nevznikne cyklus.
+    ///
+    /// # Arguments
+    /// * `keep_id` – ID ponechaného zamestnanca
+    /// * `merge_id` – ID zlučovaného (mazaného) zamestnanca
+    ///
+    /// # Returns
+    /// Počet produktov presunutých na ponechaného zamestnanca
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri zlúčení zamestnanca do seba, `StoreError::NotFound`
+    /// ak niektorý zo zamestnancov neexistuje, `StoreError::Conflict` ak majú obaja
+    /// otvorenú dochádzku
+    #[instrument(skip_all, fields(db.operation.name = "merge_employees", db.rows = Empty))]
+    pub async fn merge_employees(&self, keep_id: u32, merge_id: u32) -> Result<u64> {
+        if keep_id == merge_id {
+            return Err(StoreError::Invalid("zamestnanca nemožno zlúčiť so sebou samým".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let mut managers = [None; 2];
+        for (manager, id) in managers.iter_mut().zip([keep_id, merge_id]) {
+            let row: Option<Option<i64>> = sqlx::query_scalar("SELECT manager_id FROM employees WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+            *manager = row.ok_or_else(|| StoreError::NotFound(format!("zamestnanec {id}")))?.map(|m| m as u32);
+        }
+        let [keep_manager, merge_manager] = managers;
+
+        let open: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM attendance WHERE employee_id IN (?, ?) AND clock_out IS NULL",
+        )
+            .bind(keep_id)
+            .bind(merge_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if open > 1 {
+            return Err(StoreError::Conflict(format!(
+                "zamestnanci {keep_id} a {merge_id} majú obaja otvorenú dochádzku"
+            ))
+                .into());
+        }
+
+        let mut reassigned = 0;
+        // zmeny, na ktorých sú obaja, zostanú ponechanému raz; zvyšok zmaže trigger s vymazaním
+        sqlx::query("UPDATE OR IGNORE shift_assignments SET employee_id = ? WHERE employee_id = ?")
+            .bind(keep_id)
+            .bind(merge_id)
+            .execute(&mut *tx)
+            .await?;
+        for table in ["products", "orders", "order_returns", "stock_movements", "attendance"] {
+            let result = sqlx::query(&format!("UPDATE {table} SET employee_id = ? WHERE employee_id = ?"))
+                .bind(keep_id)
+                .bind(merge_id)
+                .execute(&mut *tx)
+                .await?;
+            if table == "products" {
+                reassigned = result.rows_affected();
+            }
+        }
+        sqlx::query("UPDATE employees SET manager_id = ? WHERE manager_id = ? AND id <> ?")
+            .bind(keep_id)
+            .bind(merge_id)
+            .bind(keep_id)
+            .execute(&mut *tx)
+            .await?;
+
+        // zlúčený nadriadený ponechaného: ponechaný prejde pod jeho nadriadeného
+        let manager = match (keep_manager, merge_manager) {
+            (Some(m), inherited) if m == merge_id => inherited,
+            (Some(m), _) => Some(m),
+            (None, Some(m)) if !Self::chain_ids(&mut tx, m).await?.contains(&keep_id) => Some(m),
+            (None, _) => None,
+        };
+        sqlx::query(
+            r#"
+            UPDATE employees SET
+                name = COALESCE(employees.name, m.name),
+                surname = COALESCE(employees.surname, m.surname),
+                position = COALESCE(employees.position, m.position),
+                department = COALESCE(employees.department, m.department),
+                shift = COALESCE(employees.shift, m.shift),
+                salary = COALESCE(employees.salary, m.salary),
+                phone_e164 = CASE WHEN employees.phone_number IS NULL THEN m.phone_e164 ELSE employees.phone_e164 END,
+                phone_number = COALESCE(employees.phone_number, m.phone_number),
+                email = COALESCE(employees.email, m.email),
+                status = COALESCE(employees.status, m.status),
+                note = COALESCE(employees.note, m.note),
+                hire_date = COALESCE(employees.hire_date, m.hire_date),
+                manager_id = ?
+            FROM (SELECT * FROM employees WHERE id = ?) AS m
+            WHERE employees.id = ?
+            "#,
+        )
+            .bind(manager)
+            .bind(merge_id)
+            .bind(keep_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM employees WHERE id = ?")
+            .bind(merge_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        self.changed(&[Entity::Employees, Entity::Products, Entity::Orders]);
+        Ok(reassigned)
+    }
+
+    /// Upraví platy aktívnych zamestnancov oddelenia na `salary * factor + flat`
+    /// v jednej transakcii (nový plat sa zaokrúhli na centy).
+    ///
+    /// Zamestnanci bez platu sa vynechajú (zoznam vráti `employees_without_salary`).
+    /// Ak by niektorý plat klesol pod nulu, nezmení sa žiadny.
+    ///
+    /// # Arguments
+    /// * `department` – oddelenie
+    /// * `factor` – násobok platu (napr. `1.05` pri zvýšení o 5 %)
+    /// * `flat` – pevná suma pripočítaná po vynásobení (môže byť záporná)
+    ///
+    /// # Returns
+    /// Počet upravených zamestnancov
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom oddelení, zápornom alebo nekonečnom
+    /// násobku, `StoreError::PolicyViolation` ak by niektorý plat bol záporný
+    #[instrument(skip_all, fields(db.operation.name = "adjust_salaries", db.rows = Empty))]
+    pub async fn adjust_salaries(&self, department: &str, factor: f64, flat: f64) -> Result<u64> {
+        if department.trim().is_empty() {
+            return Err(StoreError::Invalid("oddelenie nesmie byť prázdne".into()).into());
+        }
+        if !factor.is_finite() || factor < 0.0 || !flat.is_finite() {
+            return Err(StoreError::Invalid(format!("neplatná úprava platu: násobok {factor}, suma {flat}")).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let negative: Vec<i64> = sqlx::query_scalar(
+            "SELECT id FROM employees
+             WHERE department = ? AND COALESCE(status, 1) = 1 AND salary IS NOT NULL AND ROUND(salary * ? + ?, 2) < 0
+             ORDER BY id",
+        )
+            .bind(department)
+            .bind(factor)
+            .bind(flat)
+            .fetch_all(&mut *tx)
+            .await?;
+        if !negative.is_empty() {
+            let ids: Vec<String> = negative.iter().map(i64::to_string).collect();
+            return Err(StoreError::PolicyViolation(format!(
+                "plat zamestnancov {} by bol záporný",
+                ids.join(", ")
+            ))
+                .into());
+        }
+
+        let result = sqlx::query(
+            "UPDATE employees SET salary = ROUND(salary * ? + ?, 2)
+             WHERE department = ? AND COALESCE(status, 1) = 1 AND salary IS NOT NULL",
+        )
+            .bind(factor)
+            .bind(flat)
+            .bind(department)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Self::record_rows(result.rows_affected() as usize);
+        self.changed(&[Entity::Employees]);
+        Ok(result.rows_affected())
+    }
+
+    /// Vráti ID aktívnych zamestnancov oddelenia bez zadaného platu.
+    ///
+    /// # Arguments
+    /// * `department` – oddelenie
+    #[instrument(skip_all, fields(db.operation.name = "employees_without_salary", db.rows = Empty))]
+    pub async fn employees_without_salary(&self, department: &str) -> Result<Vec<u32>, sqlx::Error> {
+        let ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT id FROM employees WHERE department = ? AND COALESCE(status, 1) = 1 AND salary IS NULL ORDER BY id",
+        )
+            .bind(department)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(ids.len());
+        Ok(ids.into_iter().map(|id| id as u32).collect())
+    }
+
+    /// Nájde aktívnych zamestnancov, ktorých plat sa od mediánu ich pozície líši viac než o `deviation`.
+    ///
+    /// Medián sa počíta z aktívnych zamestnancov so zadaným platom a pozíciou
+    /// (pri párnom počte priemer dvoch stredných platov). Zamestnanci bez platu
+    /// alebo pozície sa neporovnávajú, pozícia s nulovým mediánom tiež nie.
+    ///
+    /// # Arguments
+    /// * `deviation` – povolená odchýlka ako podiel mediánu (0.2 = ±20 %)
+    ///
+    /// # Returns
+    /// Zamestnanci s väčšou odchýlkou, od najväčšej (v absolútnej hodnote)
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri zápornej alebo nekonečnej odchýlke
+    #[instrument(skip_all, fields(db.operation.name = "salary_outliers", db.rows = Empty))]
+    pub async fn salary_outliers(&self, deviation: f64) -> Result<Vec<SalaryOutlier>> {
+        if !deviation.is_finite() || deviation < 0.0 {
+            return Err(StoreError::Invalid(format!("neplatná odchýlka {deviation}, musí byť nezáporná")).into());
+        }
+        let rows = sqlx::query(
+            "SELECT id, name, surname, trim(position) AS position, salary FROM employees
+             WHERE COALESCE(status, 1) = 1 AND salary IS NOT NULL AND trim(COALESCE(position, '')) <> ''
+             ORDER BY id",
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let mut salaries: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+        for row in &rows {
+            salaries.entry(row.get("position")).or_default().push(row.get("salary"));
+        }
+        let medians: BTreeMap<String, f64> = salaries
+            .into_iter()
+            .map(|(position, mut salaries)| {
+                salaries.sort_by(f64::total_cmp);
+                let middle = salaries.len() / 2;
+                let median = if salaries.len() % 2 == 0 {
+                    (salaries[middle - 1] + salaries[middle]) / 2.0
+                } else {
+                    salaries[middle]
+                };
+                (position, median)
+            })
+            .collect();
+
+        let mut outliers: Vec<SalaryOutlier> = rows
+            .iter()
+            .filter_map(|row| {
+                let position: String = row.get("position");
+                let median = medians[&position];
+                let salary: f64 = row.get("salary");
+                let relative = (salary - median) / median;
+                (median != 0.0 && relative.abs() > deviation).then(|| SalaryOutlier {
+                    employee_id: row.get::<i64, _>("id") as u32,
+                    name: row.get("name"),
+                    surname: row.get("surname"),
+                    position,
+                    salary,
+                    median,
+                    deviation: relative,
+                })
+            })
+            .collect();
+        outliers.sort_by(|a, b| {
+            b.deviation.abs().total_cmp(&a.deviation.abs()).then(a.employee_id.cmp(&b.employee_id))
+        });
+        Ok(outliers)
+    }
+
+    /// Aktualizuje zamestnanca podľa ID.
+    ///
+    /// Aktualizujú sa len polia, ktoré nie sú `None`. So zmenou `phone_number`
+    /// sa zapíše aj `phone_e164`.
+    ///
+    /// # Arguments
+    /// * `employee` – nové dáta (musí obsahovať `id`)
+    ///
+    /// # Returns
+    /// `true` ak bol záznam zmenený
+    #[instrument(skip_all, fields(db.operation.name = "update_employee", db.rows = Empty))]
+    pub async fn update_employee(&self, employee: &Employee) -> Result<bool> {
+        let id = match employee.id {
+            Some(id) => id,
+            None => return Ok(false),
+        };
+
+        let assignments = Self::employee_assignments(employee);
+        if assignments.is_empty() {
+            return Ok(false);
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        if let Some(manager) = employee.manager_id.filter(|&m| m != 0) {
+            Self::check_manager(&mut tx, Some(id), manager).await?;
+        }
+        let query = format!("UPDATE employees SET {} WHERE id = ?", assignments.sql());
+        let mut args = assignments.args;
+        let _ = args.add(id);
+
+        let result = sqlx::query_with(&query, args).execute(&mut *tx).await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Employees]);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stĺpce zamestnanca, ktoré sa majú zmeniť (polia, ktoré nie sú `None`).
+    fn employee_assignments(employee: &Employee) -> Assignments<'_> {
+        let mut a = Assignments::default();
+        a.set("name", employee.name.as_ref());
+        a.set("surname", employee.surname.as_ref());
+        a.set("position", employee.position.as_ref());
+        a.set("department", employee.department.as_ref());
+        a.set("shift", employee.shift.as_ref());
+        a.set("salary", employee.salary);
+        if let Some(v) = &employee.phone_number {
+            a.set("phone_number", Some(v));
+            a.columns.push("phone_e164");
+            let _ = a.args.add(employee.phone_e164.clone());
+        }
+        a.set("email", employee.email.as_ref());
+        a.set("status", employee.status);
+        a.set("note", employee.note.as_ref());
+        a.set("hire_date", employee.hire_date);
+        a.set("manager_id", employee.manager_id.map(|m| (m != 0).then_some(m)));
+        a
+    }
+
+    /// Nastaví rovnaké polia viacerým zamestnancom naraz.
+    ///
+    /// Telefón sa normalizuje rovnako ako pri úprave jedného zamestnanca.
+    ///
+    /// # Arguments
+    /// * `ids` – ID upravovaných zamestnancov
+    /// * `set` – čiastočný záznam so zmenami
+    /// * `country` – krajina pre telefónne čísla bez medzinárodnej predvoľby
+    ///
+    /// # Returns
+    /// Výsledok pre každé ID v poradí požiadavky
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom zozname ID alebo zmene, pri zadanom
+    /// `id` či `phone_e164`, pri nerozpoznateľnom telefónnom čísle a neexistujúcom
+    /// nadriadenom, `StoreError::PolicyViolation` ak by nadriadený bol podriadeným
+    /// niektorého z upravovaných zamestnancov
+    #[instrument(skip_all, fields(db.operation.name = "bulk_update_employees", db.rows = Empty))]
+    pub async fn bulk_update_employees(&self, ids: &[u32], set: &Employee, country: &str) -> Result<Vec<BulkUpdateResult>> {
+        Self::reject_fields(&[("id", set.id.is_some()), ("phone_e164", set.phone_e164.is_some())])?;
+        let mut set = set.clone();
+        phone::normalize_employee(&mut set, country).map_err(StoreError::Invalid)?;
+        let chain = match set.manager_id.filter(|&m| m != 0) {
+            Some(manager) => {
+                let mut conn = self.m_pool.acquire().await?;
+                Self::check_manager(&mut conn, None, manager).await?;
+                Self::chain_ids(&mut conn, manager).await?
+            }
+            None => Vec::new(),
+        };
+        let check = |current: &SqliteRow| {
+            let id = current.get::<i64, _>("id") as u32;
+            if chain.contains(&id) {
+                return Err(StoreError::PolicyViolation(format!(
+                    "zamestnanec {id} by bol svojím vlastným nadriadeným (cez zamestnanca {})",
+                    chain[0]
+                ))
+                    .into());
+            }
+            Ok(())
+        };
+        self.bulk_update("employees", Entity::Employees, ids, Self::employee_assignments(&set), check)
+            .await
+    }
+
+    /// Doplní normalizovaný telefón (E.164) zamestnancom, ktorí ho ešte nemajú.
+    ///
+    /// Slúži na migráciu starších databáz a dát importovaných z JSONu. Čísla,
+    /// ktoré sa nedajú rozpoznať, zostanú bez normalizovaného tvaru.
+    ///
+    /// # Arguments
+    /// * `country` – krajina pre čísla bez medzinárodnej predvoľby
+    ///
+    /// # Returns
+    /// Počet doplnených čísel
+    #[instrument(skip_all, fields(db.operation.name = "normalize_phone_numbers", db.rows = Empty))]
+    pub async fn normalize_phone_numbers(&self, country: &str) -> Result<u64> {
+        let rows = sqlx::query(
+            "SELECT id, phone_number FROM employees WHERE phone_e164 IS NULL AND COALESCE(phone_number, '') <> ''",
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let mut updated = 0;
+        for row in &rows {
+            let raw: String = row.get("phone_number");
+            if let Ok(e164) = phone::normalize(&raw, country) {
+                sqlx::query("UPDATE employees SET phone_e164 = ? WHERE id = ?")
+                    .bind(e164)
+                    .bind(row.get::<i64, _>("id"))
+                    .execute(&self.m_pool)
+                    .await?;
+                updated += 1;
+            }
+        }
+        Self::record_rows(updated as usize);
+        self.changed(&[Entity::Employees]);
+        Ok(updated)
+    }
+
+    /// Vráti zamestnanca podľa e-mailu (presná zhoda po orezaní medzier, bez ohľadu
+    /// na veľkosť písmen). Pri viacerých zhodách vráti zamestnanca s najnižším ID.
+    ///
+    /// # Arguments
+    /// * `email` – e-mail zamestnanca
+    ///
+    /// # Returns
+    /// Zamestnanec alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_employee_by_email", db.rows = Empty))]
+    pub async fn get_employee_by_email(&self, email: &str) -> Result<Option<Employee>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM Employees WHERE lower(trim(email)) = ? ORDER BY id LIMIT 1")
+            .bind(email.trim().to_lowercase())
+            .fetch_optional(&self.m_pool)
+            .await?;
+        Ok(row.as_ref().map(Self::employee_from_row))
+    }
+
+    /// Vráti zoznam zamestnancov podľa filtra.
+    ///
+    /// # Arguments
+    /// * `employee` – filter (polia `None` sa ignorujú)
+    ///
+    /// # Returns
+    /// Zoznam zamestnancov
+    #[instrument(skip_all, fields(db.operation.name = "get_employees", db.rows = Empty))]
+    pub async fn get_employees(&self, employee: Employee) -> Result<Vec<Employee>, sqlx::Error> {
+        let (query, args) = Self::employee_filter("SELECT * FROM Employees", employee);
+        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::employee_from_row).collect())
+    }
+
+    /// Vráti zamestnanca podľa ID.
+    ///
+    /// # Arguments
+    /// * `id` – ID zamestnanca
+    ///
+    /// # Returns
+    /// Zamestnanec alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_employee", db.rows = Empty))]
+    pub async fn get_employee(&self, id: u32) -> Result<Option<Employee>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM Employees WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        Ok(row.as_ref().map(Self::employee_from_row))
+    }
+
+    /// Vráti podriadených zamestnanca.
+    ///
+    /// # Arguments
+    /// * `id` – ID nadriadeného
+    /// * `all` – `true` vráti aj nepriamych podriadených (celý podstrom)
+    ///
+    /// # Returns
+    /// Podriadení zoradení podľa úrovne a ID
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak zamestnanec neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_reports", db.rows = Empty))]
+    pub async fn get_reports(&self, id: u32, all: bool) -> Result<Vec<Employee>> {
+        let mut conn = self.m_pool.acquire().await?;
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM employees WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        if exists.is_none() {
+            return Err(StoreError::NotFound(format!("zamestnanec {id}")).into());
+        }
+        let rows = sqlx::query(
+            r#"
+            WITH RECURSIVE reports(id, depth) AS (
+                SELECT id, 1 FROM employees WHERE manager_id = ?
+                UNION
+                SELECT e.id, r.depth + 1
+                FROM employees e JOIN reports r ON e.manager_id = r.id
+                WHERE ? AND r.depth < ?
+            )
+            SELECT e.* FROM reports r JOIN employees e ON e.id = r.id
+            GROUP BY e.id
+            ORDER BY MIN(r.depth), e.id
+            "#,
+        )
+            .bind(id)
+            .bind(all)
+            .bind(MAX_CHAIN_DEPTH)
+            .fetch_all(&mut *conn)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::employee_from_row).collect())
+    }
+
+    /// Vráti reťaz nadriadených zamestnanca od priameho nadriadeného po najvyššieho.
+    ///
+    /// # Arguments
+    /// * `id` – ID zamestnanca
+    ///
+    /// # Returns
+    /// Nadriadení zoradení nahor; prázdne, ak zamestnanec nemá nadriadeného
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak zamestnanec neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_management_chain", db.rows = Empty))]
+    pub async fn get_management_chain(&self, id: u32) -> Result<Vec<Employee>> {
+        let mut conn = self.m_pool.acquire().await?;
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM employees WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        if exists.is_none() {
+            return Err(StoreError::NotFound(format!("zamestnanec {id}")).into());
+        }
+        let rows = sqlx::query(
+            r#"
+            WITH RECURSIVE chain(id, depth) AS (
+                SELECT manager_id, 1 FROM employees WHERE id = ? AND manager_id IS NOT NULL
+                UNION
+                SELECT e.manager_id, c.depth + 1
+                FROM employees e JOIN chain c ON e.id = c.id
+                WHERE e.manager_id IS NOT NULL AND c.depth < ?
+            )
+            SELECT e.* FROM chain c JOIN employees e ON e.id = c.id
+            ORDER BY c.depth
+            "#,
+        )
+            .bind(id)
+            .bind(MAX_CHAIN_DEPTH)
+            .fetch_all(&mut *conn)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::employee_from_row).collect())
+    }
+
+    /// Vráti organizačnú štruktúru podľa nadriadených (`manager_id`).
+    ///
+    /// # Returns
+    /// Stromy so zamestnancami bez nadriadeného v koreňoch (pozri `OrgNode::build`)
+    #[instrument(skip_all, fields(db.operation.name = "org_chart", db.rows = Empty))]
+    pub async fn org_chart(&self) -> Result<Vec<OrgNode>> {
+        let rows = sqlx::query("SELECT * FROM employees ORDER BY id").fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(OrgNode::build(rows.iter().map(Self::employee_from_row).collect()))
+    }
+
+    /// Vráti zamestnancov so zadanými ID jedným dotazom (dávkové načítanie).
+    ///
+    /// Dlhý zoznam sa rozdelí na dotazy po `ID_QUERY_CHUNK` ID.
+    ///
+    /// # Arguments
+    /// * `ids` – ID zamestnancov
+    ///
+    /// # Returns
+    /// Nájdení zamestnanci v poradí `ids` (neexistujúce ID sa vynechajú)
+    #[instrument(skip_all, fields(db.operation.name = "get_employees_by_ids", db.rows = Empty))]
+    pub async fn get_employees_by_ids(&self, ids: &[u32]) -> Result<Vec<Employee>, sqlx::Error> {
+        let mut employees = Vec::new();
+        for chunk in ids.chunks(ID_QUERY_CHUNK) {
+            let sql = format!("SELECT * FROM Employees WHERE id IN ({})", vec!["?"; chunk.len()].join(", "));
+            let mut query = sqlx::query(&sql);
+            for id in chunk {
+                query = query.bind(id);
+            }
+            let rows = query.fetch_all(&self.m_pool).await?;
+            employees.extend(rows.iter().map(Self::employee_from_row));
+        }
+        Self::record_rows(employees.len());
+        employees.sort_by_key(|e| ids.iter().position(|&id| Some(id) == e.id));
+        Ok(employees)
+    }
+
+    /// Vráti jednu stránku zamestnancov podľa filtra.
+    ///
+    /// # Arguments
+    /// * `employee` – filter (polia `None` sa ignorujú)
+    /// * `limit` – maximálny počet vrátených záznamov
+    /// * `offset` – počet preskočených záznamov
+    ///
+    /// # Returns
+    /// Zoznam zamestnancov na danej stránke
+    #[instrument(skip_all, fields(db.operation.name = "get_employees_page", db.rows = Empty))]
+    pub async fn get_employees_page(
+        &self,
+        employee: Employee,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Employee>, sqlx::Error> {
+        let order = Self::page_order(employee.updated_after);
+        let (mut query, mut args) = Self::employee_filter("SELECT * FROM Employees", employee);
+        query.push_str(&format!(" ORDER BY {order} LIMIT ? OFFSET ?"));
+        let _ = args.add(limit);
+        let _ = args.add(offset);
+
+        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::employee_from_row).collect())
+    }
+
+    /// Spočíta zamestnancov vyhovujúcich filtru.
+    ///
+    /// # Arguments
+    /// * `employee` – filter (polia `None` sa ignorujú)
+    ///
+    /// # Returns
+    /// Počet nájdených zamestnancov
+    #[instrument(skip_all, fields(db.operation.name = "count_employees", db.rows = Empty))]
+    pub async fn count_employees(&self, employee: Employee) -> Result<u64, sqlx::Error> {
+        let (query, args) = Self::employee_filter("SELECT COUNT(*) FROM Employees", employee);
+        let count: i64 = sqlx::query_scalar_with(&query, args).fetch_one(&self.m_pool).await?;
+        Self::record_rows(count as usize);
+        Ok(count as u64)
+    }
+
+    /// Zostaví dotaz s podmienkami `WHERE` podľa filtra zamestnanca.
+    ///
+    /// # Arguments
+    /// * `select` – začiatok dotazu (`SELECT ... FROM ...`)
+    /// * `employee` – filter (polia `None` sa ignorujú)
+    ///
+    /// # Returns
+    /// SQL dotaz a jeho argumenty
+    fn employee_filter(select: &str, employee: Employee) -> (String, SqliteArguments<'static>) {
+        let mut query = format!("{select} WHERE 1=1");
+        let mut args = SqliteArguments::default();
+
+        if let Some(id) = employee.id { query.push_str(" AND id = ?"); let _ = args.add(id); }
+        if let Some(name) = employee.name {
+            if !name.is_empty() {
+                query.push_str(" AND name LIKE ? ESCAPE '\\'");
+                let _ = args.add(format!("%{}%", Self::escape_like(&name)));
+            }
+        }
+        if let Some(surname) = employee.surname {
+            if !surname.is_empty() {
+                query.push_str(" AND surname LIKE ? ESCAPE '\\'");
+                let _ = args.add(format!("%{}%", Self::escape_like(&surname)));
+            }
+        }
+        if let Some(position) = employee.position {
+            if !position.is_empty() { query.push_str(" AND position = ?"); let _ = args.add(position); }
+        }
+        if let Some(department) = employee.department {
+            if !department.is_empty() { query.push_str(" AND department = ?"); let _ = args.add(department); }
+        }
+        if let Some(shift) = employee.shift {
+            if !shift.is_empty() { query.push_str(" AND shift = ?"); let _ = args.add(shift); }
+        }
+        if let Some(salary) = employee.salary { query.push_str(" AND salary = ?"); let _ = args.add(salary); }
+        if let Some(min) = employee.salary_min { query.push_str(" AND salary >= ?"); let _ = args.add(min); }
+        if let Some(max) = employee.salary_max { query.push_str(" AND salary <= ?"); let _ = args.add(max); }
+        if let Some(phone) = employee.phone_number {
+            if !phone.is_empty() { query.push_str(" AND phone_number = ?"); let _ = args.add(phone); }
+        }
+        if let Some(phone) = employee.phone_e164 {
+            if !phone.is_empty() { query.push_str(" AND phone_e164 = ?"); let _ = args.add(phone); }
+        }
+        if let Some(email) = employee.email {
+            if !email.is_empty() { query.push_str(" AND email = ?"); let _ = args.add(email); }
+        }
+        if let Some(status) = employee.status { query.push_str(" AND status = ?"); let _ = args.add(status); }
+        if let Some(note) = employee.note {
+            if !note.is_empty() {
+                query.push_str(" AND note LIKE ? ESCAPE '\\'");
+                let _ = args.add(format!("%{}%", Self::escape_like(&note)));
+            }
+        }
+        if let Some(date) = employee.hire_date { query.push_str(" AND hire_date = ?"); let _ = args.add(date); }
+        if let Some(manager) = employee.manager_id { query.push_str(" AND manager_id = ?"); let _ = args.add(manager); }
+        if let Some(date) = employee.date_remove { query.push_str(" AND date_remove = ?"); let _ = args.add(date); }
+        if employee.include_removed == Some(false) { query.push_str(" AND date_remove IS NULL"); }
+        Self::push_updated_after(&mut query, &mut args, employee.updated_after, employee.after_id);
+
+        (query, args)
+    }
+
+    /// Pridá podmienku filtra `updated_after`; s `after_id` pokračuje za posledným
+    /// záznamom predchádzajúcej stránky (kurzor `updated_at`, `id`).
+    ///
+    /// # Arguments
+    /// * `query` – dotaz s podmienkami `WHERE`
+    /// * `args` – argumenty dotazu
+    /// * `updated_after` – len záznamy zmenené po tomto čase
+    /// * `after_id` – pri rovnakom `updated_at` len záznamy s vyšším ID
+    fn push_updated_after(
+        query: &mut String,
+        args: &mut SqliteArguments<'static>,
+        updated_after: Option<NaiveDateTime>,
+        after_id: Option<u32>,
+    ) {
+        let Some(after) = updated_after else { return };
+        let after = after.format(UPDATED_AT_FORMAT).to_string();
+        match after_id {
+            Some(id) => {
+                query.push_str(" AND (updated_at > ? OR (updated_at = ? AND id > ?))");
+                let _ = args.add(after.clone());
+                let _ = args.add(after);
+                let _ = args.add(id);
+            }
+            None => {
+                query.push_str(" AND updated_at > ?");
+                let _ = args.add(after);
+            }
+        }
+    }
+
+    /// Poradie stránky zoznamu: pri filtri `updated_after` podľa času zmeny, inak podľa ID.
+    fn page_order(updated_after: Option<NaiveDateTime>) -> &'static str {
+        if updated_after.is_some() { "updated_at, id" } else { "id" }
+    }
+
+    /// Prevedie riadok z tabuľky `employees` na `Employee`.
+    fn employee_from_row(row: &SqliteRow) -> Employee {
+        Employee {
+            id: row.get::<Option<i64>, _>("id").map(|v| v as u32),
+            name: row.get("name"),
+            surname: row.get("surname"),
+            position: row.get("position"),
+            department: row.get("department"),
+            shift: row.get("shift"),
+            salary: row.get("salary"),
+            phone_number: row.get("phone_number"),
+            phone_e164: row.try_get("phone_e164").ok().flatten(),
+            email: row.get("email"),
+            status: row.get::<Option<i64>, _>("status").map(|v| v != 0),
+            note: row.get("note"),
+            hire_date: row.get("hire_date"),
+            manager_id: row.try_get::<Option<i64>, _>("manager_id").ok().flatten().map(|id| id as u32),
+            date_remove: row.try_get("date_remove").ok().flatten(),
+            salary_min: None,
+            salary_max: None,
+            updated_at: row.try_get("updated_at").ok().flatten(),
+            updated_after: None,
+            after_id: None,
+            include_removed: None,
+        }
+    }
+
+    // ==========================
+    // Products
+    // ==========================
+
+    /// Pridá produkt do databázy.
+    ///
+    /// Počiatočné množstvo sa zaeviduje ako šarža `legacy`. Sada nemá vlastné
+    /// zásoby, jej množstvo sa ignoruje (počíta sa zo zložiek).
+    ///
+    /// # Returns
+    /// ID nového produktu
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak množstvo nezodpovedá jednotke produktu alebo sada nie je v kusoch
+    #[instrument(skip_all, fields(db.operation.name = "add_product_to_store_db", db.rows = Empty))]
+    pub async fn add_product_to_store_db(&self, product: &Product) -> Result<u32> {
+        let mut tx = self.m_pool.begin().await?;
+        let id = Self::insert_product(&mut tx, product).await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(id)
+    }
+
+    /// Vloží produkt (a jeho úvodnú šaržu) v rámci otvorenej transakcie a vráti jeho ID.
+    ///
+    /// Produkt bez SKU dostane ďalšie SKU v poradí svojej kategórie.
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak množstvo alebo `min_stock`/`max_stock` nezodpovedá jednotke produktu alebo
+    /// sada nie je v kusoch, `StoreError::Conflict` ak zadané SKU už má iný produkt
+    async fn insert_product(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, product: &Product) -> Result<u32> {
+        let unit = product.unit.unwrap_or_default();
+        let is_bundle = product.is_bundle.unwrap_or(false);
+        if is_bundle && unit != Unit::Piece {
+            return Err(StoreError::Invalid("sada sa predáva len po kusoch".into()).into());
+        }
+        let quantity = if is_bundle { 0.0 } else { product.quantity.unwrap_or(0.0) };
+        Self::validate_unit(unit, quantity, product.pack_size)?;
+        Self::validate_stock_limit(unit, product.min_stock)?;
+        Self::validate_stock_limit(unit, product.max_stock)?;
+
+        let id = sqlx::query(
+            r#"
+            INSERT INTO products (
+                name, category, quantity, unit, pack_size, is_bundle, status, bar_code, sku, min_stock,
+                max_stock, lead_time_days, cost_price, sell_price, description, brand, supplier, employee_id,
+                date_added, date_remove
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+            .bind(product.name.clone())
+            .bind(product.category.clone())
+            .bind(quantity)
+            .bind(unit.as_str())
+            .bind(product.pack_size)
+            .bind(is_bundle)
+            .bind(product.status)
+            .bind(product.bar_code)
+            .bind(product.sku.clone())
+            .bind(product.min_stock)
+            .bind(product.max_stock)
+            .bind(product.lead_time_days)
+            .bind(product.cost_price)
+            .bind(product.sell_price)
+            .bind(product.description.clone())
+            .bind(product.brand.clone())
+            .bind(product.supplier.clone())
+            .bind(product.employee_id)
+            .bind(product.date_added)
+            .bind(product.date_remove)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| Self::sku_taken(e, product.sku.as_deref()))?
+            .last_insert_rowid();
+
+        // SKU sa prideľuje až po vložení: transakcia už drží zámok na zápis,
+        // súbežné vkladanie preto počká a nedostane rovnaké poradové číslo
+        let category = product.category.as_deref().unwrap_or_default();
+        match &product.sku {
+            Some(sku) => Self::note_sku(tx, category, sku).await?,
+            None => {
+                Self::assign_sku(tx, id as u32, category).await?;
+            }
+        }
+
+        if quantity > 0.0 {
+            Self::add_to_batches(tx, id as u32, quantity, None, None).await?;
+            Self::check_category_capacity(tx, id as u32).await?;
+        }
+
+        Ok(id as u32)
+    }
+
+    /// Pridelí produktu ďalšie SKU v poradí jeho kategórie.
+    ///
+    /// Kategória bez záznamu v `sku_sequences` dostane predponu odvodenú z názvu
+    /// (`sku::category_prefix`). Čísla obsadené ručne zadanými SKU (aj inej
+    /// kategórie s rovnakou predponou) sa preskočia.
+    ///
+    /// # Arguments
+    /// * `conn` – spojenie v otvorenej transakcii
+    /// * `id` – ID produktu
+    /// * `category` – kategória produktu
+    ///
+    /// # Returns
+    /// Pridelené SKU
+    async fn assign_sku(conn: &mut SqliteConnection, id: u32, category: &str) -> Result<String, sqlx::Error> {
+        sqlx::query("INSERT OR IGNORE INTO sku_sequences (category, prefix) VALUES (?, ?)")
+            .bind(category)
+            .bind(sku::category_prefix(category))
+            .execute(&mut *conn)
+            .await?;
+        loop {
+            let (prefix, number): (String, i64) = sqlx::query_as(
+                "UPDATE sku_sequences SET last = last + 1 WHERE category = ? RETURNING prefix, last",
+            )
+                .bind(category)
+                .fetch_one(&mut *conn)
+                .await?;
+            let candidate = sku::format(&prefix, number as u64);
+            let taken: Option<i64> = sqlx::query_scalar("SELECT id FROM products WHERE sku = ?")
+                .bind(&candidate)
+                .fetch_optional(&mut *conn)
+                .await?;
+            if taken.is_none() {
+                sqlx::query("UPDATE products SET sku = ? WHERE id = ?")
+                    .bind(&candidate)
+                    .bind(id)
+                    .execute(&mut *conn)
+                    .await?;
+                return Ok(candidate);
+            }
+        }
+    }
+
+    /// Zapíše ručne zadané SKU do poradia kategórie.
+    ///
+    /// Prvé SKU kategórie určí jej predponu (napr. `DRK` pre `Nápoje`); SKU
+    /// s predponou kategórie posunie poradie, aby ďalšie pridelené číslo nasledovalo.
+    ///
+    /// # Arguments
+    /// * `conn` – spojenie v otvorenej transakcii
+    /// * `category` – kategória produktu
+    /// * `sku` – zadané SKU
+    async fn note_sku(conn: &mut SqliteConnection, category: &str, sku: &str) -> Result<(), sqlx::Error> {
+        let Some((prefix, number)) = sku::split(sku) else { return Ok(()) };
+        sqlx::query(
+            r#"
+            INSERT INTO sku_sequences (category, prefix, last) VALUES (?, ?, ?)
+            ON CONFLICT(category) DO UPDATE SET last = MAX(last, excluded.last)
+            WHERE prefix = excluded.prefix
+            "#,
+        )
+            .bind(category)
+            .bind(prefix)
+            .bind(number as i64)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Porušenie jedinečnosti SKU prevedie na `StoreError::Conflict`, ostatné chyby ponechá.
+    fn sku_taken(e: sqlx::Error, sku: Option<&str>) -> anyhow::Error {
+        match sku {
+            Some(sku) if e.as_database_error().is_some_and(|d| d.is_unique_violation()) => {
+                StoreError::Conflict(format!("SKU {sku} už má iný produkt")).into()
+            }
+            _ => e.into(),
+        }
+    }
+
+    /// Vloží dávku importovaných zamestnancov a produktov v jednej transakcii.
+    ///
+    /// Každý záznam sa vkladá v samostatnom savepointe, takže chybný záznam
+    /// po sebe nenechá čiastočné dáta.
+    ///
+    /// # Arguments
+    /// * `records` – záznamy s číslami riadkov
+    /// * `lenient` – chybné záznamy sa preskočia; inak sa pri prvej chybe
+    ///   zahodí celá dávka
+    ///
+    /// # Returns
+    /// Chyby odmietnutých záznamov (v prísnom režime najviac jedna)
+    ///
+    /// # Errors
+    /// Ak sa nepodarí otvoriť alebo potvrdiť transakciu
+    #[instrument(skip_all, fields(db.operation.name = "import_records", db.rows = Empty))]
+    pub async fn import_records(&self, records: &[(u64, ImportRecord)], lenient: bool) -> Result<Vec<ImportLineError>> {
+        let mut tx = self.m_pool.begin().await?;
+        let mut errors = Vec::new();
+
+        for (line, record) in records {
+            let mut savepoint = tx.begin().await?;
+            let inserted = match record {
+                ImportRecord::Employee(employee) => Self::insert_employee(&mut savepoint, employee).await,
+                ImportRecord::Product(product) => Self::insert_product(&mut savepoint, product).await,
+            };
+            match inserted {
+                Ok(_) => savepoint.commit().await?,
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    errors.push(ImportLineError { line: *line, message: e.to_string() });
+                    if !lenient {
+                        return Ok(errors);
+                    }
+                }
+            }
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Employees, Entity::Products]);
+        Self::record_rows(records.len() - errors.len());
+        Ok(errors)
+    }
+
+    /// Vloží viacero produktov v jednej transakcii, každý vo vlastnom savepointe.
+    ///
+    /// # Arguments
+    /// * `products` – vkladané produkty
+    /// * `best_effort` – chybné produkty sa preskočia a ostatné sa potvrdia; inak sa
+    ///   pri prvej chybe zahodí celá dávka
+    ///
+    /// # Returns
+    /// Výsledok pre každý produkt v poradí vstupu; v prísnom režime končí prvou
+    /// chybou a nič sa nevloží
+    ///
+    /// # Errors
+    /// Ak sa nepodarí otvoriť alebo potvrdiť transakciu
+    #[instrument(skip_all, fields(db.operation.name = "add_products", db.rows = Empty))]
+    pub async fn add_products(&self, products: &[Product], best_effort: bool) -> Result<Vec<Result<u32>>> {
+        let mut tx = self.m_pool.begin().await?;
+        let mut results = Vec::with_capacity(products.len());
+
+        for product in products {
+            let mut savepoint = tx.begin().await?;
+            match Self::insert_product(&mut savepoint, product).await {
+                Ok(id) => {
+                    savepoint.commit().await?;
+                    results.push(Ok(id));
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    results.push(Err(e));
+                    if !best_effort {
+                        return Ok(results);
+                    }
+                }
+            }
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Self::record_rows(results.iter().filter(|r| r.is_ok()).count());
+        Ok(results)
+    }
+
+    /// Vymaže produkt podľa ID (aj s jeho šaržami a zložením, ak je sada).
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak je produkt zložkou niektorej sady
+    #[instrument(skip_all, fields(db.operation.name = "delete_product", db.rows = Empty))]
+    pub async fn delete_product(&self, id: u32) -> Result<bool> {
+        let mut tx = self.m_pool.begin().await?;
+        let deleted = Self::delete_product_in(&mut tx, id).await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(deleted)
+    }
+
+    /// Vymaže v transakcii všetky produkty, ktoré zodpovedajú filtru.
+    ///
+    /// Produkty sa vyberú rovnakým filtrom ako v `get_products` a mažú sa ako
+    /// v `delete_product` (sady pred zložkami). Filter bez podmienok by vymazal
+    /// celý katalóg, preto ho treba potvrdiť cez `confirm_all`.
+    ///
+    /// # Arguments
+    /// * `filter` – filter produktov (polia `None` sa ignorujú)
+    /// * `confirm_all` – povolí prázdny filter
+    ///
+    /// # Returns
+    /// Počet a ID vymazaných produktov
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom filtri bez potvrdenia, `StoreError::Conflict`
+    /// ak je niektorý produkt zložkou sady, ktorá sa nemaže (nevymaže sa nič)
+    #[instrument(skip_all, fields(db.operation.name = "delete_products_by_filter", db.rows = Empty))]
+    pub async fn delete_products_by_filter(&self, filter: Product, confirm_all: bool) -> Result<DeletionReport> {
+        let select = "SELECT id FROM products";
+        let (mut query, args) = Self::product_filter(select, filter, false);
+        if query == format!("{select} WHERE 1=1") && !confirm_all {
+            return Err(StoreError::Invalid(
+                "prázdny filter by vymazal všetky produkty; potvrďte ho cez confirm_all: true".into(),
+            )
+                .into());
+        }
+        query.push_str(" ORDER BY COALESCE(is_bundle, 0) DESC, id");
+
+        let mut tx = self.m_pool.begin().await?;
+        let mut ids: Vec<u32> = sqlx::query_scalar_with::<_, i64, _>(&query, args)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .map(|id| id as u32)
+            .collect();
+        for &id in &ids {
+            Self::delete_product_in(&mut tx, id).await?;
+        }
+        tx.commit().await?;
+        if !ids.is_empty() {
+            self.changed(&[Entity::Products]);
+        }
+        Self::record_rows(ids.len());
+        ids.sort_unstable();
+        Ok(DeletionReport { deleted: ids.len() as u64, ids })
+    }
+
+    /// Nastaví v transakcii stav všetkým produktom, ktoré zodpovedajú filtru.
+    ///
+    /// Vyradenie nastaví `status = 0` a `date_remove` na dnešok (ak ešte nie je
+    /// vyplnený), obnovenie `status = 1` a vymaže `date_remove` ako
+    /// `reactivate_by_supplier`. Produkty, ktoré už daný stav majú, sa nemenia.
+    ///
+    /// # Arguments
+    /// * `filter` – filter produktov (polia `None` sa ignorujú)
+    /// * `active` – nový stav
+    ///
+    /// # Returns
+    /// Počet vybraných produktov a ID zmenených
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom filtri (zmenil by celý katalóg)
+    #[instrument(skip_all, fields(db.operation.name = "set_status_by_filter", db.rows = Empty))]
+    pub async fn set_status_by_filter(&self, filter: Product, active: bool) -> Result<StatusChangeReport> {
+        let select = "SELECT id, status FROM products";
+        let (mut query, args) = Self::product_filter(select, filter, false);
+        if query == format!("{select} WHERE 1=1") {
+            return Err(StoreError::Invalid("prázdny filter by zmenil stav všetkých produktov".into()).into());
+        }
+        query.push_str(" ORDER BY id");
+
+        let mut tx = self.m_pool.begin().await?;
+        let rows = sqlx::query_with(&query, args).fetch_all(&mut *tx).await?;
+        let mut ids = Vec::new();
+        for row in &rows {
+            if row.get::<Option<bool>, _>("status") == Some(active) {
+                continue;
+            }
+            let id = row.get::<i64, _>("id") as u32;
+            let update = if active {
+                sqlx::query("UPDATE products SET status = 1, date_remove = NULL WHERE id = ?").bind(id)
+            } else {
+                sqlx::query("UPDATE products SET status = 0, date_remove = COALESCE(date_remove, ?) WHERE id = ?")
+                    .bind(Local::now().date_naive())
+                    .bind(id)
+            };
+            update.execute(&mut *tx).await?;
+            ids.push(id);
+        }
+        tx.commit().await?;
+        if !ids.is_empty() {
+            self.changed(&[Entity::Products]);
+        }
+        Self::record_rows(rows.len());
+        Ok(StatusChangeReport { matched: rows.len() as u64, changed: ids.len() as u64, ids })
+    }
+
+    /// Premenuje kategórie produktov podľa mapy `stará → nová` v jednej transakcii.
+    ///
+    /// Premenovania sa uplatnia naraz: produkty sa vyberú podľa pôvodných
+    /// kategórií, takže pri `A → B, B → C` skončia produkty z `A` v `B`. Ak nová
+    /// kategória už existuje, produkty sa do nej zlúčia. Limit zásob premenovanej
+    /// kategórie prejde na novú; ak ho nová kategória už má, ponechá sa jej limit.
+    ///
+    /// # Arguments
+    /// * `mapping` – pôvodná kategória → nová kategória
+    ///
+    /// # Returns
+    /// Počet preradených produktov pre každú položku mapy
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnej mape alebo prázdnom názve kategórie
+    #[instrument(skip_all, fields(db.operation.name = "remap_categories", db.rows = Empty))]
+    pub async fn remap_categories(&self, mapping: &BTreeMap<String, String>) -> Result<Vec<CategoryRemap>> {
+        if mapping.is_empty() {
+            return Err(StoreError::Invalid("mapa kategórií je prázdna".into()).into());
+        }
+        if mapping.iter().any(|(from, to)| from.trim().is_empty() || to.trim().is_empty()) {
+            return Err(StoreError::Invalid("kategória nesmie byť prázdna".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let mut moves = Vec::with_capacity(mapping.len());
+        for (from, to) in mapping {
+            let ids: Vec<i64> = if from == to {
+                Vec::new()
+            } else {
+                sqlx::query_scalar("SELECT id FROM products WHERE category = ? ORDER BY id")
+                    .bind(from)
+                    .fetch_all(&mut *tx)
+                    .await?
+            };
+            moves.push((from, to, ids));
+        }
+        for (_, to, ids) in &moves {
+            for id in ids {
+                sqlx::query("UPDATE products SET category = ? WHERE id = ?")
+                    .bind(to)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        let limits: Vec<(String, f64)> = sqlx::query_as("SELECT category, max_total_quantity FROM category_limits")
+            .fetch_all(&mut *tx)
+            .await?;
+        if limits.iter().any(|(category, _)| mapping.get(category).is_some_and(|to| to != category)) {
+            // vlastný limit cieľovej kategórie má prednosť pred presunutým
+            let mut merged: BTreeMap<&str, f64> = BTreeMap::new();
+            for (category, limit) in limits.iter().filter(|(c, _)| !mapping.contains_key(c)) {
+                merged.insert(category, *limit);
+            }
+            for (category, limit) in limits.iter().filter(|(c, _)| mapping.contains_key(c)) {
+                merged.entry(mapping[category].as_str()).or_insert(*limit);
+            }
+            sqlx::query("DELETE FROM category_limits").execute(&mut *tx).await?;
+            for (category, limit) in merged {
+                sqlx::query("INSERT INTO category_limits (category, max_total_quantity) VALUES (?, ?)")
+                    .bind(category)
+                    .bind(limit)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        tx.commit().await?;
+
+        let report: Vec<CategoryRemap> = moves
+            .into_iter()
+            .map(|(from, to, ids)| CategoryRemap { from: from.clone(), to: to.clone(), products: ids.len() as u64 })
+            .collect();
+        let total = report.iter().map(|r| r.products).sum::<u64>();
+        if total > 0 {
+            self.changed(&[Entity::Products]);
+        }
+        Self::record_rows(total as usize);
+        Ok(report)
+    }
+
+    /// Vymaže produkt v transakcii spolu so šaržami, obrázkami, štítkami, zložením sady
+    /// a naplánovanými zmenami cien.
+    ///
+    /// # Returns
+    /// `true` ak produkt existoval
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak je produkt zložkou niektorej sady
+    async fn delete_product_in(tx: &mut sqlx::Transaction<'_, Sqlite>, id: u32) -> Result<bool> {
+        let bundle: Option<i64> =
+            sqlx::query_scalar("SELECT bundle_id FROM bundle_components WHERE component_product_id = ? LIMIT 1")
+                .bind(id)
+                .fetch_optional(&mut **tx)
+                .await?;
+        if let Some(bundle) = bundle {
+            return Err(StoreError::Conflict(format!("produkt {id} je zložkou sady {bundle}")).into());
+        }
+
+        for statement in [
+            "DELETE FROM bundle_components WHERE bundle_id = ?",
+            "DELETE FROM product_batches WHERE product_id = ?",
+            "DELETE FROM stock_lots WHERE product_id = ?",
+            "DELETE FROM product_images WHERE product_id = ?",
+            "DELETE FROM product_tags WHERE product_id = ?",
+            "DELETE FROM scheduled_price_changes WHERE product_id = ?",
+        ] {
+            sqlx::query(statement).bind(id).execute(&mut **tx).await?;
+        }
+        let result = sqlx::query("DELETE FROM products WHERE id = ?")
+            .bind(id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Vytvorí kópiu produktu (napr. variant v inej veľkosti).
+    ///
+    /// Kópia prevezme polia zdroja okrem tých, ktoré `overrides` nastavuje;
+    /// dostane nové ID, nulové množstvo, dnešný dátum pridania a bez dátumu
+    /// vyradenia. Čiarový kód sa neprevezme: kópia má kód z `overrides`, inak `0`
+    /// (bez kódu). SKU tiež nie: kópia má SKU z `overrides`, inak ďalšie v poradí kategórie.
+    ///
+    /// # Arguments
+    /// * `id` – ID kopírovaného produktu
+    /// * `overrides` – polia, ktoré sa v kópii zmenia (`None` = podľa zdroja)
+    ///
+    /// # Returns
+    /// ID kópie alebo `None`, ak zdrojový produkt neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak produkt so zadaným čiarovým kódom alebo SKU už existuje,
+    /// `StoreError::Invalid` pri sade (zloženie sa nekopíruje) alebo balení bez veľkosti
+    #[instrument(skip_all, fields(db.operation.name = "clone_product", db.rows = Empty))]
+    pub async fn clone_product(&self, id: u32, overrides: &Product) -> Result<Option<u32>> {
+        let mut tx = self.m_pool.begin().await?;
+        let Some(row) = sqlx::query(&format!("{PRODUCT_SELECT} WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let source = Self::product_from_row(&row);
+        if source.is_bundle.unwrap_or(false) {
+            return Err(StoreError::Invalid(format!("produkt {id} je sada, sadu nemožno kopírovať")).into());
+        }
+
+        let bar_code = overrides.bar_code.unwrap_or(0);
+        if bar_code != 0 {
+            let existing: Option<i64> = sqlx::query_scalar("SELECT id FROM products WHERE bar_code = ? LIMIT 1")
+                .bind(bar_code)
+                .fetch_optional(&mut *tx)
+                .await?;
+            if let Some(existing) = existing {
+                return Err(StoreError::Conflict(format!(
+                    "čiarový kód {bar_code} už má produkt {existing}"
+                ))
+                    .into());
+            }
+        }
+
+        let copy = Product {
+            name: overrides.name.clone().or(source.name),
+            category: overrides.category.clone().or(source.category),
+            quantity: Some(0.0),
+            unit: overrides.unit.or(source.unit),
+            pack_size: overrides.pack_size.or(source.pack_size),
+            is_bundle: Some(false),
+            status: overrides.status.or(source.status),
+            bar_code: Some(bar_code),
+            sku: overrides.sku.clone(),
+            min_stock: overrides.min_stock.or(source.min_stock),
+            max_stock: overrides.max_stock.or(source.max_stock),
+            lead_time_days: overrides.lead_time_days.or(source.lead_time_days),
+            cost_price: overrides.cost_price.or(source.cost_price),
+            sell_price: overrides.sell_price.or(source.sell_price),
+            description: overrides.description.clone().or(source.description),
+            brand: overrides.brand.clone().or(source.brand),
+            supplier: overrides.supplier.clone().or(source.supplier),
+            employee_id: overrides.employee_id.or(source.employee_id),
+            date_added: Some(Local::now().date_naive()),
+            ..Product::default()
+        };
+        let clone_id = Self::insert_product(&mut tx, &copy).await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(Some(clone_id))
+    }
+
+    /// Znovu aktivuje všetky produkty dodávateľa (napr. po naskladnení).
+    ///
+    /// Nastaví `status = 1`, vymaže `date_remove` a voliteľne nastaví množstvo
+    /// (okrem sád); zmena množstva sa premietne do šarží.
+    ///
+    /// # Arguments
+    /// * `supplier` – dodávateľ
+    /// * `set_quantity` – nové množstvo pre všetky produkty (`None` = nemení sa)
+    ///
+    /// # Returns
+    /// Počet aktivovaných produktov
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak nové množstvo nezodpovedá jednotke niektorého produktu
+    #[instrument(skip_all, fields(db.operation.name = "reactivate_by_supplier", db.rows = Empty))]
+    pub async fn reactivate_by_supplier(&self, supplier: &str, set_quantity: Option<f64>) -> Result<u64> {
+        let mut tx = self.m_pool.begin().await?;
+
+        let products = sqlx::query("SELECT id, quantity, unit, is_bundle FROM products WHERE supplier = ?")
+            .bind(supplier)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        for row in &products {
+            let id = row.get::<i64, _>("id") as u32;
+            let previous = Self::quantity_of(row, "quantity");
+
+            sqlx::query("UPDATE products SET status = 1, date_remove = NULL WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+
+            // množstvo sady sa počíta zo zložiek
+            if let Some(quantity) = set_quantity.filter(|_| row.get::<i64, _>("is_bundle") == 0) {
+                let unit = Self::unit_of(row);
+                if !unit.accepts(quantity) {
+                    return Err(StoreError::Invalid(format!(
+                        "produkt {id}: množstvo {quantity} nie je prípustné pre jednotku {}",
+                        unit.as_str()
+                    )).into());
+                }
+                sqlx::query("UPDATE products SET quantity = ? WHERE id = ?")
+                    .bind(quantity)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                Self::apply_quantity_delta(&mut tx, id, quantity - previous).await?;
+            }
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Self::record_rows(products.len());
+        Ok(products.len() as u64)
+    }
+
+    /// Pridá štítok všetkým produktom, ktoré zodpovedajú filtru (v jednej transakcii).
+    ///
+    /// Produkty sa vyberú rovnakým filtrom ako v `get_products`, väzby sa vložia
+    /// hromadne po `TAG_INSERT_CHUNK` riadkoch. Štítok sa oreže a prevedie na malé
+    /// písmená; produkty, ktoré ho už majú, sa nemenia.
+    ///
+    /// # Arguments
+    /// * `filter` – filter produktov (polia `None` sa ignorujú)
+    /// * `tag` – štítok
+    ///
+    /// # Returns
+    /// Normalizovaný štítok, počet vybraných produktov a počet nových väzieb
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom štítku
+    #[instrument(skip_all, fields(db.operation.name = "tag_products", db.rows = Empty))]
+    pub async fn tag_products(&self, filter: Product, tag: &str) -> Result<TaggingReport> {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() {
+            return Err(StoreError::Invalid("prázdny štítok".into()).into());
+        }
+
+        let (query, args) = Self::product_filter("SELECT id FROM products", filter, false);
+        let mut tx = self.m_pool.begin().await?;
+        let ids: Vec<i64> = sqlx::query_scalar_with(&query, args).fetch_all(&mut *tx).await?;
+
+        let mut added = 0;
+        for chunk in ids.chunks(TAG_INSERT_CHUNK) {
+            let sql = format!(
+                "INSERT OR IGNORE INTO product_tags (product_id, tag) VALUES {}",
+                vec!["(?, ?)"; chunk.len()].join(", ")
+            );
+            let mut insert = sqlx::query(&sql);
+            for id in chunk {
+                insert = insert.bind(id).bind(&tag);
+            }
+            added += insert.execute(&mut *tx).await?.rows_affected();
+        }
+        tx.commit().await?;
+        Self::record_rows(ids.len());
+        Ok(TaggingReport { tag, tagged: ids.len() as u64, added })
+    }
+
+    /// Vráti štítky produktu.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    ///
+    /// # Returns
+    /// Štítky v abecednom poradí
+    #[instrument(skip_all, fields(db.operation.name = "list_product_tags", db.rows = Empty))]
+    pub async fn list_product_tags(&self, product_id: u32) -> Result<Vec<String>, sqlx::Error> {
+        let tags: Vec<String> = sqlx::query_scalar("SELECT tag FROM product_tags WHERE product_id = ? ORDER BY tag")
+            .bind(product_id)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(tags.len());
+        Ok(tags)
+    }
+
+    /// Natrvalo vymaže produkty vyradené pred zadaným dňom.
+    ///
+    /// Maže len neaktívne produkty (`status = 0`) s `date_remove < cutoff`, na ktoré
+    /// sa neodkazujú predaje, vrátenia, objednávky u dodávateľov ani pohyby skladu.
+    ///
+    /// # Arguments
+    /// * `cutoff` – produkty vyradené pred týmto dňom sa vymažú
+    ///
+    /// # Returns
+    /// Počet vymazaných produktov
+    #[instrument(skip_all, fields(db.operation.name = "purge_removed_before", db.rows = Empty))]
+    pub async fn purge_removed_before(&self, cutoff: NaiveDate) -> Result<u64> {
+        let purgeable = r#"
+            SELECT id FROM products
+            WHERE status = 0
+              AND date_remove IS NOT NULL
+              AND date_remove < ?
+              AND id NOT IN (SELECT product_id FROM order_items)
+              AND id NOT IN (SELECT product_id FROM return_items)
+              AND id NOT IN (SELECT product_id FROM purchase_order_items)
+              AND id NOT IN (SELECT product_id FROM stock_movements)
+        "#;
+
+        let mut tx = self.m_pool.begin().await?;
+        sqlx::query(&format!("DELETE FROM product_batches WHERE product_id IN ({purgeable})"))
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(&format!("DELETE FROM stock_lots WHERE product_id IN ({purgeable})"))
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(&format!("DELETE FROM product_translations WHERE product_id IN ({purgeable})"))
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(&format!("DELETE FROM product_images WHERE product_id IN ({purgeable})"))
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(&format!("DELETE FROM product_tags WHERE product_id IN ({purgeable})"))
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query(&format!("DELETE FROM products WHERE id IN ({purgeable})"))
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Self::record_rows(result.rows_affected() as usize);
+        Ok(result.rows_affected())
+    }
+
+    /// Uplatní politiku uchovávania osobných údajov.
+    ///
+    /// Zamestnancom deaktivovaným pred `employee_cutoff` sa podľa akcie politiky
+    /// prepíšu osobné údaje (meno, kontakty, mzda, poznámka, dátum nástupu), alebo
+    /// sa vymažú spolu s dochádzkou; odkazy z produktov, objednávok a vrátení sa
+    /// pred vymazaním vynulujú. Záznamy dochádzky ukončené pred `attendance_cutoff`
+    /// sa vymažú. Skutočný beh sa zapíše do audit logu.
+    ///
+    /// # Arguments
+    /// * `policy` – doby uchovávania a akcia
+    /// * `today` – deň, od ktorého sa doby počítajú
+    /// * `dry_run` – len zistí, čo by sa odstránilo, nič nezmení
+    ///
+    /// # Returns
+    /// Odstránení (pri `dry_run` odstrániteľní) zamestnanci a počty záznamov
+    #[instrument(skip_all, fields(db.operation.name = "purge_personal_data", db.rows = Empty))]
+    pub async fn purge_personal_data(
+        &self,
+        policy: &RetentionPolicy,
+        today: NaiveDate,
+        dry_run: bool,
+    ) -> Result<RetentionReport> {
+        let employee_cutoff = policy.employee_cutoff(today);
+        let attendance_cutoff = policy.attendance_cutoff(today);
+        let mut report = RetentionReport {
+            dry_run,
+            action: policy.action,
+            employee_cutoff,
+            attendance_cutoff,
+            employees: Vec::new(),
+            attendance_entries: 0,
+            references: 0,
+        };
+
+        let mut tx = self.m_pool.begin().await?;
+        if let Some(cutoff) = employee_cutoff {
+            let rows = sqlx::query(
+                r#"
+                SELECT d.employee_id, d.departed_on
+                FROM employee_departures d
+                JOIN employees e ON e.id = d.employee_id
+                WHERE e.status = 0 AND d.purged_at IS NULL AND d.departed_on < ?
+                ORDER BY d.employee_id
+                "#,
+            )
+                .bind(cutoff)
+                .fetch_all(&mut *tx)
+                .await?;
+            report.employees = rows
+                .iter()
+                .map(|r| RetentionCandidate {
+                    employee_id: r.get::<i64, _>("employee_id") as u32,
+                    departed_on: r.get("departed_on"),
+                })
+                .collect();
+        }
+
+        let mut in_args = SqliteArguments::default();
+        for candidate in &report.employees {
+            let _ = in_args.add(candidate.employee_id);
+        }
+        let ids = vec!["?"; report.employees.len()].join(", ");
+
+        let old_attendance = "date(COALESCE(clock_out, clock_in)) < date(?)";
+        if policy.action == RetentionAction::Delete && !report.employees.is_empty() {
+            // dochádzka mazaných zamestnancov odíde s nimi, nezapočíta sa dvakrát
+            for table in ["products", "orders", "order_returns", "stock_movements"] {
+                let count: i64 = sqlx::query_scalar_with(
+                    &format!("SELECT COUNT(*) FROM {table} WHERE employee_id IN ({ids})"),
+                    in_args.clone(),
+                )
+                    .fetch_one(&mut *tx)
+                    .await?;
+                report.references += count as u64;
+            }
+            let count: i64 = sqlx::query_scalar_with(
+                &format!("SELECT COUNT(*) FROM attendance WHERE employee_id IN ({ids})"),
+                in_args.clone(),
+            )
+                .fetch_one(&mut *tx)
+                .await?;
+            report.attendance_entries += count as u64;
+        }
+        if let Some(cutoff) = attendance_cutoff {
+            let mut args = SqliteArguments::default();
+            let _ = args.add(cutoff);
+            let mut query = format!("SELECT COUNT(*) FROM attendance WHERE {old_attendance}");
+            if policy.action == RetentionAction::Delete && !report.employees.is_empty() {
+                query.push_str(&format!(" AND employee_id NOT IN ({ids})"));
+                for candidate in &report.employees {
+                    let _ = args.add(candidate.employee_id);
+                }
+            }
+            let count: i64 = sqlx::query_scalar_with(&query, args).fetch_one(&mut *tx).await?;
+            report.attendance_entries += count as u64;
+        }
+        if dry_run {
+            return Ok(report);
+        }
+
+        if !report.employees.is_empty() {
+            match policy.action {
+                RetentionAction::Anonymize => {
+                    sqlx::query_with(
+                        &format!(
+                            r#"
+                            UPDATE employees
+                            SET name = 'Anonymizovaný', surname = 'zamestnanec ' || id, salary = NULL,
+                                phone_number = NULL, phone_e164 = NULL, email = NULL, note = NULL, hire_date = NULL
+                            WHERE id IN ({ids})
+                            "#
+                        ),
+                        in_args.clone(),
+                    )
+                        .execute(&mut *tx)
+                        .await?;
+                    let mut args = SqliteArguments::default();
+                    let _ = args.add(Local::now().naive_local());
+                    for candidate in &report.employees {
+                        let _ = args.add(candidate.employee_id);
+                    }
+                    sqlx::query_with(
+                        &format!("UPDATE employee_departures SET purged_at = ? WHERE employee_id IN ({ids})"),
+                        args,
+                    )
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                RetentionAction::Delete => {
+                    for table in ["products", "orders", "order_returns", "stock_movements"] {
+                        sqlx::query_with(
+                            &format!("UPDATE {table} SET employee_id = NULL WHERE employee_id IN ({ids})"),
+                            in_args.clone(),
+                        )
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                    for table in ["attendance", "employees"] {
+                        let column = if table == "employees" { "id" } else { "employee_id" };
+                        sqlx::query_with(
+                            &format!("DELETE FROM {table} WHERE {column} IN ({ids})"),
+                            in_args.clone(),
+                        )
+                            .execute(&mut *tx)
+                            .await?;
+                    }
+                }
+            }
+        }
+        if let Some(cutoff) = attendance_cutoff {
+            sqlx::query(&format!("DELETE FROM attendance WHERE {old_attendance}"))
+                .bind(cutoff)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+
+        let changed = !report.employees.is_empty() || report.attendance_entries > 0;
+        if changed {
+            self.changed(&[Entity::Employees, Entity::Products, Entity::Orders]);
+            let ids: Vec<String> = report.employees.iter().map(|c| c.employee_id.to_string()).collect();
+            self.record_audit(
+                "retention_purge",
+                Some(format!(
+                    "action={}, employees={}, attendance={}, references={}",
+                    policy.action,
+                    ids.join(","),
+                    report.attendance_entries,
+                    report.references
+                )),
+                None,
+            )
+                .await?;
+        }
+        Self::record_rows(report.employees.len());
+        Ok(report)
+    }
+
+    /// Upraví predajné ceny produktov o percento a zaokrúhli ich podľa pravidla.
+    ///
+    /// Ak by niektorá nová cena porušila politiku minimálnej marže, nezmení sa
+    /// žiadna cena.
+    ///
+    /// # Arguments
+    /// * `adjustment` – percento a voliteľný filter kategórie a dodávateľa
+    /// * `rounding` – pravidlo zaokrúhľovania nových cien
+    /// * `margin` – politika minimálnej marže (`None` = kontrola sa vynechá)
+    ///
+    /// # Returns
+    /// Nové ceny upravených produktov (vypočítané aj zaokrúhlené)
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak by ceny klesli na nulu alebo pod ňu,
+    /// `StoreError::PolicyViolation` so zoznamom produktov pod minimálnou maržou
+    #[instrument(skip_all, fields(db.operation.name = "adjust_prices", db.rows = Empty))]
+    pub async fn adjust_prices(
+        &self,
+        adjustment: &PriceAdjustment,
+        rounding: PriceRounding,
+        margin: Option<&MarginPolicy>,
+    ) -> Result<Vec<PriceChange>> {
+        if !adjustment.percent.is_finite() || adjustment.percent <= -100.0 {
+            return Err(StoreError::Invalid(format!("neplatná zmena ceny {} %", adjustment.percent)).into());
+        }
+
+        let mut query = String::from("SELECT id, name, category, cost_price, sell_price FROM products WHERE 1=1");
+        let mut args = SqliteArguments::default();
+        if let Some(category) = adjustment.category.as_ref().filter(|c| !c.is_empty()) {
+            query.push_str(" AND category = ?");
+            let _ = args.add(category.clone());
+        }
+        if let Some(supplier) = adjustment.supplier.as_ref().filter(|s| !s.is_empty()) {
+            query.push_str(" AND supplier = ?");
+            let _ = args.add(supplier.clone());
+        }
+        query.push_str(" ORDER BY id");
+
+        let mut tx = self.m_pool.begin().await?;
+        let rows = sqlx::query_with(&query, args).fetch_all(&mut *tx).await?;
+
+        let mut changes = Vec::with_capacity(rows.len());
+        let mut violations = Vec::new();
+        for row in &rows {
+            let old_price: f64 = row.get("sell_price");
+            let raw_price = old_price * (1.0 + adjustment.percent / 100.0);
+            let change = PriceChange {
+                product_id: row.get::<i64, _>("id") as u32,
+                name: row.get("name"),
+                old_price: Some(old_price),
+                raw_price,
+                price: rounding.apply(raw_price),
+            };
+
+            let category: Option<String> = row.get("category");
+            if let Some(shortfall) = margin.and_then(|p| p.check(category.as_deref(), row.get("cost_price"), change.price)) {
+                violations.push(format!(
+                    "{} ({}): {shortfall}",
+                    change.name.as_deref().unwrap_or("?"),
+                    change.product_id
+                ));
+                continue;
+            }
+
+            sqlx::query("UPDATE products SET sell_price = ? WHERE id = ?")
+                .bind(change.price)
+                .bind(change.product_id)
+                .execute(&mut *tx)
+                .await?;
+            changes.push(change);
+        }
+
+        if !violations.is_empty() {
+            return Err(StoreError::PolicyViolation(format!(
+                "{} produktov by kleslo pod minimálnu maržu: {}",
+                violations.len(),
+                violations.join("; ")
+            ))
+                .into());
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Self::record_rows(changes.len());
+        Ok(changes)
+    }
+
+    /// Zapíše nové nákupné ceny produktov (import cenníka dodávateľa).
+    ///
+    /// Všetky ceny sa menia v jednej transakcii; ak niektorý produkt neexistuje,
+    /// nezmení sa žiadna cena.
+    ///
+    /// # Arguments
+    /// * `prices` – dvojice (ID produktu, nová nákupná cena)
+    ///
+    /// # Returns
+    /// Počet zmenených produktov
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak niektorý produkt neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "set_cost_prices", db.rows = Empty))]
+    pub async fn set_cost_prices(&self, prices: &[(u32, f64)]) -> Result<u64> {
+        let mut tx = self.m_pool.begin().await?;
+        for &(id, cost_price) in prices {
+            let result = sqlx::query("UPDATE products SET cost_price = ? WHERE id = ?")
+                .bind(cost_price)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            if result.rows_affected() == 0 {
+                return Err(StoreError::NotFound(format!("produkt {id}")).into());
+            }
+        }
+        tx.commit().await?;
+        if !prices.is_empty() {
+            self.changed(&[Entity::Products]);
+        }
+        Self::record_rows(prices.len());
+        Ok(prices.len() as u64)
+    }
+
+    /// Zaokrúhli predajné ceny všetkých produktov podľa pravidla.
+    ///
+    /// Všetky ceny sa menia v jednej transakcii; ak by niektorá nová cena
+    /// porušila politiku minimálnej marže, nezmení sa žiadna cena. Pri `dry_run`
+    /// sa zmeny len vypočítajú a transakcia sa zahodí.
+    ///
+    /// # Arguments
+    /// * `rule` – pravidlo zaokrúhľovania
+    /// * `dry_run` – len náhľad, ceny sa nezapíšu
+    /// * `margin` – politika minimálnej marže (`None` = kontrola sa vynechá)
+    ///
+    /// # Returns
+    /// Produkty, ktorých cena sa zaokrúhlením zmení (pôvodná aj nová cena)
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri pravidle `none`,
+    /// `StoreError::PolicyViolation` so zoznamom produktov pod minimálnou maržou
+    #[instrument(skip_all, fields(db.operation.name = "apply_price_rounding", db.rows = Empty))]
+    pub async fn apply_price_rounding(
+        &self,
+        rule: PriceRounding,
+        dry_run: bool,
+        margin: Option<&MarginPolicy>,
+    ) -> Result<Vec<PriceChange>> {
+        if rule == PriceRounding::None {
+            return Err(StoreError::Invalid("pravidlo none nemení žiadne ceny".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let rows = sqlx::query(
+            "SELECT id, name, category, cost_price, sell_price FROM products WHERE sell_price > 0 ORDER BY id",
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let mut changes = Vec::new();
+        let mut violations = Vec::new();
+        for row in &rows {
+            let old_price: f64 = row.get("sell_price");
+            let price = rule.apply(old_price);
+            if (price - old_price).abs() < 1e-9 {
+                continue;
+            }
+            let change = PriceChange {
+                product_id: row.get::<i64, _>("id") as u32,
+                name: row.get("name"),
+                old_price: Some(old_price),
+                raw_price: old_price,
+                price,
+            };
+
+            let category: Option<String> = row.get("category");
+            if let Some(shortfall) = margin.and_then(|p| p.check(category.as_deref(), row.get("cost_price"), price)) {
+                violations.push(format!(
+                    "{} ({}): {shortfall}",
+                    change.name.as_deref().unwrap_or("?"),
+                    change.product_id
+                ));
+                continue;
+            }
+
+            if !dry_run {
+                sqlx::query("UPDATE products SET sell_price = ? WHERE id = ?")
+                    .bind(price)
+                    .bind(change.product_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            changes.push(change);
+        }
+
+        if !violations.is_empty() {
+            return Err(StoreError::PolicyViolation(format!(
+                "{} produktov by kleslo pod minimálnu maržu: {}",
+                violations.len(),
+                violations.join("; ")
+            ))
+                .into());
+        }
+
+        if dry_run {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+            if !changes.is_empty() {
+                self.changed(&[Entity::Products]);
+            }
+        }
+        Self::record_rows(changes.len());
+        Ok(changes)
+    }
 
     /// Aktualizuje produkt podľa ID.
-    pub async fn update_product(&self, product: &Product) -> Result<bool> {
+    ///
+    /// Zmena množstva sa premietne do šarží: prírastok do šarže `legacy`,
+    /// úbytok zo šarží s najskoršou expiráciou. Množstvo sady sa ignoruje
+    /// a `is_bundle` sa nemení (nastavuje ho zloženie sady). Nová predajná cena
+    /// sa porovná s (novou) nákupnou cenou podľa politiky minimálnej marže;
+    /// samotná zmena nákupnej ceny sa neblokuje.
+    ///
+    /// # Arguments
+    /// * `product` – čiastočný záznam s ID
+    /// * `margin` – politika minimálnej marže (`None` = kontrola sa vynechá)
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak (nové) množstvo alebo `min_stock`/`max_stock` nezodpovedá (novej) jednotke
+    /// produktu alebo sada nie je v kusoch, `StoreError::PolicyViolation` ak nová predajná
+    /// cena nespĺňa minimálnu maržu, `StoreError::Conflict` ak nové SKU už má iný produkt
+    /// alebo by vyššie množstvo či zmena kategórie prekročili limit kategórie
+    #[instrument(skip_all, fields(db.operation.name = "update_product", db.rows = Empty))]
+    pub async fn update_product(&self, product: &Product, margin: Option<&MarginPolicy>) -> Result<bool> {
         let id = match product.id {
             Some(id) => id,
             None => return Ok(false),
         };
 
-        let mut query = String::from("UPDATE products SET ");
-        let mut args = sqlx::sqlite::SqliteArguments::default();
-        let mut updates = Vec::new();
-
-        if let Some(v) = &product.name { updates.push("name = ?"); args.add(v); }
-        if let Some(v) = &product.category { updates.push("category = ?"); args.add(v); }
-        if let Some(v) = &product.quantity { updates.push("quantity = ?"); args.add(v); }
-        if let Some(v) = &product.status { updates.push("status = ?"); args.add(v); }
-        if let Some(v) = &product.bar_code { updates.push("bar_code = ?"); args.add(v); }
-        if let Some(v) = &product.cost_price { updates.push("cost_price = ?"); args.add(v); }
-        if let Some(v) = &product.sell_price { updates.push("sell_price = ?"); args.add(v); }
-        if let Some(v) = &product.description { updates.push("description = ?"); args.add(v); }
-        if let Some(v) = &product.brand { updates.push("brand = ?"); args.add(v); }
-        if let Some(v) = &product.supplier { updates.push("supplier = ?"); args.add(v); }
-        if let Some(v) = &product.employee_id { updates.push("employee_id = ?"); args.add(v); }
-        if let Some(v) = &product.date_added { updates.push("date_added = ?"); args.add(v); }
-        if let Some(v) = &product.date_remove { updates.push("date_remove = ?"); args.add(v); }
-
-        if updates.is_empty() {
-            return Ok(false);
+        let mut tx = self.m_pool.begin().await?;
+        let current = sqlx::query(
+            "SELECT quantity, unit, pack_size, is_bundle, category, cost_price FROM products WHERE id = ?",
+        )
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let is_bundle = current.as_ref().is_some_and(|c| c.get::<i64, _>("is_bundle") == 1);
+        if is_bundle && product.unit.is_some_and(|u| u != Unit::Piece) {
+            return Err(StoreError::Invalid("sada sa predáva len po kusoch".into()).into());
+        }
+        if let (Some(policy), Some(current), Some(sell_price)) = (margin, &current, product.sell_price) {
+            let category = product.category.clone().or_else(|| current.get("category"));
+            let cost_price = product.cost_price.or_else(|| current.get("cost_price"));
+            if let Some(shortfall) = policy.check(category.as_deref(), cost_price, sell_price) {
+                return Err(StoreError::PolicyViolation(format!(
+                    "predajná cena {sell_price:.2} €: {shortfall} (nákupná cena {:.2} €)",
+                    cost_price.unwrap_or_default()
+                ))
+                    .into());
+            }
+        }
+        let new_quantity = product.quantity.filter(|_| !is_bundle);
+
+        let assignments = Self::product_assignments(product, new_quantity);
+        if assignments.is_empty() {
+            return Ok(false);
+        }
+
+        let query = format!("UPDATE products SET {} WHERE id = ?", assignments.sql());
+        let mut args = assignments.args;
+        let _ = args.add(id);
+
+        if let Some(current) = &current {
+            let unit = product.unit.unwrap_or_else(|| Self::unit_of(current));
+            Self::validate_unit(
+                unit,
+                new_quantity.unwrap_or_else(|| Self::quantity_of(current, "quantity")),
+                product.pack_size.or_else(|| current.get::<Option<i64>, _>("pack_size").map(|v| v as u32)),
+            )?;
+            Self::validate_stock_limit(unit, product.min_stock)?;
+            Self::validate_stock_limit(unit, product.max_stock)?;
+        }
+
+        let result = sqlx::query_with(&query, args)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| Self::sku_taken(e, product.sku.as_deref()))?;
+
+        if let (Some(current), Some(quantity)) = (&current, new_quantity) {
+            Self::apply_quantity_delta(&mut tx, id, quantity - Self::quantity_of(current, "quantity")).await?;
+        }
+        if let Some(current) = &current {
+            let grows = new_quantity.is_some_and(|q| q > Self::quantity_of(current, "quantity") + QUANTITY_EPSILON);
+            let moves = product
+                .category
+                .as_ref()
+                .is_some_and(|c| current.get::<Option<String>, _>("category").as_ref() != Some(c));
+            if grows || moves {
+                Self::check_category_capacity(&mut tx, id).await?;
+            }
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Stĺpce produktu, ktoré sa majú zmeniť (polia, ktoré nie sú `None`).
+    ///
+    /// # Arguments
+    /// * `product` – čiastočný záznam
+    /// * `quantity` – nové množstvo (pri sadách sa nezapisuje)
+    fn product_assignments(product: &Product, quantity: Option<f64>) -> Assignments<'_> {
+        let mut a = Assignments::default();
+        a.set("name", product.name.as_ref());
+        a.set("category", product.category.as_ref());
+        a.set("quantity", quantity);
+        a.set("unit", product.unit.map(|u| u.as_str()));
+        a.set("pack_size", product.pack_size);
+        a.set("status", product.status);
+        a.set("bar_code", product.bar_code);
+        a.set("sku", product.sku.as_ref());
+        a.set("min_stock", product.min_stock);
+        a.set("max_stock", product.max_stock);
+        a.set("lead_time_days", product.lead_time_days);
+        a.set("cost_price", product.cost_price);
+        a.set("sell_price", product.sell_price);
+        a.set("description", product.description.as_ref());
+        a.set("brand", product.brand.as_ref());
+        a.set("supplier", product.supplier.as_ref());
+        a.set("employee_id", product.employee_id);
+        a.set("date_added", product.date_added);
+        a.set("date_remove", product.date_remove);
+        a
+    }
+
+    /// Nastaví rovnaké polia viacerým produktom naraz (napr. novú značku po zmene dodávateľa).
+    ///
+    /// Jednotka a veľkosť balenia sa overia voči každému produktu rovnako ako
+    /// pri úprave jedného produktu. Množstvo sa hromadne nemení – zásoby sa
+    /// upravujú cez šarže a inventúrne úpravy.
+    ///
+    /// # Arguments
+    /// * `ids` – ID upravovaných produktov
+    /// * `set` – čiastočný produkt so zmenami
+    ///
+    /// # Returns
+    /// Výsledok pre každé ID v poradí požiadavky
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom zozname ID alebo zmene, pri zadanom
+    /// `id`, `is_bundle`, `quantity` či `sku` (SKU je jedinečné) a ak zmena
+    /// nezodpovedá niektorému produktu, `StoreError::Conflict` ak by presun
+    /// prekročil limit novej kategórie
+    #[instrument(skip_all, fields(db.operation.name = "bulk_update_products", db.rows = Empty))]
+    pub async fn bulk_update_products(&self, ids: &[u32], set: &Product) -> Result<Vec<BulkUpdateResult>> {
+        Self::reject_fields(&[
+            ("id", set.id.is_some()),
+            ("is_bundle", set.is_bundle.is_some()),
+            ("quantity", set.quantity.is_some()),
+            ("sku", set.sku.is_some()),
+        ])?;
+        let check = |current: &SqliteRow| {
+            let is_bundle = current.get::<i64, _>("is_bundle") == 1;
+            if is_bundle && set.unit.is_some_and(|u| u != Unit::Piece) {
+                return Err(StoreError::Invalid(format!(
+                    "produkt {}: sada sa predáva len po kusoch",
+                    current.get::<i64, _>("id")
+                ))
+                .into());
+            }
+            let unit = set.unit.unwrap_or_else(|| Self::unit_of(current));
+            Self::validate_unit(
+                unit,
+                Self::quantity_of(current, "quantity"),
+                set.pack_size.or_else(|| current.get::<Option<i64>, _>("pack_size").map(|v| v as u32)),
+            )?;
+            Self::validate_stock_limit(unit, set.min_stock)?;
+            Self::validate_stock_limit(unit, set.max_stock)
+        };
+        let mut tx = self.m_pool.begin().await?;
+        let found = Self::bulk_update_in(&mut tx, "products", ids, Self::product_assignments(set, None), check).await?;
+        // presun do kategórie s limitom nesmie prekročiť jej kapacitu (všetky idú do tej istej)
+        if let (Some(&id), Some(_)) = (found.first(), &set.category) {
+            Self::check_category_capacity(&mut tx, id).await?;
+        }
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(Self::bulk_results(ids, &found))
+    }
+
+    /// Odmietne hromadnú zmenu polí, ktoré sa nedajú hromadne nastaviť.
+    ///
+    /// # Arguments
+    /// * `fields` – názov poľa a či je v zmene zadané
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` s názvom prvého zadaného poľa
+    fn reject_fields(fields: &[(&str, bool)]) -> Result<()> {
+        match fields.iter().find(|(_, present)| *present) {
+            Some((name, _)) => Err(StoreError::Invalid(format!("pole `{name}` sa nedá hromadne nastaviť")).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Zapíše rovnakú zmenu všetkým existujúcim záznamom z `ids` jedným `UPDATE` v transakcii.
+    ///
+    /// # Arguments
+    /// * `table` – tabuľka
+    /// * `entity` – entita, ktorej cache sa zneplatní
+    /// * `ids` – ID záznamov (duplicity sa ignorujú)
+    /// * `assignments` – menené stĺpce
+    /// * `check` – kontrola zmeny voči aktuálnemu riadku; chyba zruší celú zmenu
+    ///
+    /// # Returns
+    /// `updated` alebo `not_found` pre každé ID v poradí požiadavky
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom zozname ID alebo zmene, chyba z `check`
+    async fn bulk_update(
+        &self,
+        table: &str,
+        entity: Entity,
+        ids: &[u32],
+        assignments: Assignments<'_>,
+        check: impl Fn(&SqliteRow) -> Result<()>,
+    ) -> Result<Vec<BulkUpdateResult>> {
+        let mut tx = self.m_pool.begin().await?;
+        let found = Self::bulk_update_in(&mut tx, table, ids, assignments, check).await?;
+        tx.commit().await?;
+        self.changed(&[entity]);
+        Ok(Self::bulk_results(ids, &found))
+    }
+
+    /// Zapíše hromadnú zmenu v otvorenej transakcii (pozri `bulk_update`).
+    ///
+    /// # Returns
+    /// ID existujúcich, teda zmenených záznamov
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom zozname ID alebo zmene, chyba z `check`
+    async fn bulk_update_in(
+        tx: &mut sqlx::Transaction<'_, Sqlite>,
+        table: &str,
+        ids: &[u32],
+        assignments: Assignments<'_>,
+        check: impl Fn(&SqliteRow) -> Result<()>,
+    ) -> Result<Vec<u32>> {
+        if ids.is_empty() {
+            return Err(StoreError::Invalid("zoznam `ids` je prázdny".into()).into());
+        }
+        if assignments.is_empty() {
+            return Err(StoreError::Invalid("zmena `set` neobsahuje žiadne pole".into()).into());
+        }
+        let mut unique = ids.to_vec();
+        unique.sort_unstable();
+        unique.dedup();
+        let placeholders = vec!["?"; unique.len()].join(", ");
+
+        let select = format!("SELECT * FROM {table} WHERE id IN ({placeholders})");
+        let mut query = sqlx::query(&select);
+        for id in &unique {
+            query = query.bind(id);
+        }
+        let current = query.fetch_all(&mut **tx).await?;
+        for row in &current {
+            check(row)?;
+        }
+        let found: Vec<u32> = current.iter().map(|r| r.get::<i64, _>("id") as u32).collect();
+
+        if !found.is_empty() {
+            let placeholders = vec!["?"; found.len()].join(", ");
+            let sql = format!("UPDATE {table} SET {} WHERE id IN ({placeholders})", assignments.sql());
+            let mut args = assignments.args;
+            for id in &found {
+                let _ = args.add(id);
+            }
+            sqlx::query_with(&sql, args).execute(&mut **tx).await?;
+        }
+        Self::record_rows(found.len());
+        Ok(found)
+    }
+
+    /// Výsledok hromadnej zmeny pre každé ID v poradí požiadavky.
+    fn bulk_results(ids: &[u32], found: &[u32]) -> Vec<BulkUpdateResult> {
+        ids.iter()
+            .map(|&id| BulkUpdateResult {
+                id,
+                status: if found.contains(&id) { BulkUpdateStatus::Updated } else { BulkUpdateStatus::NotFound },
+            })
+            .collect()
+    }
+
+    /// Overí, že množstvo a veľkosť balenia zodpovedajú jednotke produktu.
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri desatinnom množstve kusovej jednotky, zápornom množstve
+    /// alebo nulovej veľkosti balenia
+    fn validate_unit(unit: Unit, quantity: f64, pack_size: Option<u32>) -> Result<()> {
+        if !unit.accepts(quantity) {
+            return Err(StoreError::Invalid(format!(
+                "množstvo {quantity} nie je prípustné pre jednotku {} (desatinné len pre kg, g, l, ml)",
+                unit.as_str()
+            )).into());
+        }
+        if pack_size == Some(0) {
+            return Err(StoreError::Invalid("veľkosť balenia musí byť kladná".into()).into());
+        }
+        Ok(())
+    }
+
+    /// Overí vlastnú hranicu zásob produktu (`min_stock` alebo `max_stock`).
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri zápornej hranici alebo desatinnej hranici kusovej jednotky
+    fn validate_stock_limit(unit: Unit, limit: Option<f64>) -> Result<()> {
+        match limit {
+            Some(min) if !unit.accepts(min) => Err(StoreError::Invalid(format!(
+                "hranica zásob {min} nie je prípustná pre jednotku {} (nezáporná, desatinná len pre kg, g, l, ml)",
+                unit.as_str()
+            )).into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Prepočíta množstvo zadané v jednotke `from` na jednotku produktu a overí ho.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – zadané množstvo
+    /// * `from` – jednotka zadaného množstva (`None` = jednotka produktu)
+    ///
+    /// # Returns
+    /// Množstvo v jednotke produktu
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje, `StoreError::Invalid` ak jednotky
+    /// nie sú zameniteľné alebo výsledok nie je prípustný (napr. 3 kusy z balenia po 12)
+    async fn to_product_unit(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+        from: Option<Unit>,
+    ) -> Result<f64> {
+        let row = sqlx::query("SELECT unit, pack_size FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| StoreError::NotFound(format!("produkt {product_id}")))?;
+        let unit = Self::unit_of(&row);
+        let pack_size = row.get::<Option<i64>, _>("pack_size").map(|v| v as u32);
+        let from = from.unwrap_or(unit);
+
+        if !from.accepts(quantity) {
+            return Err(StoreError::Invalid(format!(
+                "produkt {product_id}: množstvo {quantity} nie je prípustné pre jednotku {}",
+                from.as_str()
+            )).into());
+        }
+        let converted = from.convert(quantity, unit, pack_size).ok_or_else(|| {
+            StoreError::Invalid(format!(
+                "produkt {product_id}: jednotku {} nemožno prepočítať na {}",
+                from.as_str(),
+                unit.as_str()
+            ))
+        })?;
+        if !unit.accepts(converted) {
+            return Err(StoreError::Invalid(format!(
+                "produkt {product_id}: {quantity} {} je {converted} {}, čo nie je celé množstvo",
+                from.as_str(),
+                unit.as_str()
+            )).into());
+        }
+        Ok(converted)
+    }
+
+    /// Premietne zmenu stavu zásob do šarží (prírastok do `legacy`, úbytok podľa expirácie).
+    async fn apply_quantity_delta(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        delta: f64,
+    ) -> Result<()> {
+        if delta > QUANTITY_EPSILON {
+            Self::add_to_batches(tx, product_id, delta, None, None).await?;
+        } else if delta < -QUANTITY_EPSILON {
+            Self::take_from_batches(tx, product_id, -delta).await?;
+        }
+        Ok(())
+    }
+
+    // ==========================
+    // Batches
+    // ==========================
+
+    /// Pripočíta množstvo do šarže produktu (existujúcej so zhodným kódom a expiráciou, inak novej).
+    ///
+    /// Mení len šarže, stav zásob produktu musí upraviť volajúci v tej istej transakcii.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – pripočítané množstvo (v jednotke produktu)
+    /// * `lot_code` – kód šarže (`None` = `legacy`)
+    /// * `expiry_date` – dátum expirácie
+    async fn add_to_batches(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+        lot_code: Option<&str>,
+        expiry_date: Option<NaiveDate>,
+    ) -> Result<()> {
+        let lot_code = lot_code.unwrap_or(LEGACY_LOT);
+        let existing: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM product_batches WHERE product_id = ? AND lot_code = ? AND expiry_date IS ?",
+        )
+            .bind(product_id)
+            .bind(lot_code)
+            .bind(expiry_date)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        match existing {
+            Some(batch_id) => {
+                sqlx::query("UPDATE product_batches SET quantity = quantity + ? WHERE id = ?")
+                    .bind(quantity)
+                    .bind(batch_id)
+                    .execute(&mut **tx)
+                    .await?;
+            }
+            None => {
+                sqlx::query(
+                    "INSERT INTO product_batches (product_id, lot_code, expiry_date, quantity) VALUES (?, ?, ?, ?)",
+                )
+                    .bind(product_id)
+                    .bind(lot_code)
+                    .bind(expiry_date)
+                    .bind(quantity)
+                    .execute(&mut **tx)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Odpíše množstvo zo šarží produktu, najskôr z tých s najskoršou expiráciou.
+    ///
+    /// Vyprázdnené šarže sa vymažú. Mení len šarže, stav zásob produktu musí
+    /// upraviť volajúci v tej istej transakcii.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – odpísané množstvo (v jednotke produktu)
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak šarže neobsahujú dosť tovaru
+    async fn take_from_batches(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+    ) -> Result<()> {
+        let batches = sqlx::query(
+            r#"
+            SELECT id, quantity FROM product_batches
+            WHERE product_id = ? AND quantity > 0
+            ORDER BY expiry_date IS NULL, expiry_date, id
+            "#,
+        )
+            .bind(product_id)
+            .fetch_all(&mut **tx)
+            .await?;
+
+        let mut remaining = quantity;
+        for batch in &batches {
+            if remaining <= QUANTITY_EPSILON {
+                break;
+            }
+            let batch_id: i64 = batch.get("id");
+            let available = Self::quantity_of(batch, "quantity");
+            let taken = available.min(remaining);
+            remaining -= taken;
+
+            if available - taken <= QUANTITY_EPSILON {
+                sqlx::query("DELETE FROM product_batches WHERE id = ?")
+                    .bind(batch_id)
+                    .execute(&mut **tx)
+                    .await?;
+            } else {
+                sqlx::query("UPDATE product_batches SET quantity = quantity - ? WHERE id = ?")
+                    .bind(taken)
+                    .bind(batch_id)
+                    .execute(&mut **tx)
+                    .await?;
+            }
+        }
+
+        if remaining > QUANTITY_EPSILON {
+            return Err(StoreError::Conflict(format!(
+                "produkt {product_id}: v šaržiach chýba množstvo {remaining}"
+            )).into());
+        }
+        Ok(())
+    }
+
+    /// Vráti šarže produktu zoradené podľa expirácie.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    ///
+    /// # Returns
+    /// Zoznam šarží
+    #[instrument(skip_all, fields(db.operation.name = "get_batches", db.rows = Empty))]
+    pub async fn get_batches(&self, product_id: u32) -> Result<Vec<ProductBatch>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT b.*, p.unit
+            FROM product_batches b
+            JOIN products p ON p.id = b.product_id
+            WHERE b.product_id = ?
+            ORDER BY b.expiry_date IS NULL, b.expiry_date, b.id
+            "#,
+        )
+            .bind(product_id)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::batch_from_row).collect())
+    }
+
+    /// Prevedie riadok z tabuľky `product_batches` (so stĺpcom `unit` produktu) na `ProductBatch`.
+    fn batch_from_row(row: &SqliteRow) -> ProductBatch {
+        ProductBatch {
+            id: Some(row.get::<i64, _>("id") as u32),
+            product_id: Some(row.get::<i64, _>("product_id") as u32),
+            lot_code: row.get("lot_code"),
+            expiry_date: row.get("expiry_date"),
+            quantity: Some(Self::quantity_of(row, "quantity")),
+            unit: Some(Self::unit_of(row)),
+        }
+    }
+
+    /// Naskladní tovar do šarže produktu a zvýši stav zásob.
+    ///
+    /// Množstvo môže byť zadané v inej jednotke (`batch.unit`), prepočíta sa na jednotku produktu.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `batch` – kód šarže, expirácia, množstvo a jeho jednotka
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje, `StoreError::Invalid` pri nulovom
+    /// alebo neprípustnom množstve alebo ak je produkt sada
+    #[instrument(skip_all, fields(db.operation.name = "add_batch", db.rows = Empty))]
+    pub async fn add_batch(&self, product_id: u32, batch: &ProductBatch) -> Result<()> {
+        let mut tx = self.m_pool.begin().await?;
+        if Self::is_bundle(&mut tx, product_id).await? {
+            return Err(StoreError::Invalid(format!("sada {product_id} nemá vlastné zásoby")).into());
+        }
+        let quantity =
+            Self::to_product_unit(&mut tx, product_id, batch.quantity.unwrap_or(0.0), batch.unit).await?;
+        if quantity <= 0.0 {
+            return Err(StoreError::Invalid("šarža musí mať kladné množstvo".into()).into());
+        }
+
+        let updated = sqlx::query("UPDATE products SET quantity = quantity + ? WHERE id = ?")
+            .bind(quantity)
+            .bind(product_id)
+            .execute(&mut *tx)
+            .await?;
+        if updated.rows_affected() == 0 {
+            return Err(StoreError::NotFound(format!("produkt {product_id}")).into());
+        }
+
+        Self::add_to_batches(&mut tx, product_id, quantity, batch.lot_code.as_deref(), batch.expiry_date).await?;
+        Self::insert_movement(&mut tx, product_id, quantity, "adjustment", None).await?;
+        Self::record_lot(&mut tx, product_id, quantity, None).await?;
+        Self::check_category_capacity(&mut tx, product_id).await?;
+
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(())
+    }
+
+    /// Zmení stav zásob produktu o zadané množstvo (inventúrna úprava).
+    ///
+    /// Prírastok sa pripíše do šarže `legacy`, úbytok sa odpíše zo šarží podľa
+    /// expirácie. Zmena sa zapíše ako pohyb skladu `adjustment`.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `delta` – zmena množstva (kladná = naskladnenie, záporná = odpis)
+    /// * `unit` – jednotka zmeny (`None` = jednotka produktu)
+    ///
+    /// # Returns
+    /// Produkt s novým stavom zásob
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje, `StoreError::Invalid` pri nulovom
+    /// alebo neprípustnom množstve alebo ak je produkt sada, `StoreError::Conflict`
+    /// ak na sklade nie je dosť tovaru na odpis
+    #[instrument(skip_all, fields(db.operation.name = "adjust_stock", db.rows = Empty))]
+    pub async fn adjust_stock(&self, product_id: u32, delta: f64, unit: Option<Unit>) -> Result<Product> {
+        let mut tx = self.m_pool.begin().await?;
+        if Self::is_bundle(&mut tx, product_id).await? {
+            return Err(StoreError::Invalid(format!("sada {product_id} nemá vlastné zásoby")).into());
+        }
+        let quantity = Self::to_product_unit(&mut tx, product_id, delta.abs(), unit).await?;
+        if quantity <= 0.0 {
+            return Err(StoreError::Invalid("zmena zásob musí byť nenulová".into()).into());
+        }
+
+        if delta < 0.0 {
+            Self::take_stock(&mut tx, product_id, quantity).await?;
+            Self::insert_movement(&mut tx, product_id, -quantity, "adjustment", None).await?;
+        } else {
+            sqlx::query("UPDATE products SET quantity = quantity + ? WHERE id = ?")
+                .bind(quantity)
+                .bind(product_id)
+                .execute(&mut *tx)
+                .await?;
+            Self::add_to_batches(&mut tx, product_id, quantity, None, None).await?;
+            Self::insert_movement(&mut tx, product_id, quantity, "adjustment", None).await?;
+            Self::record_lot(&mut tx, product_id, quantity, None).await?;
+            Self::check_category_capacity(&mut tx, product_id).await?;
+        }
+
+        let row = sqlx::query(&format!("{PRODUCT_SELECT} WHERE id = ?"))
+            .bind(product_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(Self::product_from_row(&row))
+    }
+
+    /// Porovná spočítané množstvá z inventúry so stavom zásob v evidencii.
+    ///
+    /// S `apply` sa stav zásob nastaví na spočítané množstvo: manko sa odpíše zo
+    /// šarží podľa expirácie, prebytok sa pripíše do šarže `legacy`; každý rozdiel
+    /// sa zapíše ako pohyb skladu `stocktake`. Všetko prebehne v jednej transakcii,
+    /// pri chybe sa nezmení nič.
+    ///
+    /// # Arguments
+    /// * `counts` – spočítané množstvá (v jednotke produktu)
+    /// * `apply` – zosúladiť stav zásob so spočítaným množstvom
+    ///
+    /// # Returns
+    /// Produkty s rozdielom a hodnota manka v nákupných cenách
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` pri neznámom produkte, `StoreError::Invalid` pri
+    /// prázdnom zozname, opakovanom produkte, sade alebo neprípustnom množstve
+    #[instrument(skip_all, fields(db.operation.name = "reconcile", db.rows = Empty))]
+    pub async fn reconcile(&self, counts: &[StockCount], apply: bool) -> Result<StocktakeReport> {
+        if counts.is_empty() {
+            return Err(StoreError::Invalid("inventúra neobsahuje žiadne produkty".into()).into());
+        }
+        let mut seen = BTreeSet::new();
+        if let Some(count) = counts.iter().find(|c| !seen.insert(c.id)) {
+            return Err(StoreError::Invalid(format!("produkt {} je v inventúre viackrát", count.id)).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let mut report = StocktakeReport { applied: apply, counted: counts.len() as u32, ..StocktakeReport::default() };
+        for count in counts {
+            if Self::is_bundle(&mut tx, count.id).await? {
+                return Err(StoreError::Invalid(format!("sada {} nemá vlastné zásoby", count.id)).into());
+            }
+            let counted = Self::to_product_unit(&mut tx, count.id, count.counted, None).await?;
+            let row = sqlx::query("SELECT name, quantity, unit, cost_price FROM products WHERE id = ?")
+                .bind(count.id)
+                .fetch_one(&mut *tx)
+                .await?;
+            let recorded = Self::quantity_of(&row, "quantity");
+            let difference = counted - recorded;
+            if difference.abs() <= QUANTITY_EPSILON {
+                report.matched += 1;
+                continue;
+            }
+
+            let cost_price: Option<f64> = row.get("cost_price");
+            let value = cost_price.map(|cost| difference * cost);
+            if difference < 0.0 {
+                report.shrinkage_units -= difference;
+                match value {
+                    Some(value) => report.shrinkage_value -= value,
+                    None => report.uncosted_lines += 1,
+                }
+            }
+
+            if apply {
+                if difference < 0.0 {
+                    Self::take_stock(&mut tx, count.id, -difference).await?;
+                } else {
+                    sqlx::query("UPDATE products SET quantity = quantity + ? WHERE id = ?")
+                        .bind(difference)
+                        .bind(count.id)
+                        .execute(&mut *tx)
+                        .await?;
+                    Self::add_to_batches(&mut tx, count.id, difference, None, None).await?;
+                    Self::record_lot(&mut tx, count.id, difference, None).await?;
+                }
+                Self::insert_movement(&mut tx, count.id, difference, "stocktake", None).await?;
+            }
+
+            report.discrepancies.push(StockDiscrepancy {
+                product_id: count.id,
+                name: row.get("name"),
+                unit: Self::unit_of(&row),
+                recorded,
+                counted,
+                difference,
+                value,
+            });
+        }
+
+        if apply {
+            tx.commit().await?;
+            if !report.discrepancies.is_empty() {
+                self.changed(&[Entity::Products]);
+            }
+        } else {
+            tx.rollback().await?;
+        }
+        Self::record_rows(report.discrepancies.len());
+        Ok(report)
+    }
+
+    /// Upraví šaržu produktu; zmena množstva sa premietne do stavu zásob.
+    ///
+    /// Šarža s množstvom 0 sa vymaže.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `batch` – nové dáta šarže (musí obsahovať `id`)
+    ///
+    /// # Returns
+    /// `true` ak šarža existuje a bola upravená
+    #[instrument(skip_all, fields(db.operation.name = "update_batch", db.rows = Empty))]
+    pub async fn update_batch(&self, product_id: u32, batch: &ProductBatch) -> Result<bool> {
+        let batch_id = match batch.id {
+            Some(id) => id,
+            None => return Ok(false),
+        };
+
+        let mut tx = self.m_pool.begin().await?;
+        let previous = sqlx::query("SELECT quantity FROM product_batches WHERE id = ? AND product_id = ?")
+            .bind(batch_id)
+            .bind(product_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let previous = match previous {
+            Some(row) => Self::quantity_of(&row, "quantity"),
+            None => return Ok(false),
+        };
+
+        if let Some(v) = &batch.lot_code {
+            sqlx::query("UPDATE product_batches SET lot_code = ? WHERE id = ?")
+                .bind(v)
+                .bind(batch_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        if let Some(v) = batch.expiry_date {
+            sqlx::query("UPDATE product_batches SET expiry_date = ? WHERE id = ?")
+                .bind(v)
+                .bind(batch_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        if let Some(quantity) = batch.quantity {
+            let quantity = Self::to_product_unit(&mut tx, product_id, quantity, batch.unit).await?;
+            let delta = quantity - previous;
+            if quantity <= QUANTITY_EPSILON {
+                sqlx::query("DELETE FROM product_batches WHERE id = ?")
+                    .bind(batch_id)
+                    .execute(&mut *tx)
+                    .await?;
+            } else {
+                sqlx::query("UPDATE product_batches SET quantity = ? WHERE id = ?")
+                    .bind(quantity)
+                    .bind(batch_id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            if delta.abs() > QUANTITY_EPSILON {
+                sqlx::query("UPDATE products SET quantity = quantity + ? WHERE id = ?")
+                    .bind(delta)
+                    .bind(product_id)
+                    .execute(&mut *tx)
+                    .await?;
+                Self::insert_movement(&mut tx, product_id, delta, "adjustment", None).await?;
+            }
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(true)
+    }
+
+    /// Vráti šarže, ktoré expirujú do `days` dní (vrátane už expirovaných).
+    ///
+    /// # Arguments
+    /// * `days` – počet dní od dnešného dňa
+    ///
+    /// # Returns
+    /// Šarže zoradené podľa expirácie
+    #[instrument(skip_all, fields(db.operation.name = "expiring_batches", db.rows = Empty))]
+    pub async fn expiring_batches(&self, days: u32) -> Result<Vec<ExpiringBatch>> {
+        let today = Local::now().date_naive();
+        let until = today + chrono::Days::new(days as u64);
+
+        let rows = sqlx::query(
+            r#"
+            SELECT b.*, p.name AS product_name, p.unit
+            FROM product_batches b
+            JOIN products p ON p.id = b.product_id
+            WHERE b.expiry_date IS NOT NULL AND b.expiry_date <= ? AND b.quantity > 0
+            ORDER BY b.expiry_date, b.id
+            "#,
+        )
+            .bind(until)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let batch = Self::batch_from_row(r);
+                let days_left = batch.expiry_date.map(|d| (d - today).num_days()).unwrap_or(0);
+                ExpiringBatch {
+                    product_name: r.get("product_name"),
+                    days_left,
+                    expired: days_left < 0,
+                    batch,
+                }
+            })
+            .collect())
+    }
+
+    /// Zapíše pohyb skladu v rámci transakcie (teraz, bez zamestnanca).
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – zmena množstva (kladná = príjem, záporná = výdaj)
+    /// * `reason` – dôvod pohybu (`purchase`, `adjustment`, ...)
+    /// * `reference` – voliteľný odkaz na doklad
+    async fn insert_movement(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+        reason: &str,
+        reference: Option<String>,
+    ) -> Result<()> {
+        Self::insert_movement_by(tx, product_id, quantity, reason, reference, None, Local::now().naive_local()).await
+    }
+
+    /// Zapíše pohyb skladu v rámci transakcie so zamestnancom a časom dokladu.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – zmena množstva (kladná = príjem, záporná = výdaj)
+    /// * `reason` – dôvod pohybu (`sale`, `return`, `purchase`, `adjustment`)
+    /// * `reference` – voliteľný odkaz na doklad
+    /// * `employee_id` – zamestnanec, ktorý pohyb vykonal
+    /// * `created_at` – čas pohybu (napr. čas objednávky)
+    async fn insert_movement_by(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+        reason: &str,
+        reference: Option<String>,
+        employee_id: Option<u32>,
+        created_at: NaiveDateTime,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO stock_movements (product_id, quantity, reason, reference, employee_id, created_at) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+            .bind(product_id)
+            .bind(quantity)
+            .bind(reason)
+            .bind(reference)
+            .bind(employee_id)
+            .bind(created_at)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Vráti pohyby skladu produktu za obdobie (kniha pohybov).
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `from` – prvý deň obdobia (`None` = od začiatku)
+    /// * `to` – posledný deň obdobia (`None` = do dnes)
+    ///
+    /// # Returns
+    /// Pohyby od najstaršieho
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "movements", db.rows = Empty))]
+    pub async fn movements(
+        &self,
+        product_id: u32,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<StockMovement>> {
+        let unit = sqlx::query("SELECT unit FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_optional(&self.m_pool)
+            .await?
+            .map(|row| Self::unit_of(&row))
+            .ok_or_else(|| StoreError::NotFound(format!("produkt {product_id}")))?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, product_id, quantity, reason, reference, employee_id, created_at
+            FROM stock_movements
+            WHERE product_id = ?1
+              AND (?2 IS NULL OR date(created_at) >= ?2) AND (?3 IS NULL OR date(created_at) <= ?3)
+            ORDER BY created_at, id
+            "#,
+        )
+            .bind(product_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows.iter().map(|r| Self::movement_from_row(r, unit)).collect())
+    }
+
+    /// Vráti najnovšie pohyby skladu všetkých produktov.
+    ///
+    /// # Arguments
+    /// * `limit` – najviac toľko pohybov
+    ///
+    /// # Returns
+    /// Pohyby od najnovšieho
+    async fn recent_movements(&self, limit: u32) -> Result<Vec<StockMovement>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT m.id, m.product_id, m.quantity, m.reason, m.reference, m.employee_id, m.created_at, p.unit
+            FROM stock_movements m
+            JOIN products p ON p.id = m.product_id
+            ORDER BY m.created_at DESC, m.id DESC
+            LIMIT ?
+            "#,
+        )
+            .bind(limit)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Ok(rows.iter().map(|r| Self::movement_from_row(r, Self::unit_of(r))).collect())
+    }
+
+    /// Prevedie riadok `stock_movements` na pohyb skladu.
+    fn movement_from_row(r: &SqliteRow, unit: Unit) -> StockMovement {
+        let quantity = Self::quantity_of(r, "quantity");
+        StockMovement {
+            id: r.get::<i64, _>("id") as u32,
+            product_id: r.get::<i64, _>("product_id") as u32,
+            direction: if quantity < 0.0 { MovementDirection::Out } else { MovementDirection::In },
+            quantity,
+            unit,
+            reason: r.get("reason"),
+            reference: r.get("reference"),
+            employee_id: r.get::<Option<i64>, _>("employee_id").map(|id| id as u32),
+            created_at: r.get("created_at"),
+        }
+    }
+
+    /// Zapíše nákupnú dávku (príjem s nákupnou cenou) pre oceňovanie zásob.
+    ///
+    /// Bez zadanej ceny sa použije aktuálna nákupná cena produktu; ak ani tá nie je
+    /// známa, dávka sa nezapíše a množstvo sa oceňuje ako zásoba spred evidencie dávok.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – prijaté množstvo (v jednotke produktu)
+    /// * `unit_cost` – nákupná cena za jednotku (`None` = `cost_price` produktu)
+    async fn record_lot(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+        unit_cost: Option<f64>,
+    ) -> Result<()> {
+        if quantity <= QUANTITY_EPSILON {
+            return Ok(());
+        }
+        let unit_cost = match unit_cost {
+            Some(cost) => Some(cost),
+            None => sqlx::query_scalar("SELECT cost_price FROM products WHERE id = ?")
+                .bind(product_id)
+                .fetch_optional(&mut **tx)
+                .await?
+                .flatten(),
+        };
+        let Some(unit_cost) = unit_cost else {
+            return Ok(());
+        };
+        sqlx::query("INSERT INTO stock_lots (product_id, quantity, unit_cost, received_at) VALUES (?, ?, ?, ?)")
+            .bind(product_id)
+            .bind(quantity)
+            .bind(unit_cost)
+            .bind(Local::now().naive_local())
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Vráti zoznam produktov podľa filtra.
+    #[instrument(skip_all, fields(db.operation.name = "get_products", db.rows = Empty))]
+    pub async fn get_products(&self, product: Product) -> Result<Vec<Product>, sqlx::Error> {
+        let (query, args) = Self::product_filter(PRODUCT_SELECT, product, false);
+        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Vráti produkt podľa ID.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    ///
+    /// # Returns
+    /// Produkt alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_product", db.rows = Empty))]
+    pub async fn get_product(&self, id: u32) -> Result<Option<Product>, sqlx::Error> {
+        let row = sqlx::query(&format!("{PRODUCT_SELECT} WHERE id = ?"))
+            .bind(id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        Ok(row.as_ref().map(Self::product_from_row))
+    }
+
+    /// Vráti produkt podľa SKU.
+    ///
+    /// # Arguments
+    /// * `sku` – SKU produktu (na veľkosti písmen nezáleží)
+    ///
+    /// # Returns
+    /// Produkt alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_product_by_sku", db.rows = Empty))]
+    pub async fn get_product_by_sku(&self, sku: &str) -> Result<Option<Product>, sqlx::Error> {
+        let row = sqlx::query(&format!("{PRODUCT_SELECT} WHERE sku = ?"))
+            .bind(sku::normalize(sku))
+            .fetch_optional(&self.m_pool)
+            .await?;
+        Ok(row.as_ref().map(Self::product_from_row))
+    }
+
+    /// Vráti produkty s daným čiarovým kódom.
+    ///
+    /// # Arguments
+    /// * `bar_code` – čiarový kód
+    ///
+    /// # Returns
+    /// Produkty zoradené podľa ID (čiarový kód nemusí byť jedinečný)
+    #[instrument(skip_all, fields(db.operation.name = "get_products_by_barcode", db.rows = Empty))]
+    pub async fn get_products_by_barcode(&self, bar_code: i64) -> Result<Vec<Product>, sqlx::Error> {
+        let rows = sqlx::query(&format!("{PRODUCT_SELECT} WHERE bar_code = ? ORDER BY id"))
+            .bind(bar_code)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Vráti ID existujúcich produktov s danými čiarovými kódmi jedným dotazom.
+    ///
+    /// # Arguments
+    /// * `codes` – čiarové kódy
+    ///
+    /// # Returns
+    /// ID produktov zoradené vzostupne pre každý kód, ktorý má aspoň jeden produkt
+    #[instrument(skip_all, fields(db.operation.name = "product_ids_by_barcodes", db.rows = Empty))]
+    pub async fn product_ids_by_barcodes(&self, codes: &[i64]) -> Result<BTreeMap<i64, Vec<u32>>, sqlx::Error> {
+        let mut ids: BTreeMap<i64, Vec<u32>> = BTreeMap::new();
+        for chunk in codes.chunks(ID_QUERY_CHUNK) {
+            let sql = format!(
+                "SELECT bar_code, id FROM products WHERE bar_code IN ({}) ORDER BY id",
+                vec!["?"; chunk.len()].join(", ")
+            );
+            let mut query = sqlx::query(&sql);
+            for code in chunk {
+                query = query.bind(code);
+            }
+            for row in query.fetch_all(&self.m_pool).await? {
+                ids.entry(row.get("bar_code")).or_default().push(row.get::<i64, _>("id") as u32);
+            }
+        }
+        Self::record_rows(ids.values().map(Vec::len).sum());
+        Ok(ids)
+    }
+
+    /// Vráti kompaktný index aktívnych produktov na vyhľadávanie u klienta.
+    ///
+    /// # Returns
+    /// ID, názov, čiarový kód, kategória a predajná cena aktívnych produktov zoradené podľa ID
+    #[instrument(skip_all, fields(db.operation.name = "search_index", db.rows = Empty))]
+    pub async fn search_index(&self) -> Result<Vec<SearchIndexEntry>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, name, bar_code, category, sell_price FROM products WHERE COALESCE(status, 1) = 1 ORDER BY id",
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows
+            .iter()
+            .map(|r| SearchIndexEntry {
+                id: r.get::<i64, _>("id") as u32,
+                name: r.get("name"),
+                bar_code: r.get("bar_code"),
+                category: r.get("category"),
+                price: r.get("sell_price"),
+            })
+            .collect())
+    }
+
+    /// Vráti produkty s daným názvom.
+    ///
+    /// # Arguments
+    /// * `name` – celý názov; na veľkosti písmen (bez diakritiky) a okrajových medzerách nezáleží
+    ///
+    /// # Returns
+    /// Produkty zoradené podľa ID
+    #[instrument(skip_all, fields(db.operation.name = "get_products_by_name", db.rows = Empty))]
+    pub async fn get_products_by_name(&self, name: &str) -> Result<Vec<Product>, sqlx::Error> {
+        let rows = sqlx::query(&format!("{PRODUCT_SELECT} WHERE trim(name) = ? COLLATE NOCASE ORDER BY id"))
+            .bind(name.trim())
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Nájde jeden produkt podľa ID, čiarového kódu alebo názvu.
+    ///
+    /// # Arguments
+    /// * `lookup` – práve jeden z identifikátorov
+    ///
+    /// # Returns
+    /// Nájdený produkt
+    ///
+    /// # Errors
+    /// `StoreError::Invalid`, ak nie je zadaný práve jeden identifikátor,
+    /// `StoreError::NotFound`, ak produkt neexistuje, `StoreError::Conflict`,
+    /// ak čiarovému kódu alebo názvu zodpovedá viac produktov
+    pub async fn lookup_product(&self, lookup: &ProductLookup) -> Result<Product> {
+        let name = lookup.name.as_deref().map(str::trim).filter(|n| !n.is_empty());
+        let (mut products, key): (Vec<Product>, String) = match (lookup.id, lookup.barcode, name) {
+            (Some(id), None, None) => (self.get_product(id).await?.into_iter().collect(), format!("ID {id}")),
+            (None, Some(code), None) => (self.get_products_by_barcode(code).await?, format!("čiarovým kódom {code}")),
+            (None, None, Some(name)) => (self.get_products_by_name(name).await?, format!("názvom {name:?}")),
+            _ => {
+                return Err(StoreError::Invalid("zadajte práve jeden z parametrov id, barcode, name".into()).into())
+            }
+        };
+
+        match products.len() {
+            0 => Err(StoreError::NotFound(format!("produkt s {key}")).into()),
+            1 => Ok(products.remove(0)),
+            _ => {
+                let ids: Vec<String> = products.iter().filter_map(|p| p.id).map(|id| id.to_string()).collect();
+                Err(StoreError::Conflict(format!("produktov s {key} je viac: {}", ids.join(", "))).into())
+            }
+        }
+    }
+
+    /// Vráti produkty so zadanými ID jedným dotazom.
+    ///
+    /// # Arguments
+    /// * `ids` – ID produktov
+    ///
+    /// # Returns
+    /// Nájdené produkty v poradí `ids` (neexistujúce ID sa vynechajú)
+    #[instrument(skip_all, fields(db.operation.name = "get_products_by_ids", db.rows = Empty))]
+    pub async fn get_products_by_ids(&self, ids: &[u32]) -> Result<Vec<Product>, sqlx::Error> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sql = format!("{PRODUCT_SELECT} WHERE id IN ({})", vec!["?"; ids.len()].join(", "));
+        let mut query = sqlx::query(&sql);
+        for id in ids {
+            query = query.bind(id);
+        }
+        let rows = query.fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        let mut products: Vec<Product> = rows.iter().map(Self::product_from_row).collect();
+        products.sort_by_key(|p| ids.iter().position(|&id| Some(id) == p.id));
+        Ok(products)
+    }
+
+    /// Vráti náhodnú vzorku produktov (napr. na kontrolu fyzického stavu zásob).
+    ///
+    /// `ORDER BY RANDOM()` priradí náhodné číslo každému riadku tabuľky a zoradí
+    /// ich, cena dotazu preto rastie s počtom všetkých produktov, nie s veľkosťou
+    /// vzorky. Veľkosť vzorky je obmedzená na `MAX_SAMPLE_SIZE`.
+    ///
+    /// # Arguments
+    /// * `n` – požadovaný počet produktov
+    ///
+    /// # Returns
+    /// Najviac `n` (a najviac `MAX_SAMPLE_SIZE`) rôznych produktov v náhodnom poradí
+    #[instrument(skip_all, fields(db.operation.name = "random_products", db.rows = Empty))]
+    pub async fn random_products(&self, n: u32) -> Result<Vec<Product>, sqlx::Error> {
+        let rows = sqlx::query(&format!("{PRODUCT_SELECT} ORDER BY RANDOM() LIMIT ?"))
+            .bind(n.min(MAX_SAMPLE_SIZE))
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Vráti naposledy pridané produkty (napr. pre prehľad noviniek).
+    ///
+    /// Produkty bez `date_added` sa vynechajú; pri rovnakom dátume je novší
+    /// produkt s vyšším ID.
+    ///
+    /// # Arguments
+    /// * `limit` – počet produktov (najviac `MAX_RECENT_PRODUCTS`)
+    ///
+    /// # Returns
+    /// Produkty od najnovšieho
+    #[instrument(skip_all, fields(db.operation.name = "recently_added_products", db.rows = Empty))]
+    pub async fn recently_added_products(&self, limit: u32) -> Result<Vec<Product>, sqlx::Error> {
+        let sql = format!("{PRODUCT_SELECT} WHERE date_added IS NOT NULL ORDER BY date_added DESC, id DESC LIMIT ?");
+        let rows = sqlx::query(&sql).bind(limit.min(MAX_RECENT_PRODUCTS)).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Vráti jednu stránku produktov podľa filtra.
+    ///
+    /// # Arguments
+    /// * `product` – filter (polia `None` sa ignorujú)
+    /// * `translations` – názov a popis sa hľadajú aj v prekladoch
+    /// * `limit` – maximálny počet vrátených záznamov
+    /// * `offset` – počet preskočených záznamov
+    ///
+    /// # Returns
+    /// Zoznam produktov na danej stránke
+    #[instrument(skip_all, fields(db.operation.name = "get_products_page", db.rows = Empty))]
+    pub async fn get_products_page(
+        &self,
+        product: Product,
+        translations: bool,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Product>, sqlx::Error> {
+        let order = Self::page_order(product.updated_after);
+        let (mut query, mut args) = Self::product_filter(PRODUCT_SELECT, product, translations);
+        query.push_str(&format!(" ORDER BY {order} LIMIT ? OFFSET ?"));
+        let _ = args.add(limit);
+        let _ = args.add(offset);
+
+        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Vráti stránku produktov zmenených po danom čase, od naposledy zmeneného.
+    ///
+    /// Počet takých produktov vráti `count_products` s filtrom `updated_after`.
+    ///
+    /// # Arguments
+    /// * `since` – len produkty s `updated_at` neskorším ako tento čas
+    /// * `include_removed` – `Some(false)` vynechá vyradené produkty
+    /// * `limit` – maximálny počet vrátených záznamov
+    /// * `offset` – počet preskočených záznamov
+    ///
+    /// # Returns
+    /// Zoznam produktov na danej stránke
+    #[instrument(skip_all, fields(db.operation.name = "get_products_modified_since", db.rows = Empty))]
+    pub async fn get_products_modified_since(
+        &self,
+        since: NaiveDateTime,
+        include_removed: Option<bool>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Product>, sqlx::Error> {
+        let filter = Product { updated_after: Some(since), include_removed, ..Default::default() };
+        let (mut query, mut args) = Self::product_filter(PRODUCT_SELECT, filter, false);
+        query.push_str(" ORDER BY updated_at DESC, id DESC LIMIT ? OFFSET ?");
+        let _ = args.add(limit);
+        let _ = args.add(offset);
+
+        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Spočíta produkty vyhovujúce filtru.
+    ///
+    /// # Arguments
+    /// * `product` – filter (polia `None` sa ignorujú)
+    /// * `translations` – názov a popis sa hľadajú aj v prekladoch
+    ///
+    /// # Returns
+    /// Počet nájdených produktov
+    #[instrument(skip_all, fields(db.operation.name = "count_products", db.rows = Empty))]
+    pub async fn count_products(&self, product: Product, translations: bool) -> Result<u64, sqlx::Error> {
+        let (query, args) = Self::product_filter("SELECT COUNT(*) FROM Products", product, translations);
+        let count: i64 = sqlx::query_scalar_with(&query, args).fetch_one(&self.m_pool).await?;
+        Self::record_rows(count as usize);
+        Ok(count as u64)
+    }
+
+    /// Zostaví dotaz s podmienkami `WHERE` podľa filtra produktu.
+    ///
+    /// # Arguments
+    /// * `select` – začiatok dotazu (`SELECT ... FROM ...`)
+    /// * `product` – filter (polia `None` sa ignorujú)
+    /// * `translations` – názov a popis sa hľadajú aj v prekladoch
+    ///
+    /// # Returns
+    /// SQL dotaz a jeho argumenty
+    fn product_filter(select: &str, product: Product, translations: bool) -> (String, SqliteArguments<'static>) {
+        let mut query = format!("{select} WHERE 1=1");
+        let mut args = SqliteArguments::default();
+
+        // podmienka LIKE nad stĺpcom produktu, voliteľne aj nad prekladmi; `%` a `_` sa hľadajú doslovne
+        let mut push_like = |column: &str, value: String| {
+            let pattern = format!("%{}%", Self::escape_like(&value));
+            if translations {
+                query.push_str(&format!(
+                    " AND ({column} LIKE ? ESCAPE '\\' OR id IN \
+                     (SELECT product_id FROM product_translations WHERE {column} LIKE ? ESCAPE '\\'))"
+                ));
+                let _ = args.add(pattern.clone());
+            } else {
+                query.push_str(&format!(" AND {column} LIKE ? ESCAPE '\\'"));
+            }
+            let _ = args.add(pattern);
+        };
+        if let Some(name) = product.name {
+            if !name.is_empty() { push_like("name", name); }
+        }
+        if let Some(desc) = product.description {
+            if !desc.is_empty() { push_like("description", desc); }
+        }
+
+        if let Some(id) = product.id { query.push_str(" AND id = ?"); let _ = args.add(id); }
+        if let Some(category) = product.category {
+            if !category.is_empty() { query.push_str(" AND category = ?"); let _ = args.add(category); }
+        }
+        if let Some(quantity) = product.quantity { query.push_str(" AND quantity = ?"); let _ = args.add(quantity); }
+        if let Some(min) = product.quantity_min { query.push_str(" AND quantity >= ?"); let _ = args.add(min); }
+        if let Some(max) = product.quantity_max { query.push_str(" AND quantity <= ?"); let _ = args.add(max); }
+        if let Some(unit) = product.unit { query.push_str(" AND unit = ?"); let _ = args.add(unit.as_str()); }
+        if let Some(is_bundle) = product.is_bundle { query.push_str(" AND is_bundle = ?"); let _ = args.add(is_bundle); }
+        if let Some(status) = product.status { query.push_str(" AND status = ?"); let _ = args.add(status); }
+        if let Some(barcode) = product.bar_code { query.push_str(" AND bar_code = ?"); let _ = args.add(barcode); }
+        if let Some(sku) = product.sku {
+            if !sku.is_empty() {
+                query.push_str(" AND sku LIKE ? ESCAPE '\\'");
+                let _ = args.add(format!("{}%", Self::escape_like(&sku::normalize(&sku))));
+            }
+        }
+        if let Some(cost) = product.cost_price { query.push_str(" AND cost_price = ?"); let _ = args.add(cost); }
+        if let Some(price) = product.sell_price { query.push_str(" AND sell_price = ?"); let _ = args.add(price); }
+        if let Some(brand) = product.brand {
+            if !brand.is_empty() { query.push_str(" AND brand = ?"); let _ = args.add(brand); }
+        }
+        if let Some(supplier) = product.supplier {
+            if !supplier.is_empty() { query.push_str(" AND supplier = ?"); let _ = args.add(supplier); }
+        }
+        if let Some(emp_id) = product.employee_id { query.push_str(" AND employee_id = ?"); let _ = args.add(emp_id as i64); }
+        if let Some(date) = product.date_added { query.push_str(" AND date_added = ?"); let _ = args.add(date); }
+        if let Some(date) = product.date_remove { query.push_str(" AND date_remove = ?"); let _ = args.add(date); }
+        if product.include_removed == Some(false) {
+            query.push_str(" AND (date_remove IS NULL OR date_remove >= ?)");
+            let _ = args.add(Local::now().date_naive());
+        }
+        Self::push_updated_after(&mut query, &mut args, product.updated_after, product.after_id);
+
+        (query, args)
+    }
+
+    /// Prevedie riadok z tabuľky `products` na `Product`.
+    ///
+    /// Množstvo sady je počet sád zostaviteľných zo zložiek (stĺpec `buildable`),
+    /// voľné množstvo je stav zásob mínus platné rezervácie (stĺpec `reserved`).
+    fn product_from_row(row: &SqliteRow) -> Product {
+        let is_bundle = row.try_get::<Option<i64>, _>("is_bundle").ok().flatten() == Some(1);
+        let quantity = Self::quantity_of(row, if is_bundle { "buildable" } else { "quantity" });
+        let reserved = row.try_get::<Option<f64>, _>("reserved").ok().flatten().unwrap_or(0.0);
+        let mut product = Product {
+            id: row.get::<Option<i64>, _>("id").map(|v| v as u32),
+            name: row.get("name"),
+            category: row.get("category"),
+            quantity: Some(quantity),
+            unit: Some(Self::unit_of(row)),
+            pack_size: row.try_get::<Option<i64>, _>("pack_size").ok().flatten().map(|v| v as u32),
+            is_bundle: Some(is_bundle),
+            status: row.get::<Option<i64>, _>("status").map(|v| v != 0),
+            bar_code: row.get("bar_code"),
+            sku: row.try_get("sku").ok().flatten(),
+            min_stock: row.try_get("min_stock").ok().flatten(),
+            max_stock: row.try_get("max_stock").ok().flatten(),
+            lead_time_days: row.try_get::<Option<i64>, _>("lead_time_days").ok().flatten().map(|d| d as u32),
+            cost_price: row.get("cost_price"),
+            sell_price: row.get("sell_price"),
+            sale_price: row.try_get("sale_price").ok().flatten(),
+            sale_ends: row.try_get("sale_ends").ok().flatten(),
+            effective_price: None,
+            description: row.get("description"),
+            brand: row.get("brand"),
+            supplier: row.get("supplier"),
+            employee_id: row.get::<Option<i64>, _>("employee_id").map(|v| v as u32),
+            date_added: row.get("date_added"),
+            date_remove: row.get("date_remove"),
+            reserved_quantity: Some(reserved),
+            available_quantity: Some((quantity - reserved).max(0.0)),
+            quantity_min: None,
+            quantity_max: None,
+            updated_at: row.try_get("updated_at").ok().flatten(),
+            updated_after: None,
+            after_id: None,
+            include_removed: None,
+        };
+        product.effective_price = product.price_on(Local::now().date_naive());
+        product
+    }
+
+    // ==========================
+    // Bundles
+    // ==========================
+
+    /// Zistí, či je produkt sada (neexistujúci produkt nie je sada).
+    async fn is_bundle(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, product_id: u32) -> Result<bool> {
+        let flag: Option<i64> = sqlx::query_scalar("SELECT is_bundle FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        Ok(flag == Some(1))
+    }
+
+    /// Načíta zloženie sady ako dvojice (ID zložky, množstvo v jednej sade).
+    async fn components_of(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        bundle_id: u32,
+    ) -> Result<Vec<(u32, f64)>> {
+        let rows = sqlx::query(
+            "SELECT component_product_id, quantity FROM bundle_components WHERE bundle_id = ? ORDER BY component_product_id",
+        )
+            .bind(bundle_id)
+            .fetch_all(&mut **tx)
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|r| (r.get::<i64, _>("component_product_id") as u32, Self::quantity_of(r, "quantity")))
+            .collect())
+    }
+
+    /// Nastaví zloženie sady (nahradí pôvodné) a označí produkt ako sadu.
+    ///
+    /// Prázdne zloženie zruší sadu, produkt sa znovu správa ako bežný tovar
+    /// s nulovým stavom zásob.
+    ///
+    /// # Arguments
+    /// * `bundle_id` – ID produktu, ktorý je sadou
+    /// * `components` – zložky a ich množstvá v jednej sade (v jednotke zložky)
+    ///
+    /// # Returns
+    /// `false` ak produkt neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` pri neznámej zložke, `StoreError::Invalid` ak je zložkou
+    /// sada sama alebo iná sada, množstvo nezodpovedá jednotke zložky alebo sada nie je
+    /// v kusoch, `StoreError::Conflict` ak má produkt vlastné zásoby alebo je sám zložkou
+    #[instrument(skip_all, fields(db.operation.name = "set_bundle_components", db.rows = Empty))]
+    pub async fn set_bundle_components(&self, bundle_id: u32, components: &[BundleComponent]) -> Result<bool> {
+        let mut tx = self.m_pool.begin().await?;
+        let bundle = match sqlx::query("SELECT quantity, unit, is_bundle FROM products WHERE id = ?")
+            .bind(bundle_id)
+            .fetch_optional(&mut *tx)
+            .await?
+        {
+            Some(row) => row,
+            None => return Ok(false),
+        };
+
+        if !components.is_empty() {
+            if Self::unit_of(&bundle) != Unit::Piece {
+                return Err(StoreError::Invalid("sada sa predáva len po kusoch".into()).into());
+            }
+            if bundle.get::<i64, _>("is_bundle") == 0 && Self::quantity_of(&bundle, "quantity") > QUANTITY_EPSILON {
+                return Err(StoreError::Conflict(format!("produkt {bundle_id} má vlastné zásoby, nemôže byť sadou")).into());
+            }
+            let used_in: Option<i64> =
+                sqlx::query_scalar("SELECT bundle_id FROM bundle_components WHERE component_product_id = ? LIMIT 1")
+                    .bind(bundle_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if let Some(other) = used_in {
+                return Err(StoreError::Conflict(format!("produkt {bundle_id} je zložkou sady {other}")).into());
+            }
+        }
+
+        sqlx::query("DELETE FROM bundle_components WHERE bundle_id = ?")
+            .bind(bundle_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for component in components {
+            let id = component.component_product_id;
+            if id == bundle_id {
+                return Err(StoreError::Invalid("sada nemôže obsahovať samu seba".into()).into());
+            }
+            let row = sqlx::query("SELECT unit, is_bundle FROM products WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| StoreError::NotFound(format!("produkt {id}")))?;
+            if row.get::<i64, _>("is_bundle") == 1 {
+                return Err(StoreError::Invalid(format!("produkt {id} je sada, sady nemožno vnárať")).into());
+            }
+            let unit = Self::unit_of(&row);
+            if component.quantity <= 0.0 || !unit.accepts(component.quantity) {
+                return Err(StoreError::Invalid(format!(
+                    "produkt {id}: množstvo {} nie je prípustné pre jednotku {}",
+                    component.quantity,
+                    unit.as_str()
+                )).into());
+            }
+
+            sqlx::query(
+                r#"
+                INSERT INTO bundle_components (bundle_id, component_product_id, quantity) VALUES (?, ?, ?)
+                ON CONFLICT (bundle_id, component_product_id) DO UPDATE SET quantity = quantity + excluded.quantity
+                "#,
+            )
+                .bind(bundle_id)
+                .bind(id)
+                .bind(component.quantity)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query("UPDATE products SET is_bundle = ?, quantity = 0 WHERE id = ?")
+            .bind(!components.is_empty())
+            .bind(bundle_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(true)
+    }
+
+    /// Vráti zloženie sady a počet sád zostaviteľných z aktuálnych zásob.
+    ///
+    /// # Arguments
+    /// * `bundle_id` – ID sady
+    ///
+    /// # Returns
+    /// Sada alebo `None`, ak produkt neexistuje alebo nie je sada
+    #[instrument(skip_all, fields(db.operation.name = "get_bundle", db.rows = Empty))]
+    pub async fn get_bundle(&self, bundle_id: u32) -> Result<Option<Bundle>> {
+        Ok(self.bundles(Some(bundle_id)).await?.pop())
+    }
+
+    /// Vráti všetky sady so zložením (napr. pre export).
+    #[instrument(skip_all, fields(db.operation.name = "get_bundles", db.rows = Empty))]
+    pub async fn get_bundles(&self) -> Result<Vec<Bundle>> {
+        self.bundles(None).await
+    }
+
+    /// Načíta sady so zložením, voliteľne len jednu.
+    async fn bundles(&self, bundle_id: Option<u32>) -> Result<Vec<Bundle>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT b.id AS bundle_id, c.component_product_id, c.quantity, p.name, p.quantity AS available
+            FROM products b
+            LEFT JOIN bundle_components c ON c.bundle_id = b.id
+            LEFT JOIN products p ON p.id = c.component_product_id
+            WHERE b.is_bundle = 1 AND (? IS NULL OR b.id = ?)
+            ORDER BY b.id, c.component_product_id
+            "#,
+        )
+            .bind(bundle_id)
+            .bind(bundle_id)
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let mut bundles: Vec<Bundle> = Vec::new();
+        for row in &rows {
+            let id = row.get::<i64, _>("bundle_id") as u32;
+            if bundles.last().is_none_or(|b| b.bundle_id != id) {
+                bundles.push(Bundle { bundle_id: id, buildable: 0, components: Vec::new() });
+            }
+            let bundle = bundles.last_mut().expect("práve pridaná sada");
+            if let Some(component) = row.get::<Option<i64>, _>("component_product_id") {
+                bundle.components.push(BundleComponent {
+                    component_product_id: component as u32,
+                    quantity: Self::quantity_of(row, "quantity"),
+                    name: row.get("name"),
+                    available: Some(Self::quantity_of(row, "available")),
+                });
+            }
+        }
+
+        for bundle in &mut bundles {
+            bundle.buildable = bundle
+                .components
+                .iter()
+                .map(|c| (c.available.unwrap_or(0.0) / c.quantity + QUANTITY_EPSILON).floor().max(0.0) as u32)
+                .min()
+                .unwrap_or(0);
+        }
+        Ok(bundles)
+    }
+
+    // ==========================
+    // Translations
+    // ==========================
+
+    /// Skontroluje a znormalizuje jazykový kód (`hu`, `sk-SK` → `sk-sk`).
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri kóde, ktorý nemá tvar jazykovej značky
+    fn normalize_lang(lang: &str) -> Result<String> {
+        let lang = lang.trim().to_ascii_lowercase();
+        let mut parts = lang.split('-');
+        let primary = parts.next().unwrap_or_default();
+        let valid = (2..=3).contains(&primary.len())
+            && primary.chars().all(|c| c.is_ascii_alphabetic())
+            && parts.all(|p| (1..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()));
+        if !valid {
+            return Err(StoreError::Invalid(format!("neplatný kód jazyka {lang:?}")).into());
+        }
+        Ok(lang)
+    }
+
+    /// Uloží preklad produktu do jazyka (existujúci preklad prepíše).
+    ///
+    /// # Arguments
+    /// * `translation` – ID produktu, jazyk a preložený názov a popis
+    ///
+    /// # Returns
+    /// `true` ak sa preklad uložil, `false` ak produkt neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri neplatnom kóde jazyka alebo preklade bez názvu aj popisu
+    #[instrument(skip_all, fields(db.operation.name = "set_product_translation", db.rows = Empty))]
+    pub async fn set_product_translation(&self, translation: &ProductTranslation) -> Result<bool> {
+        let lang = Self::normalize_lang(&translation.lang)?;
+        let name = translation.name.as_deref().map(str::trim).filter(|v| !v.is_empty());
+        let description = translation.description.as_deref().map(str::trim).filter(|v| !v.is_empty());
+        if name.is_none() && description.is_none() {
+            return Err(StoreError::Invalid("preklad musí obsahovať názov alebo popis".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM products WHERE id = ?")
+            .bind(translation.product_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO product_translations (product_id, lang, name, description)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT (product_id, lang) DO UPDATE SET name = excluded.name, description = excluded.description
+            "#,
+        )
+            .bind(translation.product_id)
+            .bind(&lang)
+            .bind(name)
+            .bind(description)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(true)
+    }
+
+    /// Vráti preklady produktu, prípadne všetkých produktov (napr. pre export).
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu alebo `None` pre všetky produkty
+    ///
+    /// # Returns
+    /// Preklady zoradené podľa produktu a jazyka
+    #[instrument(skip_all, fields(db.operation.name = "get_product_translations", db.rows = Empty))]
+    pub async fn get_product_translations(&self, product_id: Option<u32>) -> Result<Vec<ProductTranslation>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT product_id, lang, name, description FROM product_translations
+            WHERE ? IS NULL OR product_id = ?
+            ORDER BY product_id, lang
+            "#,
+        )
+            .bind(product_id)
+            .bind(product_id)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows
+            .iter()
+            .map(|row| ProductTranslation {
+                product_id: row.get::<i64, _>("product_id") as u32,
+                lang: row.get("lang"),
+                name: row.get("name"),
+                description: row.get("description"),
+            })
+            .collect())
+    }
+
+    /// Pridá produktu obrázok.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `image` – adresa, poradie a príznak hlavného obrázka
+    ///
+    /// # Returns
+    /// `true` ak sa obrázok pridal, `false` ak produkt neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri neplatnej adrese
+    #[instrument(skip_all, fields(db.operation.name = "add_product_image", db.rows = Empty))]
+    pub async fn add_product_image(&self, product_id: u32, image: &ProductImage) -> Result<bool> {
+        self.add_product_images(product_id, std::slice::from_ref(image)).await
+    }
+
+    /// Pridá produktu viac obrázkov naraz (všetky alebo žiadny).
+    ///
+    /// Obrázky bez `sort_order` sa zaradia za existujúce v poradí zo vstupu.
+    /// Obrázok s `is_primary` nahradí doterajší hlavný obrázok; ak produkt
+    /// hlavný obrázok nemá, stane sa ním prvý podľa poradia.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `images` – pridávané obrázky
+    ///
+    /// # Returns
+    /// `true` ak sa obrázky pridali, `false` ak produkt neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom zozname, neplatnej adrese alebo
+    /// viacerých hlavných obrázkoch
+    #[instrument(skip_all, fields(db.operation.name = "add_product_images", db.rows = Empty))]
+    pub async fn add_product_images(&self, product_id: u32, images: &[ProductImage]) -> Result<bool> {
+        if images.is_empty() {
+            return Err(StoreError::Invalid("chýbajú obrázky".into()).into());
+        }
+        if images.iter().filter(|i| i.is_primary).count() > 1 {
+            return Err(StoreError::Invalid("hlavný môže byť len jeden obrázok".into()).into());
+        }
+        for image in images {
+            let url = image.url.trim();
+            if !(url.starts_with("http://") || url.starts_with("https://") || url.starts_with('/')) {
+                return Err(StoreError::Invalid(format!("neplatná adresa obrázka {:?}", image.url)).into());
+            }
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Ok(false);
+        }
+
+        if images.iter().any(|i| i.is_primary) {
+            sqlx::query("UPDATE product_images SET is_primary = 0 WHERE product_id = ?")
+                .bind(product_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        let mut next: i64 =
+            sqlx::query_scalar("SELECT COALESCE(MAX(sort_order) + 1, 0) FROM product_images WHERE product_id = ?")
+                .bind(product_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        for image in images {
+            let sort_order = image.sort_order.map_or(next, i64::from);
+            next = next.max(sort_order + 1);
+            sqlx::query("INSERT INTO product_images (product_id, url, is_primary, sort_order) VALUES (?, ?, ?, ?)")
+                .bind(product_id)
+                .bind(image.url.trim())
+                .bind(image.is_primary)
+                .bind(sort_order)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE product_images SET is_primary = 1
+            WHERE id = (SELECT id FROM product_images WHERE product_id = ? ORDER BY sort_order, id LIMIT 1)
+              AND NOT EXISTS (SELECT 1 FROM product_images WHERE product_id = ? AND is_primary = 1)
+            "#,
+        )
+            .bind(product_id)
+            .bind(product_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Self::record_rows(images.len());
+        Ok(true)
+    }
+
+    /// Vráti obrázky produktu.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    ///
+    /// # Returns
+    /// Obrázky zoradené podľa `sort_order` (pri zhode podľa poradia pridania)
+    #[instrument(skip_all, fields(db.operation.name = "list_product_images", db.rows = Empty))]
+    pub async fn list_product_images(&self, product_id: u32) -> Result<Vec<ProductImage>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, url, is_primary, sort_order FROM product_images WHERE product_id = ? ORDER BY sort_order, id",
+        )
+            .bind(product_id)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows
+            .iter()
+            .map(|row| ProductImage {
+                id: Some(row.get::<i64, _>("id") as u32),
+                url: row.get("url"),
+                is_primary: row.get("is_primary"),
+                sort_order: Some(row.get::<i64, _>("sort_order") as u32),
+            })
+            .collect())
+    }
+
+    /// Nastaví hlavný obrázok produktu (doterajší hlavný prestane byť hlavný).
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `image_id` – ID obrázka
+    ///
+    /// # Returns
+    /// `true` ak sa zmena uložila, `false` ak produkt taký obrázok nemá
+    #[instrument(skip_all, fields(db.operation.name = "set_primary_image", db.rows = Empty))]
+    pub async fn set_primary_image(&self, product_id: u32, image_id: u32) -> Result<bool> {
+        let mut tx = self.m_pool.begin().await?;
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM product_images WHERE id = ? AND product_id = ?")
+            .bind(image_id)
+            .bind(product_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query("UPDATE product_images SET is_primary = 0 WHERE product_id = ?")
+            .bind(product_id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("UPDATE product_images SET is_primary = 1 WHERE id = ?")
+            .bind(image_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Self::record_rows(result.rows_affected() as usize);
+        Ok(true)
+    }
+
+    /// Nahradí názov a popis produktov prekladom v prvom dostupnom jazyku.
+    ///
+    /// Pole, ktoré v preklade chýba, zostane v základnom znení.
+    ///
+    /// # Arguments
+    /// * `products` – produkty na úpravu
+    /// * `langs` – jazyky v poradí podľa preferencie
+    #[instrument(skip_all, fields(db.operation.name = "localize_products", db.rows = Empty))]
+    pub async fn localize_products(&self, products: &mut [Product], langs: &[String]) -> Result<(), sqlx::Error> {
+        let ids: Vec<u32> = products.iter().filter_map(|p| p.id).collect();
+        if ids.is_empty() || langs.is_empty() {
+            return Ok(());
+        }
+
+        let query = format!(
+            "SELECT product_id, lang, name, description FROM product_translations WHERE lang IN ({}) AND product_id IN ({})",
+            vec!["?"; langs.len()].join(", "),
+            vec!["?"; ids.len()].join(", "),
+        );
+        let mut args = SqliteArguments::default();
+        for lang in langs {
+            let _ = args.add(lang.clone());
+        }
+        for id in &ids {
+            let _ = args.add(*id);
+        }
+        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+
+        for product in products.iter_mut() {
+            let best = rows
+                .iter()
+                .filter(|row| Some(row.get::<i64, _>("product_id") as u32) == product.id)
+                .min_by_key(|row| langs.iter().position(|l| *l == row.get::<String, _>("lang")));
+            if let Some(row) = best {
+                if let Some(name) = row.get::<Option<String>, _>("name") {
+                    product.name = Some(name);
+                }
+                if let Some(description) = row.get::<Option<String>, _>("description") {
+                    product.description = Some(description);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // ==========================
+    // Orders
+    // ==========================
+
+    /// Zaeviduje predajnú objednávku a odpíše predané kusy zo skladu.
+    ///
+    /// Ak položka nemá `unit_price`, použije sa predajná cena produktu v deň
+    /// objednávky (počas akcie akciová). K položke sa uloží aj bežná predajná
+    /// cena, z ktorej sa počíta poskytnutá zľava (`discount_given`).
+    /// Pri predaji sady sa odpíšu jej zložky (všetky alebo žiadna). Predať sa dá
+    /// len tovar mimo platných rezervácií; položka s `reservation_id` rezerváciu
+    /// vyzdvihne a jej množstvo sa uvoľní pre tento predaj.
+    ///
+    /// # Arguments
+    /// * `order` – objednávka s položkami
+    /// * `allow_closed` – povolí zápis do dňa uzavretého Z-reportom
+    ///
+    /// # Returns
+    /// ID novej objednávky
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnej objednávke, `StoreError::NotFound` pri neznámom
+    /// produkte alebo rezervácii, `StoreError::Conflict` ak na sklade nie je dosť voľných
+    /// kusov (aj zložky sady), rezervácia už nie je platná alebo je deň uzavretý
+    #[instrument(skip_all, fields(db.operation.name = "add_order", db.rows = Empty))]
+    pub async fn add_order(&self, order: &Order, allow_closed: bool) -> Result<u32> {
+        if order.items.is_empty() || order.items.iter().any(|i| i.quantity.is_nan() || i.quantity <= 0.0) {
+            return Err(StoreError::Invalid("objednávka musí mať položky s kladným množstvom".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        let created_at = order.created_at.unwrap_or_else(|| Local::now().naive_local());
+
+        if !allow_closed {
+            let closed = sqlx::query("SELECT date FROM closed_days WHERE date = ?")
+                .bind(created_at.date())
+                .fetch_optional(&mut *tx)
+                .await?
+                .is_some();
+            if closed {
+                return Err(StoreError::Conflict(format!("deň {} je uzavretý", created_at.date())).into());
+            }
+        }
+
+        let order_id = sqlx::query("INSERT INTO orders (employee_id, status, created_at) VALUES (?, ?, ?)")
+            .bind(order.employee_id)
+            .bind(order.status.clone().unwrap_or_else(|| "open".into()))
+            .bind(created_at)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+
+        for item in &order.items {
+            let quantity = Self::to_product_unit(&mut tx, item.product_id, item.quantity, item.unit).await?;
+            let row = sqlx::query("SELECT sell_price, sale_price, sale_ends, is_bundle FROM products WHERE id = ?")
+                .bind(item.product_id)
+                .fetch_one(&mut *tx)
+                .await?;
+            let regular_price: f64 = row.get("sell_price");
+            let pricing = Product {
+                sell_price: Some(regular_price),
+                sale_price: row.get("sale_price"),
+                sale_ends: row.get("sale_ends"),
+                ..Product::default()
+            };
+            let unit_price = item.unit_price.or(pricing.price_on(created_at.date())).unwrap_or(regular_price);
+            if let Some(reservation_id) = item.reservation_id {
+                Self::consume_reservation(&mut tx, reservation_id, item.product_id, order_id).await?;
+            }
+
+            let taken = if row.get::<i64, _>("is_bundle") == 1 {
+                Self::components_of(&mut tx, item.product_id)
+                    .await?
+                    .into_iter()
+                    .map(|(component, per_bundle)| (component, per_bundle * quantity))
+                    .collect()
+            } else {
+                vec![(item.product_id, quantity)]
+            };
+            for (product_id, quantity) in taken {
+                Self::check_available(&mut tx, product_id, quantity).await?;
+                Self::take_stock(&mut tx, product_id, quantity).await?;
+                let reference = Some(format!("ORD-{order_id}"));
+                Self::insert_movement_by(&mut tx, product_id, -quantity, "sale", reference, order.employee_id, created_at)
+                    .await?;
+            }
+
+            sqlx::query(
+                "INSERT INTO order_items (order_id, product_id, quantity, unit_price, regular_price) VALUES (?, ?, ?, ?, ?)",
+            )
+                .bind(order_id)
+                .bind(item.product_id)
+                .bind(quantity)
+                .bind(unit_price)
+                .bind(regular_price)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Orders, Entity::Products]);
+        Ok(order_id as u32)
+    }
+
+    /// Odpíše predané množstvo zo stavu zásob a zo šarží produktu.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – odpísané množstvo (v jednotke produktu)
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak na sklade nie je dosť tovaru
+    async fn take_stock(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+    ) -> Result<()> {
+        let row = sqlx::query("SELECT quantity, unit FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_one(&mut **tx)
+            .await?;
+
+        let in_stock = Self::quantity_of(&row, "quantity");
+        if in_stock + QUANTITY_EPSILON < quantity {
+            return Err(StoreError::Conflict(format!(
+                "produkt {} má na sklade len {} {}",
+                product_id,
+                in_stock,
+                Self::unit_of(&row).as_str()
+            )).into());
+        }
+
+        sqlx::query("UPDATE products SET quantity = quantity - ? WHERE id = ?")
+            .bind(quantity)
+            .bind(product_id)
+            .execute(&mut **tx)
+            .await?;
+
+        Self::take_from_batches(tx, product_id, quantity).await
+    }
+
+    /// Vráti objednávku s položkami a všetkými vráteniami.
+    ///
+    /// # Arguments
+    /// * `id` – ID objednávky
+    ///
+    /// # Returns
+    /// Objednávka alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_order", db.rows = Empty))]
+    pub async fn get_order(&self, id: u32) -> Result<Option<Order>> {
+        let row = match sqlx::query("SELECT * FROM orders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.m_pool)
+            .await?
+        {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let items = sqlx::query(
+            r#"
+            SELECT i.product_id, i.quantity, i.unit_price, p.unit
+            FROM order_items i
+            LEFT JOIN products p ON p.id = i.product_id
+            WHERE i.order_id = ?
+            ORDER BY i.id
+            "#,
+        )
+            .bind(id)
+            .fetch_all(&self.m_pool)
+            .await?
+            .iter()
+            .map(|r| OrderItem {
+                product_id: r.get::<i64, _>("product_id") as u32,
+                quantity: Self::quantity_of(r, "quantity"),
+                unit: Some(Self::unit_of(r)),
+                unit_price: r.get("unit_price"),
+                reservation_id: None,
+            })
+            .collect();
+
+        let mut returns = Vec::new();
+        for r in sqlx::query("SELECT * FROM order_returns WHERE order_id = ? ORDER BY id")
+            .bind(id)
+            .fetch_all(&self.m_pool)
+            .await?
+        {
+            let return_id: i64 = r.get("id");
+            let items = sqlx::query("SELECT product_id, quantity, unit_price FROM return_items WHERE return_id = ? ORDER BY id")
+                .bind(return_id)
+                .fetch_all(&self.m_pool)
+                .await?
+                .iter()
+                .map(|i| ReturnItem {
+                    product_id: i.get::<i64, _>("product_id") as u32,
+                    quantity: Self::quantity_of(i, "quantity"),
+                    unit: None,
+                    unit_price: i.get("unit_price"),
+                })
+                .collect();
+
+            returns.push(OrderReturn {
+                id: Some(return_id as u32),
+                order_id: Some(id),
+                employee_id: r.get::<Option<i64>, _>("employee_id").map(|v| v as u32),
+                note: r.get("note"),
+                created_at: r.get("created_at"),
+                items,
+            });
+        }
+
+        Ok(Some(Order {
+            id: Some(id),
+            employee_id: row.get::<Option<i64>, _>("employee_id").map(|v| v as u32),
+            status: row.get("status"),
+            created_at: row.get("created_at"),
+            items,
+            returns,
+            updated_at: row.try_get("updated_at").ok().flatten(),
+        }))
+    }
+
+    /// Vráti jednu stránku objednávok s položkami a vráteniami.
+    ///
+    /// # Arguments
+    /// * `filter` – filter podľa času zmeny (`updated_after`, `after_id`)
+    /// * `limit` – maximálny počet vrátených objednávok
+    /// * `offset` – počet preskočených objednávok
+    ///
+    /// # Returns
+    /// Objednávky na danej stránke
+    #[instrument(skip_all, fields(db.operation.name = "get_orders_page", db.rows = Empty))]
+    pub async fn get_orders_page(&self, filter: OrderFilter, limit: u32, offset: u32) -> Result<Vec<Order>> {
+        let (mut query, mut args) = Self::order_filter("SELECT id FROM orders", filter);
+        query.push_str(&format!(" ORDER BY {} LIMIT ? OFFSET ?", Self::page_order(filter.updated_after)));
+        let _ = args.add(limit);
+        let _ = args.add(offset);
+
+        let ids: Vec<i64> = sqlx::query_scalar_with(&query, args).fetch_all(&self.m_pool).await?;
+        let mut orders = Vec::with_capacity(ids.len());
+        for id in ids {
+            orders.extend(self.get_order(id as u32).await?);
+        }
+        Self::record_rows(orders.len());
+        Ok(orders)
+    }
+
+    /// Spočíta objednávky vyhovujúce filtru.
+    ///
+    /// # Arguments
+    /// * `filter` – filter podľa času zmeny
+    ///
+    /// # Returns
+    /// Počet objednávok
+    #[instrument(skip_all, fields(db.operation.name = "count_orders", db.rows = Empty))]
+    pub async fn count_orders(&self, filter: OrderFilter) -> Result<u64> {
+        let (query, args) = Self::order_filter("SELECT COUNT(*) FROM orders", filter);
+        let count: i64 = sqlx::query_scalar_with(&query, args).fetch_one(&self.m_pool).await?;
+        Self::record_rows(count as usize);
+        Ok(count as u64)
+    }
+
+    /// Zostaví dotaz s podmienkami `WHERE` podľa filtra objednávok.
+    fn order_filter(select: &str, filter: OrderFilter) -> (String, SqliteArguments<'static>) {
+        let mut query = format!("{select} WHERE 1=1");
+        let mut args = SqliteArguments::default();
+        Self::push_updated_after(&mut query, &mut args, filter.updated_after, filter.after_id);
+        (query, args)
+    }
+
+    /// Zaeviduje vrátenie tovaru k objednávke a vráti tovar na sklad.
+    ///
+    /// Vracať je možné aj po častiach viacerými dokladmi a aj pri uzavretej
+    /// alebo archivovanej objednávke. Cena vrátenej položky sa berie z objednávky.
+    /// Množstvo v inej jednotke (`unit`) sa prepočíta na jednotku produktu ako pri predaji.
+    /// Vrátená sada vráti na sklad svoje (aktuálne) zložky.
+    ///
+    /// # Arguments
+    /// * `order_id` – ID objednávky
+    /// * `ret` – doklad o vrátení s položkami
+    ///
+    /// # Returns
+    /// ID nového dokladu o vrátení
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak objednávka alebo produkt neexistuje, `StoreError::Invalid` ak
+    /// sa vracia viac, než bolo predaných (po odpočítaní predošlých vrátení), alebo
+    /// množstvo nie je prípustné pre jednotku produktu (napr. pol kusa)
+    #[instrument(skip_all, fields(db.operation.name = "add_order_return", db.rows = Empty))]
+    pub async fn add_order_return(&self, order_id: u32, ret: &OrderReturn) -> Result<u32> {
+        if ret.items.is_empty() || ret.items.iter().any(|i| i.quantity.is_nan() || i.quantity <= 0.0) {
+            return Err(StoreError::Invalid("vrátenie musí mať položky s kladným množstvom".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+
+        let exists = sqlx::query("SELECT id FROM orders WHERE id = ?")
+            .bind(order_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .is_some();
+        if !exists {
+            return Err(StoreError::NotFound(format!("objednávka {order_id}")).into());
+        }
+
+        let created_at = ret.created_at.unwrap_or_else(|| Local::now().naive_local());
+        let return_id = sqlx::query("INSERT INTO order_returns (order_id, employee_id, note, created_at) VALUES (?, ?, ?, ?)")
+            .bind(order_id)
+            .bind(ret.employee_id)
+            .bind(ret.note.clone())
+            .bind(created_at)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+
+        for item in &ret.items {
+            let quantity = Self::to_product_unit(&mut tx, item.product_id, item.quantity, item.unit).await?;
+            let row = sqlx::query(
+                r#"
+                SELECT
+                    COALESCE(SUM(quantity), 0) AS sold,
+                    COALESCE(SUM(quantity * unit_price) / NULLIF(SUM(quantity), 0), 0.0) AS unit_price,
+                    (
+                        SELECT COALESCE(SUM(ri.quantity), 0)
+                        FROM return_items ri
+                        JOIN order_returns r ON r.id = ri.return_id
+                        WHERE r.order_id = ? AND ri.product_id = ?
+                    ) AS returned
+                FROM order_items
+                WHERE order_id = ? AND product_id = ?
+                "#,
+            )
+                .bind(order_id)
+                .bind(item.product_id)
+                .bind(order_id)
+                .bind(item.product_id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+            let sold = Self::quantity_of(&row, "sold");
+            let returned = Self::quantity_of(&row, "returned");
+            if quantity > sold - returned + QUANTITY_EPSILON {
+                return Err(StoreError::Invalid(format!(
+                    "produkt {}: predané {}, už vrátené {}, požadované {}",
+                    item.product_id, sold, returned, quantity
+                )).into());
+            }
+
+            sqlx::query("INSERT INTO return_items (return_id, product_id, quantity, unit_price) VALUES (?, ?, ?, ?)")
+                .bind(return_id)
+                .bind(item.product_id)
+                .bind(quantity)
+                .bind(row.get::<f64, _>("unit_price"))
+                .execute(&mut *tx)
+                .await?;
+
+            let restock = if Self::is_bundle(&mut tx, item.product_id).await? {
+                Self::components_of(&mut tx, item.product_id)
+                    .await?
+                    .into_iter()
+                    .map(|(component, per_bundle)| (component, per_bundle * quantity))
+                    .collect()
+            } else {
+                vec![(item.product_id, quantity)]
+            };
+
+            for (product_id, quantity) in restock {
+                sqlx::query("UPDATE products SET quantity = quantity + ? WHERE id = ?")
+                    .bind(quantity)
+                    .bind(product_id)
+                    .execute(&mut *tx)
+                    .await?;
+
+                Self::add_to_batches(&mut tx, product_id, quantity, None, None).await?;
+                let reference = Some(format!("RET-{return_id}"));
+                Self::insert_movement_by(&mut tx, product_id, quantity, "return", reference, ret.employee_id, created_at)
+                    .await?;
+            }
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Orders, Entity::Products]);
+        Ok(return_id as u32)
+    }
+
+    // ==========================
+    // Reservations
+    // ==========================
+
+    /// Vráti stav zásob, množstvo v platných rezerváciách a jednotku produktu.
+    async fn reserved_stock(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+    ) -> Result<(f64, f64, Unit)> {
+        let select = format!(
+            "SELECT p.quantity, p.unit, (SELECT COALESCE(SUM(quantity), 0.0) FROM reservations \
+             WHERE product_id = p.id AND {ACTIVE_RESERVATION}) AS reserved FROM products p WHERE p.id = ?"
+        );
+        let row = sqlx::query(&select)
+            .bind(product_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .ok_or_else(|| StoreError::NotFound(format!("produkt {product_id}")))?;
+        Ok((Self::quantity_of(&row, "quantity"), row.get("reserved"), Self::unit_of(&row)))
+    }
+
+    /// Overí, že sa dá predať množstvo, ktoré nie je blokované platnými rezerváciami.
+    ///
+    /// Nedostatok tovaru bez rezervácií hlási až `take_stock`.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia
+    /// * `product_id` – ID produktu
+    /// * `quantity` – predávané množstvo (v jednotke produktu)
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak voľného tovaru nie je dosť
+    async fn check_available(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        product_id: u32,
+        quantity: f64,
+    ) -> Result<()> {
+        let (in_stock, reserved, unit) = Self::reserved_stock(tx, product_id).await?;
+        let available = in_stock - reserved;
+        if reserved > 0.0 && available + QUANTITY_EPSILON < quantity {
+            return Err(StoreError::Conflict(format!(
+                "produkt {} má voľných len {} {} ({} je rezervovaných)",
+                product_id,
+                available.max(0.0),
+                unit.as_str(),
+                reserved
+            )).into());
+        }
+        Ok(())
+    }
+
+    /// Označí rezerváciu za vyzdvihnutú objednávkou.
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak rezervácia neexistuje, `StoreError::Invalid` ak patrí
+    /// inému produktu, `StoreError::Conflict` ak už nie je platná
+    async fn consume_reservation(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        reservation_id: u32,
+        product_id: u32,
+        order_id: i64,
+    ) -> Result<()> {
+        let reservation = Self::reservation_in(&mut **tx, reservation_id).await?;
+        if reservation.product_id != product_id {
+            return Err(StoreError::Invalid(format!(
+                "rezervácia {reservation_id} je na produkt {}, nie {product_id}",
+                reservation.product_id
+            )).into());
+        }
+        if reservation.status != ReservationStatus::Active {
+            return Err(StoreError::Conflict(format!(
+                "rezervácia {reservation_id} nie je platná ({})",
+                reservation.status.as_str()
+            )).into());
+        }
+
+        sqlx::query("UPDATE reservations SET status = 'fulfilled', order_id = ? WHERE id = ?")
+            .bind(order_id)
+            .bind(reservation_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Odloží tovar pre zákazníka do zadaného času.
+    ///
+    /// Rezervovať sa dá len voľný tovar (stav zásob mínus platné rezervácie);
+    /// po `expires_at` sa rezervácia automaticky uvoľní.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `request` – množstvo, zákazník a platnosť rezervácie
+    ///
+    /// # Returns
+    /// Vytvorená rezervácia
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje, `StoreError::Invalid` pri nekladnom
+    /// množstve, prázdnom zákazníkovi, platnosti v minulosti alebo sade,
+    /// `StoreError::Conflict` ak nie je dosť voľného tovaru
+    #[instrument(skip_all, fields(db.operation.name = "add_reservation", db.rows = Empty))]
+    pub async fn add_reservation(&self, product_id: u32, request: &ReservationRequest) -> Result<Reservation> {
+        let customer = request.customer.trim();
+        if customer.is_empty() {
+            return Err(StoreError::Invalid("rezervácia musí mať zákazníka".into()).into());
+        }
+        let now = Local::now().naive_local();
+        if request.expires_at <= now {
+            return Err(StoreError::Invalid("platnosť rezervácie musí byť v budúcnosti".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        if Self::is_bundle(&mut tx, product_id).await? {
+            return Err(StoreError::Invalid(format!("sada {product_id} sa nedá rezervovať, rezervujte zložky")).into());
+        }
+        let quantity = Self::to_product_unit(&mut tx, product_id, request.quantity, request.unit).await?;
+        if quantity.is_nan() || quantity <= 0.0 {
+            return Err(StoreError::Invalid("rezervované množstvo musí byť kladné".into()).into());
+        }
+
+        let (in_stock, reserved, unit) = Self::reserved_stock(&mut tx, product_id).await?;
+        let available = in_stock - reserved;
+        if available + QUANTITY_EPSILON < quantity {
+            return Err(StoreError::Conflict(format!(
+                "produkt {} má voľných len {} {}",
+                product_id,
+                available.max(0.0),
+                unit.as_str()
+            )).into());
+        }
+
+        let id = sqlx::query(
+            "INSERT INTO reservations (product_id, quantity, customer, created_at, expires_at) VALUES (?, ?, ?, ?, ?)",
+        )
+            .bind(product_id)
+            .bind(quantity)
+            .bind(customer)
+            .bind(now)
+            .bind(request.expires_at)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+        let reservation = Self::reservation_in(&mut *tx, id as u32).await?;
+        tx.commit().await?;
+
+        Self::record_rows(1);
+        self.changed(&[Entity::Products]);
+        Ok(reservation)
+    }
+
+    /// Vráti rezervácie od najnovšej.
+    ///
+    /// # Arguments
+    /// * `product_id` – len rezervácie produktu
+    /// * `active` – `Some(true)` len platné, `Some(false)` len ukončené (aj expirované)
+    ///
+    /// # Returns
+    /// Zoznam rezervácií
+    #[instrument(skip_all, fields(db.operation.name = "get_reservations", db.rows = Empty))]
+    pub async fn get_reservations(&self, product_id: Option<u32>, active: Option<bool>) -> Result<Vec<Reservation>> {
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT *, {RESERVATION_STATUS} AS effective_status FROM reservations
+            WHERE (?1 IS NULL OR product_id = ?1)
+              AND (?2 IS NULL OR ({ACTIVE_RESERVATION}) = ?2)
+            ORDER BY created_at DESC, id DESC
+            "#
+        ))
+            .bind(product_id)
+            .bind(active)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::reservation_from_row).collect())
+    }
+
+    /// Zruší platnú rezerváciu a uvoľní tovar.
+    ///
+    /// # Arguments
+    /// * `id` – ID rezervácie
+    ///
+    /// # Returns
+    /// Zrušená rezervácia
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak rezervácia neexistuje, `StoreError::Conflict` ak už
+    /// nie je platná (vyzdvihnutá, zrušená alebo expirovaná)
+    #[instrument(skip_all, fields(db.operation.name = "cancel_reservation", db.rows = Empty))]
+    pub async fn cancel_reservation(&self, id: u32) -> Result<Reservation> {
+        let mut tx = self.m_pool.begin().await?;
+        let reservation = Self::reservation_in(&mut *tx, id).await?;
+        if reservation.status != ReservationStatus::Active {
+            return Err(StoreError::Conflict(format!(
+                "rezervácia {id} nie je platná ({})",
+                reservation.status.as_str()
+            )).into());
+        }
+        sqlx::query("UPDATE reservations SET status = 'cancelled' WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let reservation = Self::reservation_in(&mut *tx, id).await?;
+        tx.commit().await?;
+
+        Self::record_rows(1);
+        self.changed(&[Entity::Products]);
+        Ok(reservation)
+    }
+
+    /// Vyzdvihne rezerváciu: zaeviduje predaj rezervovaného množstva.
+    ///
+    /// # Arguments
+    /// * `id` – ID rezervácie
+    /// * `employee_id` – zamestnanec, ktorý predaj eviduje
+    ///
+    /// # Returns
+    /// Vyzdvihnutá rezervácia s ID objednávky
+    ///
+    /// # Errors
+    /// Ako `add_order` a `StoreError::NotFound`/`StoreError::Conflict` pri neexistujúcej
+    /// alebo neplatnej rezervácii
+    pub async fn fulfill_reservation(&self, id: u32, employee_id: Option<u32>) -> Result<Reservation> {
+        let reservation = Self::reservation_in(&self.m_pool, id).await?;
+        let order = Order {
+            id: None,
+            employee_id,
+            status: None,
+            created_at: None,
+            items: vec![OrderItem {
+                product_id: reservation.product_id,
+                quantity: reservation.quantity,
+                unit: None,
+                unit_price: None,
+                reservation_id: Some(id),
+            }],
+            returns: Vec::new(),
+            updated_at: None,
+        };
+        self.add_order(&order, false).await?;
+        Self::reservation_in(&self.m_pool, id).await
+    }
+
+    /// Načíta rezerváciu (z poolu alebo v rámci transakcie).
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak rezervácia neexistuje
+    async fn reservation_in<'e>(executor: impl sqlx::Executor<'e, Database = Sqlite>, id: u32) -> Result<Reservation> {
+        let select = format!("SELECT *, {RESERVATION_STATUS} AS effective_status FROM reservations WHERE id = ?");
+        let row = sqlx::query(&select)
+            .bind(id)
+            .fetch_optional(executor)
+            .await?
+            .ok_or_else(|| StoreError::NotFound(format!("rezervácia {id}")))?;
+        Ok(Self::reservation_from_row(&row))
+    }
+
+    fn reservation_from_row(r: &SqliteRow) -> Reservation {
+        Reservation {
+            id: r.get::<i64, _>("id") as u32,
+            product_id: r.get::<i64, _>("product_id") as u32,
+            quantity: Self::quantity_of(r, "quantity"),
+            customer: r.get("customer"),
+            created_at: r.get("created_at"),
+            expires_at: r.get("expires_at"),
+            status: ReservationStatus::parse(r.get("effective_status")).unwrap_or(ReservationStatus::Active),
+            order_id: r.get::<Option<i64>, _>("order_id").map(|v| v as u32),
+        }
+    }
+
+    // ==========================
+    // Purchase orders
+    // ==========================
+
+    /// Pridá objednávku u dodávateľa.
+    ///
+    /// Ak položka nemá `unit_cost`, použije sa nákupná cena produktu.
+    /// Bez zadaného stavu sa objednávka uloží ako `draft`.
+    ///
+    /// # Arguments
+    /// * `po` – objednávka s položkami
+    ///
+    /// # Returns
+    /// ID novej objednávky
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` pri neznámom produkte, `StoreError::Invalid` pri nulovom množstve
+    #[instrument(skip_all, fields(db.operation.name = "add_purchase_order", db.rows = Empty))]
+    pub async fn add_purchase_order(&self, po: &PurchaseOrder) -> Result<u32> {
+        let mut tx = self.m_pool.begin().await?;
+
+        let po_id = sqlx::query(
+            "INSERT INTO purchase_orders (supplier, status, created_at, expected_date) VALUES (?, ?, ?, ?)",
+        )
+            .bind(po.supplier.clone())
+            .bind(po.status.unwrap_or(PurchaseOrderStatus::Draft).as_str())
+            .bind(po.created_at.unwrap_or_else(|| Local::now().naive_local()))
+            .bind(po.expected_date)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+
+        Self::insert_purchase_order_items(&mut tx, po_id, po.items.as_deref().unwrap_or_default()).await?;
+
+        tx.commit().await?;
+        self.changed(&[Entity::PurchaseOrders]);
+        Ok(po_id as u32)
+    }
+
+    /// Vloží položky objednávky u dodávateľa v rámci transakcie.
+    async fn insert_purchase_order_items(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        po_id: i64,
+        items: &[PurchaseOrderItem],
+    ) -> Result<()> {
+        for item in items {
+            if item.quantity.is_nan() || item.quantity <= 0.0 {
+                return Err(StoreError::Invalid(format!("produkt {}: nulové množstvo", item.product_id)).into());
+            }
+
+            let product = sqlx::query("SELECT cost_price, unit, is_bundle FROM products WHERE id = ?")
+                .bind(item.product_id)
+                .fetch_optional(&mut **tx)
+                .await?
+                .ok_or_else(|| StoreError::NotFound(format!("produkt {}", item.product_id)))?;
+            if product.get::<i64, _>("is_bundle") == 1 {
+                return Err(StoreError::Invalid(format!(
+                    "produkt {} je sada, objednávajú sa jej zložky",
+                    item.product_id
+                )).into());
+            }
+            let cost: Option<f64> = product.get("cost_price");
+            let unit = Self::unit_of(&product);
+            if !unit.accepts(item.quantity) {
+                return Err(StoreError::Invalid(format!(
+                    "produkt {}: množstvo {} nie je prípustné pre jednotku {}",
+                    item.product_id,
+                    item.quantity,
+                    unit.as_str()
+                )).into());
+            }
+
+            sqlx::query(
+                "INSERT INTO purchase_order_items (purchase_order_id, product_id, quantity, unit_cost) VALUES (?, ?, ?, ?)",
+            )
+                .bind(po_id)
+                .bind(item.product_id)
+                .bind(item.quantity)
+                .bind(item.unit_cost.or(cost).unwrap_or(0.0))
+                .execute(&mut **tx)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Vráti objednávku u dodávateľa s položkami.
+    ///
+    /// # Arguments
+    /// * `id` – ID objednávky
+    ///
+    /// # Returns
+    /// Objednávka alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_purchase_order", db.rows = Empty))]
+    pub async fn get_purchase_order(&self, id: u32) -> Result<Option<PurchaseOrder>> {
+        let row = sqlx::query("SELECT * FROM purchase_orders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        match row {
+            Some(row) => Ok(Some(self.purchase_order_from_row(&row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Vráti objednávky u dodávateľov, voliteľne podľa dodávateľa a stavu.
+    ///
+    /// # Arguments
+    /// * `supplier` – filter dodávateľa
+    /// * `status` – filter stavu
+    ///
+    /// # Returns
+    /// Zoznam objednávok s položkami
+    #[instrument(skip_all, fields(db.operation.name = "get_purchase_orders", db.rows = Empty))]
+    pub async fn get_purchase_orders(
+        &self,
+        supplier: Option<String>,
+        status: Option<PurchaseOrderStatus>,
+    ) -> Result<Vec<PurchaseOrder>> {
+        let mut query = String::from("SELECT * FROM purchase_orders WHERE 1=1");
+        let mut args = SqliteArguments::default();
+        if let Some(supplier) = supplier { query.push_str(" AND supplier = ?"); let _ = args.add(supplier); }
+        if let Some(status) = status { query.push_str(" AND status = ?"); let _ = args.add(status.as_str()); }
+        query.push_str(" ORDER BY id");
+
+        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        let mut orders = Vec::with_capacity(rows.len());
+        for row in &rows {
+            orders.push(self.purchase_order_from_row(row).await?);
+        }
+        Ok(orders)
+    }
+
+    /// Prevedie riadok z tabuľky `purchase_orders` na `PurchaseOrder` a načíta položky.
+    async fn purchase_order_from_row(&self, row: &SqliteRow) -> Result<PurchaseOrder> {
+        let id: i64 = row.get("id");
+        let items = sqlx::query(
+            "SELECT product_id, quantity, unit_cost, received_quantity FROM purchase_order_items WHERE purchase_order_id = ? ORDER BY id",
+        )
+            .bind(id)
+            .fetch_all(&self.m_pool)
+            .await?
+            .iter()
+            .map(|r| PurchaseOrderItem {
+                product_id: r.get::<i64, _>("product_id") as u32,
+                quantity: Self::quantity_of(r, "quantity"),
+                unit_cost: r.get("unit_cost"),
+                received_quantity: Some(Self::quantity_of(r, "received_quantity")),
+            })
+            .collect();
+
+        Ok(PurchaseOrder {
+            id: Some(id as u32),
+            supplier: row.get("supplier"),
+            status: PurchaseOrderStatus::parse(row.get("status")),
+            created_at: row.get("created_at"),
+            expected_date: row.get("expected_date"),
+            items: Some(items),
+        })
+    }
+
+    /// Aktualizuje objednávku u dodávateľa.
+    ///
+    /// Menia sa len zadané polia. Zmena stavu musí byť povolený prechod
+    /// a položky je možné nahradiť len v stave `draft`. Stavy `partially_received`
+    /// a `received` sa nastavujú len príjmom tovaru.
+    ///
+    /// # Arguments
+    /// * `po` – nové dáta (musí obsahovať `id`)
+    ///
+    /// # Returns
+    /// `true` ak objednávka existuje a bola aktualizovaná
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` pri nepovolenom prechode stavu alebo zmene položiek mimo `draft`
+    #[instrument(skip_all, fields(db.operation.name = "update_purchase_order", db.rows = Empty))]
+    pub async fn update_purchase_order(&self, po: &PurchaseOrder) -> Result<bool> {
+        let id = match po.id {
+            Some(id) => id,
+            None => return Ok(false),
+        };
+
+        let mut tx = self.m_pool.begin().await?;
+
+        let current: Option<String> = sqlx::query_scalar("SELECT status FROM purchase_orders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let current = match current.as_deref().and_then(PurchaseOrderStatus::parse) {
+            Some(status) => status,
+            None => return Ok(false),
+        };
+
+        if let Some(next) = po.status {
+            let receiving = matches!(next, PurchaseOrderStatus::PartiallyReceived | PurchaseOrderStatus::Received);
+            if receiving && next != current {
+                return Err(StoreError::Conflict("príjem tovaru sa zapisuje cez /receive".into()).into());
+            }
+            if !current.can_transition_to(next) {
+                return Err(StoreError::Conflict(format!(
+                    "objednávku v stave {} nie je možné zmeniť na {}",
+                    current.as_str(),
+                    next.as_str()
+                )).into());
+            }
+        }
+
+        let mut query = String::from("UPDATE purchase_orders SET id = id");
+        let mut args = SqliteArguments::default();
+        if let Some(v) = &po.supplier { query.push_str(", supplier = ?"); let _ = args.add(v.clone()); }
+        if let Some(v) = po.status { query.push_str(", status = ?"); let _ = args.add(v.as_str()); }
+        if let Some(v) = po.expected_date { query.push_str(", expected_date = ?"); let _ = args.add(v); }
+        query.push_str(" WHERE id = ?");
+        let _ = args.add(id);
+        sqlx::query_with(&query, args).execute(&mut *tx).await?;
+
+        if let Some(items) = &po.items {
+            if current != PurchaseOrderStatus::Draft {
+                return Err(StoreError::Conflict("položky je možné meniť len v stave draft".into()).into());
+            }
+            sqlx::query("DELETE FROM purchase_order_items WHERE purchase_order_id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            Self::insert_purchase_order_items(&mut tx, id as i64, items).await?;
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::PurchaseOrders]);
+        Ok(true)
+    }
+
+    /// Zapíše príjem tovaru k objednávke u dodávateľa.
+    ///
+    /// Prijaté množstvá sú kumulatívne, takže opakovaný príjem tej istej
+    /// objednávky naskladní len rozdiel oproti predošlému príjmu. V jednej
+    /// transakcii zvýši stav zásob, zapíše pohyby skladu s dôvodom `purchase`,
+    /// aktualizuje prijaté množstvá položiek a stav objednávky.
+    ///
+    /// # Arguments
+    /// * `id` – ID objednávky
+    /// * `receipt` – prijaté množstvá položiek
+    /// * `allow_over_delivery` – povolí prijať viac, než bolo objednané
+    /// * `average_cost` – prijatým produktom nastaví `cost_price` na vážený priemer
+    ///   dávok na sklade (`weighted_avg_cost`)
+    /// * `block_overstock` – odmietne príjem, po ktorom by zásoby produktu prekročili
+    ///   jeho `max_stock` (inak sa prekročenie len uvedie vo `warnings`)
+    ///
+    /// # Returns
+    /// Nový stav objednávky, porovnanie objednaného a prijatého množstva a varovania
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak objednávka neexistuje, `StoreError::Conflict` ak objednávka
+    /// nie je odoslaná, ide o nepovolenú nadodávku alebo zablokované prekročenie `max_stock`,
+    /// `StoreError::Invalid` pri neznámej položke alebo znížení prijatého množstva
+    #[instrument(skip_all, fields(db.operation.name = "receive_purchase_order", db.rows = Empty))]
+    pub async fn receive_purchase_order(
+        &self,
+        id: u32,
+        receipt: &PurchaseOrderReceipt,
+        allow_over_delivery: bool,
+        average_cost: bool,
+        block_overstock: bool,
+    ) -> Result<PurchaseOrderReceiptResult> {
+        let mut tx = self.m_pool.begin().await?;
+
+        let current: Option<String> = sqlx::query_scalar("SELECT status FROM purchase_orders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let current = current
+            .as_deref()
+            .and_then(PurchaseOrderStatus::parse)
+            .ok_or_else(|| StoreError::NotFound(format!("objednávka u dodávateľa {id}")))?;
+        if !matches!(current, PurchaseOrderStatus::Sent | PurchaseOrderStatus::PartiallyReceived) {
+            return Err(StoreError::Conflict(format!(
+                "tovar nie je možné prijať k objednávke v stave {}",
+                current.as_str()
+            )).into());
+        }
+
+        let mut over_delivered = Vec::new();
+        let mut restocked = Vec::new();
+        let mut warnings = Vec::new();
+
+        for line in &receipt.items {
+            let row = sqlx::query(
+                r#"
+                SELECT i.id, i.quantity, i.received_quantity, i.unit_cost, p.unit
+                FROM purchase_order_items i
+                LEFT JOIN products p ON p.id = i.product_id
+                WHERE i.purchase_order_id = ? AND i.product_id = ?
+                ORDER BY i.id LIMIT 1
+                "#,
+            )
+                .bind(id)
+                .bind(line.product_id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or_else(|| StoreError::Invalid(format!("produkt {} nie je v objednávke", line.product_id)))?;
+
+            let ordered = Self::quantity_of(&row, "quantity");
+            let previous = Self::quantity_of(&row, "received_quantity");
+            let received = line.received_quantity;
+            let unit = Self::unit_of(&row);
+
+            if !unit.accepts(received) {
+                return Err(StoreError::Invalid(format!(
+                    "produkt {}: množstvo {} nie je prípustné pre jednotku {}",
+                    line.product_id,
+                    received,
+                    unit.as_str()
+                )).into());
+            }
+            if received + QUANTITY_EPSILON < previous {
+                return Err(StoreError::Invalid(format!(
+                    "produkt {}: prijaté množstvo nie je možné znížiť z {} na {}",
+                    line.product_id, previous, received
+                )).into());
+            }
+            if received > ordered + QUANTITY_EPSILON {
+                over_delivered.push(format!("produkt {}: objednané {}, prijaté {}", line.product_id, ordered, received));
+            }
+
+            let delta = received - previous;
+            if delta.abs() <= QUANTITY_EPSILON {
+                continue;
+            }
+
+            sqlx::query("UPDATE purchase_order_items SET received_quantity = ? WHERE id = ?")
+                .bind(received)
+                .bind(row.get::<i64, _>("id"))
+                .execute(&mut *tx)
+                .await?;
+
+            sqlx::query("UPDATE products SET quantity = quantity + ? WHERE id = ?")
+                .bind(delta)
+                .bind(line.product_id)
+                .execute(&mut *tx)
+                .await?;
+
+            Self::add_to_batches(
+                &mut tx,
+                line.product_id,
+                delta,
+                line.lot_code.as_deref(),
+                line.expiry_date,
+            )
+                .await?;
+
+            Self::insert_movement(&mut tx, line.product_id, delta, "purchase", Some(format!("PO-{id}"))).await?;
+            if delta > 0.0 {
+                Self::record_lot(&mut tx, line.product_id, delta, Some(row.get("unit_cost"))).await?;
+                Self::check_category_capacity(&mut tx, line.product_id).await?;
+                if let Some(overstock) = Self::overstock_of(&mut tx, line.product_id).await? {
+                    if block_overstock {
+                        return Err(StoreError::Conflict(overstock).into());
+                    }
+                    warnings.push(overstock);
+                }
+                restocked.push(line.product_id);
+            }
+        }
+
+        if !over_delivered.is_empty() && !allow_over_delivery {
+            return Err(StoreError::Conflict(format!("nadodávka: {}", over_delivered.join("; "))).into());
+        }
+
+        if average_cost {
+            for product_id in restocked {
+                if let Some(cost) = Self::weighted_avg_cost_in(&mut tx, product_id).await? {
+                    sqlx::query("UPDATE products SET cost_price = ? WHERE id = ?")
+                        .bind(cost)
+                        .bind(product_id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+
+        let lines = Self::purchase_order_lines(&mut tx, id).await?;
+        let status = if lines.iter().all(|l| l.received + QUANTITY_EPSILON >= l.ordered) {
+            PurchaseOrderStatus::Received
+        } else if lines.iter().any(|l| l.received > 0.0) {
+            PurchaseOrderStatus::PartiallyReceived
+        } else {
+            current
+        };
+
+        sqlx::query("UPDATE purchase_orders SET status = ? WHERE id = ?")
+            .bind(status.as_str())
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        self.changed(&[Entity::PurchaseOrders, Entity::Products]);
+        Ok(PurchaseOrderReceiptResult { status, lines, warnings })
+    }
+
+    /// Vráti porovnanie objednaného a prijatého množstva položiek objednávky.
+    ///
+    /// # Arguments
+    /// * `id` – ID objednávky
+    ///
+    /// # Returns
+    /// Rozdiely po položkách alebo `None`, ak objednávka neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "purchase_order_discrepancies", db.rows = Empty))]
+    pub async fn purchase_order_discrepancies(&self, id: u32) -> Result<Option<Vec<PurchaseOrderDiscrepancy>>> {
+        let mut tx = self.m_pool.begin().await?;
+        let exists = sqlx::query("SELECT id FROM purchase_orders WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .is_some();
+        if !exists {
+            return Ok(None);
+        }
+        Ok(Some(Self::purchase_order_lines(&mut tx, id).await?))
+    }
+
+    /// Načíta objednané a prijaté množstvá položiek objednávky v rámci transakcie.
+    async fn purchase_order_lines(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        id: u32,
+    ) -> Result<Vec<PurchaseOrderDiscrepancy>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT i.product_id, i.quantity, i.received_quantity, p.unit
+            FROM purchase_order_items i
+            LEFT JOIN products p ON p.id = i.product_id
+            WHERE i.purchase_order_id = ?
+            ORDER BY i.id
+            "#,
+        )
+            .bind(id)
+            .fetch_all(&mut **tx)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let ordered = Self::quantity_of(r, "quantity");
+                let received = Self::quantity_of(r, "received_quantity");
+                PurchaseOrderDiscrepancy {
+                    product_id: r.get::<i64, _>("product_id") as u32,
+                    ordered,
+                    received,
+                    unit: Self::unit_of(r),
+                    difference: received - ordered,
+                    over_delivered: received > ordered + QUANTITY_EPSILON,
+                }
+            })
+            .collect())
+    }
+
+    /// Vymaže objednávku u dodávateľa aj s položkami.
+    ///
+    /// # Arguments
+    /// * `id` – ID objednávky
+    ///
+    /// # Returns
+    /// `true` ak bol záznam vymazaný
+    #[instrument(skip_all, fields(db.operation.name = "delete_purchase_order", db.rows = Empty))]
+    pub async fn delete_purchase_order(&self, id: u32) -> Result<bool> {
+        let mut tx = self.m_pool.begin().await?;
+        sqlx::query("DELETE FROM purchase_order_items WHERE purchase_order_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM purchase_orders WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.changed(&[Entity::PurchaseOrders]);
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Vráti návrhy na doobjednanie aktívnych produktov s nízkym stavom zásob.
+    ///
+    /// # Arguments
+    /// * `threshold` – hranica nízkeho stavu zásob (vrátane) pre produkty bez vlastnej `min_stock`
+    /// * `target` – cieľové množstvo na sklade po doobjednaní
+    ///
+    /// # Returns
+    /// Produkty na doobjednanie s navrhovaným množstvom
+    #[instrument(skip_all, fields(db.operation.name = "reorder_suggestions", db.rows = Empty))]
+    pub async fn reorder_suggestions(&self, threshold: u32, target: u32) -> Result<Vec<ReorderSuggestion>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, supplier, quantity, unit, pack_size, cost_price
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0 AND quantity <= COALESCE(min_stock, ?)
+            ORDER BY supplier, id
+            "#,
+        )
+            .bind(threshold)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let quantity = Self::quantity_of(r, "quantity").max(0.0);
+                let unit = Self::unit_of(r);
+                let mut suggested = (f64::from(target) - quantity).max(0.0);
+                if !unit.is_fractional() {
+                    suggested = suggested.ceil();
+                }
+                ReorderSuggestion {
+                    product_id: r.get::<i64, _>("id") as u32,
+                    name: r.get("name"),
+                    supplier: r.get("supplier"),
+                    quantity,
+                    unit,
+                    suggested_quantity: suggested,
+                    unit_cost: r.get("cost_price"),
+                    estimated_cost: r.get::<Option<f64>, _>("cost_price").map(|c| c * suggested),
+                    reorder_point: None,
+                    daily_sales: None,
+                }
+            })
+            .filter(|s| s.suggested_quantity > 0.0)
+            .collect())
+    }
+
+    /// Vráti produkty, ktoré treba doobjednať hneď, od najnaliehavejšieho.
+    ///
+    /// Bod doobjednania je poistná zásoba (`min_stock`, inak nula) plus predaj
+    /// počas dodacej lehoty (`lead_time_days`, inak `DEFAULT_LEAD_TIME_DAYS`)
+    /// podľa priemerného denného predaja za posledných `SALES_VELOCITY_DAYS` dní
+    /// (po odpočítaní vratiek). Produkt na bode doobjednania alebo pod ním dostane
+    /// návrh, ktorý doplní zásoby na bod doobjednania a pokryje predaj na ďalších
+    /// `SALES_VELOCITY_DAYS` dní. Naliehavosť je podiel chýbajúceho množstva
+    /// na bode doobjednania; pri zhode rozhoduje ID.
+    ///
+    /// # Returns
+    /// Návrhy s bodom doobjednania, denným predajom a odhadovanou cenou
+    #[instrument(skip_all, fields(db.operation.name = "reorder_list", db.rows = Empty))]
+    pub async fn reorder_list(&self) -> Result<Vec<ReorderSuggestion>> {
+        self.reorder_points(None).await
+    }
+
+    /// Zostaví podklad objednávky u dodávateľa: jeho produkty na bode doobjednania
+    /// alebo pod ním s navrhovaným množstvom (rovnako ako `reorder_list`).
+    ///
+    /// # Arguments
+    /// * `supplier` – dodávateľ (presná zhoda)
+    ///
+    /// # Returns
+    /// Riadky od najnaliehavejšieho a odhadovaná cena spolu; prázdny podklad,
+    /// ak netreba nič objednať
+    #[instrument(skip_all, fields(db.operation.name = "supplier_order_sheet", db.rows = Empty))]
+    pub async fn supplier_order_sheet(&self, supplier: &str) -> Result<SupplierOrderSheet> {
+        let lines = self.reorder_points(Some(supplier)).await?;
+        Ok(SupplierOrderSheet {
+            supplier: supplier.to_string(),
+            total_cost: lines.iter().filter_map(|l| l.estimated_cost).sum(),
+            uncosted_lines: lines.iter().filter(|l| l.estimated_cost.is_none()).count() as u32,
+            lines,
+        })
+    }
+
+    /// Spočíta odhadovanú cenu doobjednania (`reorder_list`) po kategóriách.
+    ///
+    /// Zahrnuté sú všetky kategórie aktívnych produktov, ktoré nie sú sadami,
+    /// aj keď v nich netreba nič doobjednať. Návrhy bez nákupnej ceny sa do
+    /// súčtu nezapočítajú.
+    ///
+    /// # Returns
+    /// Dvojice (kategória, odhadovaná cena) od najdrahšej, pri zhode podľa názvu
+    #[instrument(skip_all, fields(db.operation.name = "reorder_budget_by_category", db.rows = Empty))]
+    pub async fn reorder_budget_by_category(&self) -> Result<Vec<(String, f64)>> {
+        let suggestions = self.reorder_list().await?;
+        let categories: Vec<(i64, String)> = sqlx::query_as(
+            r#"
+            SELECT id, COALESCE(category, 'Neznáma') AS category
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let category_of: BTreeMap<u32, &str> = categories.iter().map(|(id, c)| (*id as u32, c.as_str())).collect();
+        let mut budgets: BTreeMap<&str, f64> = categories.iter().map(|(_, c)| (c.as_str(), 0.0)).collect();
+        for suggestion in &suggestions {
+            if let (Some(category), Some(cost)) = (category_of.get(&suggestion.product_id), suggestion.estimated_cost) {
+                *budgets.entry(category).or_default() += cost;
+            }
+        }
+
+        let mut budgets: Vec<(String, f64)> = budgets.into_iter().map(|(c, b)| (c.to_string(), b)).collect();
+        budgets.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Self::record_rows(budgets.len());
+        Ok(budgets)
+    }
+
+    /// Návrhy na doobjednanie podľa bodu doobjednania (pozri `reorder_list`).
+    ///
+    /// # Arguments
+    /// * `supplier` – len produkty dodávateľa (`None` = všetky)
+    async fn reorder_points(&self, supplier: Option<&str>) -> Result<Vec<ReorderSuggestion>> {
+        let rows = sqlx::query(
+            r#"
+            WITH sold AS (
+                SELECT i.product_id, i.quantity AS qty
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                WHERE date(o.created_at) > date('now', 'localtime', ?1)
+                UNION ALL
+                SELECT i.product_id, -i.quantity
+                FROM return_items i
+                JOIN order_returns r ON r.id = i.return_id
+                WHERE date(r.created_at) > date('now', 'localtime', ?1)
+            )
+            SELECT p.id, p.name, p.supplier, p.quantity, p.unit, p.cost_price, p.min_stock, p.lead_time_days,
+                   COALESCE((SELECT SUM(qty) FROM sold WHERE sold.product_id = p.id), 0.0) AS sold
+            FROM products p
+            WHERE COALESCE(p.status, 1) = 1 AND p.is_bundle = 0 AND (?2 IS NULL OR p.supplier = ?2)
+            ORDER BY p.id
+            "#,
+        )
+            .bind(format!("-{SALES_VELOCITY_DAYS} days"))
+            .bind(supplier)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let mut suggestions: Vec<(f64, ReorderSuggestion)> = rows
+            .iter()
+            .filter_map(|r| {
+                let quantity = Self::quantity_of(r, "quantity").max(0.0);
+                let unit = Self::unit_of(r);
+                let daily_sales = (Self::quantity_of(r, "sold") / f64::from(SALES_VELOCITY_DAYS)).max(0.0);
+                let lead_time = r.get::<Option<i64>, _>("lead_time_days").map_or(DEFAULT_LEAD_TIME_DAYS, |d| d as u32);
+                let safety = r.get::<Option<f64>, _>("min_stock").unwrap_or(0.0);
+                let reorder_point = safety + daily_sales * f64::from(lead_time);
+                if quantity > reorder_point + QUANTITY_EPSILON {
+                    return None;
+                }
+                let mut suggested = reorder_point - quantity + daily_sales * f64::from(SALES_VELOCITY_DAYS);
+                if !unit.is_fractional() {
+                    suggested = (suggested - QUANTITY_EPSILON).ceil();
+                }
+                if suggested <= QUANTITY_EPSILON {
+                    return None;
+                }
+                let unit_cost: Option<f64> = r.get("cost_price");
+                let urgency = (reorder_point - quantity) / reorder_point;
+                Some((urgency, ReorderSuggestion {
+                    product_id: r.get::<i64, _>("id") as u32,
+                    name: r.get("name"),
+                    supplier: r.get("supplier"),
+                    quantity,
+                    unit,
+                    suggested_quantity: suggested,
+                    unit_cost,
+                    estimated_cost: unit_cost.map(|c| c * suggested),
+                    reorder_point: Some(reorder_point),
+                    daily_sales: Some(daily_sales),
+                }))
+            })
+            .collect();
+        suggestions.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.product_id.cmp(&b.1.product_id)));
+        Ok(suggestions.into_iter().map(|(_, s)| s).collect())
+    }
+
+    /// Vypočíta, na koľko dní vystačia zásoby produktu pri súčasnom tempe predaja.
+    ///
+    /// Tempo je priemerný denný predaj za posledných `SALES_VELOCITY_DAYS` dní
+    /// po odpočítaní vratiek (ako v `reorder_list`).
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    ///
+    /// # Returns
+    /// Počet dní; `None`, ak sa produkt v tomto období nepredával (pokrytie je neobmedzené)
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje
+    pub async fn stock_coverage_days(&self, id: u32) -> Result<Option<f64>> {
+        match self.stock_coverage(id).await? {
+            Some(coverage) => Ok(coverage.coverage_days),
+            None => Err(StoreError::NotFound(format!("produkt {id}")).into()),
+        }
+    }
+
+    /// Vráti pokrytie zásob produktu s denným predajom a vysvetlením chýbajúceho odhadu.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    ///
+    /// # Returns
+    /// Pokrytie alebo `None`, ak produkt neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "stock_coverage", db.rows = Empty))]
+    pub async fn stock_coverage(&self, id: u32) -> Result<Option<StockCoverage>> {
+        Ok(self.coverage_lines(Some(id)).await?.pop())
+    }
+
+    /// Vráti aktívne produkty, ktorých zásoby pri súčasnom tempe predaja nevydržia `days` dní.
+    ///
+    /// Produkty bez predaja za posledných `SALES_VELOCITY_DAYS` dní sa vynechajú
+    /// (ich pokrytie je neobmedzené), rovnako sady.
+    ///
+    /// # Arguments
+    /// * `days` – hranica pokrytia v dňoch
+    ///
+    /// # Returns
+    /// Produkty od najkratšieho pokrytia
+    #[instrument(skip_all, fields(db.operation.name = "low_coverage", db.rows = Empty))]
+    pub async fn low_coverage(&self, days: u32) -> Result<Vec<StockCoverage>> {
+        let mut lines: Vec<StockCoverage> = self
+            .coverage_lines(None)
+            .await?
+            .into_iter()
+            .filter(|c| c.coverage_days.is_some_and(|d| d < f64::from(days)))
+            .collect();
+        lines.sort_by(|a, b| {
+            a.coverage_days.unwrap_or(f64::INFINITY).total_cmp(&b.coverage_days.unwrap_or(f64::INFINITY))
+                .then(a.product_id.cmp(&b.product_id))
+        });
+        Ok(lines)
+    }
+
+    /// Pokrytie zásob jedného produktu, bez ID všetkých aktívnych produktov okrem sád.
+    async fn coverage_lines(&self, id: Option<u32>) -> Result<Vec<StockCoverage>> {
+        let rows = sqlx::query(
+            r#"
+            WITH sold AS (
+                SELECT i.product_id, i.quantity AS qty
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                WHERE date(o.created_at) > date('now', 'localtime', ?1)
+                UNION ALL
+                SELECT i.product_id, -i.quantity
+                FROM return_items i
+                JOIN order_returns r ON r.id = i.return_id
+                WHERE date(r.created_at) > date('now', 'localtime', ?1)
+            )
+            SELECT p.id, p.name, p.quantity, p.unit,
+                   COALESCE((SELECT SUM(qty) FROM sold WHERE sold.product_id = p.id), 0.0) AS sold,
+                   EXISTS (SELECT 1 FROM order_items i WHERE i.product_id = p.id) AS has_sales
+            FROM products p
+            WHERE CASE WHEN ?2 IS NULL THEN COALESCE(p.status, 1) = 1 AND p.is_bundle = 0 ELSE p.id = ?2 END
+            ORDER BY p.id
+            "#,
+        )
+            .bind(format!("-{SALES_VELOCITY_DAYS} days"))
+            .bind(id)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let quantity = Self::quantity_of(r, "quantity").max(0.0);
+                let daily_sales = (Self::quantity_of(r, "sold") / f64::from(SALES_VELOCITY_DAYS)).max(0.0);
+                let (coverage_days, message) = if daily_sales > QUANTITY_EPSILON {
+                    (Some(quantity / daily_sales), None)
+                } else if r.get::<bool, _>("has_sales") {
+                    (None, Some(format!("za posledných {SALES_VELOCITY_DAYS} dní sa nepredal, pokrytie je neobmedzené")))
+                } else {
+                    (None, Some("produkt sa ešte nepredával, tempo predaja nie je známe".to_string()))
+                };
+                StockCoverage {
+                    product_id: r.get::<i64, _>("id") as u32,
+                    name: r.get("name"),
+                    quantity,
+                    unit: Self::unit_of(r),
+                    daily_sales,
+                    coverage_days,
+                    message,
+                }
+            })
+            .collect())
+    }
+
+    /// Vráti hodnotu zásob aktívnych produktov v nákupných cenách.
+    ///
+    /// Sady sa nezapočítavajú, ich zásoby tvoria komponenty. Záporné množstvo
+    /// sa počíta ako nula.
+    ///
+    /// # Returns
+    /// Riadky zoradené podľa kategórie a názvu so súčtami
+    #[instrument(skip_all, fields(db.operation.name = "inventory_value", db.rows = Empty))]
+    pub async fn inventory_value(&self) -> Result<InventoryValueReport> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, category, quantity, unit, cost_price
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0
+            ORDER BY category, name, id
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let lines: Vec<InventoryValueLine> = rows
+            .iter()
+            .map(|r| {
+                let quantity = Self::quantity_of(r, "quantity").max(0.0);
+                let cost_price: Option<f64> = r.get("cost_price");
+                InventoryValueLine {
+                    product_id: r.get::<i64, _>("id") as u32,
+                    name: r.get("name"),
+                    category: r.get("category"),
+                    quantity,
+                    unit: Self::unit_of(r),
+                    cost_price,
+                    value: quantity * cost_price.unwrap_or(0.0),
+                }
+            })
+            .collect();
+
+        Ok(InventoryValueReport {
+            total_quantity: lines.iter().map(|l| l.quantity).sum(),
+            total_value: lines.iter().map(|l| l.value).sum(),
+            uncosted_products: lines.iter().filter(|l| l.cost_price.is_none() && l.quantity > 0.0).count() as u32,
+            lines,
+        })
+    }
+
+    /// Zostaví súhrn pre nástenku jedným volaním.
+    ///
+    /// Jednotlivé časti (hodnota zásob, nízky stav zásob, počet zamestnancov,
+    /// posledné pohyby skladu) sa načítajú súbežne.
+    ///
+    /// # Arguments
+    /// * `low_stock_threshold` – hranica nízkeho stavu zásob pre produkty bez `min_stock`
+    ///
+    /// # Returns
+    /// Súhrn zásob, počty produktov s nízkym a nulovým stavom, najhodnotnejšie
+    /// produkty, počet aktívnych zamestnancov a posledné pohyby skladu
+    #[instrument(skip_all, fields(db.operation.name = "dashboard", db.rows = Empty))]
+    pub async fn dashboard(&self, low_stock_threshold: u32) -> Result<DashboardSummary> {
+        let employees = async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM employees WHERE COALESCE(status, 1) = 1")
+                .fetch_one(&self.m_pool)
+                .await
+        };
+        let (inventory, low_stock, employees, recent_activity) = tokio::join!(
+            self.inventory_value(),
+            self.low_stock(low_stock_threshold),
+            employees,
+            self.recent_movements(DASHBOARD_ITEMS),
+        );
+        let (inventory, low_stock, recent_activity) = (inventory?, low_stock?, recent_activity?);
+
+        let mut top_products = inventory.lines.clone();
+        top_products.sort_by(|a, b| b.value.total_cmp(&a.value).then(a.product_id.cmp(&b.product_id)));
+        top_products.truncate(DASHBOARD_ITEMS as usize);
+
+        Ok(DashboardSummary {
+            generated_at: Local::now().naive_local(),
+            inventory: DashboardInventory {
+                products: inventory.lines.len() as u32,
+                total_quantity: inventory.total_quantity,
+                total_value: inventory.total_value,
+                uncosted_products: inventory.uncosted_products,
+            },
+            low_stock: low_stock.lines.len() as u32,
+            out_of_stock: low_stock.out_of_stock,
+            top_products,
+            employees: employees? as u32,
+            recent_activity,
+        })
+    }
+
+    /// Ocení zásoby aktívnych produktov podľa nákupných dávok (`stock_lots`).
+    ///
+    /// Dávky sa zapisujú pri príjme tovaru (objednávka u dodávateľa, naskladnenie,
+    /// nová šarža). Výdaje sa k dávkam nepárujú: pri FIFO sa predpokladá, že na sklade
+    /// zostali najnovšie dávky, pri priemere sa celé množstvo ocení váženým priemerom
+    /// všetkých dávok. Množstvo, ktoré dávky nepokrývajú (zásoba spred ich evidencie),
+    /// sa ocení aktuálnou `cost_price`. Sady sa nezapočítavajú, záporné množstvo je nula.
+    ///
+    /// # Arguments
+    /// * `method` – metóda oceňovania
+    ///
+    /// # Returns
+    /// Riadky zoradené podľa kategórie a názvu so súčtami
+    #[instrument(skip_all, fields(db.operation.name = "valuation", db.rows = Empty))]
+    pub async fn valuation(&self, method: ValuationMethod) -> Result<ValuationReport> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, category, quantity, unit, cost_price
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0
+            ORDER BY category, name, id
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        let lots = sqlx::query(
+            r#"
+            SELECT l.product_id, l.quantity, l.unit_cost
+            FROM stock_lots l
+            JOIN products p ON p.id = l.product_id
+            WHERE COALESCE(p.status, 1) = 1 AND p.is_bundle = 0
+            ORDER BY l.received_at DESC, l.id DESC
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len() + lots.len());
+
+        // dávky produktu od najnovšej
+        let mut lots_of: BTreeMap<u32, Vec<(f64, f64)>> = BTreeMap::new();
+        for lot in &lots {
+            lots_of
+                .entry(lot.get::<i64, _>("product_id") as u32)
+                .or_default()
+                .push((lot.get("quantity"), lot.get("unit_cost")));
+        }
+
+        let lines: Vec<ValuationLine> = rows
+            .iter()
+            .map(|r| {
+                let product_id = r.get::<i64, _>("id") as u32;
+                let quantity = Self::quantity_of(r, "quantity").max(0.0);
+                let cost_price = r.get::<Option<f64>, _>("cost_price").unwrap_or(0.0);
+                let lots = lots_of.get(&product_id).map(Vec::as_slice).unwrap_or_default();
+                let lotted: f64 = lots.iter().map(|(q, _)| q).sum();
+                let unlotted = (quantity - lotted).max(0.0);
+
+                let value = match method {
+                    ValuationMethod::Average => {
+                        let cost: f64 = lots.iter().map(|(q, c)| q * c).sum::<f64>() + unlotted * cost_price;
+                        let received = lotted + unlotted;
+                        if received > QUANTITY_EPSILON { quantity * cost / received } else { 0.0 }
+                    }
+                    ValuationMethod::Fifo => {
+                        let mut remaining = quantity - unlotted;
+                        let mut value = unlotted * cost_price;
+                        for (lot_quantity, unit_cost) in lots {
+                            if remaining <= QUANTITY_EPSILON {
+                                break;
+                            }
+                            let taken = remaining.min(*lot_quantity);
+                            value += taken * unit_cost;
+                            remaining -= taken;
+                        }
+                        value
+                    }
+                };
+
+                ValuationLine {
+                    product_id,
+                    name: r.get("name"),
+                    category: r.get("category"),
+                    quantity,
+                    unit: Self::unit_of(r),
+                    unit_cost: (quantity > QUANTITY_EPSILON).then(|| value / quantity),
+                    value,
+                    unlotted_quantity: unlotted,
+                }
+            })
+            .collect();
+
+        Ok(ValuationReport {
+            method,
+            total_quantity: lines.iter().map(|l| l.quantity).sum(),
+            total_value: lines.iter().map(|l| l.value).sum(),
+            lines,
+        })
+    }
+
+    /// Vypočíta vážený priemer nákupných cien dávok, ktoré sú ešte na sklade.
+    ///
+    /// Výdaje sa k dávkam nepárujú, preto sa ako pri FIFO oceňovaní predpokladá,
+    /// že na sklade zostali najnovšie dávky v rozsahu súčasného stavu. Množstvo,
+    /// ktoré dávky nepokrývajú (zásoba spred ich evidencie), sa započíta
+    /// aktuálnou `cost_price`, ak je známa.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    ///
+    /// # Returns
+    /// Priemerná cena za jednotku; `None`, ak produkt nemá zásobu alebo cenu
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "weighted_avg_cost", db.rows = Empty))]
+    pub async fn weighted_avg_cost(&self, id: u32) -> Result<Option<f64>> {
+        let mut conn = self.m_pool.acquire().await?;
+        Self::weighted_avg_cost_in(&mut conn, id).await
+    }
+
+    /// Vráti vážený priemer nákupnej ceny spolu so stavom zásob a uloženou cenou.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje
+    pub async fn average_cost(&self, id: u32) -> Result<AverageCost> {
+        let average_cost = self.weighted_avg_cost(id).await?;
+        let product = self.get_product(id).await?.ok_or_else(|| StoreError::NotFound(format!("produkt {id}")))?;
+        Ok(AverageCost {
+            product_id: id,
+            quantity: product.quantity.unwrap_or(0.0),
+            average_cost,
+            cost_price: product.cost_price,
+        })
+    }
+
+    /// Pozri `weighted_avg_cost`; počíta nad daným spojením (napr. v transakcii príjmu).
+    async fn weighted_avg_cost_in(conn: &mut SqliteConnection, id: u32) -> Result<Option<f64>> {
+        let row = sqlx::query("SELECT quantity, unit, cost_price FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .ok_or_else(|| StoreError::NotFound(format!("produkt {id}")))?;
+        let lots: Vec<(f64, f64)> = sqlx::query_as(
+            "SELECT quantity, unit_cost FROM stock_lots WHERE product_id = ? ORDER BY received_at DESC, id DESC",
+        )
+            .bind(id)
+            .fetch_all(&mut *conn)
+            .await?;
+        Self::record_rows(lots.len() + 1);
+
+        let mut remaining = Self::quantity_of(&row, "quantity").max(0.0);
+        let (mut cost, mut costed) = (0.0, 0.0);
+        for (lot_quantity, unit_cost) in lots {
+            if remaining <= QUANTITY_EPSILON {
+                break;
+            }
+            let taken = remaining.min(lot_quantity);
+            cost += taken * unit_cost;
+            costed += taken;
+            remaining -= taken;
+        }
+        if let Some(cost_price) = row.get::<Option<f64>, _>("cost_price").filter(|_| remaining > QUANTITY_EPSILON) {
+            cost += remaining * cost_price;
+            costed += remaining;
+        }
+        Ok((costed > QUANTITY_EPSILON).then(|| cost / costed))
+    }
+
+    /// Nastaví alebo zruší (`None`) vlastnú hranicu nízkeho stavu zásob produktu.
+    ///
+    /// Produkt bez vlastnej hranice sa riadi spoločnou `low_stock_threshold`.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    /// * `min_stock` – hranica v jednotke produktu (vrátane)
+    ///
+    /// # Returns
+    /// Upravený produkt alebo `None`, ak neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak hranica nezodpovedá jednotke produktu
+    #[instrument(skip_all, fields(db.operation.name = "set_min_stock", db.rows = Empty))]
+    pub async fn set_min_stock(&self, id: u32, min_stock: Option<f64>) -> Result<Option<Product>> {
+        let mut tx = self.m_pool.begin().await?;
+        let Some(current) = sqlx::query("SELECT unit FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        Self::validate_stock_limit(Self::unit_of(&current), min_stock)?;
+        sqlx::query("UPDATE products SET min_stock = ? WHERE id = ?")
+            .bind(min_stock)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(self.get_product(id).await?)
+    }
+
+    /// Nastaví alebo zruší (`None`) najvyšší žiadaný stav zásob produktu.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    /// * `max_stock` – hranica v jednotke produktu (vrátane)
+    ///
+    /// # Returns
+    /// Upravený produkt alebo `None`, ak neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak hranica nezodpovedá jednotke produktu
+    #[instrument(skip_all, fields(db.operation.name = "set_max_stock", db.rows = Empty))]
+    pub async fn set_max_stock(&self, id: u32, max_stock: Option<f64>) -> Result<Option<Product>> {
+        let mut tx = self.m_pool.begin().await?;
+        let Some(current) = sqlx::query("SELECT unit FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        Self::validate_stock_limit(Self::unit_of(&current), max_stock)?;
+        sqlx::query("UPDATE products SET max_stock = ? WHERE id = ?")
+            .bind(max_stock)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(self.get_product(id).await?)
+    }
+
+    /// Vráti aktívne produkty, ktorých zásoby prekračujú ich `max_stock`.
+    ///
+    /// # Returns
+    /// Produkty zoradené od najväčšieho prebytku nad hranicou
+    #[instrument(skip_all, fields(db.operation.name = "get_overstocked", db.rows = Empty))]
+    pub async fn get_overstocked(&self) -> Result<Vec<Product>> {
+        let sql = format!(
+            "{PRODUCT_SELECT} WHERE COALESCE(status, 1) = 1 AND is_bundle = 0 AND max_stock IS NOT NULL \
+             AND quantity > max_stock + ? ORDER BY quantity - max_stock DESC, id"
+        );
+        let rows = sqlx::query(&sql).bind(QUANTITY_EPSILON).fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::product_from_row).collect())
+    }
+
+    /// Porovná stav zásob produktu po naskladnení s jeho `max_stock`.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia so zapísanou zmenou
+    /// * `product_id` – ID produktu, ktorého zásoby pribudli
+    ///
+    /// # Returns
+    /// Popis prekročenia alebo `None`, ak produkt hranicu nemá alebo ju neprekročil
+    async fn overstock_of(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, product_id: u32) -> Result<Option<String>> {
+        let row = sqlx::query("SELECT quantity, max_stock FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+        let Some(row) = row else { return Ok(None) };
+        let quantity = Self::quantity_of(&row, "quantity");
+        Ok(match row.get::<Option<f64>, _>("max_stock") {
+            Some(max) if quantity > max + QUANTITY_EPSILON => {
+                Some(format!("produkt {product_id}: stav {quantity} prekračuje najvyšší stav zásob {max}"))
+            }
+            _ => None,
+        })
+    }
+
+    /// Nastaví produktu akciovú cenu platnú do `ends` (vrátane).
+    ///
+    /// Počas akcie je `effective_price` produktu vo výpisoch akciová cena,
+    /// po jej skončení opäť `sell_price`.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    /// * `sale_price` – akciová cena, nižšia ako predajná
+    /// * `ends` – posledný deň akcie
+    ///
+    /// # Returns
+    /// Upravený produkt alebo `None`, ak neexistuje
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` ak akciová cena nie je kladná a nižšia ako predajná
+    /// alebo akcia skončila v minulosti
+    #[instrument(skip_all, fields(db.operation.name = "set_sale", db.rows = Empty))]
+    pub async fn set_sale(&self, id: u32, sale_price: f64, ends: NaiveDate) -> Result<Option<Product>> {
+        let mut tx = self.m_pool.begin().await?;
+        let Some(sell_price) = sqlx::query_scalar::<_, f64>("SELECT sell_price FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        if !sale_price.is_finite() || sale_price <= 0.0 || sale_price >= sell_price {
+            return Err(StoreError::Invalid(format!(
+                "akciová cena {sale_price} musí byť kladná a nižšia ako predajná cena {sell_price}"
+            ))
+            .into());
+        }
+        if ends < Local::now().date_naive() {
+            return Err(StoreError::Invalid(format!("akcia nemôže skončiť v minulosti ({ends})")).into());
+        }
+        sqlx::query("UPDATE products SET sale_price = ?, sale_ends = ? WHERE id = ?")
+            .bind(sale_price)
+            .bind(ends)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        self.changed(&[Entity::Products]);
+        Ok(self.get_product(id).await?)
+    }
+
+    /// Zruší akciu produktu.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    ///
+    /// # Returns
+    /// Upravený produkt alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "clear_sale", db.rows = Empty))]
+    pub async fn clear_sale(&self, id: u32) -> Result<Option<Product>> {
+        let result = sqlx::query("UPDATE products SET sale_price = NULL, sale_ends = NULL WHERE id = ?")
+            .bind(id)
+            .execute(&self.m_pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.changed(&[Entity::Products]);
+        Ok(self.get_product(id).await?)
+    }
+
+    /// Zruší akcie, ktoré skončili pred daným dňom (vynuluje akciovú cenu a koniec akcie).
+    ///
+    /// Každý produkt so zrušenou akciou sa vypíše.
+    ///
+    /// # Arguments
+    /// * `today` – prvý deň, v ktorom už akcia neplatí, ak skončila deň predtým
+    ///
+    /// # Returns
+    /// Počet produktov so zrušenou akciou
+    #[instrument(skip_all, fields(db.operation.name = "expire_sales", db.rows = Empty))]
+    pub async fn expire_sales(&self, today: NaiveDate) -> Result<u64> {
+        let rows = sqlx::query(
+            r#"
+            UPDATE products SET sale_price = NULL, sale_ends = NULL
+            WHERE sale_ends IS NOT NULL AND date(sale_ends) < date(?)
+            RETURNING id, name
+            "#,
+        )
+            .bind(today)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        for row in &rows {
+            println!("Akcia skončila: produkt {} ({})", row.get::<i64, _>("id"), row.get::<String, _>("name"));
+        }
+        if !rows.is_empty() {
+            self.changed(&[Entity::Products]);
+        }
+        Ok(rows.len() as u64)
+    }
+
+    /// Naplánuje zmenu predajnej ceny produktu od daného dňa.
+    ///
+    /// Cenu zmení až `apply_due_price_changes` v deň `effective_date` alebo neskôr.
+    ///
+    /// # Arguments
+    /// * `id` – ID produktu
+    /// * `new_price` – nová predajná cena
+    /// * `effective_date` – prvý deň platnosti novej ceny (dnes alebo neskôr)
+    ///
+    /// # Returns
+    /// Naplánovaná zmena
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` pri neznámom produkte, `StoreError::Invalid` ak cena
+    /// nie je kladná alebo deň platnosti je v minulosti
+    #[instrument(skip_all, fields(db.operation.name = "schedule_price_change", db.rows = Empty))]
+    pub async fn schedule_price_change(
+        &self,
+        id: u32,
+        new_price: f64,
+        effective_date: NaiveDate,
+    ) -> Result<ScheduledPriceChange> {
+        if !new_price.is_finite() || new_price <= 0.0 {
+            return Err(StoreError::Invalid(format!("nová cena {new_price} musí byť kladná")).into());
+        }
+        if effective_date < Local::now().date_naive() {
+            return Err(StoreError::Invalid(format!("zmena ceny nemôže platiť od minulosti ({effective_date})")).into());
+        }
+        let mut tx = self.m_pool.begin().await?;
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Err(StoreError::NotFound(format!("produkt {id}")).into());
+        }
+        let row = sqlx::query(
+            r#"
+            INSERT INTO scheduled_price_changes (product_id, new_price, effective_date, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING *
+            "#,
+        )
+            .bind(id)
+            .bind(new_price)
+            .bind(effective_date)
+            .bind(Local::now().naive_local())
+            .fetch_one(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Self::record_rows(1);
+        Ok(Self::price_change_from_row(&row))
+    }
+
+    /// Uplatní naplánované zmeny cien, ktorých deň platnosti nastal.
+    ///
+    /// Zmeny sa uplatnia v poradí dňa platnosti (pri viacerých zmenách produktu
+    /// platí posledná); každej sa uloží pôvodná cena a čas uplatnenia.
+    /// Každý produkt so zmenenou cenou sa vypíše.
+    ///
+    /// # Arguments
+    /// * `today` – dnešný deň; uplatnia sa zmeny platné od neho a skôr
+    ///
+    /// # Returns
+    /// Uplatnené zmeny
+    #[instrument(skip_all, fields(db.operation.name = "apply_due_price_changes", db.rows = Empty))]
+    pub async fn apply_due_price_changes(&self, today: NaiveDate) -> Result<Vec<ScheduledPriceChange>> {
+        let now = Local::now().naive_local();
+        let mut tx = self.m_pool.begin().await?;
+        let due: Vec<i64> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM scheduled_price_changes
+            WHERE applied_at IS NULL AND date(effective_date) <= date(?)
+            ORDER BY effective_date, id
+            "#,
+        )
+            .bind(today)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let mut applied = Vec::with_capacity(due.len());
+        for id in due {
+            let row = sqlx::query(
+                r#"
+                UPDATE scheduled_price_changes
+                SET applied_at = ?,
+                    old_price = (SELECT sell_price FROM products WHERE products.id = scheduled_price_changes.product_id)
+                WHERE id = ?
+                RETURNING *
+                "#,
+            )
+                .bind(now)
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?;
+            let change = Self::price_change_from_row(&row);
+            sqlx::query("UPDATE products SET sell_price = ? WHERE id = ?")
+                .bind(change.new_price)
+                .bind(change.product_id)
+                .execute(&mut *tx)
+                .await?;
+            applied.push(change);
+        }
+        tx.commit().await?;
+
+        Self::record_rows(applied.len());
+        for change in &applied {
+            println!(
+                "Zmena ceny: produkt {} z {} na {}",
+                change.product_id,
+                change.old_price.map_or_else(|| "-".to_string(), |p| p.to_string()),
+                change.new_price
+            );
+        }
+        if !applied.is_empty() {
+            self.changed(&[Entity::Products]);
+        }
+        Ok(applied)
+    }
+
+    /// Vráti naplánované a uplatnené zmeny cien.
+    ///
+    /// # Arguments
+    /// * `product_id` – len zmeny produktu
+    /// * `pending` – `Some(true)` len čakajúce, `Some(false)` len uplatnené
+    ///
+    /// # Returns
+    /// Zmeny zoradené podľa dňa platnosti
+    #[instrument(skip_all, fields(db.operation.name = "scheduled_price_changes", db.rows = Empty))]
+    pub async fn scheduled_price_changes(
+        &self,
+        product_id: Option<u32>,
+        pending: Option<bool>,
+    ) -> Result<Vec<ScheduledPriceChange>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM scheduled_price_changes
+            WHERE (?1 IS NULL OR product_id = ?1)
+              AND (?2 IS NULL OR (applied_at IS NULL) = ?2)
+            ORDER BY effective_date, id
+            "#,
+        )
+            .bind(product_id)
+            .bind(pending)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::price_change_from_row).collect())
+    }
+
+    fn price_change_from_row(r: &SqliteRow) -> ScheduledPriceChange {
+        ScheduledPriceChange {
+            id: r.get::<i64, _>("id") as u32,
+            product_id: r.get::<i64, _>("product_id") as u32,
+            new_price: r.get("new_price"),
+            effective_date: r.get("effective_date"),
+            created_at: r.get("created_at"),
+            applied_at: r.get("applied_at"),
+            old_price: r.get("old_price"),
+        }
+    }
+
+    /// Vráti produkty s akciou platnou v daný deň.
+    ///
+    /// # Arguments
+    /// * `today` – deň, ku ktorému sa platnosť akcie posudzuje
+    ///
+    /// # Returns
+    /// Produkty zoradené podľa konca akcie
+    #[instrument(skip_all, fields(db.operation.name = "products_on_sale", db.rows = Empty))]
+    pub async fn products_on_sale(&self, today: NaiveDate) -> Result<Vec<Product>> {
+        let rows = sqlx::query(&format!(
+            "{PRODUCT_SELECT} WHERE sale_price IS NOT NULL AND date(sale_ends) >= date(?) ORDER BY sale_ends, id"
+        ))
+            .bind(today)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let mut product = Self::product_from_row(r);
+                product.effective_price = product.price_on(today);
+                product
+            })
+            .collect())
+    }
+
+    /// Vráti aktívne produkty s nízkym stavom zásob.
+    ///
+    /// # Arguments
+    /// * `threshold` – hranica nízkeho stavu zásob (vrátane) pre produkty bez vlastnej `min_stock`
+    ///
+    /// # Returns
+    /// Produkty zoradené od najnižšieho množstva
+    #[instrument(skip_all, fields(db.operation.name = "low_stock", db.rows = Empty))]
+    pub async fn low_stock(&self, threshold: u32) -> Result<LowStockReport> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, category, supplier, quantity, unit, min_stock
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0 AND quantity <= COALESCE(min_stock, ?)
+            ORDER BY quantity, name, id
+            "#,
+        )
+            .bind(threshold)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let lines: Vec<LowStockLine> = rows
+            .iter()
+            .map(|r| LowStockLine {
+                product_id: r.get::<i64, _>("id") as u32,
+                name: r.get("name"),
+                category: r.get("category"),
+                supplier: r.get("supplier"),
+                quantity: Self::quantity_of(r, "quantity").max(0.0),
+                unit: Self::unit_of(r),
+                min_stock: r.get("min_stock"),
+            })
+            .collect();
+
+        Ok(LowStockReport {
+            threshold,
+            out_of_stock: lines.iter().filter(|l| l.quantity <= QUANTITY_EPSILON).count() as u32,
+            lines,
+        })
+    }
+
+    /// Vráti aktívne produkty na sklade, ktoré sa za posledných `days` dní nepredali
+    /// ani nenaskladnili.
+    ///
+    /// Pohybom je predaj v objednávke alebo kladný pohyb skladu (príjem, oprava);
+    /// produkt bez nich sa posudzuje podľa `date_added`, a ak nie je vyplnený,
+    /// podľa poslednej zmeny záznamu (`updated_at`). Produkty pridané počas
+    /// obdobia sa vynechajú, aby nové položky neboli označené predčasne. Sady sa
+    /// vynechajú, ich zásoby tvoria komponenty.
+    ///
+    /// # Arguments
+    /// * `days` – dĺžka sledovaného obdobia v dňoch
+    ///
+    /// # Returns
+    /// Produkty zoradené od najvyššej viazanej hodnoty
+    #[instrument(skip_all, fields(db.operation.name = "dead_stock", db.rows = Empty))]
+    pub async fn dead_stock(&self, days: u32) -> Result<DeadStockReport> {
+        let rows = sqlx::query(
+            r#"
+            WITH activity AS (
+                SELECT i.product_id, o.created_at AS at
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                UNION ALL
+                SELECT product_id, created_at FROM stock_movements WHERE quantity > 0 AND reason NOT IN ('return', 'stocktake')
+            )
+            SELECT p.id, p.name, p.category, p.quantity, p.unit, p.cost_price, p.employee_id,
+                   COALESCE(p.date_added, date(p.updated_at)) AS date_added,
+                   e.name || ' ' || e.surname AS employee_name,
+                   (SELECT MAX(date(a.at)) FROM activity a WHERE a.product_id = p.id) AS last_activity
+            FROM products p
+            LEFT JOIN employees e ON e.id = p.employee_id
+            WHERE COALESCE(p.status, 1) = 1 AND p.is_bundle = 0 AND p.quantity > ?
+            "#,
+        )
+            .bind(QUANTITY_EPSILON)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let today = Local::now().date_naive();
+        let mut lines: Vec<DeadStockLine> = rows
+            .iter()
+            .filter_map(|r| {
+                let date_added: Option<NaiveDate> = r.get("date_added");
+                let last_activity: Option<NaiveDate> = r.get("last_activity");
+                let recent = |date: Option<NaiveDate>| date.is_some_and(|d| (today - d).num_days() < i64::from(days));
+                if recent(date_added) || recent(last_activity) {
+                    return None;
+                }
+                let last_movement = last_activity.or(date_added);
+                let quantity = Self::quantity_of(r, "quantity");
+                Some(DeadStockLine {
+                    product_id: r.get::<i64, _>("id") as u32,
+                    name: r.get("name"),
+                    category: r.get("category"),
+                    quantity,
+                    unit: Self::unit_of(r),
+                    value: quantity * r.get::<f64, _>("cost_price"),
+                    last_movement,
+                    days_since_movement: last_movement.map(|d| (today - d).num_days()),
+                    employee_id: r.get::<Option<i64>, _>("employee_id").map(|id| id as u32),
+                    employee_name: r.get("employee_name"),
+                })
+            })
+            .collect();
+        lines.sort_by(|a, b| b.value.total_cmp(&a.value).then(a.product_id.cmp(&b.product_id)));
+
+        Ok(DeadStockReport { days, total_value: lines.iter().map(|l| l.value).sum(), lines })
+    }
+
+    /// Vráti aktívne produkty, ktorých aktuálne ceny nespĺňajú minimálnu maržu.
+    ///
+    /// Zachytí produkty, ktorým maržu znížilo zdraženie nákupu (to sa neblokuje).
+    ///
+    /// # Arguments
+    /// * `policy` – politika minimálnej marže
+    ///
+    /// # Returns
+    /// Produkty zoradené od najnižšej percentuálnej marže
+    #[instrument(skip_all, fields(db.operation.name = "margin_violations", db.rows = Empty))]
+    pub async fn margin_violations(&self, policy: &MarginPolicy) -> Result<Vec<MarginViolation>> {
+        if policy.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, category, cost_price, sell_price
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND cost_price IS NOT NULL AND sell_price IS NOT NULL
+            ORDER BY id
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let mut violations: Vec<MarginViolation> = rows
+            .iter()
+            .filter_map(|r| {
+                let category: Option<String> = r.get("category");
+                let (cost_price, sell_price): (f64, f64) = (r.get("cost_price"), r.get("sell_price"));
+                let shortfall = policy.check(category.as_deref(), Some(cost_price), sell_price)?;
+                Some(MarginViolation {
+                    product_id: r.get::<i64, _>("id") as u32,
+                    name: r.get("name"),
+                    category,
+                    cost_price,
+                    sell_price,
+                    margin: shortfall.margin,
+                    margin_percent: shortfall.margin_percent,
+                    threshold: shortfall.rule.to_string(),
+                })
+            })
+            .collect();
+        violations.sort_by(|a, b| a.margin_percent.total_cmp(&b.margin_percent));
+        Self::record_rows(violations.len());
+        Ok(violations)
+    }
+
+    /// Nájde aktívne produkty s podozrivou predajnou cenou.
+    ///
+    /// Podozrivá je cena vyššia ako `MAX_PRICE_MARKUP`-násobok alebo nižšia ako
+    /// `MIN_PRICE_MARKUP`-násobok nákupnej ceny a cena, ktorá sa od ostatných
+    /// produktov kategórie líši o viac ako `PRICE_OUTLIER_Z` smerodajných
+    /// odchýlok (`pricing::outlier_scores`). Produkty bez kategórie sa porovnávajú
+    /// len s nákupnou cenou.
+    ///
+    /// # Returns
+    /// Podozrivé produkty s dôvodmi, zoradené podľa ID
+    #[instrument(skip_all, fields(db.operation.name = "price_anomalies", db.rows = Empty))]
+    pub async fn price_anomalies(&self) -> Result<Vec<PriceAnomaly>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, NULLIF(TRIM(category), '') AS category, cost_price, sell_price
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND cost_price IS NOT NULL AND sell_price IS NOT NULL
+            ORDER BY id
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let mut by_category: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, r) in rows.iter().enumerate() {
+            if let Some(category) = r.get::<Option<String>, _>("category") {
+                by_category.entry(category).or_default().push(i);
+            }
+        }
+        let mut z_scores = vec![None; rows.len()];
+        for indices in by_category.values() {
+            let prices: Vec<f64> = indices.iter().map(|&i| rows[i].get("sell_price")).collect();
+            for (&i, score) in indices.iter().zip(pricing::outlier_scores(&prices)) {
+                z_scores[i] = score;
+            }
+        }
+
+        let anomalies: Vec<PriceAnomaly> = rows
+            .iter()
+            .zip(z_scores)
+            .filter_map(|(r, z_score)| {
+                let (cost_price, sell_price): (f64, f64) = (r.get("cost_price"), r.get("sell_price"));
+                let markup = (cost_price > 0.0).then(|| sell_price / cost_price);
+                let mut reasons = Vec::new();
+                match markup {
+                    Some(m) if m > MAX_PRICE_MARKUP => reasons.push(PriceAnomalyReason::MarkupTooHigh),
+                    Some(m) if m < MIN_PRICE_MARKUP => reasons.push(PriceAnomalyReason::MarkupTooLow),
+                    _ => {}
+                }
+                if z_score.is_some_and(|z: f64| z.abs() > PRICE_OUTLIER_Z) {
+                    reasons.push(PriceAnomalyReason::CategoryOutlier);
+                }
+                (!reasons.is_empty()).then(|| PriceAnomaly {
+                    product_id: r.get::<i64, _>("id") as u32,
+                    name: r.get("name"),
+                    category: r.get("category"),
+                    cost_price,
+                    sell_price,
+                    markup,
+                    z_score,
+                    reasons,
+                })
+            })
+            .collect();
+        Self::record_rows(anomalies.len());
+        Ok(anomalies)
+    }
+
+    // ==========================
+    // Audit log
+    // ==========================
+
+    /// Zapíše udalosť do audit logu.
+    ///
+    /// # Arguments
+    /// * `action` – názov akcie
+    /// * `details` – voliteľné podrobnosti
+    /// * `actor` – kto akciu vykonal (subjekt JWT; `None` pri API kľúči)
+    pub async fn record_audit(&self, action: &str, details: Option<String>, actor: Option<&str>) -> Result<()> {
+        self.record_entity_audit(action, None, details, actor).await
+    }
+
+    /// Zapíše do audit logu udalosť týkajúcu sa jedného záznamu.
+    ///
+    /// # Arguments
+    /// * `action` – názov akcie
+    /// * `entity` – druh a ID dotknutého záznamu (napr. `("product", 5)`)
+    /// * `details` – voliteľné podrobnosti (`kľúč=hodnota, ...`)
+    /// * `actor` – kto akciu vykonal (subjekt JWT; `None` pri API kľúči)
+    #[instrument(skip_all, fields(db.operation.name = "record_audit", db.rows = Empty))]
+    pub async fn record_entity_audit(
+        &self,
+        action: &str,
+        entity: Option<(&str, u32)>,
+        details: Option<String>,
+        actor: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO audit_log (action, entity_type, entity_id, details, actor, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+            .bind(action)
+            .bind(entity.map(|(entity_type, _)| entity_type))
+            .bind(entity.map(|(_, id)| id))
+            .bind(details)
+            .bind(actor)
+            .bind(Local::now().naive_local())
+            .execute(&self.m_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Vráti záznamy audit logu od najnovšieho.
+    ///
+    /// # Returns
+    /// Zoznam záznamov
+    #[instrument(skip_all, fields(db.operation.name = "get_audit_log", db.rows = Empty))]
+    pub async fn get_audit_log(&self) -> Result<Vec<AuditEntry>> {
+        let rows = sqlx::query("SELECT * FROM audit_log ORDER BY id DESC")
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::audit_entry_from_row).collect())
+    }
+
+    /// Vráti stránku záznamov audit logu vyhovujúcich filtru, od najstaršieho.
+    ///
+    /// # Arguments
+    /// * `filter` – obdobie, druh záznamu a akcia (nezadané sa neobmedzujú)
+    /// * `limit` – najviac toľko záznamov
+    /// * `after_id` – len záznamy s vyšším ID (pokračovanie po predchádzajúcej stránke)
+    ///
+    /// # Returns
+    /// Záznamy zoradené podľa ID
+    #[instrument(skip_all, fields(db.operation.name = "get_audit_page", db.rows = Empty))]
+    pub async fn get_audit_page(&self, filter: &AuditFilter, limit: u32, after_id: u32) -> Result<Vec<AuditEntry>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM audit_log
+            WHERE id > ?1
+                AND (?2 IS NULL OR date(created_at) >= ?2)
+                AND (?3 IS NULL OR date(created_at) <= ?3)
+                AND (?4 IS NULL OR entity_type = ?4)
+                AND (?5 IS NULL OR action = ?5)
+            ORDER BY id
+            LIMIT ?6
+            "#,
+        )
+            .bind(after_id)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(filter.entity.as_deref())
+            .bind(filter.action.as_deref())
+            .bind(limit)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::audit_entry_from_row).collect())
+    }
+
+    /// Prevedie riadok z tabuľky `audit_log` na `AuditEntry`.
+    fn audit_entry_from_row(r: &SqliteRow) -> AuditEntry {
+        AuditEntry {
+            id: Some(r.get::<i64, _>("id") as u32),
+            action: r.get("action"),
+            entity_type: r.get("entity_type"),
+            entity_id: r.get::<Option<i64>, _>("entity_id").map(|id| id as u32),
+            details: r.get("details"),
+            actor: r.get("actor"),
+            created_at: r.get("created_at"),
+        }
+    }
+
+    // ==========================
+    // Category limits
+    // ==========================
+
+    /// Nastaví alebo zruší maximálne množstvo tovaru v kategórii.
+    ///
+    /// Limit nižší ako aktuálny stav sa uloží; ďalšie naskladnenie v kategórii
+    /// sa potom odmietne, kým stav neklesne pod limit.
+    ///
+    /// # Arguments
+    /// * `category` – názov kategórie
+    /// * `max_total_quantity` – najväčší súčet množstiev produktov kategórie (`None` = bez limitu)
+    ///
+    /// # Returns
+    /// Limit a aktuálny stav kategórie
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnej kategórii alebo zápornom limite
+    #[instrument(skip_all, fields(db.operation.name = "set_category_limit", db.rows = Empty))]
+    pub async fn set_category_limit(&self, category: &str, max_total_quantity: Option<f64>) -> Result<CategoryLimit> {
+        if category.trim().is_empty() {
+            return Err(StoreError::Invalid("kategória nesmie byť prázdna".into()).into());
+        }
+        if max_total_quantity.is_some_and(|max| !max.is_finite() || max < 0.0) {
+            return Err(StoreError::Invalid("limit kategórie musí byť nezáporné číslo".into()).into());
+        }
+
+        let mut tx = self.m_pool.begin().await?;
+        match max_total_quantity {
+            Some(max) => {
+                sqlx::query(
+                    r#"
+                    INSERT INTO category_limits (category, max_total_quantity) VALUES (?, ?)
+                    ON CONFLICT (category) DO UPDATE SET max_total_quantity = excluded.max_total_quantity
+                    "#,
+                )
+                    .bind(category)
+                    .bind(max)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            None => {
+                sqlx::query("DELETE FROM category_limits WHERE category = ?")
+                    .bind(category)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+        let limit = Self::category_limit_of(&mut tx, category).await?;
+        tx.commit().await?;
+        Ok(limit)
+    }
+
+    /// Vráti limit a aktuálny stav kategórie.
+    ///
+    /// # Arguments
+    /// * `category` – názov kategórie
+    ///
+    /// # Returns
+    /// Limit (`None` = bez limitu) a súčet množstiev produktov kategórie
+    #[instrument(skip_all, fields(db.operation.name = "get_category_limit", db.rows = Empty))]
+    pub async fn get_category_limit(&self, category: &str) -> Result<CategoryLimit> {
+        let mut tx = self.m_pool.begin().await?;
+        let limit = Self::category_limit_of(&mut tx, category).await?;
+        tx.commit().await?;
+        Ok(limit)
+    }
+
+    /// Načíta limit a súčet množstiev kategórie (sady sa nepočítajú, nemajú vlastné zásoby).
+    async fn category_limit_of(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, category: &str) -> Result<CategoryLimit> {
+        let max_total_quantity: Option<f64> =
+            sqlx::query_scalar("SELECT max_total_quantity FROM category_limits WHERE category = ?")
+                .bind(category)
+                .fetch_optional(&mut **tx)
+                .await?;
+        let total_quantity: f64 = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(quantity), 0.0) FROM products WHERE category = ? AND is_bundle = 0",
+        )
+            .bind(category)
+            .fetch_one(&mut **tx)
+            .await?;
+        Ok(CategoryLimit { category: category.to_string(), max_total_quantity, total_quantity })
+    }
+
+    /// Overí, že po zmene zásob produktu nie je prekročený limit jeho kategórie.
+    ///
+    /// Volá sa v transakcii až po zápise nového stavu: zápis drží zámok
+    /// databázy, takže súbežné naskladnenie do tej istej kategórie nemôže
+    /// limit obísť, a pri chybe sa zahodí celá transakcia.
+    ///
+    /// # Arguments
+    /// * `tx` – otvorená transakcia so zapísanou zmenou
+    /// * `product_id` – ID produktu, ktorého zásoby pribudli
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak súčet množstiev kategórie prekročí limit
+    async fn check_category_capacity(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, product_id: u32) -> Result<()> {
+        let category: Option<String> = sqlx::query_scalar("SELECT category FROM products WHERE id = ?")
+            .bind(product_id)
+            .fetch_optional(&mut **tx)
+            .await?
+            .flatten();
+        let Some(category) = category else { return Ok(()) };
+
+        let limit = Self::category_limit_of(tx, &category).await?;
+        match limit.max_total_quantity {
+            Some(max) if limit.total_quantity > max + QUANTITY_EPSILON => Err(StoreError::Conflict(format!(
+                "kategória {category} by prekročila limit {max} (stav po zmene {})",
+                limit.total_quantity
+            )).into()),
+            _ => Ok(()),
+        }
+    }
+
+    // ==========================
+    // Saved searches
+    // ==========================
+
+    /// Uloží pomenovaný filter.
+    ///
+    /// Filter sa neoveruje, to je úloha volajúceho (schéma filtra sa môže
+    /// v ďalšej verzii zmeniť, uložený text sa preto ponechá, ako prišiel).
+    ///
+    /// # Arguments
+    /// * `search` – názov, druh záznamov a filter (`id`, `created_at` a `stale` sa ignorujú)
+    /// * `owner` – subjekt, ktorý vyhľadávanie ukladá
+    ///
+    /// # Returns
+    /// Uložené vyhľadávanie
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnom názve, `StoreError::Conflict` ak vyhľadávanie
+    /// s rovnakým názvom a druhom už existuje
+    #[instrument(skip_all, fields(db.operation.name = "add_saved_search", db.rows = Empty))]
+    pub async fn add_saved_search(&self, search: &SavedSearch, owner: Option<&str>) -> Result<SavedSearch> {
+        let name = search.name.trim();
+        if name.is_empty() {
+            return Err(StoreError::Invalid("názov vyhľadávania nesmie byť prázdny".into()).into());
+        }
+        let created_at = Local::now().naive_local();
+        let id = sqlx::query(
+            "INSERT INTO saved_searches (name, entity, filter, owner, created_at) VALUES (?, ?, ?, ?, ?)",
+        )
+            .bind(name)
+            .bind(search.entity.as_str())
+            .bind(search.filter.to_string())
+            .bind(owner)
+            .bind(created_at)
+            .execute(&self.m_pool)
+            .await
+            .map_err(|e| -> anyhow::Error {
+                if e.as_database_error().is_some_and(|d| d.is_unique_violation()) {
+                    StoreError::Conflict(format!("vyhľadávanie {name} už existuje")).into()
+                } else {
+                    e.into()
+                }
+            })?
+            .last_insert_rowid();
+        Ok(SavedSearch {
+            id: Some(id as u32),
+            name: name.to_string(),
+            entity: search.entity,
+            filter: search.filter.clone(),
+            owner: owner.map(str::to_string),
+            created_at: Some(created_at),
+            stale: None,
+        })
+    }
+
+    /// Vráti uložené vyhľadávania zoradené podľa druhu a názvu.
+    ///
+    /// # Arguments
+    /// * `entity` – len vyhľadávania tohto druhu (`None` = všetky)
+    ///
+    /// # Returns
+    /// Zoznam vyhľadávaní
+    #[instrument(skip_all, fields(db.operation.name = "list_saved_searches", db.rows = Empty))]
+    pub async fn list_saved_searches(&self, entity: Option<SearchEntity>) -> Result<Vec<SavedSearch>> {
+        let rows = sqlx::query("SELECT * FROM saved_searches WHERE ?1 IS NULL OR entity = ?1 ORDER BY entity, name")
+            .bind(entity.map(|e| e.as_str()))
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        rows.iter().map(Self::saved_search_from_row).collect()
+    }
+
+    /// Vráti uložené vyhľadávanie podľa druhu a názvu.
+    ///
+    /// # Returns
+    /// Vyhľadávanie alebo `None`, ak neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "get_saved_search", db.rows = Empty))]
+    pub async fn get_saved_search(&self, entity: SearchEntity, name: &str) -> Result<Option<SavedSearch>> {
+        let row = sqlx::query("SELECT * FROM saved_searches WHERE entity = ? AND name = ?")
+            .bind(entity.as_str())
+            .bind(name)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        row.as_ref().map(Self::saved_search_from_row).transpose()
+    }
+
+    /// Odstráni uložené vyhľadávanie.
+    ///
+    /// # Returns
+    /// `true` ak vyhľadávanie existovalo
+    #[instrument(skip_all, fields(db.operation.name = "delete_saved_search", db.rows = Empty))]
+    pub async fn delete_saved_search(&self, id: u32) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM saved_searches WHERE id = ?")
+            .bind(id)
+            .execute(&self.m_pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn saved_search_from_row(row: &SqliteRow) -> Result<SavedSearch> {
+        let entity: String = row.get("entity");
+        Ok(SavedSearch {
+            id: Some(row.get::<i64, _>("id") as u32),
+            name: row.get("name"),
+            entity: SearchEntity::parse(&entity)
+                .ok_or_else(|| anyhow::anyhow!("neznámy druh vyhľadávania {entity:?}"))?,
+            filter: serde_json::from_str(row.get("filter"))?,
+            owner: row.get("owner"),
+            created_at: row.get("created_at"),
+            stale: None,
+        })
+    }
+
+    // ==========================
+    // Meta
+    // ==========================
+
+    /// Vráti rôzne značky produktov (našepkávač).
+    ///
+    /// # Arguments
+    /// * `prefix` – začiatok hodnoty (bez rozlíšenia veľkosti písmen ASCII), `None` = všetky
+    /// * `limit` – maximálny počet hodnôt
+    ///
+    /// # Returns
+    /// Abecedne zoradené neprázdne značky
+    #[instrument(skip_all, fields(db.operation.name = "distinct_brands", db.rows = Empty))]
+    pub async fn distinct_brands(&self, prefix: Option<&str>, limit: u32) -> Result<Vec<String>> {
+        self.distinct_values("brand", prefix, limit).await
+    }
+
+    /// Vráti rôzne kategórie produktov (našepkávač).
+    ///
+    /// # Arguments
+    /// * `prefix` – začiatok hodnoty (bez rozlíšenia veľkosti písmen ASCII), `None` = všetky
+    /// * `limit` – maximálny počet hodnôt
+    ///
+    /// # Returns
+    /// Abecedne zoradené neprázdne kategórie
+    #[instrument(skip_all, fields(db.operation.name = "distinct_categories", db.rows = Empty))]
+    pub async fn distinct_categories(&self, prefix: Option<&str>, limit: u32) -> Result<Vec<String>> {
+        self.distinct_values("category", prefix, limit).await
+    }
+
+    /// Vráti rôznych dodávateľov produktov (našepkávač).
+    ///
+    /// # Arguments
+    /// * `prefix` – začiatok hodnoty (bez rozlíšenia veľkosti písmen ASCII), `None` = všetky
+    /// * `limit` – maximálny počet hodnôt
+    ///
+    /// # Returns
+    /// Abecedne zoradení neprázdni dodávatelia
+    #[instrument(skip_all, fields(db.operation.name = "distinct_suppliers", db.rows = Empty))]
+    pub async fn distinct_suppliers(&self, prefix: Option<&str>, limit: u32) -> Result<Vec<String>> {
+        self.distinct_values("supplier", prefix, limit).await
+    }
+
+    /// Rôzne neprázdne hodnoty stĺpca produktov začínajúce na `prefix`.
+    ///
+    /// Znaky `%` a `_` v prefixe sa hľadajú doslovne.
+    async fn distinct_values(&self, column: &str, prefix: Option<&str>, limit: u32) -> Result<Vec<String>> {
+        let pattern = prefix.map(|p| format!("{}%", Self::escape_like(p)));
+        let rows = sqlx::query(&format!(
+            r#"
+            SELECT DISTINCT {column} AS value
+            FROM products
+            WHERE COALESCE({column}, '') <> '' AND (? IS NULL OR {column} LIKE ? ESCAPE '\')
+            ORDER BY {column}
+            LIMIT ?
+            "#
+        ))
+            .bind(&pattern)
+            .bind(&pattern)
+            .bind(limit)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(|r| r.get("value")).collect())
+    }
+
+    // ==========================
+    // Reports
+    // ==========================
+
+    /// Zostaví dennú uzávierku (Z-report) z predajov a vrátení za daný deň.
+    ///
+    /// # Arguments
+    /// * `date` – deň uzávierky
+    ///
+    /// # Returns
+    /// Súhrn dňa; ak nebol žiadny predaj, `no_sales` je `true` a súčty sú nulové
+    #[instrument(skip_all, fields(db.operation.name = "daily_report", db.rows = Empty))]
+    pub async fn daily_report(&self, date: NaiveDate) -> Result<DailyReport> {
+        let sales = sqlx::query(
+            r#"
+            SELECT
+                COUNT(DISTINCT o.id) AS orders,
+                COALESCE(SUM(i.quantity), 0) AS units,
+                COALESCE(SUM(i.quantity * i.unit_price), 0.0) AS revenue
+            FROM orders o
+            JOIN order_items i ON i.order_id = o.id
+            WHERE date(o.created_at) = date(?)
+            "#,
+        )
+            .bind(date)
+            .fetch_one(&self.m_pool)
+            .await?;
+
+        let returns = sqlx::query(
+            r#"
+            SELECT
+                COUNT(DISTINCT r.id) AS returns,
+                COALESCE(SUM(i.quantity), 0) AS units,
+                COALESCE(SUM(i.quantity * i.unit_price), 0.0) AS amount
+            FROM order_returns r
+            JOIN return_items i ON i.return_id = r.id
+            WHERE date(r.created_at) = date(?)
+            "#,
+        )
+            .bind(date)
+            .fetch_one(&self.m_pool)
+            .await?;
+
+        let revenue_per_employee = sqlx::query(
+            r#"
+            SELECT
+                o.employee_id,
+                COUNT(DISTINCT o.id) AS orders,
+                SUM(i.quantity * i.unit_price) AS revenue
+            FROM orders o
+            JOIN order_items i ON i.order_id = o.id
+            WHERE date(o.created_at) = date(?)
+            GROUP BY o.employee_id
+            ORDER BY revenue DESC
+            "#,
+        )
+            .bind(date)
+            .fetch_all(&self.m_pool)
+            .await?
+            .iter()
+            .map(|r| EmployeeRevenue {
+                employee_id: r.get::<Option<i64>, _>("employee_id").map(|v| v as u32),
+                orders: r.get::<i64, _>("orders") as u32,
+                revenue: r.get("revenue"),
+            })
+            .collect();
+
+        let top_products = sqlx::query(
+            r#"
+            SELECT
+                i.product_id,
+                p.name,
+                p.unit,
+                SUM(i.quantity) AS quantity,
+                SUM(i.quantity * i.unit_price) AS revenue
+            FROM orders o
+            JOIN order_items i ON i.order_id = o.id
+            LEFT JOIN products p ON p.id = i.product_id
+            WHERE date(o.created_at) = date(?)
+            GROUP BY i.product_id
+            ORDER BY quantity DESC, revenue DESC
+            LIMIT 10
+            "#,
+        )
+            .bind(date)
+            .fetch_all(&self.m_pool)
+            .await?
+            .iter()
+            .map(|r| ProductSales {
+                product_id: r.get::<i64, _>("product_id") as u32,
+                name: r.get("name"),
+                quantity: Self::quantity_of(r, "quantity"),
+                unit: Self::unit_of(r),
+                revenue: r.get("revenue"),
+            })
+            .collect();
+
+        let closed = sqlx::query("SELECT date FROM closed_days WHERE date = ?")
+            .bind(date)
+            .fetch_optional(&self.m_pool)
+            .await?
+            .is_some();
+
+        let orders = sales.get::<i64, _>("orders") as u32;
+        let gross_revenue: f64 = sales.get("revenue");
+        let returned_amount: f64 = returns.get("amount");
+
+        Ok(DailyReport {
+            date,
+            closed,
+            no_sales: orders == 0,
+            orders,
+            units_sold: Self::quantity_of(&sales, "units"),
+            gross_revenue,
+            returns: returns.get::<i64, _>("returns") as u32,
+            units_returned: Self::quantity_of(&returns, "units"),
+            returned_amount,
+            net_revenue: gross_revenue - returned_amount,
+            revenue_per_employee,
+            top_products,
+        })
+    }
+
+    /// Spočíta zľavy poskytnuté pri predajoch za obdobie s rozpadom podľa kategórií.
+    ///
+    /// Zľava položky je `(bežná cena − účtovaná cena) × množstvo`; bežná cena je
+    /// predajná cena produktu v čase predaja. Predaj za plnú (alebo vyššiu) cenu
+    /// a položky bez uloženej bežnej ceny (staršie a synchronizované objednávky)
+    /// majú zľavu nula. Vrátenia sa neodpočítavajú.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Súčet zliav a rozpad podľa kategórií (od najvyššej zľavy)
+    #[instrument(skip_all, fields(db.operation.name = "discount_given", db.rows = Empty))]
+    pub async fn discount_given(&self, from: NaiveDate, to: NaiveDate) -> Result<DiscountReport> {
+        let rows = sqlx::query(
+            r#"
+            WITH lines AS (
+                SELECT
+                    i.product_id,
+                    i.quantity AS qty,
+                    i.quantity * i.unit_price AS revenue,
+                    MAX(COALESCE(i.regular_price, i.unit_price) - i.unit_price, 0) * i.quantity AS discount
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                WHERE date(o.created_at) BETWEEN date(?) AND date(?)
+            )
+            SELECT
+                COALESCE(p.category, 'Neznáma') AS category,
+                SUM(l.qty) AS units,
+                SUM(CASE WHEN l.discount > 0 THEN l.qty ELSE 0 END) AS discounted_units,
+                SUM(l.revenue) AS revenue,
+                SUM(l.discount) AS discount
+            FROM lines l
+            LEFT JOIN products p ON p.id = l.product_id
+            GROUP BY 1
+            ORDER BY discount DESC, category
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let categories: Vec<CategoryDiscount> = rows
+            .iter()
+            .map(|r| CategoryDiscount {
+                category: r.get("category"),
+                units: Self::quantity_of(r, "units"),
+                discounted_units: Self::quantity_of(r, "discounted_units"),
+                revenue: r.get("revenue"),
+                discount: r.get("discount"),
+            })
+            .collect();
+
+        Ok(DiscountReport {
+            from,
+            to,
+            units: categories.iter().map(|c| c.units).sum(),
+            discounted_units: categories.iter().map(|c| c.discounted_units).sum(),
+            revenue: categories.iter().map(|c| c.revenue).sum(),
+            discount: categories.iter().map(|c| c.discount).sum(),
+            categories,
+        })
+    }
+
+    /// Spočíta priemernú veľkosť a hodnotu nákupu (objednávky) za obdobie.
+    ///
+    /// Započítajú sa objednávky s aspoň jednou položkou; vrátenia sa neodpočítavajú.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Priemery na objednávku; bez predajov nuly a `has_sales = false`
+    #[instrument(skip_all, fields(db.operation.name = "basket_stats", db.rows = Empty))]
+    pub async fn basket_stats(&self, from: NaiveDate, to: NaiveDate) -> Result<BasketStats> {
+        let row = sqlx::query(
+            r#"
+            WITH baskets AS (
+                SELECT COUNT(*) AS lines, SUM(i.quantity) AS units, SUM(i.quantity * i.unit_price) AS value
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                WHERE date(o.created_at) BETWEEN date(?) AND date(?)
+                GROUP BY o.id
+            )
+            SELECT
+                COUNT(*) AS baskets,
+                COALESCE(AVG(lines), 0.0) AS avg_lines,
+                COALESCE(AVG(units), 0.0) AS avg_units,
+                COALESCE(AVG(value), 0.0) AS avg_value,
+                COALESCE(SUM(value), 0.0) AS total_value
+            FROM baskets
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .fetch_one(&self.m_pool)
+            .await?;
+        let baskets = row.get::<i64, _>("baskets") as u64;
+        Self::record_rows(baskets as usize);
+
+        Ok(BasketStats {
+            from,
+            to,
+            has_sales: baskets > 0,
+            baskets,
+            avg_lines: row.get("avg_lines"),
+            avg_units: row.get("avg_units"),
+            avg_value: row.get("avg_value"),
+            total_value: row.get("total_value"),
+        })
+    }
+
+    /// Nájde produkty najčastejšie kupované spolu s produktom (analýza košíka).
+    ///
+    /// Pre každý iný produkt sa spočíta, v koľkých objednávkach sa vyskytol
+    /// spolu s `product_id`. Podpora je tento počet vydelený počtom všetkých
+    /// objednávok s položkami, spoľahlivosť počtom objednávok s `product_id`.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `limit` – najviac toľko produktov
+    ///
+    /// # Returns
+    /// Produkty podľa počtu spoločných košíkov zostupne; bez spoločných nákupov prázdny zoznam
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "frequently_bought_together", db.rows = Empty))]
+    pub async fn frequently_bought_together(&self, product_id: u32, limit: u32) -> Result<Vec<CoPurchase>> {
+        if self.get_product(product_id).await?.is_none() {
+            return Err(StoreError::NotFound(format!("produkt {product_id}")).into());
+        }
+        let rows = sqlx::query(
+            r#"
+            WITH baskets AS (
+                SELECT DISTINCT order_id, product_id FROM order_items
+            ),
+            own AS (
+                SELECT order_id FROM baskets WHERE product_id = ?1
+            )
+            SELECT
+                b.product_id,
+                p.name,
+                COUNT(*) AS together,
+                (SELECT COUNT(*) FROM own) AS own_baskets,
+                (SELECT COUNT(DISTINCT order_id) FROM baskets) AS all_baskets
+            FROM baskets b
+            JOIN own o ON o.order_id = b.order_id
+            JOIN products p ON p.id = b.product_id
+            WHERE b.product_id != ?1
+            GROUP BY b.product_id, p.name
+            ORDER BY together DESC, b.product_id
+            LIMIT ?2
+            "#,
+        )
+            .bind(product_id)
+            .bind(limit)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let together = r.get::<i64, _>("together") as u64;
+                CoPurchase {
+                    product_id: r.get::<i64, _>("product_id") as u32,
+                    name: r.get("name"),
+                    baskets: together,
+                    support: together as f64 / r.get::<i64, _>("all_baskets") as f64,
+                    confidence: together as f64 / r.get::<i64, _>("own_baskets") as f64,
+                }
+            })
+            .collect())
+    }
+
+    /// Zostaví správu o hrubom zisku za obdobie s rozpadom podľa kategórií.
+    ///
+    /// Tržba je súčet predajných cien z objednávok, náklad súčet aktuálnych
+    /// nákupných cien produktov. Vrátenia sa započítajú záporne.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Správa o zisku
+    #[instrument(skip_all, fields(db.operation.name = "profit_report", db.rows = Empty))]
+    pub async fn profit_report(&self, from: NaiveDate, to: NaiveDate) -> Result<ProfitReport> {
+        let rows = sqlx::query(
+            r#"
+            WITH lines AS (
+                SELECT i.product_id, i.quantity AS qty, i.unit_price
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                WHERE date(o.created_at) BETWEEN date(?) AND date(?)
+                UNION ALL
+                SELECT i.product_id, -i.quantity AS qty, i.unit_price
+                FROM return_items i
+                JOIN order_returns r ON r.id = i.return_id
+                WHERE date(r.created_at) BETWEEN date(?) AND date(?)
+            )
+            SELECT
+                COALESCE(p.category, 'Neznáma') AS category,
+                SUM(l.qty) AS units,
+                SUM(l.qty * l.unit_price) AS revenue,
+                SUM(CASE WHEN p.cost_price IS NOT NULL THEN l.qty * l.unit_price ELSE 0.0 END) AS costed_revenue,
+                SUM(CASE WHEN p.cost_price IS NOT NULL THEN l.qty * p.cost_price ELSE 0.0 END) AS cost,
+                SUM(CASE WHEN p.cost_price IS NULL THEN l.qty ELSE 0 END) AS uncosted_units
+            FROM lines l
+            LEFT JOIN products p ON p.id = l.product_id
+            GROUP BY 1
+            ORDER BY revenue DESC
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let categories: Vec<CategoryProfit> = rows
+            .iter()
+            .map(|r| {
+                let cost: f64 = r.get("cost");
+                CategoryProfit {
+                    category: r.get("category"),
+                    units: Self::quantity_of(r, "units"),
+                    revenue: r.get("revenue"),
+                    cost,
+                    gross_profit: r.get::<f64, _>("costed_revenue") - cost,
+                    uncosted_units: Self::quantity_of(r, "uncosted_units"),
+                }
+            })
+            .collect();
+
+        let revenue: f64 = categories.iter().map(|c| c.revenue).sum();
+        let gross_profit: f64 = categories.iter().map(|c| c.gross_profit).sum();
+
+        Ok(ProfitReport {
+            from,
+            to,
+            units: categories.iter().map(|c| c.units).sum(),
+            revenue,
+            cost: categories.iter().map(|c| c.cost).sum(),
+            gross_profit,
+            margin: (revenue != 0.0).then(|| gross_profit / revenue),
+            uncosted_units: categories.iter().map(|c| c.uncosted_units).sum(),
+            categories,
+        })
+    }
+
+    /// Spočíta hrubý zisk z predajov pripísaných jednotlivým zamestnancom za obdobie.
+    ///
+    /// Predaj sa pripíše zamestnancovi objednávky (`employee_id`), vrátenie
+    /// zamestnancovi pôvodnej objednávky. Zisk sa počíta ako v `profit_report`:
+    /// (predajná − aktuálna nákupná cena) × množstvo, produkty bez nákupnej ceny
+    /// sa nezapočítajú. Aktívni zamestnanci bez predaja majú zisk 0.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Zamestnanci so ziskom zoradení zostupne (pri zhode podľa ID)
+    #[instrument(skip_all, fields(db.operation.name = "profit_by_employee", db.rows = Empty))]
+    pub async fn profit_by_employee(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<(Employee, f64)>> {
+        let rows = sqlx::query(
+            r#"
+            WITH lines AS (
+                SELECT o.employee_id, i.quantity * (i.unit_price - p.cost_price) AS margin
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                JOIN products p ON p.id = i.product_id
+                WHERE p.cost_price IS NOT NULL AND date(o.created_at) BETWEEN date(?) AND date(?)
+                UNION ALL
+                SELECT o.employee_id, -i.quantity * (i.unit_price - p.cost_price) AS margin
+                FROM return_items i
+                JOIN order_returns r ON r.id = i.return_id
+                JOIN orders o ON o.id = r.order_id
+                JOIN products p ON p.id = i.product_id
+                WHERE p.cost_price IS NOT NULL AND date(r.created_at) BETWEEN date(?) AND date(?)
+            ),
+            totals AS (
+                SELECT employee_id, SUM(margin) AS profit
+                FROM lines
+                WHERE employee_id IS NOT NULL
+                GROUP BY employee_id
+            )
+            SELECT e.*, COALESCE(t.profit, 0.0) AS profit
+            FROM employees e
+            LEFT JOIN totals t ON t.employee_id = e.id
+            WHERE COALESCE(e.status, 1) = 1 OR t.employee_id IS NOT NULL
+            ORDER BY profit DESC, e.id
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(|r| (Self::employee_from_row(r), r.get("profit"))).collect())
+    }
+
+    /// Odhadne cenovú elasticitu dopytu po produkte z predajov za obdobie.
+    ///
+    /// Predaje (mínus vrátenia) sa zoskupia podľa predajnej ceny zaokrúhlenej na
+    /// centy. Predaj za deň pri každej cene je počet kusov delený počtom dní od
+    /// prvého po posledný predaj za túto cenu, elasticita je sklon v logaritmoch
+    /// (pozri `pricing::elasticity`).
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Cenové body a odhad; pri menej ako dvoch rôznych cenách s predajom je
+    /// elasticita `None` a `note` vysvetľuje prečo
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "price_elasticity", db.rows = Empty))]
+    pub async fn price_elasticity(&self, product_id: u32, from: NaiveDate, to: NaiveDate) -> Result<PriceElasticity> {
+        if self.get_product(product_id).await?.is_none() {
+            return Err(StoreError::NotFound(format!("produkt {product_id}")).into());
+        }
+        let rows = sqlx::query(
+            r#"
+            WITH lines AS (
+                SELECT date(o.created_at) AS day, i.quantity AS qty, i.unit_price
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                WHERE i.product_id = ? AND date(o.created_at) BETWEEN date(?) AND date(?)
+                UNION ALL
+                SELECT date(r.created_at) AS day, -i.quantity AS qty, i.unit_price
+                FROM return_items i
+                JOIN order_returns r ON r.id = i.return_id
+                WHERE i.product_id = ? AND date(r.created_at) BETWEEN date(?) AND date(?)
+            )
+            SELECT ROUND(unit_price, 2) AS price, SUM(qty) AS units, MIN(day) AS first_day, MAX(day) AS last_day
+            FROM lines
+            GROUP BY 1
+            ORDER BY 1
+            "#,
+        )
+            .bind(product_id)
+            .bind(from)
+            .bind(to)
+            .bind(product_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let points: Vec<PricePoint> = rows
+            .iter()
+            .map(|r| {
+                let units = Self::quantity_of(r, "units");
+                let first_day: NaiveDate = r.get("first_day");
+                let last_day: NaiveDate = r.get("last_day");
+                let days = (last_day - first_day).num_days() + 1;
+                PricePoint { price: r.get("price"), units, first_day, last_day, daily_units: units / days as f64 }
+            })
+            .collect();
+        let rates: Vec<(f64, f64)> = points.iter().map(|p| (p.price, p.daily_units)).collect();
+        let elasticity = pricing::elasticity(&rates);
+        let note = elasticity.is_none().then(|| {
+            let priced = rates.iter().filter(|(price, rate)| *price > 0.0 && *rate > 0.0).count();
+            format!("na odhad treba predaje aspoň za 2 rôzne ceny, v období sú za {priced}")
+        });
+
+        Ok(PriceElasticity {
+            product_id,
+            from,
+            to,
+            points,
+            elasticity,
+            elastic: elasticity.map(|e| e.abs() > 1.0),
+            note,
+        })
+    }
+
+    /// Vypočíta priemerný predaj produktu v jednotlivých kalendárnych mesiacoch naprieč rokmi.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    ///
+    /// # Returns
+    /// Priemery mesiacov; pri histórii kratšej ako 12 mesiacov sú prázdne a `note` vysvetľuje prečo
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak produkt neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "seasonality", db.rows = Empty))]
+    pub async fn seasonality(&self, product_id: u32) -> Result<Seasonality> {
+        if self.get_product(product_id).await?.is_none() {
+            return Err(StoreError::NotFound(format!("produkt {product_id}")).into());
+        }
+        self.seasonality_of(Some(product_id), None).await
+    }
+
+    /// Vypočíta sezónnosť predaja všetkých produktov kategórie (pozri `seasonality`).
+    ///
+    /// # Arguments
+    /// * `category` – kategória produktov
+    #[instrument(skip_all, fields(db.operation.name = "category_seasonality", db.rows = Empty))]
+    pub async fn category_seasonality(&self, category: &str) -> Result<Seasonality> {
+        self.seasonality_of(None, Some(category)).await
+    }
+
+    /// Spočíta predaje (mínus vrátenia) po mesiacoch a zoskupí ich podľa kalendárneho mesiaca.
+    async fn seasonality_of(&self, product_id: Option<u32>, category: Option<&str>) -> Result<Seasonality> {
+        let rows = sqlx::query(
+            r#"
+            WITH lines AS (
+                SELECT date(o.created_at) AS day, i.quantity AS qty, i.product_id
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                UNION ALL
+                SELECT date(r.created_at) AS day, -i.quantity AS qty, i.product_id
+                FROM return_items i
+                JOIN order_returns r ON r.id = i.return_id
+            )
+            SELECT
+                CAST(strftime('%Y', l.day) AS INTEGER) AS year,
+                CAST(strftime('%m', l.day) AS INTEGER) AS month,
+                SUM(l.qty) AS units,
+                MIN(l.day) AS first_day,
+                MAX(l.day) AS last_day
+            FROM lines l
+            JOIN products p ON p.id = l.product_id
+            WHERE (?1 IS NULL OR l.product_id = ?1) AND (?2 IS NULL OR p.category = ?2)
+            GROUP BY 1, 2
+            ORDER BY 1, 2
+            "#,
+        )
+            .bind(product_id)
+            .bind(category)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let mut seasonality = Seasonality {
+            product_id,
+            category: category.map(str::to_string),
+            first_sale: rows.first().map(|r| r.get("first_day")),
+            last_sale: rows.last().map(|r| r.get("last_day")),
+            months: Vec::new(),
+            note: None,
+        };
+        let sales: BTreeMap<(i32, u32), f64> = rows
+            .iter()
+            .map(|r| ((r.get("year"), r.get::<i64, _>("month") as u32), Self::quantity_of(r, "units")))
+            .collect();
+        let (Some(&first), Some(&last)) = (sales.keys().next(), sales.keys().next_back()) else {
+            seasonality.note = Some("bez predajov".into());
+            return Ok(seasonality);
+        };
+        let span = (last.0 - first.0) * 12 + last.1 as i32 - first.1 as i32 + 1;
+        if span < 12 {
+            seasonality.note = Some(format!("na sezónnosť treba históriu predajov aspoň 12 mesiacov, je {span}"));
+            return Ok(seasonality);
+        }
+
+        // každý mesiac histórie (aj bez predaja) sa započíta do svojho kalendárneho mesiaca
+        let mut years = [0u32; 12];
+        let mut units = [0f64; 12];
+        let (mut year, mut month) = first;
+        while (year, month) <= last {
+            years[month as usize - 1] += 1;
+            units[month as usize - 1] += sales.get(&(year, month)).copied().unwrap_or(0.0);
+            (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+        }
+        let averages: Vec<f64> = (0..12).map(|m| units[m] / years[m] as f64).collect();
+        let overall = averages.iter().sum::<f64>() / 12.0;
+        seasonality.months = (0..12)
+            .map(|m| MonthlyAvg {
+                month: m as u32 + 1,
+                years: years[m],
+                units: units[m],
+                average: averages[m],
+                index: (overall != 0.0).then(|| averages[m] / overall),
+            })
+            .collect();
+        Ok(seasonality)
+    }
+
+    /// Vypočíta obrátku zásob za obdobie s rozpadom podľa kategórií.
+    ///
+    /// Náklady predaného tovaru sú náklady zo správy o zisku (`profit_report`).
+    /// Priemerná hodnota zásob je priemer snímok štatistík z obdobia (kategórie
+    /// zo snímok, ktoré hodnoty kategórií obsahujú); bez snímok sa použije
+    /// aktuálna hodnota zásob. Pri nulových zásobách je obrátka `None`.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Obrátka zásob celkom a po kategóriách (zoradené podľa nákladov)
+    #[instrument(skip_all, fields(db.operation.name = "inventory_turnover", db.rows = Empty))]
+    pub async fn inventory_turnover(&self, from: NaiveDate, to: NaiveDate) -> Result<InventoryTurnover> {
+        let profit = self.profit_report(from, to).await?;
+        let (average_inventory, category_inventory, snapshots) = self.average_inventory(from, to).await?;
+
+        let ratio = |cogs: f64, inventory: f64| (inventory > 0.0).then(|| cogs / inventory);
+        let mut category_cogs: BTreeMap<String, f64> =
+            profit.categories.iter().map(|c| (c.category.clone(), c.cost)).collect();
+        for category in category_inventory.keys() {
+            category_cogs.entry(category.clone()).or_default();
+        }
+        let mut categories: Vec<CategoryTurnover> = category_cogs
+            .into_iter()
+            .map(|(category, cogs)| {
+                let average_inventory = category_inventory.get(&category).copied().unwrap_or(0.0);
+                CategoryTurnover { turnover: ratio(cogs, average_inventory), category, cogs, average_inventory }
+            })
+            .collect();
+        categories.sort_by(|a, b| b.cogs.total_cmp(&a.cogs).then_with(|| a.category.cmp(&b.category)));
+        Self::record_rows(categories.len());
+
+        Ok(InventoryTurnover {
+            from,
+            to,
+            cogs: profit.cost,
+            average_inventory,
+            turnover: ratio(profit.cost, average_inventory),
+            snapshots,
+            categories,
+        })
+    }
+
+    /// Vypočíta hrubý výnos z investície do zásob (GMROI) za obdobie s rozpadom podľa kategórií.
+    ///
+    /// GMROI = hrubý zisk zo správy o zisku (`profit_report`) / priemerná hodnota
+    /// zásob v nákupných cenách, ktorá sa počíta ako pri `inventory_turnover`.
+    /// Pri nulovej priemernej hodnote zásob nie je GMROI definované (`None`).
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// GMROI celkom a po kategóriách (zoradené podľa hrubého zisku)
+    #[instrument(skip_all, fields(db.operation.name = "gmroi", db.rows = Empty))]
+    pub async fn gmroi(&self, from: NaiveDate, to: NaiveDate) -> Result<Gmroi> {
+        let profit = self.profit_report(from, to).await?;
+        let (average_inventory, category_inventory, snapshots) = self.average_inventory(from, to).await?;
+
+        let ratio = |margin: f64, inventory: f64| (inventory > 0.0).then(|| margin / inventory);
+        let mut category_margin: BTreeMap<String, f64> =
+            profit.categories.iter().map(|c| (c.category.clone(), c.gross_profit)).collect();
+        for category in category_inventory.keys() {
+            category_margin.entry(category.clone()).or_default();
+        }
+        let mut categories: Vec<CategoryGmroi> = category_margin
+            .into_iter()
+            .map(|(category, gross_margin)| {
+                let average_inventory = category_inventory.get(&category).copied().unwrap_or(0.0);
+                CategoryGmroi { gmroi: ratio(gross_margin, average_inventory), category, gross_margin, average_inventory }
+            })
+            .collect();
+        categories.sort_by(|a, b| b.gross_margin.total_cmp(&a.gross_margin).then_with(|| a.category.cmp(&b.category)));
+        Self::record_rows(categories.len());
+
+        let gmroi = ratio(profit.gross_profit, average_inventory);
+        Ok(Gmroi {
+            from,
+            to,
+            gross_margin: profit.gross_profit,
+            average_inventory,
+            gmroi,
+            snapshots,
+            note: gmroi.is_none().then(|| "priemerná hodnota zásob je nulová, GMROI nie je definované".into()),
+            categories,
+        })
+    }
+
+    /// Priemerná hodnota zásob v nákupných cenách za obdobie, celkom a po kategóriách.
+    ///
+    /// Počíta sa zo snímok štatistík z obdobia (kategórie zo snímok, ktoré
+    /// hodnoty kategórií obsahujú); bez snímok sa použije aktuálna hodnota zásob.
+    ///
+    /// # Returns
+    /// Priemer celkom, priemery kategórií a počet použitých snímok
+    async fn average_inventory(&self, from: NaiveDate, to: NaiveDate) -> Result<(f64, BTreeMap<String, f64>, u32)> {
+        let snapshots = self.stats_history(Some(from), Some(to), u32::MAX).await?;
+        let mut categories: BTreeMap<String, f64> = BTreeMap::new();
+        if snapshots.is_empty() {
+            let current = self.inventory_value().await?;
+            for line in &current.lines {
+                let category = line.category.clone().unwrap_or_else(|| "Neznáma".into());
+                *categories.entry(category).or_default() += line.value;
+            }
+            return Ok((current.total_value, categories, 0));
+        }
+
+        let average = snapshots.iter().map(|s| s.inventory_value).sum::<f64>() / snapshots.len() as f64;
+        let with_categories: Vec<_> = snapshots.iter().filter(|s| !s.category_values.is_empty()).collect();
+        for snapshot in &with_categories {
+            for (category, value) in &snapshot.category_values {
+                *categories.entry(category.clone()).or_default() += value / with_categories.len() as f64;
+            }
+        }
+        Ok((average, categories, snapshots.len() as u32))
+    }
+
+    /// Vypočíta, na koľko dní vystačia zásoby jednotlivých kategórií.
+    ///
+    /// Dni = hodnota zásob kategórie v nákupných cenách (`inventory_value`) /
+    /// priemerné denné náklady predaného tovaru za posledných `SALES_VELOCITY_DAYS`
+    /// dní vrátane dneška (predaje po odpočte vrátení ako v `profit_report`).
+    /// Kategória so zásobami bez predaja má `f64::INFINITY`; kategória bez zásob
+    /// aj bez predaja sa vynechá, lebo jej pokrytie nie je definované.
+    ///
+    /// # Returns
+    /// Dvojice (kategória, dni) zoradené zostupne, najpomalšie kategórie prvé
+    #[instrument(skip_all, fields(db.operation.name = "days_of_inventory_by_category", db.rows = Empty))]
+    pub async fn days_of_inventory_by_category(&self) -> Result<Vec<(String, f64)>> {
+        let to = Local::now().date_naive();
+        let from = to - chrono::Days::new(u64::from(SALES_VELOCITY_DAYS - 1));
+        let profit = self.profit_report(from, to).await?;
+        let inventory = self.inventory_value().await?;
+
+        let mut categories: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+        for line in &inventory.lines {
+            let category = line.category.clone().unwrap_or_else(|| "Neznáma".into());
+            categories.entry(category).or_default().0 += line.value;
+        }
+        for category in &profit.categories {
+            categories.entry(category.category.clone()).or_default().1 += category.cost;
+        }
+
+        let mut days: Vec<(String, f64)> = categories
+            .into_iter()
+            .filter_map(|(category, (value, cogs))| {
+                let daily_cogs = cogs / f64::from(SALES_VELOCITY_DAYS);
+                match (value > 0.0, daily_cogs > 0.0) {
+                    (_, true) => Some((category, value / daily_cogs)),
+                    (true, false) => Some((category, f64::INFINITY)),
+                    (false, false) => None,
+                }
+            })
+            .collect();
+        days.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Self::record_rows(days.len());
+        Ok(days)
+    }
+
+    /// Rozdelí produkty na sklade do vekových skupín podľa dátumu pridania.
+    ///
+    /// Započítavajú sa aktívne produkty s kladným množstvom okrem sád (ako v
+    /// `inventory_value`). Vek je počet dní od `date_added` po dnešok; produkty
+    /// bez dátumu patria do skupiny `unknown`.
+    ///
+    /// # Arguments
+    /// * `buckets` – rastúce horné hranice skupín v dňoch (`Config::aging_buckets`)
+    ///
+    /// # Returns
+    /// Skupiny od najmladšej, posledná otvorená a nakoniec `unknown`; aj prázdne
+    #[instrument(skip_all, fields(db.operation.name = "inventory_aging", db.rows = Empty))]
+    pub async fn inventory_aging(&self, buckets: &AgingBuckets) -> Result<Vec<AgingBucket>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT quantity, unit, cost_price, date_added
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0 AND quantity > 0
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let bucket = |label: String, min_days: Option<u32>, max_days: Option<u32>| AgingBucket {
+            label,
+            min_days,
+            max_days,
+            products: 0,
+            quantity: 0.0,
+            value: 0.0,
+        };
+        let mut min = 0;
+        let mut report: Vec<AgingBucket> = Vec::with_capacity(buckets.0.len() + 2);
+        for &max in &buckets.0 {
+            report.push(bucket(format!("{min}-{max}"), Some(min), Some(max)));
+            min = max + 1;
+        }
+        report.push(bucket(format!("{min}+"), Some(min), None));
+        report.push(bucket("unknown".into(), None, None));
+
+        let today = Local::now().date_naive();
+        let unknown = report.len() - 1;
+        for row in &rows {
+            let index = match row.get::<Option<NaiveDate>, _>("date_added") {
+                Some(added) => {
+                    let age = (today - added).num_days().clamp(0, i64::from(u32::MAX)) as u32;
+                    buckets.0.iter().position(|&max| age <= max).unwrap_or(unknown - 1)
+                }
+                None => unknown,
+            };
+            let quantity = Self::quantity_of(row, "quantity");
+            let entry = &mut report[index];
+            entry.products += 1;
+            entry.quantity += quantity;
+            entry.value += quantity * row.get::<Option<f64>, _>("cost_price").unwrap_or(0.0);
+        }
+        Ok(report)
+    }
+
+    /// Zostaví správu o stratách zásob za obdobie s rozpadom podľa kategórií.
+    ///
+    /// Straty sú záporné inventúrne úpravy a rozdiely inventúry (`adjustment`,
+    /// `stocktake`) ocenené aktuálnou nákupnou cenou. Predaje (objednávky po
+    /// odpočte vrátení) nie sú stratou, len sa priradia pre výpočet podielu
+    /// strát na úbytku tovaru. Obdobie bez strát vráti nuly.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Straty celkom a po kategóriách (zoradené podľa hodnoty strát)
+    #[instrument(skip_all, fields(db.operation.name = "shrinkage_report", db.rows = Empty))]
+    pub async fn shrinkage_report(&self, from: NaiveDate, to: NaiveDate) -> Result<ShrinkageReport> {
+        let rows = sqlx::query(
+            r#"
+            WITH lines AS (
+                SELECT product_id, -quantity AS lost, 0.0 AS sold
+                FROM stock_movements
+                WHERE reason IN ('adjustment', 'stocktake') AND quantity < 0
+                  AND date(created_at) BETWEEN date(?) AND date(?)
+                UNION ALL
+                SELECT i.product_id, 0.0, i.quantity
+                FROM order_items i
+                JOIN orders o ON o.id = i.order_id
+                WHERE date(o.created_at) BETWEEN date(?) AND date(?)
+                UNION ALL
+                SELECT i.product_id, 0.0, -i.quantity
+                FROM return_items i
+                JOIN order_returns r ON r.id = i.return_id
+                WHERE date(r.created_at) BETWEEN date(?) AND date(?)
+            )
+            SELECT
+                COALESCE(p.category, 'Neznáma') AS category,
+                SUM(l.lost) AS units_lost,
+                SUM(CASE WHEN p.cost_price IS NOT NULL THEN l.lost * p.cost_price ELSE 0.0 END) AS value_lost,
+                SUM(CASE WHEN p.cost_price IS NULL THEN l.lost ELSE 0.0 END) AS uncosted_units,
+                SUM(l.sold) AS units_sold
+            FROM lines l
+            LEFT JOIN products p ON p.id = l.product_id
+            GROUP BY 1
+            ORDER BY value_lost DESC, units_lost DESC, category
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .bind(from)
+            .bind(to)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let rate = |lost: f64, sold: f64| if lost + sold > 0.0 { lost / (lost + sold) } else { 0.0 };
+        let categories: Vec<CategoryShrinkage> = rows
+            .iter()
+            .map(|r| {
+                let units_lost = Self::quantity_of(r, "units_lost");
+                let units_sold = Self::quantity_of(r, "units_sold");
+                CategoryShrinkage {
+                    category: r.get("category"),
+                    units_lost,
+                    value_lost: Self::quantity_of(r, "value_lost"),
+                    units_sold,
+                    rate: rate(units_lost, units_sold),
+                    uncosted_units: Self::quantity_of(r, "uncosted_units"),
+                }
+            })
+            .collect();
+
+        let units_lost: f64 = categories.iter().map(|c| c.units_lost).sum();
+        let units_sold: f64 = categories.iter().map(|c| c.units_sold).sum();
+        Ok(ShrinkageReport {
+            from,
+            to,
+            units_lost,
+            value_lost: categories.iter().map(|c| c.value_lost).sum(),
+            units_sold,
+            rate: rate(units_lost, units_sold),
+            uncosted_units: categories.iter().map(|c| c.uncosted_units).sum(),
+            categories,
+        })
+    }
+
+    /// Vypočíta mieru predaja (sell-through) za obdobie po kategóriách a celkovo.
+    ///
+    /// Vychádza zo skladových pohybov: predané kusy sú predaje po odpočte
+    /// vrátení, prijaté kusy sú príjemky objednávok u dodávateľov (`purchase`)
+    /// a kladné inventúrne úpravy (`adjustment`). Rozdiely inventúry sa
+    /// nezapočítavajú. Kategória, ktorá v období nič neprijala, má mieru `None`.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Miera predaja celkom a po kategóriách (zoradené podľa predaných kusov)
+    #[instrument(skip_all, fields(db.operation.name = "sell_through", db.rows = Empty))]
+    pub async fn sell_through(&self, from: NaiveDate, to: NaiveDate) -> Result<SellThroughReport> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(p.category, 'Neznáma') AS category,
+                SUM(CASE WHEN m.reason IN ('sale', 'return') THEN -m.quantity ELSE 0.0 END) AS units_sold,
+                SUM(CASE WHEN m.reason IN ('purchase', 'adjustment') AND m.quantity > 0 THEN m.quantity ELSE 0.0 END)
+                    AS units_received
+            FROM stock_movements m
+            LEFT JOIN products p ON p.id = m.product_id
+            WHERE m.reason IN ('sale', 'return', 'purchase', 'adjustment')
+              AND date(m.created_at) BETWEEN date(?) AND date(?)
+            GROUP BY 1
+            HAVING units_sold <> 0 OR units_received > 0
+            ORDER BY units_sold DESC, category
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let rate = |sold: f64, received: f64| (received > 0.0).then(|| sold / received);
+        let categories: Vec<CategorySellThrough> = rows
+            .iter()
+            .map(|r| {
+                let units_sold = Self::quantity_of(r, "units_sold");
+                let units_received = Self::quantity_of(r, "units_received");
+                CategorySellThrough {
+                    category: r.get("category"),
+                    units_sold,
+                    units_received,
+                    rate: rate(units_sold, units_received),
+                }
+            })
+            .collect();
+
+        let units_sold: f64 = categories.iter().map(|c| c.units_sold).sum();
+        let units_received: f64 = categories.iter().map(|c| c.units_received).sum();
+        Ok(SellThroughReport {
+            from,
+            to,
+            units_sold,
+            units_received,
+            rate: rate(units_sold, units_received),
+            categories,
+        })
+    }
+
+    /// Ohodnotí dodávateľov podľa včasnosti dodávok, stálosti cien a dostupnosti tovaru.
+    ///
+    /// Zložky (0–1, vyššie je lepšie):
+    /// * včasnosť – podiel objednávok s očakávaným dňom, ktorých prvá príjemka
+    ///   (pohyb `purchase` s odkazom `PO-{id}`) prišla najneskôr v ten deň
+    /// * stálosť cien – `1 - priemer variačných koeficientov` nákupných cien
+    ///   jednotlivých produktov naprieč objednávkami dodávateľa (produkty
+    ///   objednané aspoň dvakrát), najmenej 0
+    /// * dostupnosť – `1 - podiel výdajov zo skladu`, po ktorých stav zásob
+    ///   produktu dodávateľa klesol na nulu; stav po pohybe sa dopočíta od
+    ///   súčasného stavu spätne cez neskoršie pohyby
+    ///
+    /// Skóre je `Σ váha · zložka / Σ váha` cez zložky, ktoré dodávateľ má;
+    /// chýbajúca zložka (napr. žiadna objednávka s očakávaným dňom) skóre
+    /// nezhorší. Sady sa nepočítajú.
+    ///
+    /// # Arguments
+    /// * `weights` – váhy zložiek (`supplier_score_weights` z konfigurácie)
+    ///
+    /// # Returns
+    /// Dodávatelia zoradení podľa skóre zostupne (bez skóre na konci), potom podľa názvu
+    #[instrument(skip_all, fields(db.operation.name = "supplier_scores", db.rows = Empty))]
+    pub async fn supplier_scores(&self, weights: &SupplierScoreWeights) -> Result<Vec<SupplierScore>> {
+        let deliveries = sqlx::query(
+            r#"
+            SELECT po.supplier,
+                   COUNT(*) AS deliveries,
+                   SUM(date(r.first_receipt) <= date(po.expected_date)) AS on_time
+            FROM purchase_orders po
+            JOIN (
+                SELECT reference, MIN(created_at) AS first_receipt
+                FROM stock_movements
+                WHERE reason = 'purchase' AND quantity > 0
+                GROUP BY reference
+            ) r ON r.reference = 'PO-' || po.id
+            WHERE COALESCE(po.supplier, '') <> '' AND po.expected_date IS NOT NULL
+            GROUP BY po.supplier
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let costs = sqlx::query(
+            r#"
+            SELECT po.supplier, i.product_id, i.unit_cost
+            FROM purchase_order_items i
+            JOIN purchase_orders po ON po.id = i.purchase_order_id
+            WHERE COALESCE(po.supplier, '') <> '' AND i.unit_cost > 0
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let stock_outs = sqlx::query(
+            r#"
+            SELECT supplier,
+                   COUNT(*) AS outgoing,
+                   SUM(balance <= ?) AS stock_outs
+            FROM (
+                SELECT p.supplier, m.quantity,
+                       p.quantity - COALESCE(SUM(m.quantity) OVER (
+                           PARTITION BY m.product_id ORDER BY m.id DESC
+                           ROWS BETWEEN UNBOUNDED PRECEDING AND 1 PRECEDING
+                       ), 0.0) AS balance
+                FROM stock_movements m
+                JOIN products p ON p.id = m.product_id
+                WHERE COALESCE(p.supplier, '') <> '' AND p.is_bundle = 0
+            )
+            WHERE quantity < 0
+            GROUP BY supplier
+            "#,
+        )
+            .bind(QUANTITY_EPSILON)
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let blank = |supplier: &str| SupplierScore {
+            supplier: supplier.to_string(),
+            deliveries: 0,
+            on_time_deliveries: 0,
+            on_time_rate: None,
+            price_stability: None,
+            outgoing: 0,
+            stock_outs: 0,
+            availability: None,
+            score: None,
+        };
+        let mut scores: BTreeMap<String, SupplierScore> = BTreeMap::new();
+
+        for r in &deliveries {
+            let supplier: String = r.get("supplier");
+            let score = scores.entry(supplier.clone()).or_insert_with(|| blank(&supplier));
+            score.deliveries = r.get::<i64, _>("deliveries") as u32;
+            score.on_time_deliveries = r.get::<i64, _>("on_time") as u32;
+            score.on_time_rate = Some(f64::from(score.on_time_deliveries) / f64::from(score.deliveries));
+        }
+
+        // nákupné ceny po (dodávateľ, produkt)
+        let mut prices: BTreeMap<(String, i64), Vec<f64>> = BTreeMap::new();
+        for r in &costs {
+            prices.entry((r.get("supplier"), r.get("product_id"))).or_default().push(r.get("unit_cost"));
+        }
+        let mut variations: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+        for ((supplier, _), prices) in prices {
+            let list = variations.entry(supplier).or_default();
+            if prices.len() < 2 {
+                continue;
+            }
+            let mean = prices.iter().sum::<f64>() / prices.len() as f64;
+            let variance = prices.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / prices.len() as f64;
+            list.push(variance.sqrt() / mean);
+        }
+        for (supplier, list) in variations {
+            let score = scores.entry(supplier.clone()).or_insert_with(|| blank(&supplier));
+            if !list.is_empty() {
+                let mean_variation = list.iter().sum::<f64>() / list.len() as f64;
+                score.price_stability = Some((1.0 - mean_variation).max(0.0));
+            }
+        }
+
+        for r in &stock_outs {
+            let supplier: String = r.get("supplier");
+            let score = scores.entry(supplier.clone()).or_insert_with(|| blank(&supplier));
+            score.outgoing = r.get::<i64, _>("outgoing") as u32;
+            score.stock_outs = r.get::<i64, _>("stock_outs") as u32;
+            score.availability = Some(1.0 - f64::from(score.stock_outs) / f64::from(score.outgoing));
+        }
+
+        let mut scores: Vec<SupplierScore> = scores
+            .into_values()
+            .map(|mut s| {
+                let parts = [
+                    (weights.on_time, s.on_time_rate),
+                    (weights.price_stability, s.price_stability),
+                    (weights.availability, s.availability),
+                ];
+                let (total, weight) = parts
+                    .iter()
+                    .filter_map(|(w, v)| v.filter(|_| *w > 0.0).map(|v| (w * v, *w)))
+                    .fold((0.0, 0.0), |(t, ws), (v, w)| (t + v, ws + w));
+                s.score = (weight > 0.0).then(|| total / weight);
+                s
+            })
+            .collect();
+        scores.sort_by(|a, b| {
+            b.score
+                .unwrap_or(f64::NEG_INFINITY)
+                .total_cmp(&a.score.unwrap_or(f64::NEG_INFINITY))
+                .then_with(|| a.supplier.cmp(&b.supplier))
+        });
+
+        Self::record_rows(scores.len());
+        Ok(scores)
+    }
+
+    /// Zostaví rebríček kategórií podľa marže aktívnych produktov.
+    ///
+    /// # Returns
+    /// Kategórie zoradené podľa celkovej marže zostupne
+    #[instrument(skip_all, fields(db.operation.name = "category_margins", db.rows = Empty))]
+    pub async fn category_margins(&self) -> Result<Vec<CategoryMargin>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(category, 'Neznáma') AS category,
+                COUNT(sell_price - cost_price) AS products,
+                COUNT(*) - COUNT(sell_price - cost_price) AS excluded,
+                AVG(sell_price - cost_price) AS avg_margin,
+                COALESCE(SUM((sell_price - cost_price) * quantity), 0.0) AS total_margin
+            FROM products
+            WHERE status != 0
+            GROUP BY 1
+            ORDER BY total_margin DESC, category
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        Ok(rows
+            .iter()
+            .map(|r| CategoryMargin {
+                category: r.get("category"),
+                products: r.get::<i64, _>("products") as u32,
+                excluded: r.get::<i64, _>("excluded") as u32,
+                avg_margin: r.get("avg_margin"),
+                total_margin: r.get("total_margin"),
+            })
+            .collect())
+    }
+
+    /// Spočíta aktívnych zamestnancov na každej zmene po oddeleniach.
+    ///
+    /// Oddelenia aj zmeny sa berú zo všetkých zamestnancov (aj neaktívnych), takže
+    /// oddelenie, ktorému na niektorej zmene nezostal nikto, sa objaví s nulou
+    /// a so zmenou v `uncovered`. Prázdny text sa počíta ako nezadaná hodnota.
+    ///
+    /// # Returns
+    /// Riadky podľa oddelenia (bez oddelenia na konci)
+    #[instrument(skip_all, fields(db.operation.name = "shift_coverage", db.rows = Empty))]
+    pub async fn shift_coverage(&self) -> Result<Vec<ShiftCoverage>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT department, shift, SUM(CASE WHEN COALESCE(status, 1) = 1 THEN 1 ELSE 0 END) AS active
+            FROM (
+                SELECT NULLIF(TRIM(department), '') AS department, NULLIF(TRIM(shift), '') AS shift, status
+                FROM employees
+            )
+            GROUP BY department, shift
+            ORDER BY department IS NULL, department, shift
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let shifts: BTreeMap<String, u32> = rows
+            .iter()
+            .filter_map(|r| r.get::<Option<String>, _>("shift"))
+            .map(|shift| (shift, 0))
+            .collect();
+
+        let mut coverage: Vec<ShiftCoverage> = Vec::new();
+        for r in &rows {
+            let department: Option<String> = r.get("department");
+            if coverage.last().is_none_or(|c| c.department != department) {
+                coverage.push(ShiftCoverage {
+                    department,
+                    shifts: shifts.clone(),
+                    unassigned: 0,
+                    uncovered: Vec::new(),
+                });
+            }
+            let line = coverage.last_mut().expect("riadok oddelenia bol práve pridaný");
+            let active = r.get::<i64, _>("active") as u32;
+            match r.get::<Option<String>, _>("shift") {
+                Some(shift) => *line.shifts.entry(shift).or_default() += active,
+                None => line.unassigned += active,
+            }
+        }
+        for line in &mut coverage {
+            line.uncovered = line.shifts.iter().filter(|(_, &n)| n == 0).map(|(s, _)| s.clone()).collect();
+        }
+        Ok(coverage)
+    }
+
+    /// Spočíta mzdové náklady aktívnych zamestnancov.
+    ///
+    /// Mzda (`salary`) sa považuje za mesačnú. Neaktívni zamestnanci a aktívni
+    /// bez mzdy sa do súčtov nezapočítajú, len sa spočítajú.
+    ///
+    /// # Returns
+    /// Mesačné a ročné náklady, počet zahrnutých a vynechaných zamestnancov
+    #[instrument(skip_all, fields(db.operation.name = "total_payroll", db.rows = Empty))]
+    pub async fn total_payroll(&self) -> Result<PayrollSummary> {
+        let (headcount, monthly, without_salary, excluded_inactive): (i64, f64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COALESCE(SUM(COALESCE(status, 1) = 1 AND salary IS NOT NULL), 0),
+                COALESCE(SUM(CASE WHEN COALESCE(status, 1) = 1 THEN salary END), 0.0),
+                COALESCE(SUM(COALESCE(status, 1) = 1 AND salary IS NULL), 0),
+                COALESCE(SUM(COALESCE(status, 1) = 0), 0)
+            FROM employees
+            "#,
+        )
+            .fetch_one(&self.m_pool)
+            .await?;
+        Self::record_rows((headcount + without_salary + excluded_inactive) as usize);
+        Ok(PayrollSummary {
+            headcount: headcount as u32,
+            monthly,
+            annual: monthly * 12.0,
+            without_salary: without_salary as u32,
+            excluded_inactive: excluded_inactive as u32,
+        })
+    }
+
+    /// Vypočíta pomer mesačných mzdových nákladov k hodnote zásob.
+    ///
+    /// Skladá súčty `total_payroll` (aktívni zamestnanci) a `inventory_value`
+    /// (zásoby aktívnych produktov v nákupných cenách).
+    ///
+    /// # Returns
+    /// Mesačné mzdy / hodnota zásob (`0.25` = mzdy tvoria 25 % hodnoty zásob);
+    /// `None`, ak je hodnota zásob nulová (pomer nie je definovaný)
+    #[instrument(skip_all, fields(db.operation.name = "labor_to_inventory_ratio", db.rows = Empty))]
+    pub async fn labor_to_inventory_ratio(&self) -> Result<Option<f64>> {
+        let (payroll, inventory) = tokio::try_join!(self.total_payroll(), self.inventory_value())?;
+        Ok((inventory.total_value > 0.0).then(|| payroll.monthly / inventory.total_value))
+    }
+
+    /// Spočíta zamestnancov ku koncu každého mesiaca obdobia podľa dátumu nástupu.
+    ///
+    /// Posledný mesiac sa počíta k dňu `to`. Zamestnanci bez dátumu nástupu
+    /// a neaktívni zamestnanci (bez známeho dátumu odchodu) sa vynechajú.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    ///
+    /// # Returns
+    /// Počty zamestnancov po mesiacoch
+    #[instrument(skip_all, fields(db.operation.name = "headcount_over_time", db.rows = Empty))]
+    pub async fn headcount_over_time(&self, from: NaiveDate, to: NaiveDate) -> Result<HeadcountReport> {
+        let rows = sqlx::query("SELECT hire_date, status FROM employees")
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let mut excluded_no_hire_date = 0;
+        let mut excluded_inactive = 0;
+        let mut hire_dates = Vec::new();
+        for row in &rows {
+            if row.get::<Option<i64>, _>("status") == Some(0) {
+                excluded_inactive += 1;
+                continue;
+            }
+            match row.try_get::<Option<NaiveDate>, _>("hire_date").ok().flatten() {
+                Some(date) => hire_dates.push(date),
+                None => excluded_no_hire_date += 1,
+            }
+        }
+        hire_dates.sort();
+
+        let mut months = Vec::new();
+        let mut counted = 0;
+        let mut month_start = from.with_day(1).expect("prvý deň mesiaca");
+        while month_start <= to {
+            let next_month = month_start.checked_add_months(Months::new(1)).expect("dátum v rozsahu");
+            let date = next_month.pred_opt().expect("dátum v rozsahu").min(to);
+
+            let before = hire_dates.partition_point(|d| *d < month_start);
+            while counted < hire_dates.len() && hire_dates[counted] <= date {
+                counted += 1;
+            }
+
+            months.push(MonthlyHeadcount {
+                month: month_start.format("%Y-%m").to_string(),
+                date,
+                hired: (counted - before) as u32,
+                headcount: counted as u32,
+            });
+            month_start = next_month;
+        }
+
+        Ok(HeadcountReport {
+            from,
+            to,
+            excluded_no_hire_date,
+            excluded_inactive,
+            months,
+        })
+    }
+
+    /// Uzavrie deň, aby sa doň nedali spätne zapisovať predaje.
+    ///
+    /// Opakované uzavretie toho istého dňa nič nezmení.
+    ///
+    /// # Arguments
+    /// * `date` – uzatváraný deň
+    #[instrument(skip_all, fields(db.operation.name = "close_day", db.rows = Empty))]
+    pub async fn close_day(&self, date: NaiveDate) -> Result<()> {
+        sqlx::query("INSERT OR IGNORE INTO closed_days (date, closed_at) VALUES (?, ?)")
+            .bind(date)
+            .bind(Local::now().naive_local())
+            .execute(&self.m_pool)
+            .await?;
+        self.changed(&[Entity::Orders]);
+        Ok(())
+    }
+
+    // ==========================
+    // Alerts
+    // ==========================
+
+    /// Vyhodnotí stav zásob a aktualizuje tabuľku upozornení.
+    ///
+    /// Aktívnym produktom (bez sád) na hranici alebo pod ňou vytvorí upozornenie,
+    /// ak ešte nemajú otvorené ani ručne zrušené; upozornenia produktov, ktorých
+    /// zásoby sa doplnili (alebo už nie sú aktívne), uzavrie. Obe časti sú jeden
+    /// príkaz nad všetkými produktmi v spoločnej transakcii.
+    ///
+    /// # Arguments
+    /// * `threshold` – hranica nízkeho stavu zásob (vrátane) pre produkty bez vlastnej `min_stock`
+    ///
+    /// # Returns
+    /// Počet vytvorených a uzavretých upozornení
+    #[instrument(skip_all, fields(db.operation.name = "evaluate_stock_alerts", db.rows = Empty))]
+    pub async fn evaluate_stock_alerts(&self, threshold: u32) -> Result<AlertEvaluation> {
+        let now = Local::now().naive_local();
+        let mut tx = self.m_pool.begin().await?;
+
+        let resolved = sqlx::query(
+            r#"
+            UPDATE alerts
+            SET resolved_at = COALESCE(resolved_at, ?1), dismissed = 0
+            WHERE type = ?2
+              AND (resolved_at IS NULL OR dismissed = 1)
+              AND product_id NOT IN (
+                  SELECT id FROM products
+                  WHERE COALESCE(status, 1) = 1 AND is_bundle = 0 AND quantity <= COALESCE(min_stock, ?3)
+              )
+            "#,
+        )
+            .bind(now)
+            .bind(ALERT_LOW_STOCK)
+            .bind(threshold)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        let created = sqlx::query(
+            r#"
+            INSERT INTO alerts (product_id, type, created_at, message)
+            SELECT p.id, ?2, ?1,
+                   'Nízky stav zásob: ' || COALESCE(p.name, '#' || p.id) || ' (' || printf('%g', MAX(p.quantity, 0)) || ')'
+            FROM products p
+            WHERE COALESCE(p.status, 1) = 1 AND p.is_bundle = 0 AND p.quantity <= COALESCE(p.min_stock, ?3)
+              AND NOT EXISTS (
+                  SELECT 1 FROM alerts a
+                  WHERE a.product_id = p.id AND a.type = ?2 AND (a.resolved_at IS NULL OR a.dismissed = 1)
+              )
+            ON CONFLICT (product_id, type) WHERE resolved_at IS NULL DO NOTHING
+            "#,
+        )
+            .bind(now)
+            .bind(ALERT_LOW_STOCK)
+            .bind(threshold)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        tx.commit().await?;
+
+        Self::record_rows((created + resolved) as usize);
+        Ok(AlertEvaluation { created: created as u32, resolved: resolved as u32 })
+    }
+
+    /// Vráti upozornenia od najnovšieho.
+    ///
+    /// # Arguments
+    /// * `open` – `Some(true)` len otvorené, `Some(false)` len uzavreté
+    /// * `limit` – najviac toľko upozornení
+    ///
+    /// # Returns
+    /// Zoznam upozornení
+    #[instrument(skip_all, fields(db.operation.name = "get_alerts", db.rows = Empty))]
+    pub async fn get_alerts(&self, open: Option<bool>, limit: u32) -> Result<Vec<Alert>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM alerts
+            WHERE ?1 IS NULL OR (resolved_at IS NULL) = ?1
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?2
+            "#,
+        )
+            .bind(open)
+            .bind(limit)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::alert_from_row).collect())
+    }
+
+    /// Ručne uzavrie upozornenie.
+    ///
+    /// Kým sa zásoby produktu nedoplnia, monitor preň nové upozornenie nevytvorí.
+    ///
+    /// # Arguments
+    /// * `id` – ID upozornenia
+    ///
+    /// # Returns
+    /// Uzavreté upozornenie
+    ///
+    /// # Errors
+    /// `StoreError::NotFound`, ak upozornenie neexistuje; `StoreError::Conflict`,
+    /// ak už je uzavreté
+    #[instrument(skip_all, fields(db.operation.name = "resolve_alert", db.rows = Empty))]
+    pub async fn resolve_alert(&self, id: u32) -> Result<Alert> {
+        let row = sqlx::query(
+            r#"
+            UPDATE alerts SET resolved_at = ?, dismissed = 1
+            WHERE id = ? AND resolved_at IS NULL
+            RETURNING *
+            "#,
+        )
+            .bind(Local::now().naive_local())
+            .bind(id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+
+        match row {
+            Some(row) => {
+                Self::record_rows(1);
+                Ok(Self::alert_from_row(&row))
+            }
+            None => {
+                let exists = sqlx::query("SELECT 1 FROM alerts WHERE id = ?")
+                    .bind(id)
+                    .fetch_optional(&self.m_pool)
+                    .await?
+                    .is_some();
+                Self::record_rows(0);
+                if exists {
+                    Err(StoreError::Conflict(format!("upozornenie {id} už je uzavreté")).into())
+                } else {
+                    Err(StoreError::NotFound(format!("upozornenie {id}")).into())
+                }
+            }
+        }
+    }
+
+    fn alert_from_row(r: &SqliteRow) -> Alert {
+        Alert {
+            id: r.get::<i64, _>("id") as u32,
+            product_id: r.get::<i64, _>("product_id") as u32,
+            alert_type: r.get("type"),
+            created_at: r.get("created_at"),
+            resolved_at: r.get("resolved_at"),
+            message: r.get("message"),
+        }
+    }
+
+    // ==========================
+    // Stats history
+    // ==========================
+
+    /// Vypočíta aktuálne súhrnné štatistiky a uloží ich ako novú snímku.
+    ///
+    /// Volá ho plánovač na pozadí (`stats_snapshot_interval`) alebo
+    /// `POST /admin/stats/snapshot`.
+    ///
+    /// # Returns
+    /// Uložená snímka
+    #[instrument(skip_all, fields(db.operation.name = "snapshot_stats", db.rows = Empty))]
+    pub async fn snapshot_stats(&self) -> Result<StatsSnapshot> {
+        let mut tx = self.m_pool.begin().await?;
+
+        let totals = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) AS products,
+                COALESCE(SUM(MAX(quantity, 0)), 0.0) AS total_quantity,
+                COALESCE(SUM(MAX(quantity, 0) * COALESCE(cost_price, 0)), 0.0) AS inventory_value
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0
+            "#,
+        )
+            .fetch_one(&mut *tx)
+            .await?;
+        let category_rows = sqlx::query(
+            r#"
+            SELECT
+                COALESCE(category, 'Neznáma') AS category,
+                COUNT(*) AS products,
+                COALESCE(SUM(MAX(quantity, 0) * COALESCE(cost_price, 0)), 0.0) AS inventory_value
+            FROM products
+            WHERE COALESCE(status, 1) = 1 AND is_bundle = 0
+            GROUP BY 1
+            "#,
+        )
+            .fetch_all(&mut *tx)
+            .await?;
+        let categories: BTreeMap<String, u32> = category_rows
+            .iter()
+            .map(|r| (r.get("category"), r.get::<i64, _>("products") as u32))
+            .collect();
+        let category_values: BTreeMap<String, f64> =
+            category_rows.iter().map(|r| (r.get("category"), r.get("inventory_value"))).collect();
+        let employees: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM employees WHERE COALESCE(status, 1) = 1")
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let mut snapshot = StatsSnapshot {
+            id: 0,
+            taken_at: Local::now().naive_local(),
+            products: totals.get::<i64, _>("products") as u32,
+            total_quantity: totals.get("total_quantity"),
+            inventory_value: totals.get("inventory_value"),
+            employees: employees as u32,
+            categories,
+            category_values,
+        };
+        let id = sqlx::query(
+            r#"
+            INSERT INTO stats_snapshot (
+                taken_at, products, total_quantity, inventory_value, employees, categories, category_values
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+            .bind(snapshot.taken_at)
+            .bind(snapshot.products)
+            .bind(snapshot.total_quantity)
+            .bind(snapshot.inventory_value)
+            .bind(snapshot.employees)
+            .bind(serde_json::to_string(&snapshot.categories)?)
+            .bind(serde_json::to_string(&snapshot.category_values)?)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+        tx.commit().await?;
+
+        Self::record_rows(1);
+        snapshot.id = id as u32;
+        Ok(snapshot)
+    }
+
+    /// Vráti uložené snímky štatistík za obdobie, od najstaršej.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia (bez obmedzenia, ak nie je zadaný)
+    /// * `to` – posledný deň obdobia (bez obmedzenia, ak nie je zadaný)
+    /// * `limit` – najviac toľko najnovších snímok
+    ///
+    /// # Returns
+    /// Snímky zoradené podľa času
+    #[instrument(skip_all, fields(db.operation.name = "stats_history", db.rows = Empty))]
+    pub async fn stats_history(&self, from: Option<NaiveDate>, to: Option<NaiveDate>, limit: u32) -> Result<Vec<StatsSnapshot>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT * FROM stats_snapshot
+                WHERE (?1 IS NULL OR date(taken_at) >= ?1) AND (?2 IS NULL OR date(taken_at) <= ?2)
+                ORDER BY taken_at DESC, id DESC
+                LIMIT ?3
+            )
+            ORDER BY taken_at, id
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .bind(limit)
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        rows.iter()
+            .map(|r| {
+                Ok(StatsSnapshot {
+                    id: r.get::<i64, _>("id") as u32,
+                    taken_at: r.get("taken_at"),
+                    products: r.get::<i64, _>("products") as u32,
+                    total_quantity: r.get("total_quantity"),
+                    inventory_value: r.get("inventory_value"),
+                    employees: r.get::<i64, _>("employees") as u32,
+                    categories: serde_json::from_str(r.get("categories"))?,
+                    category_values: serde_json::from_str(r.get("category_values"))?,
+                })
+            })
+            .collect()
+    }
+
+    /// Uloží aktuálne množstvo každého produktu ako novú snímku zásob.
+    ///
+    /// Na rozdiel od `snapshot_stats` zachytí jednotlivé produkty (aj neaktívne),
+    /// takže sa dá neskôr porovnať, ako sa zmenili zásoby medzi dvoma snímkami.
+    ///
+    /// # Arguments
+    /// * `label` – označenie snímky; ak chýba alebo je prázdne, použije sa čas snímky
+    ///
+    /// # Returns
+    /// Uložená snímka s počtom zachytených produktov
+    #[instrument(skip_all, fields(db.operation.name = "snapshot_stock", db.rows = Empty))]
+    pub async fn snapshot_stock(&self, label: Option<&str>) -> Result<StockSnapshot> {
+        let taken_at = Local::now().naive_local();
+        let label = match label.map(str::trim) {
+            Some(label) if !label.is_empty() => label.to_string(),
+            _ => taken_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        };
+
+        let mut tx = self.m_pool.begin().await?;
+        let id = sqlx::query("INSERT INTO stock_snapshots (label, taken_at) VALUES (?, ?)")
+            .bind(&label)
+            .bind(taken_at)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+        let products = sqlx::query(
+            r#"
+            INSERT INTO stock_snapshot_items (snapshot_id, product_id, name, quantity)
+            SELECT ?, id, name, quantity FROM products
+            "#,
+        )
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        tx.commit().await?;
+
+        Self::record_rows(products as usize);
+        Ok(StockSnapshot { id: id as u32, label, taken_at, products: products as u32 })
+    }
+
+    /// Porovná zásoby produktov medzi dvoma snímkami.
+    ///
+    /// Produkt, ktorý v jednej zo snímok chýba (pridaný alebo vymazaný medzi nimi),
+    /// sa v nej počíta s nulovým množstvom. Produkty bez zmeny sa vynechajú.
+    ///
+    /// # Arguments
+    /// * `from` – ID staršej (východiskovej) snímky
+    /// * `to` – ID novšej snímky
+    ///
+    /// # Returns
+    /// Obe snímky a zmeny produktov zoradené podľa ID produktu
+    ///
+    /// # Errors
+    /// `StoreError::NotFound`, ak niektorá snímka neexistuje
+    #[instrument(skip_all, fields(db.operation.name = "stock_delta", db.rows = Empty))]
+    pub async fn stock_delta(&self, from: u32, to: u32) -> Result<StockDeltaReport> {
+        let from_snapshot = self.get_stock_snapshot(from).await?;
+        let to_snapshot = self.get_stock_snapshot(to).await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT product_id, name, quantity, snapshot_id = ?1 AS is_from
+            FROM stock_snapshot_items
+            WHERE snapshot_id IN (?1, ?2)
+            "#,
+        )
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        // pri rovnakých snímkach sa každý riadok započíta na obe strany
+        let mut changes: BTreeMap<u32, StockDelta> = BTreeMap::new();
+        for row in &rows {
+            let product_id = row.get::<i64, _>("product_id") as u32;
+            let quantity: f64 = row.get("quantity");
+            let delta = changes.entry(product_id).or_insert_with(|| StockDelta {
+                product_id,
+                name: row.get("name"),
+                from_quantity: None,
+                to_quantity: None,
+                change: 0.0,
+            });
+            if row.get::<bool, _>("is_from") {
+                delta.from_quantity = Some(quantity);
+            }
+            if from == to || !row.get::<bool, _>("is_from") {
+                delta.to_quantity = Some(quantity);
+                delta.name = row.get("name");
+            }
+        }
+        let products: Vec<StockDelta> = changes
+            .into_values()
+            .map(|mut delta| {
+                delta.change = delta.to_quantity.unwrap_or(0.0) - delta.from_quantity.unwrap_or(0.0);
+                delta
+            })
+            .filter(|delta| delta.change != 0.0)
+            .collect();
+
+        Self::record_rows(products.len());
+        Ok(StockDeltaReport { from: from_snapshot, to: to_snapshot, products })
+    }
+
+    /// Načíta hlavičku snímky zásob.
+    ///
+    /// # Errors
+    /// `StoreError::NotFound`, ak snímka neexistuje
+    async fn get_stock_snapshot(&self, id: u32) -> Result<StockSnapshot> {
+        let row = sqlx::query(
+            r#"
+            SELECT s.id, s.label, s.taken_at,
+                (SELECT COUNT(*) FROM stock_snapshot_items i WHERE i.snapshot_id = s.id) AS products
+            FROM stock_snapshots s
+            WHERE s.id = ?
+            "#,
+        )
+            .bind(id)
+            .fetch_optional(&self.m_pool)
+            .await?
+            .ok_or_else(|| StoreError::NotFound(format!("snímka zásob {id}")))?;
+        Ok(StockSnapshot {
+            id: row.get::<i64, _>("id") as u32,
+            label: row.get("label"),
+            taken_at: row.get("taken_at"),
+            products: row.get::<i64, _>("products") as u32,
+        })
+    }
+
+    // ==========================
+    // Attendance
+    // ==========================
+
+    /// Zaeviduje príchod zamestnanca.
+    ///
+    /// # Arguments
+    /// * `employee_id` – ID zamestnanca
+    /// * `at` – čas príchodu
+    ///
+    /// # Returns
+    /// Nový otvorený záznam dochádzky
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` pri neznámom zamestnancovi, `StoreError::Conflict`
+    /// ak má zamestnanec otvorený záznam alebo by sa príchod prekrýval s predchádzajúcou zmenou
+    #[instrument(skip_all, fields(db.operation.name = "clock_in", db.rows = Empty))]
+    pub async fn clock_in(&self, employee_id: u32, at: NaiveDateTime) -> Result<AttendanceEntry> {
+        let mut tx = self.m_pool.begin().await?;
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM employees WHERE id = ?")
+            .bind(employee_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if exists.is_none() {
+            return Err(StoreError::NotFound(format!("zamestnanec {employee_id}")).into());
+        }
+        let last = sqlx::query("SELECT * FROM attendance WHERE employee_id = ? ORDER BY clock_in DESC, id DESC LIMIT 1")
+            .bind(employee_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .map(|r| Self::attendance_from_row(&r));
+        match last {
+            Some(entry) if entry.clock_out.is_none() => {
+                return Err(StoreError::Conflict(format!(
+                    "zamestnanec {employee_id} je prihlásený od {} (chýba odchod)",
+                    entry.clock_in
+                ))
+                    .into());
+            }
+            Some(AttendanceEntry { clock_out: Some(clock_out), .. }) if clock_out > at => {
+                return Err(StoreError::Conflict(format!("príchod {at} je pred posledným odchodom {clock_out}")).into());
+            }
+            _ => {}
+        }
+
+        let id = sqlx::query("INSERT INTO attendance (employee_id, clock_in) VALUES (?, ?)")
+            .bind(employee_id)
+            .bind(at)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+        tx.commit().await?;
+        Self::record_rows(1);
+        Ok(AttendanceEntry { id: id as u32, employee_id, clock_in: at, clock_out: None })
+    }
+
+    /// Zaeviduje odchod zamestnanca (uzavrie jeho otvorený záznam, aj dodatočne pri oprave).
+    ///
+    /// # Arguments
+    /// * `employee_id` – ID zamestnanca
+    /// * `at` – čas odchodu
+    ///
+    /// # Returns
+    /// Uzavretý záznam dochádzky
+    ///
+    /// # Errors
+    /// `StoreError::Conflict` ak zamestnanec nemá otvorený záznam,
+    /// `StoreError::Invalid` ak je odchod pred príchodom
+    #[instrument(skip_all, fields(db.operation.name = "clock_out", db.rows = Empty))]
+    pub async fn clock_out(&self, employee_id: u32, at: NaiveDateTime) -> Result<AttendanceEntry> {
+        let mut tx = self.m_pool.begin().await?;
+        let open = sqlx::query("SELECT * FROM attendance WHERE employee_id = ? AND clock_out IS NULL")
+            .bind(employee_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .map(|r| Self::attendance_from_row(&r))
+            .ok_or_else(|| StoreError::Conflict(format!("zamestnanec {employee_id} nemá zaevidovaný príchod")))?;
+        if at < open.clock_in {
+            return Err(StoreError::Invalid(format!("odchod {at} je pred príchodom {}", open.clock_in)).into());
+        }
+
+        sqlx::query("UPDATE attendance SET clock_out = ? WHERE id = ?")
+            .bind(at)
+            .bind(open.id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Self::record_rows(1);
+        Ok(AttendanceEntry { clock_out: Some(at), ..open })
+    }
+
+    /// Zostaví odpracované hodiny a nadčasy po týždňoch.
+    ///
+    /// Zmena cez polnoc sa rozdelí medzi dni (a týždne), v ktorých prebehla;
+    /// započíta sa len časť v období. Norma sa pri neúplnom týždni na okraji
+    /// obdobia nekráti.
+    ///
+    /// # Arguments
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    /// * `employee_id` – len jeden zamestnanec (`None` = všetci)
+    /// * `weekly_norm` – týždenná norma hodín
+    ///
+    /// # Returns
+    /// Týždne zoradené podľa zamestnanca a týždňa, chýbajúce odchody a varovania
+    #[instrument(skip_all, fields(db.operation.name = "hours_report", db.rows = Empty))]
+    pub async fn hours_report(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        employee_id: Option<u32>,
+        weekly_norm: f64,
+    ) -> Result<HoursReport> {
+        let (entries, names) = self.attendance_in(from, to, employee_id).await?;
+        let report = Self::summarize_hours(&entries, &names, from, to, weekly_norm).0;
+        Self::record_rows(report.weeks.len());
+        Ok(report)
+    }
+
+    /// Vráti detail dochádzky zamestnanca: záznamy, hodiny po dňoch a týždňoch.
+    ///
+    /// # Arguments
+    /// * `employee_id` – ID zamestnanca
+    /// * `from` – prvý deň obdobia
+    /// * `to` – posledný deň obdobia
+    /// * `weekly_norm` – týždenná norma hodín
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` pri neznámom zamestnancovi
+    #[instrument(skip_all, fields(db.operation.name = "employee_hours", db.rows = Empty))]
+    pub async fn employee_hours(
+        &self,
+        employee_id: u32,
+        from: NaiveDate,
+        to: NaiveDate,
+        weekly_norm: f64,
+    ) -> Result<EmployeeHours> {
+        let exists: Option<i64> = sqlx::query_scalar("SELECT id FROM employees WHERE id = ?")
+            .bind(employee_id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        if exists.is_none() {
+            return Err(StoreError::NotFound(format!("zamestnanec {employee_id}")).into());
+        }
+
+        let (entries, names) = self.attendance_in(from, to, Some(employee_id)).await?;
+        let (report, days) = Self::summarize_hours(&entries, &names, from, to, weekly_norm);
+        Self::record_rows(entries.len());
+        Ok(EmployeeHours {
+            employee_id,
+            entries,
+            days: days.into_iter().map(|((_, date), hours)| DailyHours { date, hours }).collect(),
+            report,
+        })
+    }
+
+    /// Záznamy dochádzky, ktoré zasahujú do obdobia (aj otvorené), a mená zamestnancov.
+    async fn attendance_in(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        employee_id: Option<u32>,
+    ) -> Result<(Vec<AttendanceEntry>, BTreeMap<u32, String>)> {
+        let (start, end) = Self::period_bounds(from, to);
+        let rows = sqlx::query(
+            r#"
+            SELECT a.*, COALESCE(e.name || ' ' || e.surname, '#' || a.employee_id) AS employee_name
+            FROM attendance a
+            LEFT JOIN employees e ON e.id = a.employee_id
+            WHERE a.clock_in < ?2 AND (a.clock_out IS NULL OR a.clock_out > ?1)
+              AND (?3 IS NULL OR a.employee_id = ?3)
+            ORDER BY a.employee_id, a.clock_in, a.id
+            "#,
+        )
+            .bind(start)
+            .bind(end)
+            .bind(employee_id)
+            .fetch_all(&self.m_pool)
+            .await?;
+
+        let names = rows.iter().map(|r| (r.get::<i64, _>("employee_id") as u32, r.get("employee_name"))).collect();
+        Ok((rows.iter().map(Self::attendance_from_row).collect(), names))
+    }
+
+    /// Začiatok prvého dňa a koniec (polnoc po) posledného dňa obdobia.
+    fn period_bounds(from: NaiveDate, to: NaiveDate) -> (NaiveDateTime, NaiveDateTime) {
+        (from.and_time(NaiveTime::MIN), to.and_time(NaiveTime::MIN) + chrono::Duration::days(1))
+    }
+
+    /// Rozpočíta uzavreté záznamy po dňoch a týždňoch; otvorené záznamy len vykáže.
+    ///
+    /// # Returns
+    /// Zostava a hodiny po (zamestnanec, deň)
+    fn summarize_hours(
+        entries: &[AttendanceEntry],
+        names: &BTreeMap<u32, String>,
+        from: NaiveDate,
+        to: NaiveDate,
+        weekly_norm: f64,
+    ) -> (HoursReport, BTreeMap<(u32, NaiveDate), f64>) {
+        let (start, end) = Self::period_bounds(from, to);
+        let name = |id: u32| names.get(&id).cloned().unwrap_or_else(|| format!("#{id}"));
+        let stale_before = Local::now().naive_local() - chrono::Duration::hours(MAX_SHIFT_HOURS);
+
+        let mut days: BTreeMap<(u32, NaiveDate), f64> = BTreeMap::new();
+        let mut missing_clock_outs = Vec::new();
+        let mut open = 0;
+        for entry in entries {
+            let Some(clock_out) = entry.clock_out else {
+                open += 1;
+                if entry.clock_in < stale_before {
+                    missing_clock_outs.push(MissingClockOut {
+                        entry_id: entry.id,
+                        employee_id: entry.employee_id,
+                        name: name(entry.employee_id),
+                        date: entry.clock_in.date(),
+                        clock_in: entry.clock_in,
+                    });
+                }
+                continue;
+            };
+
+            // zmena cez polnoc sa rozdelí na časti po dňoch
+            let mut cursor = entry.clock_in.max(start);
+            let until = clock_out.min(end);
+            while cursor < until {
+                let midnight = cursor.date().and_time(NaiveTime::MIN) + chrono::Duration::days(1);
+                let part_end = midnight.min(until);
+                let hours = (part_end - cursor).num_seconds() as f64 / 3600.0;
+                *days.entry((entry.employee_id, cursor.date())).or_default() += hours;
+                cursor = part_end;
+            }
+        }
+
+        let mut weeks: BTreeMap<(u32, NaiveDate), f64> = BTreeMap::new();
+        for ((employee_id, date), hours) in &days {
+            let week_start = *date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64);
+            *weeks.entry((*employee_id, week_start)).or_default() += hours;
+        }
+
+        let mut warnings = Vec::new();
+        if open > 0 {
+            warnings.push(format!("{open} záznamov bez odchodu sa nezapočítalo"));
+        }
+        let report = HoursReport {
+            from,
+            to,
+            weekly_norm,
+            weeks: weeks
+                .into_iter()
+                .map(|((employee_id, week_start), hours)| {
+                    let week = week_start.iso_week();
+                    WeeklyHours {
+                        employee_id,
+                        name: name(employee_id),
+                        week: format!("{}-W{:02}", week.year(), week.week()),
+                        week_start,
+                        hours,
+                        overtime: (hours - weekly_norm).max(0.0),
+                    }
+                })
+                .collect(),
+            missing_clock_outs,
+            warnings,
+        };
+        (report, days)
+    }
+
+    /// Prevedie riadok tabuľky `attendance` na záznam dochádzky.
+    fn attendance_from_row(r: &SqliteRow) -> AttendanceEntry {
+        AttendanceEntry {
+            id: r.get::<i64, _>("id") as u32,
+            employee_id: r.get::<i64, _>("employee_id") as u32,
+            clock_in: r.get("clock_in"),
+            clock_out: r.get("clock_out"),
         }
+    }
 
-        query.push_str(&updates.join(", "));
-        query.push_str(" WHERE id = ?");
-        args.add(id);
+    // ==========================
+    // Schedule
+    // ==========================
+
+    /// Priradí zamestnanca na zmenu v daný deň.
+    ///
+    /// # Arguments
+    /// * `request` – zamestnanec, deň a zmena (okrajové medzery sa orežú)
+    ///
+    /// # Returns
+    /// Nové priradenie
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri prázdnej zmene, `StoreError::NotFound` pri neznámom
+    /// zamestnancovi, `StoreError::Conflict` pri neaktívnom zamestnancovi alebo ak
+    /// už na zmene je
+    #[instrument(skip_all, fields(db.operation.name = "assign_shift", db.rows = Empty))]
+    pub async fn assign_shift(&self, request: &ShiftAssignmentRequest) -> Result<ShiftAssignment> {
+        let shift = request.shift.trim();
+        if shift.is_empty() {
+            return Err(StoreError::Invalid("zmena nesmie byť prázdna".into()).into());
+        }
+        let mut tx = self.m_pool.begin().await?;
+        let status: Option<Option<i64>> = sqlx::query_scalar("SELECT status FROM employees WHERE id = ?")
+            .bind(request.employee_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        match status {
+            None => return Err(StoreError::NotFound(format!("zamestnanec {}", request.employee_id)).into()),
+            Some(Some(0)) => {
+                return Err(StoreError::Conflict(format!("zamestnanec {} nie je aktívny", request.employee_id)).into());
+            }
+            Some(_) => {}
+        }
 
-        let result = sqlx::query_with(&query, args).execute(&self.m_pool).await?;
+        let result = sqlx::query("INSERT OR IGNORE INTO shift_assignments (employee_id, date, shift) VALUES (?, ?, ?)")
+            .bind(request.employee_id)
+            .bind(request.date)
+            .bind(shift)
+            .execute(&mut *tx)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(StoreError::Conflict(format!(
+                "zamestnanec {} je už {} na zmene {shift}",
+                request.employee_id, request.date
+            ))
+                .into());
+        }
+        tx.commit().await?;
+        Self::record_rows(1);
+        Ok(ShiftAssignment {
+            id: result.last_insert_rowid() as u32,
+            employee_id: request.employee_id,
+            date: request.date,
+            shift: shift.to_string(),
+        })
+    }
+
+    /// Zruší priradenie na zmenu.
+    ///
+    /// # Arguments
+    /// * `id` – ID priradenia
+    ///
+    /// # Returns
+    /// `true` ak priradenie existovalo
+    #[instrument(skip_all, fields(db.operation.name = "unassign_shift", db.rows = Empty))]
+    pub async fn unassign_shift(&self, id: u32) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM shift_assignments WHERE id = ?")
+            .bind(id)
+            .execute(&self.m_pool)
+            .await?;
         Ok(result.rows_affected() > 0)
     }
 
-    /// Vráti zoznam produktov podľa filtra.
-    pub async fn get_products(&self, product: Product) -> Result<Vec<Product>, sqlx::Error> {
-        let mut query = String::from("SELECT * FROM Products WHERE 1=1");
-        let mut args = sqlx::sqlite::SqliteArguments::default();
+    /// Vráti aktívnych zamestnancov priradených na zmenu v daný deň.
+    ///
+    /// Na rozdiel od poľa `shift` zamestnanca vychádza z rozpisu služieb
+    /// (`shift_assignments`); neaktívni zamestnanci sa vynechajú.
+    ///
+    /// # Arguments
+    /// * `date` – deň
+    /// * `shift` – zmena (okrajové medzery sa orežú)
+    ///
+    /// # Returns
+    /// Zamestnanci zoradení podľa ID; prázdny zoznam, ak na zmene nikto nie je
+    #[instrument(skip_all, fields(db.operation.name = "employees_on_shift", db.rows = Empty))]
+    pub async fn employees_on_shift(&self, date: NaiveDate, shift: &str) -> Result<Vec<Employee>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT e.* FROM shift_assignments a
+            JOIN employees e ON e.id = a.employee_id
+            WHERE a.date = ? AND a.shift = ? AND COALESCE(e.status, 1) = 1
+            ORDER BY e.id
+            "#,
+        )
+            .bind(date)
+            .bind(shift.trim())
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::employee_from_row).collect())
+    }
 
-        if let Some(id) = product.id { query.push_str(" AND id = ?"); args.add(id); }
-        if let Some(name) = product.name {
-            if !name.is_empty() { query.push_str(" AND name LIKE ?"); args.add(format!("%{}%", name)); }
-        }
-        if let Some(category) = product.category { query.push_str(" AND category = ?"); args.add(category); }
-        if let Some(quantity) = product.quantity { query.push_str(" AND quantity = ?"); args.add(quantity as i64); }
-        if let Some(status) = product.status { query.push_str(" AND status = ?"); args.add(status); }
-        if let Some(barcode) = product.bar_code { query.push_str(" AND bar_code = ?"); args.add(barcode as i64); }
-        if let Some(cost) = product.cost_price { query.push_str(" AND cost_price = ?"); args.add(cost); }
-        if let Some(price) = product.sell_price { query.push_str(" AND sell_price = ?"); args.add(price); }
-        if let Some(desc) = product.description {
-            if !desc.is_empty() { query.push_str(" AND description LIKE ?"); args.add(format!("%{}%", desc)); }
+    // ==========================
+    // Sync
+    // ==========================
+
+    /// Vráti `updated_at`, po ktorom sa má entita ďalej sťahovať zo zdroja
+    /// (najvyšší zaznamenaný v `sync_log`).
+    ///
+    /// # Arguments
+    /// * `source` – adresa vzdialenej inštancie
+    /// * `entity` – `employees`, `products` alebo `orders`
+    ///
+    /// # Returns
+    /// Čas poslednej prevzatej zmeny alebo `None` pri prvej synchronizácii
+    #[instrument(skip_all, fields(db.operation.name = "sync_high_water_mark", db.rows = Empty))]
+    pub async fn sync_high_water_mark(&self, source: &str, entity: &str) -> Result<Option<NaiveDateTime>> {
+        let mark: Option<NaiveDateTime> =
+            sqlx::query_scalar("SELECT MAX(high_water_mark) FROM sync_log WHERE source = ? AND entity = ?")
+                .bind(source)
+                .bind(entity)
+                .fetch_one(&self.m_pool)
+                .await?;
+        Self::record_rows(mark.is_some() as usize);
+        Ok(mark)
+    }
+
+    /// Prevezme stránku zamestnancov zo zdroja; kľúčom je zdroj a email.
+    ///
+    /// Zamestnanci bez emailu a emaily patriace v zdroji inému zamestnancovi sa
+    /// preskočia. Záznam, ktorý sa od poslednej synchronizácie zmenil aj miestne,
+    /// sa neprepíše a pridá sa medzi konflikty.
+    ///
+    /// # Arguments
+    /// * `source` – adresa vzdialenej inštancie
+    /// * `employees` – zamestnanci zo vzdialenej inštancie
+    /// * `counts` – priebežné počty synchronizácie
+    ///
+    /// # Errors
+    /// Ak zlyhá zápis do databázy
+    #[instrument(skip_all, fields(db.operation.name = "sync_employees", db.rows = Empty))]
+    pub async fn sync_employees(&self, source: &str, employees: &[Employee], counts: &mut SyncCounts) -> Result<()> {
+        let mut tx = self.m_pool.begin().await?;
+        for remote in employees {
+            Self::advance_high_water_mark(counts, remote.updated_at);
+            let Some(email) = remote.email.as_deref().filter(|e| !e.is_empty()) else {
+                counts.skipped += 1;
+                continue;
+            };
+            // ID nadriadeného platí len vo vzdialenej inštancii
+            let employee = Employee { id: None, manager_id: None, ..remote.clone() };
+            match Self::synced_row(&mut tx, "employees", "email", source, email).await? {
+                Some(row) if row.source_id != remote.id => counts.skipped += 1,
+                None => {
+                    let id = Self::insert_employee(&mut tx, &employee).await?;
+                    Self::mark_synced(&mut tx, "employees", id, source, remote.id).await?;
+                    counts.created += 1;
+                }
+                Some(row) if row.changed_locally => {
+                    counts.conflicts.push(row.conflict(email, remote.updated_at));
+                }
+                Some(row) => {
+                    let assignments = Self::employee_assignments(&employee);
+                    Self::update_synced(&mut tx, "employees", row.id, source, remote.id, assignments).await?;
+                    counts.updated += 1;
+                }
+            }
         }
-        if let Some(brand) = product.brand { query.push_str(" AND brand = ?"); args.add(brand); }
-        if let Some(supplier) = product.supplier { query.push_str(" AND supplier = ?"); args.add(supplier); }
-        if let Some(emp_id) = product.employee_id { query.push_str(" AND employee_id = ?"); args.add(emp_id as i64); }
-        if let Some(date) = product.date_added { query.push_str(" AND date_added = ?"); args.add(date); }
-        if let Some(date) = product.date_remove { query.push_str(" AND date_remove = ?"); args.add(date); }
+        tx.commit().await?;
+        Self::record_rows(employees.len());
+        self.changed(&[Entity::Employees]);
+        Ok(())
+    }
 
-        let rows = sqlx::query_with(&query, args).fetch_all(&self.m_pool).await?;
+    /// Prevezme stránku produktov zo zdroja; kľúčom je zdroj a čiarový kód.
+    ///
+    /// Sady (ich zložky sa neprenášajú), produkty bez čiarového kódu, kódy
+    /// patriace v zdroji inému produktu a produkty porušujúce miestne pravidlá
+    /// (napr. kapacitu kategórie) sa preskočia. Miestne zmenený záznam sa
+    /// neprepíše a pridá sa medzi konflikty.
+    ///
+    /// # Arguments
+    /// * `source` – adresa vzdialenej inštancie
+    /// * `products` – produkty zo vzdialenej inštancie
+    /// * `counts` – priebežné počty synchronizácie
+    ///
+    /// # Errors
+    /// Ak zlyhá zápis do databázy
+    #[instrument(skip_all, fields(db.operation.name = "sync_products", db.rows = Empty))]
+    pub async fn sync_products(&self, source: &str, products: &[Product], counts: &mut SyncCounts) -> Result<()> {
+        let mut tx = self.m_pool.begin().await?;
+        for remote in products {
+            Self::advance_high_water_mark(counts, remote.updated_at);
+            let (Some(bar_code), false) = (remote.bar_code, remote.is_bundle.unwrap_or(false)) else {
+                counts.skipped += 1;
+                continue;
+            };
+            // ID zamestnanca zo zdroja tu nič neznamená a SKU patrí číselnému radu zdroja –
+            // prevzatý produkt dostane miestne SKU
+            let product = Product { id: None, employee_id: None, is_bundle: None, sku: None, ..remote.clone() };
+            let key = bar_code.to_string();
+            let existing = Self::synced_row(&mut tx, "products", "bar_code", source, &key).await?;
+            let existing_is_new = existing.is_none();
 
-        Ok(rows.into_iter().map(|row| Product {
-            id: row.get::<Option<i64>, _>("id").map(|v| v as u32),
-            name: row.get("name"),
-            category: row.get("category"),
-            quantity: row.get::<Option<i64>, _>("quantity").map(|v| v as u32),
-            status: row.get::<Option<i64>, _>("status").map(|v| v == 1),
-            bar_code: row.get::<Option<i64>, _>("bar_code").map(|v| v as i64),
-            cost_price: row.get("cost_price"),
-            sell_price: row.get("sell_price"),
-            description: row.get("description"),
-            brand: row.get("brand"),
-            supplier: row.get("supplier"),
-            employee_id: row.get::<Option<i64>, _>("employee_id").map(|v| v as u32),
-            date_added: row.get("date_added"),
-            date_remove: row.get("date_remove"),
-        }).collect())
+            match &existing {
+                Some(row) if row.source_id != remote.id => {
+                    counts.skipped += 1;
+                    continue;
+                }
+                Some(row) if row.changed_locally => {
+                    counts.conflicts.push(row.conflict(&key, remote.updated_at));
+                    continue;
+                }
+                _ => {}
+            }
+
+            // uloženie v savepointe, aby odmietnutý produkt nezrušil celú stránku
+            let mut savepoint = tx.begin().await?;
+            let stored = match existing {
+                None => Self::insert_synced_product(&mut savepoint, source, remote.id, &product).await,
+                Some(row) => Self::update_synced_product(&mut savepoint, row.id, source, remote.id, &product).await,
+            };
+            match stored {
+                Ok(()) => {
+                    *if existing_is_new { &mut counts.created } else { &mut counts.updated } += 1;
+                    savepoint.commit().await?;
+                }
+                Err(e) if e.downcast_ref::<StoreError>().is_some() => {
+                    counts.skipped += 1;
+                    savepoint.rollback().await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        tx.commit().await?;
+        Self::record_rows(products.len());
+        self.changed(&[Entity::Products]);
+        Ok(())
+    }
+
+    /// Prevezme stránku objednávok zo zdroja; kľúčom je zdroj a ID objednávky v zdroji.
+    ///
+    /// Objednávky patria zdroju – pri opakovanom prevzatí sa zmení len stav.
+    /// Položky sa priradia k produktom prevzatým z toho istého zdroja a stav
+    /// zásob sa nemení (prevzaté produkty už majú stav zo zdroja). Objednávka
+    /// s neprevzatým produktom sa preskočí.
+    ///
+    /// # Arguments
+    /// * `source` – adresa vzdialenej inštancie
+    /// * `orders` – objednávky zo vzdialenej inštancie
+    /// * `counts` – priebežné počty synchronizácie
+    ///
+    /// # Errors
+    /// Ak zlyhá zápis do databázy
+    #[instrument(skip_all, fields(db.operation.name = "sync_orders", db.rows = Empty))]
+    pub async fn sync_orders(&self, source: &str, orders: &[Order], counts: &mut SyncCounts) -> Result<()> {
+        let mut tx = self.m_pool.begin().await?;
+        for remote in orders {
+            Self::advance_high_water_mark(counts, remote.updated_at);
+            let Some(remote_id) = remote.id else {
+                counts.skipped += 1;
+                continue;
+            };
+            let now = Local::now().naive_local().format(UPDATED_AT_FORMAT).to_string();
+            let existing: Option<i64> =
+                sqlx::query_scalar("SELECT id FROM orders WHERE source_instance = ? AND source_id = ?")
+                    .bind(source)
+                    .bind(remote_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if let Some(id) = existing {
+                sqlx::query("UPDATE orders SET status = COALESCE(?, status), updated_at = ?, synced_at = ? WHERE id = ?")
+                    .bind(remote.status.clone())
+                    .bind(&now)
+                    .bind(&now)
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+                counts.updated += 1;
+                continue;
+            }
+
+            let mut items = Vec::with_capacity(remote.items.len());
+            for item in &remote.items {
+                let product_id: Option<i64> =
+                    sqlx::query_scalar("SELECT id FROM products WHERE source_instance = ? AND source_id = ?")
+                        .bind(source)
+                        .bind(item.product_id)
+                        .fetch_optional(&mut *tx)
+                        .await?;
+                items.extend(product_id.map(|id| (id, item)));
+            }
+            if items.len() != remote.items.len() {
+                counts.skipped += 1;
+                continue;
+            }
+            let employee_id: Option<i64> = match remote.employee_id {
+                Some(id) => sqlx::query_scalar("SELECT id FROM employees WHERE source_instance = ? AND source_id = ?")
+                    .bind(source)
+                    .bind(id)
+                    .fetch_optional(&mut *tx)
+                    .await?,
+                None => None,
+            };
+
+            let order_id = sqlx::query(
+                r#"
+                INSERT INTO orders (employee_id, status, created_at, source_instance, source_id, updated_at, synced_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+                .bind(employee_id)
+                .bind(remote.status.clone().unwrap_or_else(|| "open".into()))
+                .bind(remote.created_at.unwrap_or_else(|| Local::now().naive_local()))
+                .bind(source)
+                .bind(remote_id)
+                .bind(&now)
+                .bind(&now)
+                .execute(&mut *tx)
+                .await?
+                .last_insert_rowid();
+            for (product_id, item) in items {
+                sqlx::query("INSERT INTO order_items (order_id, product_id, quantity, unit_price) VALUES (?, ?, ?, ?)")
+                    .bind(order_id)
+                    .bind(product_id)
+                    .bind(item.quantity)
+                    .bind(item.unit_price.unwrap_or(0.0))
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            counts.created += 1;
+        }
+        tx.commit().await?;
+        Self::record_rows(orders.len());
+        self.changed(&[Entity::Orders]);
+        Ok(())
+    }
+
+    /// Zapíše dokončenú synchronizáciu entity do `sync_log`.
+    ///
+    /// # Arguments
+    /// * `source` – adresa vzdialenej inštancie
+    /// * `entity` – `employees`, `products` alebo `orders`
+    /// * `started_at` – začiatok synchronizácie
+    /// * `counts` – výsledok synchronizácie
+    ///
+    /// # Errors
+    /// Ak zlyhá zápis do databázy
+    #[instrument(skip_all, fields(db.operation.name = "record_sync", db.rows = Empty))]
+    pub async fn record_sync(&self, source: &str, entity: &str, started_at: NaiveDateTime, counts: &SyncCounts) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sync_log (source, entity, started_at, finished_at, created, updated, skipped, conflicts, high_water_mark)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+            .bind(source)
+            .bind(entity)
+            .bind(started_at)
+            .bind(Local::now().naive_local())
+            .bind(counts.created)
+            .bind(counts.updated)
+            .bind(counts.skipped)
+            .bind(serde_json::to_string(&counts.conflicts)?)
+            .bind(counts.high_water_mark.map(|t| t.format(UPDATED_AT_FORMAT).to_string()))
+            .execute(&self.m_pool)
+            .await?;
+        Self::record_rows(1);
+        Ok(())
+    }
+
+    /// Vráti log synchronizácií od najnovšej.
+    ///
+    /// # Returns
+    /// Záznamy `sync_log`
+    #[instrument(skip_all, fields(db.operation.name = "get_sync_log", db.rows = Empty))]
+    pub async fn get_sync_log(&self) -> Result<Vec<SyncLogEntry>> {
+        let rows = sqlx::query("SELECT * FROM sync_log ORDER BY id DESC").fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        rows.iter()
+            .map(|row| {
+                Ok(SyncLogEntry {
+                    id: row.get::<i64, _>("id") as u32,
+                    source: row.get("source"),
+                    entity: row.get("entity"),
+                    started_at: row.get("started_at"),
+                    finished_at: row.get("finished_at"),
+                    counts: SyncCounts {
+                        created: row.get::<i64, _>("created") as u32,
+                        updated: row.get::<i64, _>("updated") as u32,
+                        skipped: row.get::<i64, _>("skipped") as u32,
+                        conflicts: serde_json::from_str(row.get("conflicts"))?,
+                        high_water_mark: row.get("high_water_mark"),
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Posunie najvyšší prevzatý `updated_at`.
+    fn advance_high_water_mark(counts: &mut SyncCounts, updated_at: Option<NaiveDateTime>) {
+        counts.high_water_mark = counts.high_water_mark.max(updated_at);
+    }
+
+    /// Nájde záznam prevzatý zo zdroja podľa kľúča (číselný stĺpec sa porovná ako číslo).
+    async fn synced_row(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        table: &str,
+        key_column: &str,
+        source: &str,
+        key: &str,
+    ) -> Result<Option<SyncedRow>> {
+        let query = format!(
+            "SELECT id, source_id, updated_at, updated_at > synced_at AS changed FROM {table}
+             WHERE source_instance = ? AND {key_column} = ? ORDER BY id LIMIT 1"
+        );
+        let row = sqlx::query(&query)
+            .bind(source)
+            .bind(key)
+            .fetch_optional(&mut **tx)
+            .await?;
+        Ok(row.map(|row| SyncedRow {
+            id: row.get::<i64, _>("id") as u32,
+            source_id: row.get::<Option<i64>, _>("source_id").map(|v| v as u32),
+            updated_at: row.try_get("updated_at").ok().flatten(),
+            changed_locally: row.get::<Option<i64>, _>("changed") == Some(1),
+        }))
+    }
+
+    /// Označí nový záznam ako prevzatý zo zdroja (čas zmeny = čas synchronizácie).
+    async fn mark_synced(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        table: &str,
+        id: u32,
+        source: &str,
+        source_id: Option<u32>,
+    ) -> Result<()> {
+        Self::update_synced(tx, table, id, source, source_id, Assignments::default()).await
+    }
+
+    /// Zapíše zmeny prevzatého záznamu a zosúladí `updated_at` so `synced_at`,
+    /// aby sa neskoršia miestna zmena dala rozpoznať.
+    async fn update_synced(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        table: &str,
+        id: u32,
+        source: &str,
+        source_id: Option<u32>,
+        mut assignments: Assignments<'_>,
+    ) -> Result<()> {
+        let now = Local::now().naive_local().format(UPDATED_AT_FORMAT).to_string();
+        assignments.set("source_instance", Some(source.to_string()));
+        assignments.set("source_id", source_id);
+        assignments.set("updated_at", Some(now.clone()));
+        assignments.set("synced_at", Some(now));
+        let query = format!("UPDATE {table} SET {} WHERE id = ?", assignments.sql());
+        let mut args = assignments.args;
+        let _ = args.add(id);
+        sqlx::query_with(&query, args).execute(&mut **tx).await?;
+        Ok(())
+    }
+
+    /// Vloží produkt prevzatý zo zdroja (s úvodnou šaržou ako pri bežnom pridaní).
+    async fn insert_synced_product(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        source: &str,
+        source_id: Option<u32>,
+        product: &Product,
+    ) -> Result<()> {
+        let id = Self::insert_product(tx, product).await?;
+        Self::mark_synced(tx, "products", id, source, source_id).await
+    }
+
+    /// Zapíše zmeny prevzatého produktu a rozdiel stavu zásob premietne do šarží.
+    async fn update_synced_product(
+        tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        id: u32,
+        source: &str,
+        source_id: Option<u32>,
+        product: &Product,
+    ) -> Result<()> {
+        let current: f64 = sqlx::query_scalar("SELECT quantity FROM products WHERE id = ?")
+            .bind(id)
+            .fetch_one(&mut **tx)
+            .await?;
+        let quantity = product.quantity.unwrap_or(current);
+        Self::validate_unit(product.unit.unwrap_or_default(), quantity, product.pack_size)?;
+        let assignments = Self::product_assignments(product, Some(quantity));
+        Self::update_synced(tx, "products", id, source, source_id, assignments).await?;
+        Self::apply_quantity_delta(tx, id, quantity - current).await
+    }
+
+    // ==========================
+    // Delta export
+    // ==========================
+
+    /// Vráti ID zamestnancov a produktov zmazaných po danom čase.
+    ///
+    /// ID, ktoré medzitým dostal nový záznam, sa vynechá – nový záznam patrí
+    /// medzi zmenené.
+    ///
+    /// # Arguments
+    /// * `since` – miestny čas začiatku rozdielového exportu
+    ///
+    /// # Returns
+    /// Zoradené ID zmazaných záznamov
+    #[instrument(skip_all, fields(db.operation.name = "deleted_since", db.rows = Empty))]
+    pub async fn deleted_since(&self, since: NaiveDateTime) -> Result<DeletedIds, sqlx::Error> {
+        let since = since.format(UPDATED_AT_FORMAT).to_string();
+        let mut deleted = DeletedIds::default();
+        for table in DELTA_TABLES {
+            let ids: Vec<i64> = sqlx::query_scalar(&format!(
+                "SELECT record_id FROM deleted_records
+                 WHERE entity = ? AND deleted_at > ? AND record_id NOT IN (SELECT id FROM {table})
+                 ORDER BY record_id"
+            ))
+                .bind(table)
+                .bind(&since)
+                .fetch_all(&self.m_pool)
+                .await?;
+            let ids = ids.into_iter().map(|id| id as u32).collect();
+            match table {
+                "employees" => deleted.employees = ids,
+                _ => deleted.products = ids,
+            }
+        }
+        Self::record_rows(deleted.employees.len() + deleted.products.len());
+        Ok(deleted)
+    }
+
+    /// Premietne rozdielový export inej inštancie v jednej transakcii.
+    ///
+    /// Databáza je zrkadlom zdroja: záznamy sa párujú podľa ID zo zdroja.
+    /// Najprv sa zmažú záznamy zo zoznamu zmazaných (odkazy na zmazaného
+    /// zamestnanca sa vynulujú, jeho dochádzka sa zmaže), potom sa zmenené
+    /// záznamy prepíšu celé alebo vložia pod svojím ID. Zmena množstva produktu
+    /// sa premietne do šarží; odkaz na zamestnanca, ktorý v databáze nie je,
+    /// sa vynechá.
+    ///
+    /// # Arguments
+    /// * `employees` – zmenení a noví zamestnanci
+    /// * `products` – zmenené a nové produkty
+    /// * `deleted` – ID zmazaných záznamov
+    ///
+    /// # Returns
+    /// Počty zapísaných a zmazaných záznamov
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri zázname bez ID alebo množstve, ktoré nezodpovedá
+    /// jednotke produktu (nezapíše sa nič), inak chyba zápisu do databázy
+    #[instrument(skip_all, fields(db.operation.name = "apply_delta", db.rows = Empty))]
+    pub async fn apply_delta(
+        &self,
+        employees: &[Employee],
+        products: &[Product],
+        deleted: &DeletedIds,
+    ) -> Result<DeltaApplyReport> {
+        let mut report = DeltaApplyReport::default();
+        let mut tx = self.m_pool.begin().await?;
+        // nadriadený môže v rozdiele nasledovať za podriadeným; cudzie kľúče sa overia pri commite
+        sqlx::query("PRAGMA defer_foreign_keys = ON").execute(&mut *tx).await?;
+
+        for &id in &deleted.products {
+            for statement in [
+                "DELETE FROM bundle_components WHERE bundle_id = ? OR component_product_id = ?",
+                "DELETE FROM product_batches WHERE product_id = ? OR product_id = ?",
+                "DELETE FROM stock_lots WHERE product_id = ? OR product_id = ?",
+                "DELETE FROM product_images WHERE product_id = ? OR product_id = ?",
+                "DELETE FROM product_tags WHERE product_id = ? OR product_id = ?",
+            ] {
+                sqlx::query(statement).bind(id).bind(id).execute(&mut *tx).await?;
+            }
+            let result = sqlx::query("DELETE FROM products WHERE id = ?").bind(id).execute(&mut *tx).await?;
+            report.deleted_products += result.rows_affected();
+        }
+        for &id in &deleted.employees {
+            for table in ["products", "orders", "order_returns", "stock_movements"] {
+                sqlx::query(&format!("UPDATE {table} SET employee_id = NULL WHERE employee_id = ?"))
+                    .bind(id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            sqlx::query("DELETE FROM attendance WHERE employee_id = ?").bind(id).execute(&mut *tx).await?;
+            let result = sqlx::query("DELETE FROM employees WHERE id = ?").bind(id).execute(&mut *tx).await?;
+            report.deleted_employees += result.rows_affected();
+        }
+
+        for employee in employees {
+            let id = employee.id.ok_or_else(|| StoreError::Invalid("zamestnanec bez ID".into()))?;
+            sqlx::query(
+                r#"
+                INSERT INTO employees (
+                    id, name, surname, position, department, shift, salary,
+                    phone_number, phone_e164, email, status, note, hire_date, manager_id, date_remove
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name, surname = excluded.surname, position = excluded.position,
+                    department = excluded.department, shift = excluded.shift, salary = excluded.salary,
+                    phone_number = excluded.phone_number, phone_e164 = excluded.phone_e164,
+                    email = excluded.email, status = excluded.status, note = excluded.note,
+                    hire_date = excluded.hire_date, manager_id = excluded.manager_id,
+                    date_remove = excluded.date_remove
+                "#,
+            )
+                .bind(id)
+                .bind(employee.name.clone())
+                .bind(employee.surname.clone())
+                .bind(employee.position.clone())
+                .bind(employee.department.clone())
+                .bind(employee.shift.clone())
+                .bind(employee.salary)
+                .bind(employee.phone_number.clone())
+                .bind(employee.phone_e164.clone())
+                .bind(employee.email.clone())
+                .bind(employee.status)
+                .bind(employee.note.clone())
+                .bind(employee.hire_date)
+                .bind(employee.manager_id)
+                .bind(employee.date_remove)
+                .execute(&mut *tx)
+                .await?;
+            report.employees += 1;
+        }
+
+        for product in products {
+            let id = product.id.ok_or_else(|| StoreError::Invalid("produkt bez ID".into()))?;
+            let unit = product.unit.unwrap_or_default();
+            let is_bundle = product.is_bundle.unwrap_or(false);
+            // množstvo sady sa počíta zo zložiek
+            let quantity = if is_bundle { 0.0 } else { product.quantity.unwrap_or(0.0) };
+            Self::validate_unit(unit, quantity, product.pack_size)?;
+            let current = sqlx::query("SELECT quantity FROM products WHERE id = ?")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .map(|row| Self::quantity_of(&row, "quantity"));
+
+            sqlx::query(
+                r#"
+                INSERT INTO products (
+                    id, name, category, quantity, unit, pack_size, is_bundle, status, bar_code, sku, min_stock,
+                    max_stock, lead_time_days, cost_price, sell_price, description, brand, supplier, employee_id,
+                    date_added, date_remove
+                )
+                VALUES (
+                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, (SELECT id FROM employees WHERE id = ?), ?, ?
+                )
+                ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name, category = excluded.category, quantity = excluded.quantity,
+                    unit = excluded.unit, pack_size = excluded.pack_size, is_bundle = excluded.is_bundle,
+                    status = excluded.status, bar_code = excluded.bar_code, sku = excluded.sku,
+                    min_stock = excluded.min_stock, max_stock = excluded.max_stock,
+                    lead_time_days = excluded.lead_time_days,
+                    cost_price = excluded.cost_price,
+                    sell_price = excluded.sell_price, description = excluded.description, brand = excluded.brand,
+                    supplier = excluded.supplier, employee_id = excluded.employee_id,
+                    date_added = excluded.date_added, date_remove = excluded.date_remove
+                "#,
+            )
+                .bind(id)
+                .bind(product.name.clone())
+                .bind(product.category.clone())
+                .bind(quantity)
+                .bind(unit.as_str())
+                .bind(product.pack_size)
+                .bind(is_bundle)
+                .bind(product.status)
+                .bind(product.bar_code)
+                .bind(product.sku.clone())
+                .bind(product.min_stock)
+                .bind(product.max_stock)
+                .bind(product.lead_time_days)
+                .bind(product.cost_price)
+                .bind(product.sell_price)
+                .bind(product.description.clone())
+                .bind(product.brand.clone())
+                .bind(product.supplier.clone())
+                .bind(product.employee_id)
+                .bind(product.date_added)
+                .bind(product.date_remove)
+                .execute(&mut *tx)
+                .await?;
+            Self::apply_quantity_delta(&mut tx, id, quantity - current.unwrap_or(0.0)).await?;
+            report.products += 1;
+        }
+
+        tx.commit().await?;
+        self.changed(&[Entity::Employees, Entity::Products, Entity::Orders]);
+        Self::record_rows((report.employees + report.products) as usize);
+        Ok(report)
+    }
+
+    // ==========================
+    // Integrity
+    // ==========================
+
+    /// Nájde odkazy (cudzie kľúče) na neexistujúce záznamy, napr. produkty
+    /// priradené zamestnancovi, ktorý v databáze nie je. Nič nemení.
+    ///
+    /// Kontrolujú sa všetky cudzie kľúče deklarované v schéme (`PRAGMA foreign_key_check`).
+    ///
+    /// # Returns
+    /// Neplatné odkazy zoskupené podľa tabuľky a stĺpca
+    #[instrument(skip_all, fields(db.operation.name = "integrity_check", db.rows = Empty))]
+    pub async fn integrity_check(&self) -> Result<IntegrityReport> {
+        let mut conn = self.m_pool.acquire().await?;
+        let report = Self::check_integrity(&mut conn, false).await?;
+        Self::record_rows(report.total as usize);
+        Ok(report)
+    }
+
+    /// Vynuluje neplatné odkazy v stĺpcoch, ktoré pripúšťajú `NULL`
+    /// (zamestnanec produktu, objednávky či vrátenia, nadriadený, objednávka rezervácie).
+    ///
+    /// Neplatné odkazy v povinných stĺpcoch (napr. položka objednávky na zmazaný
+    /// produkt) sa len vrátia v správe s `repairable: false`.
+    ///
+    /// # Returns
+    /// Nájdené neplatné odkazy a počet vynulovaných
+    #[instrument(skip_all, fields(db.operation.name = "repair_integrity", db.rows = Empty))]
+    pub async fn repair_integrity(&self) -> Result<IntegrityReport> {
+        let mut tx = self.m_pool.begin().await?;
+        let report = Self::check_integrity(&mut tx, true).await?;
+        tx.commit().await?;
+        if report.nulled > 0 {
+            self.changed(&[Entity::Employees, Entity::Products, Entity::Orders]);
+        }
+        Self::record_rows(report.nulled as usize);
+        Ok(report)
+    }
+
+    /// Zistí (a pri `repair` vynuluje opraviteľné) odkazy na neexistujúce záznamy.
+    async fn check_integrity(conn: &mut SqliteConnection, repair: bool) -> Result<IntegrityReport> {
+        let violations = sqlx::query("PRAGMA foreign_key_check").fetch_all(&mut *conn).await?;
+
+        // (tabuľka, id cudzieho kľúča) → ROWID riadkov s neplatným odkazom
+        let mut groups: BTreeMap<(String, i64), Vec<i64>> = BTreeMap::new();
+        for row in &violations {
+            let table: String = row.get(0);
+            let Some(rowid) = row.get::<Option<i64>, _>(1) else { continue };
+            groups.entry((table, row.get(3))).or_default().push(rowid);
+        }
+
+        let mut report = IntegrityReport { repaired: repair, ..IntegrityReport::default() };
+        for ((table, fk_id), mut row_ids) in groups {
+            let foreign_key = sqlx::query(&format!("PRAGMA foreign_key_list({table})"))
+                .fetch_all(&mut *conn)
+                .await?
+                .into_iter()
+                .find(|fk| fk.get::<i64, _>("id") == fk_id);
+            let Some(foreign_key) = foreign_key else { continue };
+            let column: String = foreign_key.get("from");
+            let references: String = foreign_key.get("table");
+            let repairable = sqlx::query(&format!("PRAGMA table_info({table})"))
+                .fetch_all(&mut *conn)
+                .await?
+                .iter()
+                .any(|c| c.get::<String, _>("name") == column && c.get::<i64, _>("notnull") == 0);
+
+            row_ids.sort_unstable();
+            let mut missing_ids = Vec::new();
+            for &rowid in &row_ids {
+                let missing: Option<i64> = sqlx::query_scalar(&format!("SELECT {column} FROM {table} WHERE rowid = ?"))
+                    .bind(rowid)
+                    .fetch_one(&mut *conn)
+                    .await?;
+                missing_ids.extend(missing);
+            }
+            missing_ids.sort_unstable();
+            missing_ids.dedup();
+
+            if repair && repairable {
+                for &rowid in &row_ids {
+                    let result = sqlx::query(&format!("UPDATE {table} SET {column} = NULL WHERE rowid = ?"))
+                        .bind(rowid)
+                        .execute(&mut *conn)
+                        .await?;
+                    report.nulled += result.rows_affected();
+                }
+            }
+            report.total += row_ids.len() as u64;
+            report.orphans.push(OrphanedReferences { table, column, references, row_ids, missing_ids, repairable });
+        }
+        Ok(report)
+    }
+
+    // ==========================
+    // API keys
+    // ==========================
+
+    /// Vydá zamestnancovi nový API kľúč.
+    ///
+    /// Uloží sa len hash kľúča (Argon2id); celý kľúč sa vráti iba raz, v odpovedi.
+    ///
+    /// # Arguments
+    /// * `request` – zamestnanec, rola a voliteľný názov kľúča
+    ///
+    /// # Returns
+    /// Záznam kľúča a celý kľúč
+    ///
+    /// # Errors
+    /// `StoreError::NotFound` ak zamestnanec neexistuje, `StoreError::Conflict`
+    /// ak je zamestnanec vyradený
+    #[instrument(skip_all, fields(db.operation.name = "create_api_key", db.rows = Empty))]
+    pub async fn create_api_key(&self, request: &ApiKeyRequest) -> Result<CreatedApiKey> {
+        let status: Option<Option<bool>> = sqlx::query_scalar("SELECT status FROM employees WHERE id = ?")
+            .bind(request.employee_id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        match status {
+            None => return Err(StoreError::NotFound(format!("zamestnanec {}", request.employee_id)).into()),
+            Some(Some(false)) => {
+                return Err(StoreError::Conflict(format!("zamestnanec {} je vyradený", request.employee_id)).into())
+            }
+            Some(_) => {}
+        }
+
+        let (key_id, key) = auth::generate_api_key();
+        let key_hash = auth::hash_api_key(&key)?;
+        let role = request.role.unwrap_or(Role::Viewer);
+        let created_at = Local::now().naive_local();
+        let id = sqlx::query(
+            "INSERT INTO api_keys (employee_id, name, role, key_id, key_hash, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+            .bind(request.employee_id)
+            .bind(&request.name)
+            .bind(role.as_str())
+            .bind(&key_id)
+            .bind(key_hash)
+            .bind(created_at)
+            .execute(&self.m_pool)
+            .await?
+            .last_insert_rowid();
+        Self::record_rows(1);
+
+        Ok(CreatedApiKey {
+            info: ApiKey {
+                id: id as u32,
+                employee_id: request.employee_id,
+                name: request.name.clone(),
+                role,
+                key_id,
+                created_at,
+                revoked_at: None,
+            },
+            api_key: key,
+        })
+    }
+
+    /// Vráti API kľúče zamestnancov (bez hashov), od najstaršieho.
+    #[instrument(skip_all, fields(db.operation.name = "list_api_keys", db.rows = Empty))]
+    pub async fn list_api_keys(&self) -> Result<Vec<ApiKey>> {
+        let rows = sqlx::query("SELECT * FROM api_keys ORDER BY id").fetch_all(&self.m_pool).await?;
+        Self::record_rows(rows.len());
+        Ok(rows.iter().map(Self::api_key_from_row).collect())
+    }
+
+    /// Overí API kľúč zamestnanca voči uloženému hashu.
+    ///
+    /// Odvolaný kľúč a kľúč vyradeného či vymazaného zamestnanca sa neoverí.
+    ///
+    /// # Arguments
+    /// * `key` – celý API kľúč
+    ///
+    /// # Returns
+    /// Záznam kľúča alebo `None`, ak kľúč nie je platný
+    #[instrument(skip_all, fields(db.operation.name = "verify_api_key", db.rows = Empty))]
+    pub async fn verify_api_key(&self, key: &str) -> Result<Option<ApiKey>> {
+        let Some(key_id) = auth::api_key_id(key) else {
+            return Ok(None);
+        };
+        let row = sqlx::query(
+            r#"
+            SELECT k.*
+            FROM api_keys k
+            JOIN employees e ON e.id = k.employee_id
+            WHERE k.key_id = ? AND k.revoked_at IS NULL AND COALESCE(e.status, 1) = 1
+            "#,
+        )
+            .bind(key_id)
+            .fetch_optional(&self.m_pool)
+            .await?;
+        Self::record_rows(row.iter().len());
+        Ok(row
+            .filter(|r| auth::verify_api_key_hash(key, r.get("key_hash")))
+            .map(|r| Self::api_key_from_row(&r)))
+    }
+
+    /// Odvolá API kľúč zamestnanca; opakované odvolanie pôvodný čas nezmení.
+    ///
+    /// # Arguments
+    /// * `id` – ID kľúča
+    ///
+    /// # Returns
+    /// `true` ak kľúč existuje
+    #[instrument(skip_all, fields(db.operation.name = "revoke_api_key", db.rows = Empty))]
+    pub async fn revoke_api_key(&self, id: u32) -> Result<bool> {
+        let result = sqlx::query("UPDATE api_keys SET revoked_at = COALESCE(revoked_at, ?) WHERE id = ?")
+            .bind(Local::now().naive_local())
+            .bind(id)
+            .execute(&self.m_pool)
+            .await?;
+        Self::record_rows(result.rows_affected() as usize);
+        Ok(result.rows_affected() > 0)
+    }
+
+    fn api_key_from_row(r: &SqliteRow) -> ApiKey {
+        ApiKey {
+            id: r.get::<i64, _>("id") as u32,
+            employee_id: r.get::<i64, _>("employee_id") as u32,
+            name: r.get("name"),
+            role: Role::from_claim(Some(r.get("role"))),
+            key_id: r.get("key_id"),
+            created_at: r.get("created_at"),
+            revoked_at: r.get("revoked_at"),
+        }
+    }
+
+    // ==========================
+    // Schema
+    // ==========================
+
+    /// Vypíše schému databázy ako SQL DDL zo `sqlite_master`.
+    ///
+    /// Obsahuje tabuľky, pohľady, indexy a triggery v poradí, v akom sa dajú
+    /// znovu vytvoriť (tabuľky pred indexmi a triggermi). Interné objekty
+    /// SQLite (`sqlite_*`, automatické indexy) sa vynechajú.
+    ///
+    /// # Returns
+    /// Príkazy `CREATE` ukončené bodkočiarkou
+    #[instrument(skip_all, fields(db.operation.name = "dump_schema", db.rows = Empty))]
+    pub async fn dump_schema(&self) -> Result<String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT type, name, sql
+            FROM sqlite_master
+            WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%'
+            ORDER BY CASE type WHEN 'table' THEN 0 WHEN 'view' THEN 1 WHEN 'index' THEN 2 ELSE 3 END, name
+            "#,
+        )
+            .fetch_all(&self.m_pool)
+            .await?;
+        Self::record_rows(rows.len());
+
+        let mut schema = String::from("-- Schéma databázy store_manager\n\n");
+        let mut last_type = String::new();
+        for row in &rows {
+            let kind: String = row.get("type");
+            if kind != last_type {
+                schema.push_str(&format!("-- {kind}\n"));
+                last_type = kind;
+            }
+            let sql: String = row.get("sql");
+            schema.push_str(sql.trim().trim_end_matches(';'));
+            schema.push_str(";\n\n");
+        }
+        Ok(schema)
+    }
+
+    /// Zistí režim žurnálu databázy (`PRAGMA journal_mode`, napr. `wal`).
+    #[instrument(skip_all, fields(db.operation.name = "journal_mode", db.rows = Empty))]
+    pub async fn journal_mode(&self) -> Result<String> {
+        let mode: String = sqlx::query_scalar("PRAGMA journal_mode").fetch_one(&self.m_pool).await?;
+        Self::record_rows(1);
+        Ok(mode)
     }
 }
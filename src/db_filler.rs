@@ -1,16 +1,184 @@
 use crate::db::StoreDB;
-use crate::structs::{Employee, Product};
+use crate::error::StoreError;
+use crate::pricing::MarginPolicy;
+use crate::structs::{
+    Bundle, DataImportReport, DeletedIds, DeltaApplyReport, Employee, FieldChange, ImportDiff, Product, ProductTranslation, PurchaseOrder,
+    RecordUpdate, SkippedRecord, TableDiff,
+};
 use anyhow::Result;
+use chrono::{DateTime, Local, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
 use std::fs::File;
+use std::future::Future;
 use std::io::{BufReader, BufWriter};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Časová značka v názve zálohy JSON súboru (`20240601T083000123456`).
+const BACKUP_STAMP_FORMAT: &str = "%Y%m%dT%H%M%S%6f";
+
+/// Dĺžka časovej značky zálohy.
+const BACKUP_STAMP_LEN: usize = 21;
 
 /// Pomocná štruktúra pre uloženie celého stavu databázy do súboru.
 #[derive(Serialize, Deserialize)]
 struct StoreData {
     employees: Vec<Employee>,
     products: Vec<Product>,
+    #[serde(default)]
+    purchase_orders: Vec<PurchaseOrder>,
+    #[serde(default)]
+    bundles: Vec<Bundle>,
+    #[serde(default)]
+    translations: Vec<ProductTranslation>,
+    /// Polia, ktoré export zámerne vynechal (prázdne nie sú chýbajúce dáta)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    omitted: Vec<String>,
+    /// Osobné údaje zamestnancov sú nahradené (`anonymize`); súbor nepatrí do produkcie
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    anonymized: bool,
+}
+
+/// Verzia formátu rozdielového exportu (`export_changes_since`).
+pub const DELTA_VERSION: u32 = 1;
+
+/// Rozdielový export: zamestnanci a produkty zmenení po čase `since`
+/// a ID záznamov zmazaných v tom istom období.
+#[derive(Serialize, Deserialize)]
+struct DeltaData {
+    /// Verzia formátu; iná ako `DELTA_VERSION` sa odmietne
+    version: u32,
+    /// Začiatok obdobia (ako ho zadal volajúci)
+    since: DateTime<Utc>,
+    /// Čas vytvorenia exportu – `since` ďalšieho rozdielového exportu
+    until: DateTime<Utc>,
+    #[serde(default)]
+    employees: Vec<Employee>,
+    #[serde(default)]
+    products: Vec<Product>,
+    #[serde(default)]
+    deleted: DeletedIds,
+}
+
+/// Šírka pásma, na ktorého spodnú hranicu sa pri anonymizácii zaokrúhli mzda.
+const SALARY_BUCKET: f64 = 500.0;
+
+/// Pseudonymy osobných údajov v rámci jedného exportu.
+///
+/// Rovnaká hodnota dostane vždy rovnaký pseudonym, vzťahy medzi záznamami
+/// (napr. zamestnanci s rovnakým priezviskom) sa preto dajú ďalej analyzovať.
+#[derive(Default)]
+struct Pseudonyms {
+    values: HashMap<&'static str, HashMap<String, usize>>,
+}
+
+impl Pseudonyms {
+    /// Vráti poradové číslo pseudonymu hodnoty v rámci druhu `kind` (od 1).
+    fn number(&mut self, kind: &'static str, value: &str) -> usize {
+        let values = self.values.entry(kind).or_default();
+        let next = values.len() + 1;
+        *values.entry(value.trim().to_lowercase()).or_insert(next)
+    }
+
+    /// Nahradí osobné údaje zamestnanca: meno, priezvisko, telefón a e-mail
+    /// pseudonymami, mzdu zaokrúhli na pásmo `SALARY_BUCKET` a vymaže poznámku.
+    fn anonymize(&mut self, employee: &mut Employee) {
+        if let Some(name) = &employee.name {
+            employee.name = Some(format!("Meno{}", self.number("name", name)));
+        }
+        if let Some(surname) = &employee.surname {
+            employee.surname = Some(format!("Priezvisko{}", self.number("surname", surname)));
+        }
+        let phone = employee.phone_e164.as_ref().or(employee.phone_number.as_ref());
+        employee.phone_number = phone.map(|phone| format!("+421900{:06}", self.number("phone", phone)));
+        employee.phone_e164 = employee.phone_number.clone();
+        if let Some(email) = &employee.email {
+            employee.email = Some(format!("zamestnanec{}@example.com", self.number("email", email)));
+        }
+        employee.salary = employee.salary.map(|salary| (salary / SALARY_BUCKET).floor() * SALARY_BUCKET);
+        employee.note = None;
+    }
+}
+
+/// Výber entít pri exporte a importe (`--only products` / `--only employees`).
+///
+/// Katalóg produktov zahŕňa aj sady, preklady a objednávky u dodávateľov;
+/// zamestnanci (vrátane miezd) sa s ním neprenášajú.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntitySelection {
+    /// Všetky dáta
+    #[default]
+    All,
+    /// Len katalóg produktov
+    Products,
+    /// Len zamestnanci
+    Employees,
+}
+
+impl EntitySelection {
+    /// Či výber zahŕňa produkty (a s nimi sady, preklady a objednávky).
+    pub fn products(self) -> bool {
+        self != EntitySelection::Employees
+    }
+
+    /// Či výber zahŕňa zamestnancov.
+    pub fn employees(self) -> bool {
+        self != EntitySelection::Products
+    }
+
+    /// Názov výberu v parametri `only`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntitySelection::All => "all",
+            EntitySelection::Products => "products",
+            EntitySelection::Employees => "employees",
+        }
+    }
+
+    /// Polia súboru, ktoré výber vynecháva.
+    fn omitted(self) -> Vec<String> {
+        let fields: &[&str] = match self {
+            EntitySelection::All => &[],
+            EntitySelection::Products => &["employees"],
+            EntitySelection::Employees => &["products", "purchase_orders", "bundles", "translations"],
+        };
+        fields.iter().map(|f| f.to_string()).collect()
+    }
+}
+
+impl fmt::Display for EntitySelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EntitySelection {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "all" => Ok(EntitySelection::All),
+            "products" => Ok(EntitySelection::Products),
+            "employees" => Ok(EntitySelection::Employees),
+            other => Err(format!("neznámy výber {other:?} (products, employees)")),
+        }
+    }
+}
+
+/// Správanie načítania JSON súboru pri chybnom zázname.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadMode {
+    /// Záznamy sa zapisujú po jednom; prvá chyba načítanie ukončí a už
+    /// zapísané záznamy zostanú v databáze
+    #[default]
+    Sequential,
+    /// Všetko sa zapisuje v jednej transakcii, každý záznam vo vlastnom
+    /// savepointe; chybné záznamy sa vrátia späť a preskočia, ostatné sa potvrdia
+    BestEffort,
 }
 
 /// Nástroj na import a export databázových dát.
@@ -23,37 +191,340 @@ impl DBFiller {
     /// # Arguments
     /// * `db` – databáza, do ktorej sa majú dáta vložiť
     /// * `file_path` – cesta k JSON súboru
+    /// * `mode` – správanie pri chybnom zázname
+    ///
+    /// # Returns
+    /// Počty vložených a preskočených záznamov (prázdne, ak súbor neexistuje)
+    ///
+    /// # Errors
+    /// Ak sa nepodarí otvoriť súbor, načítať JSON alebo zapísať do databázy
+    /// (v režime `BestEffort` len ak zlyhá samotná transakcia)
+    pub async fn load_from_json(db: &StoreDB, file_path: &str, mode: LoadMode) -> Result<DataImportReport> {
+        Self::load_selected_from_json(db, file_path, EntitySelection::All, mode, |_| {}).await
+    }
+
+    /// Načíta dáta z JSON súboru do databázy a priebežne hlási počet načítaných záznamov.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, do ktorej sa majú dáta vložiť
+    /// * `file_path` – cesta k JSON súboru
+    /// * `progress` – volá sa po každom vloženom zázname s doterajším počtom
     ///
     /// # Returns
     /// `Ok(())` ak sa načítanie podarilo alebo súbor neexistuje
     ///
     /// # Errors
     /// Ak sa nepodarí otvoriť súbor, načítať JSON alebo zapísať do databázy
-    pub async fn load_from_json(db: &StoreDB, file_path: &str) -> Result<()> {
+    pub async fn load_from_json_with_progress(db: &StoreDB, file_path: &str, progress: impl Fn(u64)) -> Result<()> {
+        Self::load_selected_from_json(db, file_path, EntitySelection::All, LoadMode::Sequential, progress).await?;
+        Ok(())
+    }
+
+    /// Načíta z JSON súboru len vybrané entity; ostatné polia súboru sa ignorujú.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, do ktorej sa majú dáta vložiť
+    /// * `file_path` – cesta k JSON súboru
+    /// * `only` – výber entít
+    /// * `mode` – správanie pri chybnom zázname
+    /// * `progress` – volá sa po každom vloženom zázname s doterajším počtom
+    ///
+    /// # Returns
+    /// Počty vložených a preskočených záznamov (prázdne, ak súbor neexistuje)
+    ///
+    /// # Errors
+    /// Ak sa nepodarí otvoriť súbor, načítať JSON alebo zapísať do databázy;
+    /// anonymizovaný súbor je `StoreError::Conflict`
+    pub async fn load_selected_from_json(
+        db: &StoreDB,
+        file_path: &str,
+        only: EntitySelection,
+        mode: LoadMode,
+        progress: impl Fn(u64),
+    ) -> Result<DataImportReport> {
         if !Path::new(file_path).exists() {
             println!("JSON  súbor {} neexistuje", file_path);
-            return Ok(());
+            return Ok(DataImportReport::default());
         }
 
-        let file = File::open(file_path)?;
-        let reader = BufReader::new(file);
-
-        let data: StoreData = serde_json::from_reader(reader)?;
+        // čítanie a parsovanie súboru blokuje, beží preto v blokujúcom vlákne
+        let path = file_path.to_string();
+        let data: StoreData = tokio::task::spawn_blocking(move || -> Result<StoreData> {
+            let reader = BufReader::new(File::open(path)?);
+            Ok(serde_json::from_reader(reader)?)
+        })
+            .await??;
 
         println!("Načitávam dáta z JSON-u");
+        let report = Self::load_data(db, data, only, mode, false, progress).await?;
+        if !report.skipped.is_empty() {
+            println!("Preskočených záznamov: {}", report.skipped.len());
+        }
+        println!("Databáza načitana úspešne z {}", file_path);
+        Ok(report)
+    }
 
-        for employee in data.employees {
-            db.add_employee_to_store_db(&employee).await?;
+    /// Načíta vybrané entity z obsahu JSON súboru (už načítaného do pamäte).
+    ///
+    /// # Arguments
+    /// * `db` – databáza, do ktorej sa majú dáta vložiť
+    /// * `json` – obsah súboru vo formáte `save_to_json`
+    /// * `only` – výber entít
+    /// * `margin` – politika minimálnej marže (`None` = bez kontroly)
+    /// * `allow_anonymized` – povolí import anonymizovaného exportu (vývoj, testy)
+    ///
+    /// # Returns
+    /// Počty vložených záznamov a ignorované polia súboru
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri neplatnom JSONe, `StoreError::PolicyViolation` ak
+    /// niektorý importovaný produkt nespĺňa minimálnu maržu (nevloží sa nič),
+    /// `StoreError::Conflict` pri anonymizovanom súbore bez `allow_anonymized`,
+    /// inak chyba zápisu do databázy
+    pub async fn load_from_slice(
+        db: &StoreDB,
+        json: &[u8],
+        only: EntitySelection,
+        margin: Option<&MarginPolicy>,
+        allow_anonymized: bool,
+    ) -> Result<DataImportReport> {
+        let data: StoreData = serde_json::from_slice(json)
+            .map_err(|e| StoreError::Invalid(format!("neplatný JSON súbor: {e}")))?;
+        if let Some(policy) = margin.filter(|_| only.products()) {
+            let violations: Vec<String> = data
+                .products
+                .iter()
+                .filter_map(|p| {
+                    let shortfall = policy.check(p.category.as_deref(), p.cost_price, p.sell_price?)?;
+                    Some(format!("{}: {shortfall}", p.name.as_deref().unwrap_or("?")))
+                })
+                .collect();
+            if !violations.is_empty() {
+                return Err(StoreError::PolicyViolation(format!(
+                    "{} produktov pod minimálnou maržou: {}",
+                    violations.len(),
+                    violations.join("; ")
+                ))
+                    .into());
+            }
+        }
+        Self::load_data(db, data, only, LoadMode::Sequential, allow_anonymized, |_| {}).await
+    }
+
+    /// Vloží vybrané entity do databázy; neprázdne nevybrané polia sa zalogujú ako ignorované.
+    ///
+    /// Anonymizovaný súbor sa bez `allow_anonymized` odmietne skôr, než sa čokoľvek zapíše.
+    async fn load_data(
+        db: &StoreDB,
+        mut data: StoreData,
+        only: EntitySelection,
+        mode: LoadMode,
+        allow_anonymized: bool,
+        progress: impl Fn(u64),
+    ) -> Result<DataImportReport> {
+        if data.anonymized {
+            if !allow_anonymized {
+                return Err(StoreError::Conflict(
+                    "súbor obsahuje anonymizované dáta a nepatrí do produkčnej databázy".into(),
+                )
+                    .into());
+            }
+            println!("Varovanie: importujú sa anonymizované dáta");
+        }
+        let mut report = DataImportReport { anonymized: data.anonymized, ..DataImportReport::default() };
+        let present = [
+            ("employees", !data.employees.is_empty()),
+            ("products", !data.products.is_empty()),
+            ("purchase_orders", !data.purchase_orders.is_empty()),
+            ("bundles", !data.bundles.is_empty()),
+            ("translations", !data.translations.is_empty()),
+        ];
+        for field in only.omitted() {
+            if present.iter().any(|(name, non_empty)| *name == field && *non_empty) {
+                println!("Import len {only}: pole {field} zo súboru sa ignoruje");
+                report.ignored.push(field);
+            }
+        }
+        if !only.employees() {
+            data.employees.clear();
+        }
+        if !only.products() {
+            data.products.clear();
+            data.bundles.clear();
+            data.translations.clear();
+            data.purchase_orders.clear();
         }
 
-        for product in data.products {
-            db.add_product_to_store_db(&product).await?;
+        // v režime best effort sa všetko zapisuje v jednej relácii (transakcii)
+        let session;
+        let target = match mode {
+            LoadMode::Sequential => db,
+            LoadMode::BestEffort => {
+                session = db.begin_session().await?;
+                &session
+            }
+        };
+        let written = Self::write_data(target, data, mode, &mut report, progress).await;
+        if mode == LoadMode::BestEffort {
+            match &written {
+                Ok(()) => target.commit_session().await?,
+                Err(_) => target.rollback_session().await?,
+            }
+        }
+        written?;
+        Ok(report)
+    }
+
+    /// Zapíše záznamy súboru do databázy (pri `BestEffort` do relácie) a doplní počty do `report`.
+    async fn write_data(
+        db: &StoreDB,
+        data: StoreData,
+        mode: LoadMode,
+        report: &mut DataImportReport,
+        progress: impl Fn(u64),
+    ) -> Result<()> {
+        let mut loaded = 0;
+        let mut tick = || {
+            loaded += 1;
+            progress(loaded);
+        };
+        let skipped = &mut report.skipped;
+
+        // nadriadený môže byť v súbore až za podriadeným: zamestnanci sa vložia bez
+        // nadriadených a tí sa doplnia podľa nových ID
+        let mut new_ids = HashMap::new();
+        let mut managers = Vec::new();
+        for (index, mut employee) in data.employees.into_iter().enumerate() {
+            let manager_id = employee.manager_id.take().filter(|&m| m != 0);
+            let inserted = db.add_employee_to_store_db(&employee);
+            let Some(id) = Self::write_record(db, mode, skipped, "employees", index, inserted).await? else {
+                continue;
+            };
+            if let Some(old_id) = employee.id {
+                new_ids.insert(old_id, id);
+            }
+            if let Some(manager_id) = manager_id {
+                managers.push((index, id, manager_id));
+            }
+            report.employees += 1;
+            tick();
+        }
+        for (index, id, manager_id) in managers {
+            let mut employee = Employee::new_empty();
+            employee.id = Some(id);
+            employee.manager_id = Some(new_ids.get(&manager_id).copied().unwrap_or(manager_id));
+            let updated = db.update_employee(&employee);
+            Self::write_record(db, mode, skipped, "employees", index, updated).await?;
+        }
+
+        for (index, product) in data.products.iter().enumerate() {
+            let inserted = db.add_product_to_store_db(product);
+            if Self::write_record(db, mode, skipped, "products", index, inserted).await?.is_some() {
+                report.products += 1;
+                tick();
+            }
+        }
+
+        for (index, bundle) in data.bundles.iter().enumerate() {
+            let updated = db.set_bundle_components(bundle.bundle_id, &bundle.components);
+            if Self::write_record(db, mode, skipped, "bundles", index, updated).await?.is_some() {
+                tick();
+            }
+        }
+
+        for (index, translation) in data.translations.iter().enumerate() {
+            let updated = db.set_product_translation(translation);
+            if Self::write_record(db, mode, skipped, "translations", index, updated).await?.is_some() {
+                tick();
+            }
+        }
+
+        for (index, po) in data.purchase_orders.iter().enumerate() {
+            let inserted = db.add_purchase_order(po);
+            if Self::write_record(db, mode, skipped, "purchase_orders", index, inserted).await?.is_some() {
+                tick();
+            }
         }
 
-        println!("Databáza načitana úspešne z {}", file_path);
         Ok(())
     }
 
+    /// Zapíše jeden záznam podľa režimu načítania.
+    ///
+    /// V režime `Sequential` chyba ukončí načítanie. V režime `BestEffort` sa zápis
+    /// vykoná vo vlastnom savepointe; pri chybe sa vráti späť a záznam sa pridá
+    /// do `skipped` aj s dôvodom.
+    ///
+    /// # Returns
+    /// Výsledok zápisu, alebo `None`, ak sa záznam preskočil
+    async fn write_record<T>(
+        db: &StoreDB,
+        mode: LoadMode,
+        skipped: &mut Vec<SkippedRecord>,
+        entity: &str,
+        index: usize,
+        write: impl Future<Output = Result<T>>,
+    ) -> Result<Option<T>> {
+        match mode {
+            LoadMode::Sequential => write.await.map(Some),
+            LoadMode::BestEffort => match db.savepoint(write).await {
+                Ok(value) => Ok(Some(value)),
+                Err(e) => {
+                    println!("Import: {entity}[{index}] sa preskočil: {e}");
+                    skipped.push(SkippedRecord { entity: entity.to_string(), index: index as u64, reason: e.to_string() });
+                    Ok(None)
+                }
+            },
+        }
+    }
+
+    /// Porovná JSON súbor s aktuálnou databázou bez zápisu (náhľad pred importom).
+    ///
+    /// Produkty sa párujú podľa čiarového kódu, zamestnanci podľa e-mailu
+    /// (bez ohľadu na veľkosť písmen). Záznamy bez kľúča sa hlásia ako nové.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, s ktorou sa porovnáva
+    /// * `file_path` – cesta k JSON súboru vo formáte `save_to_json`
+    ///
+    /// # Returns
+    /// Pridané, zmenené (so zoznamom zmenených polí) a zmazané záznamy
+    ///
+    /// # Errors
+    /// Ak sa nepodarí otvoriť súbor alebo čítať z databázy; neplatný JSON
+    /// je `StoreError::Invalid`
+    pub async fn diff_against_json(db: &StoreDB, file_path: &str) -> Result<ImportDiff> {
+        let path = file_path.to_string();
+        let bytes = tokio::task::spawn_blocking(move || std::fs::read(path)).await??;
+        Self::diff_against_slice(db, &bytes).await
+    }
+
+    /// Porovná obsah JSON súboru (už načítaný do pamäte) s aktuálnou databázou.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, s ktorou sa porovnáva
+    /// * `json` – obsah súboru vo formáte `save_to_json`
+    ///
+    /// # Returns
+    /// Rozdiel ako pri `diff_against_json`
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri neplatnom JSONe, inak chyba čítania z databázy
+    pub async fn diff_against_slice(db: &StoreDB, json: &[u8]) -> Result<ImportDiff> {
+        let data: StoreData = serde_json::from_slice(json)
+            .map_err(|e| StoreError::Invalid(format!("neplatný JSON súbor: {e}")))?;
+
+        let employees = db.get_employees(Employee::new_empty()).await?;
+        let products = db.get_products(Product::new_empty()).await?;
+
+        Ok(ImportDiff {
+            employees: diff_table(employees, data.employees, |e| {
+                e.email.as_deref().map(|email| email.trim().to_lowercase()).filter(|email| !email.is_empty())
+            })?,
+            products: diff_table(products, data.products, |p| p.bar_code.map(|code| code.to_string()))?,
+        })
+    }
+
     /// Uloží obsah databázy do JSON súboru.
     ///
     /// # Arguments
@@ -66,20 +537,342 @@ impl DBFiller {
     /// # Errors
     /// Ak zlyhá čítanie z databázy, zápis do súboru alebo serializácia
     pub async fn save_to_json(db: &StoreDB, file_path: &str) -> Result<()> {
-        let employees = db.get_employees(Employee::new_empty()).await?;
-        let products = db.get_products(Product::new_empty()).await?;
+        Self::save_selected_to_json(db, file_path, EntitySelection::All, false).await
+    }
 
-        let data = StoreData {
-            employees,
-            products,
+    /// Uloží obsah databázy do JSON súboru a predchádzajúci obsah odloží ako zálohu.
+    ///
+    /// Existujúci súbor sa premenuje na `<súbor>.<RRRRMMDDTHHMMSSffffff>.bak`
+    /// v tom istom adresári a zo záloh sa ponechá `keep` najnovších. Maže sa len
+    /// súbor, ktorého názov presne zodpovedá tomuto tvaru pre daný súbor, takže
+    /// iné súbory v adresári (aj zálohy iných súborov) zostanú nedotknuté.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, z ktorej sa čítajú dáta
+    /// * `file_path` – cieľový súbor
+    /// * `keep` – počet ponechaných záloh (0 = bez zálohy, len uloženie)
+    ///
+    /// # Returns
+    /// Cesta k novej zálohe alebo `None`, ak sa záloha nerobila
+    ///
+    /// # Errors
+    /// Ak zlyhá premenovanie, zápis alebo mazanie starých záloh
+    pub async fn save_to_json_with_backup(db: &StoreDB, file_path: &str, keep: usize) -> Result<Option<PathBuf>> {
+        let path = Path::new(file_path);
+        let backup = if keep > 0 && path.exists() {
+            let name = path.file_name().ok_or_else(|| anyhow::anyhow!("neplatný súbor {file_path}"))?;
+            let stamp = Local::now().format(BACKUP_STAMP_FORMAT);
+            let backup = path.with_file_name(format!("{}.{stamp}.bak", name.to_string_lossy()));
+            std::fs::rename(path, &backup)?;
+            Some(backup)
+        } else {
+            None
+        };
+
+        Self::save_to_json(db, file_path).await?;
+        if keep > 0 {
+            for old in Self::backups_of(path)?.iter().rev().skip(keep) {
+                std::fs::remove_file(old)?;
+            }
+        }
+        Ok(backup)
+    }
+
+    /// Vráti zálohy súboru od najstaršej po najnovšiu.
+    ///
+    /// # Arguments
+    /// * `path` – zálohovaný súbor
+    ///
+    /// # Returns
+    /// Cesty k súborom `<súbor>.<RRRRMMDDTHHMMSSffffff>.bak` v jeho adresári
+    ///
+    /// # Errors
+    /// Ak sa adresár nedá prečítať
+    pub fn backups_of(path: &Path) -> Result<Vec<PathBuf>> {
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+            return Ok(Vec::new());
+        };
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
         };
 
-        let file = File::create(file_path)?;
-        let writer = BufWriter::new(file);
+        let mut backups: Vec<(String, PathBuf)> = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let stamp = file_name
+                .strip_prefix(name.as_str())
+                .and_then(|rest| rest.strip_prefix('.'))
+                .and_then(|rest| rest.strip_suffix(".bak"));
+            let Some(stamp) = stamp else { continue };
+            // pevná dĺžka: abecedné poradie je aj časové
+            if stamp.len() == BACKUP_STAMP_LEN
+                && chrono::NaiveDateTime::parse_from_str(stamp, BACKUP_STAMP_FORMAT).is_ok()
+                && entry.file_type()?.is_file()
+            {
+                backups.push((stamp.to_string(), entry.path()));
+            }
+        }
+        backups.sort();
+        Ok(backups.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Uloží do JSON súboru len vybrané entity.
+    ///
+    /// Nevybrané polia sú v súbore prázdne a uvedené v poli `omitted`.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, z ktorej sa čítajú dáta
+    /// * `file_path` – cieľový súbor
+    /// * `only` – výber entít
+    /// * `anonymize` – nahradí osobné údaje zamestnancov (pozri `export_to_vec`)
+    ///
+    /// # Errors
+    /// Ak zlyhá čítanie z databázy, zápis do súboru alebo serializácia
+    pub async fn save_selected_to_json(
+        db: &StoreDB,
+        file_path: &str,
+        only: EntitySelection,
+        anonymize: bool,
+    ) -> Result<()> {
+        let data = Self::collect(db, only, anonymize).await?;
 
-        serde_json::to_writer_pretty(writer, &data)?;
+        let path = file_path.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let writer = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(writer, &data)?;
+            Ok(())
+        })
+            .await??;
 
         println!("Databáza uložená do JSON-u {}", file_path);
         Ok(())
     }
+
+    /// Vráti vybrané entity v rovnakom formáte ako `save_to_json`.
+    ///
+    /// Anonymizovaný export (na zdieľanie mimo firmy) nahradí mená, priezviská,
+    /// telefóny a e-maily zamestnancov pseudonymami, ktoré sú v rámci exportu
+    /// stále rovnaké pre rovnakú hodnotu, mzdy zaokrúhli na pásma a vymaže
+    /// poznámky. Produkty zostanú nezmenené. Súbor má príznak `anonymized`
+    /// a bežný import ho odmietne (`load_from_slice`).
+    ///
+    /// # Arguments
+    /// * `db` – databáza, z ktorej sa čítajú dáta
+    /// * `only` – výber entít
+    /// * `anonymize` – nahradí osobné údaje zamestnancov
+    ///
+    /// # Returns
+    /// Obsah JSON súboru
+    ///
+    /// # Errors
+    /// Ak zlyhá čítanie z databázy alebo serializácia
+    pub async fn export_to_vec(db: &StoreDB, only: EntitySelection, anonymize: bool) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(&Self::collect(db, only, anonymize).await?)?)
+    }
+
+    /// Uloží do JSON súboru zmeny zamestnancov a produktov od daného času.
+    ///
+    /// Súbor obsahuje zmenené a nové záznamy (podľa `updated_at`), ID záznamov
+    /// zmazaných v tom istom období a čas `until`, od ktorého má pokračovať ďalší
+    /// rozdielový export. Na prijímajúcej strane ho premietne `apply_changes_from_json`.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, z ktorej sa čítajú dáta
+    /// * `since` – začiatok obdobia
+    /// * `file_path` – cieľový súbor
+    ///
+    /// # Errors
+    /// Ak zlyhá čítanie z databázy, zápis do súboru alebo serializácia
+    pub async fn export_changes_since(db: &StoreDB, since: DateTime<Utc>, file_path: &str) -> Result<()> {
+        let data = Self::collect_changes(db, since, EntitySelection::All).await?;
+
+        let path = file_path.to_string();
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let writer = BufWriter::new(File::create(path)?);
+            serde_json::to_writer_pretty(writer, &data)?;
+            Ok(())
+        })
+            .await??;
+
+        println!("Zmeny od {} uložené do JSON-u {}", since, file_path);
+        Ok(())
+    }
+
+    /// Vráti rozdielový export vo formáte `export_changes_since`.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, z ktorej sa čítajú dáta
+    /// * `since` – začiatok obdobia
+    /// * `only` – výber entít
+    ///
+    /// # Returns
+    /// Obsah JSON súboru
+    ///
+    /// # Errors
+    /// Ak zlyhá čítanie z databázy alebo serializácia
+    pub async fn changes_to_vec(db: &StoreDB, since: DateTime<Utc>, only: EntitySelection) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(&Self::collect_changes(db, since, only).await?)?)
+    }
+
+    /// Premietne rozdielový export zo súboru do databázy.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, do ktorej sa zmeny zapíšu
+    /// * `file_path` – súbor z `export_changes_since`
+    ///
+    /// # Returns
+    /// Počty zapísaných a zmazaných záznamov
+    ///
+    /// # Errors
+    /// Ak sa nepodarí prečítať súbor, inak ako `apply_changes_from_slice`
+    pub async fn apply_changes_from_json(db: &StoreDB, file_path: &str) -> Result<DeltaApplyReport> {
+        let json = tokio::fs::read(file_path).await?;
+        Self::apply_changes_from_slice(db, &json).await
+    }
+
+    /// Premietne rozdielový export (už načítaný do pamäte) do databázy.
+    ///
+    /// Záznamy sa párujú podľa ID zo zdroja (pozri `StoreDB::apply_delta`);
+    /// opakované premietnutie toho istého exportu nič nezmení.
+    ///
+    /// # Arguments
+    /// * `db` – databáza, do ktorej sa zmeny zapíšu
+    /// * `json` – obsah súboru z `export_changes_since`
+    ///
+    /// # Returns
+    /// Počty zapísaných a zmazaných záznamov
+    ///
+    /// # Errors
+    /// `StoreError::Invalid` pri neplatnom JSONe alebo nepodporovanej verzii
+    /// formátu, inak chyba zápisu do databázy
+    pub async fn apply_changes_from_slice(db: &StoreDB, json: &[u8]) -> Result<DeltaApplyReport> {
+        let data: DeltaData =
+            serde_json::from_slice(json).map_err(|e| StoreError::Invalid(format!("neplatný rozdielový export: {e}")))?;
+        if data.version != DELTA_VERSION {
+            return Err(StoreError::Invalid(format!(
+                "nepodporovaná verzia rozdielového exportu {} (podporovaná {DELTA_VERSION})",
+                data.version
+            ))
+                .into());
+        }
+        db.apply_delta(&data.employees, &data.products, &data.deleted).await
+    }
+
+    /// Načíta z databázy vybrané entity zmenené po `since` a ID zmazaných záznamov.
+    async fn collect_changes(db: &StoreDB, since: DateTime<Utc>, only: EntitySelection) -> Result<DeltaData> {
+        // čas exportu sa zoberie pred čítaním, zmeny počas čítania zachytí ďalší export
+        let until = Utc::now();
+        let after = since.with_timezone(&Local).naive_local();
+        let deleted = db.deleted_since(after).await?;
+        let mut data = DeltaData {
+            version: DELTA_VERSION,
+            since,
+            until,
+            employees: Vec::new(),
+            products: Vec::new(),
+            deleted: DeletedIds::default(),
+        };
+        if only.employees() {
+            let mut filter = Employee::new_empty();
+            filter.updated_after = Some(after);
+            data.employees = db.get_employees(filter).await?;
+            data.deleted.employees = deleted.employees;
+        }
+        if only.products() {
+            let mut filter = Product::new_empty();
+            filter.updated_after = Some(after);
+            data.products = db.get_products(filter).await?;
+            data.deleted.products = deleted.products;
+        }
+        Ok(data)
+    }
+
+    /// Načíta z databázy vybrané entity; ostatné polia zostanú prázdne.
+    async fn collect(db: &StoreDB, only: EntitySelection, anonymize: bool) -> Result<StoreData> {
+        let mut data = StoreData {
+            employees: Vec::new(),
+            products: Vec::new(),
+            purchase_orders: Vec::new(),
+            bundles: Vec::new(),
+            translations: Vec::new(),
+            omitted: only.omitted(),
+            anonymized: anonymize,
+        };
+        if only.employees() {
+            data.employees = db.get_employees(Employee::new_empty()).await?;
+            if anonymize {
+                let mut pseudonyms = Pseudonyms::default();
+                data.employees.iter_mut().for_each(|e| pseudonyms.anonymize(e));
+            }
+        }
+        if only.products() {
+            data.products = db.get_products(Product::new_empty()).await?;
+            data.purchase_orders = db.get_purchase_orders(None, None).await?;
+            data.bundles = db.get_bundles().await?;
+            data.translations = db.get_product_translations(None).await?;
+        }
+        Ok(data)
+    }
+}
+
+/// Polia, ktoré sa pri porovnaní ignorujú (ID prideľuje databáza, E.164 sa odvodzuje z telefónu,
+/// rezervované a voľné množstvo a čas zmeny počíta server).
+const IGNORED_FIELDS: &[&str] = &["id", "phone_e164", "reserved_quantity", "available_quantity", "updated_at"];
+
+/// Porovná záznamy tabuľky z databázy so záznamami zo súboru.
+///
+/// Záznamy s rovnakým kľúčom sa párujú v poradí podľa ID; porovnávajú sa len
+/// polia, ktoré súbor uvádza (hodnota `null` znamená „bez zmeny“).
+fn diff_table<T: Serialize>(
+    current: Vec<T>,
+    incoming: Vec<T>,
+    key: impl Fn(&T) -> Option<String>,
+) -> Result<TableDiff<T>> {
+    let mut by_key: HashMap<String, VecDeque<T>> = HashMap::new();
+    let mut unkeyed = Vec::new();
+    for record in current {
+        match key(&record) {
+            Some(k) => by_key.entry(k).or_default().push_back(record),
+            None => unkeyed.push(record),
+        }
+    }
+
+    let mut diff = TableDiff::default();
+    for record in incoming {
+        let Some((k, existing)) = key(&record).and_then(|k| by_key.get_mut(&k)?.pop_front().map(|e| (k, e))) else {
+            diff.added.push(record);
+            continue;
+        };
+        let existing = serde_json::to_value(&existing)?;
+        let changes = changed_fields(&existing, &serde_json::to_value(&record)?);
+        if changes.is_empty() {
+            diff.unchanged += 1;
+        } else {
+            let id = existing.get("id").and_then(Value::as_u64).map(|id| id as u32);
+            diff.updated.push(RecordUpdate { id, key: k, changes });
+        }
+    }
+
+    diff.deleted = unkeyed;
+    diff.deleted.extend(by_key.into_values().flatten());
+    diff.deleted.sort_by_key(|record| serde_json::to_value(record).ok()?.get("id")?.as_u64());
+    Ok(diff)
+}
+
+/// Vráti polia, ktorých hodnota v súbore sa líši od databázy.
+fn changed_fields(old: &Value, new: &Value) -> Vec<FieldChange> {
+    let (Value::Object(old), Value::Object(new)) = (old, new) else {
+        return Vec::new();
+    };
+    new.iter()
+        .filter(|(field, value)| !value.is_null() && !IGNORED_FIELDS.contains(&field.as_str()))
+        .filter(|(field, value)| old.get(*field) != Some(*value))
+        .map(|(field, value)| FieldChange {
+            field: field.clone(),
+            old: old.get(field).cloned().unwrap_or(Value::Null),
+            new: value.clone(),
+        })
+        .collect()
 }
@@ -0,0 +1,30 @@
+use std::fmt;
+
+/// Doménové chyby obchodu, ktoré sa mapujú na konkrétne HTTP odpovede.
+#[derive(Debug)]
+pub enum StoreError {
+    /// Požadovaný záznam neexistuje
+    NotFound(String),
+    /// Neplatné vstupné dáta
+    Invalid(String),
+    /// Operácia je v konflikte s aktuálnym stavom dát
+    Conflict(String),
+    /// Dáta sú platné, ale zmena porušuje obchodné pravidlo (napr. minimálnu maržu)
+    PolicyViolation(String),
+    /// Textové pole je dlhšie ako povolený počet znakov
+    TooLong { field: &'static str, limit: usize },
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::NotFound(msg) => write!(f, "nenájdené: {msg}"),
+            StoreError::Invalid(msg) => write!(f, "neplatné dáta: {msg}"),
+            StoreError::Conflict(msg) => write!(f, "konflikt: {msg}"),
+            StoreError::PolicyViolation(msg) => write!(f, "porušené pravidlo: {msg}"),
+            StoreError::TooLong { field, limit } => write!(f, "pole `{field}` je dlhšie ako {limit} znakov"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
@@ -0,0 +1,283 @@
+//! Export produktov a zamestnancov s výberom polí (`POST /export`).
+//!
+//! Záznamy sa najprv prevedú na JSON objekty (rovnaké názvy a hodnoty polí ako
+//! v ostatných odpovediach API), z nich sa vyberú požadované polia a výsledok sa
+//! zapíše do zvoleného formátu. V CSV a XLSX sú stĺpce v poradí `fields`.
+//!
+//! Audit log sa exportuje do CSV s pevnými stĺpcami (`GET /audit/export.csv`).
+
+use anyhow::Result;
+use rust_xlsxwriter::{Format, Workbook};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::StoreError;
+use crate::structs::{AuditEntry, SearchEntity};
+
+/// Polia produktu, ktoré sa dajú exportovať (predvolený výber a poradie).
+pub const PRODUCT_FIELDS: &[&str] = &[
+    "id", "name", "category", "quantity", "unit", "pack_size", "is_bundle", "status", "bar_code", "sku",
+    "min_stock", "max_stock", "lead_time_days", "cost_price", "sell_price", "sale_price", "sale_ends",
+    "effective_price", "description", "brand", "supplier", "employee_id", "date_added", "date_remove", "updated_at",
+];
+
+/// Polia zamestnanca, ktoré sa dajú exportovať (predvolený výber a poradie).
+pub const EMPLOYEE_FIELDS: &[&str] = &[
+    "id", "name", "surname", "position", "department", "shift", "salary", "phone_number", "phone_e164",
+    "email", "status", "note", "hire_date", "manager_id", "date_remove", "updated_at",
+];
+
+/// Výstupný formát exportu.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    /// JSON pole objektov
+    Json,
+    /// Jeden JSON objekt na riadok
+    Ndjson,
+    /// Zošit Excelu s jedným hárkom
+    Xlsx,
+}
+
+impl ExportFormat {
+    /// Hodnota hlavičky `Content-Type`.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+
+    /// Prípona súboru na stiahnutie.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// Požiadavka na export (`{"entity": "product", "filter": {...}, "fields": [...], "format": "csv"}`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportRequest {
+    /// `product` alebo `employee` (aj v množnom čísle)
+    pub entity: SearchEntity,
+    /// Filter ako pri vyhľadávaní (`POST /products/search`); chýbajúci = všetky záznamy
+    #[serde(default)]
+    pub filter: Map<String, Value>,
+    /// Exportované polia v poradí stĺpcov; chýbajúce = všetky
+    #[serde(default)]
+    pub fields: Option<Vec<String>>,
+    pub format: ExportFormat,
+}
+
+/// Vráti polia, ktoré sa dajú exportovať pre daný druh záznamov.
+pub fn available_fields(entity: SearchEntity) -> &'static [&'static str] {
+    match entity {
+        SearchEntity::Products => PRODUCT_FIELDS,
+        SearchEntity::Employees => EMPLOYEE_FIELDS,
+    }
+}
+
+/// Skontroluje požadované polia.
+///
+/// # Arguments
+/// * `entity` – druh záznamov
+/// * `fields` – požadované polia (`None` = všetky)
+///
+/// # Returns
+/// Polia v poradí stĺpcov
+///
+/// # Errors
+/// `StoreError::Invalid` pri prázdnom zozname, neznámom alebo opakovanom poli
+pub fn select_fields(entity: SearchEntity, fields: Option<&[String]>) -> Result<Vec<String>> {
+    let available = available_fields(entity);
+    let Some(fields) = fields else {
+        return Ok(available.iter().map(|f| f.to_string()).collect());
+    };
+    if fields.is_empty() {
+        return Err(StoreError::Invalid("zoznam polí je prázdny".into()).into());
+    }
+    let unknown: Vec<&str> = fields.iter().map(String::as_str).filter(|f| !available.contains(f)).collect();
+    if !unknown.is_empty() {
+        return Err(StoreError::Invalid(format!(
+            "neznáme pole {} (povolené: {})",
+            unknown.join(", "),
+            available.join(", ")
+        ))
+        .into());
+    }
+    if let Some((i, field)) = fields.iter().enumerate().find(|(i, f)| fields[..*i].contains(f)) {
+        return Err(StoreError::Invalid(format!("pole {field} je v zozname viackrát (pozícia {})", i + 1)).into());
+    }
+    Ok(fields.to_vec())
+}
+
+/// Vyberie zo záznamov zadané polia.
+///
+/// # Arguments
+/// * `records` – záznamy
+/// * `fields` – polia (skontrolované `select_fields`)
+///
+/// # Returns
+/// Hodnoty po riadkoch v poradí `fields`; chýbajúce pole je `null`
+pub fn project<T: Serialize>(records: &[T], fields: &[String]) -> Result<Vec<Vec<Value>>> {
+    records
+        .iter()
+        .map(|record| {
+            let value = serde_json::to_value(record)?;
+            Ok(fields.iter().map(|f| value.get(f).cloned().unwrap_or(Value::Null)).collect())
+        })
+        .collect()
+}
+
+/// Zapíše vybrané riadky do zvoleného formátu.
+///
+/// # Arguments
+/// * `format` – výstupný formát
+/// * `entity` – druh záznamov (názov hárka XLSX)
+/// * `fields` – názvy stĺpcov
+/// * `rows` – hodnoty z `project`
+///
+/// # Returns
+/// Telo súboru
+///
+/// # Errors
+/// Ak zlyhá zápis CSV alebo zošitu
+pub fn render(format: ExportFormat, entity: SearchEntity, fields: &[String], rows: &[Vec<Value>]) -> Result<Vec<u8>> {
+    match format {
+        ExportFormat::Json => {
+            let mut out = vec![b'['];
+            for (i, row) in rows.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_object(&mut out, fields, row)?;
+            }
+            out.push(b']');
+            Ok(out)
+        }
+        ExportFormat::Ndjson => {
+            let mut out = Vec::new();
+            for row in rows {
+                write_object(&mut out, fields, row)?;
+                out.push(b'\n');
+            }
+            Ok(out)
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(fields)?;
+            for row in rows {
+                writer.write_record(row.iter().map(text))?;
+            }
+            Ok(writer.into_inner().map_err(|e| e.into_error())?)
+        }
+        ExportFormat::Xlsx => {
+            let mut workbook = Workbook::new();
+            let sheet = workbook.add_worksheet().set_name(entity.as_str())?;
+            let bold = Format::new().set_bold();
+            for (col, field) in fields.iter().enumerate() {
+                sheet.write_string_with_format(0, col as u16, field, &bold)?;
+            }
+            for (r, row) in rows.iter().enumerate() {
+                let r = r as u32 + 1;
+                for (col, value) in row.iter().enumerate() {
+                    let col = col as u16;
+                    match value {
+                        Value::Null => continue,
+                        Value::Bool(b) => sheet.write_boolean(r, col, *b)?,
+                        Value::Number(n) => match n.as_f64() {
+                            Some(n) => sheet.write_number(r, col, n)?,
+                            None => sheet.write_string(r, col, n.to_string())?,
+                        },
+                        other => sheet.write_string(r, col, text(other))?,
+                    };
+                }
+            }
+            Ok(workbook.save_to_buffer()?)
+        }
+    }
+}
+
+/// Stĺpce CSV exportu audit logu.
+pub const AUDIT_CSV_COLUMNS: &[&str] = &["timestamp", "entity_type", "entity_id", "action", "actor", "changed_fields"];
+
+/// Zapíše záznamy audit logu ako riadky CSV v poradí `AUDIT_CSV_COLUMNS`.
+///
+/// # Arguments
+/// * `entries` – záznamy audit logu
+/// * `header` – či sa pred riadky zapíše hlavička (prvá stránka streamovaného exportu)
+///
+/// # Errors
+/// Ak zlyhá zápis CSV
+pub fn audit_csv(entries: &[AuditEntry], header: bool) -> Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    if header {
+        writer.write_record(AUDIT_CSV_COLUMNS)?;
+    }
+    for entry in entries {
+        writer.write_record([
+            entry.created_at.map(|t| t.format("%Y-%m-%dT%H:%M:%S").to_string()).unwrap_or_default(),
+            entry.entity_type.clone().unwrap_or_default(),
+            entry.entity_id.map(|id| id.to_string()).unwrap_or_default(),
+            entry.action.clone(),
+            entry.actor.clone().unwrap_or_default(),
+            changed_fields(entry.details.as_deref()).map(|v| v.to_string()).unwrap_or_default(),
+        ])?;
+    }
+    Ok(writer.into_inner().map_err(|e| e.into_error())?)
+}
+
+/// Podrobnosti záznamu audit logu ako JSON.
+///
+/// Podrobnosti v tvare `kľúč=hodnota, kľúč=hodnota` sa prevedú na objekt
+/// (hodnoty ako reťazce), iný text sa zachová ako JSON reťazec.
+///
+/// # Returns
+/// `None` bez podrobností
+pub fn changed_fields(details: Option<&str>) -> Option<Value> {
+    let details = details?.trim();
+    if details.is_empty() {
+        return None;
+    }
+    let pairs: Option<Map<String, Value>> = details
+        .split(", ")
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let key = key.trim();
+            let simple = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            simple.then(|| (key.to_string(), Value::String(value.trim().to_string())))
+        })
+        .collect();
+    Some(pairs.map(Value::Object).unwrap_or_else(|| Value::String(details.to_string())))
+}
+
+/// Zapíše riadok ako JSON objekt s kľúčmi v poradí `fields`.
+fn write_object(out: &mut Vec<u8>, fields: &[String], row: &[Value]) -> Result<()> {
+    out.push(b'{');
+    for (i, (field, value)) in fields.iter().zip(row).enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        serde_json::to_writer(&mut *out, field)?;
+        out.push(b':');
+        serde_json::to_writer(&mut *out, value)?;
+    }
+    out.push(b'}');
+    Ok(())
+}
+
+/// Hodnota bunky ako text (`null` je prázdna bunka, reťazec bez úvodzoviek).
+fn text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
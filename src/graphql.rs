@@ -0,0 +1,496 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_graphql::{
+    dataloader::{DataLoader, Loader},
+    http::GraphiQLSource,
+    BatchRequest, BatchResponse, ComplexObject, Context, EmptySubscription, Error, ErrorExtensions, InputObject,
+    Object, Schema, SimpleObject,
+};
+use axum::{extract::State, response::Html, Json};
+use chrono::NaiveDate;
+
+use crate::{
+    config::Config,
+    db::StoreDB,
+    error::StoreError,
+    phone,
+    structs::{Employee, Product, Unit},
+};
+
+/// Najväčšia povolená hĺbka dotazu (ochrana pred príliš vnorenými dotazmi).
+pub const MAX_DEPTH: usize = 10;
+
+/// GraphQL schéma aplikácie.
+pub type StoreSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// Vytvorí GraphQL schému nad databázou.
+///
+/// Zamestnanci produktov sa načítavajú cez `DataLoader`, takže zoznam
+/// produktov s vnoreným zamestnancom stojí jeden dotaz na zamestnancov.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+///
+/// # Returns
+/// Schéma pripravená na vykonávanie dotazov
+pub fn schema(db: StoreDB, config: Arc<Config>) -> StoreSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(DataLoader::new(EmployeeLoader(db.clone()), tokio::spawn))
+        .data(db)
+        .data(config)
+        .limit_depth(MAX_DEPTH)
+        .finish()
+}
+
+/// Vykoná GraphQL požiadavku (aj dávku požiadaviek).
+///
+/// # Arguments
+/// * `schema` – GraphQL schéma
+/// * `request` – dotaz s premennými
+///
+/// # Returns
+/// Výsledok dotazu; chyby sú v poli `errors` s kódom v `extensions.code`
+pub async fn execute(State(schema): State<StoreSchema>, Json(request): Json<BatchRequest>) -> Json<BatchResponse> {
+    Json(schema.execute_batch(request).await)
+}
+
+/// Vráti GraphiQL (interaktívne rozhranie pre dotazy), len vo vývojovom režime.
+///
+/// # Returns
+/// HTML stránka GraphiQL
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+/// Dávkové načítanie zamestnancov podľa ID.
+pub struct EmployeeLoader(StoreDB);
+
+impl Loader<u32> for EmployeeLoader {
+    type Value = Employee;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, keys: &[u32]) -> Result<HashMap<u32, Employee>, Self::Error> {
+        let employees = self.0.get_employees_by_ids(keys).await.map_err(Arc::new)?;
+        Ok(employees.into_iter().filter_map(|e| Some((e.id?, e))).collect())
+    }
+}
+
+/// Produkt v GraphQL schéme.
+#[derive(SimpleObject)]
+#[graphql(name = "Product", complex)]
+pub struct ProductObject {
+    pub id:          Option<u32>,
+    pub name:        Option<String>,
+    pub category:    Option<String>,
+    pub quantity:    Option<f64>,
+    pub unit:        Option<String>,
+    pub pack_size:   Option<u32>,
+    pub is_bundle:   Option<bool>,
+    pub status:      Option<bool>,
+    pub bar_code:    Option<i64>,
+    pub sku:         Option<String>,
+    pub min_stock:   Option<f64>,
+    pub max_stock:   Option<f64>,
+    pub lead_time_days: Option<u32>,
+    pub cost_price:  Option<f64>,
+    pub sell_price:  Option<f64>,
+    pub description: Option<String>,
+    pub brand:       Option<String>,
+    pub supplier:    Option<String>,
+    pub employee_id: Option<u32>,
+    pub date_added:  Option<NaiveDate>,
+    pub date_remove: Option<NaiveDate>,
+}
+
+#[ComplexObject]
+impl ProductObject {
+    /// Zamestnanec, ktorý produkt pridal.
+    async fn employee(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<EmployeeObject>> {
+        let Some(id) = self.employee_id else { return Ok(None) };
+        let employee = ctx.data_unchecked::<DataLoader<EmployeeLoader>>().load_one(id).await?;
+        Ok(employee.map(Into::into))
+    }
+}
+
+impl From<Product> for ProductObject {
+    fn from(p: Product) -> Self {
+        Self {
+            id: p.id,
+            name: p.name,
+            category: p.category,
+            quantity: p.quantity,
+            unit: p.unit.map(|u| u.as_str().to_string()),
+            pack_size: p.pack_size,
+            is_bundle: p.is_bundle,
+            status: p.status,
+            bar_code: p.bar_code,
+            sku: p.sku,
+            min_stock: p.min_stock,
+            max_stock: p.max_stock,
+            lead_time_days: p.lead_time_days,
+            cost_price: p.cost_price,
+            sell_price: p.sell_price,
+            description: p.description,
+            brand: p.brand,
+            supplier: p.supplier,
+            employee_id: p.employee_id,
+            date_added: p.date_added,
+            date_remove: p.date_remove,
+        }
+    }
+}
+
+/// Zamestnanec v GraphQL schéme.
+#[derive(SimpleObject)]
+#[graphql(name = "Employee")]
+pub struct EmployeeObject {
+    pub id:           Option<u32>,
+    pub name:         Option<String>,
+    pub surname:      Option<String>,
+    pub position:     Option<String>,
+    pub department:   Option<String>,
+    pub shift:        Option<String>,
+    pub salary:       Option<f64>,
+    pub phone_number: Option<String>,
+    pub phone_e164:   Option<String>,
+    pub email:        Option<String>,
+    pub status:       Option<bool>,
+    pub note:         Option<String>,
+    pub hire_date:    Option<NaiveDate>,
+    pub manager_id:   Option<u32>,
+}
+
+impl From<Employee> for EmployeeObject {
+    fn from(e: Employee) -> Self {
+        Self {
+            id: e.id,
+            name: e.name,
+            surname: e.surname,
+            position: e.position,
+            department: e.department,
+            shift: e.shift,
+            salary: e.salary,
+            phone_number: e.phone_number,
+            phone_e164: e.phone_e164,
+            email: e.email,
+            status: e.status,
+            note: e.note,
+            hire_date: e.hire_date,
+            manager_id: e.manager_id,
+        }
+    }
+}
+
+/// Dáta produktu pre filter, vytvorenie a úpravu (nevyplnené polia sa ignorujú).
+#[derive(InputObject, Default)]
+pub struct ProductInput {
+    pub id:          Option<u32>,
+    pub name:        Option<String>,
+    pub category:    Option<String>,
+    pub quantity:    Option<f64>,
+    /// Jednotka ako v REST API (`piece`, `kg`, `g`, `l`, `ml`, `pack`)
+    pub unit:        Option<String>,
+    pub pack_size:   Option<u32>,
+    pub is_bundle:   Option<bool>,
+    pub status:      Option<bool>,
+    pub bar_code:    Option<i64>,
+    pub sku:         Option<String>,
+    pub min_stock:   Option<f64>,
+    pub max_stock:   Option<f64>,
+    pub lead_time_days: Option<u32>,
+    pub cost_price:  Option<f64>,
+    pub sell_price:  Option<f64>,
+    pub description: Option<String>,
+    pub brand:       Option<String>,
+    pub supplier:    Option<String>,
+    pub employee_id: Option<u32>,
+    pub date_added:  Option<NaiveDate>,
+    pub date_remove: Option<NaiveDate>,
+    /// Len vo filtri: najmenšie množstvo na sklade
+    pub quantity_min: Option<f64>,
+    /// Len vo filtri: najväčšie množstvo na sklade
+    pub quantity_max: Option<f64>,
+}
+
+impl TryFrom<ProductInput> for Product {
+    type Error = Error;
+
+    fn try_from(p: ProductInput) -> Result<Self, Error> {
+        let unit = p
+            .unit
+            .map(|u| Unit::parse(&u).ok_or_else(|| invalid(format!("neznáma jednotka {u:?}"))))
+            .transpose()?;
+        Ok(Self {
+            id: p.id,
+            name: p.name,
+            category: p.category,
+            quantity: p.quantity,
+            unit,
+            pack_size: p.pack_size,
+            is_bundle: p.is_bundle,
+            status: p.status,
+            bar_code: p.bar_code,
+            sku: p.sku,
+            min_stock: p.min_stock,
+            max_stock: p.max_stock,
+            lead_time_days: p.lead_time_days,
+            cost_price: p.cost_price,
+            sell_price: p.sell_price,
+            sale_price: None,
+            sale_ends: None,
+            effective_price: None,
+            description: p.description,
+            brand: p.brand,
+            supplier: p.supplier,
+            employee_id: p.employee_id,
+            date_added: p.date_added,
+            date_remove: p.date_remove,
+            reserved_quantity: None,
+            available_quantity: None,
+            quantity_min: p.quantity_min,
+            quantity_max: p.quantity_max,
+            updated_at: None,
+            updated_after: None,
+            after_id: None,
+            include_removed: None,
+        })
+    }
+}
+
+/// Dáta zamestnanca pre filter, vytvorenie a úpravu (nevyplnené polia sa ignorujú).
+#[derive(InputObject, Default)]
+pub struct EmployeeInput {
+    pub id:           Option<u32>,
+    pub name:         Option<String>,
+    pub surname:      Option<String>,
+    pub position:     Option<String>,
+    pub department:   Option<String>,
+    pub shift:        Option<String>,
+    pub salary:       Option<f64>,
+    pub phone_number: Option<String>,
+    pub email:        Option<String>,
+    pub status:       Option<bool>,
+    pub note:         Option<String>,
+    pub hire_date:    Option<NaiveDate>,
+    /// ID nadriadeného; pri úprave `0` nadriadeného odstráni
+    pub manager_id:   Option<u32>,
+    /// Len vo filtri: najnižšia mzda
+    pub salary_min:   Option<f64>,
+    /// Len vo filtri: najvyššia mzda
+    pub salary_max:   Option<f64>,
+}
+
+impl From<EmployeeInput> for Employee {
+    fn from(e: EmployeeInput) -> Self {
+        Self {
+            id: e.id,
+            name: e.name,
+            surname: e.surname,
+            position: e.position,
+            department: e.department,
+            shift: e.shift,
+            salary: e.salary,
+            phone_number: e.phone_number,
+            phone_e164: None,
+            email: e.email,
+            status: e.status,
+            note: e.note,
+            hire_date: e.hire_date,
+            manager_id: e.manager_id,
+            date_remove: None,
+            salary_min: e.salary_min,
+            salary_max: e.salary_max,
+            updated_at: None,
+            updated_after: None,
+            after_id: None,
+            include_removed: None,
+        }
+    }
+}
+
+/// Dotazy (čítanie).
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Produkty podľa filtra; počet je obmedzený `max_result_rows` ako v REST API.
+    async fn products(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<ProductInput>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+        #[graphql(desc = "Názov a popis sa hľadajú aj v prekladoch")] translations: Option<bool>,
+    ) -> async_graphql::Result<Vec<ProductObject>> {
+        let (db, config) = (ctx.data_unchecked::<StoreDB>(), ctx.data_unchecked::<Arc<Config>>());
+        let limit = limit.unwrap_or(config.max_result_rows).min(config.max_result_rows);
+        let products = db
+            .get_products_page(filter.unwrap_or_default().try_into()?, translations.unwrap_or(false), limit, offset.unwrap_or(0))
+            .await
+            .map_err(|e| error("Chyba pri načítaní produktov")(e.into()))?;
+        Ok(products.into_iter().map(Into::into).collect())
+    }
+
+    /// Produkt podľa ID.
+    async fn product(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<Option<ProductObject>> {
+        let product = ctx
+            .data_unchecked::<StoreDB>()
+            .get_product(id)
+            .await
+            .map_err(|e| error("Chyba pri načítaní produktu")(e.into()))?;
+        Ok(product.map(Into::into))
+    }
+
+    /// Zamestnanci podľa filtra; telefón sa porovnáva v normalizovanom tvare.
+    async fn employees(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EmployeeInput>,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> async_graphql::Result<Vec<EmployeeObject>> {
+        let (db, config) = (ctx.data_unchecked::<StoreDB>(), ctx.data_unchecked::<Arc<Config>>());
+        let mut filter: Employee = filter.unwrap_or_default().into();
+        phone::normalize_filter(&mut filter, &config.phone_country);
+        let limit = limit.unwrap_or(config.max_result_rows).min(config.max_result_rows);
+        let employees = db
+            .get_employees_page(filter, limit, offset.unwrap_or(0))
+            .await
+            .map_err(|e| error("Chyba pri načítaní zamestnancov")(e.into()))?;
+        Ok(employees.into_iter().map(Into::into).collect())
+    }
+
+    /// Zamestnanec podľa ID.
+    async fn employee(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<Option<EmployeeObject>> {
+        let employee = ctx
+            .data_unchecked::<StoreDB>()
+            .get_employee(id)
+            .await
+            .map_err(|e| error("Chyba pri načítaní zamestnanca")(e.into()))?;
+        Ok(employee.map(Into::into))
+    }
+}
+
+/// Mutácie (zápis) nad rovnakými metódami `StoreDB` ako REST API.
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Pridá produkt a vráti ho.
+    async fn create_product(&self, ctx: &Context<'_>, input: ProductInput) -> async_graphql::Result<ProductObject> {
+        let context = "Chyba pri pridávaní produktu";
+        let (db, config) = (ctx.data_unchecked::<StoreDB>(), ctx.data_unchecked::<Arc<Config>>());
+        let mut product: Product = input.try_into()?;
+        product.trim_text();
+        product.validate(&config.text_limits()).map_err(|e| error(context)(e.into()))?;
+        let id = db.add_product_to_store_db(&product).await.map_err(error(context))?;
+        let product = db.get_product(id).await.map_err(|e| error(context)(e.into()))?;
+        product.map(Into::into).ok_or_else(|| not_found(format!("produkt {id}")))
+    }
+
+    /// Upraví produkt; vráti `null`, ak neexistuje. Nová predajná cena musí spĺňať minimálnu maržu.
+    async fn update_product(
+        &self,
+        ctx: &Context<'_>,
+        id: u32,
+        input: ProductInput,
+    ) -> async_graphql::Result<Option<ProductObject>> {
+        let context = "Chyba pri updatovaní produktu";
+        let (db, config) = (ctx.data_unchecked::<StoreDB>(), ctx.data_unchecked::<Arc<Config>>());
+        let mut product: Product = input.try_into()?;
+        product.id = Some(id);
+        product.trim_text();
+        product.validate(&config.text_limits()).map_err(|e| error(context)(e.into()))?;
+        if !db.update_product(&product, Some(&config.margin_policy)).await.map_err(error(context))? {
+            return Ok(None);
+        }
+        let product = db.get_product(id).await.map_err(|e| error(context)(e.into()))?;
+        Ok(product.map(Into::into))
+    }
+
+    /// Vymaže produkt; vráti `false`, ak neexistuje.
+    async fn delete_product(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<bool> {
+        ctx.data_unchecked::<StoreDB>()
+            .delete_product(id)
+            .await
+            .map_err(error("Chyba pri mazaní produktu"))
+    }
+
+    /// Pridá zamestnanca a vráti ho.
+    async fn create_employee(&self, ctx: &Context<'_>, input: EmployeeInput) -> async_graphql::Result<EmployeeObject> {
+        let context = "Chyba pri pridávaní zamestnanca";
+        let (db, config) = (ctx.data_unchecked::<StoreDB>(), ctx.data_unchecked::<Arc<Config>>());
+        let mut employee: Employee = input.into();
+        employee.trim_text();
+        employee.validate(&config.text_limits()).map_err(|e| error(context)(e.into()))?;
+        phone::normalize_employee(&mut employee, &config.phone_country).map_err(invalid)?;
+        let id = db.add_employee_to_store_db(&employee).await.map_err(error(context))?;
+        let employee = db.get_employee(id).await.map_err(|e| error(context)(e.into()))?;
+        employee.map(Into::into).ok_or_else(|| not_found(format!("zamestnanec {id}")))
+    }
+
+    /// Upraví zamestnanca; vráti `null`, ak neexistuje.
+    async fn update_employee(
+        &self,
+        ctx: &Context<'_>,
+        id: u32,
+        input: EmployeeInput,
+    ) -> async_graphql::Result<Option<EmployeeObject>> {
+        let context = "Chyba pri updatovaní zamestnanca";
+        let (db, config) = (ctx.data_unchecked::<StoreDB>(), ctx.data_unchecked::<Arc<Config>>());
+        let mut employee: Employee = input.into();
+        employee.id = Some(id);
+        employee.trim_text();
+        employee.validate(&config.text_limits()).map_err(|e| error(context)(e.into()))?;
+        phone::normalize_employee(&mut employee, &config.phone_country).map_err(invalid)?;
+        if !db.update_employee(&employee).await.map_err(error(context))? {
+            return Ok(None);
+        }
+        let employee = db.get_employee(id).await.map_err(|e| error(context)(e.into()))?;
+        Ok(employee.map(Into::into))
+    }
+
+    /// Vymaže zamestnanca; vráti `false`, ak neexistuje.
+    async fn delete_employee(&self, ctx: &Context<'_>, id: u32) -> async_graphql::Result<bool> {
+        ctx.data_unchecked::<StoreDB>()
+            .delete_employee(id)
+            .await
+            .map_err(error("Chyba pri mazaní zamestnanca"))
+    }
+}
+
+/// Prevedie chybu na GraphQL chybu s kódom v `extensions.code`.
+///
+/// Doménové chyby sa mapujú ako v REST API (`NOT_FOUND`, `BAD_USER_INPUT`,
+/// `CONFLICT`, `POLICY_VIOLATION`), ostatné sa zalogujú s kontextom a klient dostane len `INTERNAL`.
+///
+/// # Arguments
+/// * `context` – popis operácie pre log
+///
+/// # Returns
+/// Funkcia na prevod chyby
+fn error(context: &str) -> impl FnOnce(anyhow::Error) -> Error + '_ {
+    move |e| match e.downcast_ref::<StoreError>() {
+        Some(StoreError::NotFound(_)) => not_found(e.to_string()),
+        Some(StoreError::Invalid(_) | StoreError::TooLong { .. }) => invalid(e.to_string()),
+        Some(StoreError::Conflict(_)) => Error::new(e.to_string()).extend_with(|_, ext| ext.set("code", "CONFLICT")),
+        Some(StoreError::PolicyViolation(_)) => {
+            Error::new(e.to_string()).extend_with(|_, ext| ext.set("code", "POLICY_VIOLATION"))
+        }
+        None => {
+            eprintln!("{context}: {e}");
+            Error::new("interná chyba").extend_with(|_, ext| ext.set("code", "INTERNAL"))
+        }
+    }
+}
+
+/// Chyba neplatných vstupných dát (`BAD_USER_INPUT`).
+fn invalid(message: impl Into<String>) -> Error {
+    Error::new(message).extend_with(|_, ext| ext.set("code", "BAD_USER_INPUT"))
+}
+
+/// Chyba neexistujúceho záznamu (`NOT_FOUND`).
+fn not_found(message: impl Into<String>) -> Error {
+    Error::new(message).extend_with(|_, ext| ext.set("code", "NOT_FOUND"))
+}
@@ -0,0 +1,285 @@
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use tokio::net::TcpListener;
+use tonic::{transport::server::TcpIncoming, Request, Response, Status};
+
+use crate::{
+    config::Config,
+    db::StoreDB,
+    error::StoreError,
+    structs::{self, Unit},
+};
+
+/// Kód vygenerovaný z `proto/store.proto`.
+pub mod proto {
+    tonic::include_proto!("store.v1");
+}
+
+use proto::store_service_server::{StoreService, StoreServiceServer};
+
+/// gRPC služba skladu nad tou istou databázou ako REST API.
+///
+/// Validácia je v `StoreDB`, takže gRPC aj HTTP vracajú pre rovnaké dáta
+/// rovnaké chyby; líši sa len ich kód (pozri `status`).
+#[derive(Clone)]
+pub struct GrpcService {
+    db: StoreDB,
+    config: Arc<Config>,
+}
+
+impl GrpcService {
+    /// Vytvorí gRPC službu.
+    ///
+    /// # Arguments
+    /// * `db` – databáza
+    /// * `config` – konfigurácia aplikácie (`max_result_rows`)
+    pub fn new(db: StoreDB, config: Arc<Config>) -> Self {
+        Self { db, config }
+    }
+}
+
+/// Obsluhuje gRPC požiadavky na už otvorenom sockete.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `config` – konfigurácia aplikácie
+/// * `listener` – socket, na ktorom gRPC server počúva
+///
+/// # Errors
+/// Ak zlyhá spustenie servera
+pub async fn serve(db: StoreDB, config: Arc<Config>, listener: TcpListener) -> anyhow::Result<()> {
+    tonic::transport::Server::builder()
+        .add_service(StoreServiceServer::new(GrpcService::new(db, config)))
+        .serve_with_incoming(TcpIncoming::from(listener))
+        .await?;
+    Ok(())
+}
+
+#[tonic::async_trait]
+impl StoreService for GrpcService {
+    async fn get_product(&self, request: Request<proto::GetProductRequest>) -> Result<Response<proto::Product>, Status> {
+        let id = request.into_inner().id;
+        match self.db.get_product(id).await {
+            Ok(Some(product)) => Ok(Response::new(product.into())),
+            Ok(None) => Err(Status::not_found(format!("produkt {id} neexistuje"))),
+            Err(e) => Err(status("Chyba pri načítaní produktu")(e.into())),
+        }
+    }
+
+    async fn search_products(
+        &self,
+        request: Request<proto::SearchProductsRequest>,
+    ) -> Result<Response<proto::SearchProductsResponse>, Status> {
+        let context = "Chyba pri vyhľadávaní produktov";
+        let filter: structs::Product = request.into_inner().filter.unwrap_or_default().try_into()?;
+
+        let total = self.db.count_products(filter.clone(), false).await.map_err(|e| status(context)(e.into()))?;
+        let products = self
+            .db
+            .get_products_page(filter, false, self.config.max_result_rows, 0)
+            .await
+            .map_err(|e| status(context)(e.into()))?;
+
+        Ok(Response::new(proto::SearchProductsResponse {
+            products: products.into_iter().map(Into::into).collect(),
+            total,
+        }))
+    }
+
+    async fn adjust_stock(&self, request: Request<proto::AdjustStockRequest>) -> Result<Response<proto::Product>, Status> {
+        let request = request.into_inner();
+        let unit = parse_unit(request.unit.as_deref())?;
+        self.db
+            .adjust_stock(request.product_id, request.delta, unit)
+            .await
+            .map(|product| Response::new(product.into()))
+            .map_err(status("Chyba pri úprave zásob"))
+    }
+
+    async fn record_sale(
+        &self,
+        request: Request<proto::RecordSaleRequest>,
+    ) -> Result<Response<proto::RecordSaleResponse>, Status> {
+        let request = request.into_inner();
+        let items = request
+            .items
+            .into_iter()
+            .map(|item| {
+                Ok(structs::OrderItem {
+                    product_id: item.product_id,
+                    quantity: item.quantity,
+                    unit: parse_unit(item.unit.as_deref())?,
+                    unit_price: item.unit_price,
+                    reservation_id: None,
+                })
+            })
+            .collect::<Result<Vec<_>, Status>>()?;
+        let order = structs::Order {
+            id: None,
+            employee_id: request.employee_id,
+            status: None,
+            created_at: None,
+            items,
+            returns: Vec::new(),
+            updated_at: None,
+        };
+
+        self.db
+            .add_order(&order, request.override_closed)
+            .await
+            .map(|order_id| Response::new(proto::RecordSaleResponse { order_id }))
+            .map_err(status("Chyba pri pridávaní objednávky"))
+    }
+
+    async fn get_employee(&self, request: Request<proto::GetEmployeeRequest>) -> Result<Response<proto::Employee>, Status> {
+        let id = request.into_inner().id;
+        match self.db.get_employee(id).await {
+            Ok(Some(employee)) => Ok(Response::new(employee.into())),
+            Ok(None) => Err(Status::not_found(format!("zamestnanec {id} neexistuje"))),
+            Err(e) => Err(status("Chyba pri načítaní zamestnanca")(e.into())),
+        }
+    }
+}
+
+/// Prevedie chybu na gRPC stav podľa typu chyby.
+///
+/// Doménové chyby sa mapujú ako v REST API (`NotFound` → `NOT_FOUND`,
+/// `Invalid` → `INVALID_ARGUMENT`, `Conflict` → `FAILED_PRECONDITION`),
+/// porušenie unikátnosti v databáze na `ALREADY_EXISTS`. Ostatné chyby sa
+/// zalogujú s kontextom a klient dostane len `INTERNAL`.
+///
+/// # Arguments
+/// * `context` – popis operácie pre log
+///
+/// # Returns
+/// Funkcia na prevod chyby na `Status`
+pub fn status(context: &str) -> impl FnOnce(anyhow::Error) -> Status + '_ {
+    move |e| {
+        if let Some(store_error) = e.downcast_ref::<StoreError>() {
+            return match store_error {
+                StoreError::NotFound(_) => Status::not_found(e.to_string()),
+                StoreError::Invalid(_) | StoreError::TooLong { .. } => Status::invalid_argument(e.to_string()),
+                StoreError::Conflict(_) | StoreError::PolicyViolation(_) => Status::failed_precondition(e.to_string()),
+            };
+        }
+        let unique = e
+            .downcast_ref::<sqlx::Error>()
+            .and_then(|e| e.as_database_error())
+            .is_some_and(|e| e.is_unique_violation());
+        if unique {
+            return Status::already_exists("záznam už existuje");
+        }
+        eprintln!("{context}: {e}");
+        Status::internal("")
+    }
+}
+
+/// Sparsuje jednotku z požiadavky.
+///
+/// # Errors
+/// `INVALID_ARGUMENT` pri neznámej jednotke
+fn parse_unit(unit: Option<&str>) -> Result<Option<Unit>, Status> {
+    unit.map(|u| Unit::parse(u).ok_or_else(|| Status::invalid_argument(format!("neznáma jednotka {u:?}"))))
+        .transpose()
+}
+
+/// Sparsuje dátum v tvare `YYYY-MM-DD`.
+///
+/// # Errors
+/// `INVALID_ARGUMENT` pri neplatnom dátume
+fn parse_date(field: &str, value: Option<String>) -> Result<Option<NaiveDate>, Status> {
+    value
+        .map(|v| v.parse().map_err(|_| Status::invalid_argument(format!("pole {field}: neplatný dátum {v:?}"))))
+        .transpose()
+}
+
+impl From<structs::Product> for proto::Product {
+    fn from(p: structs::Product) -> Self {
+        Self {
+            id: p.id,
+            name: p.name,
+            category: p.category,
+            quantity: p.quantity,
+            unit: p.unit.map(|u| u.as_str().to_string()),
+            pack_size: p.pack_size,
+            is_bundle: p.is_bundle,
+            status: p.status,
+            bar_code: p.bar_code,
+            sku: p.sku,
+            min_stock: p.min_stock,
+            max_stock: p.max_stock,
+            lead_time_days: p.lead_time_days,
+            cost_price: p.cost_price,
+            sell_price: p.sell_price,
+            description: p.description,
+            brand: p.brand,
+            supplier: p.supplier,
+            employee_id: p.employee_id,
+            date_added: p.date_added.map(|d| d.to_string()),
+            date_remove: p.date_remove.map(|d| d.to_string()),
+        }
+    }
+}
+
+impl TryFrom<proto::Product> for structs::Product {
+    type Error = Status;
+
+    fn try_from(p: proto::Product) -> Result<Self, Status> {
+        Ok(Self {
+            id: p.id,
+            name: p.name,
+            category: p.category,
+            quantity: p.quantity,
+            unit: parse_unit(p.unit.as_deref())?,
+            pack_size: p.pack_size,
+            is_bundle: p.is_bundle,
+            status: p.status,
+            bar_code: p.bar_code,
+            sku: p.sku,
+            min_stock: p.min_stock,
+            max_stock: p.max_stock,
+            lead_time_days: p.lead_time_days,
+            cost_price: p.cost_price,
+            sell_price: p.sell_price,
+            sale_price: None,
+            sale_ends: None,
+            effective_price: None,
+            description: p.description,
+            brand: p.brand,
+            supplier: p.supplier,
+            employee_id: p.employee_id,
+            date_added: parse_date("date_added", p.date_added)?,
+            date_remove: parse_date("date_remove", p.date_remove)?,
+            reserved_quantity: None,
+            available_quantity: None,
+            quantity_min: None,
+            quantity_max: None,
+            updated_at: None,
+            updated_after: None,
+            after_id: None,
+            include_removed: None,
+        })
+    }
+}
+
+impl From<structs::Employee> for proto::Employee {
+    fn from(e: structs::Employee) -> Self {
+        Self {
+            id: e.id,
+            name: e.name,
+            surname: e.surname,
+            position: e.position,
+            department: e.department,
+            shift: e.shift,
+            salary: e.salary,
+            phone_number: e.phone_number,
+            phone_e164: e.phone_e164,
+            email: e.email,
+            status: e.status,
+            note: e.note,
+            hire_date: e.hire_date.map(|d| d.to_string()),
+            manager_id: e.manager_id,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use axum::{extract::State, http::StatusCode, Json};
+
+use crate::{maintenance::Maintenance, structs::ReadinessStatus};
+
+/// Zdieľaný stav pripravenosti aplikácie na obsluhu požiadaviek.
+///
+/// `main` ho prepína počas štartu (import z JSONu, migrácie dát); `/readyz`
+/// vráti 200 až po `mark_ready`.
+#[derive(Clone, Default)]
+pub struct Readiness {
+    inner: Arc<ReadinessInner>,
+}
+
+#[derive(Default)]
+struct ReadinessInner {
+    ready: AtomicBool,
+    loaded_records: AtomicU64,
+    phase: Mutex<String>,
+}
+
+impl Readiness {
+    /// Vytvorí stav pre štartujúcu aplikáciu (ešte nepripravená).
+    pub fn starting() -> Self {
+        let readiness = Self::default();
+        readiness.set_phase("starting");
+        readiness
+    }
+
+    /// Vytvorí stav už pripravenej aplikácie (napr. pre testy).
+    pub fn ready() -> Self {
+        let readiness = Self::default();
+        readiness.mark_ready();
+        readiness
+    }
+
+    /// Nastaví práve prebiehajúcu fázu štartu.
+    pub fn set_phase(&self, phase: &str) {
+        *self.inner.phase.lock().unwrap_or_else(|e| e.into_inner()) = phase.to_string();
+    }
+
+    /// Nastaví počet záznamov načítaných pri štartovacom importe.
+    pub fn set_loaded(&self, records: u64) {
+        self.inner.loaded_records.store(records, Ordering::Relaxed);
+    }
+
+    /// Označí aplikáciu za pripravenú.
+    pub fn mark_ready(&self) {
+        self.set_phase("ready");
+        self.inner.ready.store(true, Ordering::Release);
+    }
+
+    /// Zistí, či je aplikácia pripravená.
+    pub fn is_ready(&self) -> bool {
+        self.inner.ready.load(Ordering::Acquire)
+    }
+
+    /// Vráti aktuálny stav pre `/readyz`.
+    pub fn status(&self) -> ReadinessStatus {
+        ReadinessStatus {
+            ready: self.is_ready(),
+            phase: self.inner.phase.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+            loaded_records: self.inner.loaded_records.load(Ordering::Relaxed),
+            maintenance: false,
+        }
+    }
+}
+
+/// Liveness: proces beží a obsluhuje požiadavky.
+///
+/// # Returns
+/// Vždy `200 OK`
+pub async fn livez() -> &'static str {
+    "ok"
+}
+
+/// Readiness: databáza je pripravená, štartovací import skončil a nie je zapnutá údržba.
+///
+/// # Arguments
+/// * `readiness` – zdieľaný stav pripravenosti
+/// * `maintenance` – zdieľaný stav údržby
+///
+/// # Returns
+/// Stav pripravenosti; `503 Service Unavailable` s priebehom importu, kým nie je
+/// pripravená, alebo s `maintenance: true` počas údržby
+pub async fn readyz(
+    State(readiness): State<Readiness>,
+    State(maintenance): State<Maintenance>,
+) -> (StatusCode, Json<ReadinessStatus>) {
+    let mut status = readiness.status();
+    status.maintenance = maintenance.is_enabled();
+    let code = if status.ready && !status.maintenance { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (code, Json(status))
+}
@@ -0,0 +1,559 @@
+use std::{collections::BTreeMap, future::poll_fn, pin::Pin};
+
+use anyhow::Result;
+use axum::body::{Body, HttpBody};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    barcode,
+    config::TextLimits,
+    db::StoreDB,
+    db_filler::EntitySelection,
+    error::StoreError,
+    phone,
+    pricing::MarginPolicy,
+    structs::{
+        BarcodeRowCheck, BarcodeValidationReport, ImportLineError, ImportRecord, ImportReport, PriceListLine,
+        PriceListReport, PriceListRow, Product,
+    },
+};
+
+/// Počet záznamov vkladaných v jednej transakcii.
+pub const IMPORT_CHUNK: usize = 500;
+
+/// Najdlhší povolený riadok; dlhší riadok sa odmietne bez načítania do pamäte.
+pub const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Najviac toľko chybných riadkov sa vypíše vo výsledku (počíta sa každý).
+pub const MAX_REPORTED_ERRORS: usize = 1000;
+
+/// Priebežný stav importu: rozpracovaný riadok a dávka čakajúca na zápis.
+struct Importer<'a> {
+    db: &'a StoreDB,
+    lenient: bool,
+    only: EntitySelection,
+    phone_country: &'a str,
+    limits: TextLimits,
+    margin: Option<&'a MarginPolicy>,
+    line: Vec<u8>,
+    line_no: u64,
+    oversized: bool,
+    chunk: Vec<(u64, ImportRecord)>,
+    report: ImportReport,
+}
+
+/// Importuje zamestnancov a produkty z NDJSON tela (jeden záznam na riadok).
+///
+/// Telo sa spracúva priebežne, ako prichádza: v pamäti je najviac jeden
+/// riadok a jedna dávka `IMPORT_CHUNK` záznamov, ktorá sa uloží v samostatnej
+/// transakcii. Prázdne riadky a záznamy mimo výberu `only` sa ignorujú.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `body` – telo požiadavky
+/// * `lenient` – chybné riadky sa preskočia; inak sa import zastaví na prvej chybe
+/// * `only` – importované entity
+/// * `phone_country` – krajina pre telefóny zamestnancov bez medzinárodnej predvoľby
+/// * `limits` – najväčšie dĺžky textových polí; dlhší text je chybný riadok
+/// * `margin` – politika minimálnej marže; produkt pod maržou je chybný riadok (`None` = bez kontroly)
+///
+/// # Returns
+/// Výsledok importu vrátane čísel chybných riadkov
+///
+/// # Errors
+/// `StoreError::Invalid` ak sa telo nepodarí prečítať, inak chyba databázy
+pub async fn import_ndjson(
+    db: &StoreDB,
+    mut body: Body,
+    lenient: bool,
+    only: EntitySelection,
+    phone_country: &str,
+    limits: TextLimits,
+    margin: Option<&MarginPolicy>,
+) -> Result<ImportReport> {
+    let mut importer = Importer {
+        db,
+        lenient,
+        only,
+        phone_country,
+        limits,
+        margin,
+        line: Vec::new(),
+        line_no: 0,
+        oversized: false,
+        chunk: Vec::new(),
+        report: ImportReport::default(),
+    };
+
+    while let Some(frame) = poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await {
+        let frame = frame.map_err(|e| StoreError::Invalid(format!("telo požiadavky sa nepodarilo načítať: {e}")))?;
+        let Ok(data) = frame.into_data() else { continue };
+        if !importer.feed(&data).await? {
+            return Ok(importer.report);
+        }
+    }
+
+    // posledný riadok bez ukončovacieho znaku
+    if (!importer.line.is_empty() || importer.oversized) && !importer.end_line().await? {
+        return Ok(importer.report);
+    }
+    if importer.flush().await? {
+        importer.report.completed = true;
+    }
+    Ok(importer.report)
+}
+
+/// Importuje produkty z CSV súboru (prvý riadok sú názvy polí produktu, napr.
+/// `name,category,quantity,bar_code,cost_price,sell_price`).
+///
+/// Oddeľovač je čiarka, alebo bodkočiarka, ak ju obsahuje hlavička a čiarku nie
+/// (export z Excelu). Prázdna bunka je nezadané pole. Riadky sa kontrolujú
+/// a ukladajú po dávkach rovnako ako pri NDJSON importe; čísla chybných riadkov
+/// sú čísla riadkov súboru (hlavička je riadok 1).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `data` – obsah CSV súboru
+/// * `lenient` – chybné riadky sa preskočia; inak sa import zastaví na prvej chybe
+/// * `only` – importované entity (bez produktov sa všetky riadky ignorujú)
+/// * `limits` – najväčšie dĺžky textových polí; dlhší text je chybný riadok
+/// * `margin` – politika minimálnej marže (`None` = bez kontroly)
+///
+/// # Returns
+/// Výsledok importu vrátane čísel chybných riadkov
+///
+/// # Errors
+/// `StoreError::Invalid` ak súbor nemá hlavičku, inak chyba databázy
+pub async fn import_csv(
+    db: &StoreDB,
+    data: &[u8],
+    lenient: bool,
+    only: EntitySelection,
+    limits: TextLimits,
+    margin: Option<&MarginPolicy>,
+) -> Result<ImportReport> {
+    let (mut reader, headers) = csv_reader(data)?;
+    let mut importer = Importer {
+        db,
+        lenient,
+        only,
+        phone_country: "",
+        limits,
+        margin,
+        line: Vec::new(),
+        line_no: 0,
+        oversized: false,
+        chunk: Vec::new(),
+        report: ImportReport::default(),
+    };
+    for row in reader.records() {
+        let Some((line_no, parsed)) = csv_record(row, &headers) else { continue };
+        if !importer.accept(line_no, parsed.map(ImportRecord::Product)).await? {
+            return Ok(importer.report);
+        }
+    }
+    if importer.flush().await? {
+        importer.report.completed = true;
+    }
+    Ok(importer.report)
+}
+
+/// Otvorí CSV súbor a prečíta jeho hlavičku.
+///
+/// Oddeľovač je čiarka, alebo bodkočiarka, ak ju obsahuje hlavička a čiarku nie.
+///
+/// # Errors
+/// `StoreError::Invalid` ak súbor nemá platnú hlavičku
+fn csv_reader(data: &[u8]) -> Result<(csv::Reader<&[u8]>, csv::StringRecord)> {
+    let header = data.split(|b| *b == b'\n').next().unwrap_or_default();
+    let delimiter = if header.contains(&b';') && !header.contains(&b',') { b';' } else { b',' };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data);
+    let headers = reader
+        .headers()
+        .map_err(|e| StoreError::Invalid(format!("CSV bez platnej hlavičky: {e}")))?
+        .clone();
+    Ok((reader, headers))
+}
+
+/// Rozparsuje riadok CSV na záznam (produkt, riadok cenníka).
+///
+/// # Returns
+/// Číslo riadku súboru a záznam alebo popis chyby; `None` pre prázdny riadok
+fn csv_record<T: DeserializeOwned>(
+    row: csv::Result<csv::StringRecord>,
+    headers: &csv::StringRecord,
+) -> Option<(u64, Result<T, String>)> {
+    match row {
+        Ok(row) if row.iter().all(str::is_empty) => None,
+        Ok(row) => Some((
+            row.position().map_or(0, |p| p.line()),
+            row.deserialize::<T>(Some(headers)).map_err(|e| format!("neplatný riadok CSV: {e}")),
+        )),
+        Err(e) => Some((e.position().map_or(0, |p| p.line()), Err(format!("neplatný riadok CSV: {e}")))),
+    }
+}
+
+/// Rozparsuje produkty CSV súboru (rovnaký formát ako `import_csv`) bez zápisu.
+///
+/// # Returns
+/// Číslo riadku a produkt alebo popis chyby pre každý neprázdny riadok
+///
+/// # Errors
+/// `StoreError::Invalid` ak súbor nemá hlavičku
+pub fn csv_products(data: &[u8]) -> Result<Vec<(u64, Result<Product, String>)>> {
+    let (mut reader, headers) = csv_reader(data)?;
+    Ok(reader.records().filter_map(|row| csv_record(row, &headers)).collect())
+}
+
+/// Rozparsuje produkty NDJSON súboru (rovnaký formát ako `import_ndjson`) bez zápisu.
+///
+/// Záznamy zamestnancov a prázdne riadky sa vynechajú.
+///
+/// # Returns
+/// Číslo riadku a produkt alebo popis chyby
+pub fn ndjson_products(data: &[u8]) -> Vec<(u64, Result<Product, String>)> {
+    data.split(|b| *b == b'\n')
+        .zip(1..)
+        .filter(|(line, _)| !line.trim_ascii().is_empty())
+        .filter_map(|(line, line_no)| match serde_json::from_slice::<ImportRecord>(line) {
+            Ok(ImportRecord::Product(product)) => Some((line_no, Ok(product))),
+            Ok(ImportRecord::Employee(_)) => None,
+            Err(e) => Some((line_no, Err(format!("neplatný JSON: {e}")))),
+        })
+        .collect()
+}
+
+/// Skontroluje čiarové kódy rozparsovaných produktov importného súboru; nič nezapíše.
+///
+/// Každý kód prejde kontrolou kontrolnej číslice EAN-13/EAN-8 (UPC-A sa
+/// overí ako EAN-13 s úvodnou nulou). Hľadajú sa aj ďalšie riadky súboru
+/// s rovnakým kódom a existujúce produkty s rovnakým kódom.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `rows` – výsledok `csv_products` alebo `ndjson_products`
+///
+/// # Returns
+/// Výsledok kontroly každého riadku v poradí súboru
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy
+pub async fn validate_barcodes(
+    db: &StoreDB,
+    rows: Vec<(u64, Result<Product, String>)>,
+) -> Result<BarcodeValidationReport> {
+    let mut lines_by_code: BTreeMap<i64, Vec<u64>> = BTreeMap::new();
+    for (line, product) in &rows {
+        if let Ok(Product { bar_code: Some(code), .. }) = product {
+            lines_by_code.entry(*code).or_default().push(*line);
+        }
+    }
+    let codes: Vec<i64> = lines_by_code.keys().copied().collect();
+    let existing = db.product_ids_by_barcodes(&codes).await?;
+
+    let mut report = BarcodeValidationReport::default();
+    for (line, product) in rows {
+        let bar_code = product.as_ref().ok().and_then(|p| p.bar_code);
+        let error = match (&product, bar_code) {
+            (Err(message), _) => Some(message.clone()),
+            (Ok(_), None) => Some("chýba čiarový kód".to_string()),
+            (Ok(_), Some(code)) => barcode::Ean::from_code(code).err(),
+        };
+        let duplicate_lines = bar_code
+            .and_then(|code| lines_by_code.get(&code))
+            .map(|lines| lines.iter().copied().filter(|&other| other != line).collect())
+            .unwrap_or_default();
+        let existing_products = bar_code.and_then(|code| existing.get(&code)).cloned().unwrap_or_default();
+        let row = BarcodeRowCheck { line, bar_code, error, duplicate_lines, existing_products };
+
+        report.rows += 1;
+        if row.error.is_some() {
+            report.invalid += 1;
+        }
+        if !row.duplicate_lines.is_empty() {
+            report.duplicates += 1;
+        }
+        if !row.existing_products.is_empty() {
+            report.collisions += 1;
+        }
+        report.lines.push(row);
+    }
+    report.valid = report.invalid == 0 && report.duplicates == 0 && report.collisions == 0;
+    Ok(report)
+}
+
+/// Rozparsuje cenník dodávateľa v CSV (stĺpce `bar_code` alebo `barcode` a `new_cost`) bez zápisu.
+///
+/// # Returns
+/// Číslo riadku a riadok cenníka alebo popis chyby pre každý neprázdny riadok
+///
+/// # Errors
+/// `StoreError::Invalid` ak súbor nemá hlavičku
+pub fn csv_price_list(data: &[u8]) -> Result<Vec<(u64, Result<PriceListRow, String>)>> {
+    let (mut reader, headers) = csv_reader(data)?;
+    Ok(reader.records().filter_map(|row| csv_record(row, &headers)).collect())
+}
+
+/// Rozparsuje cenník dodávateľa v NDJSON (`{"barcode":…,"new_cost":…}` na riadok) bez zápisu.
+///
+/// # Returns
+/// Číslo riadku a riadok cenníka alebo popis chyby
+pub fn ndjson_price_list(data: &[u8]) -> Vec<(u64, Result<PriceListRow, String>)> {
+    data.split(|b| *b == b'\n')
+        .zip(1..)
+        .filter(|(line, _)| !line.trim_ascii().is_empty())
+        .map(|(line, line_no)| (line_no, serde_json::from_slice(line).map_err(|e| format!("neplatný JSON: {e}"))))
+        .collect()
+}
+
+/// Spáruje riadky cenníka dodávateľa s produktmi podľa čiarového kódu a vypočíta
+/// dopad nových nákupných cien na maržu; nič nezapíše.
+///
+/// Kód bez produktu je nespárovaný riadok, kód viacerých produktov chyba
+/// (nie je jasné, ktorému produktu cena patrí). Marža sa počíta v percentách
+/// aktuálnej predajnej ceny.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `rows` – výsledok `csv_price_list` alebo `ndjson_price_list`
+/// * `margin` – politika minimálnej marže na označenie porušení (`None` = bez kontroly)
+///
+/// # Returns
+/// Dopad každého riadku v poradí súboru
+///
+/// # Errors
+/// Ak zlyhá čítanie z databázy
+pub async fn price_list_preview(
+    db: &StoreDB,
+    rows: Vec<(u64, Result<PriceListRow, String>)>,
+    margin: Option<&MarginPolicy>,
+) -> Result<PriceListReport> {
+    let codes: Vec<i64> = rows.iter().filter_map(|(_, row)| row.as_ref().ok().map(|r| r.bar_code)).collect();
+    let ids_by_code = db.product_ids_by_barcodes(&codes).await?;
+    let ids: Vec<u32> = ids_by_code.values().filter(|ids| ids.len() == 1).map(|ids| ids[0]).collect();
+    let products: BTreeMap<u32, Product> =
+        db.get_products_by_ids(&ids).await?.into_iter().filter_map(|p| Some((p.id?, p))).collect();
+
+    let mut report = PriceListReport::default();
+    for (line, row) in rows {
+        report.rows += 1;
+        let mut result = PriceListLine {
+            line,
+            bar_code: None,
+            product_id: None,
+            name: None,
+            old_cost: None,
+            new_cost: None,
+            sell_price: None,
+            old_margin: None,
+            new_margin: None,
+            margin_change: None,
+            erodes_margin: false,
+            below_minimum: None,
+            error: None,
+        };
+        let row = match row {
+            Ok(row) => row,
+            Err(message) => {
+                result.error = Some(message);
+                report.invalid += 1;
+                report.lines.push(result);
+                continue;
+            }
+        };
+        result.bar_code = Some(row.bar_code);
+        if !row.new_cost.is_finite() || row.new_cost < 0.0 {
+            result.error = Some(format!("neplatná nákupná cena {}", row.new_cost));
+            report.invalid += 1;
+            report.lines.push(result);
+            continue;
+        }
+        result.new_cost = Some(row.new_cost);
+
+        let product = match ids_by_code.get(&row.bar_code).map(Vec::as_slice) {
+            None | Some([]) => {
+                report.unmatched += 1;
+                report.lines.push(result);
+                continue;
+            }
+            Some([id]) => &products[id],
+            Some(ids) => {
+                let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+                result.error = Some(format!("čiarový kód má viac produktov: {}", ids.join(", ")));
+                report.invalid += 1;
+                report.lines.push(result);
+                continue;
+            }
+        };
+
+        report.matched += 1;
+        let new = Product { cost_price: Some(row.new_cost), ..product.clone() };
+        result.product_id = product.id;
+        result.name = product.name.clone();
+        result.old_cost = product.cost_price;
+        result.sell_price = product.sell_price;
+        result.old_margin = product.margin_percent();
+        result.new_margin = new.margin_percent();
+        result.margin_change = new.margin_percent().zip(product.margin_percent()).map(|(new, old)| new - old);
+        result.erodes_margin = product.cost_price.is_some_and(|old| row.new_cost > old);
+        result.below_minimum = margin
+            .zip(product.sell_price)
+            .and_then(|(policy, sell)| policy.check(product.category.as_deref(), Some(row.new_cost), sell))
+            .map(|shortfall| shortfall.to_string());
+        if result.erodes_margin {
+            report.eroding += 1;
+        }
+        report.lines.push(result);
+    }
+    Ok(report)
+}
+
+impl Importer<'_> {
+    /// Spracuje ďalší úsek tela.
+    ///
+    /// # Returns
+    /// `false` ak sa má import zastaviť (chyba v prísnom režime)
+    async fn feed(&mut self, mut data: &[u8]) -> Result<bool> {
+        while let Some(pos) = data.iter().position(|b| *b == b'\n') {
+            self.append(&data[..pos]);
+            if !self.end_line().await? {
+                return Ok(false);
+            }
+            data = &data[pos + 1..];
+        }
+        self.append(data);
+        Ok(true)
+    }
+
+    /// Pridá bajty k rozpracovanému riadku; príliš dlhý riadok sa ďalej neukladá.
+    fn append(&mut self, bytes: &[u8]) {
+        if self.oversized {
+            return;
+        }
+        if self.line.len() + bytes.len() > MAX_LINE_BYTES {
+            self.oversized = true;
+            self.line = Vec::new();
+        } else {
+            self.line.extend_from_slice(bytes);
+        }
+    }
+
+    /// Ukončí rozpracovaný riadok: rozparsuje ho a pridá do dávky.
+    ///
+    /// # Returns
+    /// `false` ak sa má import zastaviť
+    async fn end_line(&mut self) -> Result<bool> {
+        self.line_no += 1;
+        let line = std::mem::take(&mut self.line);
+        let oversized = std::mem::take(&mut self.oversized);
+
+        let parsed = if oversized {
+            Err(format!("riadok je dlhší ako {MAX_LINE_BYTES} B"))
+        } else if line.trim_ascii().is_empty() {
+            return Ok(true);
+        } else {
+            serde_json::from_slice(&line).map_err(|e| format!("neplatný JSON: {e}"))
+        };
+        self.accept(self.line_no, parsed).await
+    }
+
+    /// Skontroluje rozparsovaný záznam a pridá ho do dávky (plnú dávku uloží).
+    ///
+    /// # Returns
+    /// `false` ak sa má import zastaviť
+    async fn accept(&mut self, line_no: u64, parsed: Result<ImportRecord, String>) -> Result<bool> {
+        self.report.lines += 1;
+        match parsed.and_then(|record| self.check(record)) {
+            Ok(record) if !self.selected(&record) => {
+                if self.report.ignored == 0 {
+                    println!("Import len {}: záznamy inej entity sa ignorujú", self.only);
+                }
+                self.report.ignored += 1;
+                Ok(true)
+            }
+            Ok(record) => {
+                self.chunk.push((line_no, record));
+                if self.chunk.len() >= IMPORT_CHUNK {
+                    return self.flush().await;
+                }
+                Ok(true)
+            }
+            Err(message) => {
+                self.skip(ImportLineError { line: line_no, message });
+                Ok(self.lenient)
+            }
+        }
+    }
+
+    /// Či záznam patrí do výberu `only`.
+    fn selected(&self, record: &ImportRecord) -> bool {
+        match record {
+            ImportRecord::Employee(_) => self.only.employees(),
+            ImportRecord::Product(_) => self.only.products(),
+        }
+    }
+
+    /// Oreže a skontroluje dĺžku textových polí záznamu, doplní normalizovaný
+    /// telefón zamestnanca a skontroluje maržu produktu.
+    fn check(&self, mut record: ImportRecord) -> Result<ImportRecord, String> {
+        match &mut record {
+            ImportRecord::Employee(employee) => {
+                employee.trim_text();
+                employee.validate(&self.limits).map_err(|e| e.to_string())?;
+                phone::normalize_employee(employee, self.phone_country)?
+            }
+            ImportRecord::Product(product) => {
+                product.trim_text();
+                product.validate(&self.limits).map_err(|e| e.to_string())?;
+                if let (Some(policy), Some(sell_price)) = (self.margin, product.sell_price) {
+                    if let Some(shortfall) = policy.check(product.category.as_deref(), product.cost_price, sell_price) {
+                        return Err(StoreError::PolicyViolation(shortfall.to_string()).to_string());
+                    }
+                }
+            }
+        }
+        Ok(record)
+    }
+
+    /// Uloží rozpracovanú dávku do databázy.
+    ///
+    /// # Returns
+    /// `false` ak niektorý záznam v prísnom režime zlyhal (dávka sa zahodí)
+    async fn flush(&mut self) -> Result<bool> {
+        let chunk = std::mem::take(&mut self.chunk);
+        if chunk.is_empty() {
+            return Ok(true);
+        }
+
+        let errors = self.db.import_records(&chunk, self.lenient).await?;
+        let failed = !errors.is_empty();
+        if failed && !self.lenient {
+            errors.into_iter().for_each(|e| self.skip(e));
+            return Ok(false);
+        }
+
+        for (line, record) in &chunk {
+            if errors.iter().any(|e| e.line == *line) {
+                continue;
+            }
+            match record {
+                ImportRecord::Employee(_) => self.report.employees += 1,
+                ImportRecord::Product(_) => self.report.products += 1,
+            }
+        }
+        errors.into_iter().for_each(|e| self.skip(e));
+        Ok(true)
+    }
+
+    /// Započíta preskočený riadok a zapíše jeho chybu do výsledku.
+    fn skip(&mut self, error: ImportLineError) {
+        self.report.skipped += 1;
+        if self.report.errors.len() < MAX_REPORTED_ERRORS {
+            self.report.errors.push(error);
+        }
+    }
+}
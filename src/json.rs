@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRef, FromRequest, Request},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+use crate::{config::Config, structs::JsonBodyError};
+
+/// JSON telo požiadavky a odpovede HTTP API.
+///
+/// Na rozdiel od `axum::Json` pri nezhode tela s očakávanou štruktúrou vráti
+/// `422` s telom `JsonBodyError`, ktoré menuje chybné pole, očakávaný typ
+/// a poslanú hodnotu. V prísnom režime (`strict_json`, predvolene zapnutý)
+/// odmietne aj neznáme polia, takže sa preklep ako `sell_pricce` neignoruje.
+/// Chyby syntaxe a chýbajúci `Content-Type` sa hlásia rovnako ako pri `axum::Json`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json<T>(pub T);
+
+impl<T, S> FromRequest<S> for Json<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+    Arc<Config>: FromRef<S>,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let strict = Arc::<Config>::from_ref(state).strict_json;
+        let axum::Json(value) = axum::Json::<Value>::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        from_value(&value, strict)
+            .map(Json)
+            .map_err(|e| (StatusCode::UNPROCESSABLE_ENTITY, axum::Json(*e)).into_response())
+    }
+}
+
+impl<T: Serialize> IntoResponse for Json<T> {
+    fn into_response(self) -> Response {
+        axum::Json(self.0).into_response()
+    }
+}
+
+/// Prevedie JSON hodnotu na typ `T` a pri chybe zostaví popis pre klienta.
+///
+/// # Arguments
+/// * `value` – rozparsované telo požiadavky
+/// * `strict` – neznáme polia sú chybou
+///
+/// # Errors
+/// `JsonBodyError` s chybným poľom (zlý typ, chýbajúce pole) alebo so zoznamom neznámych polí
+pub fn from_value<T: DeserializeOwned>(value: &Value, strict: bool) -> Result<T, Box<JsonBodyError>> {
+    let mut unknown = Vec::new();
+    let mut track = |path: serde_ignored::Path<'_>| unknown.push(path.to_string());
+    let deserializer = serde_ignored::Deserializer::new(value, &mut track);
+    let parsed = serde_path_to_error::deserialize(deserializer).map_err(|e| Box::new(invalid_value(value, e)))?;
+
+    if strict && !unknown.is_empty() {
+        let list = unknown.iter().map(|f| format!("`{f}`")).collect::<Vec<_>>().join(", ");
+        return Err(Box::new(JsonBodyError {
+            error: "unknown_field".into(),
+            field: unknown.first().cloned(),
+            expected: None,
+            received: None,
+            message: format!("neznáme pole {list}"),
+            unknown_fields: unknown,
+        }));
+    }
+    Ok(parsed)
+}
+
+fn invalid_value(value: &Value, error: serde_path_to_error::Error<serde_json::Error>) -> JsonBodyError {
+    let path = error.path();
+    let at_root = path.iter().next().is_none();
+    let detail = error.inner().to_string();
+
+    // chýbajúce pole nemá vlastný segment cesty, serde ho uvádza len v texte chyby
+    let (field, received) = match missing_field(&detail) {
+        Some(name) if at_root => (Some(name.to_string()), None),
+        Some(name) => (Some(format!("{path}.{name}")), None),
+        None => {
+            let received = path.iter().try_fold(value, |v, segment| match segment {
+                Segment::Seq { index } => v.get(index),
+                Segment::Map { key } => v.get(key),
+                Segment::Enum { .. } | Segment::Unknown => Some(v),
+            });
+            ((!at_root).then(|| path.to_string()), received.cloned())
+        }
+    };
+    let expected = detail.split_once("expected ").map(|(_, expected)| expected.to_string());
+
+    let message = match &field {
+        Some(field) => format!("pole `{field}` má neplatnú hodnotu: {detail}"),
+        None => format!("neplatné telo požiadavky: {detail}"),
+    };
+    JsonBodyError { error: "invalid_value".into(), field, expected, received, unknown_fields: Vec::new(), message }
+}
+
+/// Vytiahne názov poľa zo správy serde „missing field `x`“.
+fn missing_field(detail: &str) -> Option<&str> {
+    let rest = detail.strip_prefix("missing field `")?;
+    rest.split_once('`').map(|(name, _)| name)
+}
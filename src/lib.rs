@@ -0,0 +1,45 @@
+//! Správa skladu a zamestnancov obchodu cez HTTP API.
+
+pub mod api;
+pub mod api_version;
+pub mod auth;
+pub mod barcode;
+pub mod batch;
+pub mod cache;
+pub mod client;
+pub mod body_log;
+pub mod config;
+pub mod db;
+pub mod db_filler;
+pub mod error;
+pub mod export;
+pub mod graphql;
+pub mod grpc;
+pub mod health;
+pub mod import;
+pub mod json;
+pub mod list_shape;
+pub mod maintenance;
+pub mod pdf;
+pub mod phone;
+pub mod pricing;
+pub mod qr;
+pub mod range;
+pub mod read_only;
+pub mod retention;
+pub mod route_table;
+pub mod scheduler;
+pub mod server;
+pub mod sku;
+pub mod slow_query;
+pub mod startup;
+pub mod stores;
+pub mod structs;
+pub mod sync;
+pub mod telemetry;
+pub mod tui;
+pub mod tx_session;
+pub mod web;
+
+#[doc(hidden)]
+pub mod test_support;
@@ -0,0 +1,162 @@
+//! Tvar odpovedí zoznamov.
+//!
+//! Zoznamy (`GET` a vyhľadávacie `POST` z `auth::READ_ONLY_POSTS`, ktoré vracajú
+//! JSON pole) sa predvolene posielajú ako holé pole, tak ako doteraz. S tvarom
+//! `envelope` sa pole zabalí do `{"items": [...], "total": N}`, kde `total` je
+//! hodnota `X-Total-Count` (bez nej počet položiek); hlavičky stránkovania zostanú.
+//!
+//! Predvolený tvar určuje `list_shape` v konfigurácii. Klient si ho vyberie
+//! parametrom `profile` v hlavičke `Accept`:
+//!
+//! * `Accept: application/json; profile="envelope"` – obálka `{items, total}`
+//! * `Accept: application/json; profile="array"` – holé pole
+//!
+//! Zabalená odpoveď má `Content-Type: application/json; profile="envelope"`.
+
+use std::{fmt, str::FromStr, sync::Arc};
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{api::X_TOTAL_COUNT, auth, config::Config};
+
+/// Hodnota `Accept`, ktorou si klient vyžiada zoznamy ako holé pole.
+pub const ACCEPT_ARRAY: &str = "application/json; profile=\"array\"";
+
+/// Hodnota `Accept`, ktorou si klient vyžiada zoznamy v obálke `{items, total}`.
+pub const ACCEPT_ENVELOPE: &str = "application/json; profile=\"envelope\"";
+
+/// Tvar odpovede zoznamu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListShape {
+    /// Holé JSON pole
+    #[default]
+    Array,
+    /// Obálka `{"items": [...], "total": N}`
+    Envelope,
+}
+
+impl ListShape {
+    /// Názov tvaru v konfigurácii a v parametri `profile`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ListShape::Array => "array",
+            ListShape::Envelope => "envelope",
+        }
+    }
+}
+
+impl fmt::Display for ListShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ListShape {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "array" => Ok(ListShape::Array),
+            "envelope" => Ok(ListShape::Envelope),
+            other => Err(format!("neznámy tvar zoznamu {other:?} (array, envelope)")),
+        }
+    }
+}
+
+/// Zistí tvar zoznamu vyžiadaný parametrom `profile` v hlavičke `Accept`.
+///
+/// Berie sa prvý typ `application/json` (alebo `*/*`) so známym profilom.
+///
+/// # Arguments
+/// * `headers` – hlavičky požiadavky
+///
+/// # Returns
+/// Vyžiadaný tvar alebo `None` (platí predvolený z konfigurácie)
+pub fn requested(headers: &HeaderMap) -> Option<ListShape> {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|range| {
+            let mut params = range.split(';');
+            let media = params.next()?.trim();
+            if !media.eq_ignore_ascii_case("application/json") && media != "*/*" {
+                return None;
+            }
+            params.find_map(|param| {
+                let (key, value) = param.split_once('=')?;
+                if !key.trim().eq_ignore_ascii_case("profile") {
+                    return None;
+                }
+                value.trim().trim_matches('"').parse().ok()
+            })
+        })
+}
+
+/// Middleware, ktoré odpovede zoznamov prevedie do vyžiadaného tvaru.
+///
+/// Mení len úspešné odpovede `application/json`, ktorých telo je pole. Všetky
+/// JSON odpovede na čítanie dostanú `Vary: Accept`, keďže tvar závisí od hlavičky.
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie (predvolený tvar `list_shape`)
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď handlera, prípadne s poľom zabaleným do obálky
+pub async fn negotiate(State(config): State<Arc<Config>>, req: Request, next: Next) -> Response {
+    let listing = req.method() == Method::GET
+        || (req.method() == Method::POST && auth::READ_ONLY_POSTS.contains(&req.uri().path()));
+    let shape = requested(req.headers()).unwrap_or(config.list_shape);
+
+    let mut res = next.run(req).await;
+    if !listing || !res.status().is_success() || !is_json(res.headers()) {
+        return res;
+    }
+    res.headers_mut().append(header::VARY, HeaderValue::from_static("accept"));
+    if shape == ListShape::Array {
+        return res;
+    }
+
+    let (mut parts, body) = res.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("Chyba pri čítaní odpovede zoznamu: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Ok(Value::Array(items)) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let total = parts
+        .headers
+        .get(X_TOTAL_COUNT)
+        .and_then(|v| v.to_str().ok()?.parse::<u64>().ok())
+        .unwrap_or(items.len() as u64);
+    let envelope = serde_json::json!({ "items": items, "total": total });
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(ACCEPT_ENVELOPE));
+    Response::from_parts(parts, Body::from(envelope.to_string()))
+}
+
+/// Či je odpoveď JSON (bez ohľadu na parametre typu).
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|media| media.trim().eq_ignore_ascii_case("application/json"))
+}
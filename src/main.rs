@@ -1,63 +1,219 @@
-mod db;
-mod structs;
-mod db_filler;
-mod api;
-mod server;
-
-use db_filler::DBFiller;
+use store_manager::config::Config;
+use store_manager::db::StoreDB;
+use store_manager::health::Readiness;
+use store_manager::scheduler;
+use store_manager::server;
+use store_manager::startup;
+use store_manager::stores;
+use store_manager::structs::SyncCounts;
+use store_manager::sync;
+use store_manager::telemetry;
+use store_manager::tui;
 use anyhow::Result;
-use db::StoreDB;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tokio::signal;
 
 /// Vstupný bod aplikácie.
-#[tokio::main]
-async fn main() -> Result<()> {
-    let db_path = "store.db";
-    let json_path = "store_data.json";
-    let db_exists = Path::new(db_path).exists();
-
-    // Inicializácia databázy
-    let store_db = StoreDB::new().await?;
-
-    // Ak DB neexistuje, pokúsi sa ju naplniť z JSONu
-    if !db_exists {
-        println!("Databaza nenajdena, pokus o načitávanie z JSONu...");
-        match DBFiller::load_from_json(&store_db, json_path).await {
-            Ok(_) => {
-                println!("Načtenie úspešné.");
-                if let Err(e) = DBFiller::save_to_json(&store_db, json_path).await {
-                    eprintln!("Nepodarilo sa uložiť databázu do JSONu: {}", e);
-                }
-            }
-            Err(e) => {
-                eprintln!("Nepodarilo sa načítať dáta z JSONu: {}. Začina sa z prázdnou databázou", e);
-            }
+///
+/// Runtime sa vytvára ručne, aby sa dal počet vlákien nastaviť z konfigurácie.
+/// Neplatná konfigurácia ukončí štart s chybou.
+fn main() -> Result<()> {
+    let args = parse_args()?;
+    let mut config = Config::load(args.config.as_deref())?;
+    config.read_only |= args.read_only;
+    if args.tui {
+        return server::build_runtime(&config)?.block_on(tui::run(config));
+    }
+    if let Some(source) = args.sync {
+        return server::build_runtime(&config)?.block_on(run_sync(config, source));
+    }
+    // export tracov sa zapína premennými OTEL_*; vytvára sa mimo runtime
+    let tracer_provider = telemetry::init_from_env();
+
+    let result = server::build_runtime(&config)?.block_on(run(config));
+
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Nepodarilo sa odoslať zvyšné traces: {}", e);
         }
-    } else {
-        println!("Databáza už existuje.");
     }
+    result
+}
+
+/// Argumenty príkazového riadku.
+struct Args {
+    /// Cesta ku konfiguračnému súboru (`--config <cesta>`)
+    config: Option<PathBuf>,
+    /// Spustiť administrátorskú konzolu v termináli namiesto servera (`tui`)
+    tui: bool,
+    /// Spustiť v režime len na čítanie (`--read-only`, prepíše `read_only` z konfigurácie)
+    read_only: bool,
+    /// Namiesto servera stiahnuť zmeny z inej inštancie (`sync --from <url>`)
+    sync: Option<SyncSource>,
+}
+
+/// Vzdialená inštancia pre `sync`.
+struct SyncSource {
+    /// Adresa inštancie (`--from <url>`)
+    url: String,
+    /// API kľúč inštancie (`--api-key <kľúč>`)
+    api_key: Option<String>,
+}
+
+/// Prečíta argumenty `[tui | sync --from <url> [--api-key <kľúč>]] [--read-only] [--config <cesta>]`.
+///
+/// # Returns
+/// Zadané argumenty
+///
+/// # Errors
+/// Ak chýba hodnota argumentu alebo je zadaný neznámy argument
+fn parse_args() -> Result<Args> {
+    let mut args = std::env::args().skip(1);
+    let mut path = None;
+    let mut tui = false;
+    let mut read_only = false;
+    let mut sync = false;
+    let mut from = None;
+    let mut api_key = None;
+    while let Some(arg) = args.next() {
+        if arg == "tui" {
+            tui = true;
+            continue;
+        }
+        if arg == "sync" {
+            sync = true;
+            continue;
+        }
+        if let Some(value) = arg.strip_prefix("--config=") {
+            path = Some(PathBuf::from(value));
+        } else if arg == "--config" {
+            let value = args.next().ok_or_else(|| anyhow::anyhow!("--config vyžaduje cestu k súboru"))?;
+            path = Some(PathBuf::from(value));
+        } else if arg == "--read-only" {
+            read_only = true;
+        } else if arg == "--from" {
+            from = Some(args.next().ok_or_else(|| anyhow::anyhow!("--from vyžaduje adresu inštancie"))?);
+        } else if arg == "--api-key" {
+            api_key = Some(args.next().ok_or_else(|| anyhow::anyhow!("--api-key vyžaduje kľúč"))?);
+        } else {
+            anyhow::bail!(
+                "neznámy argument {arg:?} (použitie: store_manager [tui | sync --from <url> [--api-key <kľúč>]] [--read-only] [--config <cesta>])"
+            );
+        }
+    }
+    let sync = match (sync, from) {
+        (true, Some(url)) => Some(SyncSource { url, api_key }),
+        (true, None) => anyhow::bail!("sync vyžaduje --from <url>"),
+        (false, Some(_)) => anyhow::bail!("--from sa používa len so sync"),
+        (false, None) if api_key.is_some() => anyhow::bail!("--api-key sa používa len so sync"),
+        (false, None) => None,
+    };
+    if read_only && sync.is_some() {
+        anyhow::bail!("sync zapisuje do databázy, nedá sa spustiť s --read-only");
+    }
+    Ok(Args { config: path, tui, read_only, sync })
+}
 
-    // Spustenie servera
-    let server = server::Server::new(store_db.clone());
+/// Stiahne zmeny z inej inštancie do databázy z konfigurácie a vypíše výsledok.
+async fn run_sync(config: Config, source: SyncSource) -> Result<()> {
+    let store_db = StoreDB::new(&config).await?;
+    let report = sync::pull(&store_db, &source.url, source.api_key.as_deref()).await?;
+
+    println!("Synchronizácia z {}:", report.source);
+    for (entity, counts) in [("zamestnanci", &report.employees), ("produkty", &report.products), ("objednávky", &report.orders)] {
+        print_sync_counts(entity, counts);
+    }
+    Ok(())
+}
+
+/// Vypíše výsledok synchronizácie jednej entity vrátane konfliktov.
+fn print_sync_counts(entity: &str, counts: &SyncCounts) {
+    println!(
+        "  {entity}: {} nových, {} zmenených, {} preskočených, {} konfliktov",
+        counts.created,
+        counts.updated,
+        counts.skipped,
+        counts.conflicts.len()
+    );
+    for conflict in &counts.conflicts {
+        println!(
+            "    konflikt {}: zmenené miestne ({}) aj v zdroji ({}), miestna verzia ponechaná",
+            conflict.key,
+            conflict.local_updated_at.map(|t| t.to_string()).unwrap_or_default(),
+            conflict.remote_updated_at.map(|t| t.to_string()).unwrap_or_default()
+        );
+    }
+}
+
+/// Inicializuje databázu, spustí server a pri ukončení uloží dáta do JSONu (ak je zapnutý `json_sync`).
+async fn run(config: Config) -> Result<()> {
+    let db_exists = Path::new(&config.db_path).exists();
+    let readiness = Readiness::starting();
+
+    // Inicializácia databázy (vrátane migrácií) a databáz ďalších obchodov
+    let store_db = StoreDB::new(&config).await?;
+    let stores = stores::open_all(&config).await?;
+
+    // Server beží už počas importu: /livez odpovedá, /readyz hlási priebeh
+    let server = server::Server::new(store_db.clone(), config.clone())
+        .with_readiness(readiness.clone())
+        .with_stores(stores.clone());
+    let grpc_handle = config.grpc_enabled.then(|| {
+        let server = server.clone();
+        tokio::spawn(async move {
+            if let Err(e) = server.run_grpc().await {
+                eprintln!("gRPC server error: {}", e);
+            }
+        })
+    });
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
     let server_handle = tokio::spawn(async move {
-        if let Err(e) = server.run().await {
+        let shutdown = async {
+            shutdown_rx.await.ok();
+        };
+        if let Err(e) = server.run(shutdown).await {
             eprintln!("Server error: {}", e);
         }
     });
 
+    // naplnenie novej databázy z JSONu (`json_sync`) a doplnenie dát
+    startup::prepare(&store_db, &config, db_exists, &readiness).await;
+
+    // snímky štatistík pre /stats/history, upozornenia na stav zásob, politika
+    // uchovávania osobných údajov a rušenie skončených akcií (pre každý obchod)
+    let mut background = Vec::new();
+    for db in std::iter::once(&store_db).chain(stores.values()) {
+        background.push(scheduler::spawn_stats_snapshots(db.clone(), Duration::from_secs(config.stats_snapshot_interval)));
+        background.push(scheduler::spawn_stock_alerts(
+            db.clone(),
+            Duration::from_secs(config.alert_check_interval),
+            config.low_stock_threshold,
+        ));
+        background.push(scheduler::spawn_retention(
+            db.clone(),
+            Duration::from_secs(config.retention_interval),
+            config.retention,
+        ));
+        background.push(scheduler::spawn_sale_expiry(db.clone(), Duration::from_secs(config.sale_expiry_interval)));
+        background.push(scheduler::spawn_price_changes(db.clone(), Duration::from_secs(config.price_change_interval)));
+    }
+
     // Čakanie na Ctrl+C
     signal::ctrl_c().await.ok();
     println!("\nVypína sa server");
+    // rozbehnuté požiadavky sa dokončia (najviac `shutdown_timeout`) pred uložením dát
+    let _ = shutdown_tx.send(());
+    server_handle.await.ok();
+    for handle in background.into_iter().flatten() {
+        handle.abort();
+    }
 
     // Uloženie databázy do JSONu
-    println!("Databáza sa uloží do JSONu...");
-    if let Err(e) = DBFiller::save_to_json(&store_db, json_path).await {
-        eprintln!("Nepodarilo sa uložiť databázu do JSONu: {}", e);
-    } else {
-        println!("Databáza úspešne uložená.");
-    }
+    startup::shutdown(&store_db, &config).await;
 
-    server_handle.abort();
+    if let Some(handle) = grpc_handle {
+        handle.abort();
+    }
     Ok(())
 }
@@ -0,0 +1,93 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::structs::MaintenanceStatus;
+
+/// Cesty, ktoré odpovedajú aj počas údržby (health checky, prepínač údržby
+/// a webové rozhranie, ktoré údržbu zobrazí).
+pub const EXEMPT_PATHS: &[&str] = &["/", "/livez", "/readyz", "/admin/maintenance"];
+
+/// Zdieľaný stav režimu údržby, prepínaný za behu cez `/admin/maintenance`.
+#[derive(Clone)]
+pub struct Maintenance {
+    inner: Arc<MaintenanceInner>,
+}
+
+struct MaintenanceInner {
+    enabled: AtomicBool,
+    retry_after: AtomicU64,
+}
+
+impl Maintenance {
+    /// Vytvorí vypnutý režim údržby.
+    ///
+    /// # Arguments
+    /// * `retry_after` – predvolený počet sekúnd pre hlavičku `Retry-After`
+    pub fn new(retry_after: u64) -> Self {
+        Self {
+            inner: Arc::new(MaintenanceInner {
+                enabled: AtomicBool::new(false),
+                retry_after: AtomicU64::new(retry_after),
+            }),
+        }
+    }
+
+    /// Zapne alebo vypne režim údržby.
+    ///
+    /// # Arguments
+    /// * `enabled` – `true` zapne údržbu
+    /// * `retry_after` – nový počet sekúnd pre `Retry-After` (`None` = bez zmeny)
+    pub fn set(&self, enabled: bool, retry_after: Option<u64>) {
+        if let Some(seconds) = retry_after {
+            self.inner.retry_after.store(seconds, Ordering::Relaxed);
+        }
+        self.inner.enabled.store(enabled, Ordering::Release);
+    }
+
+    /// Zistí, či je zapnutý režim údržby.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::Acquire)
+    }
+
+    /// Vráti aktuálny stav údržby.
+    pub fn status(&self) -> MaintenanceStatus {
+        MaintenanceStatus {
+            maintenance: self.is_enabled(),
+            retry_after: self.inner.retry_after.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Middleware, ktorý počas údržby odmietne požiadavky mimo `EXEMPT_PATHS`.
+///
+/// # Arguments
+/// * `maintenance` – zdieľaný stav údržby
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď handlera alebo `503 Service Unavailable` s `Retry-After`
+/// a telom `{"maintenance": true, "retry_after": N}`
+pub async fn guard(State(maintenance): State<Maintenance>, req: Request, next: Next) -> Response {
+    if !maintenance.is_enabled() || EXEMPT_PATHS.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let status = maintenance.status();
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(header::RETRY_AFTER, HeaderValue::from(status.retry_after))],
+        Json(status),
+    )
+        .into_response()
+}
@@ -0,0 +1,465 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use printpdf::{BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point};
+
+use crate::structs::{InventoryValueReport, LowStockReport, SupplierOrderSheet, Unit};
+
+/// Rozmery strany A4 (mm).
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+/// Okraj strany (mm).
+const MARGIN: f32 = 15.0;
+/// Výška riadku tabuľky (mm).
+const ROW_HEIGHT: f32 = 5.5;
+/// Veľkosť písma tabuľky (pt).
+const FONT_SIZE: f32 = 9.0;
+/// Výška hlavičky strany nad tabuľkou (mm).
+const HEADER_HEIGHT: f32 = 22.0;
+/// Výška päty so stránkovaním (mm).
+const FOOTER_HEIGHT: f32 = 8.0;
+/// Vnútorný okraj bunky (mm).
+const CELL_PADDING: f32 = 1.5;
+/// Prepočet bodov na milimetre.
+const PT_TO_MM: f32 = 0.352_778;
+
+/// Zarovnanie textu v stĺpci.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Stĺpec tabuľky zostavy.
+#[derive(Debug, Clone)]
+pub struct Column {
+    /// Nadpis stĺpca (opakuje sa na každej strane)
+    pub title: &'static str,
+    /// Šírka stĺpca (mm); súčet šírok má byť najviac 180 mm
+    pub width: f32,
+    pub align: Align,
+}
+
+impl Column {
+    fn new(title: &'static str, width: f32, align: Align) -> Self {
+        Self { title, width, align }
+    }
+}
+
+/// Tabuľková zostava na tlač.
+#[derive(Debug, Clone)]
+pub struct TableReport {
+    /// Názov obchodu v hlavičke
+    pub shop_name: String,
+    /// Názov zostavy
+    pub title: String,
+    /// Dátum zostavy v hlavičke
+    pub date: NaiveDate,
+    pub columns: Vec<Column>,
+    /// Bunky riadkov v poradí stĺpcov
+    pub rows: Vec<Vec<String>>,
+    /// Súčty na konci zostavy (popis, hodnota)
+    pub totals: Vec<(String, String)>,
+}
+
+/// Vykreslí tabuľkovú zostavu do PDF na strany A4.
+///
+/// Každá strana má hlavičku s názvom obchodu, názvom zostavy a dátumom,
+/// nadpisy stĺpcov a číslo strany; riadky, ktoré sa nezmestia, pokračujú
+/// na ďalšej strane. Súčty sú za posledným riadkom. Písmo je vstavaná
+/// Helvetica, znaky mimo kódovania WinAnsi (napr. `č`, `ľ`) sa nahradia
+/// znakmi bez diakritiky.
+///
+/// # Arguments
+/// * `report` – zostava
+///
+/// # Returns
+/// Obsah PDF súboru
+///
+/// # Errors
+/// Ak sa PDF nepodarí zostaviť
+pub fn render(report: &TableReport) -> Result<Vec<u8>> {
+    let pages = paginate(report.rows.len(), report.totals.len());
+    let title = to_win_ansi(&report.title);
+    let (doc, first_page, first_layer) = PdfDocument::new(&title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Vrstva 1");
+    let font = doc.add_builtin_font(BuiltinFont::Helvetica).map_err(|e| anyhow!("písmo PDF: {e}"))?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold).map_err(|e| anyhow!("písmo PDF: {e}"))?;
+
+    for (index, page) in pages.iter().enumerate() {
+        let layer = if index == 0 {
+            doc.get_page(first_page).get_layer(first_layer)
+        } else {
+            let (page, layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Vrstva 1");
+            doc.get_page(page).get_layer(layer)
+        };
+        let canvas = Canvas { layer, font: &font, bold: &bold };
+
+        canvas.header(report);
+        let mut top = MARGIN + HEADER_HEIGHT;
+        canvas.row(&report.columns, report.columns.iter().map(|c| c.title), top, true);
+        top += ROW_HEIGHT;
+        canvas.rule(top - 1.5, 0.6);
+
+        for row in &report.rows[page.rows.clone()] {
+            canvas.row(&report.columns, row.iter().map(String::as_str), top, false);
+            top += ROW_HEIGHT;
+        }
+
+        if page.totals {
+            canvas.rule(top - 1.5, 0.6);
+            top += 1.0;
+            for (label, value) in &report.totals {
+                canvas.total(label, value, top);
+                top += ROW_HEIGHT;
+            }
+        }
+
+        canvas.footer(index + 1, pages.len());
+    }
+
+    doc.save_to_bytes().map_err(|e| anyhow!("nepodarilo sa uložiť PDF: {e}"))
+}
+
+/// Zostava hodnoty zásob v PDF.
+///
+/// # Arguments
+/// * `report` – hodnota zásob
+/// * `shop_name` – názov obchodu v hlavičke
+/// * `date` – dátum zostavy
+///
+/// # Errors
+/// Ak sa PDF nepodarí zostaviť
+pub fn inventory_value(report: &InventoryValueReport, shop_name: &str, date: NaiveDate) -> Result<Vec<u8>> {
+    let rows = report
+        .lines
+        .iter()
+        .map(|l| {
+            vec![
+                l.product_id.to_string(),
+                l.name.clone().unwrap_or_default(),
+                l.category.clone().unwrap_or_default(),
+                quantity(l.quantity, l.unit),
+                l.cost_price.map(money).unwrap_or_else(|| "–".into()),
+                money(l.value),
+            ]
+        })
+        .collect();
+
+    let mut totals = vec![
+        ("Počet produktov".into(), report.lines.len().to_string()),
+        ("Hodnota zásob spolu".into(), money(report.total_value)),
+    ];
+    if report.uncosted_products > 0 {
+        totals.push(("Produkty bez nákupnej ceny".into(), report.uncosted_products.to_string()));
+    }
+
+    render(&TableReport {
+        shop_name: shop_name.into(),
+        title: "Hodnota zásob".into(),
+        date,
+        columns: vec![
+            Column::new("ID", 12.0, Align::Right),
+            Column::new("Názov", 54.0, Align::Left),
+            Column::new("Kategória", 32.0, Align::Left),
+            Column::new("Množstvo", 24.0, Align::Right),
+            Column::new("Nákupná cena", 28.0, Align::Right),
+            Column::new("Hodnota", 30.0, Align::Right),
+        ],
+        rows,
+        totals,
+    })
+}
+
+/// Inventúrny súpis zásob v PDF (názov, kategória, množstvo a hodnota produktov).
+///
+/// Za súpisom sú súčty hodnoty po kategóriách a celková hodnota zásob.
+///
+/// # Arguments
+/// * `report` – hodnota zásob
+/// * `shop_name` – názov obchodu v hlavičke
+/// * `date` – dátum zostavy
+///
+/// # Errors
+/// Ak sa PDF nepodarí zostaviť
+pub fn inventory(report: &InventoryValueReport, shop_name: &str, date: NaiveDate) -> Result<Vec<u8>> {
+    let rows = report
+        .lines
+        .iter()
+        .map(|l| {
+            vec![
+                l.name.clone().unwrap_or_default(),
+                l.category.clone().unwrap_or_default(),
+                quantity(l.quantity, l.unit),
+                money(l.value),
+            ]
+        })
+        .collect();
+
+    let mut categories: BTreeMap<&str, f64> = BTreeMap::new();
+    for line in &report.lines {
+        *categories.entry(line.category.as_deref().unwrap_or("Neznáma")).or_default() += line.value;
+    }
+    let mut totals: Vec<(String, String)> = categories
+        .into_iter()
+        .map(|(category, value)| (format!("Kategória {category}"), money(value)))
+        .collect();
+    totals.push(("Počet produktov".into(), report.lines.len().to_string()));
+    totals.push(("Hodnota zásob spolu".into(), money(report.total_value)));
+
+    render(&TableReport {
+        shop_name: shop_name.into(),
+        title: "Inventúrny súpis zásob".into(),
+        date,
+        columns: vec![
+            Column::new("Názov", 76.0, Align::Left),
+            Column::new("Kategória", 44.0, Align::Left),
+            Column::new("Množstvo", 28.0, Align::Right),
+            Column::new("Hodnota", 32.0, Align::Right),
+        ],
+        rows,
+        totals,
+    })
+}
+
+/// Zostava produktov s nízkym stavom zásob v PDF.
+///
+/// # Arguments
+/// * `report` – produkty s nízkym stavom
+/// * `shop_name` – názov obchodu v hlavičke
+/// * `date` – dátum zostavy
+///
+/// # Errors
+/// Ak sa PDF nepodarí zostaviť
+pub fn low_stock(report: &LowStockReport, shop_name: &str, date: NaiveDate) -> Result<Vec<u8>> {
+    let rows = report
+        .lines
+        .iter()
+        .map(|l| {
+            vec![
+                l.product_id.to_string(),
+                l.name.clone().unwrap_or_default(),
+                l.category.clone().unwrap_or_default(),
+                l.supplier.clone().unwrap_or_default(),
+                quantity(l.quantity, l.unit),
+            ]
+        })
+        .collect();
+
+    render(&TableReport {
+        shop_name: shop_name.into(),
+        title: format!("Nízky stav zásob (hranica {})", report.threshold),
+        date,
+        columns: vec![
+            Column::new("ID", 14.0, Align::Right),
+            Column::new("Názov", 62.0, Align::Left),
+            Column::new("Kategória", 36.0, Align::Left),
+            Column::new("Dodávateľ", 44.0, Align::Left),
+            Column::new("Množstvo", 24.0, Align::Right),
+        ],
+        rows,
+        totals: vec![
+            ("Počet produktov".into(), report.lines.len().to_string()),
+            ("Z toho vypredané".into(), report.out_of_stock.to_string()),
+        ],
+    })
+}
+
+/// Objednávka u dodávateľa v PDF (produkty na doobjednanie s množstvom a cenou).
+///
+/// # Arguments
+/// * `sheet` – podklad objednávky (`StoreDB::supplier_order_sheet`)
+/// * `shop_name` – názov obchodu v hlavičke
+/// * `date` – dátum objednávky
+///
+/// # Errors
+/// Ak sa PDF nepodarí zostaviť
+pub fn purchase_order(sheet: &SupplierOrderSheet, shop_name: &str, date: NaiveDate) -> Result<Vec<u8>> {
+    let rows = sheet
+        .lines
+        .iter()
+        .map(|l| {
+            vec![
+                l.product_id.to_string(),
+                l.name.clone().unwrap_or_default(),
+                quantity(l.suggested_quantity, l.unit),
+                l.unit_cost.map(money).unwrap_or_else(|| "–".into()),
+                l.estimated_cost.map(money).unwrap_or_else(|| "–".into()),
+            ]
+        })
+        .collect();
+
+    let mut totals = vec![
+        ("Počet položiek".into(), sheet.lines.len().to_string()),
+        ("Spolu".into(), money(sheet.total_cost)),
+    ];
+    if sheet.uncosted_lines > 0 {
+        totals.push(("Položky bez nákupnej ceny".into(), sheet.uncosted_lines.to_string()));
+    }
+
+    render(&TableReport {
+        shop_name: shop_name.into(),
+        title: format!("Objednávka – {}", sheet.supplier),
+        date,
+        columns: vec![
+            Column::new("ID", 14.0, Align::Right),
+            Column::new("Názov", 74.0, Align::Left),
+            Column::new("Množstvo", 30.0, Align::Right),
+            Column::new("Cena/j.", 28.0, Align::Right),
+            Column::new("Spolu", 34.0, Align::Right),
+        ],
+        rows,
+        totals,
+    })
+}
+
+/// Riadky jednej strany a či sú na nej súčty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Page {
+    rows: std::ops::Range<usize>,
+    totals: bool,
+}
+
+/// Rozdelí riadky na strany; súčty idú na poslednú stranu alebo na novú, ak sa nezmestia.
+fn paginate(rows: usize, totals: usize) -> Vec<Page> {
+    let body = PAGE_HEIGHT - 2.0 * MARGIN - HEADER_HEIGHT - FOOTER_HEIGHT - ROW_HEIGHT;
+    let per_page = ((body / ROW_HEIGHT) as usize).max(1);
+    let totals_rows = totals + 1;
+
+    let mut pages: Vec<Page> = (0..rows.div_ceil(per_page).max(1))
+        .map(|i| Page { rows: i * per_page..((i + 1) * per_page).min(rows), totals: false })
+        .collect();
+    let last = pages.last_mut().expect("aspoň jedna strana");
+    if last.rows.len() + totals_rows <= per_page {
+        last.totals = true;
+    } else {
+        pages.push(Page { rows: rows..rows, totals: true });
+    }
+    pages
+}
+
+/// Kreslenie na jednu stranu.
+struct Canvas<'a> {
+    layer: PdfLayerReference,
+    font: &'a IndirectFontRef,
+    bold: &'a IndirectFontRef,
+}
+
+impl Canvas<'_> {
+    fn text(&self, text: &str, size: f32, x: f32, top: f32, bold: bool) {
+        let font = if bold { self.bold } else { self.font };
+        self.layer.use_text(to_win_ansi(text), size, Mm(x), Mm(PAGE_HEIGHT - top), font);
+    }
+
+    fn header(&self, report: &TableReport) {
+        let date = report.date.format("%d.%m.%Y").to_string();
+        self.text(&report.shop_name, 14.0, MARGIN, MARGIN + 5.0, true);
+        self.text(&report.title, 11.0, MARGIN, MARGIN + 12.0, false);
+        self.text(&date, 10.0, PAGE_WIDTH - MARGIN - text_width(&date, 10.0), MARGIN + 5.0, false);
+        self.rule(MARGIN + 15.0, 0.8);
+    }
+
+    fn row<'s>(&self, columns: &[Column], cells: impl Iterator<Item = &'s str>, top: f32, bold: bool) {
+        let mut x = MARGIN;
+        for (column, cell) in columns.iter().zip(cells) {
+            let text = fit(cell, column.width - 2.0 * CELL_PADDING, FONT_SIZE);
+            let offset = match column.align {
+                Align::Left => CELL_PADDING,
+                Align::Right => column.width - CELL_PADDING - text_width(&text, FONT_SIZE),
+            };
+            self.text(&text, FONT_SIZE, x + offset, top + ROW_HEIGHT - 1.5, bold);
+            x += column.width;
+        }
+    }
+
+    fn total(&self, label: &str, value: &str, top: f32) {
+        let right = PAGE_WIDTH - MARGIN - CELL_PADDING;
+        self.text(label, FONT_SIZE, MARGIN + CELL_PADDING, top + ROW_HEIGHT - 1.5, true);
+        self.text(value, FONT_SIZE, right - text_width(value, FONT_SIZE), top + ROW_HEIGHT - 1.5, true);
+    }
+
+    fn footer(&self, page: usize, pages: usize) {
+        let text = format!("Strana {page} / {pages}");
+        self.text(&text, 8.0, PAGE_WIDTH - MARGIN - text_width(&text, 8.0), PAGE_HEIGHT - MARGIN, false);
+    }
+
+    fn rule(&self, top: f32, thickness: f32) {
+        let y = Mm(PAGE_HEIGHT - top);
+        self.layer.set_outline_thickness(thickness);
+        self.layer.add_line(Line {
+            points: vec![(Point::new(Mm(MARGIN), y), false), (Point::new(Mm(PAGE_WIDTH - MARGIN), y), false)],
+            is_closed: false,
+        });
+    }
+}
+
+/// Skráti text, aby sa zmestil do šírky (mm), a doplní `...`.
+fn fit(text: &str, width: f32, size: f32) -> String {
+    if text_width(text, size) <= width {
+        return text.to_string();
+    }
+    let mut fitted = String::new();
+    for c in text.chars() {
+        if text_width(&fitted, size) + text_width(&format!("{c}..."), size) > width {
+            break;
+        }
+        fitted.push(c);
+    }
+    fitted.trim_end().to_string() + "..."
+}
+
+/// Približná šírka textu v písme Helvetica (mm).
+fn text_width(text: &str, size: f32) -> f32 {
+    let em: f32 = text
+        .chars()
+        .map(|c| match c {
+            ' ' | '.' | ',' | ':' | ';' | '!' | '|' | 'i' | 'j' | 'l' | 'í' | 'ĺ' | 'ľ' | '\'' => 0.278,
+            'f' | 't' | 'r' | 'ť' | 'ŕ' | '(' | ')' | '-' | '/' => 0.333,
+            'm' | 'M' | 'W' => 0.833,
+            'w' => 0.722,
+            c if c.is_ascii_digit() => 0.556,
+            c if c.is_uppercase() => 0.667,
+            _ => 0.556,
+        })
+        .sum();
+    em * size * PT_TO_MM
+}
+
+/// Nahradí znaky, ktoré kódovanie WinAnsi vstavaných písiem nepozná.
+fn to_win_ansi(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'č' => 'c',
+            'Č' => 'C',
+            'ď' => 'd',
+            'Ď' => 'D',
+            'ľ' | 'ĺ' => 'l',
+            'Ľ' | 'Ĺ' => 'L',
+            'ň' => 'n',
+            'Ň' => 'N',
+            'ŕ' | 'ř' => 'r',
+            'Ŕ' | 'Ř' => 'R',
+            'ť' => 't',
+            'Ť' => 'T',
+            'ě' => 'e',
+            'Ě' => 'E',
+            'ů' => 'u',
+            'Ů' => 'U',
+            c => c,
+        })
+        .collect()
+}
+
+/// Peňažná suma s dvoma desatinnými miestami.
+fn money(value: f64) -> String {
+    format!("{value:.2}")
+}
+
+/// Množstvo s jednotkou (kusy a balenia bez desatinných miest).
+fn quantity(value: f64, unit: Unit) -> String {
+    if unit.is_fractional() {
+        format!("{value:.3} {}", unit.as_str())
+    } else {
+        format!("{value:.0} {}", unit.as_str())
+    }
+}
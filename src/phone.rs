@@ -0,0 +1,115 @@
+use crate::structs::Employee;
+
+/// Predvolená krajina pre telefónne čísla zadané bez medzinárodnej predvoľby.
+pub const DEFAULT_COUNTRY: &str = "SK";
+
+/// Medzinárodné predvoľby podporovaných krajín (ISO 3166-1 alpha-2).
+const COUNTRY_CODES: &[(&str, &str)] = &[
+    ("SK", "421"),
+    ("CZ", "420"),
+    ("AT", "43"),
+    ("HU", "36"),
+    ("PL", "48"),
+    ("DE", "49"),
+    ("UA", "380"),
+    ("GB", "44"),
+    ("US", "1"),
+];
+
+/// Najkratšie národné číslo, ktoré sa ešte považuje za úplné (bez predvoľby krajiny).
+const MIN_NATIONAL_DIGITS: usize = 8;
+
+/// Vráti medzinárodnú predvoľbu krajiny.
+///
+/// # Arguments
+/// * `country` – kód krajiny (napr. `SK`), na veľkosti písmen nezáleží
+///
+/// # Returns
+/// Predvoľba bez `+` alebo `None` pre nepodporovanú krajinu
+pub fn country_code(country: &str) -> Option<&'static str> {
+    COUNTRY_CODES
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(country.trim()))
+        .map(|(_, code)| *code)
+}
+
+/// Prevedie telefónne číslo do tvaru E.164 (`+421900123456`).
+///
+/// Akceptuje medzinárodný tvar (`+421 ...`, `00421 ...`), národný tvar
+/// s úvodnou nulou (`0900 ...`) a číslo s predvoľbou krajiny bez `+`
+/// (`421900...`). Medzery, pomlčky, bodky, lomky a zátvorky sa ignorujú.
+/// Číslo bez predvoľby dostane predvoľbu krajiny `default_country`.
+///
+/// # Arguments
+/// * `input` – zadané číslo
+/// * `default_country` – krajina pre čísla bez medzinárodnej predvoľby
+///
+/// # Returns
+/// Číslo v tvare E.164
+///
+/// # Errors
+/// Popis chyby, ak číslo obsahuje nepovolené znaky, má nesprávnu dĺžku
+/// alebo je bez predvoľby a krajina nie je podporovaná
+pub fn normalize(input: &str, default_country: &str) -> Result<String, String> {
+    let trimmed = input.trim();
+    let (plus, rest) = match trimmed.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed),
+    };
+
+    if let Some(c) = rest.chars().find(|c| !c.is_ascii_digit() && !" -./()".contains(*c)) {
+        return Err(format!("telefónne číslo {input:?} obsahuje nepovolený znak {c:?}"));
+    }
+    let digits: String = rest.chars().filter(char::is_ascii_digit).collect();
+
+    let international = if plus {
+        digits
+    } else if let Some(rest) = digits.strip_prefix("00") {
+        rest.to_string()
+    } else {
+        let code = country_code(default_country)
+            .ok_or_else(|| format!("nepodporovaná krajina {default_country:?} pre číslo {input:?}"))?;
+        match digits.strip_prefix('0') {
+            Some(national) => format!("{code}{national}"),
+            None if digits.starts_with(code) && digits.len() - code.len() >= MIN_NATIONAL_DIGITS => digits,
+            None => format!("{code}{digits}"),
+        }
+    };
+
+    if !(8..=15).contains(&international.len()) || international.starts_with('0') {
+        return Err(format!("telefónne číslo {input:?} nemá platný tvar"));
+    }
+    Ok(format!("+{international}"))
+}
+
+/// Doplní zamestnancovi telefón v tvare E.164 podľa zadaného `phone_number`.
+///
+/// Prázdne číslo nemá normalizovaný tvar.
+///
+/// # Arguments
+/// * `employee` – zamestnanec
+/// * `country` – krajina pre čísla bez medzinárodnej predvoľby
+///
+/// # Errors
+/// Popis chyby, ak sa číslo nedá rozpoznať
+pub fn normalize_employee(employee: &mut Employee, country: &str) -> Result<(), String> {
+    employee.phone_e164 = match employee.phone_number.as_deref().map(str::trim) {
+        Some(raw) if !raw.is_empty() => Some(normalize(raw, country)?),
+        _ => None,
+    };
+    Ok(())
+}
+
+/// Upraví filter zamestnancov tak, aby sa telefón porovnával v normalizovanom tvare.
+///
+/// Číslo, ktoré sa nedá rozpoznať, sa porovnáva tak, ako bolo zadané.
+///
+/// # Arguments
+/// * `filter` – filter zamestnancov
+/// * `country` – krajina pre čísla bez medzinárodnej predvoľby
+pub fn normalize_filter(filter: &mut Employee, country: &str) {
+    if let Some(e164) = filter.phone_number.as_deref().and_then(|raw| normalize(raw, country).ok()) {
+        filter.phone_e164 = Some(e164);
+        filter.phone_number = None;
+    }
+}
@@ -0,0 +1,325 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Počet milióntin v eure (presnosť výpočtu pri zaokrúhľovaní).
+const MICROS: f64 = 1_000_000.0;
+
+/// Pravidlo zaokrúhľovania predajných cien.
+///
+/// Zaokrúhľuje sa na najbližšiu povolenú cenu, pri zhode vzdialeností nahor.
+/// Kladná cena sa nikdy nezaokrúhli na nulu, namiesto toho sa použije
+/// najnižšia povolená cena (0.05, 0.10, 0.09, 0.99, resp. 1.00).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceRounding {
+    /// Cena sa nezaokrúhľuje
+    #[default]
+    None,
+    /// Na najbližších 0.05
+    Nearest005,
+    /// Na najbližších 0.10
+    Nearest010,
+    /// Psychologická cena končiaca na .x9 (3.49, 3.59, ...)
+    Psychological,
+    /// Na najbližšiu cenu končiacu na .99 (2.99, 3.99, ...)
+    Nearest099,
+    /// Nahor na celé eurá
+    CeilInteger,
+}
+
+impl PriceRounding {
+    /// Zaokrúhli cenu podľa pravidla.
+    ///
+    /// Cena sa najprv zaokrúhli na milióntiny eura, aby výsledok neovplyvnil
+    /// šum z desatinnej aritmetiky (3.475 sa správa ako presná polovica).
+    ///
+    /// # Arguments
+    /// * `price` – vypočítaná cena
+    ///
+    /// # Returns
+    /// Zaokrúhlená cena; nekladné a neplatné ceny sa vrátia bez zmeny
+    pub fn apply(&self, price: f64) -> f64 {
+        if !price.is_finite() || price <= 0.0 {
+            return price;
+        }
+
+        // ceny v milióntinách eura
+        let micro = (price * MICROS).round() as i64;
+        let rounded = match self {
+            PriceRounding::None => return price,
+            PriceRounding::Nearest005 => Self::nearest_step(micro, 50_000),
+            PriceRounding::Nearest010 => Self::nearest_step(micro, 100_000),
+            // kandidáti sú 0.10 * n - 0.01 (x.09, x.19, ...); pri zhode sa berie vyšší
+            PriceRounding::Psychological => ((micro + 60_000) / 100_000).max(1) * 100_000 - 10_000,
+            // kandidáti sú celé eurá mínus 0.01 (0.99, 1.99, ...); pri zhode sa berie vyšší
+            PriceRounding::Nearest099 => ((micro + 510_000) / 1_000_000).max(1) * 1_000_000 - 10_000,
+            PriceRounding::CeilInteger => (micro + 999_999) / 1_000_000 * 1_000_000,
+        };
+        rounded as f64 / MICROS
+    }
+
+    /// Zaokrúhli kladnú sumu na najbližší násobok kroku (pri zhode nahor), najmenej na jeden krok.
+    fn nearest_step(micro: i64, step: i64) -> i64 {
+        ((micro + step / 2) / step).max(1) * step
+    }
+}
+
+impl fmt::Display for PriceRounding {
+    /// Zapíše pravidlo v tvare konfigurácie (`none`, `0.05`, `0.10`, `x9`, `0.99`, `ceil`).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PriceRounding::None => "none",
+            PriceRounding::Nearest005 => "0.05",
+            PriceRounding::Nearest010 => "0.10",
+            PriceRounding::Psychological => "x9",
+            PriceRounding::Nearest099 => "0.99",
+            PriceRounding::CeilInteger => "ceil",
+        })
+    }
+}
+
+impl FromStr for PriceRounding {
+    type Err = String;
+
+    /// Prevedie hodnotu z konfigurácie (`none`, `0.05`, `0.10`, `x9`, `0.99`, `ceil`) na pravidlo.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "none" => Ok(PriceRounding::None),
+            "0.05" | "nearest005" | "nearest_005" => Ok(PriceRounding::Nearest005),
+            "0.10" | "0.1" | "nearest010" | "nearest_010" => Ok(PriceRounding::Nearest010),
+            "x9" | ".x9" | "psychological" => Ok(PriceRounding::Psychological),
+            "0.99" | ".99" | "nearest099" | "nearest_099" => Ok(PriceRounding::Nearest099),
+            "ceil" | "up" | "ceil_integer" => Ok(PriceRounding::CeilInteger),
+            other => Err(format!("neznáme pravidlo zaokrúhľovania: {other}")),
+        }
+    }
+}
+
+/// Minimálna marža predajnej ceny nad nákupnou cenou.
+///
+/// Percentuálna marža sa počíta z predajnej ceny (`(predaj - nákup) / predaj`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(try_from = "String", into = "String")]
+pub enum MarginRule {
+    /// Najmenší rozdiel predajnej a nákupnej ceny v eurách
+    Absolute(f64),
+    /// Najmenší podiel marže na predajnej cene v percentách
+    Percent(f64),
+}
+
+impl MarginRule {
+    /// Či cena spĺňa pravidlo; porovnáva sa s toleranciou na zaokrúhľovací šum.
+    ///
+    /// # Arguments
+    /// * `cost_price` – nákupná cena
+    /// * `sell_price` – predajná cena
+    pub fn allows(&self, cost_price: f64, sell_price: f64) -> bool {
+        let (margin, percent) = margin_of(cost_price, sell_price);
+        match self {
+            MarginRule::Absolute(min) => margin >= min - 1e-9,
+            MarginRule::Percent(min) => percent >= min - 1e-9,
+        }
+    }
+}
+
+impl fmt::Display for MarginRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarginRule::Absolute(min) => write!(f, "{min:.2} €"),
+            MarginRule::Percent(min) => write!(f, "{min} %"),
+        }
+    }
+}
+
+impl FromStr for MarginRule {
+    type Err = String;
+
+    /// Prevedie hodnotu z konfigurácie na pravidlo: `15%` je percentuálna, `0.20` absolútna marža.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (number, percent) = match value.strip_suffix('%') {
+            Some(number) => (number.trim(), true),
+            None => (value, false),
+        };
+        let min: f64 = number.parse().map_err(|_| format!("neplatná marža {value:?} (napr. 15% alebo 0.20)"))?;
+        if !min.is_finite() || min < 0.0 || (percent && min >= 100.0) {
+            return Err(format!("marža {value:?} je mimo rozsahu"));
+        }
+        Ok(if percent { MarginRule::Percent(min) } else { MarginRule::Absolute(min) })
+    }
+}
+
+impl From<MarginRule> for String {
+    /// Zapíše pravidlo v tvare konfigurácie (`15%`, `0.2`).
+    fn from(rule: MarginRule) -> Self {
+        match rule {
+            MarginRule::Absolute(min) => min.to_string(),
+            MarginRule::Percent(min) => format!("{min}%"),
+        }
+    }
+}
+
+impl TryFrom<String> for MarginRule {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Politika minimálnej marže: globálne pravidlo a výnimky pre kategórie.
+///
+/// Kontroluje sa pri zmene predajnej ceny; produkt bez nákupnej ceny sa nekontroluje.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarginPolicy {
+    /// Pravidlo pre všetky kategórie (`None` = marža sa nekontroluje)
+    pub default: Option<MarginRule>,
+    /// Pravidlá pre jednotlivé kategórie, majú prednosť pred `default`
+    pub categories: BTreeMap<String, MarginRule>,
+}
+
+impl MarginPolicy {
+    /// Či politika neobsahuje žiadne pravidlo.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.categories.is_empty()
+    }
+
+    /// Pravidlo platné pre kategóriu.
+    ///
+    /// # Arguments
+    /// * `category` – kategória produktu
+    pub fn rule_for(&self, category: Option<&str>) -> Option<MarginRule> {
+        category.and_then(|c| self.categories.get(c)).copied().or(self.default)
+    }
+
+    /// Skontroluje ceny produktu voči pravidlu jeho kategórie.
+    ///
+    /// # Arguments
+    /// * `category` – kategória produktu
+    /// * `cost_price` – nákupná cena (`None` = kontrola sa preskočí)
+    /// * `sell_price` – predajná cena
+    ///
+    /// # Returns
+    /// Zistená marža, ak porušuje pravidlo
+    pub fn check(&self, category: Option<&str>, cost_price: Option<f64>, sell_price: f64) -> Option<MarginShortfall> {
+        let rule = self.rule_for(category)?;
+        let cost_price = cost_price?;
+        if rule.allows(cost_price, sell_price) {
+            return None;
+        }
+        let (margin, margin_percent) = margin_of(cost_price, sell_price);
+        Some(MarginShortfall { margin, margin_percent, rule })
+    }
+
+    /// Prevedie zoznam výnimiek `Kategória=15%,Iná=0.20` (premenná prostredia) na mapu.
+    ///
+    /// # Errors
+    /// Ak položka nemá tvar `kategória=marža` alebo marža nie je platná
+    pub fn parse_categories(value: &str) -> Result<BTreeMap<String, MarginRule>, String> {
+        value
+            .split(',')
+            .filter(|item| !item.trim().is_empty())
+            .map(|item| {
+                let (category, rule) =
+                    item.split_once('=').ok_or_else(|| format!("očakáva sa kategória=marža, nie {item:?}"))?;
+                Ok((category.trim().to_string(), rule.parse()?))
+            })
+            .collect()
+    }
+}
+
+/// Marža, ktorá nespĺňa pravidlo politiky.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarginShortfall {
+    /// Rozdiel predajnej a nákupnej ceny
+    pub margin: f64,
+    /// Marža v percentách predajnej ceny
+    pub margin_percent: f64,
+    /// Porušené pravidlo
+    pub rule: MarginRule,
+}
+
+impl fmt::Display for MarginShortfall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "marža {:.2} € ({:.1} %) je pod minimom {}", self.margin, self.margin_percent, self.rule)
+    }
+}
+
+/// Marža v eurách a v percentách predajnej ceny (pri nekladnej predajnej cene 0 %).
+fn margin_of(cost_price: f64, sell_price: f64) -> (f64, f64) {
+    let margin = sell_price - cost_price;
+    let percent = if sell_price > 0.0 { margin / sell_price * 100.0 } else { 0.0 };
+    (margin, percent)
+}
+
+/// Odhadne cenovú elasticitu dopytu z cenových bodov.
+///
+/// Elasticita je sklon priamky `ln(predaj) = a + e · ln(cena)` metódou najmenších
+/// štvorcov; pri dvoch bodoch je to oblúková elasticita v logaritmoch
+/// `ln(q2/q1) / ln(p2/p1)`. Body s nekladnou cenou alebo predajom sa vynechajú.
+///
+/// # Arguments
+/// * `points` – dvojice (cena, predaj za deň)
+///
+/// # Returns
+/// Elasticita (záporná, ak predaj s cenou klesá) alebo `None` pri menej ako
+/// dvoch rôznych použiteľných cenách
+pub fn elasticity(points: &[(f64, f64)]) -> Option<f64> {
+    let logs: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(price, rate)| *price > 0.0 && *rate > 0.0 && price.is_finite() && rate.is_finite())
+        .map(|(price, rate)| (price.ln(), rate.ln()))
+        .collect();
+    if logs.len() < 2 {
+        return None;
+    }
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    (sxx > 1e-12).then(|| sxy / sxx)
+}
+
+/// Najmenší počet ostatných cien skupiny, s ktorými má zmysel cenu porovnávať.
+pub const MIN_OUTLIER_PEERS: usize = 3;
+
+/// Najmenšia smerodajná odchýlka logaritmu ceny (asi 5 %); pri takmer rovnakých
+/// cenách ostatných by inak aj bežný rozdiel vyšiel ako extrém.
+const MIN_LOG_SPREAD: f64 = 0.05;
+
+/// Vypočíta, o koľko smerodajných odchýlok sa každá cena líši od ostatných cien skupiny.
+///
+/// Porovnáva sa logaritmus ceny (ceny v kategórii sa líšia skôr násobkom než
+/// rozdielom) a každá cena sa meria voči priemeru a smerodajnej odchýlke
+/// ostatných cien bez nej, aby extrémna cena nezväčšila rozptyl, voči ktorému
+/// sa posudzuje. Nekladné a neplatné ceny sa vynechajú.
+///
+/// # Arguments
+/// * `prices` – ceny produktov jednej skupiny
+///
+/// # Returns
+/// Z-skóre pre každú cenu v rovnakom poradí (kladné = drahšia ako ostatné);
+/// `None` pre vynechané ceny a pre všetky ceny skupiny s menej ako
+/// `MIN_OUTLIER_PEERS` ostatnými cenami
+pub fn outlier_scores(prices: &[f64]) -> Vec<Option<f64>> {
+    let logs: Vec<Option<f64>> = prices.iter().map(|&p| (p > 0.0 && p.is_finite()).then(|| p.ln())).collect();
+    let valid: Vec<f64> = logs.iter().flatten().copied().collect();
+    if valid.len() <= MIN_OUTLIER_PEERS {
+        return vec![None; prices.len()];
+    }
+    let peers = (valid.len() - 1) as f64;
+    let sum: f64 = valid.iter().sum();
+    let sum_sq: f64 = valid.iter().map(|x| x * x).sum();
+    logs.iter()
+        .map(|x| {
+            let x = (*x)?;
+            let (others, others_sq) = (sum - x, sum_sq - x * x);
+            let mean = others / peers;
+            let variance = ((others_sq - others * mean) / (peers - 1.0)).max(0.0);
+            Some((x - mean) / variance.sqrt().max(MIN_LOG_SPREAD))
+        })
+        .collect()
+}
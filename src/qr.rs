@@ -0,0 +1,81 @@
+//! Vykreslenie QR kódu do PNG (napr. odkaz na produkt na tabuľke v predajni).
+
+use qrcode::{Color, QrCode};
+
+/// Predvolená strana obrázka v pixeloch.
+pub const DEFAULT_SIZE: u32 = 256;
+/// Najmenšia povolená strana obrázka v pixeloch.
+pub const MIN_SIZE: u32 = 64;
+/// Najväčšia povolená strana obrázka v pixeloch.
+pub const MAX_SIZE: u32 = 2048;
+/// Tichá zóna okolo kódu v moduloch (podľa normy).
+const QUIET_ZONE: u32 = 4;
+
+/// Zakódovaný QR kód (štvorec modulov).
+#[derive(Debug, Clone)]
+pub struct Qr {
+    width: u32,
+    dark:  Vec<bool>,
+}
+
+impl Qr {
+    /// Zakóduje text so strednou úrovňou opravy chýb.
+    ///
+    /// # Errors
+    /// Text sa do QR kódu nezmestí
+    pub fn new(text: &str) -> Result<Self, String> {
+        let code = QrCode::new(text.as_bytes()).map_err(|e| format!("text sa nedá zakódovať do QR kódu: {e}"))?;
+        Ok(Self {
+            width: code.width() as u32,
+            dark:  code.to_colors().into_iter().map(|c| c == Color::Dark).collect(),
+        })
+    }
+
+    /// Najmenšia strana obrázka v pixeloch (kód s tichou zónou, modul 1 px).
+    pub fn min_size(&self) -> u32 {
+        self.width + 2 * QUIET_ZONE
+    }
+
+    /// Overí stranu obrázka.
+    ///
+    /// # Errors
+    /// Popis chyby, ak je strana mimo rozsahu alebo sa do nej kód nezmestí
+    pub fn validate_size(&self, size: u32) -> Result<(), String> {
+        if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+            return Err(format!("veľkosť musí byť {MIN_SIZE} až {MAX_SIZE} px"));
+        }
+        if size < self.min_size() {
+            return Err(format!("kód potrebuje aspoň {} px", self.min_size()));
+        }
+        Ok(())
+    }
+
+    /// Vykreslí kód ako štvorcové PNG so stranou `size` (8-bitové odtiene sivej).
+    ///
+    /// Moduly majú najväčšiu celočíselnú veľkosť, pri ktorej sa kód s tichou zónou
+    /// zmestí; zvyšok sa rozdelí do bieleho okraja, takže kód je v strede.
+    ///
+    /// # Errors
+    /// Chyba kódovania PNG
+    pub fn to_png(&self, size: u32) -> Result<Vec<u8>, png::EncodingError> {
+        let module = (size / self.min_size()).max(1);
+        let offset = (size.saturating_sub(self.width * module)) / 2;
+        let mut pixels = vec![255u8; (size * size) as usize];
+        for (i, _) in self.dark.iter().enumerate().filter(|(_, &dark)| dark) {
+            let (x, y) = (i as u32 % self.width, i as u32 / self.width);
+            for row in offset + y * module..offset + (y + 1) * module {
+                let start = (row * size + offset + x * module) as usize;
+                pixels[start..start + module as usize].fill(0);
+            }
+        }
+
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, size, size);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        writer.finish()?;
+        Ok(out)
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Range,
+};
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+/// Chyba hlavičky `Range`, na ktorú sa odpovedá `416 Range Not Satisfiable`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RangeNotSatisfiable;
+
+/// Sparsuje hodnotu hlavičky `Range` pre telo danej dĺžky.
+///
+/// Podporuje sa jeden rozsah v bajtoch: `bytes=od-do`, `bytes=od-`
+/// a `bytes=-posledných`. Koniec za dĺžkou tela sa skráti.
+///
+/// # Arguments
+/// * `value` – hodnota hlavičky
+/// * `len` – dĺžka celého tela
+///
+/// # Returns
+/// Rozsah bajtov; `None` ak sa hlavička má ignorovať (iná jednotka alebo
+/// viac rozsahov naraz – vráti sa celé telo)
+///
+/// # Errors
+/// `RangeNotSatisfiable` ak je rozsah neplatný alebo mimo tela
+pub fn parse_range(value: &str, len: u64) -> Result<Option<Range<u64>>, RangeNotSatisfiable> {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let (start, end) = spec.trim().split_once('-').ok_or(RangeNotSatisfiable)?;
+    let number = |s: &str| s.trim().parse::<u64>().map_err(|_| RangeNotSatisfiable);
+
+    let range = match (start.trim(), end.trim()) {
+        ("", "") => return Err(RangeNotSatisfiable),
+        ("", suffix) => {
+            let suffix = number(suffix)?;
+            if suffix == 0 {
+                return Err(RangeNotSatisfiable);
+            }
+            len.saturating_sub(suffix)..len
+        }
+        (start, "") => number(start)?..len,
+        (start, end) => {
+            let (start, end) = (number(start)?, number(end)?);
+            if end < start {
+                return Err(RangeNotSatisfiable);
+            }
+            start..end.saturating_add(1).min(len)
+        }
+    };
+
+    if range.start >= len {
+        return Err(RangeNotSatisfiable);
+    }
+    Ok(Some(range))
+}
+
+/// Vytvorí odpoveď so súborom na stiahnutie, ktorá rešpektuje hlavičku `Range`.
+///
+/// Bez `Range` (alebo ak `If-Range` nezodpovedá aktuálnemu `ETag`) sa vráti
+/// celé telo s `200 OK`, inak `206 Partial Content` s `Content-Range`.
+/// Neplatný rozsah vráti `416` s `Content-Range: bytes */dĺžka`.
+///
+/// # Arguments
+/// * `request_headers` – hlavičky požiadavky
+/// * `headers` – hlavičky odpovede (typ obsahu, názov súboru)
+/// * `body` – celé telo exportu
+///
+/// # Returns
+/// Odpoveď s `Accept-Ranges: bytes` a `ETag`
+pub fn ranged_response(request_headers: &HeaderMap, mut headers: HeaderMap, body: Vec<u8>) -> Response {
+    let len = body.len() as u64;
+    let etag = etag(&body);
+    headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        headers.insert(header::ETAG, value);
+    }
+
+    let Some(range) = request_headers.get(header::RANGE) else {
+        return (headers, body).into_response();
+    };
+    // pri zmenenom exporte sa pokračovanie sťahovania nesmie zlepiť so starým
+    if let Some(if_range) = request_headers.get(header::IF_RANGE) {
+        if if_range.to_str().ok().map(str::trim) != Some(etag.as_str()) {
+            return (headers, body).into_response();
+        }
+    }
+
+    let range = range.to_str().map_err(|_| RangeNotSatisfiable).and_then(|v| parse_range(v, len));
+    match range {
+        Ok(None) => (headers, body).into_response(),
+        Ok(Some(range)) => {
+            let content_range = format!("bytes {}-{}/{len}", range.start, range.end - 1);
+            if let Ok(value) = HeaderValue::from_str(&content_range) {
+                headers.insert(header::CONTENT_RANGE, value);
+            }
+            let part = body[range.start as usize..range.end as usize].to_vec();
+            (StatusCode::PARTIAL_CONTENT, headers, part).into_response()
+        }
+        Err(RangeNotSatisfiable) => {
+            headers.remove(header::CONTENT_TYPE);
+            headers.remove(header::CONTENT_DISPOSITION);
+            if let Ok(value) = HeaderValue::from_str(&format!("bytes */{len}")) {
+                headers.insert(header::CONTENT_RANGE, value);
+            }
+            (StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response()
+        }
+    }
+}
+
+/// Vypočíta `ETag` z obsahu tela (rovnaký export má rovnaký tag).
+pub fn etag(body: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    format!("\"{:016x}-{:x}\"", hasher.finish(), body.len())
+}
@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::db::StoreDB;
+
+/// Chybová správa pre odmietnuté zmeny.
+pub const MESSAGE: &str = "server je v režime len na čítanie (read-only), zmeny nie sú povolené";
+
+/// `POST` cesty, ktoré len čítajú (vyhľadávanie, export, porovnanie importu) a prepínač
+/// režimu. GraphQL mutácie zastaví až databáza otvorená s príznakom read-only.
+pub const READ_PATHS: &[&str] = &[
+    "/admin/read-only",
+    "/employees/search",
+    "/employees/batch",
+    "/products/search",
+    "/products/export",
+    "/export",
+    "/import/diff",
+    "/import/validate-barcodes",
+    "/import/price-list/preview",
+    "/graphql",
+];
+
+/// Middleware, ktorý v režime len na čítanie odmietne požiadavky meniace dáta.
+///
+/// Za zmenu sa považuje každá metóda okrem `GET`, `HEAD` a `OPTIONS` mimo `READ_PATHS`.
+///
+/// # Arguments
+/// * `db` – databáza (nesie stav režimu)
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď handlera alebo `503 Service Unavailable` s vysvetlením
+pub async fn guard(State(db): State<StoreDB>, req: Request, next: Next) -> Response {
+    let reads = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS)
+        || READ_PATHS.contains(&req.uri().path());
+    if reads || !db.is_read_only() {
+        return next.run(req).await;
+    }
+    (StatusCode::SERVICE_UNAVAILABLE, MESSAGE).into_response()
+}
@@ -0,0 +1,92 @@
+//! Politika uchovávania osobných údajov (GDPR).
+//!
+//! Osobné údaje bývalých zamestnancov a staré záznamy dochádzky sa po uplynutí
+//! nastaveného počtu rokov anonymizujú alebo vymažú (`StoreDB::purge_personal_data`).
+//! Náhľad toho, čo by ďalší beh odstránil, je na `GET /admin/retention`.
+
+use std::{fmt, str::FromStr};
+
+use chrono::{Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Čo sa stane so zamestnancom po uplynutí doby uchovávania.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    /// Osobné údaje sa prepíšu, záznam (a štatistiky naň naviazané) zostane
+    #[default]
+    Anonymize,
+    /// Záznam sa vymaže spolu s dochádzkou; odkazy z produktov a objednávok sa vynulujú
+    Delete,
+}
+
+impl RetentionAction {
+    /// Názov akcie v konfigurácii a audit logu.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RetentionAction::Anonymize => "anonymize",
+            RetentionAction::Delete => "delete",
+        }
+    }
+}
+
+impl fmt::Display for RetentionAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RetentionAction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "anonymize" => Ok(RetentionAction::Anonymize),
+            "delete" => Ok(RetentionAction::Delete),
+            other => Err(format!("neznáma akcia {other:?} (anonymize, delete)")),
+        }
+    }
+}
+
+/// Doby uchovávania jednotlivých druhov osobných údajov v rokoch.
+///
+/// Nenastavená doba znamená, že sa údaje daného druhu nemažú; prázdna politika
+/// nerobí nič.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionPolicy {
+    /// Roky od odchodu (deaktivácie) zamestnanca, po ktorých sa jeho údaje odstránia
+    pub employee_years: Option<u32>,
+    /// Roky, po ktorých sa vymažú záznamy dochádzky
+    pub attendance_years: Option<u32>,
+    /// Anonymizácia alebo vymazanie zamestnanca
+    pub action: RetentionAction,
+}
+
+impl RetentionPolicy {
+    /// Zistí, či politika nič neodstraňuje.
+    pub fn is_empty(&self) -> bool {
+        self.employee_years.is_none() && self.attendance_years.is_none()
+    }
+
+    /// Deň, pred ktorým odchod zamestnanca znamená odstránenie jeho údajov.
+    ///
+    /// # Arguments
+    /// * `today` – dnešný deň
+    pub fn employee_cutoff(&self, today: NaiveDate) -> Option<NaiveDate> {
+        self.employee_years.map(|years| cutoff(today, years))
+    }
+
+    /// Deň, pred ktorým sa záznamy dochádzky vymažú.
+    ///
+    /// # Arguments
+    /// * `today` – dnešný deň
+    pub fn attendance_cutoff(&self, today: NaiveDate) -> Option<NaiveDate> {
+        self.attendance_years.map(|years| cutoff(today, years))
+    }
+}
+
+/// Vráti deň pred `years` rokmi (29. február sa posunie na 28.).
+fn cutoff(today: NaiveDate, years: u32) -> NaiveDate {
+    today.checked_sub_months(Months::new(years.saturating_mul(12))).unwrap_or(NaiveDate::MIN)
+}
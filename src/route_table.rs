@@ -0,0 +1,129 @@
+//! Zoznam trás HTTP API.
+//!
+//! axum nevie vypísať zaregistrované trasy, preto sa router skladá cez
+//! `RouteTable`, ktorá si pri každom `route` zapamätá cestu a metódy. Z tej istej
+//! tabuľky vzniká router aj zoznam pre `GET /routes`, takže sa nemôžu rozísť.
+//!
+//! Funkcie `get`, `post`, `put` a `delete` nahrádzajú rovnomenné funkcie
+//! z `axum::routing` a zápis trás sa tak nemení:
+//!
+//! ```ignore
+//! RouteTable::new().route("/employees", get(list_employees).post(add_employee))
+//! ```
+
+use std::sync::Arc;
+
+use axum::{
+    handler::Handler,
+    routing::{self, MethodRouter},
+    Router,
+};
+
+use crate::structs::RouteInfo;
+
+/// Obsluha jednej cesty spolu s názvami metód, ktoré obsluhuje.
+pub struct Endpoint<S> {
+    router:  MethodRouter<S>,
+    methods: Vec<&'static str>,
+}
+
+macro_rules! endpoint_methods {
+    ($($name:ident => $method:literal),* $(,)?) => {
+        $(
+            #[doc = concat!("Obsluha metódy `", $method, "` (ako `axum::routing::", stringify!($name), "`).")]
+            pub fn $name<H, T, S>(handler: H) -> Endpoint<S>
+            where
+                H: Handler<T, S>,
+                T: 'static,
+                S: Clone + Send + Sync + 'static,
+            {
+                Endpoint { router: routing::$name(handler), methods: vec![$method] }
+            }
+        )*
+
+        impl<S: Clone + Send + Sync + 'static> Endpoint<S> {
+            $(
+                #[doc = concat!("Pridá obsluhu metódy `", $method, "`.")]
+                pub fn $name<H, T>(mut self, handler: H) -> Self
+                where
+                    H: Handler<T, S>,
+                    T: 'static,
+                {
+                    self.router = self.router.$name(handler);
+                    self.methods.push($method);
+                    self
+                }
+            )*
+        }
+    };
+}
+
+endpoint_methods!(get => "GET", post => "POST", put => "PUT", delete => "DELETE");
+
+/// Router spolu so zoznamom zaregistrovaných trás.
+pub struct RouteTable<S = ()> {
+    router: Router<S>,
+    routes: Vec<RouteInfo>,
+}
+
+impl<S: Clone + Send + Sync + 'static> Default for RouteTable<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Clone + Send + Sync + 'static> RouteTable<S> {
+    /// Vytvorí prázdnu tabuľku trás.
+    pub fn new() -> Self {
+        Self { router: Router::new(), routes: Vec::new() }
+    }
+
+    /// Zaregistruje cestu (ako `Router::route`) a zapíše ju do zoznamu.
+    ///
+    /// # Arguments
+    /// * `path` – cesta v zápise axum (`/products/{id}`)
+    /// * `endpoint` – obsluha metód cesty
+    pub fn route(mut self, path: &str, endpoint: Endpoint<S>) -> Self {
+        self.router = self.router.route(path, endpoint.router);
+        self.routes.push(RouteInfo {
+            path:    path.to_string(),
+            methods: endpoint.methods.into_iter().map(String::from).collect(),
+        });
+        self
+    }
+
+    /// Pripojí trasy inej tabuľky (ako `Router::merge`).
+    pub fn merge(mut self, other: RouteTable<S>) -> Self {
+        self.router = self.router.merge(other.router);
+        self.routes.extend(other.routes);
+        self
+    }
+
+    /// Dodá trasám stav (ako `Router::with_state`); zoznam trás sa zachová.
+    pub fn with_state<S2: Clone + Send + Sync + 'static>(self, state: S) -> RouteTable<S2> {
+        RouteTable { router: self.router.with_state(state), routes: self.routes }
+    }
+
+    /// Zaregistrované trasy zoradené podľa cesty.
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        let mut routes = self.routes.clone();
+        routes.sort_by(|a, b| a.path.cmp(&b.path));
+        routes
+    }
+
+    /// Vráti router bez zoznamu trás.
+    pub fn into_router(self) -> Router<S> {
+        self.router
+    }
+
+    /// Vráti router doplnený o `GET <path>`, ktorý vypíše zoznam trás (vrátane seba).
+    ///
+    /// # Arguments
+    /// * `path` – cesta zoznamu (`/routes`)
+    pub fn into_router_with_listing(mut self, path: &str) -> Router<S> {
+        self.routes.push(RouteInfo { path: path.to_string(), methods: vec!["GET".to_string()] });
+        let routes = Arc::new(self.routes());
+        self.router
+            .route(path, routing::get(move || async move { axum::Json(routes.as_ref().clone()) }))
+    }
+}
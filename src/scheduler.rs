@@ -0,0 +1,167 @@
+//! Periodické úlohy na pozadí servera.
+
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::Local;
+use tokio::task::JoinHandle;
+use tokio::time::{self, MissedTickBehavior};
+
+use crate::db::StoreDB;
+use crate::retention::RetentionPolicy;
+
+/// Spúšťa úlohu opakovane s daným intervalom, prvýkrát po uplynutí intervalu.
+///
+/// Chyba jedného behu sa vypíše a plánovač pokračuje ďalším behom. Ak beh
+/// trvá dlhšie ako interval, zmeškané behy sa nedobiehajú.
+///
+/// # Arguments
+/// * `name` – názov úlohy do chybových hlásení
+/// * `interval` – interval medzi behmi; nulový interval úlohu vypne
+/// * `task` – jeden beh úlohy
+///
+/// # Returns
+/// Handle úlohy (`None`, ak je vypnutá)
+pub fn spawn_periodic<F, Fut>(name: &'static str, interval: Duration, mut task: F) -> Option<JoinHandle<()>>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send,
+{
+    if interval.is_zero() {
+        return None;
+    }
+    Some(tokio::spawn(async move {
+        let mut ticks = time::interval_at(time::Instant::now() + interval, interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            if let Err(e) = task().await {
+                eprintln!("Úloha {name} zlyhala: {e:#}");
+            }
+        }
+    }))
+}
+
+/// Spustí ukladanie snímok štatistík (`stats_snapshot_interval`).
+///
+/// V režime len na čítanie sa snímky neukladajú.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `interval` – interval medzi snímkami
+///
+/// # Returns
+/// Handle úlohy (`None`, ak je vypnutá)
+pub fn spawn_stats_snapshots(db: StoreDB, interval: Duration) -> Option<JoinHandle<()>> {
+    spawn_periodic("stats_snapshot", interval, move || {
+        let db = db.clone();
+        async move {
+            if db.is_read_only() {
+                return Ok(());
+            }
+            db.snapshot_stats().await.map(|_| ())
+        }
+    })
+}
+
+/// Spustí monitor upozornení na nízky stav zásob (`alert_check_interval`).
+///
+/// V režime len na čítanie sa upozornenia nevyhodnocujú (ukladajú sa do databázy).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `interval` – interval medzi vyhodnoteniami
+/// * `threshold` – hranica nízkeho stavu zásob
+///
+/// # Returns
+/// Handle úlohy (`None`, ak je vypnutá)
+pub fn spawn_stock_alerts(db: StoreDB, interval: Duration, threshold: u32) -> Option<JoinHandle<()>> {
+    spawn_periodic("stock_alerts", interval, move || {
+        let db = db.clone();
+        async move {
+            if db.is_read_only() {
+                return Ok(());
+            }
+            db.evaluate_stock_alerts(threshold).await.map(|_| ())
+        }
+    })
+}
+
+/// Spustí uplatňovanie politiky uchovávania osobných údajov (`retention_interval`).
+///
+/// S prázdnou politikou sa úloha nespustí; v režime len na čítanie sa nič nemaže.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `interval` – interval medzi behmi
+/// * `policy` – doby uchovávania a akcia
+///
+/// # Returns
+/// Handle úlohy (`None`, ak je vypnutá)
+pub fn spawn_retention(db: StoreDB, interval: Duration, policy: RetentionPolicy) -> Option<JoinHandle<()>> {
+    if policy.is_empty() {
+        return None;
+    }
+    spawn_periodic("retention", interval, move || {
+        let db = db.clone();
+        async move {
+            if db.is_read_only() {
+                return Ok(());
+            }
+            let report = db.purge_personal_data(&policy, Local::now().date_naive(), false).await?;
+            if !report.employees.is_empty() || report.attendance_entries > 0 {
+                println!(
+                    "Politika uchovávania: {} zamestnancov ({}), {} záznamov dochádzky",
+                    report.employees.len(),
+                    policy.action,
+                    report.attendance_entries
+                );
+            }
+            Ok(())
+        }
+    })
+}
+
+/// Spustí rušenie skončených akcií produktov (`sale_expiry_interval`).
+///
+/// V režime len na čítanie sa akcie nerušia (výpisy ich aj tak nezapočítajú).
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `interval` – interval medzi behmi
+///
+/// # Returns
+/// Handle úlohy (`None`, ak je vypnutá)
+pub fn spawn_sale_expiry(db: StoreDB, interval: Duration) -> Option<JoinHandle<()>> {
+    spawn_periodic("sale_expiry", interval, move || {
+        let db = db.clone();
+        async move {
+            if db.is_read_only() {
+                return Ok(());
+            }
+            db.expire_sales(Local::now().date_naive()).await.map(|_| ())
+        }
+    })
+}
+
+/// Spustí uplatňovanie naplánovaných zmien predajných cien (`price_change_interval`).
+///
+/// V režime len na čítanie sa zmeny neuplatnia; počkajú na ďalší beh.
+///
+/// # Arguments
+/// * `db` – databáza
+/// * `interval` – interval medzi behmi
+///
+/// # Returns
+/// Handle úlohy (`None`, ak je vypnutá)
+pub fn spawn_price_changes(db: StoreDB, interval: Duration) -> Option<JoinHandle<()>> {
+    spawn_periodic("price_changes", interval, move || {
+        let db = db.clone();
+        async move {
+            if db.is_read_only() {
+                return Ok(());
+            }
+            db.apply_due_price_changes(Local::now().date_naive()).await.map(|_| ())
+        }
+    })
+}
@@ -1,11 +1,50 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use axum::Router;
 use tokio::net::TcpListener;
+use tokio::runtime::{Builder, Runtime};
+use tokio::sync::Notify;
+use crate::config::Config;
 use crate::db::StoreDB;
-use crate::api;
+use crate::grpc;
+use crate::api::{self, AppState};
+use crate::health::Readiness;
+use crate::maintenance::Maintenance;
+use crate::cache::QueryCache;
+use crate::auth::JwtVerifier;
+use crate::tx_session::TxSessions;
+use crate::stores::StoreRegistry;
+
+/// Vytvorí viacvláknový tokio runtime podľa konfigurácie.
+///
+/// Bez nastaveného `worker_threads` použije tokio počet jadier CPU.
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie
+///
+/// # Returns
+/// Runtime pre server
+///
+/// # Errors
+/// Ak sa runtime nepodarí vytvoriť
+pub fn build_runtime(config: &Config) -> std::io::Result<Runtime> {
+    let mut builder = Builder::new_multi_thread();
+    builder.enable_all().max_blocking_threads(config.max_blocking_threads);
+    if let Some(workers) = config.worker_threads {
+        builder.worker_threads(workers);
+    }
+    builder.build()
+}
 
 /// HTTP server aplikácie.
 #[derive(Clone)]
 pub struct Server {
     db: StoreDB,
+    config: Arc<Config>,
+    readiness: Readiness,
+    stores: BTreeMap<String, StoreDB>,
 }
 
 impl Server {
@@ -13,28 +52,156 @@ impl Server {
     ///
     /// # Arguments
     /// * `db` – databáza použitá serverom
+    /// * `config` – konfigurácia aplikácie
     ///
     /// # Returns
     /// Nová inštancia `Server`
-    pub fn new(db: StoreDB) -> Self {
-        Self { db }
+    pub fn new(db: StoreDB, config: Config) -> Self {
+        Self { db, config: Arc::new(config), readiness: Readiness::ready(), stores: BTreeMap::new() }
+    }
+
+    /// Nastaví zdieľaný stav pripravenosti (predvolene je server hneď pripravený).
+    ///
+    /// # Arguments
+    /// * `readiness` – stav, ktorý prepína štart aplikácie
+    ///
+    /// # Returns
+    /// Server so zadaným stavom pripravenosti
+    pub fn with_readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    /// Nastaví ďalšie obchody dostupné pod `/stores/{id}/...` (predvolene žiadne).
+    ///
+    /// # Arguments
+    /// * `stores` – databázy obchodov podľa ID (`stores::open_all`)
+    ///
+    /// # Returns
+    /// Server s ďalšími obchodmi
+    pub fn with_stores(mut self, stores: BTreeMap<String, StoreDB>) -> Self {
+        self.stores = stores;
+        self
     }
 
     /// Spustí HTTP server.
     ///
-    /// Server počúva na `0.0.0.0:8000`
+    /// Server počúva na adrese `listen_addr` z konfigurácie. Po dokončení
+    /// `shutdown` prestane prijímať nové spojenia a rozbehnuté požiadavky
+    /// majú `shutdown_timeout` sekúnd na dokončenie.
+    ///
+    /// # Arguments
+    /// * `shutdown` – future, ktorej dokončenie spustí vypínanie servera
     ///
     /// # Returns
     /// `Ok(())` ak sa server ukončí bez chyby
     ///
     /// # Errors
     /// Ak zlyhá vytvorenie socketu alebo spustenie servera
-    pub async fn run(self) -> anyhow::Result<()> {
-        let app = api::create_router(self.db);
-        let listener = TcpListener::bind("0.0.0.0:8000").await?;
+    pub async fn run(self, shutdown: impl Future<Output = ()> + Send + 'static) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(self.config.listen_addr.as_str()).await?;
 
-        println!("Databaza pripravena na: http://localhost:8000");
-        axum::serve(listener, app).await?;
+        println!("Databaza pripravena na: http://{}", listener.local_addr()?);
+        let timeout = Duration::from_secs(self.config.shutdown_timeout);
+        serve_with_shutdown(listener, self.router()?, shutdown, timeout).await
+    }
+
+    /// Spustí gRPC server na adrese `grpc_addr` z konfigurácie.
+    ///
+    /// # Returns
+    /// `Ok(())` ak sa server ukončí bez chyby
+    ///
+    /// # Errors
+    /// Ak zlyhá vytvorenie socketu alebo spustenie servera
+    pub async fn run_grpc(self) -> anyhow::Result<()> {
+        let listener = TcpListener::bind(self.config.grpc_addr.as_str()).await?;
+
+        println!("gRPC rozhranie pripravené na: {}", listener.local_addr()?);
+        grpc::serve(self.db, self.config, listener).await
+    }
+
+    /// Obsluhuje HTTP požiadavky na už otvorenom sockete.
+    ///
+    /// # Arguments
+    /// * `listener` – socket, na ktorom server počúva
+    ///
+    /// # Returns
+    /// `Ok(())` ak sa server ukončí bez chyby
+    ///
+    /// # Errors
+    /// Ak zlyhá spustenie servera
+    pub async fn serve(self, listener: TcpListener) -> anyhow::Result<()> {
+        axum::serve(listener, self.router()?).await?;
         Ok(())
     }
+
+    /// Vytvorí router aplikácie nad stavom servera.
+    ///
+    /// # Errors
+    /// Ak sa nepodarí načítať kľúč na overenie JWT
+    fn router(self) -> anyhow::Result<Router> {
+        let maintenance = Maintenance::new(self.config.maintenance_retry_after);
+        let jwt = JwtVerifier::from_config(&self.config)?;
+        // každý obchod má vlastnú cache a transakcie, údržba a prihlasovanie sú spoločné
+        let state = |db: StoreDB, stores: StoreRegistry| AppState {
+            cache: QueryCache::new(&self.config, db.generations()),
+            db,
+            config: self.config.clone(),
+            readiness: self.readiness.clone(),
+            maintenance: maintenance.clone(),
+            jwt: jwt.clone(),
+            tx: TxSessions::new(),
+            stores,
+        };
+        let stores = self.stores.iter().map(|(id, db)| {
+            (id.clone(), db.clone(), api::create_router(state(db.clone(), StoreRegistry::default())))
+        });
+        let stores = StoreRegistry::new(stores);
+        Ok(api::create_router(state(self.db.clone(), stores)))
+    }
+}
+
+/// Obsluhuje HTTP požiadavky, kým sa nedokončí `shutdown`, potom server vypne.
+///
+/// Po signáli sa prestanú prijímať nové spojenia a čaká sa na rozbehnuté
+/// požiadavky, najviac však `timeout`. Po jeho uplynutí sa server ukončí
+/// aj so zaseknutými požiadavkami a zaloguje sa varovanie.
+///
+/// # Arguments
+/// * `listener` – socket, na ktorom server počúva
+/// * `app` – router aplikácie
+/// * `shutdown` – future, ktorej dokončenie spustí vypínanie
+/// * `timeout` – najdlhší čas na dokončenie rozbehnutých požiadaviek
+///
+/// # Returns
+/// `Ok(())` po vypnutí servera (aj násilnom)
+///
+/// # Errors
+/// Ak zlyhá spustenie servera
+pub async fn serve_with_shutdown(
+    listener: TcpListener,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let triggered = Arc::new(Notify::new());
+    let signal = triggered.clone();
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+        shutdown.await;
+        signal.notify_one();
+    });
+
+    tokio::select! {
+        result = server => result?,
+        _ = async {
+            triggered.notified().await;
+            tokio::time::sleep(timeout).await;
+        } => {
+            eprintln!(
+                "Varovanie: požiadavky sa nedokončili do {} s, server sa ukončuje násilne",
+                timeout.as_secs_f64()
+            );
+        }
+    }
+    Ok(())
 }
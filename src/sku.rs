@@ -0,0 +1,108 @@
+/// Predvolená maska predpony SKU: tri písmená (napr. `DRK-0042`).
+pub const DEFAULT_PREFIX_PATTERN: &str = "AAA";
+
+/// Najmenší počet číslic poradového čísla SKU (kratšie číslo sa doplní nulami).
+pub const MIN_DIGITS: usize = 4;
+
+/// Predpona kategórie, z ktorej sa nedá odvodiť žiadne písmeno.
+const FALLBACK_PREFIX: &str = "SKU";
+
+/// Upraví zadané SKU do uloženého tvaru (bez okrajových medzier, veľkými písmenami).
+pub fn normalize(sku: &str) -> String {
+    sku.trim().to_uppercase()
+}
+
+/// Rozdelí SKU na predponu a poradové číslo.
+///
+/// # Arguments
+/// * `sku` – SKU v uloženom tvare
+///
+/// # Returns
+/// Predpona a číslo, alebo `None`, ak SKU nemá tvar `PREDPONA-ČÍSLO`
+pub fn split(sku: &str) -> Option<(&str, u64)> {
+    let (prefix, number) = sku.rsplit_once('-')?;
+    if prefix.is_empty() || number.len() < MIN_DIGITS || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((prefix, number.parse().ok()?))
+}
+
+/// Zostaví SKU z predpony a poradového čísla.
+pub fn format(prefix: &str, number: u64) -> String {
+    format!("{prefix}-{number:0MIN_DIGITS$}")
+}
+
+/// Skontroluje masku predpony SKU z konfigurácie.
+///
+/// V maske `A` znamená písmeno A–Z, `9` číslicu; ostatné znaky (veľké písmená
+/// a číslice) sa musia zhodovať presne.
+///
+/// # Errors
+/// Popis chyby pri prázdnej maske alebo nepovolenom znaku
+pub fn check_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.is_empty() {
+        return Err("maska nesmie byť prázdna".into());
+    }
+    match pattern.chars().find(|c| !c.is_ascii_uppercase() && !c.is_ascii_digit()) {
+        Some(c) => Err(format!("nepovolený znak {c:?} (povolené sú veľké písmená A–Z a číslice)")),
+        None => Ok(()),
+    }
+}
+
+/// Overí, že SKU má tvar `PREDPONA-ČÍSLO` a predpona zodpovedá maske.
+///
+/// # Arguments
+/// * `sku` – SKU v uloženom tvare (pozri `normalize`)
+/// * `pattern` – maska predpony (pozri `check_pattern`)
+///
+/// # Errors
+/// Popis chyby, ak SKU nezodpovedá formátu
+pub fn validate(sku: &str, pattern: &str) -> Result<(), String> {
+    let expected = || format!("SKU {sku:?} nemá tvar {}", format(pattern, 42));
+    let (prefix, _) = split(sku).ok_or_else(expected)?;
+    let matches = prefix.len() == pattern.len()
+        && prefix.chars().zip(pattern.chars()).all(|(c, p)| match p {
+            'A' => c.is_ascii_uppercase(),
+            '9' => c.is_ascii_digit(),
+            p => c == p,
+        });
+    if matches { Ok(()) } else { Err(expected()) }
+}
+
+/// Odvodí predponu SKU z názvu kategórie: prvé tri písmená bez diakritiky
+/// (`Nápoje` → `NAP`), kratší názov sa doplní znakom `X`.
+///
+/// # Arguments
+/// * `category` – kategória produktu
+///
+/// # Returns
+/// Trojpísmenová predpona
+pub fn category_prefix(category: &str) -> String {
+    let letters: String = category.chars().filter_map(ascii_letter).take(3).collect();
+    match letters.len() {
+        0 => FALLBACK_PREFIX.into(),
+        n => letters + &"X".repeat(3 - n),
+    }
+}
+
+/// Písmeno bez diakritiky veľkým písmenom (`None` pre iné znaky).
+fn ascii_letter(c: char) -> Option<char> {
+    let base = match c.to_lowercase().next()? {
+        'á' | 'ä' => 'a',
+        'č' => 'c',
+        'ď' => 'd',
+        'é' | 'ě' => 'e',
+        'í' => 'i',
+        'ĺ' | 'ľ' => 'l',
+        'ň' => 'n',
+        'ó' | 'ô' | 'ö' => 'o',
+        'ŕ' | 'ř' => 'r',
+        'š' => 's',
+        'ť' => 't',
+        'ú' | 'ů' | 'ü' => 'u',
+        'ý' => 'y',
+        'ž' => 'z',
+        c => c,
+    };
+    base.is_ascii_alphabetic().then(|| base.to_ascii_uppercase())
+}
@@ -0,0 +1,192 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Write},
+    sync::Mutex,
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Subscriber,
+};
+use tracing_subscriber::{
+    filter::filter_fn, layer::Context, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, Layer,
+};
+
+/// Cieľ udalostí, ktorými sqlx hlási vykonané príkazy.
+const SQLX_TARGET: &str = "sqlx::query";
+
+/// Pole spanu `StoreDB` metódy s názvom operácie.
+const OPERATION_FIELD: &str = "db.operation.name";
+
+/// Počty pomalých príkazov podľa metódy `StoreDB`.
+static SLOW_QUERIES: Mutex<BTreeMap<String, u64>> = Mutex::new(BTreeMap::new());
+
+/// Názov operácie uložený v rozšíreniach spanu.
+struct Operation(String);
+
+/// `tracing` vrstva, ktorá zaloguje pomalé SQL príkazy a spočíta ich podľa metódy.
+///
+/// Časy príkazov meria sqlx pri každom príkaze; príkaz dlhší ako prah nastavený
+/// v `StoreDB::set_slow_query_threshold` nahlási udalosťou s úrovňou WARN. Rýchle
+/// príkazy sa nelogujú vôbec, vrstva ich preto nespomalí.
+struct SlowQueryLayer;
+
+impl<S> Layer<S> for SlowQueryLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let mut visitor = OperationVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(operation), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(Operation(operation));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut statement = SlowStatement::default();
+        event.record(&mut statement);
+        if !statement.slow {
+            return;
+        }
+
+        let method = ctx
+            .event_scope(event)
+            .and_then(|scope| scope.into_iter().find_map(|span| span.extensions().get::<Operation>().map(|o| o.0.clone())))
+            .unwrap_or_else(|| "unknown".into());
+        *SLOW_QUERIES.lock().unwrap().entry(method.clone()).or_default() += 1;
+
+        let sql = if statement.sql.trim().is_empty() { &statement.summary } else { &statement.sql };
+        let sql = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+        eprintln!(
+            "Varovanie: pomalý SQL príkaz v {method} ({:.1} ms, parametre: {}, vrátené riadky: {}, zmenené riadky: {}): {sql}",
+            statement.elapsed_secs * 1000.0,
+            parameter_count(&sql),
+            statement.rows_returned,
+            statement.rows_affected,
+        );
+    }
+}
+
+/// Prečíta názov operácie z atribútov spanu.
+struct OperationVisitor(Option<String>);
+
+impl Visit for OperationVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == OPERATION_FIELD {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == OPERATION_FIELD {
+            self.0 = Some(format!("{value:?}").trim_matches('"').to_string());
+        }
+    }
+}
+
+/// Polia udalosti sqlx o pomalom príkaze.
+#[derive(Default)]
+struct SlowStatement {
+    slow: bool,
+    summary: String,
+    sql: String,
+    rows_returned: u64,
+    rows_affected: u64,
+    elapsed_secs: f64,
+}
+
+impl Visit for SlowStatement {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = value.to_string(),
+            "db.statement" => self.sql = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "rows_returned" => self.rows_returned = value,
+            "rows_affected" => self.rows_affected = value,
+            _ => {}
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = value;
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, _value: &dyn Debug) {
+        if field.name() == "slow_threshold" {
+            self.slow = true;
+        }
+    }
+}
+
+/// Vytvorí vrstvu pre pomalé SQL príkazy.
+///
+/// Vrstva vidí len spany `StoreDB` metód a udalosti sqlx s úrovňou WARN,
+/// ostatné udalosti sqlx (napr. bežné príkazy na úrovni DEBUG) zostanú vypnuté.
+///
+/// # Returns
+/// Vrstva pre `tracing_subscriber`
+pub fn layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    SlowQueryLayer.with_filter(filter_fn(|meta| {
+        if meta.is_span() {
+            meta.fields().field(OPERATION_FIELD).is_some()
+        } else {
+            meta.target() == SQLX_TARGET && *meta.level() == Level::WARN
+        }
+    }))
+}
+
+/// Nainštaluje globálny `tracing` subscriber len s vrstvou pre pomalé príkazy.
+///
+/// Ak už je subscriber nainštalovaný, nerobí nič.
+pub fn init() {
+    let _ = tracing_subscriber::registry().with(layer()).try_init();
+}
+
+/// Spočíta parametre (`?`) v SQL príkaze, otázniky v reťazcoch a identifikátoroch sa nerátajú.
+///
+/// # Arguments
+/// * `sql` – text SQL príkazu
+///
+/// # Returns
+/// Počet viazaných parametrov
+pub fn parameter_count(sql: &str) -> usize {
+    let mut quote = None;
+    let mut count = 0;
+    for c in sql.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if matches!(c, '\'' | '"' | '`') => quote = Some(c),
+            None if c == '?' => count += 1,
+            None => {}
+        }
+    }
+    count
+}
+
+/// Vráti počty pomalých príkazov podľa metódy `StoreDB`.
+pub fn counts() -> BTreeMap<String, u64> {
+    SLOW_QUERIES.lock().unwrap().clone()
+}
+
+/// Vypíše počty pomalých príkazov vo formáte Prometheus.
+pub fn render_metrics() -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# HELP store_db_slow_queries_total SQL statements slower than the configured threshold.");
+    let _ = writeln!(out, "# TYPE store_db_slow_queries_total counter");
+    for (method, count) in counts() {
+        let _ = writeln!(out, "store_db_slow_queries_total{{method=\"{method}\"}} {count}");
+    }
+    out
+}
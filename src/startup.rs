@@ -0,0 +1,75 @@
+//! Kroky pri štarte a vypínaní servera okolo databázy (synchronizácia s JSON súborom).
+
+use crate::config::Config;
+use crate::db::StoreDB;
+use crate::db_filler::DBFiller;
+use crate::health::Readiness;
+
+/// Pripraví dáta po otvorení databázy a označí server za pripravený.
+///
+/// Nová databáza sa pri zapnutom `json_sync` naplní z `json_path` a stav sa
+/// hneď uloží späť; pri vypnutom `json_sync` sa JSON súbor vôbec nečíta.
+/// Potom sa doplnia normalizované telefónne čísla. V režime len na čítanie
+/// (`read_only`) sa nič z toho nerobí.
+///
+/// # Arguments
+/// * `store_db` – otvorená databáza
+/// * `config` – konfigurácia aplikácie
+/// * `db_existed` – súbor databázy existoval už pred jej otvorením
+/// * `readiness` – stav pripravenosti (fáza a priebeh importu pre `/readyz`)
+pub async fn prepare(store_db: &StoreDB, config: &Config, db_existed: bool, readiness: &Readiness) {
+    if config.read_only {
+        println!("Režim len na čítanie: JSON sa nenačíta a dáta sa nedopĺňajú.");
+        readiness.mark_ready();
+        return;
+    }
+    if db_existed {
+        println!("Databáza už existuje.");
+    } else if !config.json_sync {
+        println!("Nová databáza, synchronizácia s JSONom je vypnutá.");
+    } else {
+        // Ak DB neexistuje, pokúsi sa ju naplniť z JSONu
+        println!("Databaza nenajdena, pokus o načitávanie z JSONu...");
+        readiness.set_phase("import");
+        let json_path = config.json_path.as_str();
+        match DBFiller::load_from_json_with_progress(store_db, json_path, |loaded| readiness.set_loaded(loaded)).await {
+            Ok(_) => {
+                println!("Načtenie úspešné.");
+                if let Err(e) = DBFiller::save_to_json(store_db, json_path).await {
+                    eprintln!("Nepodarilo sa uložiť databázu do JSONu: {}", e);
+                }
+            }
+            Err(e) => {
+                eprintln!("Nepodarilo sa načítať dáta z JSONu: {}. Začina sa z prázdnou databázou", e);
+            }
+        }
+    }
+
+    // Doplnenie normalizovaných telefónnych čísel (staršie databázy a import z JSONu)
+    readiness.set_phase("phone_normalization");
+    if let Err(e) = store_db.normalize_phone_numbers(&config.phone_country).await {
+        eprintln!("Nepodarilo sa normalizovať telefónne čísla: {}", e);
+    }
+
+    readiness.mark_ready();
+}
+
+/// Uloží databázu do `json_path` pri vypínaní (len pri zapnutom `json_sync`).
+///
+/// Predchádzajúci obsah súboru sa odloží ako záloha, ponechá sa ich `backup_keep`.
+/// Server spustený v režime len na čítanie JSON neprepisuje (napr. dátami zo zálohy).
+///
+/// # Arguments
+/// * `store_db` – databáza
+/// * `config` – konfigurácia aplikácie
+pub async fn shutdown(store_db: &StoreDB, config: &Config) {
+    if !config.json_sync || config.read_only {
+        return;
+    }
+    println!("Databáza sa uloží do JSONu...");
+    if let Err(e) = DBFiller::save_to_json_with_backup(store_db, &config.json_path, config.backup_keep).await {
+        eprintln!("Nepodarilo sa uložiť databázu do JSONu: {}", e);
+    } else {
+        println!("Databáza úspešne uložená.");
+    }
+}
@@ -0,0 +1,112 @@
+//! Viac obchodov v jednom serveri.
+//!
+//! Každý obchod zo sekcie `[stores]` konfigurácie má vlastnú databázu a je dostupný
+//! pod `/stores/{id}/...`. Požiadavka sa bez tejto predpony vykoná v routeri obchodu,
+//! ktorý má rovnaké trasy aj middleware (autorizácia, údržba, read-only, transakcie)
+//! ako hlavný obchod – len nad svojou databázou a cache.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{Context, Result};
+use axum::{
+    extract::{Request, State},
+    http::{StatusCode, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use tower::ServiceExt;
+
+use crate::config::Config;
+use crate::db::StoreDB;
+
+/// Predpona ciest ďalších obchodov.
+const STORES_PATH: &str = "/stores/";
+
+/// Ďalšie obchody servera podľa ID.
+#[derive(Clone, Default)]
+pub struct StoreRegistry {
+    stores: Arc<BTreeMap<String, Store>>,
+}
+
+/// Obchod s vlastnou databázou.
+struct Store {
+    db: StoreDB,
+    /// Router aplikácie so stavom nad `db`
+    router: Router,
+}
+
+impl StoreRegistry {
+    /// Vytvorí register z obchodov a ich routerov.
+    ///
+    /// # Arguments
+    /// * `stores` – ID obchodu, jeho databáza a router nad ňou
+    pub fn new(stores: impl IntoIterator<Item = (String, StoreDB, Router)>) -> Self {
+        let stores = stores.into_iter().map(|(id, db, router)| (id, Store { db, router })).collect();
+        Self { stores: Arc::new(stores) }
+    }
+
+    /// Vráti ID obchodov v abecednom poradí.
+    pub fn ids(&self) -> Vec<String> {
+        self.stores.keys().cloned().collect()
+    }
+
+    /// Vráti databázu obchodu.
+    ///
+    /// # Arguments
+    /// * `id` – ID obchodu
+    pub fn db(&self, id: &str) -> Option<StoreDB> {
+        self.stores.get(id).map(|store| store.db.clone())
+    }
+}
+
+/// Otvorí databázy všetkých obchodov z `stores` (s migráciami, pri `read_only` len na čítanie).
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie
+///
+/// # Returns
+/// Databázy obchodov podľa ID
+///
+/// # Errors
+/// Ak sa niektorú databázu nepodarí otvoriť
+pub async fn open_all(config: &Config) -> Result<BTreeMap<String, StoreDB>> {
+    let mut stores = BTreeMap::new();
+    for (id, path) in &config.stores {
+        let store_config = Config { db_path: path.clone(), ..config.clone() };
+        let db = StoreDB::new(&store_config).await.with_context(|| format!("obchod {id} ({path})"))?;
+        stores.insert(id.clone(), db);
+    }
+    Ok(stores)
+}
+
+/// Middleware, ktorý požiadavku na `/stores/{id}/...` vykoná v routeri daného obchodu.
+///
+/// Ostatné požiadavky pokračujú do hlavného obchodu.
+///
+/// # Arguments
+/// * `registry` – ďalšie obchody servera
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď obchodu alebo `404 Not Found` pre neznámy obchod
+pub async fn dispatch(State(registry): State<StoreRegistry>, mut req: Request, next: Next) -> Response {
+    let Some(rest) = req.uri().path().strip_prefix(STORES_PATH) else {
+        return next.run(req).await;
+    };
+    let (id, path) = rest.find('/').map_or((rest, "/"), |i| rest.split_at(i));
+    let Some(store) = registry.stores.get(id) else {
+        return (StatusCode::NOT_FOUND, format!("obchod {id} neexistuje")).into_response();
+    };
+
+    let path_and_query = match req.uri().query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    match path_and_query.parse::<Uri>() {
+        Ok(uri) => *req.uri_mut() = uri,
+        Err(_) => return StatusCode::BAD_REQUEST.into_response(),
+    }
+    store.router.clone().oneshot(req).await.into_response()
+}
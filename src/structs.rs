@@ -1,27 +1,347 @@
-use serde::{Serialize, Deserialize};
-use chrono::NaiveDate;
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Serialize, Deserialize, Deserializer};
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::auth::Role;
+use crate::config::TextLimits;
+use crate::error::StoreError;
+use crate::retention::RetentionAction;
+
+/// Príznak `status` tak, ako môže prísť v JSONe alebo v parametroch dotazu.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StatusValue {
+    Bool(bool),
+    Int(i64),
+    Text(String),
+}
+
+/// Deserializuje `status` zadaný ako `true`/`false`, `1`/`0` (tvar v databáze) alebo text.
+///
+/// Nenulové číslo je `true`; `null` a prázdny text znamenajú, že stav nie je zadaný.
+fn deserialize_status<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<bool>, D::Error> {
+    match Option::<StatusValue>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(StatusValue::Bool(v)) => Ok(Some(v)),
+        Some(StatusValue::Int(v)) => Ok(Some(v != 0)),
+        Some(StatusValue::Text(v)) => match v.trim().to_ascii_lowercase().as_str() {
+            "" => Ok(None),
+            "true" | "1" => Ok(Some(true)),
+            "false" | "0" => Ok(Some(false)),
+            other => Err(serde::de::Error::custom(format!("neplatný stav: {other}"))),
+        },
+    }
+}
+
+/// Číselné pole tak, ako môže prísť v JSONe, v CSV alebo v parametroch dotazu.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberValue {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Text(String),
+}
+
+/// Deserializuje čiarový kód zadaný ako číslo alebo ako text (`"8586000000001"`).
+///
+/// Kód mimo rozsahu `i64` je chyba s jasnou správou namiesto všeobecnej chyby
+/// serde; `null` a prázdny text znamenajú, že kód nie je zadaný.
+fn deserialize_bar_code<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    let out_of_range = |v: &dyn std::fmt::Display| -> D::Error {
+        serde::de::Error::custom(format!("čiarový kód {v} je mimo rozsahu (najviac {})", i64::MAX))
+    };
+    match Option::<NumberValue>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(NumberValue::Int(v)) => Ok(Some(v)),
+        Some(NumberValue::UInt(v)) => Err(out_of_range(&v)),
+        Some(NumberValue::Float(v)) if v.fract() != 0.0 || !v.is_finite() => {
+            Err(serde::de::Error::custom(format!("čiarový kód musí byť celé číslo, nie {v}")))
+        }
+        // i64::MAX sa v f64 zaokrúhli na 2^63, ktoré už je mimo rozsahu
+        Some(NumberValue::Float(v)) if v.abs() >= i64::MAX as f64 => Err(out_of_range(&v)),
+        Some(NumberValue::Float(v)) => Ok(Some(v as i64)),
+        Some(NumberValue::Text(v)) => {
+            let text = v.trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            text.parse().map(Some).map_err(|_| {
+                let digits = text.strip_prefix(['+', '-']).unwrap_or(text);
+                if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+                    out_of_range(&text)
+                } else {
+                    serde::de::Error::custom(format!("neplatný čiarový kód: {text:?}"))
+                }
+            })
+        }
+    }
+}
+
+/// Deserializuje množstvo zadané ako číslo alebo ako text (`"12"`, `"2,5"`).
+///
+/// Desatinná čiarka sa prijme rovnako ako bodka; `null` a prázdny text
+/// znamenajú, že množstvo nie je zadané.
+fn deserialize_quantity<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+    let value = match Option::<NumberValue>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(NumberValue::Int(v)) => v as f64,
+        Some(NumberValue::UInt(v)) => v as f64,
+        Some(NumberValue::Float(v)) => v,
+        Some(NumberValue::Text(v)) => {
+            let text = v.trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            text.replace(',', ".")
+                .parse()
+                .map_err(|_| serde::de::Error::custom(format!("neplatné množstvo: {text:?}")))?
+        }
+    };
+    if !value.is_finite() {
+        return Err(serde::de::Error::custom(format!("neplatné množstvo: {value}")));
+    }
+    Ok(Some(value))
+}
+
+/// Oreže biele znaky na okrajoch textového poľa.
+fn trim_field(value: &mut Option<String>) {
+    if let Some(text) = value {
+        let trimmed = text.trim();
+        if trimmed.len() != text.len() {
+            *text = trimmed.to_string();
+        }
+    }
+}
+
+/// Overí, že textové pole nemá viac ako `limit` znakov.
+fn check_length(field: &'static str, value: &Option<String>, limit: usize) -> Result<(), StoreError> {
+    match value {
+        Some(text) if text.chars().count() > limit => Err(StoreError::TooLong { field, limit }),
+        _ => Ok(()),
+    }
+}
 
 /// Reprezentuje produkt v obchode.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Product {
     pub id:           Option<u32>,
     pub name:         Option<String>,
     pub category:     Option<String>,
-    pub quantity:     Option<u32>,
+    #[serde(default, deserialize_with = "deserialize_quantity")]
+    pub quantity:     Option<f64>,
+    pub unit:         Option<Unit>,
+    pub pack_size:    Option<u32>,
+    pub is_bundle:    Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_status")]
     pub status:       Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_bar_code")]
     pub bar_code:     Option<i64>,
+    /// Interné skladové číslo (napr. `DRK-0042`); pri pridaní bez hodnoty ho pridelí server
+    #[serde(default)]
+    pub sku:          Option<String>,
+    /// Hranica nízkeho stavu zásob produktu (`None` = platí `low_stock_threshold`)
+    pub min_stock:    Option<f64>,
+    /// Najvyšší žiadaný stav zásob (`None` = bez hranice, pozri `/products/overstocked`)
+    pub max_stock:    Option<f64>,
+    /// Dodacia lehota dodávateľa v dňoch (`None` = `DEFAULT_LEAD_TIME_DAYS`)
+    pub lead_time_days: Option<u32>,
     pub cost_price:   Option<f64>,
     pub sell_price:   Option<f64>,
+    /// Akciová cena (nastavuje `POST /products/{id}/sale`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sale_price:   Option<f64>,
+    /// Posledný deň akcie (vrátane)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sale_ends:    Option<NaiveDate>,
+    /// Aktuálna predajná cena: počas akcie akciová, inak `sell_price` (počíta server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_price: Option<f64>,
     pub description:  Option<String>,
     pub brand:        Option<String>,
     pub supplier:     Option<String>,
     pub employee_id:  Option<u32>,
     pub date_added:   Option<NaiveDate>,
     pub date_remove:  Option<NaiveDate>,
+    /// Množstvo v platných rezerváciách (počíta server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserved_quantity: Option<f64>,
+    /// Množstvo, ktoré sa dá predať: stav zásob mínus rezervácie (počíta server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_quantity: Option<f64>,
+    /// Len vo filtri: najmenšie množstvo na sklade (vrátane)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_min: Option<f64>,
+    /// Len vo filtri: najväčšie množstvo na sklade (vrátane)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity_max: Option<f64>,
+    /// Čas poslednej zmeny záznamu (nastavuje server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<NaiveDateTime>,
+    /// Len vo filtri: záznamy zmenené po tomto čase, zoradené podľa `updated_at` a ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_after: Option<NaiveDateTime>,
+    /// Len vo filtri s `updated_after`: pri rovnakom `updated_at` len záznamy s vyšším ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<u32>,
+    /// Len vo filtri: `false` vynechá vyradené produkty (`date_remove` pred dneškom),
+    /// `None` a `true` vrátia všetky
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_removed: Option<bool>,
 }
 
-/// Reprezentuje zamestnanca obchodu.
+/// Merná jednotka, v ktorej sa vedie stav zásob produktu.
+///
+/// Množstvo je v jednotke produktu; desatinné množstvá sú povolené len pre
+/// hmotnosť a objem (`kg`, `g`, `l`, `ml`), kusy a balenia musia byť celé.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Unit {
+    #[default]
+    Piece,
+    Kg,
+    G,
+    L,
+    Ml,
+    Pack,
+}
+
+impl Unit {
+    /// Textová hodnota uložená v databáze.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Unit::Piece => "piece",
+            Unit::Kg => "kg",
+            Unit::G => "g",
+            Unit::L => "l",
+            Unit::Ml => "ml",
+            Unit::Pack => "pack",
+        }
+    }
+
+    /// Prevedie textovú hodnotu z databázy na jednotku.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "piece" => Some(Unit::Piece),
+            "kg" => Some(Unit::Kg),
+            "g" => Some(Unit::G),
+            "l" => Some(Unit::L),
+            "ml" => Some(Unit::Ml),
+            "pack" => Some(Unit::Pack),
+            _ => None,
+        }
+    }
+
+    /// Či jednotka pripúšťa desatinné množstvá (hmotnosť a objem).
+    pub fn is_fractional(&self) -> bool {
+        matches!(self, Unit::Kg | Unit::G | Unit::L | Unit::Ml)
+    }
+
+    /// Overí, či je množstvo v tejto jednotke prípustné.
+    ///
+    /// # Arguments
+    /// * `quantity` – množstvo
+    ///
+    /// # Returns
+    /// `true` ak je množstvo konečné, nezáporné a pri kusových jednotkách celé
+    pub fn accepts(&self, quantity: f64) -> bool {
+        quantity.is_finite() && quantity >= 0.0 && (self.is_fractional() || quantity.fract() == 0.0)
+    }
+
+    /// Prepočíta množstvo z tejto jednotky do jednotky `to`.
+    ///
+    /// Prepočet je možný len v rámci rovnakej veličiny: `kg ↔ g`, `l ↔ ml`
+    /// a `pack ↔ piece` (podľa `pack_size`, počtu kusov v balení).
+    ///
+    /// # Arguments
+    /// * `quantity` – množstvo v tejto jednotke
+    /// * `to` – cieľová jednotka
+    /// * `pack_size` – počet kusov v balení (pre `pack ↔ piece`)
+    ///
+    /// # Returns
+    /// Prepočítané množstvo, alebo `None` ak jednotky nie sú zameniteľné
+    pub fn convert(&self, quantity: f64, to: Unit, pack_size: Option<u32>) -> Option<f64> {
+        use Unit::*;
+        let factor = match (self, to) {
+            (a, b) if *a == b => 1.0,
+            (Kg, G) | (L, Ml) => 1000.0,
+            (G, Kg) | (Ml, L) => 0.001,
+            (Pack, Piece) => f64::from(pack_size.filter(|p| *p > 0)?),
+            (Piece, Pack) => 1.0 / f64::from(pack_size.filter(|p| *p > 0)?),
+            _ => return None,
+        };
+        // zaokrúhlenie odstráni šum z prepočtu (napr. 0.25 namiesto 0.25000000000000006)
+        Some((quantity * factor * 1e6).round() / 1e6)
+    }
+}
+
+/// Odvodený stav zásob produktu (pre odznaky v UI).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum StockStatus {
+    InStock,
+    LowStock,
+    OutOfStock,
+    Discontinued,
+}
+
+/// Detail produktu rozšírený o odvodené údaje.
+#[derive(Debug, Serialize, Clone)]
+pub struct ProductDetail {
+    #[serde(flatten)]
+    pub product:      Product,
+    pub stock_status: StockStatus,
+    /// Obrázky produktu zoradené podľa `sort_order`
+    pub images:       Vec<ProductImage>,
+}
+
+/// Obrázok produktu.
+///
+/// Produkt s obrázkami má vždy práve jeden hlavný (`is_primary`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductImage {
+    /// ID obrázka (dopĺňa server)
+    pub id:           Option<u32>,
+    /// Adresa obrázka (`http://`, `https://` alebo cesta od koreňa `/`)
+    pub url:          String,
+    #[serde(default)]
+    pub is_primary:   bool,
+    /// Poradie pri zobrazení (bez hodnoty = za posledný obrázok)
+    pub sort_order:   Option<u32>,
+}
+
+/// Parametre stránkovania zoznamov (`?offset=..&limit=..`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct Pagination {
+    pub offset:       Option<u32>,
+    pub limit:        Option<u32>,
+}
+
+/// Zoznam v obálke (tvar `envelope`, pozri `list_shape`).
 #[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListEnvelope<T> {
+    pub items:        Vec<T>,
+    /// Počet všetkých záznamov vyhovujúcich filtru (ako `X-Total-Count`)
+    pub total:        u64,
+}
+
+/// Vyhľadávanie aj vyradených produktov (`?include_removed=true`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct RemovedProducts {
+    pub include_removed: Option<bool>,
+}
+
+/// Vyhľadávanie produktov aj v prekladoch (`?translations=true`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct TranslationSearch {
+    pub translations: Option<bool>,
+}
+
+/// Reprezentuje zamestnanca obchodu.
+///
+/// `phone_number` je telefón tak, ako bol zadaný, `phone_e164` jeho normalizovaný
+/// tvar (dopĺňa ho server, podľa neho sa vyhľadáva).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Employee {
     pub id:           Option<u32>,
     pub name:         Option<String>,
@@ -31,10 +351,38 @@ pub struct Employee {
     pub shift:        Option<String>,
     pub salary:       Option<f64>,
     pub phone_number: Option<String>,
+    pub phone_e164:   Option<String>,
     pub email:        Option<String>,
+    #[serde(default, deserialize_with = "deserialize_status")]
     pub status:       Option<bool>,
     pub note:         Option<String>,
     pub hire_date:    Option<NaiveDate>,
+    /// ID nadriadeného; pri úprave `0` nadriadeného odstráni
+    #[serde(default)]
+    pub manager_id:   Option<u32>,
+    /// Dátum vyradenia (`DELETE /employees/{id}?soft=true`); vyradený zamestnanec
+    /// zostáva v databáze, kým ho `POST /employees/{id}/restore` neobnoví
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_remove:  Option<NaiveDate>,
+    /// Len vo filtri: najnižšia mzda (vrátane); zamestnanci bez mzdy sa nezhodujú
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salary_min: Option<f64>,
+    /// Len vo filtri: najvyššia mzda (vrátane); zamestnanci bez mzdy sa nezhodujú
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salary_max: Option<f64>,
+    /// Čas poslednej zmeny záznamu (nastavuje server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<NaiveDateTime>,
+    /// Len vo filtri: záznamy zmenené po tomto čase, zoradené podľa `updated_at` a ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_after: Option<NaiveDateTime>,
+    /// Len vo filtri s `updated_after`: pri rovnakom `updated_at` len záznamy s vyšším ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<u32>,
+    /// Len vo filtri: `false` vynechá vyradených zamestnancov (s `date_remove`),
+    /// `None` a `true` vrátia všetkých
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_removed: Option<bool>,
 }
 
 impl Employee {
@@ -49,6 +397,7 @@ impl Employee {
     /// * `shift_p` – pracovná zmena
     /// * `salart_p` – mzda
     /// * `phoen_number_p` – telefón
+    /// * `phone_e164_p` – telefón v tvare E.164
     /// * `email_p` – email
     /// * `status_p` – aktívny / neaktívny
     /// * `note_p` – poznámka
@@ -56,6 +405,7 @@ impl Employee {
     ///
     /// # Returns
     /// Nový `Employee`
+    #[allow(dead_code, clippy::too_many_arguments)]
     pub fn new(
         id_p:           Option<u32>,
         name_p:         Option<String>,
@@ -65,6 +415,7 @@ impl Employee {
         shift_p:        Option<String>,
         salart_p:       Option<f64>,
         phoen_number_p: Option<String>,
+        phone_e164_p:   Option<String>,
         email_p:        Option<String>,
         status_p:       Option<bool>,
         note_p:         Option<String>,
@@ -79,10 +430,19 @@ impl Employee {
             shift:          shift_p,
             salary:         salart_p,
             phone_number:   phoen_number_p,
+            phone_e164:     phone_e164_p,
             email:          email_p,
             status:         status_p,
             note:           note_p,
             hire_date:      hire_date_p,
+            manager_id:     None,
+            date_remove:    None,
+            salary_min:     None,
+            salary_max:     None,
+            updated_at:     None,
+            updated_after:  None,
+            after_id:       None,
+            include_removed: None,
         }
     }
 
@@ -102,14 +462,59 @@ impl Employee {
             shift:          None,
             salary:         None,
             phone_number:   None,
+            phone_e164:     None,
             email:          None,
             status:         None,
             note:           None,
             hire_date:      None,
+            manager_id:     None,
+            date_remove:    None,
+            salary_min:     None,
+            salary_max:     None,
+            updated_at:     None,
+            updated_after:  None,
+            after_id:       None,
+            include_removed: None,
+        }
+    }
+
+    /// Oreže biele znaky na okrajoch textových polí (pred zápisom).
+    pub fn trim_text(&mut self) {
+        let fields = [
+            &mut self.name,
+            &mut self.surname,
+            &mut self.position,
+            &mut self.department,
+            &mut self.shift,
+            &mut self.phone_number,
+            &mut self.email,
+            &mut self.note,
+        ];
+        for field in fields {
+            trim_field(field);
         }
     }
 
+    /// Overí dĺžku textových polí.
+    ///
+    /// # Arguments
+    /// * `limits` – najväčšie dĺžky polí
+    ///
+    /// # Errors
+    /// `StoreError::TooLong` s prvým príliš dlhým poľom
+    pub fn validate(&self, limits: &TextLimits) -> Result<(), StoreError> {
+        check_length("name", &self.name, limits.text)?;
+        check_length("surname", &self.surname, limits.text)?;
+        check_length("position", &self.position, limits.text)?;
+        check_length("department", &self.department, limits.text)?;
+        check_length("shift", &self.shift, limits.text)?;
+        check_length("phone_number", &self.phone_number, limits.text)?;
+        check_length("email", &self.email, limits.text)?;
+        check_length("note", &self.note, limits.long_text)
+    }
+
     /// Vypíše informácie o zamestnancovi na konzolu.
+    #[allow(dead_code)]
     fn print_employee(&self) {
         println!("ID: {}", self.id.map(|v| v.to_string()).unwrap_or("None".into()));
         println!("Name: {}", self.name.as_deref().unwrap_or("None"));
@@ -146,13 +551,21 @@ impl Product {
     ///
     /// # Returns
     /// Nový `Product`
+    #[allow(dead_code, clippy::too_many_arguments)]
     pub fn new(
         id_p:           Option<u32>,
         name_p:         Option<String>,
         category_p:     Option<String>,
-        quantity_p:     Option<u32>,
+        quantity_p:     Option<f64>,
+        unit_p:         Option<Unit>,
+        pack_size_p:    Option<u32>,
+        is_bundle_p:    Option<bool>,
         status_p:       Option<bool>,
         bar_code_p:     Option<i64>,
+        sku_p:          Option<String>,
+        min_stock_p:    Option<f64>,
+        max_stock_p:    Option<f64>,
+        lead_time_days_p: Option<u32>,
         cost_price_p:   Option<f64>,
         sell_price_p:   Option<f64>,
         description_p:  Option<String>,
@@ -167,16 +580,34 @@ impl Product {
             name:           name_p,
             category:       category_p,
             quantity:       quantity_p,
+            unit:           unit_p,
+            pack_size:      pack_size_p,
+            is_bundle:      is_bundle_p,
             status:         status_p,
             bar_code:       bar_code_p,
+            sku:            sku_p,
+            min_stock:      min_stock_p,
+            max_stock:      max_stock_p,
+            lead_time_days: lead_time_days_p,
             cost_price:     cost_price_p,
             sell_price:     sell_price_p,
+            sale_price:     None,
+            sale_ends:      None,
+            effective_price: None,
             description:    description_p,
             brand:          brand_p,
             supplier:       supplier_p,
             employee_id:    employee_id_p,
             date_added:     date_added_p,
             date_remove:    date_remove_p,
+            reserved_quantity: None,
+            available_quantity: None,
+            quantity_min:   None,
+            quantity_max:   None,
+            updated_at:     None,
+            updated_after:  None,
+            after_id:       None,
+            include_removed: None,
         }
     }
 
@@ -190,25 +621,144 @@ impl Product {
             name:           None,
             category:       None,
             quantity:       None,
+            unit:           None,
+            pack_size:      None,
+            is_bundle:      None,
             status:         None,
             bar_code:       None,
+            sku:            None,
+            min_stock:      None,
+            max_stock:      None,
+            lead_time_days: None,
             cost_price:     None,
             sell_price:     None,
+            sale_price:     None,
+            sale_ends:      None,
+            effective_price: None,
             description:    None,
             brand:          None,
             supplier:       None,
             employee_id:    None,
             date_added:     None,
             date_remove:    None,
+            reserved_quantity: None,
+            available_quantity: None,
+            quantity_min:   None,
+            quantity_max:   None,
+            updated_at:     None,
+            updated_after:  None,
+            after_id:       None,
+            include_removed: None,
+        }
+    }
+
+    /// Zistí, či má produkt v daný deň platnú akciu (`sale_ends` je posledný deň akcie).
+    pub fn on_sale(&self, today: NaiveDate) -> bool {
+        self.sale_price.is_some() && self.sale_ends.is_some_and(|ends| today <= ends)
+    }
+
+    /// Predajná cena v daný deň: počas akcie akciová cena, inak `sell_price`.
+    pub fn price_on(&self, today: NaiveDate) -> Option<f64> {
+        if self.on_sale(today) { self.sale_price } else { self.sell_price }
+    }
+
+    /// Oreže biele znaky na okrajoch textových polí (pred zápisom).
+    pub fn trim_text(&mut self) {
+        for field in [&mut self.name, &mut self.category, &mut self.description, &mut self.brand, &mut self.supplier] {
+            trim_field(field);
+        }
+    }
+
+    /// Overí dĺžku textových polí.
+    ///
+    /// # Arguments
+    /// * `limits` – najväčšie dĺžky polí
+    ///
+    /// # Errors
+    /// `StoreError::TooLong` s prvým príliš dlhým poľom
+    pub fn validate(&self, limits: &TextLimits) -> Result<(), StoreError> {
+        check_length("name", &self.name, limits.text)?;
+        check_length("category", &self.category, limits.text)?;
+        check_length("brand", &self.brand, limits.text)?;
+        check_length("supplier", &self.supplier, limits.text)?;
+        check_length("description", &self.description, limits.long_text)
+    }
+
+    /// Marža produktu: rozdiel predajnej a nákupnej ceny.
+    ///
+    /// # Returns
+    /// Marža alebo `None`, ak niektorá cena chýba
+    pub fn margin(&self) -> Option<f64> {
+        Some(self.sell_price? - self.cost_price?)
+    }
+
+    /// Marža produktu v percentách predajnej ceny.
+    ///
+    /// # Returns
+    /// Marža v percentách alebo `None`, ak niektorá cena chýba alebo je predajná cena nulová
+    pub fn margin_percent(&self) -> Option<f64> {
+        let sell = self.sell_price.filter(|&p| p != 0.0)?;
+        Some(self.margin()? / sell * 100.0)
+    }
+
+    /// Čistá marža pri predaji za danú cenu po odpočte poplatkov (napr. za platbu kartou).
+    ///
+    /// # Arguments
+    /// * `sale_price` – skutočná predajná cena (počas akcie akciová)
+    /// * `fee_rate` – poplatok ako podiel z ceny (`0.02` = 2 %)
+    ///
+    /// # Returns
+    /// `sale_price − poplatky − cost_price` alebo `None`, ak chýba nákupná cena
+    pub fn net_margin(&self, sale_price: f64, fee_rate: f64) -> Option<f64> {
+        Some(sale_price - sale_price * fee_rate - self.cost_price?)
+    }
+
+    /// Bod zvratu: počet predaných kusov, ktorých marža pokryje fixné náklady.
+    ///
+    /// # Arguments
+    /// * `fixed_costs` – fixné náklady spojené s produktom
+    ///
+    /// # Returns
+    /// `fixed_costs / (sell_price − cost_price)` zaokrúhlené nahor alebo `None`,
+    /// ak niektorá cena chýba alebo marža nie je kladná
+    pub fn break_even_quantity(&self, fixed_costs: f64) -> Option<u32> {
+        let margin = self.margin().filter(|&m| m > 0.0)?;
+        Some((fixed_costs / margin).ceil() as u32)
+    }
+
+    /// Určí stav zásob produktu.
+    ///
+    /// Neaktívny produkt je `Discontinued`, nulové (alebo neznáme) množstvo
+    /// `OutOfStock`, množstvo do hranice vrátane `LowStock`, inak `InStock`.
+    /// Hranicou je `min_stock` produktu, ak ju má, inak `low_threshold`.
+    ///
+    /// # Arguments
+    /// * `low_threshold` – spoločná hranica nízkeho stavu zásob
+    ///
+    /// # Returns
+    /// Stav zásob
+    pub fn stock_status(&self, low_threshold: u32) -> StockStatus {
+        if self.status == Some(false) {
+            return StockStatus::Discontinued;
+        }
+        match self.quantity.unwrap_or(0.0) {
+            q if q <= 0.0 => StockStatus::OutOfStock,
+            q if q <= self.min_stock.unwrap_or(f64::from(low_threshold)) => StockStatus::LowStock,
+            _ => StockStatus::InStock,
         }
     }
 
     /// Vypíše informácie o produkte na konzolu.
+    #[allow(dead_code)]
     fn print_product(&self) {
         println!("ID: {}", self.id.map(|v| v.to_string()).unwrap_or("None".into()));
         println!("Name: {}", self.name.as_deref().unwrap_or("None"));
         println!("Category: {}", self.category.as_deref().unwrap_or("None"));
-        println!("Quantity: {}", self.quantity.map(|v| v.to_string()).unwrap_or("None".into()));
+        println!(
+            "Quantity: {} {}",
+            self.quantity.map(|v| v.to_string()).unwrap_or("None".into()),
+            self.unit.unwrap_or_default().as_str()
+        );
         println!(
             "Status: {}",
             match self.status {
@@ -228,3 +778,2318 @@ impl Product {
         println!();
     }
 }
+
+/// Položka predajnej objednávky.
+///
+/// `quantity` je v jednotke `unit`; ak chýba, použije sa jednotka produktu.
+/// Uložená položka má množstvo vždy prepočítané na jednotku produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderItem {
+    pub product_id:   u32,
+    pub quantity:     f64,
+    pub unit:         Option<Unit>,
+    pub unit_price:   Option<f64>,
+    /// Rezervácia, ktorú položka vyzdvihuje (jej množstvo sa nepočíta ako blokované)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reservation_id: Option<u32>,
+}
+
+/// Predajná objednávka (doklad o predaji).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Order {
+    pub id:           Option<u32>,
+    pub employee_id:  Option<u32>,
+    pub status:       Option<String>,
+    pub created_at:   Option<NaiveDateTime>,
+    #[serde(default)]
+    pub items:        Vec<OrderItem>,
+    #[serde(default)]
+    pub returns:      Vec<OrderReturn>,
+    /// Čas poslednej zmeny objednávky (nastavuje server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at:   Option<NaiveDateTime>,
+}
+
+/// Filter zoznamu objednávok (`GET /orders`) pre postupné sťahovanie zmien.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct OrderFilter {
+    /// Len objednávky zmenené po tomto čase, zoradené podľa `updated_at` a ID
+    pub updated_after: Option<NaiveDateTime>,
+    /// Len s `updated_after`: pri rovnakom `updated_at` len objednávky s vyšším ID
+    pub after_id:      Option<u32>,
+}
+
+/// Vrátená položka objednávky.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReturnItem {
+    pub product_id:   u32,
+    pub quantity:     f64,
+    /// Jednotka vráteného množstva (`None` = jednotka produktu; uložené množstvo je vždy v nej)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit:         Option<Unit>,
+    pub unit_price:   Option<f64>,
+}
+
+/// Doklad o vrátení tovaru naviazaný na objednávku.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrderReturn {
+    pub id:           Option<u32>,
+    pub order_id:     Option<u32>,
+    pub employee_id:  Option<u32>,
+    pub note:         Option<String>,
+    pub created_at:   Option<NaiveDateTime>,
+    pub items:        Vec<ReturnItem>,
+}
+
+/// Tržba jedného zamestnanca za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmployeeRevenue {
+    pub employee_id:  Option<u32>,
+    pub orders:       u32,
+    pub revenue:      f64,
+}
+
+/// Predaj jedného produktu za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductSales {
+    pub product_id:   u32,
+    pub name:         Option<String>,
+    pub quantity:     f64,
+    pub unit:         Unit,
+    pub revenue:      f64,
+}
+
+/// Denná uzávierka (Z-report).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyReport {
+    pub date:                 NaiveDate,
+    pub closed:               bool,
+    pub no_sales:             bool,
+    pub orders:               u32,
+    pub units_sold:           f64,
+    pub gross_revenue:        f64,
+    pub returns:              u32,
+    pub units_returned:       f64,
+    pub returned_amount:      f64,
+    pub net_revenue:          f64,
+    pub revenue_per_employee: Vec<EmployeeRevenue>,
+    pub top_products:         Vec<ProductSales>,
+}
+
+/// Stav objednávky u dodávateľa.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PurchaseOrderStatus {
+    Draft,
+    Sent,
+    PartiallyReceived,
+    Received,
+    Cancelled,
+}
+
+impl PurchaseOrderStatus {
+    /// Textová hodnota uložená v databáze.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PurchaseOrderStatus::Draft => "draft",
+            PurchaseOrderStatus::Sent => "sent",
+            PurchaseOrderStatus::PartiallyReceived => "partially_received",
+            PurchaseOrderStatus::Received => "received",
+            PurchaseOrderStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Prevedie textovú hodnotu z databázy na stav.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(PurchaseOrderStatus::Draft),
+            "sent" => Some(PurchaseOrderStatus::Sent),
+            "partially_received" => Some(PurchaseOrderStatus::PartiallyReceived),
+            "received" => Some(PurchaseOrderStatus::Received),
+            "cancelled" => Some(PurchaseOrderStatus::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Overí, či je povolený prechod do nového stavu.
+    ///
+    /// `draft → sent | cancelled`, `sent → partially_received | received | cancelled`,
+    /// `partially_received → received | cancelled`; `received` a `cancelled` sú konečné stavy.
+    ///
+    /// # Arguments
+    /// * `next` – nový stav
+    ///
+    /// # Returns
+    /// `true` ak je prechod povolený (alebo sa stav nemení)
+    pub fn can_transition_to(&self, next: PurchaseOrderStatus) -> bool {
+        use PurchaseOrderStatus::*;
+        *self == next
+            || matches!(
+                (self, next),
+                (Draft, Sent)
+                    | (Draft, Cancelled)
+                    | (Sent, PartiallyReceived)
+                    | (Sent, Received)
+                    | (Sent, Cancelled)
+                    | (PartiallyReceived, Received)
+                    | (PartiallyReceived, Cancelled)
+            )
+    }
+}
+
+/// Položka objednávky u dodávateľa.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PurchaseOrderItem {
+    pub product_id:        u32,
+    pub quantity:          f64,
+    pub unit_cost:         Option<f64>,
+    pub received_quantity: Option<f64>,
+}
+
+/// Prijaté množstvo jednej položky objednávky (kumulatívne, nie prírastok).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReceivedLine {
+    pub product_id:        u32,
+    pub received_quantity: f64,
+    pub lot_code:          Option<String>,
+    pub expiry_date:       Option<NaiveDate>,
+}
+
+/// Príjem tovaru k objednávke u dodávateľa.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PurchaseOrderReceipt {
+    pub items:             Vec<ReceivedLine>,
+}
+
+/// Rozdiel medzi objednaným a prijatým množstvom položky.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PurchaseOrderDiscrepancy {
+    pub product_id:     u32,
+    pub ordered:        f64,
+    pub received:       f64,
+    pub unit:           Unit,
+    pub difference:     f64,
+    pub over_delivered: bool,
+}
+
+/// Výsledok príjmu tovaru k objednávke.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PurchaseOrderReceiptResult {
+    pub status:         PurchaseOrderStatus,
+    pub lines:          Vec<PurchaseOrderDiscrepancy>,
+    /// Produkty, ktorých zásoby po príjme prekračujú `max_stock`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings:       Vec<String>,
+}
+
+/// Objednávka tovaru u dodávateľa.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PurchaseOrder {
+    pub id:            Option<u32>,
+    pub supplier:      Option<String>,
+    pub status:        Option<PurchaseOrderStatus>,
+    pub created_at:    Option<NaiveDateTime>,
+    pub expected_date: Option<NaiveDate>,
+    pub items:         Option<Vec<PurchaseOrderItem>>,
+}
+
+/// Návrh na doobjednanie produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReorderSuggestion {
+    pub product_id:         u32,
+    pub name:               Option<String>,
+    pub supplier:           Option<String>,
+    pub quantity:           f64,
+    pub unit:               Unit,
+    pub suggested_quantity: f64,
+    pub unit_cost:          Option<f64>,
+    /// Odhadovaná cena objednávky (`suggested_quantity × unit_cost`)
+    #[serde(default)]
+    pub estimated_cost:     Option<f64>,
+    /// Bod doobjednania: poistná zásoba a predaj počas dodacej lehoty (len `reorder-list`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reorder_point:      Option<f64>,
+    /// Priemerný denný predaj (len `reorder-list`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_sales:        Option<f64>,
+}
+
+/// Odhadovaná cena doobjednania produktov kategórie (`/stats/reorder-budget`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryReorderBudget {
+    pub category: String,
+    /// Súčet `estimated_cost` návrhov z `reorder-list`; nula, ak netreba nič doobjednať
+    pub budget:   f64,
+}
+
+/// Smer pohybu skladu.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MovementDirection {
+    /// Príjem (naskladnenie, vrátenie tovaru)
+    In,
+    /// Výdaj (predaj, odpis)
+    Out,
+}
+
+/// Jeden pohyb v knihe skladových pohybov (`GET /products/{id}/movements`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StockMovement {
+    pub id:           u32,
+    pub product_id:   u32,
+    pub direction:    MovementDirection,
+    /// Zmena množstva v jednotke produktu (kladná = príjem, záporná = výdaj)
+    pub quantity:     f64,
+    pub unit:         Unit,
+    /// Dôvod pohybu (`sale`, `return`, `purchase`, `adjustment`)
+    pub reason:       String,
+    /// Doklad, ktorý pohyb spôsobil (`ORD-12`, `RET-3`, `PO-5`)
+    pub reference:    Option<String>,
+    pub employee_id:  Option<u32>,
+    pub created_at:   NaiveDateTime,
+}
+
+/// Na koľko dní vystačia zásoby produktu pri súčasnom tempe predaja.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StockCoverage {
+    pub product_id:    u32,
+    pub name:          Option<String>,
+    pub quantity:      f64,
+    pub unit:          Unit,
+    /// Priemerný denný predaj za posledných `SALES_VELOCITY_DAYS` dní (po vratkách)
+    pub daily_sales:   f64,
+    /// `quantity / daily_sales`; `None` bez predaja v období
+    pub coverage_days: Option<f64>,
+    /// Prečo odhad chýba (bez predaja v období, produkt sa ešte nepredával)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message:       Option<String>,
+}
+
+/// Čistá marža produktu po akciovej cene a poplatkoch (`/products/{id}/net-margin`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetMargin {
+    pub product_id:    u32,
+    /// Cena, za ktorú sa dnes predáva (počas akcie akciová)
+    pub price:         Option<f64>,
+    pub on_sale:       bool,
+    pub cost_price:    Option<f64>,
+    pub fee_rate:      f64,
+    /// Poplatky z ceny (`price × fee_rate`)
+    pub fees:          Option<f64>,
+    /// `None`, ak chýba cena alebo nákupná cena
+    pub net_margin:    Option<f64>,
+    /// Čistá marža v percentách ceny; `None` aj pri nulovej cene
+    pub net_margin_percent: Option<f64>,
+}
+
+/// Bod zvratu produktu (`/products/{id}/break-even`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BreakEven {
+    pub product_id:   u32,
+    pub fixed_costs:  f64,
+    /// Marža na kus (`sell_price − cost_price`)
+    pub unit_margin:  f64,
+    /// Počet kusov, ktorých predaj pokryje fixné náklady
+    pub quantity:     u32,
+}
+
+/// Podklad objednávky u dodávateľa (`/suppliers/{name}/order-sheet`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SupplierOrderSheet {
+    pub supplier:       String,
+    pub lines:          Vec<ReorderSuggestion>,
+    /// Súčet odhadovaných cien riadkov so známou nákupnou cenou
+    pub total_cost:     f64,
+    /// Počet riadkov bez nákupnej ceny (nie sú v `total_cost`)
+    pub uncosted_lines: u32,
+}
+
+/// Riadok zostavy hodnoty zásob.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryValueLine {
+    pub product_id:         u32,
+    pub name:               Option<String>,
+    pub category:           Option<String>,
+    pub quantity:           f64,
+    pub unit:               Unit,
+    pub cost_price:         Option<f64>,
+    /// Množstvo × nákupná cena (bez nákupnej ceny 0)
+    pub value:              f64,
+}
+
+/// Hodnota zásob aktívnych produktov v nákupných cenách.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryValueReport {
+    pub lines:              Vec<InventoryValueLine>,
+    pub total_quantity:     f64,
+    pub total_value:        f64,
+    /// Počet produktov na sklade bez nákupnej ceny (do hodnoty sa nezapočítali)
+    pub uncosted_products:  u32,
+}
+
+/// Súhrn zásob aktívnych produktov na nástenke.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DashboardInventory {
+    pub products:           u32,
+    pub total_quantity:     f64,
+    /// Hodnota zásob v nákupných cenách
+    pub total_value:        f64,
+    /// Počet produktov na sklade bez nákupnej ceny
+    pub uncosted_products:  u32,
+}
+
+/// Súhrn pre nástenku (`GET /dashboard`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DashboardSummary {
+    pub generated_at:       NaiveDateTime,
+    pub inventory:          DashboardInventory,
+    /// Počet produktov s nízkym stavom zásob (vrátane vypredaných)
+    pub low_stock:          u32,
+    /// Počet vypredaných produktov
+    pub out_of_stock:       u32,
+    /// Produkty s najvyššou hodnotou zásob
+    pub top_products:       Vec<InventoryValueLine>,
+    /// Počet aktívnych zamestnancov
+    pub employees:          u32,
+    /// Posledné pohyby skladu, od najnovšieho
+    pub recent_activity:    Vec<StockMovement>,
+}
+
+/// Metóda oceňovania zásob podľa nákupných dávok.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ValuationMethod {
+    /// Vážený priemer nákupných cien všetkých dávok
+    #[default]
+    Average,
+    /// Na sklade zostávajú najnovšie dávky (vydáva sa najstarší tovar)
+    Fifo,
+}
+
+/// Parametre ocenenia zásob (`?method=fifo`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct ValuationQuery {
+    pub method: Option<ValuationMethod>,
+}
+
+/// Riadok ocenenia zásob jedného produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ValuationLine {
+    pub product_id:         u32,
+    pub name:               Option<String>,
+    pub category:           Option<String>,
+    pub quantity:           f64,
+    pub unit:               Unit,
+    /// Hodnota zásoby / množstvo (`None` pri nulovom množstve)
+    pub unit_cost:          Option<f64>,
+    pub value:              f64,
+    /// Množstvo nepokryté dávkami (zásoba spred evidencie dávok), ocenené `cost_price`
+    pub unlotted_quantity:  f64,
+}
+
+/// Ocenenie zásob aktívnych produktov zvolenou metódou.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ValuationReport {
+    pub method:             ValuationMethod,
+    pub lines:              Vec<ValuationLine>,
+    pub total_quantity:     f64,
+    pub total_value:        f64,
+}
+
+/// Vážená priemerná nákupná cena zásoby produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AverageCost {
+    pub product_id:   u32,
+    pub quantity:     f64,
+    /// Priemer nákupných dávok na sklade vážený množstvom; `None` bez zásoby alebo ceny
+    pub average_cost: Option<f64>,
+    /// Nákupná cena uložená pri produkte
+    pub cost_price:   Option<f64>,
+}
+
+/// Riadok zostavy produktov s nízkym stavom zásob.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LowStockLine {
+    pub product_id:         u32,
+    pub name:               Option<String>,
+    pub category:           Option<String>,
+    pub supplier:           Option<String>,
+    pub quantity:           f64,
+    pub unit:               Unit,
+    /// Vlastná hranica produktu (`None` = platí spoločná `threshold`)
+    pub min_stock:          Option<f64>,
+}
+
+/// Aktívne produkty s množstvom na hranici nízkeho stavu zásob alebo pod ňou.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LowStockReport {
+    pub threshold:          u32,
+    pub lines:              Vec<LowStockLine>,
+    /// Z toho produkty, ktoré sú úplne vypredané
+    pub out_of_stock:       u32,
+}
+
+/// Riadok zostavy nepredajných zásob.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeadStockLine {
+    pub product_id:         u32,
+    pub name:               Option<String>,
+    pub category:           Option<String>,
+    pub quantity:           f64,
+    pub unit:               Unit,
+    /// Hodnota zásob v nákupných cenách
+    pub value:              f64,
+    /// Posledný predaj, príjem na sklad alebo pridanie produktu (`None` = neznáme)
+    pub last_movement:      Option<NaiveDate>,
+    pub days_since_movement: Option<i64>,
+    /// Zamestnanec zodpovedný za produkt
+    pub employee_id:        Option<u32>,
+    pub employee_name:      Option<String>,
+}
+
+/// Aktívne produkty na sklade bez predaja a príjmu za posledných `days` dní.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeadStockReport {
+    pub days:               u32,
+    pub lines:              Vec<DeadStockLine>,
+    /// Spolu viazaná hodnota zásob v nákupných cenách
+    pub total_value:        f64,
+}
+
+/// Produkt, ktorého predajná cena nespĺňa minimálnu maržu (napr. po zdražení nákupu).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MarginViolation {
+    pub product_id:         u32,
+    pub name:               Option<String>,
+    pub category:           Option<String>,
+    pub cost_price:         f64,
+    pub sell_price:         f64,
+    /// Rozdiel predajnej a nákupnej ceny
+    pub margin:             f64,
+    /// Marža v percentách predajnej ceny
+    pub margin_percent:     f64,
+    /// Minimálna marža platná pre kategóriu (`15 %` alebo `0.20 €`)
+    pub threshold:          String,
+}
+
+/// Dôvod, prečo je predajná cena produktu podozrivá.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceAnomalyReason {
+    /// Predajná cena je viac ako `MAX_PRICE_MARKUP`-násobok nákupnej
+    MarkupTooHigh,
+    /// Predajná cena je menej ako `MIN_PRICE_MARKUP`-násobok nákupnej
+    MarkupTooLow,
+    /// Cena sa výrazne líši od ostatných produktov kategórie
+    CategoryOutlier,
+}
+
+/// Produkt s podozrivou predajnou cenou (pravdepodobná chyba pri zadávaní).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceAnomaly {
+    pub product_id:         u32,
+    pub name:               Option<String>,
+    pub category:           Option<String>,
+    pub cost_price:         f64,
+    pub sell_price:         f64,
+    /// Predajná cena / nákupná cena; `None` pri nulovej nákupnej cene
+    pub markup:             Option<f64>,
+    /// Odchýlka ceny od ostatných produktov kategórie v smerodajných odchýlkach;
+    /// `None`, ak je kategória na porovnanie príliš malá
+    pub z_score:            Option<f64>,
+    pub reasons:            Vec<PriceAnomalyReason>,
+}
+
+/// Upozornenie na stav zásob produktu.
+///
+/// Otvorené upozornenie má `resolved_at` prázdne; uzavrie ho monitor, keď sa
+/// zásoby doplnia, alebo používateľ ručne.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Alert {
+    pub id:                 u32,
+    pub product_id:         u32,
+    /// Druh upozornenia (`low_stock`)
+    #[serde(rename = "type")]
+    pub alert_type:         String,
+    pub created_at:         NaiveDateTime,
+    pub resolved_at:        Option<NaiveDateTime>,
+    pub message:            String,
+}
+
+/// Výsledok jedného vyhodnotenia stavu zásob monitorom upozornení.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct AlertEvaluation {
+    /// Novo vytvorené upozornenia
+    pub created:            u32,
+    /// Automaticky uzavreté upozornenia (zásoby sa doplnili)
+    pub resolved:           u32,
+}
+
+/// Uložená snímka súhrnných štatistík skladu.
+///
+/// Počíta sa z aktívnych produktov bez sád (rovnako ako hodnota zásob)
+/// a aktívnych zamestnancov.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatsSnapshot {
+    pub id:                 u32,
+    pub taken_at:           NaiveDateTime,
+    pub products:           u32,
+    pub total_quantity:     f64,
+    /// Hodnota zásob v nákupných cenách
+    pub inventory_value:    f64,
+    pub employees:          u32,
+    /// Počet produktov v jednotlivých kategóriách
+    pub categories:         BTreeMap<String, u32>,
+    /// Hodnota zásob jednotlivých kategórií v nákupných cenách (staršie snímky ju nemajú)
+    #[serde(default)]
+    pub category_values:    BTreeMap<String, f64>,
+}
+
+/// Snímka množstiev všetkých produktov (`POST /admin/stock-snapshot`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StockSnapshot {
+    pub id:       u32,
+    pub label:    String,
+    pub taken_at: NaiveDateTime,
+    /// Počet zachytených produktov
+    pub products: u32,
+}
+
+/// Zmena zásob produktu medzi dvoma snímkami.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StockDelta {
+    pub product_id:    u32,
+    /// Názov v novšej snímke (ak v nej produkt chýba, v staršej)
+    pub name:          String,
+    /// Množstvo v staršej snímke (`None`, ak v nej produkt nebol)
+    pub from_quantity: Option<f64>,
+    /// Množstvo v novšej snímke (`None`, ak v nej produkt nebol)
+    pub to_quantity:   Option<f64>,
+    /// `to_quantity − from_quantity`, chýbajúce množstvo sa počíta ako 0
+    pub change:        f64,
+}
+
+/// Porovnanie dvoch snímok zásob (`/stats/stock-delta`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StockDeltaReport {
+    pub from:     StockSnapshot,
+    pub to:       StockSnapshot,
+    /// Len produkty, ktorých množstvo sa zmenilo
+    pub products: Vec<StockDelta>,
+}
+
+/// Predaj produktu za jednu cenu (cenový bod odhadu elasticity).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PricePoint {
+    /// Predajná cena zaokrúhlená na centy
+    pub price:        f64,
+    /// Predané kusy po odpočítaní vrátení
+    pub units:        f64,
+    /// Prvý a posledný deň predaja za túto cenu
+    pub first_day:    NaiveDate,
+    pub last_day:     NaiveDate,
+    /// Priemerný predaj za deň medzi `first_day` a `last_day` (vrátane)
+    pub daily_units:  f64,
+}
+
+/// Hrubý odhad cenovej elasticity dopytu po produkte za obdobie.
+///
+/// Elasticita −2 znamená, že zdraženie o 1 % zníži predaj približne o 2 %.
+/// Odhad nezohľadňuje sezónnosť, akcie ani výpadky zásob, slúži len ako nápoveda.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceElasticity {
+    pub product_id:   u32,
+    pub from:         NaiveDate,
+    pub to:           NaiveDate,
+    /// Cenové body zoradené podľa ceny
+    pub points:       Vec<PricePoint>,
+    /// Odhad elasticity (`None`, ak je málo rôznych cien)
+    pub elasticity:   Option<f64>,
+    /// Či je dopyt elastický (`|elasticita| > 1`)
+    pub elastic:      Option<bool>,
+    /// Dôvod, prečo sa elasticita nedala odhadnúť
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note:         Option<String>,
+}
+
+/// Priemerný predaj v jednom kalendárnom mesiaci naprieč rokmi.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MonthlyAvg {
+    /// Kalendárny mesiac 1–12
+    pub month:        u32,
+    /// Počet rokov, v ktorých mesiac patrí do histórie predajov
+    pub years:        u32,
+    /// Predané kusy v tomto mesiaci za všetky roky (po odpočítaní vrátení)
+    pub units:        f64,
+    /// `units / years`
+    pub average:      f64,
+    /// Podiel priemeru mesiaca a priemeru všetkých mesiacov (1 = bežný mesiac);
+    /// `None`, ak sa v histórii nič nepredalo
+    pub index:        Option<f64>,
+}
+
+/// Sezónnosť predaja produktu alebo kategórie (`/products/{id}/seasonality`).
+///
+/// História siaha od mesiaca prvého po mesiac posledného predaja; mesiace
+/// bez predaja v nej sa počítajú s nulou.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Seasonality {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product_id:   Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category:     Option<String>,
+    /// Prvý a posledný deň s predajom (`None` bez predajov)
+    pub first_sale:   Option<NaiveDate>,
+    pub last_sale:    Option<NaiveDate>,
+    /// Mesiace 1–12 (prázdne pri histórii kratšej ako rok)
+    pub months:       Vec<MonthlyAvg>,
+    /// Dôvod, prečo sa sezónnosť nedala vypočítať
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note:         Option<String>,
+}
+
+/// Obdobie od–do (vrátane oboch dní).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct DateRange {
+    pub from:         NaiveDate,
+    pub to:           NaiveDate,
+}
+
+/// Hrubý zisk jednej kategórie za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryProfit {
+    pub category:       String,
+    pub units:          f64,
+    pub revenue:        f64,
+    pub cost:           f64,
+    pub gross_profit:   f64,
+    pub uncosted_units: f64,
+}
+
+/// Marža produktov jednej kategórie (rebríček `/stats/category-margins`).
+///
+/// Marža produktu je rozdiel predajnej a nákupnej ceny. `total_margin` je súčet
+/// marží vynásobených množstvom na sklade. Produkty bez niektorej ceny sa do
+/// priemeru ani súčtu nezapočítajú, ich počet je v `excluded`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryMargin {
+    pub category:     String,
+    pub products:     u32,
+    pub excluded:     u32,
+    pub avg_margin:   Option<f64>,
+    pub total_margin: f64,
+}
+
+/// Limit množstva tovaru v kategórii a jej aktuálny stav.
+///
+/// Množstvá sa sčítavajú v jednotkách produktov (kusy aj kg spolu).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryLimit {
+    pub category:           String,
+    /// Najväčší povolený súčet množstiev (`None` = bez limitu)
+    pub max_total_quantity: Option<f64>,
+    pub total_quantity:     f64,
+}
+
+/// Požiadavka na nastavenie limitu kategórie (`null` limit zruší).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryLimitRequest {
+    pub max_total_quantity: Option<f64>,
+}
+
+/// Požiadavka na nastavenie hranice nízkeho stavu zásob produktu (`null` hranicu zruší).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MinStockRequest {
+    pub min_stock: Option<f64>,
+}
+
+/// Požiadavka na nastavenie najvyššieho stavu zásob produktu (`null` hranicu zruší).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaxStockRequest {
+    pub max_stock: Option<f64>,
+}
+
+/// Naplánovaná zmena predajnej ceny produktu.
+///
+/// Uplatnená zmena zostáva uložená s pôvodnou cenou a časom uplatnenia
+/// ako história cien produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScheduledPriceChange {
+    pub id:             u32,
+    pub product_id:     u32,
+    pub new_price:      f64,
+    /// Prvý deň platnosti novej ceny
+    pub effective_date: NaiveDate,
+    pub created_at:     NaiveDateTime,
+    /// Kedy sa cena zmenila; `None`, kým zmena čaká
+    pub applied_at:     Option<NaiveDateTime>,
+    /// Predajná cena pred uplatnením zmeny
+    pub old_price:      Option<f64>,
+}
+
+/// Požiadavka na naplánovanie zmeny predajnej ceny.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceScheduleRequest {
+    pub new_price:      f64,
+    pub effective_date: NaiveDate,
+}
+
+/// Filter naplánovaných zmien cien (`?pending=true` len čakajúce, `false` len uplatnené).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ScheduledChangesQuery {
+    pub product_id: Option<u32>,
+    pub pending:    Option<bool>,
+}
+
+/// Požiadavka na akciu produktu (`null` akciovú cenu akciu zruší).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaleRequest {
+    pub sale_price: Option<f64>,
+    /// Posledný deň akcie (vrátane); povinný pri akciovej cene
+    #[serde(default)]
+    pub ends:       Option<NaiveDate>,
+}
+
+/// Záznamy, ktoré prehľadáva uložené vyhľadávanie.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchEntity {
+    #[serde(alias = "product")]
+    Products,
+    #[serde(alias = "employee")]
+    Employees,
+}
+
+impl SearchEntity {
+    /// Textová hodnota uložená v databáze.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchEntity::Products => "products",
+            SearchEntity::Employees => "employees",
+        }
+    }
+
+    /// Prevedie textovú hodnotu z databázy na druh záznamov.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "products" => Some(SearchEntity::Products),
+            "employees" => Some(SearchEntity::Employees),
+            _ => None,
+        }
+    }
+}
+
+/// Pomenovaný filter produktov alebo zamestnancov (napr. „akciový tovar na dochádzaní“).
+///
+/// Filter má tvar tela `POST /products/search`, resp. `POST /employees/search`.
+/// Názov je jedinečný v rámci druhu záznamov.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SavedSearch {
+    #[serde(default)]
+    pub id:           Option<u32>,
+    pub name:         String,
+    pub entity:       SearchEntity,
+    pub filter:       serde_json::Value,
+    /// Subjekt JWT, ktorý vyhľadávanie uložil (nastavuje server)
+    #[serde(default)]
+    pub owner:        Option<String>,
+    #[serde(default)]
+    pub created_at:   Option<NaiveDateTime>,
+    /// Prečo filtru aktuálna verzia nerozumie (`None` = filter je platný; počíta server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale:        Option<String>,
+}
+
+/// Parametre obrázka čiarového kódu (`?module_width=2&height=60&text=true`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct BarcodeQuery {
+    /// Šírka modulu (najužšej čiary) v pixeloch
+    pub module_width: Option<u32>,
+    /// Výška čiar v pixeloch
+    pub height:       Option<u32>,
+    /// Či sa pod kódom vypíšu číslice (predvolene áno)
+    pub text:         Option<bool>,
+}
+
+/// Položka indexu katalógu na vyhľadávanie u klienta (`GET /products/index`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchIndexEntry {
+    pub id:           u32,
+    pub name:         Option<String>,
+    pub bar_code:     Option<i64>,
+    pub category:     Option<String>,
+    /// Predajná cena
+    pub price:        Option<f64>,
+}
+
+/// Identifikátor produktu pre `GET /products/lookup`; zadáva sa práve jeden.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ProductLookup {
+    pub id:      Option<u32>,
+    /// Čiarový kód (napr. zo skenera)
+    pub barcode: Option<i64>,
+    /// Presný názov produktu (bez ohľadu na veľkosť písmen)
+    pub name:    Option<String>,
+}
+
+/// Parametre QR kódu produktu (`?url=https://obchod.sk/p/{id}&size=512`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct QrQuery {
+    /// Odkaz, ktorý kód zakóduje; `{id}` sa nahradí ID produktu (bez neho sa zakóduje len ID)
+    pub url:          Option<String>,
+    /// Strana obrázka v pixeloch
+    pub size:         Option<u32>,
+}
+
+/// Telo chybovej odpovede obrázka čiarového kódu.
+///
+/// `error` je `product_not_found` a `no_barcode` (`404`) alebo `invalid_barcode`
+/// (`422`, uložený kód nie je platný EAN-13/EAN-8 – treba opraviť dáta produktu).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BarcodeError {
+    pub error:        String,
+    pub product_id:   u32,
+    /// Uložený čiarový kód (pri `invalid_barcode`)
+    pub bar_code:     Option<i64>,
+    pub message:      String,
+}
+
+/// Filter zoznamu uložených vyhľadávaní (`?entity=products`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct SavedSearchQuery {
+    pub entity: Option<SearchEntity>,
+}
+
+/// Správa o hrubom zisku za obdobie.
+///
+/// Predaje produktov bez známej nákupnej ceny sú v `revenue`, ale nie sú
+/// započítané do `cost` ani `gross_profit`; ich počet je v `uncosted_units`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProfitReport {
+    pub from:           NaiveDate,
+    pub to:             NaiveDate,
+    pub units:          f64,
+    pub revenue:        f64,
+    pub cost:           f64,
+    pub gross_profit:   f64,
+    pub margin:         Option<f64>,
+    pub uncosted_units: f64,
+    pub categories:     Vec<CategoryProfit>,
+}
+
+/// Hrubý zisk z predajov pripísaných zamestnancovi za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmployeeProfit {
+    pub employee_id: u32,
+    pub name:        Option<String>,
+    pub surname:     Option<String>,
+    pub position:    Option<String>,
+    /// Marža predaných produktov so známou nákupnou cenou po odpočte vrátení
+    pub profit:      f64,
+}
+
+/// Zľavy poskytnuté v jednej kategórii za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryDiscount {
+    pub category:         String,
+    pub units:            f64,
+    /// Množstvo predané pod bežnou cenou
+    pub discounted_units: f64,
+    /// Tržba v účtovaných cenách
+    pub revenue:          f64,
+    /// Súčet (bežná cena − účtovaná cena) × množstvo
+    pub discount:         f64,
+}
+
+/// Zľavy poskytnuté pri predajoch za obdobie (`/stats/discounts`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiscountReport {
+    pub from:             NaiveDate,
+    pub to:               NaiveDate,
+    pub units:            f64,
+    pub discounted_units: f64,
+    pub revenue:          f64,
+    pub discount:         f64,
+    pub categories:       Vec<CategoryDiscount>,
+}
+
+/// Priemerný nákup (košík) za obdobie (`/stats/baskets`).
+///
+/// Košík je jedna objednávka; vrátenia sa neodpočítavajú. Obdobie bez predajov
+/// má `has_sales = false` a nulové priemery.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BasketStats {
+    pub from:             NaiveDate,
+    pub to:               NaiveDate,
+    pub has_sales:        bool,
+    /// Počet objednávok s aspoň jednou položkou
+    pub baskets:          u64,
+    /// Priemerný počet riadkov (rôznych položiek) na objednávku
+    pub avg_lines:        f64,
+    /// Priemerné predané množstvo na objednávku
+    pub avg_units:        f64,
+    /// Priemerná hodnota objednávky
+    pub avg_value:        f64,
+    /// Hodnota všetkých objednávok spolu
+    pub total_value:      f64,
+}
+
+/// Produkt kupovaný spolu s iným produktom (`/products/{id}/cross-sell`).
+///
+/// Košík je jedna objednávka; počíta sa len výskyt v košíku, nie množstvo.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CoPurchase {
+    pub product_id:       u32,
+    pub name:             String,
+    /// Počet košíkov s oboma produktmi
+    pub baskets:          u64,
+    /// Podiel košíkov s oboma produktmi na všetkých košíkoch
+    pub support:          f64,
+    /// Podiel košíkov s oboma produktmi na košíkoch s pôvodným produktom
+    pub confidence:       f64,
+}
+
+/// Obrátka zásob kategórie za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryTurnover {
+    pub category:           String,
+    /// Náklady predaného tovaru (v aktuálnych nákupných cenách, po odpočte vrátení)
+    pub cogs:               f64,
+    pub average_inventory:  f64,
+    /// `cogs / average_inventory`; `None`, ak kategória nemala zásoby
+    pub turnover:           Option<f64>,
+}
+
+/// Na koľko dní vystačia zásoby kategórie pri súčasnom tempe predaja.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryDaysOfInventory {
+    pub category: String,
+    /// Hodnota zásob / priemerné denné náklady predaného tovaru; `None`, ak sa
+    /// kategória v období nepredávala (zásoby vystačia neobmedzene)
+    pub days:     Option<f64>,
+}
+
+/// Veková skupina zásob podľa dátumu pridania produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AgingBucket {
+    /// Rozsah dní (`0-30`, `91+`) alebo `unknown` pre produkty bez `date_added`
+    pub label:    String,
+    pub min_days: Option<u32>,
+    /// `None` pre poslednú otvorenú skupinu
+    pub max_days: Option<u32>,
+    /// Počet produktov na sklade
+    pub products: u32,
+    pub quantity: f64,
+    /// Hodnota v nákupných cenách
+    pub value:    f64,
+}
+
+/// Obrátka zásob za obdobie: náklady predaného tovaru / priemerná hodnota zásob.
+///
+/// Priemerná hodnota zásob je priemer snímok štatistík z obdobia; bez snímok
+/// sa použije aktuálna hodnota zásob (`snapshots` je potom 0).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InventoryTurnover {
+    pub from:               NaiveDate,
+    pub to:                 NaiveDate,
+    pub cogs:               f64,
+    pub average_inventory:  f64,
+    /// `None`, ak v období neboli žiadne zásoby (delenie nulou)
+    pub turnover:           Option<f64>,
+    /// Počet snímok, z ktorých sa počítal priemer zásob
+    pub snapshots:          u32,
+    pub categories:         Vec<CategoryTurnover>,
+}
+
+/// Hrubý výnos z investície do zásob kategórie za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryGmroi {
+    pub category:           String,
+    /// Hrubý zisk z predajov so známou nákupnou cenou (po odpočte vrátení)
+    pub gross_margin:       f64,
+    pub average_inventory:  f64,
+    /// `gross_margin / average_inventory`; `None`, ak kategória nemala zásoby
+    pub gmroi:              Option<f64>,
+}
+
+/// Hrubý výnos z investície do zásob (GMROI) za obdobie: hrubý zisk / priemerná hodnota zásob.
+///
+/// Priemerná hodnota zásob sa počíta ako pri obrátke zásob (`InventoryTurnover`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Gmroi {
+    pub from:               NaiveDate,
+    pub to:                 NaiveDate,
+    pub gross_margin:       f64,
+    pub average_inventory:  f64,
+    /// `None`, ak bola priemerná hodnota zásob nulová (delenie nulou)
+    pub gmroi:              Option<f64>,
+    /// Počet snímok, z ktorých sa počítal priemer zásob
+    pub snapshots:          u32,
+    /// Dôvod, prečo GMROI nie je definované
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note:               Option<String>,
+    pub categories:         Vec<CategoryGmroi>,
+}
+
+/// Straty zásob kategórie za obdobie (odpisy bez predaja).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategoryShrinkage {
+    pub category:       String,
+    pub units_lost:     f64,
+    /// Hodnota strát v aktuálnych nákupných cenách
+    pub value_lost:     f64,
+    /// Predané množstvo po odpočte vrátení
+    pub units_sold:     f64,
+    /// `units_lost / (units_lost + units_sold)`, 0 ak sa s tovarom nehýbalo
+    pub rate:           f64,
+    pub uncosted_units: f64,
+}
+
+/// Správa o stratách zásob (krádeže, poškodenie) za obdobie.
+///
+/// Stratou je záporná inventúrna úprava (pohyb skladu `adjustment`); predaje
+/// a vrátenia sa zapisujú do objednávok, nie do pohybov skladu, takže sa do
+/// strát nezapočítajú. Straty produktov bez nákupnej ceny nie sú vo `value_lost`,
+/// ich počet je v `uncosted_units`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShrinkageReport {
+    pub from:           NaiveDate,
+    pub to:             NaiveDate,
+    pub units_lost:     f64,
+    pub value_lost:     f64,
+    pub units_sold:     f64,
+    pub rate:           f64,
+    pub uncosted_units: f64,
+    pub categories:     Vec<CategoryShrinkage>,
+}
+
+/// Miera predaja kategórie za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CategorySellThrough {
+    pub category:       String,
+    /// Predané množstvo po odpočte vrátení
+    pub units_sold:     f64,
+    /// Prijaté množstvo (príjemky objednávok a kladné inventúrne úpravy)
+    pub units_received: f64,
+    /// `units_sold / units_received`; `None`, ak kategória v období nič neprijala
+    pub rate:           Option<f64>,
+}
+
+/// Miera predaja (sell-through) za obdobie: predané / prijaté kusy podľa skladových pohybov.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SellThroughReport {
+    pub from:           NaiveDate,
+    pub to:             NaiveDate,
+    pub units_sold:     f64,
+    pub units_received: f64,
+    /// `None`, ak sa v období nič neprijalo (miera nie je definovaná)
+    pub rate:           Option<f64>,
+    pub categories:     Vec<CategorySellThrough>,
+}
+
+/// Hodnotenie dodávateľa zložené zo včasnosti dodávok, stálosti cien a dostupnosti tovaru.
+///
+/// Zložky sú v rozsahu 0–1 (1 = najlepšie); `None` znamená, že pre zložku
+/// chýbajú údaje a do skóre sa nezapočíta.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SupplierScore {
+    pub supplier:           String,
+    /// Objednávky s očakávaným dňom, ku ktorým sa už niečo prijalo
+    pub deliveries:         u32,
+    /// Z nich prijaté (prvou príjemkou) najneskôr v očakávaný deň
+    pub on_time_deliveries: u32,
+    pub on_time_rate:       Option<f64>,
+    /// `1 - priemerný variačný koeficient` nákupných cien produktov (aspoň 0)
+    pub price_stability:    Option<f64>,
+    /// Výdaje zo skladu produktov dodávateľa
+    pub outgoing:           u32,
+    /// Z nich výdaje, po ktorých stav zásob klesol na nulu
+    pub stock_outs:         u32,
+    /// `1 - stock_outs / outgoing`
+    pub availability:       Option<f64>,
+    /// Vážený priemer dostupných zložiek; `None`, ak nie je žiadna
+    pub score:              Option<f64>,
+}
+
+/// Počet zamestnancov ku koncu jedného mesiaca.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MonthlyHeadcount {
+    pub month:        String,
+    pub date:         NaiveDate,
+    pub hired:        u32,
+    pub headcount:    u32,
+}
+
+/// Záznam dochádzky: príchod a odchod zamestnanca (`clock_out` prázdne = otvorený záznam).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AttendanceEntry {
+    pub id:             u32,
+    pub employee_id:    u32,
+    pub clock_in:       NaiveDateTime,
+    pub clock_out:      Option<NaiveDateTime>,
+}
+
+/// Priradenie zamestnanca na zmenu v konkrétny deň (rozpis služieb).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShiftAssignment {
+    pub id:             u32,
+    pub employee_id:    u32,
+    pub date:           NaiveDate,
+    pub shift:          String,
+}
+
+/// Telo `POST /schedule`: zamestnanec, deň a zmena (napr. `morning`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShiftAssignmentRequest {
+    pub employee_id:    u32,
+    pub date:           NaiveDate,
+    pub shift:          String,
+}
+
+/// Odpracované hodiny zamestnanca v jednom týždni (pondelok – nedeľa).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WeeklyHours {
+    pub employee_id:    u32,
+    pub name:           String,
+    /// ISO týždeň (`2026-W42`)
+    pub week:           String,
+    pub week_start:     NaiveDate,
+    pub hours:          f64,
+    /// Hodiny nad týždennú normu
+    pub overtime:       f64,
+}
+
+/// Odpracované hodiny v jednom dni (zmena cez polnoc sa rozdelí medzi dni).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DailyHours {
+    pub date:           NaiveDate,
+    pub hours:          f64,
+}
+
+/// Otvorený záznam dochádzky, ktorému chýba odchod a treba ho opraviť.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MissingClockOut {
+    pub entry_id:       u32,
+    pub employee_id:    u32,
+    pub name:           String,
+    pub date:           NaiveDate,
+    pub clock_in:       NaiveDateTime,
+}
+
+/// Odpracované hodiny a nadčasy za obdobie pre mzdy.
+///
+/// Otvorené záznamy (bez odchodu) sa nezapočítavajú; staršie ako najdlhšia
+/// zmena sú v `missing_clock_outs`, všetky sú spomenuté vo `warnings`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HoursReport {
+    pub from:               NaiveDate,
+    pub to:                 NaiveDate,
+    /// Týždenná norma hodín, nad ktorú sa počíta nadčas
+    pub weekly_norm:        f64,
+    pub weeks:              Vec<WeeklyHours>,
+    pub missing_clock_outs: Vec<MissingClockOut>,
+    pub warnings:           Vec<String>,
+}
+
+/// Detail dochádzky jedného zamestnanca za obdobie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmployeeHours {
+    pub employee_id:        u32,
+    pub entries:            Vec<AttendanceEntry>,
+    pub days:               Vec<DailyHours>,
+    #[serde(flatten)]
+    pub report:             HoursReport,
+}
+
+/// Prehľad výkonu zamestnanca.
+///
+/// `catalog_value` je hodnota zásob ním pridaných produktov v predajných cenách,
+/// `revenue` tržba z objednávok, ktoré zaevidoval (bez odpočtu vrátení).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Scorecard {
+    pub employee_id:    u32,
+    pub products_added: u32,
+    pub catalog_value:  f64,
+    pub orders:         u32,
+    pub revenue:        f64,
+}
+
+/// Vývoj počtu zamestnancov po mesiacoch.
+///
+/// Odchod zamestnanca sa neeviduje, preto sa počítajú len aktívni zamestnanci
+/// (podľa dátumu nástupu); neaktívni sú v `excluded_inactive`, zamestnanci bez
+/// dátumu nástupu v `excluded_no_hire_date`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeadcountReport {
+    pub from:                  NaiveDate,
+    pub to:                    NaiveDate,
+    pub excluded_no_hire_date: u32,
+    pub excluded_inactive:     u32,
+    pub months:                Vec<MonthlyHeadcount>,
+}
+
+/// Mzdové náklady aktívnych zamestnancov (`/stats/payroll`).
+///
+/// `salary` zamestnanca je mesačná mzda; ročné náklady sú jej dvanásťnásobok.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PayrollSummary {
+    /// Počet aktívnych zamestnancov s vyplnenou mzdou
+    pub headcount:         u32,
+    /// Súčet mesačných miezd
+    pub monthly:           f64,
+    /// `monthly × 12`
+    pub annual:            f64,
+    /// Aktívni zamestnanci bez mzdy (nie sú v súčtoch)
+    pub without_salary:    u32,
+    /// Neaktívni zamestnanci (nie sú v súčtoch)
+    pub excluded_inactive: u32,
+}
+
+/// Pomer mesačných mzdových nákladov k hodnote zásob (`GET /stats/labor-ratio`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LaborRatio {
+    /// Mesačné mzdy / hodnota zásob; `None` pri nulovej hodnote zásob
+    pub ratio:        Option<f64>,
+    /// `ratio × 100`
+    pub percent:      Option<f64>,
+    /// Dôvod, prečo pomer nie je definovaný
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note:         Option<String>,
+}
+
+/// Obsadenie zmien jedného oddelenia aktívnymi zamestnancami.
+///
+/// `shifts` má v každom riadku rovnaké kľúče (všetky zmeny zamestnancov v evidencii),
+/// riadky preto tvoria maticu oddelenie × zmena.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ShiftCoverage {
+    /// Oddelenie (`None` = zamestnanci bez oddelenia)
+    pub department:   Option<String>,
+    /// Počet aktívnych zamestnancov na každej zmene
+    pub shifts:       BTreeMap<String, u32>,
+    /// Aktívni zamestnanci oddelenia bez zadanej zmeny
+    pub unassigned:   u32,
+    /// Zmeny, na ktorých oddelenie nemá žiadneho aktívneho zamestnanca
+    pub uncovered:    Vec<String>,
+}
+
+/// Uzol organizačnej štruktúry: zamestnanec a jeho priami podriadení.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrgNode {
+    pub employee:     Employee,
+    /// Priami podriadení zoradení podľa ID
+    pub reports:      Vec<OrgNode>,
+}
+
+impl OrgNode {
+    /// Zostaví organizačnú štruktúru z nadriadených (`manager_id`).
+    ///
+    /// Koreňmi sú zamestnanci bez nadriadeného alebo s neexistujúcim nadriadeným.
+    /// Kontroly pri zápise cykly nepripustia, staršie dáta ich však mať môžu:
+    /// zamestnanci v cykle sa nedajú dosiahnuť z koreňa, preto sa koreňom stane
+    /// ten s najnižším ID a cyklus sa pri ňom preruší. Každý zamestnanec je
+    /// v štruktúre práve raz.
+    ///
+    /// # Arguments
+    /// * `employees` – všetci zamestnanci
+    ///
+    /// # Returns
+    /// Stromy organizačnej štruktúry zoradené podľa ID koreňa
+    pub fn build(employees: Vec<Employee>) -> Vec<OrgNode> {
+        let mut by_id: BTreeMap<u32, Employee> = employees.into_iter().filter_map(|e| Some((e.id?, e))).collect();
+        let mut children: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        let mut roots = Vec::new();
+        for (&id, employee) in &by_id {
+            match employee.manager_id.filter(|m| *m != id && by_id.contains_key(m)) {
+                Some(manager) => children.entry(manager).or_default().push(id),
+                None => roots.push(id),
+            }
+        }
+
+        let mut pending: BTreeSet<u32> = by_id.keys().copied().collect();
+        let mut trees = Vec::new();
+        let mut roots = roots.into_iter();
+        // najprv skutoční koreňi, potom zvyšky cyklov od najnižšieho ID
+        while let Some(root) = roots.next().or_else(|| pending.first().copied()) {
+            if pending.contains(&root) {
+                trees.push(Self::subtree(root, &mut by_id, &children, &mut pending));
+            }
+        }
+        trees.sort_by_key(|node| node.employee.id);
+        trees
+    }
+
+    /// Zostaví podstrom zamestnanca; navštívení zamestnanci sa vyradia z `pending`.
+    fn subtree(
+        id: u32,
+        by_id: &mut BTreeMap<u32, Employee>,
+        children: &BTreeMap<u32, Vec<u32>>,
+        pending: &mut BTreeSet<u32>,
+    ) -> OrgNode {
+        pending.remove(&id);
+        let employee = by_id.remove(&id).unwrap_or_default();
+        let mut reports = Vec::new();
+        for &child in children.get(&id).into_iter().flatten() {
+            if pending.contains(&child) {
+                reports.push(Self::subtree(child, by_id, children, pending));
+            }
+        }
+        OrgNode { employee, reports }
+    }
+}
+
+/// Záznam v audit logu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub id:           Option<u32>,
+    pub action:       String,
+    /// Druh dotknutého záznamu (`product`, `api_key`, ...), ak sa akcia týka jedného záznamu
+    #[serde(default)]
+    pub entity_type:  Option<String>,
+    #[serde(default)]
+    pub entity_id:    Option<u32>,
+    pub details:      Option<String>,
+    /// Subjekt JWT, ktorý akciu vykonal (`None` pri API kľúči)
+    #[serde(default)]
+    pub actor:        Option<String>,
+    pub created_at:   Option<NaiveDateTime>,
+}
+
+/// Filter exportu audit logu (`?from=YYYY-MM-DD&to=YYYY-MM-DD&entity=product&action=...`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AuditFilter {
+    /// Prvý deň obdobia
+    pub from:         Option<NaiveDate>,
+    /// Posledný deň obdobia
+    pub to:           Option<NaiveDate>,
+    /// Druh dotknutého záznamu
+    pub entity:       Option<String>,
+    pub action:       Option<String>,
+}
+
+/// Požiadavka na vydanie API kľúča zamestnancovi (`POST /admin/keys`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiKeyRequest {
+    pub employee_id:  u32,
+    /// Rola kľúča, predvolene `viewer`
+    #[serde(default)]
+    pub role:         Option<Role>,
+    /// Popis kľúča (napr. pokladňa, zariadenie)
+    #[serde(default)]
+    pub name:         Option<String>,
+}
+
+/// API kľúč zamestnanca bez tajomstva (hash sa nikdy nevracia).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiKey {
+    pub id:           u32,
+    pub employee_id:  u32,
+    pub name:         Option<String>,
+    pub role:         Role,
+    /// Verejný identifikátor v kľúči (`smk_<key_id>_...`)
+    pub key_id:       String,
+    pub created_at:   NaiveDateTime,
+    /// Čas odvolania; odvolaný kľúč sa už neoverí
+    pub revoked_at:   Option<NaiveDateTime>,
+}
+
+/// Novo vydaný API kľúč; celý kľúč sa vráti len v tejto odpovedi.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub info:         ApiKey,
+    /// Celý kľúč pre hlavičku `x-api-key`
+    pub api_key:      String,
+}
+
+/// Bývalý zamestnanec, ktorého údaje politika uchovávania odstráni.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RetentionCandidate {
+    pub employee_id:    u32,
+    /// Deň deaktivácie zamestnanca
+    pub departed_on:    NaiveDate,
+}
+
+/// Výsledok (alebo pri `dry_run` náhľad) uplatnenia politiky uchovávania osobných údajov.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RetentionReport {
+    pub dry_run:            bool,
+    pub action:             RetentionAction,
+    /// Zamestnanci, ktorí odišli pred týmto dňom (`None` = zamestnanci sa nemažú)
+    pub employee_cutoff:    Option<NaiveDate>,
+    /// Dochádzka pred týmto dňom (`None` = dochádzka sa nemaže)
+    pub attendance_cutoff:  Option<NaiveDate>,
+    pub employees:          Vec<RetentionCandidate>,
+    /// Počet vymazaných záznamov dochádzky
+    pub attendance_entries: u64,
+    /// Počet produktov, objednávok a vrátení, ktorým sa vynuluje odkaz na vymazaného zamestnanca
+    pub references:         u64,
+}
+
+/// Odkazy jedného stĺpca na neexistujúce záznamy (`GET /admin/integrity-check`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OrphanedReferences {
+    pub table:        String,
+    pub column:       String,
+    /// Odkazovaná tabuľka
+    pub references:   String,
+    /// ROWID riadkov s neplatným odkazom (v tabuľkách s `id` je to ID)
+    pub row_ids:      Vec<i64>,
+    /// Odkazované ID, ktoré neexistujú
+    pub missing_ids:  Vec<i64>,
+    /// Stĺpec pripúšťa `NULL`, odkaz sa dá opravou vynulovať
+    pub repairable:   bool,
+}
+
+/// Výsledok kontroly (alebo opravy) referenčnej integrity.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Opraviteľné odkazy sa vynulovali (`POST`), inak ide len o kontrolu
+    pub repaired:     bool,
+    pub orphans:      Vec<OrphanedReferences>,
+    /// Počet riadkov s neplatným odkazom
+    pub total:        u64,
+    /// Počet vynulovaných odkazov
+    pub nulled:       u64,
+}
+
+/// Šarža (lot) produktu s dátumom expirácie.
+///
+/// Pri úprave zásob môže `unit` určiť jednotku zadaného množstva (prepočíta sa
+/// na jednotku produktu); vo výpise je to vždy jednotka produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductBatch {
+    pub id:           Option<u32>,
+    pub product_id:   Option<u32>,
+    pub lot_code:     Option<String>,
+    pub expiry_date:  Option<NaiveDate>,
+    pub quantity:     Option<f64>,
+    pub unit:         Option<Unit>,
+}
+
+/// Šarža blížiaca sa k expirácii.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExpiringBatch {
+    #[serde(flatten)]
+    pub batch:        ProductBatch,
+    pub product_name: Option<String>,
+    pub days_left:    i64,
+    pub expired:      bool,
+}
+
+/// Požiadavka na opätovnú aktiváciu produktov dodávateľa.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SupplierReactivation {
+    pub supplier:     String,
+    pub quantity:     Option<f64>,
+}
+
+/// Požiadavka na zlúčenie duplicitného záznamu zamestnanca do iného.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmployeeMerge {
+    /// Zamestnanec, ktorý zostane
+    pub keep_id:      u32,
+    /// Duplicitný zamestnanec, ktorý sa po zlúčení vymaže
+    pub merge_id:     u32,
+}
+
+/// Hromadná úprava platov oddelenia: `salary * factor + flat`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SalaryAdjustment {
+    pub department:   String,
+    /// Násobok platu, predvolene 1 (`1.05` = zvýšenie o 5 %)
+    pub factor:       Option<f64>,
+    /// Pevná suma pripočítaná po vynásobení, predvolene 0
+    pub flat:         Option<f64>,
+}
+
+/// Výsledok hromadnej úpravy platov oddelenia.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SalaryAdjustmentReport {
+    pub department:   String,
+    /// Počet zamestnancov s upraveným platom
+    pub adjusted:     u64,
+    /// Aktívni zamestnanci oddelenia bez platu (nezmenení)
+    pub skipped_without_salary: Vec<u32>,
+}
+
+/// Zamestnanec, ktorého plat sa odchyľuje od mediánu jeho pozície.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SalaryOutlier {
+    pub employee_id: u32,
+    pub name:        Option<String>,
+    pub surname:     Option<String>,
+    pub position:    String,
+    pub salary:      f64,
+    /// Medián platov aktívnych zamestnancov na rovnakej pozícii
+    pub median:      f64,
+    /// `(salary - median) / median`; záporná pri plate pod mediánom
+    pub deviation:   f64,
+}
+
+/// Zložka sady (darčekového koša).
+///
+/// `name` a `available` (stav zásob zložky) sa len vypisujú, pri definovaní
+/// zloženia sa ignorujú.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BundleComponent {
+    pub component_product_id: u32,
+    pub quantity:             f64,
+    pub name:                 Option<String>,
+    pub available:            Option<f64>,
+}
+
+/// Sada zložená z iných produktov.
+///
+/// `buildable` je počet sád, ktoré sa dajú zostaviť z aktuálnych zásob zložiek.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Bundle {
+    pub bundle_id:    u32,
+    #[serde(default)]
+    pub buildable:    u32,
+    pub components:   Vec<BundleComponent>,
+}
+
+/// Preklad názvu a popisu produktu do jazyka (`sk`, `hu`, ...).
+///
+/// `product_id` a `lang` dopĺňa server z cesty; chýbajúce pole sa pri
+/// zobrazení nahradí základnou hodnotou produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductTranslation {
+    #[serde(default)]
+    pub product_id:   u32,
+    #[serde(default)]
+    pub lang:         String,
+    pub name:         Option<String>,
+    pub description:  Option<String>,
+}
+
+/// Hromadná úprava predajných cien o percento.
+///
+/// Bez filtra sa upravia ceny všetkých produktov.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceAdjustment {
+    pub percent:      f64,
+    pub category:     Option<String>,
+    pub supplier:     Option<String>,
+}
+
+/// Hromadná úprava: rovnaká zmena polí pre všetky zadané ID.
+///
+/// `set` je čiastočný záznam – menia sa len polia, ktoré nie sú `None`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkUpdate<T> {
+    pub ids:          Vec<u32>,
+    pub set:          T,
+}
+
+/// Výsledok hromadnej úpravy pre jedno ID.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkUpdateStatus {
+    Updated,
+    NotFound,
+}
+
+/// Výsledok hromadnej úpravy jedného záznamu.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BulkUpdateResult {
+    pub id:           u32,
+    pub status:       BulkUpdateStatus,
+}
+
+/// Parametre hromadného vytvorenia (`?best_effort=true`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default)]
+pub struct BulkCreateQuery {
+    /// Platné položky sa vytvoria aj pri chybe inej položky (`207 Multi-Status`);
+    /// inak sa pri prvej chybe nevytvorí nič
+    #[serde(default)]
+    pub best_effort:  bool,
+}
+
+/// Výsledok hromadného vytvorenia jednej položky.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BulkCreateResult {
+    /// Poradie položky v tele požiadavky (od 0)
+    pub index:        u32,
+    /// Stav HTTP, ktorý by položka dostala samostatne (`201` pri úspechu)
+    pub status:       u16,
+    /// ID vytvoreného záznamu
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id:           Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error:        Option<String>,
+}
+
+/// Hromadné pridanie štítku produktom podľa filtra (`POST /products/tag-by-filter`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TagByFilter {
+    /// Filter produktov ako pri vyhľadávaní (prázdny = všetky produkty)
+    #[serde(default)]
+    pub filter:       Product,
+    pub tag:          String,
+}
+
+/// Výsledok hromadného pridania štítku.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TaggingReport {
+    /// Štítok po normalizácii (orezaný, malými písmenami)
+    pub tag:          String,
+    /// Počet produktov, ktoré zodpovedajú filtru (všetky štítok teraz majú)
+    pub tagged:       u64,
+    /// Počet produktov, ktoré štítok predtým nemali
+    pub added:        u64,
+}
+
+/// Hromadné vymazanie produktov podľa filtra (`POST /products/delete-by-filter`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DeleteByFilter {
+    /// Filter produktov ako pri vyhľadávaní
+    #[serde(default)]
+    pub filter:       Product,
+    /// Prázdny filter vymaže všetky produkty len s `confirm_all: true`
+    #[serde(default)]
+    pub confirm_all:  bool,
+}
+
+/// Výsledok hromadného vymazania produktov.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DeletionReport {
+    pub deleted:      u64,
+    /// ID vymazaných produktov vzostupne
+    pub ids:          Vec<u32>,
+}
+
+/// Hromadná zmena stavu produktov podľa filtra (`POST /products/set-status-by-filter`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StatusByFilter {
+    /// Filter produktov ako pri vyhľadávaní (nesmie byť prázdny)
+    #[serde(default)]
+    pub filter:       Product,
+    /// Nový stav: `false` produkty vyradí, `true` ich obnoví
+    pub active:       bool,
+}
+
+/// Výsledok hromadnej zmeny stavu produktov.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StatusChangeReport {
+    /// Počet produktov, ktoré zodpovedajú filtru
+    pub matched:      u64,
+    /// Počet produktov, ktorým sa stav zmenil
+    pub changed:      u64,
+    /// ID zmenených produktov vzostupne
+    pub ids:          Vec<u32>,
+}
+
+/// Výsledok premenovania jednej kategórie (`POST /products/remap-categories`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CategoryRemap {
+    pub from:         String,
+    pub to:           String,
+    /// Počet preradených produktov
+    pub products:     u64,
+}
+
+/// Rozdiel produktu oproti prvému produktu porovnania (`other − base`).
+///
+/// Rozdiel je `None`, ak niektorému z produktov chýba potrebná hodnota.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProductDelta {
+    /// ID produktu, s ktorým sa porovnáva (prvý v `ids`)
+    pub base_id:        u32,
+    pub product_id:     u32,
+    pub sell_price:     Option<f64>,
+    pub cost_price:     Option<f64>,
+    /// Rozdiel marží (predajná mínus nákupná cena)
+    pub margin:         Option<f64>,
+    /// Rozdiel marží v percentuálnych bodoch (marža v percentách predajnej ceny)
+    pub margin_percent: Option<f64>,
+    pub quantity:       Option<f64>,
+}
+
+impl ProductDelta {
+    /// Vypočíta rozdiely cien, marže a zásob produktu `other` oproti `base`.
+    ///
+    /// # Arguments
+    /// * `base` – produkt, voči ktorému sa porovnáva
+    /// * `other` – porovnávaný produkt
+    ///
+    /// # Returns
+    /// Rozdiely `other − base`
+    pub fn between(base: &Product, other: &Product) -> Self {
+        let diff = |a: Option<f64>, b: Option<f64>| Some(b? - a?);
+        Self {
+            base_id:        base.id.unwrap_or_default(),
+            product_id:     other.id.unwrap_or_default(),
+            sell_price:     diff(base.sell_price, other.sell_price),
+            cost_price:     diff(base.cost_price, other.cost_price),
+            margin:         diff(base.margin(), other.margin()),
+            margin_percent: diff(base.margin_percent(), other.margin_percent()),
+            quantity:       diff(base.quantity, other.quantity),
+        }
+    }
+}
+
+/// Dávkové načítanie zamestnancov (`POST /employees/batch`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmployeeBatch {
+    /// Nájdení zamestnanci v poradí požadovaných ID
+    pub employees:    Vec<Employee>,
+    /// Požadované ID, ktoré neexistujú
+    pub not_found:    Vec<u32>,
+}
+
+/// Porovnanie produktov (`GET /products/compare?ids=1,2`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProductComparison {
+    /// Nájdené produkty v poradí `ids`
+    pub products:     Vec<Product>,
+    /// Rozdiely každého ďalšieho produktu oproti prvému
+    pub deltas:       Vec<ProductDelta>,
+    /// Požadované ID, ktoré neexistujú
+    pub not_found:    Vec<u32>,
+}
+
+/// Nová predajná cena produktu: vypočítaná (`raw_price`) a zaokrúhlená (`price`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceChange {
+    pub product_id:   u32,
+    pub name:         Option<String>,
+    pub old_price:    Option<f64>,
+    pub raw_price:    f64,
+    pub price:        f64,
+}
+
+/// Výsledok zaokrúhlenia všetkých predajných cien (`POST /products/round-prices`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PriceRoundingReport {
+    /// Použité pravidlo v tvare konfigurácie (`0.99`, `0.05`, `ceil`, ...)
+    pub rule:         String,
+    /// Len náhľad, ceny sa nezapísali
+    pub dry_run:      bool,
+    /// Počet zmenených (pri náhľade menených) cien
+    pub changed:      u64,
+    /// Pôvodné a nové ceny zmenených produktov
+    pub changes:      Vec<PriceChange>,
+}
+
+/// Jeden riadok NDJSON importu; typ záznamu určuje pole `type`.
+///
+/// Príklad: `{"type":"product","name":"Mlieko","quantity":10,...}`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImportRecord {
+    Employee(Employee),
+    Product(Product),
+}
+
+/// Chybný riadok importu (riadky sa číslujú od 1).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportLineError {
+    pub line:         u64,
+    pub message:      String,
+}
+
+/// Výsledok hromadného importu.
+///
+/// `completed` je `false`, ak sa import v prísnom režime zastavil na chybe;
+/// dávky uložené pred chybným riadkom zostávajú v databáze.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ImportReport {
+    pub lines:        u64,
+    pub employees:    u64,
+    pub products:     u64,
+    pub skipped:      u64,
+    /// Platné riadky inej entity, ako určuje výber `only`
+    #[serde(default)]
+    pub ignored:      u64,
+    pub completed:    bool,
+    pub errors:       Vec<ImportLineError>,
+}
+
+/// Výsledok importu nahraného súboru (`POST /import/upload`) podľa jeho formátu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum UploadImportReport {
+    /// Súbor vo formáte `store_data.json`
+    Json(DataImportReport),
+    /// Jeden JSON záznam na riadok
+    Ndjson(ImportReport),
+    /// Produkty, jeden na riadok
+    Csv(ImportReport),
+}
+
+/// Výsledok importu JSON súboru vo formáte exportu (`POST /admin/import`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DataImportReport {
+    pub employees:    u64,
+    pub products:     u64,
+    /// Neprázdne polia súboru, ktoré sa pre výber `only` ignorovali
+    pub ignored:      Vec<String>,
+    /// Súbor bol anonymizovaný export
+    #[serde(default)]
+    pub anonymized:   bool,
+    /// Záznamy preskočené v režime `LoadMode::BestEffort`
+    #[serde(default)]
+    pub skipped:      Vec<SkippedRecord>,
+}
+
+/// Záznam JSON súboru, ktorý import v režime `LoadMode::BestEffort` preskočil.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SkippedRecord {
+    /// Pole súboru (`employees`, `products`, `bundles`, `translations`, `purchase_orders`)
+    pub entity:       String,
+    /// Poradie záznamu v poli (od 0)
+    pub index:        u64,
+    pub reason:       String,
+}
+
+/// ID záznamov zmazaných od začiatku rozdielového exportu.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct DeletedIds {
+    pub employees:    Vec<u32>,
+    pub products:     Vec<u32>,
+}
+
+/// Výsledok premietnutia rozdielového exportu (`POST /admin/import-delta`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DeltaApplyReport {
+    /// Vložení alebo prepísaní zamestnanci
+    pub employees:    u64,
+    /// Vložené alebo prepísané produkty
+    pub products:     u64,
+    /// Zmazaní zamestnanci (ID, ktoré už neexistovali, sa nerátajú)
+    pub deleted_employees: u64,
+    /// Zmazané produkty
+    pub deleted_products: u64,
+}
+
+/// Zmena jedného poľa záznamu pri porovnaní importu s databázou.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field:        String,
+    /// Hodnota v databáze
+    pub old:          serde_json::Value,
+    /// Hodnota v importovanom súbore
+    pub new:          serde_json::Value,
+}
+
+/// Záznam, ktorý import zmení; `key` je čiarový kód produktu alebo e-mail zamestnanca.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecordUpdate {
+    pub id:           Option<u32>,
+    pub key:          String,
+    pub changes:      Vec<FieldChange>,
+}
+
+/// Rozdiel jednej tabuľky medzi importovaným súborom a databázou.
+///
+/// `added` sú záznamy zo súboru, ktoré v databáze nie sú, `deleted` záznamy
+/// z databázy, ktoré v súbore chýbajú.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TableDiff<T> {
+    pub added:        Vec<T>,
+    pub updated:      Vec<RecordUpdate>,
+    pub deleted:      Vec<T>,
+    /// Počet zhodných záznamov bez zmeny
+    pub unchanged:    u64,
+}
+
+impl<T> Default for TableDiff<T> {
+    fn default() -> Self {
+        Self { added: Vec::new(), updated: Vec::new(), deleted: Vec::new(), unchanged: 0 }
+    }
+}
+
+/// Náhľad zmien, ktoré by spôsobil import JSON súboru (`POST /import/diff`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ImportDiff {
+    pub employees:    TableDiff<Employee>,
+    pub products:     TableDiff<Product>,
+}
+
+/// Kontrola čiarového kódu jedného riadku importného súboru.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BarcodeRowCheck {
+    /// Číslo riadku súboru (pri CSV je hlavička riadok 1)
+    pub line:              u64,
+    pub bar_code:          Option<i64>,
+    /// Neplatný riadok, chýbajúci kód alebo nesprávna kontrolná číslica
+    pub error:             Option<String>,
+    /// Ostatné riadky súboru s rovnakým kódom
+    pub duplicate_lines:   Vec<u64>,
+    /// ID existujúcich produktov s rovnakým kódom
+    pub existing_products: Vec<u32>,
+}
+
+/// Výsledok kontroly čiarových kódov importného súboru (`POST /import/validate-barcodes`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BarcodeValidationReport {
+    /// Či súbor neobsahuje žiadnu chybu, duplicitu ani kolíziu
+    pub valid:        bool,
+    pub rows:         u64,
+    /// Riadky s chybou (`error`)
+    pub invalid:      u64,
+    /// Riadky s kódom, ktorý sa v súbore opakuje
+    pub duplicates:   u64,
+    /// Riadky s kódom existujúceho produktu
+    pub collisions:   u64,
+    pub lines:        Vec<BarcodeRowCheck>,
+}
+
+/// Riadok cenníka dodávateľa: nová nákupná cena produktu s daným čiarovým kódom.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PriceListRow {
+    #[serde(alias = "barcode")]
+    pub bar_code:     i64,
+    pub new_cost:     f64,
+}
+
+/// Dopad jedného riadku cenníka na nákupnú cenu a maržu produktu.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PriceListLine {
+    /// Číslo riadku súboru (pri CSV je hlavička riadok 1)
+    pub line:           u64,
+    pub bar_code:       Option<i64>,
+    /// Nájdený produkt (`None` pri nespárovanom alebo chybnom riadku)
+    pub product_id:     Option<u32>,
+    pub name:           Option<String>,
+    pub old_cost:       Option<f64>,
+    pub new_cost:       Option<f64>,
+    pub sell_price:     Option<f64>,
+    /// Marža v percentách predajnej ceny pri starej a novej nákupnej cene
+    pub old_margin:     Option<f64>,
+    pub new_margin:     Option<f64>,
+    /// Zmena marže v percentuálnych bodoch
+    pub margin_change:  Option<f64>,
+    /// Nová nákupná cena znižuje maržu
+    pub erodes_margin:  bool,
+    /// Porušenie politiky minimálnej marže pri novej nákupnej cene
+    pub below_minimum:  Option<String>,
+    /// Neplatný riadok, neznámy alebo nejednoznačný čiarový kód
+    pub error:          Option<String>,
+}
+
+/// Náhľad alebo výsledok importu cenníka dodávateľa (`POST /import/price-list/preview`, `/apply`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct PriceListReport {
+    pub rows:         u64,
+    /// Riadky spárované s produktom
+    pub matched:      u64,
+    /// Riadky s kódom, ktorý nemá žiadny produkt
+    pub unmatched:    u64,
+    /// Chybné riadky a kódy viacerých produktov
+    pub invalid:      u64,
+    /// Spárované riadky, ktoré znižujú maržu
+    pub eroding:      u64,
+    /// Počet zapísaných nákupných cien (pri náhľade 0)
+    pub applied:      u64,
+    pub lines:        Vec<PriceListLine>,
+}
+
+/// Telo odpovede `422 Unprocessable Entity` pri JSON tele, ktoré nezodpovedá očakávanej štruktúre.
+///
+/// `error` je `unknown_field` (pole, ktoré API nepozná – typicky preklep)
+/// alebo `invalid_value` (zlý typ či hodnota poľa, chýbajúce povinné pole).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JsonBodyError {
+    pub error:        String,
+    /// Cesta k poľu, napr. `items[0].quantity`; `None` pri chybe celého tela
+    pub field:        Option<String>,
+    /// Očakávaný typ alebo hodnoty
+    pub expected:     Option<String>,
+    /// Hodnota, ktorú klient poslal
+    pub received:     Option<serde_json::Value>,
+    /// Všetky neznáme polia tela (pri `unknown_field`)
+    #[serde(default)]
+    pub unknown_fields: Vec<String>,
+    pub message:      String,
+}
+
+/// Stav pripravenosti aplikácie (`/readyz`).
+///
+/// `phase` je práve prebiehajúca fáza štartu, `loaded_records` počet záznamov
+/// načítaných pri štartovacom importe z JSONu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReadinessStatus {
+    pub ready:          bool,
+    pub phase:          String,
+    pub loaded_records: u64,
+    /// Zapnutý režim údržby (`/readyz` vtedy vracia 503)
+    #[serde(default)]
+    pub maintenance:    bool,
+}
+
+/// Stav režimu údržby; zároveň telo odpovede `503` počas údržby.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaintenanceStatus {
+    pub maintenance: bool,
+    /// Odporúčaný počet sekúnd pred ďalším pokusom (`Retry-After`)
+    pub retry_after: u64,
+}
+
+/// Požiadavka na zapnutie alebo vypnutie režimu údržby.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MaintenanceRequest {
+    pub enabled:     bool,
+    /// Nový počet sekúnd pre `Retry-After` (bez hodnoty sa nemení)
+    pub retry_after: Option<u64>,
+}
+
+/// Stav režimu len na čítanie (`/admin/read-only`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReadOnlyStatus {
+    pub read_only: bool,
+}
+
+/// Požiadavka na zapnutie alebo vypnutie režimu len na čítanie.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReadOnlyRequest {
+    pub enabled: bool,
+}
+
+/// Stav rezervácie tovaru pre zákazníka.
+///
+/// `Expired` sa neukladá – aktívna rezervácia po čase `expires_at` sa tak
+/// zobrazuje a prestane blokovať tovar.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationStatus {
+    Active,
+    Fulfilled,
+    Cancelled,
+    Expired,
+}
+
+impl ReservationStatus {
+    /// Textová hodnota uložená v databáze.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReservationStatus::Active => "active",
+            ReservationStatus::Fulfilled => "fulfilled",
+            ReservationStatus::Cancelled => "cancelled",
+            ReservationStatus::Expired => "expired",
+        }
+    }
+
+    /// Prevedie hodnotu z databázy; neznámu hodnotu vráti ako `None`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "active" => Some(ReservationStatus::Active),
+            "fulfilled" => Some(ReservationStatus::Fulfilled),
+            "cancelled" => Some(ReservationStatus::Cancelled),
+            "expired" => Some(ReservationStatus::Expired),
+            _ => None,
+        }
+    }
+}
+
+/// Tovar odložený pre zákazníka (napr. po telefonickej objednávke).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Reservation {
+    pub id:           u32,
+    pub product_id:   u32,
+    /// Množstvo v jednotke produktu
+    pub quantity:     f64,
+    /// Označenie zákazníka (meno, telefón, číslo objednávky)
+    pub customer:     String,
+    pub created_at:   NaiveDateTime,
+    pub expires_at:   NaiveDateTime,
+    pub status:       ReservationStatus,
+    /// Objednávka, ktorou bola rezervácia vyzdvihnutá
+    pub order_id:     Option<u32>,
+}
+
+/// Požiadavka na rezerváciu tovaru.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReservationRequest {
+    pub quantity:     f64,
+    /// Jednotka množstva (bez hodnoty = jednotka produktu)
+    pub unit:         Option<Unit>,
+    pub customer:     String,
+    pub expires_at:   NaiveDateTime,
+}
+
+/// Spočítané množstvo produktu pri inventúre (`POST /products/reconcile`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StockCount {
+    /// ID produktu
+    pub id:      u32,
+    /// Spočítané množstvo v jednotke produktu
+    pub counted: f64,
+}
+
+/// Rozdiel medzi spočítaným a evidovaným množstvom produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StockDiscrepancy {
+    pub product_id:   u32,
+    pub name:         Option<String>,
+    pub unit:         Unit,
+    /// Množstvo v evidencii pred inventúrou
+    pub recorded:     f64,
+    pub counted:      f64,
+    /// `counted - recorded` (záporné = manko, kladné = prebytok)
+    pub difference:   f64,
+    /// Rozdiel v nákupných cenách; `None` bez nákupnej ceny
+    pub value:        Option<f64>,
+}
+
+/// Výsledok inventúry: rozdiely oproti evidencii a hodnota manka.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct StocktakeReport {
+    /// Stav zásob bol zosúladený so spočítaným množstvom
+    pub applied:          bool,
+    /// Počet spočítaných produktov
+    pub counted:          u32,
+    /// Počet produktov bez rozdielu
+    pub matched:          u32,
+    /// Produkty s rozdielom v poradí požiadavky
+    pub discrepancies:    Vec<StockDiscrepancy>,
+    /// Chýbajúce množstvo spolu (súčet mank)
+    pub shrinkage_units:  f64,
+    /// Hodnota manka v nákupných cenách (bez produktov bez nákupnej ceny)
+    pub shrinkage_value:  f64,
+    /// Počet produktov s mankom bez nákupnej ceny
+    pub uncosted_lines:   u32,
+}
+
+/// Požiadavka na inventúrnu úpravu stavu zásob produktu.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StockAdjustment {
+    /// Zmena množstva (kladná = naskladnenie, záporná = odpis)
+    pub delta: f64,
+    /// Jednotka zmeny (bez hodnoty = jednotka produktu)
+    pub unit:  Option<Unit>,
+}
+
+/// Trasa HTTP API v zozname `GET /routes`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// Cesta v zápise axum (`/products/{id}`), dostupná aj pod `/api/v1`
+    pub path:    String,
+    /// Metódy HTTP (`GET` obsluhuje aj `HEAD`)
+    pub methods: Vec<String>,
+}
+
+/// Začatá transakcia naprieč viacerými volaniami API (`POST /tx`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TxStarted {
+    /// Token pre hlavičku `X-Transaction-Id`
+    pub id: String,
+    /// Čas nečinnosti (sekundy), po ktorom sa transakcia vráti späť
+    pub timeout: u64,
+}
+
+/// Otvorená transakcia z `POST /tx` (`GET /admin/tx-sessions`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TxSessionInfo {
+    /// Token transakcie
+    pub id:              String,
+    pub started_at:      NaiveDateTime,
+    /// Sekundy od posledného volania v transakcii
+    pub idle_secs:       f64,
+    /// Sekundy, po ktorých sa transakcia bez ďalšieho volania vráti späť
+    pub expires_in_secs: f64,
+}
+
+/// Druh operácie v dávke `POST /batch`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOp {
+    CreateProduct,
+    UpdateProduct,
+    DeleteProduct,
+    /// Zmena zásob ako `POST /products/{id}/adjust-stock`
+    AdjustStock,
+    CreateEmployee,
+    UpdateEmployee,
+    DeleteEmployee,
+}
+
+/// Operácia dávky.
+///
+/// `id` a hodnoty v `body` môžu byť odkazom na výsledok skoršej operácie
+/// v tvare `"$<index>.<pole>"` (napr. `"$0.id"`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchOperation {
+    pub op:           BatchOp,
+    /// ID upravovaného záznamu (pri `create_*` chýba)
+    pub id:           Option<serde_json::Value>,
+    /// Telo ako pri zodpovedajúcom volaní REST API
+    pub body:         Option<serde_json::Value>,
+}
+
+/// Dávka operácií vykonaná v jednej transakcii.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchRequest {
+    pub operations:   Vec<BatchOperation>,
+}
+
+/// Výsledok jednej operácie dávky.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchStepResult {
+    pub index:        usize,
+    pub op:           BatchOp,
+    /// ID vytvoreného alebo zmeneného záznamu
+    pub id:           u32,
+    /// Záznam po zmene (pri mazaní chýba)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result:       Option<serde_json::Value>,
+}
+
+/// Chyba dávky; žiadna z jej operácií sa nezapísala.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatchError {
+    /// Poradie operácie, ktorá zlyhala (`None` = chyba celej dávky)
+    pub index:        Option<usize>,
+    pub op:           Option<BatchOp>,
+    pub error:        String,
+}
+
+/// Záznam zmenený na oboch stranách od poslednej synchronizácie; miestna verzia sa neprepíše.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncConflict {
+    /// Kľúč záznamu (čiarový kód produktu alebo email zamestnanca)
+    pub key: String,
+    /// Čas poslednej miestnej zmeny
+    pub local_updated_at: Option<NaiveDateTime>,
+    /// Čas zmeny vo vzdialenej inštancii
+    pub remote_updated_at: Option<NaiveDateTime>,
+}
+
+/// Výsledok synchronizácie jednej entity z inej inštancie.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SyncCounts {
+    pub created: u32,
+    pub updated: u32,
+    /// Záznamy, ktoré sa nedali prevziať (napr. zamestnanec bez emailu, sada, objednávka neznámeho produktu)
+    pub skipped: u32,
+    /// Záznamy zmenené aj miestne – nechajú sa bez zmeny
+    pub conflicts: Vec<SyncConflict>,
+    /// Najneskorší `updated_at` prevzatý zo vzdialenej inštancie (začiatok ďalšej synchronizácie)
+    pub high_water_mark: Option<NaiveDateTime>,
+}
+
+/// Výsledok synchronizácie z inej inštancie (`store_manager sync`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SyncReport {
+    /// Adresa vzdialenej inštancie
+    pub source: String,
+    pub employees: SyncCounts,
+    pub products: SyncCounts,
+    pub orders: SyncCounts,
+}
+
+/// Záznam v logu synchronizácií (tabuľka `sync_log`), jeden za entitu a beh.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncLogEntry {
+    pub id: u32,
+    pub source: String,
+    /// `employees`, `products` alebo `orders`
+    pub entity: String,
+    pub started_at: NaiveDateTime,
+    pub finished_at: NaiveDateTime,
+    #[serde(flatten)]
+    pub counts: SyncCounts,
+}
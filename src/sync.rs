@@ -0,0 +1,157 @@
+//! Synchronizácia z inej inštancie store_manager (`store_manager sync --from <url>`).
+//!
+//! Zo vzdialenej inštancie sa cez `GET /employees`, `GET /products` a `GET /orders`
+//! stiahnu záznamy zmenené od poslednej synchronizácie (filter `updated_after`,
+//! stránky pokračujú kurzorom `updated_at` + `after_id`). Prevzaté záznamy majú
+//! vyplnený zdroj (`source_instance`), takže sa ich ID nebijú s miestnymi.
+//! Každá entita sa zapíše do `sync_log` s počtami a najvyšším prevzatým
+//! `updated_at`, od ktorého pokračuje ďalší beh.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::de::DeserializeOwned;
+
+use crate::auth::API_KEY_HEADER;
+use crate::client::ApiError;
+use crate::db::StoreDB;
+use crate::list_shape;
+use crate::structs::{Employee, Order, Product, SyncCounts, SyncReport};
+
+/// Počet záznamov na jednu stránku zo vzdialenej inštancie.
+const PAGE_SIZE: u32 = 100;
+
+/// Entita, ktorá sa dá stiahnuť zo vzdialenej inštancie.
+trait Synced: DeserializeOwned {
+    /// Názov entity v `sync_log`
+    const ENTITY: &'static str;
+    /// Cesta zoznamu vo vzdialenom API
+    const PATH: &'static str;
+
+    /// Kurzor za týmto záznamom (`updated_at`, ID).
+    fn cursor(&self) -> (Option<NaiveDateTime>, Option<u32>);
+
+    /// Prevezme stránku záznamov do miestnej databázy.
+    async fn store(db: &StoreDB, source: &str, page: &[Self], counts: &mut SyncCounts) -> Result<()>;
+}
+
+impl Synced for Employee {
+    const ENTITY: &'static str = "employees";
+    // aj vyradení zamestnanci, ktorých predvolený zoznam vynecháva
+    const PATH: &'static str = "/employees?include_removed=true";
+
+    fn cursor(&self) -> (Option<NaiveDateTime>, Option<u32>) {
+        (self.updated_at, self.id)
+    }
+
+    async fn store(db: &StoreDB, source: &str, page: &[Self], counts: &mut SyncCounts) -> Result<()> {
+        db.sync_employees(source, page, counts).await
+    }
+}
+
+impl Synced for Product {
+    const ENTITY: &'static str = "products";
+    const PATH: &'static str = "/products";
+
+    fn cursor(&self) -> (Option<NaiveDateTime>, Option<u32>) {
+        (self.updated_at, self.id)
+    }
+
+    async fn store(db: &StoreDB, source: &str, page: &[Self], counts: &mut SyncCounts) -> Result<()> {
+        db.sync_products(source, page, counts).await
+    }
+}
+
+impl Synced for Order {
+    const ENTITY: &'static str = "orders";
+    const PATH: &'static str = "/orders";
+
+    fn cursor(&self) -> (Option<NaiveDateTime>, Option<u32>) {
+        (self.updated_at, self.id)
+    }
+
+    async fn store(db: &StoreDB, source: &str, page: &[Self], counts: &mut SyncCounts) -> Result<()> {
+        db.sync_orders(source, page, counts).await
+    }
+}
+
+/// Stiahne zmeny zo vzdialenej inštancie do miestnej databázy.
+///
+/// Zamestnanci a produkty sa prevezmú pred objednávkami, aby sa dali priradiť
+/// ich položky. Záznamy zmenené od poslednej synchronizácie na oboch stranách
+/// sa neprepíšu – sú v `conflicts` výsledku aj v `sync_log`.
+///
+/// # Arguments
+/// * `db` – miestna databáza
+/// * `url` – adresa vzdialenej inštancie, napr. `http://shop2:8000`
+/// * `api_key` – API kľúč vzdialenej inštancie (hlavička `x-api-key`)
+///
+/// # Returns
+/// Počty prevzatých záznamov a konflikty po entitách
+///
+/// # Errors
+/// Ak zlyhá spojenie, vzdialená inštancia vráti chybu alebo zlyhá zápis do databázy;
+/// entity prevzaté pred chybou zostanú zapísané v `sync_log`
+pub async fn pull(db: &StoreDB, url: &str, api_key: Option<&str>) -> Result<SyncReport> {
+    let remote = Remote { http: reqwest::Client::new(), base_url: url.trim_end_matches('/').to_string(), api_key };
+    Ok(SyncReport {
+        employees: pull_entity::<Employee>(db, &remote).await?,
+        products: pull_entity::<Product>(db, &remote).await?,
+        orders: pull_entity::<Order>(db, &remote).await?,
+        source: remote.base_url,
+    })
+}
+
+/// Stiahne všetky zmeny jednej entity a zapíše ich do `sync_log`.
+async fn pull_entity<T: Synced>(db: &StoreDB, remote: &Remote<'_>) -> Result<SyncCounts> {
+    let started_at = Local::now().naive_local();
+    let since = db.sync_high_water_mark(&remote.base_url, T::ENTITY).await?;
+    let mut counts = SyncCounts { high_water_mark: since, ..SyncCounts::default() };
+
+    // prvá synchronizácia ide tiež podľa `updated_at`, aby kurzor fungoval od prvej stránky
+    let mut cursor = (since.unwrap_or(DateTime::UNIX_EPOCH.naive_utc()), None);
+    loop {
+        let page: Vec<T> = remote.page(T::PATH, cursor).await?;
+        let Some(last) = page.last() else { break };
+        cursor = match last.cursor() {
+            (Some(updated_at), id) => (updated_at, id),
+            (None, _) => bail!("{} nevracia čas zmeny záznamov (updated_at) – treba novšiu verziu", remote.base_url),
+        };
+        T::store(db, &remote.base_url, &page, &mut counts).await?;
+    }
+
+    db.record_sync(&remote.base_url, T::ENTITY, started_at, &counts).await?;
+    Ok(counts)
+}
+
+/// Vzdialená inštancia store_manager.
+struct Remote<'a> {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: Option<&'a str>,
+}
+
+impl Remote<'_> {
+    /// Stiahne stránku záznamov zmenených po kurzore.
+    async fn page<T: DeserializeOwned>(&self, path: &str, (after, after_id): (NaiveDateTime, Option<u32>)) -> Result<Vec<T>> {
+        let mut request = self
+            .http
+            .get(format!("{}{path}", self.base_url))
+            .header(reqwest::header::ACCEPT, list_shape::ACCEPT_ARRAY)
+            .query(&[("updated_after", after.format("%Y-%m-%dT%H:%M:%S%.3f").to_string())])
+            .query(&[("limit", PAGE_SIZE)]);
+        if let Some(id) = after_id {
+            request = request.query(&[("after_id", id)]);
+        }
+        if let Some(key) = self.api_key {
+            request = request.header(API_KEY_HEADER, key);
+        }
+
+        let response = request.send().await.with_context(|| format!("nepodarilo sa spojiť s {}", self.base_url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default().trim().to_string();
+            return Err(ApiError { status: status.as_u16(), message }.into());
+        }
+        response.json().await.with_context(|| format!("neplatná odpoveď z {}{path}", self.base_url))
+    }
+}
@@ -0,0 +1,179 @@
+use std::env;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    error::OTelSdkResult,
+    trace::{SdkTracerProvider, SpanData, SpanExporter},
+    Resource,
+};
+use tracing::{field::Empty, Instrument};
+use tracing_subscriber::{layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, Layer};
+
+use crate::slow_query;
+
+/// Názov služby v tracoch, ak nie je nastavený `OTEL_SERVICE_NAME`.
+pub const SERVICE_NAME: &str = "store_manager";
+
+/// Zistí, či je export tracov zapnutý štandardnými premennými `OTEL_*`.
+///
+/// Export je zapnutý, ak je nastavený endpoint (`OTEL_EXPORTER_OTLP_ENDPOINT`
+/// alebo `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`) alebo `OTEL_TRACES_EXPORTER=otlp`.
+/// Vypína ho `OTEL_SDK_DISABLED=true` a `OTEL_TRACES_EXPORTER` s inou hodnotou.
+///
+/// # Returns
+/// `true` ak sa majú traces exportovať
+pub fn enabled_from_env() -> bool {
+    let var = |key: &str| env::var(key).ok().map(|v| v.trim().to_ascii_lowercase()).filter(|v| !v.is_empty());
+
+    if var("OTEL_SDK_DISABLED").as_deref() == Some("true") {
+        return false;
+    }
+    match var("OTEL_TRACES_EXPORTER").as_deref() {
+        Some("otlp") => true,
+        Some(_) => false,
+        None => var("OTEL_EXPORTER_OTLP_ENDPOINT").is_some() || var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT").is_some(),
+    }
+}
+
+/// Vytvorí poskytovateľa tracov s OTLP exportérom (HTTP/protobuf).
+///
+/// Endpoint, hlavičky a timeout sa čítajú z premenných `OTEL_EXPORTER_OTLP_*`,
+/// atribúty služby z `OTEL_SERVICE_NAME` a `OTEL_RESOURCE_ATTRIBUTES`. Spany sa
+/// posielajú v dávkach z vlastného vlákna, nedostupný endpoint preto obsluhu
+/// požiadaviek nespomalí; chyby exportu sa len zalogujú.
+///
+/// Volať mimo async kontextu (blokujúci HTTP klient si vytvára vlastný runtime).
+///
+/// # Arguments
+/// * `endpoint` – endpoint namiesto hodnoty z prostredia
+///
+/// # Returns
+/// Poskytovateľ tracov
+///
+/// # Errors
+/// Ak sa exportér nepodarí vytvoriť (napr. neplatný endpoint)
+pub fn otlp_provider(endpoint: Option<&str>) -> anyhow::Result<SdkTracerProvider> {
+    let mut builder = opentelemetry_otlp::SpanExporter::builder().with_http();
+    if let Some(endpoint) = endpoint {
+        builder = builder.with_endpoint(endpoint);
+    }
+    let exporter = LoggingExporter(builder.build()?);
+
+    let mut resource = Resource::builder();
+    let service_in_attributes = env::var("OTEL_RESOURCE_ATTRIBUTES").is_ok_and(|v| v.contains("service.name="));
+    if env::var("OTEL_SERVICE_NAME").is_err() && !service_in_attributes {
+        resource = resource.with_service_name(SERVICE_NAME);
+    }
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build())
+}
+
+/// Vytvorí `tracing` vrstvu, ktorá posiela spany poskytovateľovi tracov.
+///
+/// # Arguments
+/// * `provider` – poskytovateľ tracov
+///
+/// # Returns
+/// Vrstva pre `tracing_subscriber`
+pub fn layer<S>(provider: &SdkTracerProvider) -> impl Layer<S>
+where
+    S: tracing::Subscriber + for<'span> LookupSpan<'span>,
+{
+    tracing_opentelemetry::layer().with_tracer(provider.tracer(SERVICE_NAME))
+}
+
+/// Nainštaluje `tracing` subscriber s vrstvou pre pomalé SQL príkazy (`slow_query`)
+/// a zapne export tracov, ak je nakonfigurovaný premennými `OTEL_*`.
+///
+/// Chyba pri vytváraní exportéra aplikáciu nezastaví, traces sa len neexportujú.
+///
+/// # Returns
+/// Poskytovateľ tracov, ktorý treba pri ukončení zavrieť (`shutdown`),
+/// alebo `None`, ak je export vypnutý
+pub fn init_from_env() -> Option<SdkTracerProvider> {
+    let provider = enabled_from_env()
+        .then(|| otlp_provider(None))
+        .and_then(|result| result.inspect_err(|e| eprintln!("Nepodarilo sa zapnúť export tracov: {e}")).ok());
+
+    tracing_subscriber::registry()
+        .with(provider.as_ref().map(layer))
+        .with(slow_query::layer())
+        .init();
+    if provider.is_some() {
+        println!("Export tracov cez OTLP je zapnutý.");
+    }
+    provider
+}
+
+/// Exportér, ktorý zaloguje neúspešný export a inak sa správa ako vnútorný exportér.
+#[derive(Debug)]
+struct LoggingExporter<E>(E);
+
+impl<E: SpanExporter> SpanExporter for LoggingExporter<E> {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let count = batch.len();
+        let result = self.0.export(batch).await;
+        if let Err(e) = &result {
+            eprintln!("Nepodarilo sa exportovať {count} spanov: {e}");
+        }
+        result
+    }
+
+    fn shutdown_with_timeout(&mut self, timeout: std::time::Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn force_flush(&mut self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.0.set_resource(resource);
+    }
+}
+
+/// Middleware, ktorý z každej HTTP požiadavky urobí span (koreň tracu).
+///
+/// Zaznamená sa len metóda, šablóna cesty (`/products/{id}`) a stavový kód;
+/// parametre dotazu ani telo nie, keďže môžu obsahovať osobné údaje.
+///
+/// # Arguments
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď handlera
+pub async fn trace_requests(req: Request, next: Next) -> Response {
+    let method = req.method().clone();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or("<neznáma cesta>", |p| p.as_str())
+        .to_string();
+
+    let span = tracing::info_span!(
+        "http_request",
+        otel.name = %format!("{method} {route}"),
+        otel.kind = "server",
+        otel.status_code = Empty,
+        http.request.method = %method,
+        http.route = %route,
+        http.response.status_code = Empty,
+    );
+
+    let res = next.run(req).instrument(span.clone()).await;
+    span.record("http.response.status_code", u64::from(res.status().as_u16()));
+    if res.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+    res
+}
@@ -0,0 +1,278 @@
+//! Pomôcky pre integračné testy.
+//!
+//! Spustí celú aplikáciu nad dočasnou databázou na náhodnom porte, takže
+//! viacero testov môže bežať paralelne.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::config::Config;
+use crate::db::StoreDB;
+use crate::health::Readiness;
+use crate::server::Server;
+use crate::stores;
+
+/// Počítadlo pre jedinečné názvy dočasných databáz.
+static DB_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// Bežiaca inštancia aplikácie pre testy.
+pub struct TestApp {
+    pub addr: SocketAddr,
+    pub db: StoreDB,
+    db_path: PathBuf,
+}
+
+/// Odpoveď HTTP servera.
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl TestApp {
+    /// Spustí aplikáciu s predvolenou konfiguráciou.
+    ///
+    /// # Returns
+    /// Bežiaca aplikácia
+    pub async fn spawn() -> Self {
+        Self::spawn_with_config(Config::default()).await
+    }
+
+    /// Spustí aplikáciu s danou konfiguráciou nad prázdnou dočasnou databázou.
+    ///
+    /// Ďalšie obchody zo `stores` sa otvoria zo zadaných ciest.
+    ///
+    /// # Arguments
+    /// * `config` – konfigurácia aplikácie
+    ///
+    /// # Returns
+    /// Bežiaca aplikácia
+    pub async fn spawn_with_config(config: Config) -> Self {
+        Self::spawn_with_readiness(config, Readiness::ready()).await
+    }
+
+    /// Spustí aplikáciu so zadaným stavom pripravenosti (test štartu a `/readyz`).
+    ///
+    /// # Arguments
+    /// * `config` – konfigurácia aplikácie
+    /// * `readiness` – zdieľaný stav pripravenosti
+    ///
+    /// # Returns
+    /// Bežiaca aplikácia
+    pub async fn spawn_with_readiness(config: Config, readiness: Readiness) -> Self {
+        let db_path = std::env::temp_dir().join(format!(
+            "store_manager_test_{}_{}.db",
+            std::process::id(),
+            DB_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_file(&db_path);
+
+        let db = StoreDB::open(db_path.to_str().expect("cesta k dočasnej databáze"))
+            .await
+            .expect("otvorenie dočasnej databázy");
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("otvorenie socketu");
+        let addr = listener.local_addr().expect("adresa socketu");
+
+        let stores = stores::open_all(&config).await.expect("otvorenie databáz obchodov");
+        let server = Server::new(db.clone(), config).with_readiness(readiness).with_stores(stores);
+        tokio::spawn(async move {
+            if let Err(e) = server.serve(listener).await {
+                eprintln!("Server error: {}", e);
+            }
+        });
+
+        Self { addr, db, db_path }
+    }
+
+    /// Cesta k dočasnej databáze (priamy zápis mimo API, napr. poškodené dáta).
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    /// Pošle HTTP požiadavku na aplikáciu.
+    ///
+    /// # Arguments
+    /// * `method` – HTTP metóda
+    /// * `path` – cesta vrátane parametrov dotazu
+    /// * `body` – voliteľné JSON telo
+    ///
+    /// # Returns
+    /// Odpoveď servera
+    pub async fn request(&self, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+        self.request_with_headers(method, path, body, &[]).await
+    }
+
+    /// Pošle HTTP požiadavku s doplnkovými hlavičkami.
+    ///
+    /// # Arguments
+    /// * `method` – HTTP metóda
+    /// * `path` – cesta vrátane parametrov dotazu
+    /// * `body` – voliteľné JSON telo
+    /// * `headers` – doplnkové hlavičky (názov, hodnota); `Content-Type` nahradí predvolený JSON
+    ///
+    /// # Returns
+    /// Odpoveď servera
+    pub async fn request_with_headers(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+        headers: &[(&str, &str)],
+    ) -> TestResponse {
+        let mut stream = TcpStream::connect(self.addr).await.expect("pripojenie k serveru");
+
+        let body = body.unwrap_or("");
+        let mut extra: String = headers.iter().map(|(k, v)| format!("{k}: {v}\r\n")).collect();
+        if !headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("content-type")) {
+            extra.insert_str(0, "Content-Type: application/json\r\n");
+        }
+        let request = format!(
+            "{method} {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n{extra}Content-Length: {}\r\n\r\n{body}",
+            self.addr,
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.expect("odoslanie požiadavky");
+
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).await.expect("čítanie odpovede");
+        TestResponse::parse(&raw)
+    }
+
+    /// Pošle `GET` požiadavku.
+    pub async fn get(&self, path: &str) -> TestResponse {
+        self.request("GET", path, None).await
+    }
+
+    /// Pošle `POST` požiadavku s JSON telom.
+    pub async fn post(&self, path: &str, body: &str) -> TestResponse {
+        self.request("POST", path, Some(body)).await
+    }
+
+    /// Pošle `PUT` požiadavku s JSON telom.
+    pub async fn put(&self, path: &str, body: &str) -> TestResponse {
+        self.request("PUT", path, Some(body)).await
+    }
+
+    /// Pošle `DELETE` požiadavku.
+    pub async fn delete(&self, path: &str) -> TestResponse {
+        self.request("DELETE", path, None).await
+    }
+
+    /// Založí aktívny produkt cez API a overí, že sa vytvoril.
+    ///
+    /// # Arguments
+    /// * `name` – názov produktu
+    /// * `category` – kategória
+    /// * `quantity` – počiatočné množstvo na sklade
+    /// * `cost_price` – nákupná cena
+    /// * `sell_price` – predajná cena
+    pub async fn add_product(&self, name: &str, category: &str, quantity: f64, cost_price: f64, sell_price: f64) {
+        let body = format!(
+            r#"{{"name":"{name}","category":"{category}","quantity":{quantity},"status":true,"bar_code":1,"cost_price":{cost_price},"sell_price":{sell_price}}}"#
+        );
+        let res = self.post("/products", &body).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+
+    /// Predá produkt jednou objednávkou a overí, že sa prijala.
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `quantity` – predané množstvo
+    pub async fn sell(&self, product_id: u32, quantity: f64) {
+        self.sell_at(product_id, quantity, None).await;
+    }
+
+    /// Predá produkt objednávkou s voliteľným časom vytvorenia (spätné predaje).
+    ///
+    /// # Arguments
+    /// * `product_id` – ID produktu
+    /// * `quantity` – predané množstvo
+    /// * `created_at` – čas objednávky, napr. `2024-06-01T10:00:00`; `None` = teraz
+    pub async fn sell_at(&self, product_id: u32, quantity: f64, created_at: Option<&str>) {
+        let created_at = created_at.map(|c| format!(r#""created_at":"{c}","#)).unwrap_or_default();
+        let body = format!(r#"{{{created_at}"items":[{{"product_id":{product_id},"quantity":{quantity}}}]}}"#);
+        let res = self.post("/orders", &body).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+}
+
+impl Drop for TestApp {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.db_path);
+    }
+}
+
+impl TestResponse {
+    /// Rozparsuje surovú HTTP/1.1 odpoveď (vrátane `chunked` tela).
+    fn parse(raw: &[u8]) -> Self {
+        let split = raw
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .expect("hlavička odpovede");
+        let head = String::from_utf8_lossy(&raw[..split]);
+        let mut lines = head.lines();
+
+        let status = lines
+            .next()
+            .and_then(|l| l.split_whitespace().nth(1))
+            .and_then(|s| s.parse().ok())
+            .expect("stavový riadok");
+
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|l| l.split_once(':'))
+            .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+            .collect();
+
+        let mut body = raw[split + 4..].to_vec();
+        let chunked = headers
+            .iter()
+            .any(|(k, v)| k == "transfer-encoding" && v.eq_ignore_ascii_case("chunked"));
+        if chunked {
+            body = Self::dechunk(&body);
+        }
+
+        Self { status, headers, body }
+    }
+
+    /// Zloží telo prenesené kódovaním `chunked`.
+    fn dechunk(mut data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        while let Some(end) = data.windows(2).position(|w| w == b"\r\n") {
+            let size = usize::from_str_radix(String::from_utf8_lossy(&data[..end]).trim(), 16).unwrap_or(0);
+            if size == 0 {
+                break;
+            }
+            let start = end + 2;
+            out.extend_from_slice(&data[start..start + size]);
+            data = &data[start + size + 2..];
+        }
+        out
+    }
+
+    /// Vráti hodnotu hlavičky (názov bez ohľadu na veľkosť písmen).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Vráti telo odpovede ako text.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    /// Deserializuje telo odpovede z JSONu.
+    pub fn json<T: DeserializeOwned>(&self) -> T {
+        serde_json::from_slice(&self.body).expect("platný JSON v tele odpovede")
+    }
+}
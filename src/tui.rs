@@ -0,0 +1,468 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Local;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Tabs},
+    Frame,
+};
+
+use crate::{
+    config::Config,
+    db::StoreDB,
+    error::StoreError,
+    structs::{CategoryMargin, DailyReport, Employee, Product},
+};
+
+/// Počet riadkov na jednej strane tabuľky.
+pub const PAGE_SIZE: u32 = 20;
+
+/// Záložka konzoly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Products,
+    Employees,
+    Dashboard,
+}
+
+impl Tab {
+    const ALL: [Tab; 3] = [Tab::Products, Tab::Employees, Tab::Dashboard];
+
+    fn title(self) -> &'static str {
+        match self {
+            Tab::Products => "Produkty",
+            Tab::Employees => "Zamestnanci",
+            Tab::Dashboard => "Prehľad",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+}
+
+/// Čo práve robí klávesnica.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    /// Písanie do vyhľadávania
+    Search,
+    /// Zadávanie nového množstva vybraného produktu
+    Edit(String),
+    /// Čaká sa na potvrdenie vymazania vybraného produktu
+    ConfirmDelete,
+}
+
+/// Súhrn pre záložku Prehľad.
+struct Dashboard {
+    products: u64,
+    active_products: u64,
+    employees: u64,
+    low_stock: usize,
+    today: DailyReport,
+    margins: Vec<CategoryMargin>,
+}
+
+/// Stav administrátorskej konzoly v termináli.
+///
+/// Údaje sa čítajú a menia cez `StoreDB` (tie isté filtre a validácia ako
+/// v HTTP API), takže konzola nepotrebuje bežiaci server.
+pub struct App {
+    db: StoreDB,
+    config: Arc<Config>,
+    tab: Tab,
+    mode: Mode,
+    search: String,
+    page: u32,
+    total: u64,
+    products: Vec<Product>,
+    employees: Vec<Employee>,
+    dashboard: Option<Dashboard>,
+    table: TableState,
+    status: String,
+    quit: bool,
+}
+
+/// Spustí konzolu nad databázou z konfigurácie a obsluhuje ju do stlačenia `q`.
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie (`db_path`, hranice zásob)
+///
+/// # Errors
+/// Ak sa nepodarí otvoriť databázu alebo pracovať s terminálom
+pub async fn run(config: Config) -> Result<()> {
+    let db = StoreDB::new(&config).await?;
+    let mut app = App::new(db, Arc::new(config));
+    app.refresh().await?;
+
+    let mut terminal = ratatui::init();
+    let result = async {
+        while !app.should_quit() {
+            terminal.draw(|frame| app.draw(frame))?;
+            // čítanie z terminálu blokuje, preto mimo vlákien runtime
+            if let Event::Key(key) = tokio::task::spawn_blocking(event::read).await?? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+    .await;
+    ratatui::restore();
+    result
+}
+
+impl App {
+    /// Vytvorí konzolu so záložkou produktov.
+    ///
+    /// # Arguments
+    /// * `db` – databáza
+    /// * `config` – konfigurácia aplikácie
+    pub fn new(db: StoreDB, config: Arc<Config>) -> Self {
+        Self {
+            db,
+            config,
+            tab: Tab::Products,
+            mode: Mode::Normal,
+            search: String::new(),
+            page: 0,
+            total: 0,
+            products: Vec::new(),
+            employees: Vec::new(),
+            dashboard: None,
+            table: TableState::default().with_selected(Some(0)),
+            status: String::new(),
+            quit: false,
+        }
+    }
+
+    /// Aktuálna záložka.
+    pub fn tab(&self) -> Tab {
+        self.tab
+    }
+
+    /// Či používateľ konzolu ukončil.
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Načíta údaje aktuálnej záložky (strana a vyhľadávanie sa zachovajú).
+    ///
+    /// # Errors
+    /// Ak zlyhá čítanie z databázy
+    pub async fn refresh(&mut self) -> Result<()> {
+        let offset = self.page * PAGE_SIZE;
+        match self.tab {
+            Tab::Products => {
+                let filter = Product { name: Some(self.search.clone()), ..Product::default() };
+                self.total = self.db.count_products(filter.clone(), false).await?;
+                self.products = self.db.get_products_page(filter, false, PAGE_SIZE, offset).await?;
+                self.clamp_selection(self.products.len());
+            }
+            Tab::Employees => {
+                let filter = Employee { surname: Some(self.search.clone()), ..Employee::default() };
+                self.total = self.db.count_employees(filter.clone()).await?;
+                self.employees = self.db.get_employees_page(filter, PAGE_SIZE, offset).await?;
+                self.clamp_selection(self.employees.len());
+            }
+            Tab::Dashboard => {
+                let active = Product { status: Some(true), ..Product::default() };
+                self.dashboard = Some(Dashboard {
+                    products: self.db.count_products(Product::default(), false).await?,
+                    active_products: self.db.count_products(active, false).await?,
+                    employees: self.db.count_employees(Employee::default()).await?,
+                    low_stock: self
+                        .db
+                        .reorder_suggestions(self.config.low_stock_threshold, self.config.reorder_target)
+                        .await?
+                        .len(),
+                    today: self.db.daily_report(Local::now().date_naive()).await?,
+                    margins: self.db.category_margins().await?,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Spracuje stlačenú klávesu.
+    ///
+    /// Doménové chyby (napr. odpis viac, než je na sklade) sa zobrazia
+    /// v stavovom riadku, konzola beží ďalej.
+    ///
+    /// # Errors
+    /// Ak zlyhá databáza
+    pub async fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        let result = match self.mode.clone() {
+            Mode::Normal => self.normal_key(key.code).await,
+            Mode::Search => self.search_key(key.code).await,
+            Mode::Edit(input) => self.edit_key(key.code, input).await,
+            Mode::ConfirmDelete => self.confirm_delete_key(key.code).await,
+        };
+        match result {
+            Err(e) if e.downcast_ref::<StoreError>().is_some() => {
+                self.status = e.to_string();
+                Ok(())
+            }
+            other => other,
+        }
+    }
+
+    async fn normal_key(&mut self, code: KeyCode) -> Result<()> {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Tab => self.switch_tab(1).await?,
+            KeyCode::BackTab => self.switch_tab(Tab::ALL.len() - 1).await?,
+            KeyCode::Up => self.table.select_previous(),
+            KeyCode::Down => self.table.select_next(),
+            KeyCode::PageDown | KeyCode::Right if (self.page as u64 + 1) * (PAGE_SIZE as u64) < self.total => {
+                self.page += 1;
+                self.table.select(Some(0));
+                self.refresh().await?;
+            }
+            KeyCode::PageUp | KeyCode::Left if self.page > 0 => {
+                self.page -= 1;
+                self.table.select(Some(0));
+                self.refresh().await?;
+            }
+            KeyCode::Char('/') if self.tab != Tab::Dashboard => self.mode = Mode::Search,
+            KeyCode::Char('r') => {
+                self.status.clear();
+                self.refresh().await?;
+            }
+            KeyCode::Enter if self.selected_product().is_some() => self.mode = Mode::Edit(String::new()),
+            KeyCode::Char('d') if self.selected_product().is_some() => self.mode = Mode::ConfirmDelete,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn search_key(&mut self, code: KeyCode) -> Result<()> {
+        match code {
+            KeyCode::Char(c) => self.search.push(c),
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                self.page = 0;
+                self.table.select(Some(0));
+                self.refresh().await?;
+            }
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.search.clear();
+                self.page = 0;
+                self.refresh().await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Zadanie množstva: číslo je nový stav, `+n`/`-n` je zmena o `n`.
+    async fn edit_key(&mut self, code: KeyCode, mut input: String) -> Result<()> {
+        match code {
+            KeyCode::Char(c) if c.is_ascii_digit() || "+-.,".contains(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                return Ok(());
+            }
+            KeyCode::Enter => {
+                self.mode = Mode::Normal;
+                let Some((id, current)) = self.selected_product().map(|p| (p.id, p.quantity.unwrap_or(0.0))) else {
+                    return Ok(());
+                };
+                let Ok(value) = input.replace(',', ".").parse::<f64>() else {
+                    self.status = format!("neplatné množstvo {input:?}");
+                    return Ok(());
+                };
+                let delta = if input.starts_with(['+', '-']) { value } else { value - current };
+                let product = self.db.adjust_stock(id.unwrap_or_default(), delta, None).await?;
+                self.status = format!(
+                    "{}: stav zásob {}",
+                    product.name.as_deref().unwrap_or_default(),
+                    product.quantity.unwrap_or_default()
+                );
+                self.refresh().await?;
+                return Ok(());
+            }
+            _ => {}
+        }
+        self.mode = Mode::Edit(input);
+        Ok(())
+    }
+
+    async fn confirm_delete_key(&mut self, code: KeyCode) -> Result<()> {
+        self.mode = Mode::Normal;
+        if code != KeyCode::Char('y') {
+            self.status = "Mazanie zrušené".into();
+            return Ok(());
+        }
+        let Some((id, name)) = self.selected_product().map(|p| (p.id.unwrap_or_default(), p.name.clone())) else {
+            return Ok(());
+        };
+        if self.db.delete_product(id).await? {
+            self.status = format!("Produkt {} vymazaný", name.unwrap_or_default());
+        }
+        self.refresh().await
+    }
+
+    async fn switch_tab(&mut self, step: usize) -> Result<()> {
+        self.tab = Tab::ALL[(self.tab.index() + step) % Tab::ALL.len()];
+        self.search.clear();
+        self.page = 0;
+        self.table.select(Some(0));
+        self.status.clear();
+        self.refresh().await
+    }
+
+    fn selected_product(&self) -> Option<&Product> {
+        if self.tab != Tab::Products {
+            return None;
+        }
+        self.products.get(self.table.selected()?)
+    }
+
+    fn clamp_selection(&mut self, rows: usize) {
+        let selected = self.table.selected().unwrap_or(0).min(rows.saturating_sub(1));
+        self.table.select(Some(selected));
+    }
+
+    /// Vykreslí konzolu.
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [tabs, body, input, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let titles = Tab::ALL.iter().map(|t| t.title());
+        frame.render_widget(
+            Tabs::new(titles)
+                .select(self.tab.index())
+                .block(Block::default().borders(Borders::ALL).title("Správa skladu"))
+                .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)),
+            tabs,
+        );
+
+        let pages = self.total.div_ceil(PAGE_SIZE as u64).max(1);
+        let title = format!("{} – strana {}/{pages}, spolu {}", self.tab.title(), self.page + 1, self.total);
+        let header_style = Style::default().add_modifier(Modifier::BOLD);
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+        match self.tab {
+            Tab::Products => {
+                let threshold = self.config.low_stock_threshold;
+                let rows = self.products.iter().map(|p| {
+                    Row::new(vec![
+                        p.id.map(|id| id.to_string()).unwrap_or_default(),
+                        p.name.clone().unwrap_or_default(),
+                        p.category.clone().unwrap_or_default(),
+                        p.quantity.map(|q| q.to_string()).unwrap_or_default(),
+                        p.unit.unwrap_or_default().as_str().to_string(),
+                        p.sell_price.map(|c| format!("{c:.2}")).unwrap_or_default(),
+                        format!("{:?}", p.stock_status(threshold)),
+                    ])
+                });
+                let widths = [
+                    Constraint::Length(6),
+                    Constraint::Fill(2),
+                    Constraint::Fill(1),
+                    Constraint::Length(10),
+                    Constraint::Length(6),
+                    Constraint::Length(10),
+                    Constraint::Length(12),
+                ];
+                let table = Table::new(rows, widths)
+                    .header(Row::new(["ID", "Názov", "Kategória", "Množstvo", "Jedn.", "Cena", "Stav"]).style(header_style))
+                    .block(Block::default().borders(Borders::ALL).title(title))
+                    .row_highlight_style(highlight);
+                frame.render_stateful_widget(table, body, &mut self.table);
+            }
+            Tab::Employees => {
+                let rows = self.employees.iter().map(|e| {
+                    Row::new(vec![
+                        e.id.map(|id| id.to_string()).unwrap_or_default(),
+                        e.name.clone().unwrap_or_default(),
+                        e.surname.clone().unwrap_or_default(),
+                        e.position.clone().unwrap_or_default(),
+                        e.department.clone().unwrap_or_default(),
+                        e.phone_number.clone().unwrap_or_default(),
+                    ])
+                });
+                let widths = [
+                    Constraint::Length(6),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                    Constraint::Length(16),
+                ];
+                let table = Table::new(rows, widths)
+                    .header(Row::new(["ID", "Meno", "Priezvisko", "Pozícia", "Oddelenie", "Telefón"]).style(header_style))
+                    .block(Block::default().borders(Borders::ALL).title(title))
+                    .row_highlight_style(highlight);
+                frame.render_stateful_widget(table, body, &mut self.table);
+            }
+            Tab::Dashboard => {
+                let lines = self.dashboard.as_ref().map(dashboard_lines).unwrap_or_default();
+                frame.render_widget(
+                    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(self.tab.title())),
+                    body,
+                );
+            }
+        }
+
+        let input_line = match &self.mode {
+            Mode::Search => format!("Hľadať: {}_", self.search),
+            Mode::Edit(value) => format!(
+                "Nové množstvo (alebo +n / -n), teraz {}: {value}_",
+                self.selected_product().and_then(|p| p.quantity).unwrap_or_default()
+            ),
+            Mode::ConfirmDelete => format!(
+                "Vymazať produkt {}? (y/n)",
+                self.selected_product().and_then(|p| p.name.as_deref()).unwrap_or_default()
+            ),
+            Mode::Normal if !self.search.is_empty() => format!("Filter: {} | {}", self.search, self.status),
+            Mode::Normal => self.status.clone(),
+        };
+        frame.render_widget(Paragraph::new(input_line), input);
+        frame.render_widget(
+            Paragraph::new("Tab záložky  ↑↓ výber  ←→ strany  / hľadať  Enter množstvo  d vymazať  r obnoviť  q koniec")
+                .style(Style::default().add_modifier(Modifier::DIM)),
+            help,
+        );
+    }
+}
+
+fn dashboard_lines(d: &Dashboard) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!("Produkty: {} (aktívne {})", d.products, d.active_products)),
+        Line::from(format!("Zamestnanci: {}", d.employees)),
+        Line::from(format!("Na doobjednanie: {}", d.low_stock)),
+        Line::from(format!(
+            "Dnes: {} objednávok, tržba {:.2} (po vrátení {:.2})",
+            d.today.orders, d.today.gross_revenue, d.today.net_revenue
+        )),
+        Line::from(""),
+        Line::from("Marža podľa kategórií:"),
+    ];
+    lines.extend(d.margins.iter().take(5).map(|m| {
+        Line::from(format!(
+            "  {:<20} {:>10.2}  (priemer {})",
+            m.category,
+            m.total_margin,
+            m.avg_margin.map(|a| format!("{a:.2}")).unwrap_or_else(|| "–".into())
+        ))
+    }));
+    lines
+}
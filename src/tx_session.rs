@@ -0,0 +1,221 @@
+//! Transakcie naprieč viacerými volaniami API.
+//!
+//! `POST /tx` začne transakciu a vráti jej token. Požiadavky s hlavičkou
+//! `X-Transaction-Id: <token>` sa potom vykonajú v tejto transakcii a
+//! `POST /tx/{id}/commit` alebo `POST /tx/{id}/rollback` ju ukončí.
+//!
+//! Každá otvorená transakcia drží jedno spojenie do SQLite a zámok zápisu celej
+//! databázy (`BEGIN IMMEDIATE`). Kým je otvorená, ostatné zápisy čakajú najviac
+//! `busy_timeout` (5 s) a potom zlyhajú, a druhá transakcia sa nezačne (`409`).
+//! Transakcia, ktorá nedostane žiadne volanie počas `tx_timeout` sekúnd, sa preto
+//! automaticky vráti späť. Otvorené transakcie ukazuje `GET /admin/tx-sessions`
+//! a metriky `store_tx_sessions_*` na `/metrics`.
+
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::Result;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderName, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Router,
+};
+use chrono::{Local, NaiveDateTime};
+use tokio::time::Instant;
+use tower::ServiceExt;
+
+use crate::db::StoreDB;
+use crate::error::StoreError;
+use crate::structs::TxSessionInfo;
+
+/// Hlavička s tokenom transakcie, v ktorej sa má požiadavka vykonať.
+pub const X_TRANSACTION_ID: HeaderName = HeaderName::from_static("x-transaction-id");
+
+/// Predpona ciest na správu transakcií, ktoré sa nikdy nepresmerujú do transakcie.
+const TX_PATH: &str = "/tx";
+
+/// Otvorené transakcie podľa tokenu.
+#[derive(Clone, Default)]
+pub struct TxSessions {
+    inner: Arc<Mutex<HashMap<String, Session>>>,
+    /// Počet transakcií vrátených späť po vypršaní
+    expired: Arc<AtomicU64>,
+}
+
+/// Otvorená transakcia.
+struct Session {
+    /// Databáza nad spojením transakcie
+    db: StoreDB,
+    /// Router aplikácie so stavom nad `db`
+    router: Router,
+    /// Čas nečinnosti, po ktorom sa transakcia zruší
+    timeout: Duration,
+    /// Čas začatia transakcie
+    started_at: NaiveDateTime,
+    /// Okamih, kedy sa transakcia zruší, ak medzitým nepríde ďalšie volanie
+    deadline: Instant,
+}
+
+impl TxSessions {
+    /// Vytvorí prázdny zoznam transakcií.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Začne transakciu a naplánuje jej zrušenie po `timeout` nečinnosti.
+    ///
+    /// # Arguments
+    /// * `db` – hlavná databáza aplikácie
+    /// * `timeout` – čas nečinnosti, po ktorom sa transakcia vráti späť
+    /// * `router` – zostaví router aplikácie nad databázou transakcie
+    ///
+    /// # Returns
+    /// Token transakcie pre hlavičku `X-Transaction-Id`
+    ///
+    /// # Errors
+    /// `StoreError::Conflict`, ak je otvorená iná transakcia, inak ak zlyhá databáza
+    pub async fn begin(&self, db: &StoreDB, timeout: Duration, router: impl FnOnce(StoreDB) -> Router) -> Result<String> {
+        let session_db = db.begin_session().await?;
+        let id = format!("{:032x}", rand::random::<u128>());
+        let session = Session {
+            db: session_db.clone(),
+            router: router(session_db),
+            timeout,
+            started_at: Local::now().naive_local(),
+            deadline: Instant::now() + timeout,
+        };
+        self.lock().insert(id.clone(), session);
+        tokio::spawn(self.clone().expire(id.clone()));
+        Ok(id)
+    }
+
+    /// Ukončí transakciu – potvrdí alebo zahodí jej zmeny.
+    ///
+    /// # Arguments
+    /// * `id` – token transakcie
+    /// * `commit` – `true` zmeny potvrdí, `false` ich vráti späť
+    ///
+    /// # Errors
+    /// `StoreError::NotFound`, ak transakcia neexistuje alebo už vypršala,
+    /// inak ak zlyhá ukončenie transakcie v databáze
+    pub async fn finish(&self, id: &str, commit: bool) -> Result<()> {
+        let session = self.lock().remove(id).ok_or_else(|| not_found(id))?;
+        if commit {
+            session.db.commit_session().await
+        } else {
+            session.db.rollback_session().await
+        }
+    }
+
+    /// Vráti otvorené transakcie od najstaršej.
+    pub fn list(&self) -> Vec<TxSessionInfo> {
+        let now = Instant::now();
+        let mut sessions: Vec<TxSessionInfo> = self
+            .lock()
+            .iter()
+            .map(|(id, session)| {
+                let expires_in = session.deadline.saturating_duration_since(now);
+                TxSessionInfo {
+                    id: id.clone(),
+                    started_at: session.started_at,
+                    idle_secs: session.timeout.saturating_sub(expires_in).as_secs_f64(),
+                    expires_in_secs: expires_in.as_secs_f64(),
+                }
+            })
+            .collect();
+        sessions.sort_by(|a, b| a.started_at.cmp(&b.started_at).then_with(|| a.id.cmp(&b.id)));
+        sessions
+    }
+
+    /// Vypíše počet otvorených a vypršaných transakcií vo formáte Prometheus.
+    pub fn render_metrics(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# HELP store_tx_sessions_open Open transactions started with POST /tx.");
+        let _ = writeln!(out, "# TYPE store_tx_sessions_open gauge");
+        let _ = writeln!(out, "store_tx_sessions_open {}", self.lock().len());
+        let _ = writeln!(out, "# HELP store_tx_sessions_expired_total Transactions rolled back after the idle timeout.");
+        let _ = writeln!(out, "# TYPE store_tx_sessions_expired_total counter");
+        let _ = writeln!(out, "store_tx_sessions_expired_total {}", self.expired.load(Ordering::Relaxed));
+        out
+    }
+
+    /// Vráti router transakcie a predĺži jej platnosť.
+    fn touch(&self, id: &str) -> Option<Router> {
+        let mut sessions = self.lock();
+        let session = sessions.get_mut(id)?;
+        session.deadline = Instant::now() + session.timeout;
+        Some(session.router.clone())
+    }
+
+    /// Čaká na uplynutie platnosti transakcie a potom ju vráti späť.
+    async fn expire(self, id: String) {
+        loop {
+            let Some(deadline) = self.lock().get(&id).map(|s| s.deadline) else {
+                return;
+            };
+            tokio::time::sleep_until(deadline).await;
+
+            let expired = {
+                let mut sessions = self.lock();
+                match sessions.get(&id) {
+                    Some(session) if session.deadline <= Instant::now() => sessions.remove(&id),
+                    Some(_) => continue,
+                    None => return,
+                }
+            };
+            if let Some(session) = expired {
+                self.expired.fetch_add(1, Ordering::Relaxed);
+                eprintln!("Transakcia {id} vypršala, zmeny sa vracajú späť.");
+                if let Err(e) = session.db.rollback_session().await {
+                    eprintln!("Nepodarilo sa vrátiť späť transakciu {id}: {e}");
+                }
+            }
+            return;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Session>> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn not_found(id: &str) -> anyhow::Error {
+    StoreError::NotFound(format!("transakcia {id} neexistuje alebo vypršala")).into()
+}
+
+/// Middleware, ktorý požiadavku s hlavičkou `X-Transaction-Id` vykoná v danej transakcii.
+///
+/// Požiadavky bez hlavičky a správa transakcií (`/tx/...`) pokračujú bežne.
+///
+/// # Arguments
+/// * `sessions` – otvorené transakcie
+/// * `req` – prichádzajúca požiadavka
+/// * `next` – ďalší handler v reťazci
+///
+/// # Returns
+/// Odpoveď handlera v transakcii, `404 Not Found` pre neznámy token
+/// alebo `400 Bad Request` pre neplatnú hlavičku
+pub async fn forward(State(sessions): State<TxSessions>, req: Request, next: Next) -> Response {
+    let Some(value) = req.headers().get(&X_TRANSACTION_ID) else {
+        return next.run(req).await;
+    };
+    if req.uri().path() == TX_PATH || req.uri().path().starts_with("/tx/") {
+        return next.run(req).await;
+    }
+    let Ok(id) = value.to_str() else {
+        return (StatusCode::BAD_REQUEST, "neplatná hlavička X-Transaction-Id").into_response();
+    };
+    match sessions.touch(id) {
+        Some(router) => router.oneshot(req).await.into_response(),
+        None => (StatusCode::NOT_FOUND, not_found(id).to_string()).into_response(),
+    }
+}
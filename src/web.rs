@@ -0,0 +1,129 @@
+use std::{
+    path::{Path as FsPath, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{Html, IntoResponse, Response},
+};
+
+use crate::config::Config;
+
+/// Hlavná stránka vložená do binárky pri kompilácii.
+pub const INDEX_HTML: &str = include_str!("web/index.html");
+
+/// Skript vložený do stránky vo vývojovom režime.
+///
+/// Každú sekundu sa pýta na `/__reload` a pri zmene hodnoty stránku obnoví.
+pub const RELOAD_SCRIPT: &str = r#"<script>
+(() => {
+    let last = null;
+    setInterval(async () => {
+        try {
+            const token = await (await fetch('/__reload', { cache: 'no-store' })).text();
+            if (last !== null && token !== last) location.reload();
+            last = token;
+        } catch (_) {}
+    }, 1000);
+})();
+</script>
+"#;
+
+/// Vráti hlavnú HTML stránku.
+///
+/// Bežne sa vracia stránka vložená do binárky. Vo vývojovom režime
+/// (`dev_mode`) sa `index.html` číta pri každej požiadavke z `web_dir`
+/// a pred `</body>` sa doplní `RELOAD_SCRIPT`.
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie
+///
+/// # Returns
+/// HTML obsah stránky; vo vývojovom režime `500`, ak sa súbor nedá prečítať
+pub async fn index_page(State(config): State<Arc<Config>>) -> Response {
+    if !config.dev_mode {
+        return Html(INDEX_HTML).into_response();
+    }
+
+    let path = FsPath::new(&config.web_dir).join("index.html");
+    let mut html = match tokio::fs::read_to_string(&path).await {
+        Ok(html) => html,
+        Err(e) => {
+            eprintln!("Nepodarilo sa načítať {}: {}", path.display(), e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, format!("{} sa nepodarilo načítať: {e}", path.display()))
+                .into_response();
+        }
+    };
+    match html.rfind("</body>") {
+        Some(pos) => html.insert_str(pos, RELOAD_SCRIPT),
+        None => html.push_str(RELOAD_SCRIPT),
+    }
+    ([(header::CACHE_CONTROL, HeaderValue::from_static("no-store"))], Html(html)).into_response()
+}
+
+/// Vráti súbor z adresára webového rozhrania (len vo vývojovom režime).
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie
+/// * `file` – názov súboru v `web_dir` (bez podadresárov)
+///
+/// # Returns
+/// Obsah súboru alebo `404 Not Found`
+pub async fn asset(State(config): State<Arc<Config>>, Path(file): Path<String>) -> Response {
+    if file.is_empty() || file.starts_with('.') || file.contains(['/', '\\']) {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    match tokio::fs::read(FsPath::new(&config.web_dir).join(&file)).await {
+        Ok(bytes) => (
+            [
+                (header::CONTENT_TYPE, HeaderValue::from_static(content_type(&file))),
+                (header::CACHE_CONTROL, HeaderValue::from_static("no-store")),
+            ],
+            bytes,
+        )
+            .into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Vráti značku, ktorá sa zmení pri úprave niektorého súboru webového rozhrania.
+///
+/// # Arguments
+/// * `config` – konfigurácia aplikácie
+///
+/// # Returns
+/// Najnovší čas úpravy súborov v `web_dir` (nanosekundy od epochy)
+pub async fn reload_token(State(config): State<Arc<Config>>) -> Response {
+    let dir = PathBuf::from(&config.web_dir);
+    let latest = tokio::task::spawn_blocking(move || latest_modification(&dir)).await.unwrap_or(0);
+    ([(header::CACHE_CONTROL, HeaderValue::from_static("no-store"))], latest.to_string()).into_response()
+}
+
+/// Zistí najnovší čas úpravy súborov v adresári (bez podadresárov).
+fn latest_modification(dir: &FsPath) -> u128 {
+    let Ok(entries) = std::fs::read_dir(dir) else { return 0 };
+    entries
+        .filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok())
+        .filter_map(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Určí typ obsahu podľa prípony súboru.
+fn content_type(file: &str) -> &'static str {
+    match file.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()).as_deref() {
+        Some("html") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("ico") => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
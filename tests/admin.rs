@@ -0,0 +1,177 @@
+use chrono::{Days, Local};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use store_manager::db::StoreDB;
+use store_manager::config::Config;
+use store_manager::structs::{MaintenanceStatus, Product, ReadinessStatus};
+use store_manager::test_support::TestApp;
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_admin_app() -> TestApp {
+    TestApp::spawn_with_config(Config {
+        admin_api_key: Some(KEY.into()),
+        ..Config::default()
+    })
+    .await
+}
+
+async fn add_product(app: &TestApp, name: &str, status: bool, date_remove: Option<String>) {
+    let date_remove = date_remove.map(|d| format!(r#""{d}""#)).unwrap_or("null".into());
+    let body = format!(
+        r#"{{"name":"{name}","category":"Test","quantity":1,"status":{status},"bar_code":1,
+            "cost_price":1.0,"sell_price":2.0,"date_remove":{date_remove}}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn purge_deletes_only_old_removed_products() {
+    let app = spawn_admin_app().await;
+    let today = Local::now().date_naive();
+
+    add_product(&app, "Aktívny", true, None).await;
+    add_product(&app, "Nedávno vyradený", false, Some((today - Days::new(3)).to_string())).await;
+    add_product(&app, "Dávno vyradený", false, Some((today - Days::new(400)).to_string())).await;
+
+    let res = app
+        .request_with_headers("POST", "/admin/purge-removed?days=30&confirm=true", None, &[("x-api-key", KEY)])
+        .await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.json::<u64>(), 1);
+
+    let names: Vec<String> = app
+        .get("/products")
+        .await
+        .json::<Vec<Product>>()
+        .into_iter()
+        .filter_map(|p| p.name)
+        .collect();
+    assert_eq!(names, vec!["Aktívny", "Nedávno vyradený"]);
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "purge_removed");
+}
+
+#[tokio::test]
+async fn purge_requires_key_and_confirmation() {
+    let app = spawn_admin_app().await;
+
+    let res = app.post("/admin/purge-removed?days=30&confirm=true", "").await;
+    assert_eq!(res.status, 401);
+
+    let res = app
+        .request_with_headers("POST", "/admin/purge-removed?days=30", None, &[("x-api-key", KEY)])
+        .await;
+    assert_eq!(res.status, 400);
+
+    let disabled = TestApp::spawn().await;
+    let res = disabled
+        .request_with_headers("POST", "/admin/purge-removed?days=30&confirm=true", None, &[("x-api-key", KEY)])
+        .await;
+    assert_eq!(res.status, 403);
+}
+
+#[tokio::test]
+async fn maintenance_mode_toggles_at_runtime() {
+    let app = spawn_admin_app().await;
+    let toggle = |body: &'static str| app.request_with_headers("POST", "/admin/maintenance", Some(body), &[("x-api-key", KEY)]);
+
+    assert_eq!(app.request("POST", "/admin/maintenance", Some(r#"{"enabled":true}"#)).await.status, 401);
+    assert_eq!(app.get("/products").await.status, 200);
+
+    let res = toggle(r#"{"enabled":true}"#).await;
+    assert_eq!(res.status, 200);
+    let status: MaintenanceStatus = res.json();
+    assert!(status.maintenance);
+    assert_eq!(status.retry_after, 120);
+
+    let res = app.get("/products").await;
+    assert_eq!(res.status, 503);
+    assert_eq!(res.header("retry-after"), Some("120"));
+    let body: MaintenanceStatus = res.json();
+    assert!(body.maintenance);
+    assert_eq!(body.retry_after, 120);
+    assert_eq!(app.post("/products", "{}").await.status, 503);
+
+    assert_eq!(app.get("/livez").await.status, 200);
+    let res = app.get("/readyz").await;
+    assert_eq!(res.status, 503);
+    assert!(res.json::<ReadinessStatus>().maintenance);
+
+    toggle(r#"{"enabled":true,"retry_after":300}"#).await;
+    assert_eq!(app.get("/products").await.header("retry-after"), Some("300"));
+
+    let res = toggle(r#"{"enabled":false}"#).await;
+    assert!(!res.json::<MaintenanceStatus>().maintenance);
+    assert_eq!(app.get("/products").await.status, 200);
+    let res = app.get("/readyz").await;
+    assert_eq!(res.status, 200);
+    assert!(!res.json::<ReadinessStatus>().maintenance);
+}
+
+#[tokio::test]
+async fn schema_dump_requires_admin_key() {
+    let app = spawn_admin_app().await;
+    assert_eq!(app.get("/admin/schema.sql").await.status, 401);
+
+    let res = app.request_with_headers("GET", "/admin/schema.sql", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("content-type"), Some("application/sql; charset=utf-8"));
+    let schema = res.text();
+    assert!(schema.contains("CREATE TABLE products"), "{schema}");
+    assert!(schema.contains("CREATE INDEX idx_product_batches_product"));
+    assert!(schema.contains("CREATE UNIQUE INDEX idx_product_images_primary"));
+    assert!(!schema.contains("sqlite_"));
+    assert!(schema.find("CREATE TABLE").unwrap() < schema.find("CREATE INDEX").unwrap());
+}
+
+#[tokio::test]
+async fn config_dump_redacts_secrets() {
+    let app = TestApp::spawn_with_config(Config {
+        admin_api_key: Some(KEY.into()),
+        jwt_secret: Some("jwt-tajomstvo".into()),
+        db_max_connections: 3,
+        ..Config::default()
+    })
+    .await;
+    assert_eq!(app.get("/admin/config").await.status, 401);
+
+    let res = app.request_with_headers("GET", "/admin/config", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 200);
+    let text = res.text();
+    assert!(!text.contains(KEY) && !text.contains("jwt-tajomstvo"), "{text}");
+    let config: serde_json::Value = res.json();
+    assert_eq!(config["admin_api_key"], "***");
+    assert_eq!(config["jwt_secret"], "***");
+    assert!(config["viewer_api_key"].is_null());
+    assert_eq!(config["db_max_connections"], 3);
+    assert_eq!(config["price_rounding"], "none");
+    assert!(config["journal_mode"].is_string());
+}
+
+#[tokio::test]
+async fn schema_dump_recreates_structure_with_triggers() {
+    let dir = std::env::temp_dir();
+    let source = dir.join(format!("schema-source-{}.db", std::process::id()));
+    let copy = dir.join(format!("schema-copy-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&source);
+    let _ = std::fs::remove_file(&copy);
+
+    let db = StoreDB::open(source.to_str().unwrap()).await.unwrap();
+    let pool = SqlitePool::connect(&format!("sqlite:{}", source.display())).await.unwrap();
+    sqlx::raw_sql("CREATE TRIGGER audit_no_delete BEFORE DELETE ON audit_log BEGIN SELECT RAISE(ABORT, 'nie'); END")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let schema = db.dump_schema().await.unwrap();
+    assert!(schema.contains("CREATE TRIGGER audit_no_delete"), "{schema}");
+
+    let options = SqliteConnectOptions::new().filename(&copy).create_if_missing(true);
+    let target = SqlitePool::connect_with(options).await.unwrap();
+    sqlx::raw_sql(&schema).execute(&target).await.unwrap();
+    let recreated = StoreDB::open(copy.to_str().unwrap()).await.unwrap();
+    assert_eq!(recreated.dump_schema().await.unwrap(), schema);
+
+    let _ = std::fs::remove_file(&source);
+    let _ = std::fs::remove_file(&copy);
+}
@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use store_manager::scheduler;
+use store_manager::structs::Alert;
+use store_manager::test_support::TestApp;
+
+async fn adjust(app: &TestApp, id: u32, delta: f64) {
+    let res = app.post(&format!("/products/{id}/adjust-stock"), &format!(r#"{{"delta":{delta}}}"#)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+}
+
+async fn open_alerts(app: &TestApp) -> Vec<Alert> {
+    app.get("/alerts?open=true").await.json()
+}
+
+#[tokio::test]
+async fn alert_opens_below_threshold_and_resolves_when_stock_recovers() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 3.0, 1.0, 2.0).await;
+    app.add_product("Vinea", "Nápoje", 10.0, 1.0, 2.0).await;
+
+    let evaluation = app.db.evaluate_stock_alerts(5).await.unwrap();
+    assert_eq!((evaluation.created, evaluation.resolved), (1, 0));
+    let alerts = open_alerts(&app).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!((alerts[0].product_id, alerts[0].alert_type.as_str()), (1, "low_stock"));
+    assert!(alerts[0].message.contains("Kofola (3)"), "{}", alerts[0].message);
+
+    // opakované vyhodnotenie nevytvorí duplicitné otvorené upozornenie
+    app.db.evaluate_stock_alerts(5).await.unwrap();
+    assert_eq!(open_alerts(&app).await.len(), 1);
+
+    adjust(&app, 2, -6.0).await;
+    adjust(&app, 1, 10.0).await;
+    let evaluation = app.db.evaluate_stock_alerts(5).await.unwrap();
+    assert_eq!((evaluation.created, evaluation.resolved), (1, 1));
+
+    let alerts = open_alerts(&app).await;
+    assert_eq!(alerts.iter().map(|a| a.product_id).collect::<Vec<_>>(), [2]);
+    let resolved: Vec<Alert> = app.get("/alerts?open=false").await.json();
+    assert_eq!(resolved.len(), 1);
+    assert!(resolved[0].resolved_at.is_some());
+    assert_eq!(app.get("/alerts").await.json::<Vec<Alert>>().len(), 2);
+}
+
+#[tokio::test]
+async fn dismissed_alert_is_not_recreated_until_stock_recovers() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 2.0, 1.0, 2.0).await;
+    app.db.evaluate_stock_alerts(5).await.unwrap();
+    let id = open_alerts(&app).await[0].id;
+
+    let res = app.post(&format!("/alerts/{id}/resolve"), "").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert!(res.json::<Alert>().resolved_at.is_some());
+    assert_eq!(app.post(&format!("/alerts/{id}/resolve"), "").await.status, 409);
+    assert_eq!(app.post("/alerts/99/resolve", "").await.status, 404);
+
+    app.db.evaluate_stock_alerts(5).await.unwrap();
+    assert!(open_alerts(&app).await.is_empty());
+
+    // po doplnení a opätovnom poklese vznikne nové upozornenie
+    adjust(&app, 1, 10.0).await;
+    app.db.evaluate_stock_alerts(5).await.unwrap();
+    adjust(&app, 1, -11.0).await;
+    let evaluation = app.db.evaluate_stock_alerts(5).await.unwrap();
+    assert_eq!(evaluation.created, 1);
+    assert_ne!(open_alerts(&app).await[0].id, id);
+}
+
+#[tokio::test]
+async fn monitor_evaluates_alerts_in_background() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 0.0, 1.0, 2.0).await;
+
+    let handle = scheduler::spawn_stock_alerts(app.db.clone(), Duration::from_millis(20), 5).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    handle.abort();
+
+    assert_eq!(open_alerts(&app).await.len(), 1);
+}
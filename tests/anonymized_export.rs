@@ -0,0 +1,76 @@
+use serde_json::Value;
+use store_manager::config::Config;
+use store_manager::structs::{DataImportReport, Employee};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+const PRODUCT: &str = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+
+async fn spawn_admin_app() -> TestApp {
+    TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await
+}
+
+async fn admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+async fn seeded_app() -> TestApp {
+    let app = spawn_admin_app().await;
+    assert_eq!(admin(&app, "POST", "/products", Some(PRODUCT)).await.status, 201);
+    for (name, email, salary) in [("Jana", "jana@obchod.sk", 1234.5), ("Eva", "eva@obchod.sk", 980.0)] {
+        let body = format!(
+            r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true,"email":"{email}","phone_number":"0901 123 456","salary":{salary},"note":"súkromné"}}"#
+        );
+        assert_eq!(admin(&app, "POST", "/employees", Some(&body)).await.status, 201);
+    }
+    app
+}
+
+#[tokio::test]
+async fn anonymized_export_replaces_personal_data_consistently() {
+    let app = seeded_app().await;
+
+    let res = admin(&app, "GET", "/admin/export?anonymize=true", None).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert!(res.header("content-disposition").unwrap().contains("store_data-all-anonymized.json"));
+    let text = res.text();
+    for personal in ["Jana", "Nová", "obchod.sk", "123 456", "súkromné", "1234.5"] {
+        assert!(!text.contains(personal), "{personal} v {text}");
+    }
+
+    let data: Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(data["anonymized"], Value::Bool(true));
+    let employees: Vec<Employee> = serde_json::from_value(data["employees"].clone()).unwrap();
+    assert_eq!(employees.len(), 2);
+    assert_ne!(employees[0].name, employees[1].name);
+    assert_eq!(employees[0].surname, employees[1].surname);
+    assert_eq!(employees[0].phone_number, employees[1].phone_number);
+    assert_ne!(employees[0].email, employees[1].email);
+    assert_eq!((employees[0].salary, employees[1].salary), (Some(1000.0), Some(500.0)));
+    assert!(employees.iter().all(|e| e.note.is_none() && e.position.as_deref() == Some("Predavačka")));
+
+    let plain: Value = admin(&app, "GET", "/admin/export", None).await.json();
+    assert_eq!(data["products"], plain["products"]);
+    assert!(plain.get("anonymized").is_none());
+}
+
+#[tokio::test]
+async fn anonymized_file_is_imported_only_when_allowed() {
+    let source = seeded_app().await;
+    let export = String::from_utf8(admin(&source, "GET", "/admin/export?anonymize=true", None).await.body).unwrap();
+
+    let app = spawn_admin_app().await;
+    let res = admin(&app, "POST", "/admin/import", Some(&export)).await;
+    assert_eq!(res.status, 409, "{}", res.text());
+    assert!(app.get("/products").await.text() == "[]");
+
+    let res = admin(&app, "POST", "/admin/import?allow_anonymized=true", Some(&export)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: DataImportReport = res.json();
+    assert_eq!((report.employees, report.products, report.anonymized), (2, 1, true));
+
+    let employees: Vec<Employee> = admin(&app, "GET", "/employees", None).await.json();
+    assert_eq!(employees[0].phone_e164.as_deref(), Some("+421900000001"));
+    assert_eq!(employees[0].email.as_deref(), Some("zamestnanec1@example.com"));
+}
@@ -0,0 +1,98 @@
+use sqlx::sqlite::SqlitePool;
+use store_manager::auth::{self, Role};
+use store_manager::config::Config;
+use store_manager::structs::{ApiKey, CreatedApiKey};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const ADMIN_KEY: &str = "tajny-kluc";
+
+async fn spawn_with_employees() -> TestApp {
+    // kľúč `viewer` zapne vynútené prihlásenie pre zápis
+    let app = TestApp::spawn_with_config(Config {
+        admin_api_key: Some(ADMIN_KEY.into()),
+        viewer_api_key: Some("citatel".into()),
+        ..Config::default()
+    })
+    .await;
+    for name in ["Jana", "Eva"] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true}}"#);
+        let res = app.request_with_headers("POST", "/employees", Some(&body), &[("x-api-key", ADMIN_KEY)]).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+    app
+}
+
+async fn as_admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", ADMIN_KEY)]).await
+}
+
+async fn create_key(app: &TestApp, body: &str) -> CreatedApiKey {
+    let res = as_admin(app, "POST", "/admin/keys", Some(body)).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    res.json()
+}
+
+#[test]
+fn key_hash_verifies_only_the_original_key() {
+    let (key_id, key) = auth::generate_api_key();
+    assert_eq!(auth::api_key_id(&key), Some(key_id.as_str()));
+    let hash = auth::hash_api_key(&key).unwrap();
+    assert!(hash.starts_with("$argon2id$"));
+    assert!(!hash.contains(&key));
+
+    assert!(auth::verify_api_key_hash(&key, &hash));
+    assert!(!auth::verify_api_key_hash(&format!("{key}x"), &hash));
+    assert!(!auth::verify_api_key_hash(&key, "nie-je-hash"));
+    assert_ne!(auth::hash_api_key(&key).unwrap(), hash);
+}
+
+#[tokio::test]
+async fn created_key_is_returned_once_and_stored_hashed() {
+    let app = spawn_with_employees().await;
+
+    let created = create_key(&app, r#"{"employee_id":1,"role":"editor","name":"pokladňa"}"#).await;
+    assert!(created.api_key.starts_with(auth::EMPLOYEE_KEY_PREFIX));
+    assert_eq!((created.info.employee_id, created.info.role), (1, Role::Editor));
+
+    let res = as_admin(&app, "GET", "/admin/keys", None).await;
+    assert_eq!(res.status, 200);
+    assert!(!res.text().contains(&created.api_key));
+    let keys: Vec<ApiKey> = res.json();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].name.as_deref(), Some("pokladňa"));
+
+    let pool = SqlitePool::connect(&format!("sqlite:{}", app.db_path().display())).await.unwrap();
+    let stored: String = sqlx::query_scalar("SELECT key_hash FROM api_keys").fetch_one(&pool).await.unwrap();
+    assert_ne!(stored, created.api_key);
+    assert!(auth::verify_api_key_hash(&created.api_key, &stored));
+
+    let verified = app.db.verify_api_key(&created.api_key).await.unwrap().unwrap();
+    assert_eq!(verified.id, created.info.id);
+    assert!(app.db.verify_api_key("smk_00000000_zly").await.unwrap().is_none());
+
+    assert_eq!(as_admin(&app, "POST", "/admin/keys", Some(r#"{"employee_id":9}"#)).await.status, 404);
+}
+
+#[tokio::test]
+async fn employee_key_authorizes_by_its_role_until_revoked() {
+    let app = spawn_with_employees().await;
+    let editor = create_key(&app, r#"{"employee_id":1,"role":"editor"}"#).await;
+    let viewer = create_key(&app, r#"{"employee_id":2}"#).await;
+    let product = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+
+    let res = app.request_with_headers("POST", "/products", Some(product), &[("x-api-key", &editor.api_key)]).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let res = app.request_with_headers("POST", "/products", Some(product), &[("x-api-key", &viewer.api_key)]).await;
+    assert_eq!(res.status, 403);
+    let res = app.request_with_headers("GET", "/admin/keys", None, &[("x-api-key", &editor.api_key)]).await;
+    assert_eq!(res.status, 403);
+
+    assert_eq!(as_admin(&app, "DELETE", &format!("/admin/keys/{}", editor.info.id), None).await.status, 204);
+    let res = app.request_with_headers("GET", "/products", None, &[("x-api-key", &editor.api_key)]).await;
+    assert_eq!(res.status, 401);
+    assert!(app.db.verify_api_key(&editor.api_key).await.unwrap().is_none());
+
+    let keys: Vec<ApiKey> = as_admin(&app, "GET", "/admin/keys", None).await.json();
+    assert!(keys[0].revoked_at.is_some() && keys[1].revoked_at.is_none());
+    assert_eq!(as_admin(&app, "DELETE", "/admin/keys/9", None).await.status, 404);
+}
@@ -0,0 +1,79 @@
+use store_manager::structs::{AttendanceEntry, EmployeeHours, HoursReport};
+use store_manager::test_support::{TestApp, TestResponse};
+
+async fn spawn_with_employees() -> TestApp {
+    let app = TestApp::spawn().await;
+    for name in ["Jana", "Peter"] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nový","position":"Predavač","status":true}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    app
+}
+
+async fn clock(app: &TestApp, employee_id: u32, action: &str, at: &str) -> TestResponse {
+    app.post(&format!("/employees/{employee_id}/clock-{action}?at={at}"), "").await
+}
+
+async fn shift(app: &TestApp, employee_id: u32, from: &str, to: &str) {
+    assert_eq!(clock(app, employee_id, "in", from).await.status, 201);
+    let res = clock(app, employee_id, "out", to).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+}
+
+#[tokio::test]
+async fn weekly_hours_split_night_shifts_and_count_overtime() {
+    let app = spawn_with_employees().await;
+    // pondelok 12. – piatok 16. októbra 2026 po 9 hodín = 45 h
+    for day in 12..=16 {
+        shift(&app, 1, &format!("2026-10-{day}T08:00:00"), &format!("2026-10-{day}T17:00:00")).await;
+    }
+    // nočná zmena z nedele 18. na pondelok 19. patrí z časti do dvoch týždňov
+    shift(&app, 2, "2026-10-18T22:00:00", "2026-10-19T06:00:00").await;
+
+    let report: HoursReport = app.get("/reports/hours?from=2026-10-12&to=2026-10-25").await.json();
+    assert_eq!(report.weekly_norm, 40.0);
+    let weeks: Vec<_> = report.weeks.iter().map(|w| (w.employee_id, w.week.as_str(), w.hours, w.overtime)).collect();
+    assert_eq!(weeks, [(1, "2026-W42", 45.0, 5.0), (2, "2026-W42", 2.0, 0.0), (2, "2026-W43", 6.0, 0.0)]);
+    assert_eq!(report.weeks[0].name, "Jana Nový");
+
+    // časť zmeny mimo obdobia sa nezapočíta
+    let report: HoursReport = app.get("/reports/hours?from=2026-10-19&to=2026-10-25&employee_id=2").await.json();
+    assert_eq!(report.weeks.len(), 1);
+    assert_eq!(report.weeks[0].hours, 6.0);
+
+    let res = app.get("/reports/hours?from=2026-10-12&to=2026-10-18&format=csv").await;
+    assert_eq!(res.status, 200);
+    let csv = res.text();
+    assert!(csv.starts_with("employee_id,name,week,week_start,hours,overtime\r\n"), "{csv}");
+    assert!(csv.contains("1,Jana Nový,2026-W42,2026-10-12,45.00,5.00\r\n"), "{csv}");
+}
+
+#[tokio::test]
+async fn open_entries_are_excluded_and_flagged() {
+    let app = spawn_with_employees().await;
+    shift(&app, 1, "2026-10-12T08:00:00", "2026-10-12T16:00:00").await;
+    assert_eq!(clock(&app, 1, "in", "2026-10-13T08:00:00").await.status, 201);
+    assert_eq!(clock(&app, 1, "in", "2026-10-13T09:00:00").await.status, 409);
+
+    let report: HoursReport = app.get("/reports/hours?from=2026-10-12&to=2026-10-18").await.json();
+    assert_eq!(report.weeks[0].hours, 8.0);
+    assert_eq!(report.missing_clock_outs.len(), 1);
+    assert_eq!(report.missing_clock_outs[0].date.to_string(), "2026-10-13");
+    assert_eq!(report.warnings.len(), 1);
+
+    // oprava chýbajúceho odchodu
+    assert_eq!(clock(&app, 1, "out", "2026-10-13T07:00:00").await.status, 400);
+    let entry: AttendanceEntry = clock(&app, 1, "out", "2026-10-13T12:30:00").await.json();
+    assert!(entry.clock_out.is_some());
+    assert_eq!(clock(&app, 1, "out", "2026-10-13T13:00:00").await.status, 409);
+    assert_eq!(clock(&app, 1, "in", "2026-10-13T12:00:00").await.status, 409);
+
+    let detail: EmployeeHours = app.get("/employees/1/hours?from=2026-10-12&to=2026-10-18").await.json();
+    assert_eq!(detail.entries.len(), 2);
+    assert_eq!(detail.days.iter().map(|d| d.hours).collect::<Vec<_>>(), [8.0, 4.5]);
+    assert_eq!(detail.report.weeks[0].hours, 12.5);
+    assert!(detail.report.missing_clock_outs.is_empty() && detail.report.warnings.is_empty());
+
+    assert_eq!(app.get("/employees/9/hours?from=2026-10-12&to=2026-10-18").await.status, 404);
+    assert_eq!(clock(&app, 9, "in", "2026-10-13T08:00:00").await.status, 404);
+}
@@ -0,0 +1,33 @@
+use store_manager::config::Config;
+use store_manager::test_support::TestApp;
+
+const KEY: &str = "tajny-kluc";
+
+#[tokio::test]
+async fn exports_audit_log_as_csv() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    let body = r#"{"name":"Kofola","category":"Nápoje","quantity":5,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    assert_eq!(app.post("/products/1/clone", r#"{"bar_code":2}"#).await.status, 201);
+    app.db.record_audit("read_only", Some("enabled=true".into()), Some("jana")).await.unwrap();
+
+    let res = app.request_with_headers("GET", "/audit/export.csv", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert!(res.header("content-type").unwrap().starts_with("text/csv"));
+    let text = res.text();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "timestamp,entity_type,entity_id,action,actor,changed_fields");
+    assert_eq!(lines.len(), 3, "{text}");
+    assert!(
+        lines[1].ends_with(r#",product,2,clone_product,,"{""clone_id"":""2"",""source_id"":""1""}""#),
+        "{}",
+        lines[1]
+    );
+    assert!(lines[2].ends_with(r#",,,read_only,jana,"{""enabled"":""true""}""#), "{}", lines[2]);
+
+    let res = app.request_with_headers("GET", "/audit/export.csv?entity=product", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.text().lines().count(), 2);
+    let res = app.request_with_headers("GET", "/audit/export.csv?from=2000-01-01&to=2000-01-31", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.text().lines().collect::<Vec<_>>(), ["timestamp,entity_type,entity_id,action,actor,changed_fields"]);
+    assert_eq!(app.get("/audit/export.csv").await.status, 401);
+}
@@ -0,0 +1,85 @@
+use store_manager::config::Config;
+use store_manager::structs::{AverageCost, Product};
+use store_manager::test_support::TestApp;
+
+async fn spawn(average_cost_on_restock: bool) -> TestApp {
+    let app = TestApp::spawn_with_config(Config { average_cost_on_restock, ..Config::default() }).await;
+    let product = r#"{"name":"Káva","category":"Potraviny","quantity":0,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":5.0}"#;
+    assert_eq!(app.post("/products", product).await.status, 201);
+    app
+}
+
+async fn restock(app: &TestApp, quantity: u32, unit_cost: f64) {
+    let po = format!(r#"{{"supplier":"Baliareň","items":[{{"product_id":1,"quantity":{quantity},"unit_cost":{unit_cost}}}]}}"#);
+    let id: u32 = app.post("/purchase-orders", &po).await.json();
+    assert_eq!(app.put(&format!("/purchase-orders/{id}"), r#"{"status":"sent"}"#).await.status, 200);
+    let receipt = format!(r#"{{"items":[{{"product_id":1,"received_quantity":{quantity}}}]}}"#);
+    let res = app.post(&format!("/purchase-orders/{id}/receive"), &receipt).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+}
+
+async fn sell(app: &TestApp, quantity: u32) {
+    let order = format!(r#"{{"items":[{{"product_id":1,"quantity":{quantity}}}]}}"#);
+    assert_eq!(app.post("/orders", &order).await.status, 201);
+}
+
+async fn avg_cost(app: &TestApp) -> AverageCost {
+    let res = app.get("/products/1/avg-cost").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+fn close(actual: Option<f64>, expected: f64) -> bool {
+    actual.is_some_and(|v| (v - expected).abs() < 1e-9)
+}
+
+#[tokio::test]
+async fn average_follows_lots_remaining_in_stock() {
+    let app = spawn(false).await;
+    assert_eq!(avg_cost(&app).await.average_cost, None);
+
+    restock(&app, 10, 1.0).await;
+    restock(&app, 10, 2.0).await;
+    assert!(close(app.db.weighted_avg_cost(1).await.unwrap(), 1.5));
+
+    // po predaji 15 ks zostáva 5 ks z novšej dávky za 2.0
+    sell(&app, 15).await;
+    assert!(close(app.db.weighted_avg_cost(1).await.unwrap(), 2.0));
+
+    restock(&app, 5, 3.0).await;
+    let cost = avg_cost(&app).await;
+    assert_eq!(cost.quantity, 10.0);
+    assert!(close(cost.average_cost, 2.5), "{cost:?}");
+    // bez zapnutej voľby sa nákupná cena nemení
+    assert_eq!(cost.cost_price, Some(1.0));
+
+    assert_eq!(app.get("/products/9/avg-cost").await.status, 404);
+    assert!(app.db.weighted_avg_cost(9).await.is_err());
+}
+
+#[tokio::test]
+async fn stock_without_lots_uses_cost_price() {
+    let app = spawn(false).await;
+    // zásoba zadaná pri založení produktu nemá nákupnú dávku
+    let product = r#"{"name":"Čaj","category":"Potraviny","quantity":4,"status":true,"bar_code":2,"cost_price":1.0,"sell_price":3.0}"#;
+    assert_eq!(app.post("/products", product).await.status, 201);
+    let po = r#"{"supplier":"Baliareň","items":[{"product_id":2,"quantity":4,"unit_cost":3.0}]}"#;
+    let id: u32 = app.post("/purchase-orders", po).await.json();
+    assert_eq!(app.put(&format!("/purchase-orders/{id}"), r#"{"status":"sent"}"#).await.status, 200);
+    let receipt = r#"{"items":[{"product_id":2,"received_quantity":4}]}"#;
+    assert_eq!(app.post(&format!("/purchase-orders/{id}/receive"), receipt).await.status, 200);
+
+    // 4 ks za 3.0 a 4 ks bez dávky ocenené aktuálnou cenou 1.0
+    assert!(close(app.db.weighted_avg_cost(2).await.unwrap(), 2.0));
+}
+
+#[tokio::test]
+async fn enabled_flag_updates_cost_price_on_restock() {
+    let app = spawn(true).await;
+    restock(&app, 10, 1.0).await;
+    restock(&app, 10, 2.0).await;
+
+    let product: Product = app.get("/products/1").await.json();
+    assert!(close(product.cost_price, 1.5), "{:?}", product.cost_price);
+    assert!(close(avg_cost(&app).await.average_cost, 1.5));
+}
@@ -0,0 +1,59 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use store_manager::db_filler::DBFiller;
+use store_manager::test_support::TestApp;
+
+/// Prázdny dočasný adresár testu.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("store_manager_{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn files(dir: &PathBuf) -> BTreeSet<String> {
+    fs::read_dir(dir).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().into_owned()).collect()
+}
+
+#[tokio::test]
+async fn only_newest_backups_survive() {
+    let app = TestApp::spawn().await;
+    let dir = temp_dir("backup_rotation");
+    let json = dir.join("store_data.json");
+    let path = json.to_str().unwrap();
+
+    // staršie zálohy a súbory, ktoré zálohami nie sú
+    let unrelated = [
+        "store_data.json.bak",
+        "store_data.json.poznamky.bak",
+        "store_data.json.2020010112000000000.bak",
+        "ine_data.json.20200101T120000000000.bak",
+        "store_data.jsonx.20200101T120000000000.bak",
+    ];
+    for name in unrelated.iter().chain(&["store_data.json.20200101T120000000000.bak"]) {
+        fs::write(dir.join(name), "{}").unwrap();
+    }
+    assert_eq!(DBFiller::save_to_json_with_backup(&app.db, path, 3).await.unwrap(), None);
+
+    let mut created = Vec::new();
+    for _ in 0..5 {
+        let backup = DBFiller::save_to_json_with_backup(&app.db, path, 3).await.unwrap().unwrap();
+        created.push(backup.file_name().unwrap().to_string_lossy().into_owned());
+    }
+
+    let backups: Vec<_> = DBFiller::backups_of(&json).unwrap();
+    let names: Vec<_> = backups.iter().map(|p| p.file_name().unwrap().to_string_lossy().into_owned()).collect();
+    assert_eq!(names, created[2..]);
+    let mut expected: BTreeSet<String> = unrelated.iter().map(|n| n.to_string()).collect();
+    expected.extend(names);
+    expected.insert("store_data.json".into());
+    assert_eq!(files(&dir), expected);
+
+    // bez zálohy sa súbor len prepíše
+    assert_eq!(DBFiller::save_to_json_with_backup(&app.db, path, 0).await.unwrap(), None);
+    assert_eq!(DBFiller::backups_of(&json).unwrap().len(), 3);
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,101 @@
+use std::io::Cursor;
+
+use store_manager::structs::BarcodeError;
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, bar_code: i64) {
+    let body = format!(
+        r#"{{"name":"Kofola","category":"Nápoje","quantity":1,"bar_code":{bar_code},"cost_price":1.0,"sell_price":2.0}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+/// Rozmery a pixely (odtiene sivej) PNG obrázka.
+fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+    let mut reader = png::Decoder::new(Cursor::new(png)).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!(info.color_type, png::ColorType::Grayscale);
+    (info.width, info.height, pixels)
+}
+
+#[tokio::test]
+async fn png_encodes_ean13_with_quiet_zones_and_digits() {
+    let app = TestApp::spawn().await;
+    add_product(&app, 5901234123457).await;
+
+    let res = app.get("/products/1/barcode.png?module_width=1&height=20").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("image/png"));
+    let (width, height, pixels) = decode(&res.body);
+    assert_eq!((width, height), (11 + 95 + 7, 20 + 9));
+
+    // prvý riadok = moduly kódu medzi tichými zónami
+    let row: String = pixels[..width as usize].iter().map(|&p| if p == 0 { '1' } else { '0' }).collect();
+    let expected = [
+        "0".repeat(11).as_str(),
+        "101",
+        "0001011", "0100111", "0110011", "0010011", "0111101", "0011101",
+        "01010",
+        "1100110", "1101100", "1000010", "1011100", "1001110", "1000100",
+        "101",
+        "0".repeat(7).as_str(),
+    ]
+    .concat();
+    assert_eq!(row, expected);
+    // pás s číslicami nie je prázdny
+    assert!(pixels[(21 * width) as usize..].contains(&0));
+
+    let res = app.get("/products/1/barcode.png?module_width=3&height=40&text=false").await;
+    let (width, height, _) = decode(&res.body);
+    assert_eq!((width, height), (113 * 3, 40));
+}
+
+#[tokio::test]
+async fn svg_encodes_ean8_and_is_cacheable() {
+    let app = TestApp::spawn().await;
+    add_product(&app, 96385074).await;
+
+    let res = app.get("/products/1/barcode.svg").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("image/svg+xml"));
+    let svg = res.text();
+    assert!(svg.starts_with("<svg") && svg.contains(r#"width="162""#), "{svg}");
+    assert_eq!(svg.matches("<text").count(), 8);
+
+    let etag = res.header("etag").unwrap().to_string();
+    assert_eq!(res.header("cache-control"), Some("public, no-cache"));
+    let res = app.request_with_headers("GET", "/products/1/barcode.svg", None, &[("if-none-match", &etag)]).await;
+    assert_eq!(res.status, 304);
+    assert!(res.body.is_empty());
+
+    // iný kód = iný ETag
+    assert_eq!(app.put("/products/1", r#"{"bar_code":5901234123457}"#).await.status, 200);
+    let res = app.request_with_headers("GET", "/products/1/barcode.svg", None, &[("if-none-match", &etag)]).await;
+    assert_eq!(res.status, 200);
+    assert_ne!(res.header("etag"), Some(etag.as_str()));
+}
+
+#[tokio::test]
+async fn missing_and_invalid_barcodes_return_structured_errors() {
+    let app = TestApp::spawn().await;
+    let res = app.get("/products/1/barcode.png").await;
+    assert_eq!(res.status, 404);
+    assert_eq!(res.json::<BarcodeError>().error, "product_not_found");
+
+    add_product(&app, 0).await;
+    let res = app.get("/products/1/barcode.png").await;
+    assert_eq!(res.status, 404);
+    assert_eq!(res.json::<BarcodeError>().error, "no_barcode");
+
+    add_product(&app, 5901234123458).await;
+    let res = app.get("/products/2/barcode.svg").await;
+    assert_eq!(res.status, 422);
+    let error: BarcodeError = res.json();
+    assert_eq!((error.error.as_str(), error.product_id, error.bar_code), ("invalid_barcode", 2, Some(5901234123458)));
+    assert!(error.message.contains("kontrolnú číslicu"), "{}", error.message);
+
+    add_product(&app, 5901234123457).await;
+    assert_eq!(app.get("/products/3/barcode.png?module_width=0").await.status, 400);
+    assert_eq!(app.get("/products/3/barcode.png?height=5000").await.status, 400);
+}
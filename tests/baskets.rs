@@ -0,0 +1,54 @@
+use store_manager::structs::BasketStats;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for body in [
+        r#"{"name":"Kofola","category":"Nápoje","quantity":100,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#,
+        r#"{"name":"Chlieb","category":"Pečivo","quantity":100,"bar_code":2,"cost_price":0.5,"sell_price":1.0}"#,
+    ] {
+        assert_eq!(app.post("/products", body).await.status, 201);
+    }
+    app
+}
+
+async fn stats(app: &TestApp, from: &str, to: &str) -> BasketStats {
+    let res = app.get(&format!("/stats/baskets?from={from}&to={to}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn averages_lines_units_and_value_per_order() {
+    let app = spawn_with_products().await;
+    for body in [
+        // 2 riadky, 4 kusy, 3 × 2.00 + 1 × 1.00 = 7.00
+        r#"{"created_at":"2024-03-01T09:00:00","items":[{"product_id":1,"quantity":3},{"product_id":2,"quantity":1}]}"#,
+        // 1 riadok, 2 kusy, 2 × 1.50 = 3.00
+        r#"{"created_at":"2024-03-02T17:30:00","items":[{"product_id":1,"quantity":2,"unit_price":1.5}]}"#,
+        // mimo obdobia
+        r#"{"created_at":"2024-04-01T10:00:00","items":[{"product_id":2,"quantity":9}]}"#,
+    ] {
+        assert_eq!(app.post("/orders", body).await.status, 201);
+    }
+
+    let report = stats(&app, "2024-03-01", "2024-03-31").await;
+    assert!(report.has_sales);
+    assert_eq!(report.baskets, 2);
+    assert!((report.avg_lines - 1.5).abs() < 1e-9, "{report:?}");
+    assert!((report.avg_units - 3.0).abs() < 1e-9, "{report:?}");
+    assert!((report.avg_value - 5.0).abs() < 1e-9, "{report:?}");
+    assert!((report.total_value - 10.0).abs() < 1e-9, "{report:?}");
+}
+
+#[tokio::test]
+async fn window_without_sales_returns_zeros() {
+    let app = spawn_with_products().await;
+
+    let report = stats(&app, "2024-03-01", "2024-03-31").await;
+    assert!(!report.has_sales);
+    assert_eq!(report.baskets, 0);
+    assert_eq!((report.avg_lines, report.avg_units, report.avg_value, report.total_value), (0.0, 0.0, 0.0, 0.0));
+
+    assert_eq!(app.get("/stats/baskets?from=2024-03-31&to=2024-03-01").await.status, 400);
+}
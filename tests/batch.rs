@@ -0,0 +1,109 @@
+use serde_json::json;
+use store_manager::config::Config;
+use store_manager::structs::{BatchError, BatchOp, BatchStepResult, Employee, Product};
+use store_manager::test_support::TestApp;
+
+const EMPLOYEE: &str = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","status":true}"#;
+
+async fn counts(app: &TestApp) -> (usize, usize) {
+    let employees = app.get("/employees").await.json::<Vec<Employee>>().len();
+    let products = app.get("/products").await.json::<Vec<Product>>().len();
+    (employees, products)
+}
+
+#[tokio::test]
+async fn batch_runs_steps_in_order_with_references() {
+    let app = TestApp::spawn().await;
+    let body = json!({"operations": [
+        {"op": "create_employee", "body": serde_json::from_str::<serde_json::Value>(EMPLOYEE).unwrap()},
+        {"op": "create_product", "body": {"name": "Kofola", "category": "Nápoje", "quantity": 0, "bar_code": 1,
+            "cost_price": 1.0, "sell_price": 2.0, "employee_id": "$0.id"}},
+        {"op": "adjust_stock", "id": "$1.id", "body": {"delta": 12}},
+        {"op": "update_employee", "id": "$0.id", "body": {"department": "Sklad"}},
+    ]});
+    let res = app.post("/batch", &body.to_string()).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let steps: Vec<BatchStepResult> = res.json();
+    let summary: Vec<_> = steps.iter().map(|s| (s.index, s.op, s.id)).collect();
+    assert_eq!(summary, [
+        (0, BatchOp::CreateEmployee, 1),
+        (1, BatchOp::CreateProduct, 1),
+        (2, BatchOp::AdjustStock, 1),
+        (3, BatchOp::UpdateEmployee, 1),
+    ]);
+    assert_eq!(steps[2].result.as_ref().unwrap()["quantity"], 12.0);
+
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!((product.employee_id, product.quantity), (Some(1), Some(12.0)));
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    assert_eq!(employees[0].department.as_deref(), Some("Sklad"));
+}
+
+#[tokio::test]
+async fn failing_step_rolls_back_the_whole_batch() {
+    let app = TestApp::spawn().await;
+    let body = json!({"operations": [
+        {"op": "create_employee", "body": serde_json::from_str::<serde_json::Value>(EMPLOYEE).unwrap()},
+        {"op": "create_product", "body": {"name": "Kofola", "category": "Nápoje", "quantity": 2, "bar_code": 1,
+            "cost_price": 1.0, "sell_price": 2.0}},
+        {"op": "adjust_stock", "id": "$1.id", "body": {"delta": -5}},
+    ]});
+    let res = app.post("/batch", &body.to_string()).await;
+    assert_eq!(res.status, 409, "{}", res.text());
+    let error: BatchError = res.json();
+    assert_eq!((error.index, error.op), (Some(2), Some(BatchOp::AdjustStock)));
+    assert_eq!(counts(&app).await, (0, 0));
+
+    // chybné telo aj neznámy záznam sa hlásia s poradím operácie
+    let res = app.post("/batch", r#"{"operations":[{"op":"create_product","body":{"name":"Kofola","sell_pricce":2}}]}"#).await;
+    assert_eq!(res.status, 422);
+    assert_eq!(res.json::<BatchError>().index, Some(0));
+    let res = app.post("/batch", r#"{"operations":[{"op":"update_product","id":7,"body":{"quantity":1}}]}"#).await;
+    assert_eq!(res.status, 404);
+
+    // po zlyhaní sa dá zapisovať bežne aj ďalšou dávkou
+    let res = app.post("/batch", &json!({"operations": [{"op": "create_employee", "body": serde_json::from_str::<serde_json::Value>(EMPLOYEE).unwrap()}]}).to_string()).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(counts(&app).await, (1, 0));
+}
+
+#[tokio::test]
+async fn batch_enforces_size_operations_and_references() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/batch", r#"{"operations":[]}"#).await.status, 400);
+    let many: Vec<_> = (0..51).map(|_| json!({"op": "delete_product", "id": 1})).collect();
+    let res = app.post("/batch", &json!({ "operations": many }).to_string()).await;
+    assert_eq!(res.status, 400);
+    assert_eq!(res.json::<BatchError>().index, None);
+
+    // mimo povolenej množiny operácií
+    assert_eq!(app.post("/batch", r#"{"operations":[{"op":"purge_removed"}]}"#).await.status, 422);
+
+    let res = app.post("/batch", r#"{"operations":[{"op":"delete_product","id":"$0.id"}]}"#).await;
+    assert_eq!(res.status, 400);
+    assert!(res.json::<BatchError>().error.contains("ešte neprebehla"));
+    let res = app.post("/batch", r#"{"operations":[{"op":"create_employee","id":3,"body":{}}]}"#).await;
+    assert_eq!(res.status, 400);
+    assert_eq!(app.post("/batch", r#"{"operations":[{"op":"delete_employee"}]}"#).await.status, 400);
+}
+
+#[tokio::test]
+async fn each_operation_needs_the_role_of_its_endpoint() {
+    let app = TestApp::spawn_with_config(Config {
+        admin_api_key: Some("admin-kluc".into()),
+        editor_api_key: Some("editor-kluc".into()),
+        ..Config::default()
+    })
+    .await;
+    let body = json!({"operations": [
+        {"op": "create_employee", "body": serde_json::from_str::<serde_json::Value>(EMPLOYEE).unwrap()},
+        {"op": "delete_employee", "id": "$0.id"},
+    ]})
+    .to_string();
+
+    let res = app.request_with_headers("POST", "/batch", Some(&body), &[("x-api-key", "editor-kluc")]).await;
+    assert_eq!(res.status, 403);
+    assert_eq!(res.json::<BatchError>().index, Some(1));
+    assert_eq!(app.request_with_headers("POST", "/batch", Some(&body), &[("x-api-key", "admin-kluc")]).await.status, 200);
+    assert_eq!(app.post("/batch", &body).await.status, 401);
+}
@@ -0,0 +1,71 @@
+use chrono::{Days, Local};
+use store_manager::structs::{ExpiringBatch, Product, ProductBatch};
+use store_manager::test_support::TestApp;
+
+async fn total_matches_batches(app: &TestApp, id: u32) -> f64 {
+    let product: Product = app.get(&format!("/products/{id}")).await.json();
+    let batches: Vec<ProductBatch> = app.get(&format!("/products/{id}/batches")).await.json();
+    let sum: f64 = batches.iter().filter_map(|b| b.quantity).sum();
+    assert_eq!(product.quantity, Some(sum));
+    sum
+}
+
+#[tokio::test]
+async fn sales_consume_soonest_expiring_batch_first() {
+    let app = TestApp::spawn().await;
+    let today = Local::now().date_naive();
+
+    let res = app
+        .post(
+            "/products",
+            r#"{"name":"Jogurt","category":"Mliečne","quantity":0,"status":true,
+                "bar_code":1,"cost_price":0.3,"sell_price":0.6}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+
+    let late = today + Days::new(20);
+    let soon = today + Days::new(3);
+    for (lot, expiry, qty) in [("L2", late, 10), ("L1", soon, 5)] {
+        let body = format!(r#"{{"lot_code":"{lot}","expiry_date":"{expiry}","quantity":{qty}}}"#);
+        assert_eq!(app.post("/products/1/batches", &body).await.status, 201);
+    }
+    assert_eq!(total_matches_batches(&app, 1).await, 15.0);
+
+    let res = app.post("/orders", r#"{"items":[{"product_id":1,"quantity":7}]}"#).await;
+    assert_eq!(res.status, 201);
+
+    let batches: Vec<ProductBatch> = app.get("/products/1/batches").await.json();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].lot_code.as_deref(), Some("L2"));
+    assert_eq!(batches[0].quantity, Some(8.0));
+    assert_eq!(total_matches_batches(&app, 1).await, 8.0);
+
+    let expiring: Vec<ExpiringBatch> = app.get("/reports/expiring-batches?days=30").await.json();
+    assert_eq!(expiring.len(), 1);
+    assert_eq!(expiring[0].days_left, 20);
+    let expiring: Vec<ExpiringBatch> = app.get("/reports/expiring-batches?days=7").await.json();
+    assert!(expiring.is_empty());
+}
+
+#[tokio::test]
+async fn quantity_edits_keep_batches_in_sync() {
+    let app = TestApp::spawn().await;
+    let res = app
+        .post(
+            "/products",
+            r#"{"name":"Chlieb","category":"Pečivo","quantity":4,"status":true,
+                "bar_code":1,"cost_price":0.5,"sell_price":1.0}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+    assert_eq!(total_matches_batches(&app, 1).await, 4.0);
+
+    assert_eq!(app.put("/products/1", r#"{"quantity":9}"#).await.status, 200);
+    assert_eq!(total_matches_batches(&app, 1).await, 9.0);
+
+    let batches: Vec<ProductBatch> = app.get("/products/1/batches").await.json();
+    let id = batches[0].id.unwrap();
+    assert_eq!(app.put(&format!("/products/1/batches/{id}"), r#"{"quantity":2}"#).await.status, 200);
+    assert_eq!(total_matches_batches(&app, 1).await, 2.0);
+}
@@ -0,0 +1,63 @@
+use store_manager::db::StoreDB;
+use store_manager::db_filler::{DBFiller, LoadMode};
+use store_manager::structs::{Employee, Product};
+
+/// Súbor s platnými aj chybnými záznamami: druhý produkt má obsadené SKU, druhý
+/// zamestnanec neexistujúceho nadriadeného a druhý preklad neplatný jazyk.
+const MIXED: &str = r#"{
+    "employees":[{"id":1,"name":"Jana","surname":"A","position":"P","status":true},
+                 {"id":2,"name":"Ján","surname":"B","position":"P","status":true,"manager_id":99}],
+    "products":[{"name":"A","category":"T","quantity":5,"bar_code":1,"cost_price":1,"sell_price":2,"sku":"T-0001"},
+                {"name":"B","category":"T","quantity":5,"bar_code":2,"cost_price":1,"sell_price":2,"sku":"T-0001"},
+                {"name":"C","category":"T","quantity":5,"bar_code":3,"cost_price":1,"sell_price":2}],
+    "translations":[{"product_id":1,"lang":"hu","name":"A hu"},
+                    {"product_id":1,"lang":"???","name":"A ?"}]
+}"#;
+
+async fn open_with_file(name: &str) -> (StoreDB, std::path::PathBuf, std::path::PathBuf) {
+    let dir = std::env::temp_dir();
+    let json = dir.join(format!("store_manager_{name}_{}.json", std::process::id()));
+    let path = dir.join(format!("store_manager_{name}_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    std::fs::write(&json, MIXED).unwrap();
+    (StoreDB::open(path.to_str().unwrap()).await.unwrap(), json, path)
+}
+
+#[tokio::test]
+async fn best_effort_commits_valid_rows_and_reports_skipped_ones() {
+    let (db, json, path) = open_with_file("best_effort").await;
+
+    let report = DBFiller::load_from_json(&db, json.to_str().unwrap(), LoadMode::BestEffort).await.unwrap();
+    assert_eq!((report.employees, report.products), (2, 2));
+    let skipped: Vec<_> = report.skipped.iter().map(|s| (s.entity.as_str(), s.index)).collect();
+    assert_eq!(skipped, [("employees", 1), ("products", 1), ("translations", 1)]);
+    assert!(report.skipped[1].reason.contains("T-0001"), "{}", report.skipped[1].reason);
+
+    // odmietnutý produkt po sebe nenechal šaržu ani SKU, ďalší dostal SKU v poradí
+    let products = db.get_products(Product::new_empty()).await.unwrap();
+    let names: Vec<_> = products.iter().map(|p| p.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["A", "C"]);
+    assert_eq!(products[1].sku.as_deref(), Some("T-0002"));
+    assert_eq!(db.get_batches(products[1].id.unwrap()).await.unwrap().len(), 1);
+
+    let employees = db.get_employees(Employee::new_empty()).await.unwrap();
+    assert_eq!(employees.len(), 2);
+    assert_eq!(employees[1].manager_id, None);
+    assert_eq!(db.get_product_translations(None).await.unwrap().len(), 1);
+
+    let _ = std::fs::remove_file(&json);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn sequential_load_stops_at_the_first_bad_row() {
+    let (db, json, path) = open_with_file("sequential").await;
+
+    assert!(DBFiller::load_from_json(&db, json.to_str().unwrap(), LoadMode::Sequential).await.is_err());
+    // zamestnanci pred chybou (nadriadený 99) zostali zapísaní, produkty sa už nenačítali
+    assert_eq!(db.get_employees(Employee::new_empty()).await.unwrap().len(), 2);
+    assert!(db.get_products(Product::new_empty()).await.unwrap().is_empty());
+
+    let _ = std::fs::remove_file(&json);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,43 @@
+use store_manager::body_log::describe_body;
+use store_manager::config::Config;
+use store_manager::structs::Employee;
+use store_manager::test_support::TestApp;
+
+#[test]
+fn salary_and_phone_are_redacted_in_log_output() {
+    let body = br#"[{"name":"Jana","salary":1850.5,"phone_number":"0905123456","note":{"salary":900}}]"#;
+    let logged = describe_body(body, 1000);
+
+    assert!(!logged.contains("1850.5"));
+    assert!(!logged.contains("0905123456"));
+    assert!(!logged.contains("900"));
+    assert!(logged.contains(r#""salary":"***""#));
+    assert!(logged.contains("Jana"));
+}
+
+#[test]
+fn logged_body_is_capped_and_non_json_is_not_logged() {
+    let body = format!(r#"{{"note":"{}"}}"#, "x".repeat(500));
+    let logged = describe_body(body.as_bytes(), 20);
+    assert!(logged.starts_with(r#"{"note":"xxxxxxxxxxx…"#));
+    assert!(logged.len() < 60);
+
+    assert_eq!(describe_body(b"salary=1850", 100), "<ne-JSON telo 11 B>");
+}
+
+#[tokio::test]
+async fn logging_keeps_bodies_intact() {
+    let app = TestApp::spawn_with_config(Config { log_bodies: true, ..Config::default() }).await;
+
+    let res = app
+        .post(
+            "/employees",
+            r#"{"name":"Jana","surname":"Nová","position":"Pokladníčka","salary":1850.5,"phone_number":"0905123456"}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    assert_eq!(employees[0].salary, Some(1850.5));
+    assert_eq!(employees[0].phone_number.as_deref(), Some("0905123456"));
+}
@@ -0,0 +1,50 @@
+use store_manager::structs::{BreakEven, Product};
+use store_manager::test_support::TestApp;
+
+async fn add(app: &TestApp, bar_code: u32, prices: &str) {
+    let body = format!(r#"{{"name":"Produkt {bar_code}","category":"Potraviny","quantity":1,"status":true,"bar_code":{bar_code}{prices}}}"#);
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[test]
+fn quantity_is_rounded_up_and_requires_positive_margin() {
+    let product = Product { cost_price: Some(3.0), sell_price: Some(5.0), ..Product::default() };
+    assert_eq!(product.break_even_quantity(1000.0), Some(500));
+    assert_eq!(product.break_even_quantity(1001.0), Some(501));
+    assert_eq!(product.break_even_quantity(0.0), Some(0));
+
+    let even = Product { cost_price: Some(5.0), sell_price: Some(5.0), ..Product::default() };
+    assert_eq!(even.break_even_quantity(1000.0), None);
+    let loss = Product { cost_price: Some(6.0), sell_price: Some(5.0), ..Product::default() };
+    assert_eq!(loss.break_even_quantity(1000.0), None);
+    assert_eq!(Product::default().break_even_quantity(1000.0), None);
+}
+
+#[tokio::test]
+async fn profitable_product_reports_break_even() {
+    let app = TestApp::spawn().await;
+    add(&app, 1, r#","cost_price":1.5,"sell_price":4.0"#).await;
+
+    let res = app.get("/products/1/break-even?fixed=1000").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let result: BreakEven = res.json();
+    assert_eq!((result.product_id, result.quantity), (1, 400));
+    assert_eq!((result.fixed_costs, result.unit_margin), (1000.0, 2.5));
+
+    assert_eq!(app.get("/products/1/break-even?fixed=-5").await.status, 400);
+    assert_eq!(app.get("/products/1/break-even").await.status, 400);
+    assert_eq!(app.get("/products/9/break-even?fixed=1000").await.status, 404);
+}
+
+#[tokio::test]
+async fn loss_making_product_is_rejected_with_message() {
+    let app = TestApp::spawn().await;
+    add(&app, 1, r#","cost_price":3.0,"sell_price":2.0"#).await;
+    add(&app, 2, r#","cost_price":2.0,"sell_price":2.0"#).await;
+
+    for id in [1, 2] {
+        let res = app.get(&format!("/products/{id}/break-even?fixed=1000")).await;
+        assert_eq!(res.status, 422);
+        assert!(res.text().contains("nepredáva so ziskom"), "{}", res.text());
+    }
+}
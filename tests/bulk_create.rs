@@ -0,0 +1,50 @@
+use store_manager::structs::{BulkCreateResult, Product};
+use store_manager::test_support::TestApp;
+
+/// Dávka s jednou chybnou položkou: druhý produkt má rovnaké SKU ako prvý.
+const MIXED: &str = r#"[
+    {"name":"Mlieko","category":"Nápoje","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0,"sku":"NAP-0100"},
+    {"name":"Kefír","category":"Nápoje","quantity":5,"status":true,"bar_code":2,"cost_price":1.0,"sell_price":2.0,"sku":"NAP-0100"},
+    {"name":"Džús","category":"Nápoje","quantity":5,"status":true,"bar_code":3,"cost_price":1.0,"sell_price":2.0}
+]"#;
+
+#[tokio::test]
+async fn strict_bulk_create_inserts_nothing_when_an_item_fails() {
+    let app = TestApp::spawn().await;
+
+    let res = app.post("/products/bulk", MIXED).await;
+    assert_eq!(res.status, 409);
+    assert!(res.text().starts_with("položka 1:"), "{}", res.text());
+    assert!(app.get("/products").await.json::<Vec<Product>>().is_empty());
+
+    let res = app.post("/products/bulk", r#"[{"name":"Čaj","category":"Nápoje","quantity":1,"status":true,"bar_code":4,"cost_price":1.0,"sell_price":2.0}]"#).await;
+    assert_eq!(res.status, 201);
+    let results: Vec<BulkCreateResult> = res.json();
+    assert_eq!(results, [BulkCreateResult { index: 0, status: 201, id: Some(1), error: None }]);
+
+    assert_eq!(app.post("/products/bulk", "[]").await.status, 400);
+}
+
+#[tokio::test]
+async fn best_effort_bulk_create_reports_each_item() {
+    let app = TestApp::spawn().await;
+    let long_name = "x".repeat(1000);
+    let body = MIXED.replacen(
+        "\n]",
+        &format!(r#",{{"name":"{long_name}","category":"Nápoje","quantity":1,"status":true,"bar_code":5}}]"#),
+        1,
+    );
+
+    let res = app.post("/products/bulk?best_effort=true", &body).await;
+    assert_eq!(res.status, 207);
+    let results: Vec<BulkCreateResult> = res.json();
+    let statuses: Vec<_> = results.iter().map(|r| (r.index, r.status)).collect();
+    assert_eq!(statuses, [(0, 201), (1, 409), (2, 201), (3, 422)]);
+    assert!(results[1].error.as_deref().unwrap().contains("NAP-0100"));
+    assert_eq!(results[1].id, None);
+
+    let products: Vec<Product> = app.get("/products").await.json();
+    let names: Vec<_> = products.iter().map(|p| p.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Mlieko", "Džús"]);
+    assert_eq!(products.iter().map(|p| p.id).collect::<Vec<_>>(), [results[0].id, results[2].id]);
+}
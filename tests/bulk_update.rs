@@ -0,0 +1,91 @@
+use store_manager::structs::{BulkUpdateResult, BulkUpdateStatus, Employee, Product};
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, unit: &str, quantity: f64) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"unit":"{unit}","status":true,
+            "bar_code":1,"cost_price":1.0,"sell_price":2.0,"brand":"Stará","supplier":"Dodávateľ A"}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+fn statuses(results: &[BulkUpdateResult]) -> Vec<(u32, BulkUpdateStatus)> {
+    results.iter().map(|r| (r.id, r.status)).collect()
+}
+
+#[tokio::test]
+async fn bulk_update_sets_fields_on_all_listed_products() {
+    let app = TestApp::spawn().await;
+    for name in ["Kofola", "Vinea", "Rajec"] {
+        add_product(&app, name, "piece", 10.0).await;
+    }
+
+    let res = app
+        .post("/products/bulk-update", r#"{"ids":[1,3,99],"set":{"brand":"Nová","supplier":"X"}}"#)
+        .await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let results: Vec<BulkUpdateResult> = res.json();
+    assert_eq!(
+        statuses(&results),
+        [(1, BulkUpdateStatus::Updated), (3, BulkUpdateStatus::Updated), (99, BulkUpdateStatus::NotFound)]
+    );
+
+    let products: Vec<Product> = app.get("/products").await.json();
+    let brands: Vec<_> = products.iter().map(|p| (p.brand.as_deref().unwrap(), p.supplier.as_deref().unwrap())).collect();
+    assert_eq!(brands, [("Nová", "X"), ("Stará", "Dodávateľ A"), ("Nová", "X")]);
+    assert!(products.iter().all(|p| p.quantity == Some(10.0)));
+}
+
+#[tokio::test]
+async fn bulk_update_rejects_empty_requests_and_computed_fields() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Kofola", "piece", 10.0).await;
+
+    assert_eq!(app.post("/products/bulk-update", r#"{"ids":[],"set":{"brand":"X"}}"#).await.status, 400);
+    assert_eq!(app.post("/products/bulk-update", r#"{"ids":[1],"set":{}}"#).await.status, 400);
+    for set in [r#"{"id":2}"#, r#"{"is_bundle":true}"#, r#"{"quantity":5}"#] {
+        let res = app.post("/products/bulk-update", &format!(r#"{{"ids":[1],"set":{set}}}"#)).await;
+        assert_eq!(res.status, 400, "{set}");
+        assert!(res.text().contains("hromadne"), "{}", res.text());
+    }
+    assert_eq!(app.post("/products/bulk-update", r#"{"ids":[1],"set":{"colour":"red"}}"#).await.status, 422);
+}
+
+#[tokio::test]
+async fn invalid_change_for_one_product_updates_none() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Jablká", "kg", 2.0).await;
+    add_product(&app, "Syr", "kg", 1.5).await;
+
+    // 1.5 kg sa nedá vyjadriť v kusoch, preto sa nezmení ani prvý produkt
+    let res = app.post("/products/bulk-update", r#"{"ids":[1,2],"set":{"unit":"piece"}}"#).await;
+    assert_eq!(res.status, 400, "{}", res.text());
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert!(products.iter().all(|p| p.unit.map(|u| u.as_str()) == Some("kg")));
+}
+
+#[tokio::test]
+async fn employees_bulk_update_normalizes_phone() {
+    let app = TestApp::spawn().await;
+    for name in ["Jana", "Peter"] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Pokladník","status":true}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+
+    let res = app
+        .post("/employees/bulk-update", r#"{"ids":[1,2,2],"set":{"department":"Sklad","phone_number":"0900 123 456"}}"#)
+        .await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let results: Vec<BulkUpdateResult> = res.json();
+    assert!(results.iter().all(|r| r.status == BulkUpdateStatus::Updated));
+
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    for e in &employees {
+        assert_eq!(e.department.as_deref(), Some("Sklad"));
+        assert_eq!(e.phone_e164.as_deref(), Some("+421900123456"));
+    }
+
+    let res = app.post("/employees/bulk-update", r#"{"ids":[1],"set":{"phone_e164":"+421900000000"}}"#).await;
+    assert_eq!(res.status, 400);
+    assert_eq!(app.post("/employees/bulk-update", r#"{"ids":[1],"set":{"phone_number":"abc"}}"#).await.status, 400);
+}
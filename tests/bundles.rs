@@ -0,0 +1,85 @@
+use store_manager::structs::{Bundle, Product};
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, quantity: f64, unit: &str) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Test","quantity":{quantity},"unit":"{unit}","status":true,
+            "bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+async fn quantity(app: &TestApp, id: u32) -> f64 {
+    app.get(&format!("/products/{id}")).await.json::<Product>().quantity.unwrap()
+}
+
+/// Kôš (4) = 2× víno (1) + 0,5 kg syra (2) + 1 čokoláda (3).
+async fn spawn_with_basket() -> TestApp {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Víno", 7.0, "piece").await;
+    add_product(&app, "Syr", 2.0, "kg").await;
+    add_product(&app, "Čokoláda", 10.0, "piece").await;
+    add_product(&app, "Darčekový kôš", 0.0, "piece").await;
+
+    let res = app
+        .put(
+            "/products/4/components",
+            r#"[{"component_product_id":1,"quantity":2},{"component_product_id":2,"quantity":0.5},
+                {"component_product_id":3,"quantity":1}]"#,
+        )
+        .await;
+    assert_eq!(res.status, 200);
+    app
+}
+
+#[tokio::test]
+async fn bundle_quantity_is_buildable_count_and_sale_takes_components() {
+    let app = spawn_with_basket().await;
+
+    let bundle: Bundle = app.get("/products/4/components").await.json();
+    assert_eq!(bundle.components.len(), 3);
+    assert_eq!(bundle.buildable, 3);
+    assert_eq!(quantity(&app, 4).await, 3.0);
+    let basket: Product = app.get("/products/4").await.json();
+    assert_eq!(basket.is_bundle, Some(true));
+
+    assert_eq!(app.post("/orders", r#"{"items":[{"product_id":4,"quantity":2}]}"#).await.status, 201);
+    assert_eq!(quantity(&app, 1).await, 3.0);
+    assert_eq!(quantity(&app, 2).await, 1.0);
+    assert_eq!(quantity(&app, 3).await, 8.0);
+    assert_eq!(quantity(&app, 4).await, 1.0);
+
+    // na dva koše už nie je dosť vína, nič sa neodpíše
+    assert_eq!(app.post("/orders", r#"{"items":[{"product_id":4,"quantity":2}]}"#).await.status, 409);
+    assert_eq!(quantity(&app, 1).await, 3.0);
+    assert_eq!(quantity(&app, 3).await, 8.0);
+
+    // vrátený kôš vráti zložky na sklad
+    assert_eq!(app.post("/orders/1/returns", r#"{"items":[{"product_id":4,"quantity":1}]}"#).await.status, 201);
+    assert_eq!(quantity(&app, 1).await, 5.0);
+    assert_eq!(quantity(&app, 2).await, 1.5);
+    assert_eq!(quantity(&app, 4).await, 2.0);
+}
+
+#[tokio::test]
+async fn bundle_composition_is_validated() {
+    let app = spawn_with_basket().await;
+
+    assert_eq!(app.put("/products/4/components", r#"[{"component_product_id":4,"quantity":1}]"#).await.status, 400);
+    // sady nemožno vnárať
+    add_product(&app, "Veľký kôš", 0.0, "piece").await;
+    assert_eq!(app.put("/products/5/components", r#"[{"component_product_id":4,"quantity":1}]"#).await.status, 400);
+    assert_eq!(app.put("/products/4/components", r#"[{"component_product_id":9,"quantity":1}]"#).await.status, 404);
+    assert_eq!(app.put("/products/4/components", r#"[{"component_product_id":1,"quantity":1.5}]"#).await.status, 400);
+    // produkt so zásobami nemôže byť sadou
+    assert_eq!(app.put("/products/3/components", r#"[{"component_product_id":1,"quantity":1}]"#).await.status, 409);
+
+    assert_eq!(app.delete("/products/1").await.status, 409);
+    assert_eq!(app.post("/products/4/batches", r#"{"quantity":5}"#).await.status, 400);
+    assert_eq!(app.get("/products/1/components").await.status, 404);
+
+    // prázdne zloženie sadu zruší
+    assert_eq!(app.put("/products/4/components", "[]").await.status, 200);
+    assert_eq!(app.get("/products/4/components").await.status, 404);
+    assert_eq!(app.delete("/products/1").await.status, 204);
+}
@@ -0,0 +1,90 @@
+use store_manager::config::Config;
+use store_manager::test_support::TestApp;
+
+fn product(name: &str, quantity: f64) -> String {
+    format!(
+        r#"{{"name":"{name}","category":"Pečivo","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+    )
+}
+
+/// Vráti hodnotu metriky z výpisu `/metrics`.
+async fn metric(app: &TestApp, name: &str) -> u64 {
+    let text = app.get("/metrics").await.text();
+    text.lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(' ')?.parse().ok())
+        .unwrap_or_else(|| panic!("metrika {name} chýba vo výpise:\n{text}"))
+}
+
+#[tokio::test]
+async fn repeated_query_is_served_from_cache() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Chlieb", 5.0)).await.status, 201);
+
+    let first = app.get("/products?category=Pečivo").await;
+    let second = app.get("/products?category=Pečivo").await;
+    assert_eq!(first.text(), second.text());
+    assert_eq!(second.header("x-total-count"), Some("1"));
+    assert_eq!(metric(&app, r#"store_cache_misses_total{query="products"}"#).await, 1);
+    assert_eq!(metric(&app, r#"store_cache_hits_total{query="products"}"#).await, 1);
+
+    // iný filter je iný kľúč
+    app.get("/products?category=Mliečne").await;
+    assert_eq!(metric(&app, r#"store_cache_misses_total{query="products"}"#).await, 2);
+    assert_eq!(metric(&app, "store_cache_entries").await, 2);
+    assert_eq!(metric(&app, "store_cache_enabled").await, 1);
+}
+
+#[tokio::test]
+async fn writes_invalidate_dependent_queries() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Chlieb", 5.0)).await.status, 201);
+    assert_eq!(app.get("/products").await.json::<Vec<serde_json::Value>>().len(), 1);
+    app.get("/stats/headcount?from=2024-01-01&to=2024-03-31").await;
+
+    assert_eq!(app.post("/products", &product("Rožok", 5.0)).await.status, 201);
+    assert_eq!(app.get("/products").await.json::<Vec<serde_json::Value>>().len(), 2);
+
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":3}"#).await.status, 200);
+    let products: Vec<serde_json::Value> = app.get("/products").await.json();
+    assert_eq!(products[0]["quantity"], 8.0);
+    assert_eq!(metric(&app, r#"store_cache_hits_total{query="products"}"#).await, 0);
+
+    // zmena produktov nezneplatní dotazy nad zamestnancami
+    app.get("/stats/headcount?from=2024-01-01&to=2024-03-31").await;
+    assert_eq!(metric(&app, r#"store_cache_hits_total{query="headcount"}"#).await, 1);
+}
+
+#[tokio::test]
+async fn graphql_mutation_invalidates_cache() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Chlieb", 5.0)).await.status, 201);
+    app.get("/products").await;
+
+    let mutation = r#"{"query":"mutation { updateProduct(id: 1, input: { name: \"Bageta\" }) { id } }"}"#;
+    assert_eq!(app.post("/graphql", mutation).await.status, 200);
+
+    let products: Vec<serde_json::Value> = app.get("/products").await.json();
+    assert_eq!(products[0]["name"], "Bageta");
+}
+
+#[tokio::test]
+async fn disabled_cache_never_hits() {
+    let app = TestApp::spawn_with_config(Config { cache_enabled: false, ..Config::default() }).await;
+    app.get("/products").await;
+    app.get("/products").await;
+
+    assert_eq!(metric(&app, "store_cache_enabled").await, 0);
+    assert_eq!(metric(&app, r#"store_cache_hits_total{query="products"}"#).await, 0);
+    assert_eq!(metric(&app, r#"store_cache_misses_total{query="products"}"#).await, 0);
+    assert_eq!(metric(&app, "store_cache_entries").await, 0);
+}
+
+#[tokio::test]
+async fn expired_entries_are_reloaded() {
+    let app = TestApp::spawn_with_config(Config { cache_ttl: 0, ..Config::default() }).await;
+    app.get("/stats/category-margins").await;
+    app.get("/stats/category-margins").await;
+
+    assert_eq!(metric(&app, r#"store_cache_hits_total{query="category_margins"}"#).await, 0);
+    assert_eq!(metric(&app, r#"store_cache_misses_total{query="category_margins"}"#).await, 2);
+}
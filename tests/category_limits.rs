@@ -0,0 +1,89 @@
+use store_manager::structs::CategoryLimit;
+use store_manager::test_support::TestApp;
+
+fn product(name: &str, category: &str, quantity: f64) -> String {
+    format!(
+        r#"{{"name":"{name}","category":"{category}","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+    )
+}
+
+#[tokio::test]
+async fn stock_changes_respect_category_limit() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Chlieb", "Pečivo", 10.0)).await.status, 201);
+    assert_eq!(app.post("/products", &product("Mlieko", "Mliečne", 50.0)).await.status, 201);
+
+    let res = app.put("/categories/Pečivo/limit", r#"{"max_total_quantity":20}"#).await;
+    assert_eq!(res.status, 200);
+    let limit: CategoryLimit = res.json();
+    assert_eq!((limit.max_total_quantity, limit.total_quantity), (Some(20.0), 10.0));
+
+    // presne na hranici je povolené, nad ňou nie
+    assert_eq!(app.post("/products", &product("Rožok", "Pečivo", 10.0)).await.status, 201);
+    assert_eq!(app.post("/products", &product("Bageta", "Pečivo", 1.0)).await.status, 409);
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":1}"#).await.status, 409);
+    assert_eq!(app.post("/products/1/batches", r#"{"lot_code":"L1","quantity":1}"#).await.status, 409);
+    let limit: CategoryLimit = app.get("/categories/Pečivo/limit").await.json();
+    assert_eq!(limit.total_quantity, 20.0);
+    assert_eq!(app.get("/products?category=Pečivo").await.json::<Vec<serde_json::Value>>().len(), 2);
+
+    // odpis je vždy povolený a uvoľní miesto
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":-2}"#).await.status, 200);
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":2}"#).await.status, 200);
+
+    // iná kategória limit nemá
+    assert_eq!(app.post("/products/2/adjust-stock", r#"{"delta":100}"#).await.status, 200);
+
+    let res = app.put("/categories/Pečivo/limit", r#"{"max_total_quantity":null}"#).await;
+    assert_eq!(res.json::<CategoryLimit>().max_total_quantity, None);
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":5}"#).await.status, 200);
+}
+
+#[tokio::test]
+async fn negative_limit_is_rejected() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.put("/categories/Pečivo/limit", r#"{"max_total_quantity":-1}"#).await.status, 400);
+    let limit: CategoryLimit = app.get("/categories/Nová/limit").await.json();
+    assert_eq!((limit.max_total_quantity, limit.total_quantity), (None, 0.0));
+}
+
+#[tokio::test]
+async fn product_updates_respect_category_limit() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Chlieb", "Pečivo", 10.0)).await.status, 201);
+    assert_eq!(app.post("/products", &product("Mlieko", "Mliečne", 5.0)).await.status, 201);
+    assert_eq!(app.post("/products", &product("Jogurt", "Mliečne", 6.0)).await.status, 201);
+    assert_eq!(app.put("/categories/Pečivo/limit", r#"{"max_total_quantity":15}"#).await.status, 200);
+
+    // PUT s množstvom: hranica áno, nad ňou nie
+    assert_eq!(app.put("/products/1", r#"{"quantity":16}"#).await.status, 409);
+    assert_eq!(app.put("/products/1", r#"{"quantity":15}"#).await.status, 200);
+    assert_eq!(app.put("/products/1", r#"{"quantity":10}"#).await.status, 200);
+
+    // PUT s kategóriou: 10 + 5 sa zmestí, 10 + 6 nie
+    assert_eq!(app.put("/products/3", r#"{"category":"Pečivo"}"#).await.status, 409);
+    assert_eq!(app.db.get_product(3).await.unwrap().unwrap().category.as_deref(), Some("Mliečne"));
+    assert_eq!(app.put("/products/2", r#"{"category":"Pečivo"}"#).await.status, 200);
+    let limit: CategoryLimit = app.get("/categories/Pečivo/limit").await.json();
+    assert_eq!(limit.total_quantity, 15.0);
+}
+
+#[tokio::test]
+async fn bulk_category_change_respects_category_limit() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Chlieb", "Pečivo", 10.0)).await.status, 201);
+    assert_eq!(app.post("/products", &product("Mlieko", "Mliečne", 5.0)).await.status, 201);
+    assert_eq!(app.post("/products", &product("Jogurt", "Mliečne", 1.0)).await.status, 201);
+    assert_eq!(app.put("/categories/Pečivo/limit", r#"{"max_total_quantity":15}"#).await.status, 200);
+
+    // 10 + 5 + 1 je nad limitom, nezmení sa nič
+    let res = app.post("/products/bulk-update", r#"{"ids":[2,3],"set":{"category":"Pečivo"}}"#).await;
+    assert_eq!(res.status, 409, "{}", res.text());
+    assert_eq!(app.db.get_product(2).await.unwrap().unwrap().category.as_deref(), Some("Mliečne"));
+
+    // 10 + 5 je presne na hranici
+    let res = app.post("/products/bulk-update", r#"{"ids":[2],"set":{"category":"Pečivo"}}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let limit: CategoryLimit = app.get("/categories/Pečivo/limit").await.json();
+    assert_eq!(limit.total_quantity, 15.0);
+}
@@ -0,0 +1,71 @@
+use std::process::Output;
+
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+use tokio::process::Command;
+
+async fn cli(app: &TestApp, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_store-cli"))
+        .args(args)
+        .env("STORE_URL", format!("http://{}", app.addr))
+        .current_dir(std::env::temp_dir())
+        .output()
+        .await
+        .expect("spustenie store-cli")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[tokio::test]
+async fn cli_manages_products() {
+    let app = TestApp::spawn().await;
+
+    for (name, category) in [("Kofola", "Drinks"), ("Rožok", "Pečivo"), ("Voda, 1l", "Drinks")] {
+        let args = [
+            "products", "add", "--name", name, "--price", "1.5", "--cost-price", "1", "--category", category,
+            "--bar-code", "1", "--quantity", "10",
+        ];
+        let out = cli(&app, &args).await;
+        assert_eq!(stdout(&out).trim(), "Produkt pridaný.");
+    }
+
+    let table = stdout(&cli(&app, &["products", "list", "--category", "Drinks"]).await);
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("id  name"), "{table}");
+    assert!(lines[1].starts_with("--  "));
+    assert_eq!(lines.len(), 4);
+    assert!(table.contains("Kofola") && !table.contains("Rožok"));
+
+    let csv = stdout(&cli(&app, &["products", "list", "--category", "Drinks", "--format", "csv"]).await);
+    assert!(csv.starts_with("id,name,category,quantity,unit,sell_price,supplier,sku\r\n"));
+    assert!(csv.contains("3,\"Voda, 1l\",Drinks,10,piece,1.5,,DRI-0002\r\n"), "{csv}");
+
+    let json = stdout(&cli(&app, &["--format", "json", "products", "adjust", "1", "--delta", "-3"]).await);
+    let adjusted: Vec<Product> = serde_json::from_str(&json).unwrap();
+    assert_eq!(adjusted[0].quantity, Some(7.0));
+}
+
+#[tokio::test]
+async fn cli_searches_employees() {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Peter","surname":"Novak","position":"Skladník","salary":1100}"#;
+    assert_eq!(app.post("/employees", body).await.status, 201);
+    let body = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","salary":1200}"#;
+    assert_eq!(app.post("/employees", body).await.status, 201);
+
+    let table = stdout(&cli(&app, &["employees", "search", "--surname", "Novak"]).await);
+    assert!(table.contains("Peter") && !table.contains("Jana"), "{table}");
+}
+
+#[tokio::test]
+async fn cli_reports_server_errors() {
+    let app = TestApp::spawn().await;
+
+    let out = cli(&app, &["products", "adjust", "42", "--delta", "-1"]).await;
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.starts_with("Chyba: server vrátil 404 Not Found: "), "{stderr}");
+}
@@ -0,0 +1,59 @@
+use store_manager::db::MAX_COMPARE_PRODUCTS;
+use store_manager::structs::ProductComparison;
+use store_manager::test_support::TestApp;
+
+async fn add(app: &TestApp, name: &str, quantity: f64, cost: f64, sell: f64) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"bar_code":0,"cost_price":{cost},"sell_price":{sell}}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn two_products_are_compared_against_the_first() {
+    let app = TestApp::spawn().await;
+    add(&app, "Kofola", 10.0, 1.0, 2.0).await;
+    add(&app, "Vinea", 4.0, 1.5, 2.5).await;
+
+    let res = app.get("/products/compare?ids=2,1").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let cmp: ProductComparison = res.json();
+    let names: Vec<_> = cmp.products.iter().map(|p| p.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Vinea", "Kofola"]);
+    assert!(cmp.not_found.is_empty());
+
+    // Kofola − Vinea: marža 1.0 (50 %) oproti 1.0 (40 %)
+    let [delta] = cmp.deltas.as_slice() else { panic!("{:?}", cmp.deltas) };
+    assert_eq!((delta.base_id, delta.product_id), (2, 1));
+    assert_eq!(delta.sell_price, Some(-0.5));
+    assert_eq!(delta.cost_price, Some(-0.5));
+    assert_eq!(delta.margin, Some(0.0));
+    assert!((delta.margin_percent.unwrap() - 10.0).abs() < 1e-9);
+    assert_eq!(delta.quantity, Some(6.0));
+}
+
+#[tokio::test]
+async fn three_products_with_a_missing_id_are_compared() {
+    let app = TestApp::spawn().await;
+    add(&app, "Kofola", 10.0, 1.0, 2.0).await;
+    add(&app, "Vinea", 4.0, 1.5, 2.5).await;
+    add(&app, "Rajec", 0.0, 0.25, 1.0).await;
+
+    let cmp: ProductComparison = app.get("/products/compare?ids=1,%2099,3,2,1").await.json();
+    assert_eq!(cmp.products.len(), 3);
+    assert_eq!(cmp.not_found, [99]);
+    let deltas: Vec<_> = cmp.deltas.iter().map(|d| (d.product_id, d.sell_price, d.margin, d.quantity)).collect();
+    assert_eq!(deltas, [(3, Some(-1.0), Some(-0.25), Some(-10.0)), (2, Some(0.5), Some(0.0), Some(-6.0))]);
+}
+
+#[tokio::test]
+async fn invalid_comparisons_are_rejected() {
+    let app = TestApp::spawn().await;
+    add(&app, "Kofola", 10.0, 1.0, 2.0).await;
+
+    assert_eq!(app.get("/products/compare?ids=1").await.status, 400);
+    assert_eq!(app.get("/products/compare?ids=1,1").await.status, 400);
+    assert_eq!(app.get("/products/compare?ids=1,x").await.status, 400);
+    let ids: Vec<String> = (1..=MAX_COMPARE_PRODUCTS + 1).map(|i| i.to_string()).collect();
+    assert_eq!(app.get(&format!("/products/compare?ids={}", ids.join(","))).await.status, 400);
+}
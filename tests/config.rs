@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use store_manager::config::Config;
+use store_manager::pricing::{MarginRule, PriceRounding};
+
+#[test]
+fn empty_file_matches_defaults() {
+    let config = Config::from_toml("").expect("prázdna konfigurácia");
+    let defaults = Config::default();
+    assert_eq!(config.max_result_rows, defaults.max_result_rows);
+    assert_eq!(config.db_path, "store.db");
+    assert_eq!(config.json_path, "store_data.json");
+    assert_eq!(config.listen_addr, "0.0.0.0:8000");
+    assert_eq!(config.price_rounding, PriceRounding::None);
+    assert!(config.admin_api_key.is_none());
+}
+
+#[test]
+fn file_values_and_env_overrides() {
+    let mut config = Config::from_toml(
+        r#"
+        max_result_rows = 50
+        price_rounding = "x9"
+        phone_country = "CZ"
+        db_path = "/var/lib/store/store.db"
+        "#,
+    )
+    .expect("platná konfigurácia");
+    assert_eq!(config.max_result_rows, 50);
+    assert_eq!(config.price_rounding, PriceRounding::Psychological);
+
+    let env: HashMap<&str, &str> = HashMap::from([
+        ("STORE_MAX_RESULT_ROWS", "20"),
+        ("STORE_LISTEN_ADDR", "127.0.0.1:9000"),
+        ("STORE_ADMIN_API_KEY", " "),
+    ]);
+    config.apply_overrides(|key| env.get(key).map(|v| v.to_string())).expect("platné premenné");
+    config.validate().expect("platná konfigurácia");
+
+    assert_eq!(config.max_result_rows, 20);
+    assert_eq!(config.listen_addr, "127.0.0.1:9000");
+    assert_eq!(config.phone_country, "CZ");
+    assert_eq!(config.db_path, "/var/lib/store/store.db");
+    assert!(config.admin_api_key.is_none());
+}
+
+#[test]
+fn invalid_file_reports_field_and_reason() {
+    let err = Config::from_toml("log_bodies = true\nmax_result_rows = \"veľa\"\n").unwrap_err().to_string();
+    assert!(err.contains("`max_result_rows`"), "{err}");
+    assert!(err.contains("invalid type"), "{err}");
+
+    let err = Config::from_toml("price_rounding = \"0.07\"").unwrap_err().to_string();
+    assert!(err.contains("`price_rounding`") && err.contains("0.07"), "{err}");
+
+    let err = Config::from_toml("max_rows = 10").unwrap_err().to_string();
+    assert!(err.contains("max_rows"), "{err}");
+
+    let err = Config { max_result_rows: 0, ..Config::default() }.validate().unwrap_err().to_string();
+    assert!(err.contains("`max_result_rows`"), "{err}");
+}
+
+#[test]
+fn invalid_env_override_fails() {
+    let mut config = Config::default();
+    let err = config
+        .apply_overrides(|key| (key == "STORE_LOW_STOCK_THRESHOLD").then(|| "-3".to_string()))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("STORE_LOW_STOCK_THRESHOLD") && err.contains("`low_stock_threshold`"), "{err}");
+}
+
+#[test]
+fn load_reads_given_file() {
+    let path = std::env::temp_dir().join(format!("store_manager_config_{}.toml", std::process::id()));
+    std::fs::write(&path, "reorder_target = 42\n").expect("zápis konfigurácie");
+    let config = Config::load(Some(&path));
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(config.expect("načítaná konfigurácia").reorder_target, 42);
+
+    let missing = std::env::temp_dir().join("store_manager_config_neexistuje.toml");
+    assert!(Config::load(Some(&missing)).is_err());
+}
+
+#[test]
+fn margin_policy_from_file_and_env() {
+    let mut config = Config::from_toml(
+        r#"
+        [margin_policy]
+        default = "15%"
+        categories = { "Pečivo" = "0.20" }
+        "#,
+    )
+    .expect("platná konfigurácia");
+    assert_eq!(config.margin_policy.default, Some(MarginRule::Percent(15.0)));
+    assert_eq!(config.margin_policy.rule_for(Some("Pečivo")), Some(MarginRule::Absolute(0.20)));
+
+    let env: HashMap<&str, &str> = HashMap::from([("STORE_MIN_MARGIN", "0.50"), ("STORE_CATEGORY_MIN_MARGINS", "Nápoje=25%")]);
+    config.apply_overrides(|key| env.get(key).map(|v| v.to_string())).expect("platné premenné");
+    assert_eq!(config.margin_policy.rule_for(Some("Pečivo")), Some(MarginRule::Absolute(0.50)));
+    assert_eq!(config.margin_policy.rule_for(Some("Nápoje")), Some(MarginRule::Percent(25.0)));
+
+    let err = Config::from_toml("[margin_policy]\ndefault = \"120%\"").unwrap_err().to_string();
+    assert!(err.contains("120%") && err.contains("mimo rozsahu"), "{err}");
+}
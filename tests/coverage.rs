@@ -0,0 +1,57 @@
+use store_manager::structs::StockCoverage;
+use store_manager::test_support::TestApp;
+
+#[tokio::test]
+async fn coverage_divides_stock_by_daily_sales() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 100.0, 1.0, 2.0).await;
+    app.add_product("Vinea", "Nápoje", 10.0, 1.0, 2.0).await;
+    app.add_product("Rajec", "Nápoje", 10.0, 1.0, 2.0).await;
+    // 60 ks za 30 dní = 2 ks denne, zostane 40 ks na 20 dní
+    app.sell(1, 60.0).await;
+    // 6 ks za 30 dní = 0.2 ks denne, zostanú 4 ks na 20 dní
+    app.sell(2, 6.0).await;
+    // predaj mimo obdobia tempa
+    app.sell_at(3, 5.0, Some("2020-01-10T10:00:00")).await;
+
+    let res = app.get("/products/1/coverage").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let coverage: StockCoverage = res.json();
+    assert_eq!((coverage.quantity, coverage.daily_sales), (40.0, 2.0));
+    assert!((coverage.coverage_days.unwrap() - 20.0).abs() < 1e-9, "{coverage:?}");
+    assert!(coverage.message.is_none());
+    assert!((app.db.stock_coverage_days(2).await.unwrap().unwrap() - 20.0).abs() < 1e-9);
+
+    let coverage: StockCoverage = app.get("/products/3/coverage").await.json();
+    assert_eq!(coverage.coverage_days, None);
+    assert!(coverage.message.unwrap().contains("30 dní"));
+    assert_eq!(app.db.stock_coverage_days(3).await.unwrap(), None);
+
+    assert_eq!(app.get("/products/9/coverage").await.status, 404);
+    assert!(app.db.stock_coverage_days(9).await.is_err());
+}
+
+#[tokio::test]
+async fn catalog_coverage_flags_products_under_threshold() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 100.0, 1.0, 2.0).await;
+    app.add_product("Vinea", "Nápoje", 33.0, 1.0, 2.0).await;
+    app.add_product("Rajec", "Nápoje", 0.0, 1.0, 2.0).await;
+    app.add_product("Tonik", "Nápoje", 5.0, 1.0, 2.0).await;
+    // Kofola: 40 ks pri 2 ks denne = 20 dní, Vinea: 3 ks pri 1 ks denne = 3 dni,
+    // Rajec sa nepredával, Tonik sa predal celý (0 dní)
+    app.sell(1, 60.0).await;
+    app.sell(2, 30.0).await;
+    app.sell(4, 5.0).await;
+
+    let res = app.get("/products/coverage?days=14").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let lines: Vec<StockCoverage> = res.json();
+    let ids: Vec<_> = lines.iter().map(|l| (l.product_id, l.coverage_days.unwrap())).collect();
+    assert_eq!(ids, [(4, 0.0), (2, 3.0)]);
+
+    let lines: Vec<StockCoverage> = app.get("/products/coverage?days=30").await.json();
+    assert_eq!(lines.iter().map(|l| l.product_id).collect::<Vec<_>>(), [4, 2, 1]);
+
+    assert_eq!(app.get("/products/coverage?days=0").await.status, 400);
+}
@@ -0,0 +1,55 @@
+use store_manager::structs::CoPurchase;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, bar_code) in [("Chlieb", 1), ("Maslo", 2), ("Mlieko", 3), ("Pivo", 4)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":100,"bar_code":{bar_code},"cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+async fn sell(app: &TestApp, product_ids: &[u32]) {
+    let items: Vec<String> = product_ids.iter().map(|id| format!(r#"{{"product_id":{id},"quantity":1}}"#)).collect();
+    let res = app.post("/orders", &format!(r#"{{"items":[{}]}}"#, items.join(","))).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+}
+
+#[tokio::test]
+async fn ranks_products_bought_in_the_same_baskets() {
+    let app = spawn_with_products().await;
+    // chlieb v 4 košíkoch: 3× s maslom, 1× s mliekom; pivo sa kupuje samo
+    sell(&app, &[1, 2]).await;
+    sell(&app, &[1, 2, 3]).await;
+    sell(&app, &[1, 2, 1]).await;
+    sell(&app, &[1]).await;
+    sell(&app, &[4]).await;
+
+    let res = app.get("/products/1/cross-sell").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let suggestions: Vec<CoPurchase> = res.json();
+    assert_eq!(suggestions.iter().map(|s| (s.product_id, s.baskets)).collect::<Vec<_>>(), [(2, 3), (3, 1)]);
+    assert_eq!(suggestions[0].name, "Maslo");
+    assert!((suggestions[0].confidence - 0.75).abs() < 1e-9, "{suggestions:?}");
+    assert!((suggestions[0].support - 0.6).abs() < 1e-9, "{suggestions:?}");
+    assert!((suggestions[1].confidence - 0.25).abs() < 1e-9, "{suggestions:?}");
+
+    let maslo: Vec<CoPurchase> = app.get("/products/2/cross-sell?limit=1").await.json();
+    assert_eq!(maslo.len(), 1);
+    assert_eq!((maslo[0].product_id, maslo[0].confidence), (1, 1.0));
+}
+
+#[tokio::test]
+async fn product_without_co_purchases_has_no_suggestions() {
+    let app = spawn_with_products().await;
+    sell(&app, &[4]).await;
+
+    let suggestions: Vec<CoPurchase> = app.get("/products/4/cross-sell").await.json();
+    assert!(suggestions.is_empty());
+    let suggestions: Vec<CoPurchase> = app.get("/products/3/cross-sell").await.json();
+    assert!(suggestions.is_empty());
+    assert_eq!(app.get("/products/99/cross-sell").await.status, 404);
+}
@@ -0,0 +1,232 @@
+use store_manager::config::Config;
+use store_manager::structs::{Employee, Product};
+use store_manager::test_support::TestApp;
+
+#[tokio::test]
+async fn product_create_get_update_delete_round_trip() {
+    let app = TestApp::spawn().await;
+
+    let res = app
+        .post(
+            "/products",
+            r#"{"name":"Jablko","category":"Ovocie","quantity":10,"status":true,
+                "bar_code":123456,"cost_price":0.5,"sell_price":0.9}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+
+    let products: Vec<Product> = app.get("/products?name=Jablko").await.json();
+    assert_eq!(products.len(), 1);
+    let id = products[0].id.unwrap();
+
+    let res = app.put(&format!("/products/{id}"), r#"{"quantity":3}"#).await;
+    assert_eq!(res.status, 200);
+
+    let product: Product = app.get(&format!("/products/{id}")).await.json();
+    assert_eq!(product.quantity, Some(3.0));
+    assert_eq!(product.name.as_deref(), Some("Jablko"));
+
+    assert_eq!(app.delete(&format!("/products/{id}")).await.status, 204);
+    assert_eq!(app.get(&format!("/products/{id}")).await.status, 404);
+    assert_eq!(app.delete(&format!("/products/{id}")).await.status, 404);
+}
+
+#[tokio::test]
+async fn employee_create_search_update_delete_round_trip() {
+    let app = TestApp::spawn().await;
+
+    let res = app
+        .post(
+            "/employees",
+            r#"{"name":"Jana","surname":"Nová","position":"Pokladník","status":true}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+
+    let found: Vec<Employee> = app.post("/employees/search", r#"{"surname":"Nov"}"#).await.json();
+    assert_eq!(found.len(), 1);
+    let id = found[0].id.unwrap();
+
+    let res = app.put(&format!("/employees/{id}"), r#"{"shift":"Ranná"}"#).await;
+    assert_eq!(res.status, 200);
+
+    let employees: Vec<Employee> = app.get(&format!("/employees?id={id}")).await.json();
+    assert_eq!(employees[0].shift.as_deref(), Some("Ranná"));
+
+    assert_eq!(app.delete(&format!("/employees/{id}")).await.status, 204);
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    assert!(employees.is_empty());
+}
+
+#[tokio::test]
+async fn empty_text_filters_are_ignored() {
+    let app = TestApp::spawn().await;
+
+    let res = app
+        .post(
+            "/employees",
+            r#"{"name":"Peter","surname":"Malý","position":"Skladník","department":"Sklad",
+                "shift":"Nočná","email":"peter@obchod.sk","phone_number":"0900111222","status":true}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+    let res = app
+        .post(
+            "/products",
+            r#"{"name":"Soľ","category":"Koreniny","quantity":5,"status":true,"bar_code":7,
+                "cost_price":0.2,"sell_price":0.4,"brand":"Solivary","supplier":"Solivary a.s."}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+
+    let found: Vec<Employee> = app
+        .post(
+            "/employees/search",
+            r#"{"name":"","surname":"","position":"","department":"","shift":"",
+                "phone_number":"","email":"","note":""}"#,
+        )
+        .await
+        .json();
+    assert_eq!(found.len(), 1);
+
+    let found: Vec<Product> = app
+        .post(
+            "/products/search",
+            r#"{"name":"","category":"","description":"","brand":"","supplier":""}"#,
+        )
+        .await
+        .json();
+    assert_eq!(found.len(), 1);
+
+    let listed: Vec<Employee> = app.get("/employees?position=&shift=&email=").await.json();
+    assert_eq!(listed.len(), 1);
+    let listed: Vec<Product> = app.get("/products?category=&brand=&supplier=").await.json();
+    assert_eq!(listed.len(), 1);
+}
+
+#[tokio::test]
+async fn reactivate_by_supplier_with_and_without_quantity() {
+    let app = TestApp::spawn().await;
+    for (name, supplier) in [("Mlieko", "Rajo"), ("Maslo", "Rajo"), ("Chlieb", "Penam")] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":2,"status":false,"bar_code":1,
+                "cost_price":1.0,"sell_price":2.0,"supplier":"{supplier}","date_remove":"2024-01-01"}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+
+    let res = app.post("/products/reactivate-by-supplier", r#"{"supplier":"Rajo"}"#).await;
+    assert_eq!(res.json::<u64>(), 2);
+    let rajo: Vec<Product> = app.get("/products?supplier=Rajo").await.json();
+    assert!(rajo.iter().all(|p| p.status == Some(true) && p.date_remove.is_none() && p.quantity == Some(2.0)));
+
+    let res = app.post("/products/reactivate-by-supplier", r#"{"supplier":"Penam","quantity":30}"#).await;
+    assert_eq!(res.json::<u64>(), 1);
+    let penam: Vec<Product> = app.get("/products?supplier=Penam").await.json();
+    assert_eq!(penam[0].quantity, Some(30.0));
+    assert_eq!(penam[0].status, Some(true));
+}
+
+#[tokio::test]
+async fn export_downloads_only_filtered_products() {
+    let app = TestApp::spawn().await;
+    for (name, category) in [("Jablko", "Ovocie"), ("Hruška", "Ovocie"), ("Mrkva", "Zelenina")] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"{category}","quantity":5,"status":true,"bar_code":1,
+                "cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+
+    let res = app.post("/products/export", r#"{"category":"Ovocie"}"#).await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("content-type"), Some("application/json"));
+    let disposition = res.header("content-disposition").unwrap();
+    assert!(disposition.starts_with("attachment; filename=\"products-"), "{disposition}");
+
+    let exported: Vec<Product> = res.json();
+    let names: Vec<_> = exported.iter().map(|p| p.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Jablko", "Hruška"]);
+    assert!(exported.iter().all(|p| p.category.as_deref() == Some("Ovocie")));
+}
+
+#[tokio::test]
+async fn export_supports_byte_ranges() {
+    let app = TestApp::spawn().await;
+    for name in ["Jablko", "Hruška", "Slivka"] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Ovocie","quantity":5,"status":true,"bar_code":1,
+                "cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+
+    let full = app.post("/products/export", "{}").await;
+    assert_eq!(full.status, 200);
+    assert_eq!(full.header("accept-ranges"), Some("bytes"));
+    let etag = full.header("etag").unwrap().to_string();
+    let len = full.body.len();
+
+    let part = app.request_with_headers("POST", "/products/export", Some("{}"), &[("Range", "bytes=10-")]).await;
+    assert_eq!(part.status, 206);
+    assert_eq!(part.header("content-range"), Some(format!("bytes 10-{}/{len}", len - 1).as_str()));
+    assert_eq!(part.body, full.body[10..]);
+
+    let head = app.request_with_headers("POST", "/products/export", Some("{}"), &[("Range", "bytes=0-9"), ("If-Range", &etag)]).await;
+    assert_eq!(head.status, 206);
+    assert_eq!(head.body, full.body[..10]);
+
+    let stale = app.request_with_headers("POST", "/products/export", Some("{}"), &[("Range", "bytes=0-9"), ("If-Range", "\"iny\"")]).await;
+    assert_eq!(stale.status, 200);
+    assert_eq!(stale.body, full.body);
+
+    for bad in ["bytes=abc", "bytes=9-3", &format!("bytes={len}-")] {
+        let res = app.request_with_headers("POST", "/products/export", Some("{}"), &[("Range", bad)]).await;
+        assert_eq!(res.status, 416, "{bad}");
+        assert_eq!(res.header("content-range"), Some(format!("bytes */{len}").as_str()));
+    }
+}
+
+#[tokio::test]
+async fn link_header_has_next_only_before_last_page() {
+    let app = TestApp::spawn().await;
+    for name in ["Jablko", "Hruška", "Slivka"] {
+        let body = format!(r#"{{"name":"{name}","category":"Ovocie","quantity":1,"status":true,"bar_code":1,"cost_price":0.5,"sell_price":0.9}}"#);
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+
+    let res = app.get("/products?category=Ovocie&limit=2").await;
+    assert_eq!(res.json::<Vec<Product>>().len(), 2);
+    let link = res.header("link").unwrap();
+    assert!(link.contains(r#"</products?category=Ovocie&offset=2&limit=2>; rel="next""#), "{link}");
+    assert!(!link.contains(r#"rel="prev""#), "{link}");
+
+    let res = app.get("/products?category=Ovocie&limit=2&offset=2").await;
+    assert_eq!(res.json::<Vec<Product>>().len(), 1);
+    let link = res.header("link").unwrap();
+    assert!(!link.contains(r#"rel="next""#), "{link}");
+    assert!(link.contains(r#"offset=0&limit=2>; rel="prev""#), "{link}");
+    assert!(link.contains(r#"offset=2&limit=2>; rel="last""#), "{link}");
+}
+
+#[tokio::test]
+async fn responses_are_capped_at_max_result_rows() {
+    let app = TestApp::spawn_with_config(Config { max_result_rows: 2, ..Config::default() }).await;
+    for name in ["Jablko", "Hruška", "Slivka"] {
+        let body = format!(r#"{{"name":"{name}","category":"Ovocie","quantity":1,"status":true,"bar_code":1,"cost_price":0.5,"sell_price":0.9}}"#);
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+
+    for path in ["/products", "/products?limit=100"] {
+        let res = app.get(path).await;
+        assert_eq!(res.json::<Vec<Product>>().len(), 2, "{path}");
+        assert_eq!(res.header("x-result-truncated"), Some("true"), "{path}");
+        assert_eq!(res.header("x-total-count"), Some("3"), "{path}");
+        assert!(res.header("link").unwrap().contains(r#"rel="next""#), "{path}");
+    }
+
+    // klientom zvolená menšia stránka ani posledná stránka nie sú skrátené
+    assert_eq!(app.get("/products?limit=1").await.header("x-result-truncated"), None);
+    let res = app.get("/products?offset=2").await;
+    assert_eq!((res.json::<Vec<Product>>().len(), res.header("x-result-truncated")), (1, None));
+}
@@ -0,0 +1,44 @@
+use serde_json::Value;
+use store_manager::structs::DashboardSummary;
+use store_manager::test_support::TestApp;
+
+#[tokio::test]
+async fn dashboard_contains_all_sections() {
+    let app = TestApp::spawn().await;
+    let employee = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","status":true}"#;
+    assert_eq!(app.post("/employees", employee).await.status, 201);
+    for body in [
+        r#"{"name":"Kofola","category":"Nápoje","quantity":100,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#,
+        r#"{"name":"Chlieb","category":"Pečivo","quantity":2,"status":true,"bar_code":2,"cost_price":0.5,"sell_price":1.0}"#,
+        r#"{"name":"Syr","category":"Potraviny","quantity":4,"status":true,"bar_code":3,"cost_price":30.0,"sell_price":45.0}"#,
+    ] {
+        assert_eq!(app.post("/products", body).await.status, 201);
+    }
+    let order = r#"{"employee_id":1,"items":[{"product_id":2,"quantity":2}]}"#;
+    assert_eq!(app.post("/orders", order).await.status, 201);
+
+    let res = app.get("/dashboard").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let raw: Value = res.json();
+    for section in [
+        "generated_at",
+        "inventory",
+        "low_stock",
+        "out_of_stock",
+        "top_products",
+        "employees",
+        "recent_activity",
+    ] {
+        assert!(raw.get(section).is_some(), "chýba časť {section}: {raw}");
+    }
+
+    let summary: DashboardSummary = res.json();
+    assert_eq!(summary.inventory.products, 3);
+    assert_eq!(summary.inventory.total_value, 220.0);
+    assert_eq!((summary.low_stock, summary.out_of_stock), (2, 1));
+    let top: Vec<_> = summary.top_products.iter().map(|l| l.product_id).collect();
+    assert_eq!(top, [3, 1, 2]);
+    assert_eq!(summary.employees, 1);
+    assert_eq!(summary.recent_activity.len(), 1);
+    assert_eq!(summary.recent_activity[0].reason, "sale");
+}
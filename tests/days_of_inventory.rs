@@ -0,0 +1,52 @@
+use store_manager::structs::CategoryDaysOfInventory;
+use store_manager::test_support::TestApp;
+
+async fn product(app: &TestApp, name: &str, category: &str, quantity: u32, cost_price: f64, bar_code: u32) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"{category}","quantity":{quantity},"status":true,"bar_code":{bar_code},"cost_price":{cost_price},"sell_price":{}}}"#,
+        cost_price * 2.0
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn categories_are_sorted_from_slowest() {
+    let app = TestApp::spawn().await;
+    product(&app, "Ryža", "Potraviny", 40, 1.0, 1).await;
+    product(&app, "Kladivo", "Náradie", 20, 2.0, 2).await;
+    product(&app, "Mydlo", "Drogéria", 5, 1.0, 3).await;
+    product(&app, "Kofola", "Nápoje", 2, 1.0, 4).await;
+    product(&app, "Lopta", "Hračky", 0, 3.0, 5).await;
+
+    // Potraviny: zásoby 10, náklady 30 za 30 dní → 10 dní
+    app.sell(1, 30.0).await;
+    // Náradie: zásoby 17 × 2 = 34, náklady 6 za 30 dní → 170 dní
+    app.sell(2, 3.0).await;
+    // Nápoje: všetko predané → 0 dní
+    app.sell(4, 2.0).await;
+
+    let days = app.db.days_of_inventory_by_category().await.unwrap();
+    let names: Vec<&str> = days.iter().map(|(c, _)| c.as_str()).collect();
+    // Drogéria sa nepredáva, Hračky nemajú zásoby ani predaj
+    assert_eq!(names, ["Drogéria", "Náradie", "Potraviny", "Nápoje"]);
+    assert_eq!(days[0].1, f64::INFINITY);
+    assert!((days[1].1 - 170.0).abs() < 1e-9, "{days:?}");
+    assert!((days[2].1 - 10.0).abs() < 1e-9, "{days:?}");
+    assert_eq!(days[3].1, 0.0);
+
+    let res = app.get("/stats/days-of-inventory").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: Vec<CategoryDaysOfInventory> = res.json();
+    assert_eq!(report[0].category, "Drogéria");
+    assert_eq!(report[0].days, None);
+    assert!(report[1].days.is_some_and(|d| (d - 170.0).abs() < 1e-9));
+    assert_eq!(report.len(), 4);
+}
+
+#[tokio::test]
+async fn empty_store_has_no_categories() {
+    let app = TestApp::spawn().await;
+    assert!(app.db.days_of_inventory_by_category().await.unwrap().is_empty());
+    let report: Vec<CategoryDaysOfInventory> = app.get("/stats/days-of-inventory").await.json();
+    assert!(report.is_empty());
+}
@@ -0,0 +1,68 @@
+use store_manager::structs::DeadStockReport;
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, quantity: u32, cost_price: f64, extra: &str) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":{cost_price},"sell_price":20.0{extra}}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+/// Kofola a Vinea stoja od roku 2020, Rajec sa predal a Voda naskladnila dnes,
+/// Tonik je nový, Kinley nie je na sklade.
+async fn spawn_with_stock() -> TestApp {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","status":true}"#;
+    assert_eq!(app.post("/employees", body).await.status, 201);
+    let old = r#","date_added":"2020-01-01""#;
+    add_product(&app, "Kofola", 10, 2.0, r#","date_added":"2020-01-01","employee_id":1"#).await;
+    add_product(&app, "Vinea", 5, 10.0, old).await;
+    add_product(&app, "Rajec", 5, 1.0, old).await;
+    add_product(&app, "Voda", 5, 1.0, old).await;
+    add_product(&app, "Tonik", 5, 1.0, "").await;
+    add_product(&app, "Kinley", 0, 1.0, old).await;
+
+    let res = app.post("/orders", r#"{"created_at":"2020-06-01T10:00:00","items":[{"product_id":2,"quantity":1}]}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    assert_eq!(app.post("/orders", r#"{"items":[{"product_id":3,"quantity":1}]}"#).await.status, 201);
+    assert_eq!(app.post("/products/4/adjust-stock", r#"{"delta":3}"#).await.status, 200);
+    app
+}
+
+#[tokio::test]
+async fn dead_stock_lists_products_without_recent_movement_by_value() {
+    let app = spawn_with_stock().await;
+
+    let res = app.get("/reports/dead-stock").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: DeadStockReport = res.json();
+    assert_eq!(report.days, 90);
+    let lines: Vec<_> = report.lines.iter().map(|l| (l.name.as_deref().unwrap(), l.quantity, l.value)).collect();
+    assert_eq!(lines, [("Vinea", 4.0, 40.0), ("Kofola", 10.0, 20.0)]);
+    assert_eq!(report.total_value, 60.0);
+
+    let vinea = &report.lines[0];
+    assert_eq!(vinea.last_movement.unwrap().to_string(), "2020-06-01");
+    assert!(vinea.days_since_movement.unwrap() > 90);
+    let kofola = &report.lines[1];
+    assert_eq!(kofola.last_movement.unwrap().to_string(), "2020-01-01");
+    assert_eq!((kofola.employee_id, kofola.employee_name.as_deref()), (Some(1), Some("Jana Nová")));
+}
+
+#[tokio::test]
+async fn dead_stock_exports_csv_and_validates_window() {
+    let app = spawn_with_stock().await;
+
+    let res = app.get("/reports/dead-stock?days=30&format=csv").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("text/csv; charset=utf-8"));
+    let csv = res.text();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "product_id,name,category,quantity,unit,value,last_movement,days_since_movement,employee");
+    assert!(rows[1].starts_with("2,Vinea,Nápoje,4,piece,40.00,2020-06-01,"), "{csv}");
+    assert!(rows[2].ends_with(",Jana Nová"), "{csv}");
+    assert_eq!(rows.len(), 3);
+
+    assert_eq!(app.get("/reports/dead-stock?days=0").await.status, 400);
+    assert_eq!(app.get("/reports/dead-stock?days=-1").await.status, 400);
+}
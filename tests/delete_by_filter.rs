@@ -0,0 +1,70 @@
+use store_manager::structs::{DeletionReport, Product};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, supplier) in [("Kofola", "Kofola a.s."), ("Vinea", "Kofola a.s."), ("Chlieb", "Pekáreň")] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Nápoje","quantity":5,"status":true,"bar_code":1,"supplier":"{supplier}","cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+async fn remaining(app: &TestApp) -> Vec<u32> {
+    app.get("/products").await.json::<Vec<Product>>().iter().filter_map(|p| p.id).collect()
+}
+
+#[tokio::test]
+async fn matching_products_are_deleted_and_audited() {
+    let app = spawn_with_products().await;
+
+    let res = app.post("/products/delete-by-filter", r#"{"filter":{"supplier":"Kofola a.s."}}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: DeletionReport = res.json();
+    assert_eq!(report, DeletionReport { deleted: 2, ids: vec![1, 2] });
+    assert_eq!(remaining(&app).await, [3]);
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "delete_products_by_filter");
+    assert_eq!(audit[0].details.as_deref(), Some("deleted=2, ids=1,2"));
+
+    let report: DeletionReport = app.post("/products/delete-by-filter", r#"{"filter":{"name":"Mlieko"}}"#).await.json();
+    assert_eq!(report.deleted, 0);
+}
+
+#[tokio::test]
+async fn empty_filter_requires_confirmation() {
+    let app = spawn_with_products().await;
+
+    for body in ["{}", r#"{"filter":{}}"#, r#"{"filter":{"name":"","supplier":""}}"#, r#"{"confirm_all":false}"#] {
+        let res = app.post("/products/delete-by-filter", body).await;
+        assert_eq!(res.status, 400, "{body}: {}", res.text());
+        assert!(res.text().contains("confirm_all"), "{}", res.text());
+    }
+    assert_eq!(remaining(&app).await, [1, 2, 3]);
+
+    let report: DeletionReport = app.post("/products/delete-by-filter", r#"{"confirm_all":true}"#).await.json();
+    assert_eq!(report.deleted, 3);
+    assert!(remaining(&app).await.is_empty());
+}
+
+#[tokio::test]
+async fn bundle_component_blocks_the_whole_deletion() {
+    let app = spawn_with_products().await;
+    let bundle = r#"{"name":"Balík","category":"Sady","quantity":0,"status":true,"bar_code":9,"is_bundle":true,"cost_price":1.0,"sell_price":3.0}"#;
+    assert_eq!(app.post("/products", bundle).await.status, 201);
+    let res = app.put("/products/4/components", r#"[{"component_product_id":1,"quantity":2}]"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+
+    let res = app.post("/products/delete-by-filter", r#"{"filter":{"supplier":"Kofola a.s."}}"#).await;
+    assert_eq!(res.status, 409, "{}", res.text());
+    assert_eq!(remaining(&app).await, [1, 2, 3, 4]);
+
+    // sada sa maže pred svojimi zložkami
+    let report: DeletionReport = app.post("/products/delete-by-filter", r#"{"filter":{"sell_price":3.0}}"#).await.json();
+    assert_eq!(report.ids, [4]);
+    let report: DeletionReport = app.post("/products/delete-by-filter", r#"{"filter":{"bar_code":1}}"#).await.json();
+    assert_eq!(report.ids, [1, 2, 3]);
+}
@@ -0,0 +1,141 @@
+use serde_json::Value;
+use store_manager::config::Config;
+use store_manager::db::StoreDB;
+use store_manager::db_filler::DBFiller;
+use store_manager::structs::{DeltaApplyReport, Employee, Product};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_admin_app() -> TestApp {
+    TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await
+}
+
+async fn admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+fn product(name: &str, quantity: u32) -> String {
+    format!(
+        r#"{{"name":"{name}","category":"Pečivo","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+    )
+}
+
+fn employee(name: &str) -> String {
+    format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true,"phone_number":"0901 123 456"}}"#)
+}
+
+/// Zamestnanci a produkty databázy bez času zmeny (ten sa pri prenose mení).
+async fn snapshot(db: &StoreDB) -> (Vec<Value>, Vec<Value>) {
+    fn strip<T: serde::Serialize>(records: Vec<T>) -> Vec<Value> {
+        records
+            .into_iter()
+            .map(|record| {
+                let mut value = serde_json::to_value(record).unwrap();
+                value.as_object_mut().unwrap().remove("updated_at");
+                value
+            })
+            .collect()
+    }
+    let employees = db.get_employees(Employee::new_empty()).await.unwrap();
+    let products = db.get_products(Product::new_empty()).await.unwrap();
+    (strip(employees), strip(products))
+}
+
+/// Prenesie zmeny od `since` zo zdroja do cieľa a vráti `since` ďalšieho kroku.
+async fn sync_step(source: &TestApp, target: &TestApp, since: &str) -> (String, DeltaApplyReport) {
+    let res = admin(source, "GET", &format!("/admin/export?since={since}"), None).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert!(res.header("content-disposition").unwrap().contains("store_data-delta-all.json"));
+    let delta: Value = res.json();
+    assert_eq!(delta["version"], 1);
+
+    let res = admin(target, "POST", "/admin/import-delta", Some(&res.text())).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(snapshot(&source.db).await, snapshot(&target.db).await);
+
+    // zmeny v ďalšom kroku musia mať neskorší čas ako `until`
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    (delta["until"].as_str().unwrap().to_string(), res.json())
+}
+
+#[tokio::test]
+async fn chained_deltas_keep_target_in_sync() {
+    let source = spawn_admin_app().await;
+    let target = spawn_admin_app().await;
+    for name in ["Chlieb", "Rožok", "Bageta"] {
+        assert_eq!(admin(&source, "POST", "/products", Some(&product(name, 5))).await.status, 201);
+    }
+    for name in ["Jana", "Eva"] {
+        assert_eq!(admin(&source, "POST", "/employees", Some(&employee(name))).await.status, 201);
+    }
+
+    let (since, report) = sync_step(&source, &target, "1970-01-01T00:00:00Z").await;
+    assert_eq!(report, DeltaApplyReport { employees: 2, products: 3, ..Default::default() });
+
+    // bez zmien je rozdiel prázdny
+    let (since, report) = sync_step(&source, &target, &since).await;
+    assert_eq!(report, DeltaApplyReport::default());
+
+    // zmena, nový záznam a zmazanie
+    assert_eq!(admin(&source, "PUT", "/products/1", Some(&product("Chlieb tmavý", 2))).await.status, 200);
+    assert_eq!(admin(&source, "POST", "/products", Some(&product("Croissant", 7))).await.status, 201);
+    assert_eq!(admin(&source, "DELETE", "/products/2", None).await.status, 204);
+    assert_eq!(admin(&source, "DELETE", "/employees/1", None).await.status, 204);
+    let (since, report) = sync_step(&source, &target, &since).await;
+    assert_eq!(
+        report,
+        DeltaApplyReport { employees: 0, products: 2, deleted_employees: 1, deleted_products: 1 }
+    );
+
+    // zamestnanec vytvorený a zmazaný v jednom kroku sa v cieli nezmaže (nikdy tam nebol)
+    assert_eq!(admin(&source, "DELETE", "/products/4", None).await.status, 204);
+    assert_eq!(admin(&source, "POST", "/products", Some(&product("Koláč", 3))).await.status, 201);
+    assert_eq!(admin(&source, "POST", "/employees", Some(&employee("Mária"))).await.status, 201);
+    assert_eq!(admin(&source, "DELETE", "/employees/3", None).await.status, 204);
+    assert_eq!(admin(&source, "POST", "/employees", Some(&employee("Zuzana"))).await.status, 201);
+    let (_, report) = sync_step(&source, &target, &since).await;
+    assert_eq!(
+        report,
+        DeltaApplyReport { employees: 1, products: 1, deleted_employees: 0, deleted_products: 1 }
+    );
+    let employees: Vec<_> =
+        target.db.get_employees(Employee::new_empty()).await.unwrap().into_iter().map(|e| (e.id, e.name)).collect();
+    assert_eq!(employees, [(Some(2), Some("Eva".into())), (Some(4), Some("Zuzana".into()))]);
+    let names: Vec<_> = target.db.get_products(Product::new_empty()).await.unwrap().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, [Some("Chlieb tmavý".into()), Some("Bageta".into()), Some("Koláč".into())]);
+}
+
+#[tokio::test]
+async fn delta_with_unsupported_version_is_rejected() {
+    let target = spawn_admin_app().await;
+    for body in [
+        r#"{"version":2,"since":"2024-06-01T00:00:00Z","until":"2024-06-02T00:00:00Z"}"#,
+        r#"{"since":"2024-06-01T00:00:00Z","until":"2024-06-02T00:00:00Z"}"#,
+        "nie je json",
+    ] {
+        assert_eq!(admin(&target, "POST", "/admin/import-delta", Some(body)).await.status, 400, "{body}");
+    }
+    assert_eq!(admin(&target, "GET", "/admin/export?since=2024-06-01T00:00:00Z&anonymize=true", None).await.status, 400);
+    assert_eq!(admin(&target, "GET", "/admin/export?since=vcera", None).await.status, 400);
+}
+
+#[tokio::test]
+async fn delta_file_round_trip() {
+    let source = spawn_admin_app().await;
+    let target = spawn_admin_app().await;
+    assert_eq!(admin(&source, "POST", "/products", Some(&product("Chlieb", 5))).await.status, 201);
+    assert_eq!(admin(&source, "POST", "/employees", Some(&employee("Jana"))).await.status, 201);
+
+    let path = std::env::temp_dir().join(format!("store_manager_delta_{}.json", std::process::id()));
+    let path = path.to_str().unwrap();
+    DBFiller::export_changes_since(&source.db, "2024-06-01T00:00:00Z".parse().unwrap(), path).await.unwrap();
+    let report = DBFiller::apply_changes_from_json(&target.db, path).await.unwrap();
+    assert_eq!(report, DeltaApplyReport { employees: 1, products: 1, ..Default::default() });
+
+    // opakované premietnutie nič nezmení
+    DBFiller::apply_changes_from_json(&target.db, path).await.unwrap();
+    let _ = std::fs::remove_file(path);
+    assert_eq!(snapshot(&source.db).await, snapshot(&target.db).await);
+    assert_eq!(target.db.get_products(Product::new_empty()).await.unwrap()[0].quantity, Some(5.0));
+}
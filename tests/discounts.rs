@@ -0,0 +1,51 @@
+use chrono::{Days, Local};
+use store_manager::structs::DiscountReport;
+use store_manager::test_support::TestApp;
+
+async fn sell(app: &TestApp, product_id: u32, quantity: u32, unit_price: Option<f64>, created_at: Option<&str>) {
+    let price = unit_price.map(|p| format!(r#","unit_price":{p}"#)).unwrap_or_default();
+    let created_at = created_at.map(|c| format!(r#""created_at":"{c}","#)).unwrap_or_default();
+    let body = format!(r#"{{{created_at}"items":[{{"product_id":{product_id},"quantity":{quantity}{price}}}]}}"#);
+    let res = app.post("/orders", &body).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+}
+
+#[tokio::test]
+async fn discounts_sum_regular_minus_charged_price_per_category() {
+    let app = TestApp::spawn().await;
+    for body in [
+        r#"{"name":"Kofola","category":"Nápoje","quantity":100,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#,
+        r#"{"name":"Chlieb","category":"Pečivo","quantity":100,"bar_code":2,"cost_price":0.5,"sell_price":1.0}"#,
+    ] {
+        assert_eq!(app.post("/products", body).await.status, 201);
+    }
+    let today = Local::now().date_naive();
+    let ends = today.checked_add_days(Days::new(7)).unwrap();
+    let sale = format!(r#"{{"sale_price":1.5,"ends":"{ends}"}}"#);
+    assert_eq!(app.post("/products/1/sale", &sale).await.status, 200);
+
+    // akciová cena sa použije sama: zľava 4 × 0.50
+    sell(&app, 1, 4, None, None).await;
+    // ručne zadaná cena: zľava 2 × 1.00; predaj nad bežnú cenu zľavu nemá
+    sell(&app, 1, 2, Some(1.0), None).await;
+    sell(&app, 1, 1, Some(2.5), None).await;
+    // plná cena
+    sell(&app, 2, 3, None, None).await;
+    // mimo obdobia
+    sell(&app, 1, 5, Some(0.5), Some("2020-01-10T10:00:00")).await;
+
+    let res = app.get(&format!("/stats/discounts?from={today}&to={today}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: DiscountReport = res.json();
+    assert!((report.discount - 4.0).abs() < 1e-9, "{report:?}");
+    assert_eq!((report.units, report.discounted_units), (10.0, 6.0));
+
+    let categories: Vec<_> = report.categories.iter().map(|c| (c.category.as_str(), c.discounted_units, c.revenue, c.discount)).collect();
+    assert_eq!(categories, [("Nápoje", 6.0, 10.5, 4.0), ("Pečivo", 0.0, 3.0, 0.0)]);
+
+    let old: DiscountReport = app.get("/stats/discounts?from=2020-01-01&to=2020-01-31").await.json();
+    assert_eq!(old.discount, 7.5);
+    let empty: DiscountReport = app.get("/stats/discounts?from=2019-01-01&to=2019-01-31").await.json();
+    assert!(empty.categories.is_empty() && empty.discount == 0.0);
+    assert_eq!(app.get("/stats/discounts?from=2020-02-01&to=2020-01-01").await.status, 400);
+}
@@ -0,0 +1,62 @@
+use store_manager::pricing;
+use store_manager::structs::PriceElasticity;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_product() -> TestApp {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Kofola","category":"Nápoje","quantity":1000,"bar_code":0,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    app
+}
+
+#[tokio::test]
+async fn elasticity_compares_daily_sales_at_two_prices() {
+    let app = spawn_with_product().await;
+    // za 2.00 € 10 ks za 2 dni (5/deň), po zdražení na 2.50 € 6 ks za 3 dni (2/deň)
+    app.sell_at(1, 6.0, Some("2024-03-01T10:00:00")).await;
+    app.sell_at(1, 4.0, Some("2024-03-02T10:00:00")).await;
+    assert_eq!(app.put("/products/1", r#"{"sell_price":2.5}"#).await.status, 200);
+    app.sell_at(1, 3.0, Some("2024-03-03T10:00:00")).await;
+    app.sell_at(1, 3.0, Some("2024-03-05T10:00:00")).await;
+    // mimo obdobia
+    app.sell_at(1, 50.0, Some("2024-04-01T10:00:00")).await;
+
+    let res = app.get("/products/1/elasticity?from=2024-03-01&to=2024-03-31").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: PriceElasticity = res.json();
+    let points: Vec<_> = report.points.iter().map(|p| (p.price, p.units, p.daily_units)).collect();
+    assert_eq!(points, [(2.0, 10.0, 5.0), (2.5, 6.0, 2.0)]);
+
+    let expected = (2.0f64 / 5.0).ln() / 1.25f64.ln();
+    assert!((report.elasticity.unwrap() - expected).abs() < 1e-9, "{:?}", report.elasticity);
+    assert_eq!(report.elastic, Some(true));
+    assert!(report.note.is_none());
+}
+
+#[tokio::test]
+async fn single_price_has_no_estimate() {
+    let app = spawn_with_product().await;
+    app.sell_at(1, 6.0, Some("2024-03-01T10:00:00")).await;
+    app.sell_at(1, 2.0, Some("2024-03-04T10:00:00")).await;
+
+    let report: PriceElasticity = app.get("/products/1/elasticity?from=2024-03-01&to=2024-03-31").await.json();
+    assert_eq!(report.points.len(), 1);
+    assert_eq!(report.points[0].daily_units, 2.0);
+    assert_eq!((report.elasticity, report.elastic), (None, None));
+    assert!(report.note.unwrap().contains("aspoň za 2 rôzne ceny"));
+
+    assert_eq!(app.get("/products/2/elasticity?from=2024-03-01&to=2024-03-31").await.status, 404);
+    assert_eq!(app.get("/products/1/elasticity?from=2024-03-31&to=2024-03-01").await.status, 400);
+}
+
+#[test]
+fn regression_slope_over_log_prices_and_sales() {
+    // predaj klesá presne úmerne cene → elasticita −1
+    let e = pricing::elasticity(&[(1.0, 8.0), (2.0, 4.0), (4.0, 2.0)]).unwrap();
+    assert!((e + 1.0).abs() < 1e-9);
+    let e = pricing::elasticity(&[(1.0, 3.0), (2.0, 3.0), (3.0, 3.0)]).unwrap();
+    assert!(e.abs() < 1e-9);
+    // bez predaja alebo s jedinou cenou sa odhadnúť nedá
+    assert_eq!(pricing::elasticity(&[(1.0, 3.0), (2.0, 0.0)]), None);
+    assert_eq!(pricing::elasticity(&[(2.0, 3.0), (2.0, 5.0)]), None);
+}
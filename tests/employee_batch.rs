@@ -0,0 +1,42 @@
+use serde_json::json;
+use store_manager::structs::EmployeeBatch;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_employees() -> TestApp {
+    let app = TestApp::spawn().await;
+    for name in ["Jana", "Eva", "Mária"] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    app
+}
+
+async fn batch(app: &TestApp, ids: &[u32]) -> EmployeeBatch {
+    let res = app.post("/employees/batch", &json!({ "ids": ids }).to_string()).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn returns_employees_in_request_order_and_reports_missing() {
+    let app = spawn_with_employees().await;
+    let result = batch(&app, &[3, 1, 9, 3]).await;
+    let ids: Vec<_> = result.employees.iter().map(|e| e.id.unwrap()).collect();
+    assert_eq!(ids, [3, 1]);
+    assert_eq!(result.employees[0].name.as_deref(), Some("Mária"));
+    assert_eq!(result.not_found, [9]);
+
+    let empty = batch(&app, &[]).await;
+    assert!(empty.employees.is_empty() && empty.not_found.is_empty());
+}
+
+#[tokio::test]
+async fn long_id_list_is_split_into_several_queries() {
+    let app = spawn_with_employees().await;
+    let ids: Vec<u32> = (1..=1500).rev().collect();
+    let result = batch(&app, &ids).await;
+    let found: Vec<_> = result.employees.iter().map(|e| e.id.unwrap()).collect();
+    assert_eq!(found, [3, 2, 1]);
+    assert_eq!(result.not_found.len(), 1497);
+    assert!(!result.not_found.contains(&2));
+}
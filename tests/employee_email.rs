@@ -0,0 +1,42 @@
+use store_manager::structs::Employee;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_employee() -> TestApp {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Jana","surname":"Nová","position":"Pokladník","status":true,"email":"Jana.Nova@Obchod.sk"}"#;
+    assert_eq!(app.post("/employees", body).await.status, 201);
+    let body = r#"{"name":"Peter","surname":"Starý","position":"Skladník","status":true,"email":"peter@obchod.sk"}"#;
+    assert_eq!(app.post("/employees", body).await.status, 201);
+    app
+}
+
+#[tokio::test]
+async fn employee_is_found_by_exact_email() {
+    let app = spawn_with_employee().await;
+    let res = app.get("/employees/by-email?email=peter@obchod.sk").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let employee: Employee = res.json();
+    assert_eq!((employee.id, employee.name.as_deref()), (Some(2), Some("Peter")));
+
+    let found = app.db.get_employee_by_email("jana.nova@obchod.sk").await.unwrap();
+    assert_eq!(found.unwrap().id, Some(1));
+}
+
+#[tokio::test]
+async fn email_lookup_ignores_case_and_surrounding_spaces() {
+    let app = spawn_with_employee().await;
+    let employee: Employee = app.get("/employees/by-email?email=%20JANA.NOVA@obchod.SK%20").await.json();
+    assert_eq!(employee.surname.as_deref(), Some("Nová"));
+    // e-mail sa uloží tak, ako bol zadaný
+    assert_eq!(employee.email.as_deref(), Some("Jana.Nova@Obchod.sk"));
+}
+
+#[tokio::test]
+async fn unknown_or_partial_email_is_not_found() {
+    let app = spawn_with_employee().await;
+    assert_eq!(app.get("/employees/by-email?email=nikto@obchod.sk").await.status, 404);
+    assert_eq!(app.get("/employees/by-email?email=peter").await.status, 404);
+    assert_eq!(app.get("/employees/by-email?email=").await.status, 400);
+    assert_eq!(app.get("/employees/by-email").await.status, 400);
+    assert!(app.db.get_employee_by_email("nikto@obchod.sk").await.unwrap().is_none());
+}
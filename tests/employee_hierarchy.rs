@@ -0,0 +1,102 @@
+use serde_json::Value;
+use store_manager::config::Config;
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_admin_app() -> TestApp {
+    TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await
+}
+
+async fn admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+fn employee(name: &str, manager_id: Option<u32>) -> String {
+    let manager = manager_id.map(|m| format!(r#","manager_id":{m}"#)).unwrap_or_default();
+    format!(
+        r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true,"phone_number":"0901 123 456"{manager}}}"#
+    )
+}
+
+/// Mená zamestnancov z odpovede v poradí, v akom prišli.
+fn names(res: &TestResponse) -> Vec<String> {
+    assert_eq!(res.status, 200, "{}", res.text());
+    let employees: Vec<Value> = res.json();
+    employees.iter().map(|e| e["name"].as_str().unwrap().to_string()).collect()
+}
+
+async fn manager_of(app: &TestApp, id: u32) -> Value {
+    let employees: Vec<Value> = admin(app, "GET", "/employees", None).await.json();
+    employees.into_iter().find(|e| e["id"] == id).unwrap()["manager_id"].clone()
+}
+
+/// Riaditeľka (1) → vedúca (2) → predavačky (3, 4); vedúca (5) priamo pod riaditeľkou.
+async fn spawn_hierarchy() -> TestApp {
+    let app = spawn_admin_app().await;
+    for (name, manager) in
+        [("Riaditeľka", None), ("Vedúca", Some(1)), ("Jana", Some(2)), ("Eva", Some(2)), ("Mária", Some(1))]
+    {
+        let res = admin(&app, "POST", "/employees", Some(&employee(name, manager))).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+    app
+}
+
+#[tokio::test]
+async fn reports_and_chain_follow_the_hierarchy() {
+    let app = spawn_hierarchy().await;
+    assert_eq!(manager_of(&app, 3).await, 2);
+    assert_eq!(manager_of(&app, 1).await, Value::Null);
+
+    assert_eq!(names(&admin(&app, "GET", "/employees/1/reports", None).await), ["Vedúca", "Mária"]);
+    assert_eq!(
+        names(&admin(&app, "GET", "/employees/1/reports?all=true", None).await),
+        ["Vedúca", "Mária", "Jana", "Eva"]
+    );
+    assert!(names(&admin(&app, "GET", "/employees/4/reports", None).await).is_empty());
+
+    assert_eq!(names(&admin(&app, "GET", "/employees/3/chain", None).await), ["Vedúca", "Riaditeľka"]);
+    assert!(names(&admin(&app, "GET", "/employees/1/chain", None).await).is_empty());
+
+    assert_eq!(admin(&app, "GET", "/employees/99/reports", None).await.status, 404);
+    assert_eq!(admin(&app, "GET", "/employees/99/chain", None).await.status, 404);
+}
+
+#[tokio::test]
+async fn cycles_and_unknown_managers_are_rejected() {
+    let app = spawn_hierarchy().await;
+    // sám sebe nadriadeným
+    assert_eq!(admin(&app, "PUT", "/employees/2", Some(&employee("Vedúca", Some(2)))).await.status, 422);
+    // nepriamy cyklus: riaditeľka pod predavačkou
+    assert_eq!(admin(&app, "PUT", "/employees/1", Some(&employee("Riaditeľka", Some(3)))).await.status, 422);
+    let res = admin(&app, "POST", "/employees/bulk-update", Some(r#"{"ids":[1,5],"set":{"manager_id":4}}"#)).await;
+    assert_eq!(res.status, 422, "{}", res.text());
+    assert_eq!(manager_of(&app, 5).await, 1);
+
+    assert_eq!(admin(&app, "POST", "/employees", Some(&employee("Zuzana", Some(99)))).await.status, 400);
+    assert_eq!(admin(&app, "PUT", "/employees/3", Some(&employee("Jana", Some(99)))).await.status, 400);
+
+    // presun v rámci stromu bez cyklu je v poriadku, 0 nadriadeného odstráni
+    assert_eq!(admin(&app, "PUT", "/employees/3", Some(&employee("Jana", Some(5)))).await.status, 200);
+    assert_eq!(manager_of(&app, 3).await, 5);
+    assert_eq!(admin(&app, "PUT", "/employees/3", Some(&employee("Jana", Some(0)))).await.status, 200);
+    assert_eq!(manager_of(&app, 3).await, Value::Null);
+}
+
+#[tokio::test]
+async fn offboarded_manager_hands_reports_to_own_manager() {
+    let app = spawn_hierarchy().await;
+    // ukončený pracovný pomer vedúcej: podriadení prejdú pod riaditeľku
+    let res = admin(&app, "PUT", "/employees/2", Some(r#"{"status":false}"#)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(manager_of(&app, 3).await, 1);
+    assert_eq!(manager_of(&app, 4).await, 1);
+    assert_eq!(manager_of(&app, 2).await, 1);
+
+    // zmazanie riaditeľky bez nadriadeného podriadeným nadriadeného odstráni
+    assert_eq!(admin(&app, "DELETE", "/employees/1", None).await.status, 204);
+    for id in [2, 3, 4, 5] {
+        assert_eq!(manager_of(&app, id).await, Value::Null, "{id}");
+    }
+}
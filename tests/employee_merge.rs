@@ -0,0 +1,96 @@
+use serde_json::Value;
+use store_manager::config::Config;
+use store_manager::structs::{Employee, Product};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_admin_app() -> TestApp {
+    TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await
+}
+
+async fn admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+fn product(name: &str, employee_id: u32) -> String {
+    format!(
+        r#"{{"name":"{name}","category":"Pečivo","quantity":1,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0,"employee_id":{employee_id}}}"#
+    )
+}
+
+async fn merge(app: &TestApp, keep_id: u32, merge_id: u32) -> TestResponse {
+    let body = format!(r#"{{"keep_id":{keep_id},"merge_id":{merge_id}}}"#);
+    admin(app, "POST", "/employees/merge", Some(&body)).await
+}
+
+async fn employee(app: &TestApp, id: u32) -> Option<Employee> {
+    app.db.get_employees(Employee::new_empty()).await.unwrap().into_iter().find(|e| e.id == Some(id))
+}
+
+#[tokio::test]
+async fn merge_reassigns_products_and_fills_missing_fields() {
+    let app = spawn_admin_app().await;
+    for body in [
+        r#"{"name":"Eva","surname":"Vedúca","position":"Vedúca","status":true,"phone_number":"0902 000 001"}"#,
+        r#"{"name":"Jana","surname":"Nová","position":"Predavačka","status":true}"#,
+        r#"{"name":"Jana","surname":"Nováková","position":"Pokladníčka","phone_number":"0901 123 456","email":"jana@obchod.sk","salary":1200.0,"manager_id":1}"#,
+        r#"{"name":"Mária","surname":"Nová","position":"Predavačka","status":true,"phone_number":"0902 000 002","manager_id":3}"#,
+    ] {
+        let res = admin(&app, "POST", "/employees", Some(body)).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+    for (name, owner) in [("Chlieb", 3), ("Rožok", 3), ("Bageta", 2), ("Koláč", 1)] {
+        assert_eq!(admin(&app, "POST", "/products", Some(&product(name, owner))).await.status, 201);
+    }
+
+    let res = merge(&app, 2, 3).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<Value>(), 2);
+
+    assert!(employee(&app, 3).await.is_none());
+    let kept = employee(&app, 2).await.unwrap();
+    // vlastné hodnoty sa neprepíšu, chýbajúce sa doplnia
+    assert_eq!(kept.surname.as_deref(), Some("Nová"));
+    assert_eq!(kept.position.as_deref(), Some("Predavačka"));
+    assert_eq!(kept.email.as_deref(), Some("jana@obchod.sk"));
+    assert_eq!(kept.phone_number.as_deref(), Some("0901 123 456"));
+    assert_eq!(kept.phone_e164.as_deref(), Some("+421901123456"));
+    assert_eq!(kept.salary, Some(1200.0));
+    assert_eq!(kept.manager_id, Some(1));
+    // podriadení zlúčeného prejdú na ponechaného
+    assert_eq!(employee(&app, 4).await.unwrap().manager_id, Some(2));
+
+    let owners: Vec<_> = app
+        .db
+        .get_products(Product::new_empty())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| (p.name.unwrap(), p.employee_id))
+        .collect();
+    assert_eq!(
+        owners,
+        [("Chlieb".into(), Some(2)), ("Rožok".into(), Some(2)), ("Bageta".into(), Some(2)), ("Koláč".into(), Some(1))]
+    );
+}
+
+#[tokio::test]
+async fn invalid_merges_are_rejected() {
+    let app = spawn_admin_app().await;
+    for name in ["Jana", "Eva"] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true,"phone_number":"0901 123 456"}}"#);
+        assert_eq!(admin(&app, "POST", "/employees", Some(&body)).await.status, 201);
+    }
+    assert_eq!(merge(&app, 1, 1).await.status, 400);
+    assert_eq!(merge(&app, 1, 99).await.status, 404);
+    assert_eq!(merge(&app, 99, 1).await.status, 404);
+
+    // obaja majú otvorenú dochádzku: zlúčenie by porušilo jednu otvorenú smenu
+    for id in [1, 2] {
+        let res = admin(&app, "POST", &format!("/employees/{id}/clock-in"), None).await;
+        assert!(res.status < 300, "{}", res.text());
+    }
+    assert_eq!(merge(&app, 1, 2).await.status, 409);
+    assert!(employee(&app, 2).await.is_some());
+}
@@ -0,0 +1,86 @@
+use serde_json::Value;
+use store_manager::config::Config;
+use store_manager::structs::{DataImportReport, Employee, ImportReport, Product};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+const PRODUCT: &str = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+const EMPLOYEE: &str = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","status":true}"#;
+
+async fn spawn_admin_app() -> TestApp {
+    TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await
+}
+
+async fn admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+async fn seeded_app() -> TestApp {
+    let app = spawn_admin_app().await;
+    assert_eq!(admin(&app, "POST", "/products", Some(PRODUCT)).await.status, 201);
+    assert_eq!(admin(&app, "POST", "/employees", Some(EMPLOYEE)).await.status, 201);
+    app
+}
+
+#[tokio::test]
+async fn products_only_export_marks_employees_as_omitted() {
+    let app = seeded_app().await;
+
+    let res = admin(&app, "GET", "/admin/export?only=products", None).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert!(res.header("content-disposition").unwrap().contains("store_data-products.json"));
+    let data: Value = res.json();
+    assert_eq!(data["products"].as_array().unwrap().len(), 1);
+    assert_eq!(data["employees"], Value::Array(Vec::new()));
+    assert_eq!(data["omitted"], serde_json::json!(["employees"]));
+
+    let data: Value = admin(&app, "GET", "/admin/export", None).await.json();
+    assert_eq!(data["employees"].as_array().unwrap().len(), 1);
+    assert!(data.get("omitted").is_none());
+
+    assert_eq!(admin(&app, "GET", "/admin/export?only=orders", None).await.status, 400);
+}
+
+#[tokio::test]
+async fn employees_only_import_leaves_products_untouched() {
+    let source = seeded_app().await;
+    let export = admin(&source, "GET", "/admin/export", None).await.body;
+
+    let app = spawn_admin_app().await;
+    let kept = r#"{"name":"Mlieko","category":"Potraviny","quantity":3,"status":true,"bar_code":2,"cost_price":0.5,"sell_price":0.9}"#;
+    assert_eq!(admin(&app, "POST", "/products", Some(kept)).await.status, 201);
+    let before: Vec<Product> = app.get("/products").await.json();
+
+    let body = String::from_utf8(export).unwrap();
+    let res = admin(&app, "POST", "/admin/import?only=employees", Some(&body)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: DataImportReport = res.json();
+    assert_eq!((report.employees, report.products), (1, 0));
+    assert!(report.ignored.contains(&"products".to_string()), "{:?}", report.ignored);
+
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    assert_eq!(employees.len(), 1);
+    let after: Vec<Product> = app.get("/products").await.json();
+    assert_eq!(serde_json::to_value(&after).unwrap(), serde_json::to_value(&before).unwrap());
+
+    assert_eq!(admin(&app, "POST", "/admin/import", Some("{nie je json")).await.status, 400);
+}
+
+#[tokio::test]
+async fn ndjson_import_ignores_unselected_records() {
+    let app = spawn_admin_app().await;
+    let body = [
+        r#"{"type":"employee","name":"Jana","surname":"Nová","position":"Predavačka"}"#,
+        r#"{"type":"product","name":"Mlieko","category":"Potraviny","quantity":3,"status":true,"bar_code":2,"cost_price":0.5,"sell_price":0.9}"#,
+    ]
+    .join("\n");
+
+    let res = admin(&app, "POST", "/admin/import-ndjson?only=products", Some(&body)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: ImportReport = res.json();
+    assert_eq!((report.employees, report.products, report.ignored), (0, 1, 1));
+
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    assert!(employees.is_empty());
+}
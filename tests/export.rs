@@ -0,0 +1,104 @@
+use serde_json::{json, Value};
+use store_manager::test_support::{TestApp, TestResponse};
+
+async fn spawn_with_data() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, category, bar_code) in [("Mlieko, polotučné", "Potraviny", 1), ("Kladivo", "Náradie", 2), ("Chlieb", "Potraviny", 3)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"{category}","quantity":5,"status":true,"bar_code":{bar_code},"cost_price":1.0,"sell_price":1.5}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    for (name, department) in [("Jana", "Sklad"), ("Eva", "Pokladňa")] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","department":"{department}","status":true}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    app
+}
+
+async fn export(app: &TestApp, request: Value) -> TestResponse {
+    app.post("/export", &request.to_string()).await
+}
+
+fn assert_attachment(res: &TestResponse, content_type: &str, extension: &str) {
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some(content_type));
+    let disposition = res.header("content-disposition").unwrap();
+    assert!(disposition.starts_with("attachment; filename=\""), "{disposition}");
+    assert!(disposition.ends_with(&format!(".{extension}\"")), "{disposition}");
+}
+
+#[tokio::test]
+async fn csv_contains_selected_fields_in_order() {
+    let app = spawn_with_data().await;
+    let res = export(
+        &app,
+        json!({"entity": "product", "filter": {"category": "Potraviny"}, "fields": ["name", "id", "sell_price"], "format": "csv"}),
+    )
+    .await;
+    assert_attachment(&res, "text/csv; charset=utf-8", "csv");
+    assert!(res.header("content-disposition").unwrap().contains("products-"));
+    assert_eq!(res.text(), "name,id,sell_price\n\"Mlieko, polotučné\",1,1.5\nChlieb,3,1.5\n");
+}
+
+#[tokio::test]
+async fn json_and_ndjson_contain_only_selected_fields() {
+    let app = spawn_with_data().await;
+    let request = |format: &str| {
+        json!({"entity": "employee", "filter": {"department": "Sklad"}, "fields": ["surname", "name"], "format": format})
+    };
+
+    let res = export(&app, request("json")).await;
+    assert_attachment(&res, "application/json", "json");
+    let rows: Value = res.json();
+    assert_eq!(rows, json!([{"surname": "Nová", "name": "Jana"}]));
+
+    let res = export(&app, request("ndjson")).await;
+    assert_attachment(&res, "application/x-ndjson", "ndjson");
+    assert_eq!(res.text(), "{\"surname\":\"Nová\",\"name\":\"Jana\"}\n");
+}
+
+#[tokio::test]
+async fn all_fields_are_exported_without_selection() {
+    let app = spawn_with_data().await;
+    let res = export(&app, json!({"entity": "products", "format": "json"})).await;
+    assert_attachment(&res, "application/json", "json");
+    let rows: Vec<Value> = res.json();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1]["name"], "Kladivo");
+    assert_eq!(rows[1]["cost_price"], 1.0);
+    assert!(rows[1].get("sale_price").is_some_and(Value::is_null));
+    // polia, ktoré slúžia len ako filter, sa neexportujú
+    assert!(rows[1].get("quantity_min").is_none());
+}
+
+#[tokio::test]
+async fn xlsx_is_a_workbook() {
+    let app = spawn_with_data().await;
+    let res = export(&app, json!({"entity": "product", "fields": ["id", "name", "status"], "format": "xlsx"})).await;
+    assert_attachment(&res, "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "xlsx");
+    // XLSX je ZIP archív
+    assert!(res.body.starts_with(b"PK\x03\x04"));
+}
+
+#[tokio::test]
+async fn invalid_requests_are_rejected() {
+    let app = spawn_with_data().await;
+    let unknown = export(&app, json!({"entity": "product", "fields": ["name", "heslo"], "format": "csv"})).await;
+    assert_eq!(unknown.status, 400);
+    assert!(unknown.text().contains("heslo"), "{}", unknown.text());
+    // pole zamestnanca nie je pole produktu
+    let foreign = export(&app, json!({"entity": "product", "fields": ["surname"], "format": "csv"})).await;
+    assert_eq!(foreign.status, 400);
+    let duplicate = export(&app, json!({"entity": "product", "fields": ["name", "name"], "format": "csv"})).await;
+    assert_eq!(duplicate.status, 400);
+    let empty = export(&app, json!({"entity": "product", "fields": [], "format": "csv"})).await;
+    assert_eq!(empty.status, 400);
+
+    let format = export(&app, json!({"entity": "product", "format": "pdf"})).await;
+    assert_eq!(format.status, 422);
+    let entity = export(&app, json!({"entity": "order", "format": "csv"})).await;
+    assert_eq!(entity.status, 422);
+    let filter = export(&app, json!({"entity": "product", "filter": {"farba": "modrá"}, "format": "csv"})).await;
+    assert_eq!(filter.status, 422);
+}
@@ -0,0 +1,50 @@
+use chrono::Local;
+use store_manager::structs::Gmroi;
+use store_manager::test_support::TestApp;
+
+async fn gmroi(app: &TestApp) -> Gmroi {
+    let today = Local::now().date_naive();
+    let res = app.get(&format!("/stats/gmroi?from={today}&to={today}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn gross_margin_over_average_inventory_per_category() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 10.0, 2.0, 10.0).await;
+    app.add_product("Rožok", "Pečivo", 10.0, 1.0, 10.0).await;
+    app.db.snapshot_stats().await.unwrap();
+
+    app.sell(1, 5.0).await;
+    app.sell(2, 2.0).await;
+    app.db.snapshot_stats().await.unwrap();
+
+    let report = gmroi(&app).await;
+    assert_eq!(report.snapshots, 2);
+    // zásoby 30 a 18 → priemer 24; zisk 5 × (10 − 2) + 2 × (10 − 1) = 58
+    assert_eq!((report.gross_margin, report.average_inventory), (58.0, 24.0));
+    assert!((report.gmroi.unwrap() - 58.0 / 24.0).abs() < 1e-9, "{report:?}");
+    assert!(report.note.is_none());
+
+    let drinks = &report.categories[0];
+    assert_eq!((drinks.category.as_str(), drinks.gross_margin, drinks.average_inventory), ("Nápoje", 40.0, 15.0));
+    assert!((drinks.gmroi.unwrap() - 40.0 / 15.0).abs() < 1e-9);
+    let bakery = &report.categories[1];
+    assert_eq!((bakery.gross_margin, bakery.average_inventory, bakery.gmroi), (18.0, 9.0, Some(2.0)));
+}
+
+#[tokio::test]
+async fn zero_inventory_leaves_gmroi_undefined() {
+    let app = TestApp::spawn().await;
+    let report = gmroi(&app).await;
+    assert_eq!((report.gross_margin, report.average_inventory, report.gmroi), (0.0, 0.0, None));
+    assert!(report.note.unwrap().contains("nulová"));
+
+    // bez snímok sa použijú aktuálne zásoby
+    app.add_product("Kofola", "Nápoje", 4.0, 2.5, 10.0).await;
+    let report = gmroi(&app).await;
+    assert_eq!((report.average_inventory, report.gmroi), (10.0, Some(0.0)));
+
+    assert_eq!(app.get("/stats/gmroi?from=2024-02-01&to=2024-01-01").await.status, 400);
+}
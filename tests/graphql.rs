@@ -0,0 +1,70 @@
+use serde_json::{json, Value};
+use store_manager::config::Config;
+use store_manager::test_support::TestApp;
+
+async fn query(app: &TestApp, query: &str) -> Value {
+    let res = app.post("/graphql", &json!({ "query": query }).to_string()).await;
+    assert_eq!(res.status, 200);
+    res.json()
+}
+
+#[tokio::test]
+async fn graphql_crud_and_nested_employee() {
+    let app = TestApp::spawn().await;
+
+    let res = query(
+        &app,
+        r#"mutation { createEmployee(input: {name: "Jana", surname: "Nová", position: "Predavačka", salary: 1200}) { id name } }"#,
+    )
+    .await;
+    assert_eq!(res["data"]["createEmployee"]["id"], 1, "{res}");
+
+    for (name, category) in [("Jablko", "Ovocie"), ("Hruška", "Ovocie"), ("Mlieko", "Mliečne")] {
+        let res = query(
+            &app,
+            &format!(
+                r#"mutation {{ createProduct(input: {{name: "{name}", category: "{category}", quantity: 10, status: true, barCode: 1, costPrice: 1.0, sellPrice: 2.0, employeeId: 1}}) {{ id unit }} }}"#
+            ),
+        )
+        .await;
+        assert_eq!(res["data"]["createProduct"]["unit"], "piece", "{res}");
+    }
+
+    let res = query(&app, r#"{ products(filter: {category: "Ovocie"}) { name employee { name surname } } }"#).await;
+    let products = res["data"]["products"].as_array().expect("zoznam produktov");
+    assert_eq!(products.len(), 2);
+    assert!(products.iter().all(|p| p["employee"]["surname"] == "Nová"));
+
+    let res = query(&app, r#"mutation { updateProduct(id: 3, input: {sellPrice: 2.5}) { name sellPrice } }"#).await;
+    assert_eq!(res["data"]["updateProduct"], json!({ "name": "Mlieko", "sellPrice": 2.5 }));
+    let res = query(&app, r#"mutation { updateProduct(id: 99, input: {sellPrice: 2.5}) { id } }"#).await;
+    assert_eq!(res["data"]["updateProduct"], Value::Null);
+
+    let res = query(&app, r#"mutation { deleteProduct(id: 3) }"#).await;
+    assert_eq!(res["data"]["deleteProduct"], true);
+    let res = query(&app, r#"{ product(id: 3) { id } employee(id: 1) { name } }"#).await;
+    assert_eq!(res["data"]["product"], Value::Null);
+    assert_eq!(res["data"]["employee"]["name"], "Jana");
+}
+
+#[tokio::test]
+async fn graphql_reports_validation_errors() {
+    let app = TestApp::spawn().await;
+
+    let res = query(&app, r#"mutation { createProduct(input: {name: "X", unit: "sud"}) { id } }"#).await;
+    assert_eq!(res["errors"][0]["extensions"]["code"], "BAD_USER_INPUT", "{res}");
+
+    let res = query(&app, r#"mutation { createEmployee(input: {name: "Jana", phoneNumber: "abc"}) { id } }"#).await;
+    assert_eq!(res["errors"][0]["extensions"]["code"], "BAD_USER_INPUT", "{res}");
+}
+
+#[tokio::test]
+async fn graphiql_only_in_dev_mode() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.get("/graphql").await.status, 405);
+
+    let app = TestApp::spawn_with_config(Config { dev_mode: true, ..Config::default() }).await;
+    let res = app.get("/graphql").await;
+    assert_eq!(res.status, 200);
+    assert!(res.text().contains("graphiql"));
+}
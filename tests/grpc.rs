@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use store_manager::config::Config;
+use store_manager::grpc::{self, proto};
+use store_manager::grpc::proto::store_service_client::StoreServiceClient;
+use store_manager::test_support::TestApp;
+use tokio::net::TcpListener;
+use tonic::{transport::Channel, Code};
+
+async fn spawn_grpc(app: &TestApp) -> StoreServiceClient<Channel> {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(grpc::serve(app.db.clone(), Arc::new(Config::default()), listener));
+    StoreServiceClient::connect(format!("http://{addr}")).await.expect("pripojenie gRPC klienta")
+}
+
+#[tokio::test]
+async fn grpc_round_trip() {
+    let app = TestApp::spawn().await;
+    let mut client = spawn_grpc(&app).await;
+
+    let body = r#"{"name":"Jablko","category":"Ovocie","quantity":10,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    let body = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","salary":1200}"#;
+    assert_eq!(app.post("/employees", body).await.status, 201);
+
+    let product = client.get_product(proto::GetProductRequest { id: 1 }).await.unwrap().into_inner();
+    assert_eq!(product.name.as_deref(), Some("Jablko"));
+    assert_eq!(product.quantity, Some(10.0));
+    assert_eq!(product.unit.as_deref(), Some("piece"));
+
+    let filter = proto::Product { category: Some("Ovocie".into()), ..Default::default() };
+    let found = client
+        .search_products(proto::SearchProductsRequest { filter: Some(filter) })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(found.total, 1);
+    assert_eq!(found.products[0].id, Some(1));
+
+    let adjusted = client
+        .adjust_stock(proto::AdjustStockRequest { product_id: 1, delta: -3.0, unit: None })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(adjusted.quantity, Some(7.0));
+
+    let sale = proto::RecordSaleRequest {
+        employee_id: Some(1),
+        items: vec![proto::OrderItem { product_id: 1, quantity: 2.0, unit: None, unit_price: None }],
+        override_closed: false,
+    };
+    let order_id = client.record_sale(sale).await.unwrap().into_inner().order_id;
+    assert!(order_id > 0);
+    let product = client.get_product(proto::GetProductRequest { id: 1 }).await.unwrap().into_inner();
+    assert_eq!(product.quantity, Some(5.0));
+
+    let employee = client.get_employee(proto::GetEmployeeRequest { id: 1 }).await.unwrap().into_inner();
+    assert_eq!(employee.surname.as_deref(), Some("Nová"));
+}
+
+#[tokio::test]
+async fn grpc_errors_map_to_status_codes() {
+    let app = TestApp::spawn().await;
+    let mut client = spawn_grpc(&app).await;
+    let body = r#"{"name":"Jablko","category":"Ovocie","quantity":1,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+
+    let err = client.get_product(proto::GetProductRequest { id: 99 }).await.unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+    let err = client.get_employee(proto::GetEmployeeRequest { id: 99 }).await.unwrap_err();
+    assert_eq!(err.code(), Code::NotFound);
+
+    let err = client
+        .adjust_stock(proto::AdjustStockRequest { product_id: 1, delta: -5.0, unit: None })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::FailedPrecondition);
+
+    let err = client
+        .adjust_stock(proto::AdjustStockRequest { product_id: 1, delta: 0.5, unit: None })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+
+    let err = client
+        .record_sale(proto::RecordSaleRequest { employee_id: None, items: vec![], override_closed: false })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Code::InvalidArgument);
+}
@@ -0,0 +1,48 @@
+use store_manager::config::Config;
+use store_manager::db_filler::DBFiller;
+use store_manager::health::Readiness;
+use store_manager::structs::ReadinessStatus;
+use store_manager::test_support::TestApp;
+
+#[tokio::test]
+async fn readiness_follows_startup_import() {
+    let readiness = Readiness::starting();
+    let app = TestApp::spawn_with_readiness(Config::default(), readiness.clone()).await;
+
+    assert_eq!(app.get("/livez").await.status, 200);
+    let res = app.get("/readyz").await;
+    assert_eq!(res.status, 503);
+    assert!(!res.json::<ReadinessStatus>().ready);
+
+    // import zo súboru hlási počet načítaných záznamov
+    let json = std::env::temp_dir().join(format!("store_manager_readyz_{}.json", std::process::id()));
+    std::fs::write(
+        &json,
+        r#"{"employees":[{"name":"Jana","surname":"A","position":"P"},{"name":"Ján","surname":"B","position":"P"}],
+            "products":[{"name":"A","category":"T","quantity":1,"bar_code":1,"cost_price":1,"sell_price":2}]}"#,
+    )
+    .unwrap();
+    readiness.set_phase("import");
+    DBFiller::load_from_json_with_progress(&app.db, json.to_str().unwrap(), |n| readiness.set_loaded(n))
+        .await
+        .unwrap();
+    let _ = std::fs::remove_file(&json);
+
+    let res = app.get("/readyz").await;
+    assert_eq!(res.status, 503);
+    let status: ReadinessStatus = res.json();
+    assert_eq!((status.phase.as_str(), status.loaded_records), ("import", 3));
+    assert_eq!(app.get("/livez").await.status, 200);
+
+    readiness.mark_ready();
+    let res = app.get("/readyz").await;
+    assert_eq!(res.status, 200);
+    assert!(res.json::<ReadinessStatus>().ready);
+}
+
+#[tokio::test]
+async fn default_app_is_ready() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.get("/readyz").await.status, 200);
+    assert_eq!(app.get("/livez").await.text(), "ok");
+}
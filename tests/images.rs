@@ -0,0 +1,68 @@
+use serde_json::Value;
+use store_manager::structs::ProductImage;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_product() -> TestApp {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Chlieb","category":"Potraviny","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    app
+}
+
+fn primary(images: &[ProductImage]) -> Vec<&str> {
+    images.iter().filter(|i| i.is_primary).map(|i| i.url.as_str()).collect()
+}
+
+#[tokio::test]
+async fn images_are_ordered_with_single_primary() {
+    let app = spawn_with_product().await;
+
+    let res = app.post("/products/1/images", r#"[{"url":"/img/b.jpg","sort_order":5},{"url":"https://cdn/a.jpg","sort_order":1}]"#).await;
+    assert_eq!(res.status, 201);
+    let images: Vec<ProductImage> = res.json();
+    let urls: Vec<&str> = images.iter().map(|i| i.url.as_str()).collect();
+    assert_eq!(urls, ["https://cdn/a.jpg", "/img/b.jpg"]);
+    // bez zadaného hlavného obrázka je hlavný prvý podľa poradia
+    assert_eq!(primary(&images), ["https://cdn/a.jpg"]);
+
+    // bez poradia sa zaradí na koniec; is_primary nahradí doterajší hlavný
+    let res = app.post("/products/1/images", r#"[{"url":"/img/c.jpg","is_primary":true}]"#).await;
+    let images: Vec<ProductImage> = res.json();
+    assert_eq!(images.last().map(|i| (i.url.as_str(), i.sort_order)), Some(("/img/c.jpg", Some(6))));
+    assert_eq!(primary(&images), ["/img/c.jpg"]);
+
+    let first = images[0].id.unwrap();
+    let res = app.put(&format!("/products/1/images/{first}/primary"), "").await;
+    assert_eq!(res.status, 200);
+    assert_eq!(primary(&res.json::<Vec<ProductImage>>()), ["https://cdn/a.jpg"]);
+
+    let detail: Value = app.get("/products/1").await.json();
+    let urls: Vec<&str> = detail["images"].as_array().unwrap().iter().map(|i| i["url"].as_str().unwrap()).collect();
+    assert_eq!(urls, ["https://cdn/a.jpg", "/img/b.jpg", "/img/c.jpg"]);
+    assert_eq!(app.get("/products/1/images").await.json::<Vec<ProductImage>>().len(), 3);
+}
+
+#[tokio::test]
+async fn invalid_images_are_rejected_atomically() {
+    let app = spawn_with_product().await;
+
+    let res = app.post("/products/1/images", r#"[{"url":"/a.jpg"},{"url":"ftp://x/b.jpg"}]"#).await;
+    assert_eq!(res.status, 400);
+    let res = app.post("/products/1/images", r#"[{"url":"/a.jpg","is_primary":true},{"url":"/b.jpg","is_primary":true}]"#).await;
+    assert_eq!(res.status, 400);
+    assert_eq!(app.post("/products/1/images", "[]").await.status, 400);
+    assert!(app.get("/products/1/images").await.json::<Vec<ProductImage>>().is_empty());
+
+    assert_eq!(app.post("/products/9/images", r#"[{"url":"/a.jpg"}]"#).await.status, 404);
+    assert_eq!(app.get("/products/9/images").await.status, 404);
+    assert_eq!(app.put("/products/1/images/1/primary", "").await.status, 404);
+}
+
+#[tokio::test]
+async fn deleting_product_removes_images() {
+    let app = spawn_with_product().await;
+    assert_eq!(app.post("/products/1/images", r#"[{"url":"/a.jpg"}]"#).await.status, 201);
+
+    assert_eq!(app.delete("/products/1").await.status, 204);
+    assert!(app.db.list_product_images(1).await.unwrap().is_empty());
+}
@@ -0,0 +1,104 @@
+use store_manager::config::Config;
+use store_manager::import::IMPORT_CHUNK;
+use store_manager::structs::{Employee, ImportReport, Product};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_admin_app() -> TestApp {
+    TestApp::spawn_with_config(Config {
+        admin_api_key: Some(KEY.into()),
+        ..Config::default()
+    })
+    .await
+}
+
+async fn import(app: &TestApp, query: &str, body: &str) -> TestResponse {
+    app.request_with_headers("POST", &format!("/admin/import-ndjson{query}"), Some(body), &[("x-api-key", KEY)])
+        .await
+}
+
+fn product_line(i: usize) -> String {
+    format!(
+        r#"{{"type":"product","name":"Produkt {i}","category":"Import","quantity":{},"status":true,"bar_code":{i},"cost_price":1.0,"sell_price":2.0,"description":"Hromadne importovaný produkt s dlhším popisom"}}"#,
+        i % 7
+    )
+}
+
+#[tokio::test]
+async fn imports_employees_and_products_line_by_line() {
+    let app = spawn_admin_app().await;
+    let body = [
+        r#"{"type":"employee","name":"Jana","surname":"Nová","position":"Predavačka","phone_number":"0900 123 456"}"#,
+        "",
+        &product_line(1),
+        r#"{"type":"product","name":"Mlieko","category":"Potraviny","quantity":3,"status":true,"bar_code":2,"cost_price":0.5,"sell_price":0.9}"#,
+    ]
+    .join("\r\n");
+
+    let res = import(&app, "", &body).await;
+    assert_eq!(res.status, 200);
+    let report: ImportReport = res.json();
+    assert_eq!((report.lines, report.employees, report.products, report.skipped), (3, 1, 2, 0));
+    assert!(report.completed);
+
+    let employees: Vec<Employee> = app.post("/employees/search", r#"{"phone_number":"+421900123456"}"#).await.json();
+    assert_eq!(employees.len(), 1);
+    let products: Vec<Product> = app.get("/products?name=Mlieko").await.json();
+    assert_eq!(products[0].quantity, Some(3.0));
+
+    assert_eq!(app.post("/admin/import-ndjson", &product_line(1)).await.status, 401);
+}
+
+#[tokio::test]
+async fn malformed_lines_are_skipped_only_in_lenient_mode() {
+    let app = spawn_admin_app().await;
+    let body = [
+        product_line(1),
+        "{nie je json".into(),
+        r#"{"type":"customer","name":"X"}"#.into(),
+        r#"{"type":"product","name":"Syr","quantity":1.5,"unit":"piece"}"#.into(),
+        product_line(2),
+    ]
+    .join("\n");
+
+    let res = import(&app, "?lenient=true", &body).await;
+    assert_eq!(res.status, 200);
+    let report: ImportReport = res.json();
+    assert_eq!((report.products, report.skipped), (2, 3));
+    let lines: Vec<u64> = report.errors.iter().map(|e| e.line).collect();
+    assert_eq!(lines, [2, 3, 4]);
+
+    // prísny režim sa zastaví na prvej chybe a rozpracovanú dávku neuloží
+    let res = import(&app, "", &body).await;
+    assert_eq!(res.status, 400);
+    let report: ImportReport = res.json();
+    assert!(!report.completed);
+    assert_eq!(report.products, 0);
+    assert_eq!(report.errors[0].line, 2);
+
+    let products: Vec<Product> = app.get("/products?category=Import").await.json();
+    assert_eq!(products.len(), 2);
+}
+
+#[tokio::test]
+async fn large_input_is_streamed_in_chunks() {
+    let app = spawn_admin_app().await;
+    let count = IMPORT_CHUNK * 60 + 17;
+    let mut body = String::new();
+    for i in 0..count {
+        body.push_str(&product_line(i));
+        body.push('\n');
+    }
+    // väčšie ako predvolený limit tela pre JSON extraktory (2 MB)
+    assert!(body.len() > 5 * 1024 * 1024);
+
+    let res = import(&app, "", &body).await;
+    assert_eq!(res.status, 200);
+    let report: ImportReport = res.json();
+    assert_eq!(report.products, count as u64);
+    assert!(report.completed);
+
+    let res = app.get("/products?category=Import&limit=1").await;
+    assert_eq!(res.header("x-total-count"), Some(count.to_string().as_str()));
+}
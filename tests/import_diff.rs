@@ -0,0 +1,131 @@
+use serde_json::json;
+use store_manager::db_filler::DBFiller;
+use store_manager::structs::{Employee, FieldChange, ImportDiff, Product};
+use store_manager::test_support::TestApp;
+
+/// Aplikácia s dvoma produktmi a dvoma zamestnancami.
+async fn seeded_app() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, bar_code) in [("Chlieb", 100), ("Mlieko", 200)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":10,"status":true,"bar_code":{bar_code},
+                "cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    for (name, email) in [("Jana", "jana@obchod.sk"), ("Peter", "peter@obchod.sk")] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavač","email":"{email}"}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    app
+}
+
+fn product(name: &str, bar_code: i64, sell_price: f64) -> serde_json::Value {
+    json!({"name": name, "category": "Potraviny", "quantity": 10.0, "status": true, "bar_code": bar_code,
+           "cost_price": 1.0, "sell_price": sell_price})
+}
+
+async fn diff(app: &TestApp, file: serde_json::Value) -> ImportDiff {
+    let res = app.post("/import/diff", &file.to_string()).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn identical_file_has_no_changes() {
+    let app = seeded_app().await;
+    let file = json!({
+        "employees": [{"name": "Jana", "surname": "Nová", "position": "Predavač", "email": "jana@obchod.sk"},
+                      {"name": "Peter", "surname": "Nová", "position": "Predavač", "email": "peter@obchod.sk"}],
+        "products": [product("Chlieb", 100, 2.0), product("Mlieko", 200, 2.0)],
+    });
+
+    let diff = diff(&app, file).await;
+    assert_eq!((diff.products.unchanged, diff.employees.unchanged), (2, 2));
+    assert!(diff.products.added.is_empty() && diff.products.updated.is_empty() && diff.products.deleted.is_empty());
+    assert!(diff.employees.added.is_empty() && diff.employees.updated.is_empty() && diff.employees.deleted.is_empty());
+}
+
+#[tokio::test]
+async fn new_records_are_reported_as_added() {
+    let app = seeded_app().await;
+    let file = json!({
+        "employees": [{"name": "Jana", "email": "jana@obchod.sk"}, {"name": "Peter", "email": "peter@obchod.sk"},
+                      {"name": "Eva", "surname": "Stará", "position": "Skladník", "email": "eva@obchod.sk"}],
+        "products": [product("Chlieb", 100, 2.0), product("Mlieko", 200, 2.0), product("Maslo", 300, 3.0)],
+    });
+
+    let diff = diff(&app, file).await;
+    let added: Vec<_> = diff.products.added.iter().map(|p| p.name.as_deref()).collect();
+    assert_eq!(added, [Some("Maslo")]);
+    let added: Vec<_> = diff.employees.added.iter().map(|e| e.email.as_deref()).collect();
+    assert_eq!(added, [Some("eva@obchod.sk")]);
+    assert_eq!((diff.products.unchanged, diff.employees.unchanged), (2, 2));
+}
+
+#[tokio::test]
+async fn changed_fields_are_listed_for_updates() {
+    let app = seeded_app().await;
+    let file = json!({
+        "employees": [{"name": "Jana", "position": "Vedúca", "email": "jana@obchod.sk"},
+                      {"name": "Peter", "email": "peter@obchod.sk"}],
+        "products": [product("Chlieb celozrnný", 100, 2.5), product("Mlieko", 200, 2.0)],
+    });
+
+    let diff = diff(&app, file).await;
+    assert_eq!(diff.products.updated.len(), 1);
+    let update = &diff.products.updated[0];
+    assert_eq!((update.id, update.key.as_str()), (Some(1), "100"));
+    let mut changes = update.changes.clone();
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    assert_eq!(
+        changes,
+        [
+            FieldChange { field: "name".into(), old: json!("Chlieb"), new: json!("Chlieb celozrnný") },
+            FieldChange { field: "sell_price".into(), old: json!(2.0), new: json!(2.5) },
+        ]
+    );
+
+    let update = &diff.employees.updated[0];
+    assert_eq!(update.key, "jana@obchod.sk");
+    assert_eq!(update.changes, [FieldChange { field: "position".into(), old: json!("Predavač"), new: json!("Vedúca") }]);
+    assert_eq!((diff.products.unchanged, diff.employees.unchanged), (1, 1));
+}
+
+#[tokio::test]
+async fn missing_records_are_reported_as_deleted() {
+    let app = seeded_app().await;
+    let file = json!({
+        "employees": [{"name": "Peter", "email": "peter@obchod.sk"}],
+        "products": [product("Mlieko", 200, 2.0)],
+    });
+
+    let diff = diff(&app, file).await;
+    let deleted: Vec<_> = diff.products.deleted.iter().map(|p| p.bar_code).collect();
+    assert_eq!(deleted, [Some(100)]);
+    let deleted: Vec<_> = diff.employees.deleted.iter().map(|e| e.email.as_deref()).collect();
+    assert_eq!(deleted, [Some("jana@obchod.sk")]);
+
+    // náhľad nič nezapíše
+    assert_eq!(app.db.get_products(Product::new_empty()).await.unwrap().len(), 2);
+    assert_eq!(app.db.get_employees(Employee::new_empty()).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn diff_reads_json_file() {
+    let app = seeded_app().await;
+    let path = std::env::temp_dir().join(format!("store_manager_import_diff_{}.json", std::process::id()));
+    std::fs::write(&path, json!({"employees": [], "products": [product("Chlieb", 100, 2.0)]}).to_string()).unwrap();
+
+    let diff = DBFiller::diff_against_json(&app.db, path.to_str().unwrap()).await.unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(diff.products.unchanged, 1);
+    assert_eq!(diff.products.deleted.len(), 1);
+    assert_eq!(diff.employees.deleted.len(), 2);
+}
+
+#[tokio::test]
+async fn invalid_file_is_rejected() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/import/diff", "{\"products\": 5}").await.status, 400);
+}
@@ -0,0 +1,76 @@
+use store_manager::config::Config;
+use store_manager::structs::{Product, UploadImportReport};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+const BOUNDARY: &str = "hranica-suboru";
+
+async fn spawn_admin_app(config: Config) -> TestApp {
+    TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..config }).await
+}
+
+async fn upload(app: &TestApp, query: &str, field: &str, file_name: &str, content: &str) -> TestResponse {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"{file_name}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n{content}\r\n--{BOUNDARY}--\r\n"
+    );
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+    app.request_with_headers(
+        "POST",
+        &format!("/import/upload{query}"),
+        Some(&body),
+        &[("x-api-key", KEY), ("content-type", &content_type)],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn uploads_store_data_json() {
+    let app = spawn_admin_app(Config::default()).await;
+    let data = r#"{"employees":[{"name":"Jana","surname":"Nová","position":"Predavačka","status":true}],
+        "products":[{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}]}"#;
+
+    let res = upload(&app, "", "file", "store_data.json", data).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let UploadImportReport::Json(report) = res.json() else { panic!("{}", res.text()) };
+    assert_eq!((report.employees, report.products), (1, 1));
+
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert_eq!(products[0].name.as_deref(), Some("Chlieb"));
+}
+
+#[tokio::test]
+async fn uploads_csv_and_skips_invalid_rows_when_lenient() {
+    let app = spawn_admin_app(Config::default()).await;
+    let csv = "name;category;quantity;status;bar_code;cost_price;sell_price\n\
+               Mlieko;Potraviny;3;true;2;0.5;0.9\n\
+               Maslo;Potraviny;nie;true;3;1.0;1.8\n\
+               Syr;Potraviny;4;true;4;2.0;3.5\n";
+
+    let res = upload(&app, "", "file", "produkty.csv", csv).await;
+    assert_eq!(res.status, 400, "{}", res.text());
+    let UploadImportReport::Csv(report) = res.json() else { panic!("{}", res.text()) };
+    assert!(!report.completed);
+    assert_eq!(report.errors[0].line, 3);
+    assert_eq!(app.get("/products").await.text(), "[]");
+
+    let app = spawn_admin_app(Config::default()).await;
+    let res = upload(&app, "?lenient=true", "file", "produkty.csv", csv).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let UploadImportReport::Csv(report) = res.json() else { panic!("{}", res.text()) };
+    assert_eq!((report.products, report.skipped), (2, 1));
+
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert_eq!(products.len(), 2);
+}
+
+#[tokio::test]
+async fn rejects_missing_unknown_or_oversized_files() {
+    let app = spawn_admin_app(Config { max_upload_size: 256, ..Config::default() }).await;
+
+    assert_eq!(upload(&app, "", "subor", "data.json", "{}").await.status, 400);
+    assert_eq!(upload(&app, "", "file", "data.txt", "ahoj").await.status, 415);
+    let big = format!("name,category\n{}", "Produkt,Kategória\n".repeat(50));
+    assert_eq!(upload(&app, "", "file", "data.csv", &big).await.status, 413);
+    assert_eq!(app.get("/products").await.text(), "[]");
+}
@@ -0,0 +1,79 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use store_manager::config::Config;
+use store_manager::structs::{IntegrityReport, Product};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn admin(app: &TestApp, method: &str, path: &str) -> TestResponse {
+    app.request_with_headers(method, path, None, &[("x-api-key", KEY)]).await
+}
+
+/// Produkt 1 patrí zamestnancovi 1; produkt 2 a položka objednávky odkazujú na
+/// neexistujúceho zamestnanca 42 a produkt 99 (zapísané bez kontroly cudzích kľúčov).
+async fn spawn_with_orphans() -> TestApp {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    let employee = r#"{"name":"Jana","surname":"Nová","position":"Predavač","status":true}"#;
+    assert_eq!(app.post("/employees", employee).await.status, 201);
+    for name in ["Chlieb", "Rožok"] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Pečivo","quantity":5,"bar_code":1,"cost_price":1.0,"sell_price":2.0,"employee_id":1}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    let order = r#"{"items":[{"product_id":1,"quantity":1}]}"#;
+    assert_eq!(app.post("/orders", order).await.status, 201);
+
+    let options = SqliteConnectOptions::new().filename(app.db_path()).foreign_keys(false);
+    let pool = SqlitePool::connect_with(options).await.unwrap();
+    sqlx::raw_sql("UPDATE products SET employee_id = 42 WHERE id = 2; UPDATE order_items SET product_id = 99")
+        .execute(&pool)
+        .await
+        .unwrap();
+    app
+}
+
+#[tokio::test]
+async fn check_reports_orphans_without_changing_anything() {
+    let app = spawn_with_orphans().await;
+
+    let res = admin(&app, "GET", "/admin/integrity-check").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: IntegrityReport = res.json();
+    assert!(!report.repaired);
+    assert_eq!((report.total, report.nulled), (2, 0));
+    let orphans: Vec<_> = report
+        .orphans
+        .iter()
+        .map(|o| (o.table.as_str(), o.column.as_str(), o.references.as_str(), o.row_ids.clone(), o.missing_ids.clone(), o.repairable))
+        .collect();
+    assert_eq!(
+        orphans,
+        [
+            ("order_items", "product_id", "products", vec![1], vec![99], false),
+            ("products", "employee_id", "employees", vec![2], vec![42], true),
+        ]
+    );
+
+    let product: Product = app.get("/products/2").await.json();
+    assert_eq!(product.employee_id, Some(42));
+    assert_eq!(app.get("/admin/integrity-check").await.status, 401);
+}
+
+#[tokio::test]
+async fn repair_nulls_only_nullable_references() {
+    let app = spawn_with_orphans().await;
+
+    let report: IntegrityReport = admin(&app, "POST", "/admin/integrity-check").await.json();
+    assert!(report.repaired);
+    assert_eq!((report.total, report.nulled), (2, 1));
+
+    let product: Product = app.get("/products/2").await.json();
+    assert_eq!(product.employee_id, None);
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.employee_id, Some(1));
+
+    let report: IntegrityReport = admin(&app, "GET", "/admin/integrity-check").await.json();
+    assert_eq!(report.total, 1);
+    assert_eq!(report.orphans[0].table, "order_items");
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use chrono::{Days, Local};
+use store_manager::config::{AgingBuckets, Config};
+use store_manager::structs::AgingBucket;
+use store_manager::test_support::TestApp;
+
+async fn product(app: &TestApp, bar_code: u32, quantity: u32, age: Option<u64>) {
+    let date_added = age
+        .map(|days| format!(r#","date_added":"{}""#, Local::now().date_naive() - Days::new(days)))
+        .unwrap_or_default();
+    let body = format!(
+        r#"{{"name":"Tovar {bar_code}","category":"Potraviny","quantity":{quantity},"status":true,"bar_code":{bar_code},"cost_price":2.0,"sell_price":3.0{date_added}}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+async fn seed(app: &TestApp) {
+    product(app, 1, 4, Some(0)).await;
+    product(app, 2, 1, Some(30)).await;
+    product(app, 3, 2, Some(31)).await;
+    product(app, 4, 3, Some(75)).await;
+    product(app, 5, 5, Some(90)).await;
+    product(app, 6, 10, Some(400)).await;
+    product(app, 7, 6, None).await;
+    // bez zásob sa nezapočíta
+    product(app, 8, 0, Some(400)).await;
+}
+
+fn by_label(buckets: &[AgingBucket]) -> HashMap<&str, (u32, f64, f64)> {
+    buckets.iter().map(|b| (b.label.as_str(), (b.products, b.quantity, b.value))).collect()
+}
+
+#[tokio::test]
+async fn default_buckets_group_products_by_age() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    let res = app.get("/stats/aging").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let buckets: Vec<AgingBucket> = res.json();
+    let labels: Vec<&str> = buckets.iter().map(|b| b.label.as_str()).collect();
+    assert_eq!(labels, ["0-30", "31-60", "61-90", "91+", "unknown"]);
+    let groups = by_label(&buckets);
+    assert_eq!(groups["0-30"], (2, 5.0, 10.0));
+    assert_eq!(groups["31-60"], (1, 2.0, 4.0));
+    assert_eq!(groups["61-90"], (2, 8.0, 16.0));
+    assert_eq!(groups["91+"], (1, 10.0, 20.0));
+    assert_eq!(groups["unknown"], (1, 6.0, 12.0));
+    assert_eq!((buckets[3].min_days, buckets[3].max_days), (Some(91), None));
+}
+
+#[tokio::test]
+async fn boundaries_come_from_config() {
+    let app = TestApp::spawn_with_config(Config { aging_buckets: AgingBuckets(vec![7, 365]), ..Config::default() }).await;
+    seed(&app).await;
+
+    let buckets: Vec<AgingBucket> = app.get("/stats/aging").await.json();
+    let groups = by_label(&buckets);
+    assert_eq!(groups.len(), 4);
+    assert_eq!(groups["0-7"], (1, 4.0, 8.0));
+    assert_eq!(groups["8-365"], (4, 11.0, 22.0));
+    assert_eq!(groups["366+"], (1, 10.0, 20.0));
+
+    let empty = TestApp::spawn().await;
+    let buckets: Vec<AgingBucket> = empty.get("/stats/aging").await.json();
+    assert!(buckets.iter().all(|b| b.products == 0 && b.value == 0.0));
+}
+
+#[test]
+fn invalid_boundaries_are_rejected() {
+    let config = Config::from_toml("aging_buckets = [14, 28]").unwrap();
+    assert_eq!(config.aging_buckets, AgingBuckets(vec![14, 28]));
+    config.validate().unwrap();
+
+    let mut config = Config::default();
+    config.apply_overrides(|key| (key == "STORE_AGING_BUCKETS").then(|| "10, 20,40".to_string())).unwrap();
+    assert_eq!(config.aging_buckets, AgingBuckets(vec![10, 20, 40]));
+    assert!(config.apply_overrides(|key| (key == "STORE_AGING_BUCKETS").then(|| "10,x".to_string())).is_err());
+
+    for buckets in [vec![], vec![0, 30], vec![60, 30], vec![30, 30]] {
+        let err = Config { aging_buckets: AgingBuckets(buckets), ..Config::default() }.validate().unwrap_err();
+        assert!(err.to_string().contains("aging_buckets"), "{err}");
+    }
+}
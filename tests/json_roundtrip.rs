@@ -0,0 +1,73 @@
+use store_manager::db::StoreDB;
+use store_manager::db_filler::{DBFiller, LoadMode};
+use store_manager::structs::{Employee, Product};
+use store_manager::test_support::TestApp;
+
+/// Uloží databázu aplikácie do JSONu a načíta ho do novej databázy.
+async fn round_trip(app: &TestApp, name: &str) -> StoreDB {
+    let dir = std::env::temp_dir();
+    let json = dir.join(format!("store_manager_{name}_{}.json", std::process::id()));
+    let copy = dir.join(format!("store_manager_{name}_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&copy);
+
+    DBFiller::save_to_json(&app.db, json.to_str().unwrap()).await.unwrap();
+    let db = StoreDB::open(copy.to_str().unwrap()).await.unwrap();
+    DBFiller::load_from_json(&db, json.to_str().unwrap(), LoadMode::Sequential).await.unwrap();
+
+    let _ = std::fs::remove_file(&json);
+    db
+}
+
+#[tokio::test]
+async fn status_survives_json_round_trip() {
+    let app = TestApp::spawn().await;
+    for (name, status) in [("Aktívny", "true"), ("Vyradený", "false"), ("Bez stavu", "null")] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Test","quantity":1,"status":{status},"bar_code":1,
+                "cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+        let body = format!(r#"{{"name":"{name}","surname":"Test","position":"Predavač","status":{status}}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+
+    let db = round_trip(&app, "status").await;
+    let products = db.get_products(Product::new_empty()).await.unwrap();
+    let statuses: Vec<_> = products.iter().map(|p| p.status).collect();
+    assert_eq!(statuses, [Some(true), Some(false), None]);
+
+    let employees = db.get_employees(Employee::new_empty()).await.unwrap();
+    let statuses: Vec<_> = employees.iter().map(|e| e.status).collect();
+    assert_eq!(statuses, [Some(true), Some(false), None]);
+}
+
+#[tokio::test]
+async fn status_accepts_database_integers_in_json() {
+    let dir = std::env::temp_dir();
+    let json = dir.join(format!("store_manager_status_int_{}.json", std::process::id()));
+    let path = dir.join(format!("store_manager_status_int_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    std::fs::write(
+        &json,
+        r#"{"employees":[{"name":"Jana","surname":"A","position":"P","status":0},
+                         {"name":"Ján","surname":"B","position":"P","status":"true"}],
+            "products":[{"name":"A","category":"T","quantity":0,"bar_code":1,"cost_price":1,"sell_price":2,"status":1},
+                        {"name":"B","category":"T","quantity":0,"bar_code":1,"cost_price":1,"sell_price":2,"status":0},
+                        {"name":"C","category":"T","quantity":0,"bar_code":1,"cost_price":1,"sell_price":2,"status":"false"},
+                        {"name":"D","category":"T","quantity":0,"bar_code":1,"cost_price":1,"sell_price":2}]}"#,
+    )
+    .unwrap();
+
+    let db = StoreDB::open(path.to_str().unwrap()).await.unwrap();
+    DBFiller::load_from_json(&db, json.to_str().unwrap(), LoadMode::Sequential).await.unwrap();
+
+    let products = db.get_products(Product::new_empty()).await.unwrap();
+    let statuses: Vec<_> = products.iter().map(|p| p.status).collect();
+    assert_eq!(statuses, [Some(true), Some(false), Some(false), None]);
+    let employees = db.get_employees(Employee::new_empty()).await.unwrap();
+    let statuses: Vec<_> = employees.iter().map(|e| e.status).collect();
+    assert_eq!(statuses, [Some(false), Some(true)]);
+
+    let _ = std::fs::remove_file(&json);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,116 @@
+use jsonwebtoken::{encode, EncodingKey, Header, Algorithm};
+use store_manager::auth::Claims;
+use store_manager::config::Config;
+use store_manager::test_support::{TestApp, TestResponse};
+
+const SECRET: &str = "jwt-tajomstvo";
+const API_KEY: &str = "tajny-kluc";
+const PRODUCT: &str = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+
+fn now() -> u64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn token(secret: &str, role: Option<&str>, exp: u64) -> String {
+    let claims = Claims { sub: "jana@obchod.sk".into(), role: role.map(Into::into), exp };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+}
+
+async fn spawn_jwt_app() -> TestApp {
+    TestApp::spawn_with_config(Config {
+        jwt_secret: Some(SECRET.into()),
+        admin_api_key: Some(API_KEY.into()),
+        ..Config::default()
+    })
+    .await
+}
+
+async fn post_with_token(app: &TestApp, path: &str, body: &str, token: &str) -> TestResponse {
+    let bearer = format!("Bearer {token}");
+    app.request_with_headers("POST", path, Some(body), &[("authorization", &bearer)]).await
+}
+
+#[tokio::test]
+async fn valid_token_allows_writes() {
+    let app = spawn_jwt_app().await;
+    let res = post_with_token(&app, "/products", PRODUCT, &token(SECRET, Some("editor"), now() + 3600)).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+}
+
+#[tokio::test]
+async fn writes_without_token_are_rejected_but_reads_are_open() {
+    let app = spawn_jwt_app().await;
+    let res = app.post("/products", PRODUCT).await;
+    assert_eq!(res.status, 401);
+    assert_eq!(res.header("www-authenticate"), Some("Bearer"));
+
+    assert_eq!(app.get("/products").await.status, 200);
+    assert_eq!(app.post("/products/search", "{}").await.status, 200);
+
+    // API kľúč zostáva alternatívou k tokenu
+    let res = app.request_with_headers("POST", "/products", Some(PRODUCT), &[("x-api-key", API_KEY)]).await;
+    assert_eq!(res.status, 201);
+}
+
+#[tokio::test]
+async fn expired_token_is_rejected() {
+    let app = spawn_jwt_app().await;
+    let res = post_with_token(&app, "/products", PRODUCT, &token(SECRET, None, now() - 3600)).await;
+    assert_eq!(res.status, 401);
+    assert!(res.text().contains("ExpiredSignature"), "{}", res.text());
+
+    // neplatný token sa odmietne aj pri čítaní
+    let bearer = format!("Bearer {}", token(SECRET, None, now() - 3600));
+    assert_eq!(app.request_with_headers("GET", "/products", None, &[("authorization", &bearer)]).await.status, 401);
+}
+
+#[tokio::test]
+async fn tampered_token_is_rejected() {
+    let app = spawn_jwt_app().await;
+
+    let valid = token(SECRET, None, now() + 3600);
+    let admin_payload = token(SECRET, Some("admin"), now() + 3600);
+    // podpis bežného tokenu s nárokmi administrátora
+    let parts: Vec<_> = valid.split('.').collect();
+    let forged = format!("{}.{}.{}", parts[0], admin_payload.split('.').nth(1).unwrap(), parts[2]);
+    assert_eq!(post_with_token(&app, "/products", PRODUCT, &forged).await.status, 401);
+
+    let foreign = token("iné-tajomstvo", None, now() + 3600);
+    assert_eq!(post_with_token(&app, "/products", PRODUCT, &foreign).await.status, 401);
+    assert_eq!(post_with_token(&app, "/products", PRODUCT, "nie.je.token").await.status, 401);
+}
+
+#[tokio::test]
+async fn admin_role_unlocks_admin_endpoints_and_is_audited() {
+    let app = spawn_jwt_app().await;
+    let body = r#"{"enabled":false}"#;
+
+    let res = post_with_token(&app, "/admin/maintenance", body, &token(SECRET, None, now() + 3600)).await;
+    assert_eq!(res.status, 403);
+
+    let res = post_with_token(&app, "/admin/maintenance", body, &token(SECRET, Some("admin"), now() + 3600)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+
+    let log = app.db.get_audit_log().await.unwrap();
+    assert_eq!(log[0].action, "maintenance");
+    assert_eq!(log[0].actor.as_deref(), Some("jana@obchod.sk"));
+}
+
+#[tokio::test]
+async fn rs256_tokens_are_verified_with_public_key() {
+    let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+    let app = TestApp::spawn_with_config(Config {
+        jwt_public_key_path: Some(format!("{fixtures}/jwt_rs256_public.pem")),
+        ..Config::default()
+    })
+    .await;
+
+    let private = std::fs::read(format!("{fixtures}/jwt_rs256_private.pem")).unwrap();
+    let claims = Claims { sub: "pokladna-1".into(), role: Some("editor".into()), exp: now() + 3600 };
+    let signed = encode(&Header::new(Algorithm::RS256), &claims, &EncodingKey::from_rsa_pem(&private).unwrap()).unwrap();
+    assert_eq!(post_with_token(&app, "/products", PRODUCT, &signed).await.status, 201);
+
+    // HS256 token s verejným kľúčom ako tajomstvom sa neprijme
+    let public = std::fs::read_to_string(format!("{fixtures}/jwt_rs256_public.pem")).unwrap();
+    assert_eq!(post_with_token(&app, "/products", PRODUCT, &token(&public, None, now() + 3600)).await.status, 401);
+}
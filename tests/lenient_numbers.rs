@@ -0,0 +1,50 @@
+use store_manager::structs::{JsonBodyError, Product};
+use store_manager::test_support::TestApp;
+
+fn product(bar_code: &str, quantity: &str) -> String {
+    format!(
+        r#"{{"name":"Jablká","category":"Ovocie","quantity":{quantity},"unit":"kg","status":true,"bar_code":{bar_code},"cost_price":1.0,"sell_price":2.0}}"#
+    )
+}
+
+#[tokio::test]
+async fn string_barcode_and_quantity_are_parsed() {
+    let app = TestApp::spawn().await;
+
+    let res = app.post("/products", &product(r#""8586000000001""#, r#""12""#)).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let res = app.post("/products", &product(r#"" 42 ""#, r#""2,5""#)).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+
+    let first: Product = app.get("/products/1").await.json();
+    assert_eq!((first.bar_code, first.quantity), (Some(8586000000001), Some(12.0)));
+    let second: Product = app.get("/products/2").await.json();
+    assert_eq!((second.bar_code, second.quantity), (Some(42), Some(2.5)));
+
+    let parsed: Product = serde_json::from_str(r#"{"bar_code":"","quantity":null}"#).unwrap();
+    assert_eq!((parsed.bar_code, parsed.quantity), (None, None));
+    let found: Vec<Product> = app.get("/products?bar_code=42").await.json();
+    assert_eq!(found.len(), 1);
+}
+
+#[tokio::test]
+async fn overflowing_barcode_is_reported_clearly() {
+    let app = TestApp::spawn().await;
+
+    for bar_code in [r#""99999999999999999999""#, "99999999999999999999", "10000000000000000000"] {
+        let res = app.post("/products", &product(bar_code, "1")).await;
+        assert_eq!(res.status, 422, "{}", res.text());
+        let error: JsonBodyError = res.json();
+        assert_eq!(error.field.as_deref(), Some("bar_code"));
+        assert!(error.message.contains("mimo rozsahu"), "{}", error.message);
+    }
+
+    let error: JsonBodyError = app.post("/products", &product(r#""EAN-1""#, "1")).await.json();
+    assert!(error.message.contains("neplatný čiarový kód"), "{}", error.message);
+    let error: JsonBodyError = app.post("/products", &product("1", r#""veľa""#)).await.json();
+    assert_eq!(error.field.as_deref(), Some("quantity"));
+    assert!(error.message.contains("neplatné množstvo"), "{}", error.message);
+
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert!(products.is_empty());
+}
@@ -0,0 +1,55 @@
+use store_manager::structs::{Employee, Product};
+use store_manager::test_support::TestApp;
+
+async fn product(app: &TestApp, name: &str, bar_code: u32) {
+    let body = serde_json::json!({
+        "name": name, "category": "Akcie", "quantity": 1, "status": true,
+        "bar_code": bar_code, "cost_price": 0.05, "sell_price": 1.0,
+    });
+    assert_eq!(app.post("/products", &body.to_string()).await.status, 201);
+}
+
+async fn search_names(app: &TestApp, name: &str) -> Vec<String> {
+    let body = serde_json::json!({ "name": name }).to_string();
+    let res = app.post("/products/search", &body).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let products: Vec<Product> = res.json();
+    products.into_iter().filter_map(|p| p.name).collect()
+}
+
+#[tokio::test]
+async fn percent_and_underscore_match_literally() {
+    let app = TestApp::spawn().await;
+    product(&app, "Zľava 50% na kávu", 1).await;
+    product(&app, "Zľava 50 kusov", 2).await;
+    product(&app, "Kód A_1", 3).await;
+    product(&app, "Kód AB1", 4).await;
+    product(&app, r"Cesta C:\temp", 5).await;
+    product(&app, "Cesta C:temp", 6).await;
+
+    assert_eq!(search_names(&app, "50%").await, ["Zľava 50% na kávu"]);
+    assert_eq!(search_names(&app, "A_1").await, ["Kód A_1"]);
+    assert_eq!(search_names(&app, r"C:\t").await, [r"Cesta C:\temp"]);
+    assert!(search_names(&app, "%").await.iter().all(|n| n.contains('%')));
+    assert_eq!(search_names(&app, "_").await, ["Kód A_1"]);
+    // obyčajný podreťazec sa stále hľadá kdekoľvek v názve
+    assert_eq!(search_names(&app, "Zľava 50").await.len(), 2);
+
+    let res = app.get("/products?name=50%25").await;
+    let products: Vec<Product> = res.json();
+    assert_eq!(products.len(), 1);
+}
+
+#[tokio::test]
+async fn employee_search_escapes_wildcards() {
+    let app = TestApp::spawn().await;
+    for (name, note) in [("Jana", "prémia 10%"), ("Eva", "prémia 100 €"), ("Anna_M", "")] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","department":"Sklad","status":true,"note":"{note}"}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+
+    let found: Vec<Employee> = app.post("/employees/search", r#"{"note":"10%"}"#).await.json();
+    assert_eq!(found.iter().filter_map(|e| e.name.as_deref()).collect::<Vec<_>>(), ["Jana"]);
+    let found: Vec<Employee> = app.post("/employees/search", r#"{"name":"a_"}"#).await.json();
+    assert_eq!(found.iter().filter_map(|e| e.name.as_deref()).collect::<Vec<_>>(), ["Anna_M"]);
+}
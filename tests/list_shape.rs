@@ -0,0 +1,73 @@
+use store_manager::config::Config;
+use store_manager::list_shape::{ListShape, ACCEPT_ARRAY, ACCEPT_ENVELOPE};
+use store_manager::structs::{Employee, ListEnvelope, Product};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products(config: Config) -> TestApp {
+    let app = TestApp::spawn_with_config(config).await;
+    for i in 1..=3 {
+        let body = format!(
+            r#"{{"name":"Produkt {i}","category":"Nápoje","quantity":5,"status":true,"bar_code":{i},"cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+#[tokio::test]
+async fn lists_are_bare_arrays_unless_envelope_is_requested() {
+    let app = spawn_with_products(Config::default()).await;
+
+    let res = app.get("/products?limit=2").await;
+    assert_eq!(res.json::<Vec<Product>>().len(), 2);
+    assert_eq!(res.header("vary"), Some("accept"));
+
+    let res = app.request_with_headers("GET", "/products?limit=2", None, &[("accept", ACCEPT_ENVELOPE)]).await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("content-type"), Some(ACCEPT_ENVELOPE));
+    assert!(res.header("link").is_some());
+    let page: ListEnvelope<Product> = res.json();
+    assert_eq!((page.items.len(), page.total), (2, 3));
+
+    // vyhľadávanie aj zoznamy bez `X-Total-Count` majú rovnakú obálku
+    let res = app
+        .request_with_headers("POST", "/products/search", Some(r#"{"category":"Nápoje"}"#), &[("accept", ACCEPT_ENVELOPE)])
+        .await;
+    assert_eq!(res.json::<ListEnvelope<Product>>().total, 3);
+    let res = app.request_with_headers("GET", "/meta/categories", None, &[("accept", ACCEPT_ENVELOPE)]).await;
+    assert_eq!(res.json::<ListEnvelope<serde_json::Value>>().total, 1);
+
+    // jednotlivé záznamy sa nemenia
+    let res = app.request_with_headers("GET", "/products/1", None, &[("accept", ACCEPT_ENVELOPE)]).await;
+    assert_eq!(res.json::<Product>().id, Some(1));
+}
+
+#[tokio::test]
+async fn configured_envelope_default_can_be_overridden_per_request() {
+    let app = spawn_with_products(Config { list_shape: ListShape::Envelope, ..Config::default() }).await;
+
+    let page: ListEnvelope<Product> = app.get("/api/v1/products").await.json();
+    assert_eq!((page.items.len(), page.total), (3, 3));
+    let page: ListEnvelope<Employee> = app.get("/employees").await.json();
+    assert_eq!((page.items.len(), page.total), (0, 0));
+
+    let res = app.request_with_headers("GET", "/products", None, &[("accept", ACCEPT_ARRAY)]).await;
+    assert_eq!(res.json::<Vec<Product>>().len(), 3);
+    // profil bez úvodzoviek a za iným typom
+    let res = app
+        .request_with_headers("GET", "/products", None, &[("accept", "text/html, application/json;profile=array")])
+        .await;
+    assert_eq!(res.json::<Vec<Product>>().len(), 3);
+}
+
+#[test]
+fn list_shape_is_configurable() {
+    assert_eq!(Config::default().list_shape, ListShape::Array);
+    let config = Config::from_toml(r#"list_shape = "envelope""#).unwrap();
+    assert_eq!(config.list_shape, ListShape::Envelope);
+
+    let mut config = Config::default();
+    config.apply_overrides(|key| (key == "STORE_LIST_SHAPE").then(|| "Envelope".to_string())).unwrap();
+    assert_eq!(config.list_shape, ListShape::Envelope);
+    assert!(Config::from_toml(r#"list_shape = "pages""#).is_err());
+}
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use store_manager::config::Config;
+use store_manager::pricing::{MarginPolicy, MarginRule};
+use store_manager::structs::{ImportReport, MarginViolation, PriceChange, Product};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_app() -> TestApp {
+    let margin_policy = MarginPolicy {
+        default: Some(MarginRule::Percent(20.0)),
+        categories: BTreeMap::from([("Pečivo".to_string(), MarginRule::Absolute(0.30))]),
+    };
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), margin_policy, ..Config::default() })
+        .await;
+    for (name, category, cost, sell) in [("Kofola", "Nápoje", 1.0, 1.5), ("Chlieb", "Pečivo", 1.0, 1.4)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"{category}","quantity":5,"status":true,"bar_code":1,"cost_price":{cost},"sell_price":{sell}}}"#
+        );
+        assert_eq!(send(&app, "POST", "/products", Some(&body)).await.status, 201);
+    }
+    app
+}
+
+async fn send(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+async fn sell_price(app: &TestApp, id: u32) -> f64 {
+    send(app, "GET", &format!("/products/{id}"), None).await.json::<Product>().sell_price.unwrap()
+}
+
+#[test]
+fn margin_rules_parse_and_check() {
+    assert_eq!("15%".parse(), Ok(MarginRule::Percent(15.0)));
+    assert_eq!(" 0.20 ".parse(), Ok(MarginRule::Absolute(0.20)));
+    assert!("100%".parse::<MarginRule>().is_err());
+    assert!("-1".parse::<MarginRule>().is_err());
+    assert!("veľa".parse::<MarginRule>().is_err());
+
+    assert!(MarginRule::Percent(20.0).allows(0.8, 1.0));
+    assert!(!MarginRule::Percent(20.0).allows(0.81, 1.0));
+    assert!(MarginRule::Absolute(0.2).allows(1.0, 1.2));
+
+    let categories = MarginPolicy::parse_categories("Pečivo=10%, Nápoje=0.30").unwrap();
+    assert_eq!(categories["Nápoje"], MarginRule::Absolute(0.30));
+    assert!(MarginPolicy::parse_categories("Pečivo").is_err());
+}
+
+#[tokio::test]
+async fn price_update_below_margin_is_rejected_with_details() {
+    let app = spawn_app().await;
+
+    // Nápoje: 20 % z predajnej ceny, 1.25 je presne na hranici
+    let res = send(&app, "PUT", "/products/1", Some(r#"{"sell_price":1.1}"#)).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("9.1 %") && res.text().contains("20 %"), "{}", res.text());
+    assert_eq!(send(&app, "PUT", "/products/1", Some(r#"{"sell_price":1.25}"#)).await.status, 200);
+
+    // Pečivo: výnimka kategórie 0.30 €
+    let res = send(&app, "PUT", "/products/2", Some(r#"{"sell_price":1.25}"#)).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("0.30 €"), "{}", res.text());
+    assert_eq!(sell_price(&app, 2).await, 1.4);
+
+    // výslovné vynechanie kontroly sa zapíše do audit logu
+    let res = send(&app, "PUT", "/products/2?override_margin_check=true", Some(r#"{"sell_price":1.25}"#)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(sell_price(&app, 2).await, 1.25);
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "margin_override");
+    assert!(audit[0].details.as_deref().unwrap().contains("product_id=2"));
+}
+
+#[tokio::test]
+async fn cost_increase_is_reported_not_blocked() {
+    let app = spawn_app().await;
+    assert!(send(&app, "GET", "/reports/margin-violations", None).await.json::<Vec<MarginViolation>>().is_empty());
+
+    assert_eq!(send(&app, "PUT", "/products/1", Some(r#"{"cost_price":1.3}"#)).await.status, 200);
+
+    let report: Vec<MarginViolation> = send(&app, "GET", "/reports/margin-violations", None).await.json();
+    assert_eq!(report.len(), 1);
+    assert_eq!((report[0].product_id, report[0].threshold.as_str()), (1, "20 %"));
+    assert!((report[0].margin - 0.2).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn bulk_adjustment_is_all_or_nothing() {
+    let app = spawn_app().await;
+
+    let res = send(&app, "POST", "/products/adjust-prices", Some(r#"{"percent":-10}"#)).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("Chlieb (2)") && !res.text().contains("Kofola"), "{}", res.text());
+    assert_eq!((sell_price(&app, 1).await, sell_price(&app, 2).await), (1.5, 1.4));
+
+    let res = send(&app, "POST", "/products/adjust-prices?override_margin_check=true", Some(r#"{"percent":-10}"#)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<Vec<PriceChange>>().len(), 2);
+    assert_eq!(app.db.get_audit_log().await.unwrap()[0].action, "margin_override");
+}
+
+#[tokio::test]
+async fn importers_reject_products_below_margin() {
+    let app = spawn_app().await;
+    let cheap = r#"{"type":"product","name":"Rožok","category":"Pečivo","quantity":1,"status":true,"bar_code":2,"cost_price":0.1,"sell_price":0.2}"#;
+    let fine = r#"{"type":"product","name":"Vinea","category":"Nápoje","quantity":1,"status":true,"bar_code":3,"cost_price":1.0,"sell_price":2.0}"#;
+
+    let res = send(&app, "POST", "/admin/import-ndjson?lenient=true", Some(&format!("{cheap}\n{fine}\n"))).await;
+    let report: ImportReport = res.json();
+    assert_eq!((report.products, report.skipped), (1, 1));
+    assert!(report.errors[0].message.contains("0.30 €"), "{}", report.errors[0].message);
+
+    let data = r#"{"employees":[],"products":[{"name":"Rožok","category":"Pečivo","quantity":1,"status":true,"bar_code":2,"cost_price":0.1,"sell_price":0.2}]}"#;
+    let res = send(&app, "POST", "/admin/import", Some(data)).await;
+    assert_eq!(res.status, 422, "{}", res.text());
+    assert_eq!(send(&app, "GET", "/products", None).await.json::<Vec<Product>>().len(), 3);
+
+    let res = send(&app, "POST", "/admin/import?override_margin_check=true", Some(data)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(send(&app, "GET", "/products", None).await.json::<Vec<Product>>().len(), 4);
+}
@@ -0,0 +1,80 @@
+use store_manager::config::Config;
+use store_manager::structs::{Product, PurchaseOrderReceiptResult};
+use store_manager::test_support::TestApp;
+
+async fn spawn(block_overstock: bool) -> TestApp {
+    let app = TestApp::spawn_with_config(Config { block_overstock, ..Config::default() }).await;
+    for (name, quantity, max_stock) in [("Kofola", 30, Some(20)), ("Vinea", 10, Some(20)), ("Rajec", 50, None)] {
+        let max_stock = max_stock.map(|m| format!(r#","max_stock":{m}"#)).unwrap_or_default();
+        let body = format!(
+            r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0{max_stock}}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+/// Objedná a prijme `quantity` kusov produktu 2 (Vinea).
+async fn receive(app: &TestApp, quantity: u32) -> (u16, String) {
+    let po = format!(r#"{{"supplier":"Baliareň","items":[{{"product_id":2,"quantity":{quantity},"unit_cost":1.0}}]}}"#);
+    let id: u32 = app.post("/purchase-orders", &po).await.json();
+    assert_eq!(app.put(&format!("/purchase-orders/{id}"), r#"{"status":"sent"}"#).await.status, 200);
+    let receipt = format!(r#"{{"items":[{{"product_id":2,"received_quantity":{quantity}}}]}}"#);
+    let res = app.post(&format!("/purchase-orders/{id}/receive"), &receipt).await;
+    (res.status, res.text())
+}
+
+async fn overstocked(app: &TestApp) -> Vec<u32> {
+    let products: Vec<Product> = app.get("/products/overstocked").await.json();
+    products.iter().map(|p| p.id.unwrap()).collect()
+}
+
+#[tokio::test]
+async fn lists_products_above_their_max_stock() {
+    let app = spawn(false).await;
+    assert_eq!(overstocked(&app).await, [1]);
+
+    let res = app.put("/products/3/max-stock", r#"{"max_stock":10}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<Product>().max_stock, Some(10.0));
+    // Rajec prekračuje o 40, Kofola o 10
+    assert_eq!(overstocked(&app).await, [3, 1]);
+
+    let product: Product = app.put("/products/1/max-stock", r#"{"max_stock":null}"#).await.json();
+    assert_eq!(product.max_stock, None);
+    assert_eq!(overstocked(&app).await, [3]);
+
+    assert_eq!(app.put("/products/99/max-stock", r#"{"max_stock":1}"#).await.status, 404);
+    assert_eq!(app.put("/products/1/max-stock", r#"{"max_stock":-1}"#).await.status, 400);
+}
+
+#[tokio::test]
+async fn restock_over_max_stock_warns_by_default() {
+    let app = spawn(false).await;
+
+    let (status, body) = receive(&app, 10).await;
+    assert_eq!(status, 200, "{body}");
+    let result: PurchaseOrderReceiptResult = serde_json::from_str(&body).unwrap();
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+
+    let (status, body) = receive(&app, 5).await;
+    assert_eq!(status, 200, "{body}");
+    let result: PurchaseOrderReceiptResult = serde_json::from_str(&body).unwrap();
+    assert_eq!(result.warnings.len(), 1);
+    assert!(result.warnings[0].contains("produkt 2"), "{:?}", result.warnings);
+    assert_eq!(app.db.get_product(2).await.unwrap().unwrap().quantity, Some(25.0));
+    assert_eq!(overstocked(&app).await, [1, 2]);
+}
+
+#[tokio::test]
+async fn restock_over_max_stock_is_blocked_when_configured() {
+    let app = spawn(true).await;
+
+    let (status, body) = receive(&app, 15).await;
+    assert_eq!(status, 409, "{body}");
+    assert_eq!(app.db.get_product(2).await.unwrap().unwrap().quantity, Some(10.0));
+
+    let (status, body) = receive(&app, 10).await;
+    assert_eq!(status, 200, "{body}");
+    assert_eq!(app.db.get_product(2).await.unwrap().unwrap().quantity, Some(20.0));
+}
@@ -0,0 +1,59 @@
+use store_manager::config::Config;
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, brand: &str, supplier: &str) {
+    let body = format!(
+        r#"{{"name":"Produkt","category":"Nápoje","quantity":1,"status":true,"bar_code":1,
+            "cost_price":1.0,"sell_price":2.0,"brand":"{brand}","supplier":"{supplier}"}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn brands_are_filtered_by_prefix() {
+    let app = TestApp::spawn().await;
+    for brand in ["Sony", "Solo", "Sony", "Samsung", "sodovka", "Rajec"] {
+        add_product(&app, brand, "A").await;
+    }
+
+    let brands: Vec<String> = app.get("/meta/brands").await.json();
+    assert_eq!(brands, ["Rajec", "Samsung", "Solo", "Sony", "sodovka"]);
+
+    let brands: Vec<String> = app.get("/meta/brands?prefix=so").await.json();
+    assert_eq!(brands, ["Solo", "Sony", "sodovka"]);
+
+    let brands: Vec<String> = app.get("/meta/brands?prefix=x").await.json();
+    assert!(brands.is_empty());
+}
+
+#[tokio::test]
+async fn limit_is_applied_and_capped() {
+    let app = TestApp::spawn_with_config(Config { max_result_rows: 3, ..Config::default() }).await;
+    for brand in ["A1", "A2", "A3", "A4", "A5"] {
+        add_product(&app, brand, "Kofola a.s.").await;
+    }
+
+    let brands: Vec<String> = app.get("/meta/brands?prefix=a&limit=2").await.json();
+    assert_eq!(brands, ["A1", "A2"]);
+    let brands: Vec<String> = app.get("/meta/brands?limit=100").await.json();
+    assert_eq!(brands.len(), 3);
+
+    let suppliers: Vec<String> = app.get("/meta/suppliers?prefix=Kof").await.json();
+    assert_eq!(suppliers, ["Kofola a.s."]);
+    let categories: Vec<String> = app.get("/meta/categories").await.json();
+    assert_eq!(categories, ["Nápoje"]);
+}
+
+#[tokio::test]
+async fn wildcards_in_prefix_match_literally() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "50% zľava", "A").await;
+    add_product(&app, "500 ml", "A").await;
+    add_product(&app, "Bio_line", "A").await;
+    add_product(&app, "Biosfera", "A").await;
+
+    let brands: Vec<String> = app.get("/meta/brands?prefix=50%25").await.json();
+    assert_eq!(brands, ["50% zľava"]);
+    let brands: Vec<String> = app.get("/meta/brands?prefix=Bio_").await.json();
+    assert_eq!(brands, ["Bio_line"]);
+}
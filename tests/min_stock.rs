@@ -0,0 +1,73 @@
+use store_manager::structs::{Alert, LowStockReport, Product, ReorderSuggestion, StockStatus};
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, quantity: u32, min_stock: Option<u32>) {
+    let min_stock = min_stock.map(|m| format!(r#","min_stock":{m}"#)).unwrap_or_default();
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0{min_stock}}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+/// Spoločná hranica je 5: Kofola (3) pod ňou, Vinea (8) s vlastnou 10, Rajec (4) s vlastnou 2, Voda (9) bez.
+async fn spawn_with_stock() -> TestApp {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Kofola", 3, None).await;
+    add_product(&app, "Vinea", 8, Some(10)).await;
+    add_product(&app, "Rajec", 4, Some(2)).await;
+    add_product(&app, "Voda", 9, None).await;
+    app
+}
+
+#[tokio::test]
+async fn low_stock_uses_product_threshold_where_set() {
+    let app = spawn_with_stock().await;
+
+    let report: LowStockReport = app.get("/reports/low-stock").await.json();
+    let names: Vec<_> = report.lines.iter().map(|l| l.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Kofola", "Vinea"]);
+    assert_eq!(report.lines.iter().map(|l| l.min_stock).collect::<Vec<_>>(), [None, Some(10.0)]);
+
+    let suggestions: Vec<ReorderSuggestion> = app.get("/reports/reorder-suggestions").await.json();
+    assert_eq!(suggestions.iter().map(|s| s.product_id).collect::<Vec<_>>(), [1, 2]);
+
+    assert_eq!(app.get("/products/2/stock-status").await.json::<StockStatus>(), StockStatus::LowStock);
+    assert_eq!(app.get("/products/3/stock-status").await.json::<StockStatus>(), StockStatus::InStock);
+
+    app.db.evaluate_stock_alerts(5).await.unwrap();
+    let alerts: Vec<Alert> = app.get("/alerts?open=true").await.json();
+    let mut ids: Vec<_> = alerts.iter().map(|a| a.product_id).collect();
+    ids.sort();
+    assert_eq!(ids, [1, 2]);
+}
+
+#[tokio::test]
+async fn min_stock_can_be_set_and_cleared() {
+    let app = spawn_with_stock().await;
+
+    let res = app.put("/products/4/min-stock", r#"{"min_stock":12}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<Product>().min_stock, Some(12.0));
+    let product: Product = app.put("/products/2/min-stock", r#"{"min_stock":null}"#).await.json();
+    assert_eq!(product.min_stock, None);
+
+    let report: LowStockReport = app.get("/reports/low-stock").await.json();
+    let names: Vec<_> = report.lines.iter().map(|l| l.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Kofola", "Voda"]);
+
+    // resolve sa vyhodnotí podľa novej hranice
+    app.db.evaluate_stock_alerts(5).await.unwrap();
+    app.put("/products/4/min-stock", r#"{"min_stock":1}"#).await;
+    let evaluation = app.db.evaluate_stock_alerts(5).await.unwrap();
+    assert_eq!((evaluation.created, evaluation.resolved), (0, 1));
+}
+
+#[tokio::test]
+async fn min_stock_rejects_invalid_values() {
+    let app = spawn_with_stock().await;
+    assert_eq!(app.put("/products/99/min-stock", r#"{"min_stock":1}"#).await.status, 404);
+    assert_eq!(app.put("/products/1/min-stock", r#"{"min_stock":-1}"#).await.status, 400);
+    assert_eq!(app.put("/products/1/min-stock", r#"{"min_stock":1.5}"#).await.status, 400);
+    assert_eq!(app.put("/products/1", r#"{"min_stock":-2}"#).await.status, 400);
+    assert_eq!(app.db.get_product(1).await.unwrap().unwrap().min_stock, None);
+}
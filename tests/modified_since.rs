@@ -0,0 +1,44 @@
+use std::time::Duration;
+
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+
+fn names(products: &[Product]) -> Vec<&str> {
+    products.iter().map(|p| p.name.as_deref().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn modified_since_returns_only_products_changed_after_timestamp() {
+    let app = TestApp::spawn().await;
+    for name in ["Kofola", "Vinea", "Rajec"] {
+        let body = format!(r#"{{"name":"{name}","category":"Nápoje","quantity":5,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#);
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    let products: Vec<Product> = app.get("/products").await.json();
+    let ts = products.iter().filter_map(|p| p.updated_at).max().unwrap();
+
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert_eq!(app.put("/products/2", r#"{"quantity":7}"#).await.status, 200);
+    let url = format!("/products/modified-since?ts={}", ts.format("%Y-%m-%dT%H:%M:%S%.3f"));
+    let res = app.get(&url).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("x-total-count"), Some("1"));
+    assert_eq!(names(&res.json::<Vec<Product>>()), ["Vinea"]);
+
+    // naposledy zmenený prvý, stránkovanie ako pri GET /products
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert_eq!(app.put("/products/1", r#"{"quantity":8}"#).await.status, 200);
+    assert_eq!(names(&app.get(&url).await.json::<Vec<Product>>()), ["Kofola", "Vinea"]);
+    let res = app.get(&format!("{url}&limit=1&offset=1")).await;
+    assert_eq!(res.header("x-total-count"), Some("2"));
+    assert_eq!(names(&res.json::<Vec<Product>>()), ["Vinea"]);
+}
+
+#[tokio::test]
+async fn modified_since_rejects_bad_timestamp() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.get("/products/modified-since").await.status, 400);
+    assert_eq!(app.get("/products/modified-since?ts=včera").await.status, 400);
+    assert_eq!(app.get("/products/modified-since?ts=2024-13-01T00:00:00").await.status, 400);
+    assert_eq!(app.get("/products/modified-since?ts=2024-06-01T00:00:00").await.status, 200);
+}
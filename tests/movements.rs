@@ -0,0 +1,64 @@
+use store_manager::structs::{MovementDirection, StockMovement};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_product() -> TestApp {
+    let app = TestApp::spawn().await;
+    let employee = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","status":true}"#;
+    assert_eq!(app.post("/employees", employee).await.status, 201);
+    let product = r#"{"name":"Kofola","category":"Nápoje","quantity":10,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", product).await.status, 201);
+    app
+}
+
+async fn movements(app: &TestApp, query: &str) -> Vec<StockMovement> {
+    let res = app.get(&format!("/products/1/movements{query}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn sale_return_and_restock_each_write_a_ledger_row() {
+    let app = spawn_with_product().await;
+    let order = r#"{"employee_id":1,"created_at":"2024-03-01T10:00:00","items":[{"product_id":1,"quantity":3}]}"#;
+    assert_eq!(app.post("/orders", order).await.status, 201);
+    let ret = r#"{"employee_id":1,"created_at":"2024-03-02T09:00:00","items":[{"product_id":1,"quantity":1}]}"#;
+    assert_eq!(app.post("/orders/1/returns", ret).await.status, 201);
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":5}"#).await.status, 200);
+
+    let ledger = movements(&app, "").await;
+    let rows: Vec<_> = ledger
+        .iter()
+        .map(|m| (m.direction, m.quantity, m.reason.as_str(), m.reference.as_deref(), m.employee_id))
+        .collect();
+    assert_eq!(
+        rows,
+        [
+            (MovementDirection::Out, -3.0, "sale", Some("ORD-1"), Some(1)),
+            (MovementDirection::In, 1.0, "return", Some("RET-1"), Some(1)),
+            (MovementDirection::In, 5.0, "adjustment", None, None),
+        ]
+    );
+    assert_eq!(ledger[0].created_at.to_string(), "2024-03-01 10:00:00");
+
+    // súčet pohybov zodpovedá zmene stavu zásob
+    let net: f64 = ledger.iter().map(|m| m.quantity).sum();
+    assert_eq!(net, 3.0);
+}
+
+#[tokio::test]
+async fn ledger_filters_by_period_and_rejects_unknown_product() {
+    let app = spawn_with_product().await;
+    for day in ["2024-03-01", "2024-03-05", "2024-03-09"] {
+        let order = format!(r#"{{"created_at":"{day}T12:00:00","items":[{{"product_id":1,"quantity":1}}]}}"#);
+        assert_eq!(app.post("/orders", &order).await.status, 201);
+    }
+
+    let ledger = movements(&app, "?from=2024-03-02&to=2024-03-09").await;
+    let refs: Vec<_> = ledger.iter().filter_map(|m| m.reference.clone()).collect();
+    assert_eq!(refs, ["ORD-2", "ORD-3"]);
+    assert_eq!(movements(&app, "?to=2024-03-01").await.len(), 1);
+    assert_eq!(app.db.movements(1, None, None).await.unwrap().len(), 3);
+
+    assert_eq!(app.get("/products/1/movements?from=2024-03-09&to=2024-03-01").await.status, 400);
+    assert_eq!(app.get("/products/9/movements").await.status, 404);
+}
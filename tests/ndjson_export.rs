@@ -0,0 +1,41 @@
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+
+fn product(i: u32) -> Product {
+    let category = if i.is_multiple_of(3) { "Pečivo" } else { "Nápoje" };
+    serde_json::from_str(&format!(
+        r#"{{"name":"Produkt {i}","category":"{category}","quantity":{i},"status":true,"bar_code":{i},"cost_price":1.0,"sell_price":2.0}}"#
+    ))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn ndjson_export_streams_every_product_across_pages() {
+    let app = TestApp::spawn().await;
+    // viac ako dve stránky exportu
+    for i in 1..=1100 {
+        app.db.add_product_to_store_db(&product(i)).await.unwrap();
+    }
+
+    let res = app.get("/products/export.ndjson").await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("content-type"), Some("application/x-ndjson"));
+    let text = res.text();
+    assert!(text.ends_with('\n'));
+    let products: Vec<Product> = text.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(products.len() as u64, app.db.count_products(Product::new_empty(), false).await.unwrap());
+    assert_eq!(products.first().and_then(|p| p.id), Some(1));
+    assert_eq!(products.last().and_then(|p| p.id), Some(1100));
+
+    // filter z parametrov dotazu
+    let res = app.get("/products/export.ndjson?category=Pe%C4%8Divo").await;
+    assert_eq!(res.text().lines().count(), 366);
+}
+
+#[tokio::test]
+async fn ndjson_export_of_empty_store_is_empty() {
+    let app = TestApp::spawn().await;
+    let res = app.get("/products/export.ndjson").await;
+    assert_eq!(res.status, 200);
+    assert!(res.body.is_empty());
+}
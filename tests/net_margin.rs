@@ -0,0 +1,53 @@
+use store_manager::structs::{NetMargin, Product};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_product() -> TestApp {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Kofola","category":"Nápoje","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    app
+}
+
+async fn net_margin(app: &TestApp, query: &str) -> NetMargin {
+    let res = app.get(&format!("/products/1/net-margin{query}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[test]
+fn net_margin_subtracts_cost_and_fees() {
+    let product = Product { cost_price: Some(1.0), ..Product::new_empty() };
+    assert_eq!(product.net_margin(2.0, 0.0), Some(1.0));
+    assert!((product.net_margin(2.0, 0.02).unwrap() - 0.96).abs() < 1e-9);
+    assert_eq!(Product::new_empty().net_margin(2.0, 0.02), None);
+}
+
+#[tokio::test]
+async fn net_margin_uses_regular_price_without_sale() {
+    let app = spawn_with_product().await;
+
+    let margin = net_margin(&app, "").await;
+    assert_eq!((margin.price, margin.on_sale, margin.net_margin), (Some(2.0), false, Some(1.0)));
+    assert_eq!(margin.net_margin_percent, Some(50.0));
+
+    let margin = net_margin(&app, "?fee=0.02").await;
+    assert!((margin.fees.unwrap() - 0.04).abs() < 1e-9, "{margin:?}");
+    assert!((margin.net_margin.unwrap() - 0.96).abs() < 1e-9, "{margin:?}");
+
+    assert_eq!(app.get("/products/1/net-margin?fee=1.5").await.status, 400);
+    assert_eq!(app.get("/products/1/net-margin?fee=-0.1").await.status, 400);
+    assert_eq!(app.get("/products/9/net-margin").await.status, 404);
+}
+
+#[tokio::test]
+async fn net_margin_honors_active_sale() {
+    let app = spawn_with_product().await;
+    let ends = chrono::Local::now().date_naive();
+    let res = app.post("/products/1/sale", &format!(r#"{{"sale_price":1.5,"ends":"{ends}"}}"#)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+
+    let margin = net_margin(&app, "?fee=0.02").await;
+    assert_eq!((margin.price, margin.on_sale), (Some(1.5), true));
+    assert!((margin.fees.unwrap() - 0.03).abs() < 1e-9, "{margin:?}");
+    assert!((margin.net_margin.unwrap() - 0.47).abs() < 1e-9, "{margin:?}");
+}
@@ -0,0 +1,60 @@
+use store_manager::config::Config;
+use store_manager::structs::{Employee, OrgNode};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+/// Strom ako `meno(podriadení...)` pre prehľadné porovnanie.
+fn outline(nodes: &[OrgNode]) -> Vec<String> {
+    nodes
+        .iter()
+        .map(|node| {
+            let name = node.employee.name.clone().unwrap_or_default();
+            if node.reports.is_empty() { name } else { format!("{name}({})", outline(&node.reports).join(" ")) }
+        })
+        .collect()
+}
+
+fn employee(id: u32, name: &str, manager_id: Option<u32>) -> Employee {
+    Employee { id: Some(id), name: Some(name.into()), manager_id, ..Employee::default() }
+}
+
+#[tokio::test]
+async fn org_chart_nests_reports_under_managers() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    assert!(admin(&app, "GET", "/employees/org-chart", None).await.json::<Vec<OrgNode>>().is_empty());
+
+    // Riaditeľka (1) → vedúca (2) → predavačky (3, 4); vedúca (5) pod riaditeľkou; skladník (6) bez nadriadeného
+    for (name, manager) in
+        [("Riaditeľka", None), ("Vedúca", Some(1)), ("Jana", Some(2)), ("Eva", Some(2)), ("Mária", Some(1)), ("Peter", None)]
+    {
+        let manager = manager.map(|m| format!(r#","manager_id":{m}"#)).unwrap_or_default();
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true{manager}}}"#);
+        assert_eq!(admin(&app, "POST", "/employees", Some(&body)).await.status, 201);
+    }
+
+    let res = admin(&app, "GET", "/employees/org-chart", None).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let chart: Vec<OrgNode> = res.json();
+    assert_eq!(outline(&chart), ["Riaditeľka(Vedúca(Jana Eva) Mária)", "Peter"]);
+    assert_eq!(chart[0].reports[0].employee.id, Some(2));
+}
+
+#[test]
+fn cycles_and_missing_managers_do_not_lose_employees() {
+    // 2 → 3 → 4 → 2 je cyklus zo starších dát, 5 má neexistujúceho nadriadeného, 6 je sám sebe nadriadeným
+    let chart = OrgNode::build(vec![
+        employee(4, "D", Some(2)),
+        employee(1, "A", None),
+        employee(2, "B", Some(3)),
+        employee(3, "C", Some(4)),
+        employee(5, "E", Some(99)),
+        employee(6, "F", Some(6)),
+        employee(7, "G", Some(3)),
+    ]);
+    assert_eq!(outline(&chart), ["A", "B(D(C(G)))", "E", "F"]);
+}
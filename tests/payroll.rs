@@ -0,0 +1,59 @@
+use store_manager::structs::{LaborRatio, PayrollSummary};
+use store_manager::test_support::TestApp;
+
+async fn add(app: &TestApp, name: &str, salary: Option<f64>, status: bool) {
+    let salary = salary.map(|s| format!(r#","salary":{s}"#)).unwrap_or_default();
+    let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":{status}{salary}}}"#);
+    assert_eq!(app.post("/employees", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn payroll_sums_active_salaries() {
+    let app = TestApp::spawn().await;
+    add(&app, "Jana", Some(1200.0), true).await;
+    add(&app, "Eva", Some(1500.5), true).await;
+    add(&app, "Mária", None, true).await;
+    add(&app, "Zuzana", Some(2000.0), false).await;
+
+    let res = app.get("/stats/payroll").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let payroll: PayrollSummary = res.json();
+    assert_eq!(
+        payroll,
+        PayrollSummary { headcount: 2, monthly: 2700.5, annual: 32406.0, without_salary: 1, excluded_inactive: 1 }
+    );
+}
+
+#[tokio::test]
+async fn empty_staff_has_zero_payroll() {
+    let app = TestApp::spawn().await;
+    let payroll = app.db.total_payroll().await.unwrap();
+    assert_eq!((payroll.headcount, payroll.monthly, payroll.annual), (0, 0.0, 0.0));
+}
+
+#[tokio::test]
+async fn labor_ratio_divides_payroll_by_inventory_value() {
+    let app = TestApp::spawn().await;
+    add(&app, "Jana", Some(1200.0), true).await;
+    add(&app, "Eva", Some(800.0), true).await;
+    add(&app, "Zuzana", Some(5000.0), false).await;
+
+    assert_eq!(app.db.labor_to_inventory_ratio().await.unwrap(), None);
+    let ratio: LaborRatio = app.get("/stats/labor-ratio").await.json();
+    assert_eq!((ratio.ratio, ratio.percent), (None, None));
+    assert!(ratio.note.unwrap().contains("nulová"));
+
+    // zásoby 100 × 40 € + 50 × 80 € = 8000 €, mzdy 2000 €
+    for (name, quantity, cost_price) in [("Kofola", 100, 40.0), ("Vinea", 50, 80.0)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":{cost_price},"sell_price":100.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    assert_eq!(app.db.labor_to_inventory_ratio().await.unwrap(), Some(0.25));
+
+    let res = app.get("/stats/labor-ratio").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let ratio: LaborRatio = res.json();
+    assert_eq!((ratio.ratio, ratio.percent, ratio.note), (Some(0.25), Some(25.0), None));
+}
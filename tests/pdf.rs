@@ -0,0 +1,155 @@
+use chrono::NaiveDate;
+use lopdf::{content::Content, Document};
+use store_manager::config::Config;
+use store_manager::pdf::{self, Align, Column, TableReport};
+use store_manager::structs::{InventoryValueReport, LowStockReport};
+use store_manager::test_support::TestApp;
+
+/// Text každej strany PDF, jeden textový príkaz na riadok.
+///
+/// Zostavy používajú len vstavané písma v kódovaní WinAnsi.
+fn pages_text(bytes: &[u8]) -> Vec<String> {
+    let doc = Document::load_mem(bytes).expect("PDF sa dá načítať");
+    doc.get_pages()
+        .values()
+        .map(|page| {
+            let content = Content::decode(&doc.get_page_content(*page).unwrap()).unwrap();
+            content
+                .operations
+                .iter()
+                .filter(|op| op.operator == "Tj")
+                .flat_map(|op| &op.operands)
+                .filter_map(|operand| operand.as_str().ok())
+                .map(|text| Document::decode_text(Some("WinAnsiEncoding"), text) + "\n")
+                .collect()
+        })
+        .collect()
+}
+
+async fn add_product(app: &TestApp, name: &str, quantity: u32, cost: f64) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","supplier":"Kofola a.s.","quantity":{quantity},"status":true,
+            "bar_code":1,"cost_price":{cost},"sell_price":{}}}"#,
+        cost * 2.0
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[test]
+fn table_spans_pages_with_repeated_headers_and_totals_at_end() {
+    let report = TableReport {
+        shop_name: "Potraviny U Janka".into(),
+        title: "Skúšobná zostava".into(),
+        date: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+        columns: vec![Column { title: "Položka", width: 120.0, align: Align::Left }, Column {
+            title: "Suma",
+            width: 60.0,
+            align: Align::Right,
+        }],
+        rows: (1..=120).map(|i| vec![format!("Riadok {i}"), format!("{i}.00")]).collect(),
+        totals: vec![("Spolu".into(), "7260.00".into())],
+    };
+
+    let pages = pages_text(&pdf::render(&report).unwrap());
+    assert!(pages.len() >= 3, "{} strán", pages.len());
+    assert!(pages[0].contains("Potraviny U Janka"), "{}", pages[0]);
+    assert!(pages[0].contains("Skúšobná zostava"));
+    assert!(pages[0].contains("01.06.2024"));
+    for (i, page) in pages.iter().enumerate() {
+        assert!(page.contains("Položka") && page.contains("Suma"), "strana {}: {page}", i + 1);
+        assert!(page.contains(&format!("Strana {} / {}", i + 1, pages.len())), "{page}");
+    }
+
+    let last = pages.last().unwrap();
+    assert!(last.contains("Riadok 120") && last.contains("Spolu") && last.contains("7260.00"), "{last}");
+    assert!(!pages[0].contains("Spolu"));
+    assert_eq!(pages.iter().filter(|p| p.contains("Riadok 1\n")).count(), 1);
+}
+
+#[test]
+fn characters_outside_win_ansi_are_folded() {
+    let report = TableReport {
+        shop_name: "Čerstvé ovocie".into(),
+        title: "Ľadový čaj".into(),
+        date: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+        columns: vec![Column { title: "Názov", width: 180.0, align: Align::Left }],
+        rows: vec![vec!["Šťavnatý džús".into()]],
+        totals: Vec::new(),
+    };
+
+    let text = pages_text(&pdf::render(&report).unwrap()).concat();
+    assert!(text.contains("Cerstvé ovocie"), "{text}");
+    assert!(text.contains("Ladový caj"));
+    assert!(text.contains("Štavnatý džús"));
+}
+
+#[tokio::test]
+async fn inventory_value_report_as_json_and_pdf() {
+    let app = TestApp::spawn_with_config(Config { shop_name: "Potraviny U Janka".into(), ..Config::default() }).await;
+    for i in 1..=80 {
+        add_product(&app, &format!("Limonáda {i:02}"), 10, 0.5).await;
+    }
+
+    let report: InventoryValueReport = app.get("/reports/inventory-value").await.json();
+    assert_eq!(report.lines.len(), 80);
+    assert!((report.total_value - 400.0).abs() < 1e-9);
+
+    let res = app.get("/reports/inventory-value?format=pdf").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("application/pdf"));
+    assert!(res.header("content-disposition").unwrap().starts_with("attachment; filename=\"inventory-value-"));
+
+    let pages = pages_text(&res.body);
+    assert!(pages.len() >= 2);
+    assert!(pages[0].contains("Potraviny U Janka") && pages[0].contains("Hodnota zásob"));
+    assert!(pages.iter().all(|p| p.contains("Nákupná cena")));
+    let last = pages.last().unwrap();
+    assert!(last.contains("Limonáda 80") && last.contains("Hodnota zásob spolu") && last.contains("400.00"), "{last}");
+}
+
+#[tokio::test]
+async fn low_stock_report_lists_products_at_or_below_threshold() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Kofola", 0, 1.0).await;
+    add_product(&app, "Vinea", 5, 1.0).await;
+    add_product(&app, "Rajec", 6, 1.0).await;
+
+    let report: LowStockReport = app.get("/reports/low-stock").await.json();
+    assert_eq!(report.threshold, 5);
+    let names: Vec<_> = report.lines.iter().map(|l| l.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Kofola", "Vinea"]);
+    assert_eq!(report.out_of_stock, 1);
+
+    let res = app.get("/reports/low-stock?format=pdf").await;
+    assert_eq!(res.status, 200);
+    let text = pages_text(&res.body).concat();
+    assert!(text.contains("Nízky stav zásob") && text.contains("Dodávatel"), "{text}");
+    assert!(text.contains("Vinea") && !text.contains("Rajec"));
+    assert!(text.contains("Z toho vypredané"));
+
+    assert_eq!(app.get("/reports/low-stock?format=xlsx").await.status, 400);
+}
+
+#[tokio::test]
+async fn inventory_pdf_lists_products_with_category_totals() {
+    let app = TestApp::spawn_with_config(Config { shop_name: "Potraviny U Janka".into(), ..Config::default() }).await;
+    for i in 1..=70 {
+        add_product(&app, &format!("Limonáda {i:02}"), 10, 0.5).await;
+    }
+    let body = r#"{"name":"Chlieb","category":"Pečivo","quantity":4,"bar_code":2,"cost_price":1.25,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+
+    let res = app.get("/reports/inventory.pdf").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("application/pdf"));
+    assert!(res.header("content-disposition").unwrap().starts_with("attachment; filename=\"inventory-"));
+
+    let pages = pages_text(&res.body);
+    assert!(pages.len() >= 2, "{} strán", pages.len());
+    assert!(pages[0].contains("Inventúrny súpis zásob") && pages[0].contains("Hodnota"), "{}", pages[0]);
+    assert!(pages.iter().all(|p| p.contains("Kategória") && p.contains("Množstvo")));
+    let last = pages.last().unwrap();
+    assert!(last.contains("Kategória Nápoje\n350.00\n"), "{last}");
+    assert!(last.contains("Kategória Pecivo\n5.00\n"), "{last}");
+    assert!(last.contains("Pocet produktov\n71\n") && last.contains("Hodnota zásob spolu\n355.00\n"), "{last}");
+}
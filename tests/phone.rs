@@ -0,0 +1,62 @@
+use store_manager::config::Config;
+use store_manager::phone::normalize;
+use store_manager::structs::Employee;
+use store_manager::test_support::TestApp;
+
+#[test]
+fn common_formats_normalize_to_e164() {
+    for input in ["+421 900 123 456", "0900123456", "421900123456", "00421 900/123-456", "0900 123 456", "900123456"] {
+        assert_eq!(normalize(input, "SK").as_deref(), Ok("+421900123456"), "{input}");
+    }
+    assert_eq!(normalize("0601 234 567", "CZ").as_deref(), Ok("+420601234567"));
+    assert_eq!(normalize("+420 601 234 567", "SK").as_deref(), Ok("+420601234567"));
+}
+
+#[test]
+fn invalid_numbers_are_rejected() {
+    assert!(normalize("0900 12x 456", "SK").is_err());
+    assert!(normalize("123", "SK").is_err());
+    assert!(normalize("+4219001234567890", "SK").is_err());
+    assert!(normalize("0900123456", "XX").is_err());
+}
+
+#[tokio::test]
+async fn employee_search_matches_any_phone_format() {
+    let app = TestApp::spawn().await;
+    let res = app
+        .post(
+            "/employees",
+            r#"{"name":"Jana","surname":"Nová","position":"Predavačka","phone_number":"+421 900 123 456"}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+
+    for phone in ["+421 900 123 456", "0900123456", "421900123456", "0900 123 456"] {
+        let found: Vec<Employee> = app.post("/employees/search", &format!(r#"{{"phone_number":"{phone}"}}"#)).await.json();
+        assert_eq!(found.len(), 1, "{phone}");
+        assert_eq!(found[0].phone_number.as_deref(), Some("+421 900 123 456"));
+        assert_eq!(found[0].phone_e164.as_deref(), Some("+421900123456"));
+    }
+
+    let found: Vec<Employee> = app.get("/employees?phone_number=0900123456").await.json();
+    assert_eq!(found.len(), 1);
+
+    assert_eq!(app.put("/employees/1", r#"{"phone_number":"0911 222 333"}"#).await.status, 200);
+    let found: Vec<Employee> = app.post("/employees/search", r#"{"phone_number":"+421911222333"}"#).await.json();
+    assert_eq!(found.len(), 1);
+
+    assert_eq!(app.put("/employees/1", r#"{"phone_number":"abc"}"#).await.status, 400);
+    assert_eq!(app.post("/employees", r#"{"name":"Ján","surname":"Malý","position":"Skladník","phone_number":"12"}"#).await.status, 400);
+}
+
+#[tokio::test]
+async fn default_country_is_configurable() {
+    let app = TestApp::spawn_with_config(Config { phone_country: "CZ".into(), ..Config::default() }).await;
+    let res = app
+        .post("/employees", r#"{"name":"Petr","surname":"Novák","position":"Skladník","phone_number":"601 234 567"}"#)
+        .await;
+    assert_eq!(res.status, 201);
+
+    let found: Vec<Employee> = app.post("/employees/search", r#"{"phone_number":"+420601234567"}"#).await.json();
+    assert_eq!(found.len(), 1);
+}
@@ -0,0 +1,60 @@
+use store_manager::config::Config;
+use store_manager::pricing;
+use store_manager::structs::{PriceAnomaly, PriceAnomalyReason};
+use store_manager::test_support::TestApp;
+
+const KEY: &str = "tajny-kluc";
+
+#[test]
+fn outlier_is_measured_against_the_other_prices() {
+    let scores = pricing::outlier_scores(&[1.5, 1.6, 1.2, 1.4, 14.0]);
+    assert!(scores[4].unwrap() > 10.0, "{scores:?}");
+    assert!(scores[..4].iter().all(|z| z.unwrap().abs() < 3.0), "{scores:?}");
+
+    // primálo cien na porovnanie, nekladná cena sa vynechá
+    assert_eq!(pricing::outlier_scores(&[1.0, 2.0, 30.0]), [None, None, None]);
+    assert_eq!(pricing::outlier_scores(&[1.0, 1.0, 1.0, 1.0, 0.0])[4], None);
+}
+
+#[tokio::test]
+async fn mispriced_products_are_flagged_with_reasons() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    for (name, category, cost, sell, status) in [
+        ("Kofola", "Nápoje", 1.0, 1.5, true),
+        ("Vinea", "Nápoje", 1.0, 1.6, true),
+        ("Rajec", "Nápoje", 0.5, 1.2, true),
+        ("Tonik", "Nápoje", 1.0, 1.4, true),
+        // preklep: 14 € namiesto 1.40 €, prirážka je pritom v poriadku
+        ("Mattoni", "Nápoje", 10.0, 14.0, true),
+        ("Bonaqua", "Nápoje", 1.3, 1.35, true),
+        ("Semtex", "Nápoje", 0.2, 1.5, true),
+        // vyradený produkt sa nekontroluje
+        ("Starý", "Nápoje", 1.0, 50.0, false),
+        // dve ceny v kategórii sa navzájom neporovnávajú
+        ("Chlieb", "Pečivo", 0.5, 1.0, true),
+        ("Torta", "Pečivo", 15.0, 30.0, true),
+    ] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"{category}","quantity":5,"status":{status},"bar_code":1,"cost_price":{cost},"sell_price":{sell}}}"#
+        );
+        let res = app.request_with_headers("POST", "/products", Some(&body), &[("x-api-key", KEY)]).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+
+    let res = app.request_with_headers("GET", "/admin/price-anomalies", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let anomalies: Vec<PriceAnomaly> = res.json();
+    let flagged: Vec<_> = anomalies.iter().map(|a| (a.product_id, a.reasons.clone())).collect();
+    assert_eq!(
+        flagged,
+        [
+            (5, vec![PriceAnomalyReason::CategoryOutlier]),
+            (6, vec![PriceAnomalyReason::MarkupTooLow]),
+            (7, vec![PriceAnomalyReason::MarkupTooHigh]),
+        ]
+    );
+    assert!(anomalies[0].z_score.unwrap() > 3.0);
+    assert_eq!(anomalies[2].markup, Some(7.5));
+
+    assert_eq!(app.get("/admin/price-anomalies").await.status, 401);
+}
@@ -0,0 +1,103 @@
+use store_manager::config::Config;
+use store_manager::pricing::{MarginPolicy, MarginRule};
+use store_manager::structs::PriceListReport;
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+const BOUNDARY: &str = "hranica-suboru";
+
+async fn spawn(margin_policy: MarginPolicy) -> TestApp {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), margin_policy, ..Config::default() }).await;
+    for (name, bar_code, cost_price) in [("Kofola", 4006381333931_i64, 1.0), ("Vinea", 5901234123457, 1.5), ("Rajec", 96385074, 0.5)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Nápoje","quantity":10,"status":true,"bar_code":{bar_code},"cost_price":{cost_price},"sell_price":2.0}}"#
+        );
+        let res = app.request_with_headers("POST", "/products", Some(&body), &[("x-api-key", KEY)]).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+    app
+}
+
+async fn upload(app: &TestApp, path: &str, file_name: &str, content: &str) -> TestResponse {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n{content}\r\n--{BOUNDARY}--\r\n"
+    );
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+    app.request_with_headers("POST", path, Some(&body), &[("x-api-key", KEY), ("content-type", &content_type)]).await
+}
+
+async fn cost_price(app: &TestApp, id: u32) -> Option<f64> {
+    app.db.get_product(id).await.unwrap().unwrap().cost_price
+}
+
+const PRICE_LIST: &str = "barcode,new_cost\n\
+                          4006381333931,1.2\n\
+                          5901234123457,1.0\n\
+                          1234567890128,3.0\n\
+                          96385074,abc\n";
+
+#[tokio::test]
+async fn preview_reports_matched_unmatched_and_eroding_rows_without_writing() {
+    let app = spawn(MarginPolicy::default()).await;
+
+    let res = upload(&app, "/import/price-list/preview", "cennik.csv", PRICE_LIST).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: PriceListReport = res.json();
+    assert_eq!((report.rows, report.matched, report.unmatched, report.invalid, report.eroding), (4, 2, 1, 1, 1));
+    assert_eq!(report.applied, 0);
+
+    let kofola = &report.lines[0];
+    assert_eq!((kofola.product_id, kofola.old_cost, kofola.new_cost, kofola.sell_price), (Some(1), Some(1.0), Some(1.2), Some(2.0)));
+    // marža 50 % → 40 %
+    assert_eq!((kofola.old_margin, kofola.new_margin), (Some(50.0), Some(40.0)));
+    assert!((kofola.margin_change.unwrap() + 10.0).abs() < 1e-9, "{kofola:?}");
+    assert!(kofola.erodes_margin);
+
+    let vinea = &report.lines[1];
+    assert_eq!((vinea.product_id, vinea.erodes_margin), (Some(2), false));
+    assert!(vinea.margin_change.unwrap() > 0.0);
+    assert_eq!((report.lines[2].product_id, report.lines[2].error.as_deref()), (None, None));
+    assert!(report.lines[3].error.is_some());
+
+    assert_eq!(cost_price(&app, 1).await, Some(1.0));
+    assert_eq!(cost_price(&app, 2).await, Some(1.5));
+}
+
+#[tokio::test]
+async fn apply_writes_matched_costs_in_one_transaction() {
+    let app = spawn(MarginPolicy::default()).await;
+    let ndjson = "{\"barcode\":4006381333931,\"new_cost\":1.2}\n{\"bar_code\":1234567890128,\"new_cost\":3.0}\n";
+
+    let res = upload(&app, "/import/price-list/apply", "cennik.ndjson", ndjson).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: PriceListReport = res.json();
+    assert_eq!((report.matched, report.unmatched, report.applied), (1, 1, 1));
+    assert_eq!(cost_price(&app, 1).await, Some(1.2));
+    assert_eq!(cost_price(&app, 3).await, Some(0.5));
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "import_price_list");
+    assert_eq!(audit[0].details.as_deref(), Some("applied=1, unmatched=1, invalid=0"));
+}
+
+#[tokio::test]
+async fn apply_refuses_costs_below_minimum_margin() {
+    let app = spawn(MarginPolicy { default: Some(MarginRule::Percent(45.0)), ..MarginPolicy::default() }).await;
+
+    let res = upload(&app, "/import/price-list/preview", "cennik.csv", PRICE_LIST).await;
+    let report: PriceListReport = res.json();
+    assert!(report.lines[0].below_minimum.is_some());
+    assert!(report.lines[1].below_minimum.is_none());
+
+    let res = upload(&app, "/import/price-list/apply", "cennik.csv", PRICE_LIST).await;
+    assert_eq!(res.status, 422, "{}", res.text());
+    assert_eq!(cost_price(&app, 1).await, Some(1.0));
+    assert_eq!(cost_price(&app, 2).await, Some(1.5));
+
+    let res = upload(&app, "/import/price-list/apply?override_margin_check=true", "cennik.csv", PRICE_LIST).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<PriceListReport>().applied, 2);
+    assert_eq!(cost_price(&app, 1).await, Some(1.2));
+    assert_eq!(cost_price(&app, 2).await, Some(1.0));
+}
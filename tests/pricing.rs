@@ -0,0 +1,186 @@
+use store_manager::config::Config;
+use store_manager::pricing::PriceRounding;
+use store_manager::structs::{PriceChange, PriceRoundingReport, Product};
+use store_manager::test_support::TestApp;
+
+fn assert_rounds(rounding: PriceRounding, cases: &[(f64, f64)]) {
+    for &(raw, expected) in cases {
+        let rounded = rounding.apply(raw);
+        assert!((rounded - expected).abs() < 1e-9, "{rounding:?}: {raw} -> {rounded}, očakávané {expected}");
+    }
+}
+
+#[test]
+fn nearest_005_rounds_half_way_up() {
+    assert_rounds(
+        PriceRounding::Nearest005,
+        &[
+            (3.47, 3.45),
+            (3.48, 3.50),
+            (3.475, 3.50),
+            (3.425, 3.45),
+            (3.4249, 3.40),
+            (3.50, 3.50),
+            (1.1 * 1.1, 1.20),
+            (10.0249, 10.0),
+        ],
+    );
+}
+
+#[test]
+fn nearest_010_rounds_half_way_up() {
+    assert_rounds(
+        PriceRounding::Nearest010,
+        &[(3.47, 3.50), (3.44, 3.40), (3.45, 3.50), (3.4499, 3.40), (0.95, 1.0), (99.96, 100.0)],
+    );
+}
+
+#[test]
+fn psychological_ends_in_nine() {
+    assert_rounds(
+        PriceRounding::Psychological,
+        &[
+            (3.47, 3.49),
+            (3.43, 3.39),
+            (3.44, 3.49),
+            (3.50, 3.49),
+            (3.54, 3.59),
+            (3.49, 3.49),
+            (9.96, 9.99),
+            (10.04, 10.09),
+            (10.03, 9.99),
+        ],
+    );
+}
+
+#[test]
+fn nearest_099_picks_closest_whole_euro_minus_cent() {
+    assert_rounds(
+        PriceRounding::Nearest099,
+        &[(3.47, 2.99), (3.51, 3.99), (3.49, 3.99), (3.4899, 2.99), (3.99, 3.99), (4.20, 3.99), (0.30, 0.99), (12.0, 11.99)],
+    );
+}
+
+#[test]
+fn ceil_integer_rounds_up_to_whole_euros() {
+    assert_rounds(PriceRounding::CeilInteger, &[(3.01, 4.0), (3.0, 3.0), (3.99, 4.0), (0.01, 1.0), (1.1 * 3.0, 4.0)]);
+}
+
+#[test]
+fn small_prices_never_round_to_zero() {
+    assert_rounds(PriceRounding::Nearest005, &[(0.01, 0.05), (0.024, 0.05), (0.025, 0.05), (0.074, 0.05), (0.075, 0.10)]);
+    assert_rounds(PriceRounding::Nearest010, &[(0.01, 0.10), (0.049, 0.10), (0.15, 0.20)]);
+    assert_rounds(PriceRounding::Psychological, &[(0.01, 0.09), (0.13, 0.09), (0.14, 0.19)]);
+    assert_rounds(PriceRounding::Nearest099, &[(0.01, 0.99), (1.48, 0.99), (1.49, 1.99)]);
+    assert_rounds(PriceRounding::CeilInteger, &[(0.000001, 1.0)]);
+}
+
+#[test]
+fn no_rounding_and_invalid_prices_are_unchanged() {
+    assert_eq!(PriceRounding::None.apply(3.4712), 3.4712);
+    for rounding in [
+        PriceRounding::Nearest005,
+        PriceRounding::Nearest010,
+        PriceRounding::Psychological,
+        PriceRounding::Nearest099,
+        PriceRounding::CeilInteger,
+    ] {
+        assert_eq!(rounding.apply(0.0), 0.0);
+        assert_eq!(rounding.apply(-1.23), -1.23);
+        assert!(rounding.apply(f64::NAN).is_nan());
+    }
+}
+
+#[test]
+fn rounding_policy_parses_from_config_values() {
+    assert_eq!("0.05".parse(), Ok(PriceRounding::Nearest005));
+    assert_eq!("0.10".parse(), Ok(PriceRounding::Nearest010));
+    assert_eq!("x9".parse(), Ok(PriceRounding::Psychological));
+    assert_eq!("none".parse(), Ok(PriceRounding::None));
+    assert_eq!(".99".parse(), Ok(PriceRounding::Nearest099));
+    assert_eq!("ceil".parse(), Ok(PriceRounding::CeilInteger));
+    for rounding in [PriceRounding::Nearest099, PriceRounding::CeilInteger, PriceRounding::Psychological] {
+        assert_eq!(rounding.to_string().parse(), Ok(rounding));
+    }
+    assert!("0.07".parse::<PriceRounding>().is_err());
+}
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn_with_config(Config { price_rounding: PriceRounding::Psychological, ..Config::default() }).await;
+    for (name, category, price) in [("Kofola", "Nápoje", 1.49), ("Voda", "Nápoje", 0.59), ("Chlieb", "Pečivo", 2.10)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"{category}","quantity":5,"status":true,
+                "bar_code":1,"cost_price":0.5,"sell_price":{price}}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+#[tokio::test]
+async fn bulk_adjustment_returns_raw_and_rounded_prices() {
+    let app = spawn_with_products().await;
+
+    let changes: Vec<PriceChange> =
+        app.post("/products/adjust-prices", r#"{"percent":10,"category":"Nápoje"}"#).await.json();
+    assert_eq!(changes.len(), 2);
+    assert!((changes[0].raw_price - 1.639).abs() < 1e-9);
+    assert_eq!(changes[0].price, 1.59);
+    assert_eq!(changes[0].old_price, Some(1.49));
+    assert!((changes[1].raw_price - 0.649).abs() < 1e-9);
+    assert_eq!(changes[1].price, 0.69);
+
+    let bread: Product = app.get("/products/3").await.json();
+    assert_eq!(bread.sell_price, Some(2.10));
+    let cola: Product = app.get("/products/1").await.json();
+    assert_eq!(cola.sell_price, Some(1.59));
+
+    assert_eq!(app.post("/products/adjust-prices", r#"{"percent":-100}"#).await.status, 400);
+}
+
+#[tokio::test]
+async fn single_update_rounds_only_when_asked() {
+    let app = spawn_with_products().await;
+
+    let res = app.put("/products/3?round_price=true", r#"{"sell_price":2.23}"#).await;
+    assert_eq!(res.status, 200);
+    let change: PriceChange = res.json();
+    assert_eq!((change.old_price, change.raw_price, change.price), (Some(2.10), 2.23, 2.19));
+
+    assert_eq!(app.put("/products/3", r#"{"sell_price":2.23}"#).await.status, 200);
+    let bread: Product = app.get("/products/3").await.json();
+    assert_eq!(bread.sell_price, Some(2.23));
+
+    assert_eq!(app.put("/products/3?round_price=true", r#"{"name":"Chlieb"}"#).await.status, 400);
+    assert_eq!(app.put("/products/9?round_price=true", r#"{"sell_price":1}"#).await.status, 404);
+}
+
+#[tokio::test]
+async fn round_prices_previews_and_applies_rule_to_all_products() {
+    let app = spawn_with_products().await;
+
+    let res = app.post("/products/round-prices?dry_run=true", r#"{"rule":"0.99"}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: PriceRoundingReport = res.json();
+    assert!(report.dry_run);
+    assert_eq!((report.rule.as_str(), report.changed), ("0.99", 3));
+    let prices: Vec<_> = report.changes.iter().map(|c| (c.product_id, c.old_price, c.price)).collect();
+    assert_eq!(prices, [(1, Some(1.49), 1.99), (2, Some(0.59), 0.99), (3, Some(2.10), 1.99)]);
+    let cola: Product = app.get("/products/1").await.json();
+    assert_eq!(cola.sell_price, Some(1.49));
+
+    let report: PriceRoundingReport = app.post("/products/round-prices", r#"{"rule":"ceil"}"#).await.json();
+    assert!(!report.dry_run);
+    assert_eq!(report.changed, 3);
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert_eq!(products.iter().map(|p| p.sell_price.unwrap()).collect::<Vec<_>>(), [2.0, 1.0, 3.0]);
+
+    // ceny už sú celé eurá, nič sa nemení
+    let report: PriceRoundingReport = app.post("/products/round-prices", r#"{"rule":"ceil"}"#).await.json();
+    assert_eq!(report.changed, 0);
+    let report: PriceRoundingReport = app.post("/products/round-prices", r#"{"rule":"0.05"}"#).await.json();
+    assert_eq!(report.changed, 0);
+
+    assert_eq!(app.post("/products/round-prices", r#"{"rule":"none"}"#).await.status, 400);
+    assert_eq!(app.post("/products/round-prices", r#"{"rule":"0.07"}"#).await.status, 400);
+}
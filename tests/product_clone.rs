@@ -0,0 +1,47 @@
+use chrono::Local;
+use store_manager::structs::{Product, ProductBatch};
+use store_manager::test_support::TestApp;
+
+const PRODUCT: &str = r#"{"name":"Mlieko 1 l","category":"Mliečne","quantity":12,"unit":"piece","status":true,"bar_code":858001,"cost_price":0.8,"sell_price":1.2,"description":"Plnotučné","brand":"Rajo","supplier":"Rajo a.s.","date_added":"2024-01-15"}"#;
+
+#[tokio::test]
+async fn clone_copies_fields_with_overrides_and_resets_stock() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", PRODUCT).await.status, 201);
+
+    let res = app.post("/products/1/clone", r#"{"name":"Mlieko 0,5 l","sell_price":0.7,"bar_code":858002}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let copy: Product = res.json();
+    assert_eq!(copy.id, Some(2));
+    assert_eq!(copy.name.as_deref(), Some("Mlieko 0,5 l"));
+    assert_eq!((copy.sell_price, copy.cost_price), (Some(0.7), Some(0.8)));
+    assert_eq!(copy.bar_code, Some(858002));
+    assert_eq!((copy.category.as_deref(), copy.brand.as_deref()), (Some("Mliečne"), Some("Rajo")));
+    assert_eq!(copy.description.as_deref(), Some("Plnotučné"));
+    assert_eq!(copy.quantity, Some(0.0));
+    assert_eq!(copy.date_added, Some(Local::now().date_naive()));
+    assert!(app.get("/products/2/batches").await.json::<Vec<ProductBatch>>().is_empty());
+
+    // bez tela sa čiarový kód neprevezme
+    let copy: Product = app.post("/products/1/clone", "").await.json();
+    assert_eq!((copy.id, copy.bar_code), (Some(3), Some(0)));
+    assert_eq!(copy.name.as_deref(), Some("Mlieko 1 l"));
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "clone_product");
+    assert_eq!(audit[0].details.as_deref(), Some("source_id=1, clone_id=3"));
+    assert_eq!(audit[1].details.as_deref(), Some("source_id=1, clone_id=2"));
+}
+
+#[tokio::test]
+async fn clone_rejects_missing_product_and_duplicate_barcode() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products/1/clone", "").await.status, 404);
+    assert_eq!(app.post("/products", PRODUCT).await.status, 201);
+
+    assert_eq!(app.post("/products/1/clone", r#"{"bar_code":858001}"#).await.status, 409);
+    assert_eq!(app.post("/products/1/clone", r#"{"name":"#).await.status, 400);
+    assert_eq!(app.post("/products/1/clone", &format!(r#"{{"name":"{}"}}"#, "x".repeat(300))).await.status, 422);
+    assert_eq!(app.db.count_products(Product::new_empty(), false).await.unwrap(), 1);
+    assert!(app.db.get_audit_log().await.unwrap().iter().all(|e| e.action != "clone_product"));
+}
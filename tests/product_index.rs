@@ -0,0 +1,55 @@
+use store_manager::structs::SearchIndexEntry;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, bar_code, status) in [("Kofola", 1, true), ("Vinea", 2, true), ("Rajec", 3, false)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Nápoje","quantity":5,"status":{status},"bar_code":{bar_code},"cost_price":0.5,"sell_price":1.2}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+#[tokio::test]
+async fn index_lists_active_products() {
+    let app = spawn_with_products().await;
+    let res = app.get("/products/index").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("cache-control"), Some("private, no-cache"));
+    let index: Vec<SearchIndexEntry> = res.json();
+    assert_eq!(
+        index,
+        [
+            SearchIndexEntry { id: 1, name: Some("Kofola".into()), bar_code: Some(1), category: Some("Nápoje".into()), price: Some(1.2) },
+            SearchIndexEntry { id: 2, name: Some("Vinea".into()), bar_code: Some(2), category: Some("Nápoje".into()), price: Some(1.2) },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn etag_changes_after_product_mutation() {
+    let app = spawn_with_products().await;
+    let res = app.get("/products/index").await;
+    let etag = res.header("etag").unwrap().to_string();
+
+    let res = app.request_with_headers("GET", "/products/index", None, &[("if-none-match", &etag)]).await;
+    assert_eq!(res.status, 304);
+    assert!(res.body.is_empty());
+    let metrics = app.get("/metrics").await.text();
+    assert!(metrics.contains(r#"store_cache_hits_total{query="search_index"} 1"#), "{metrics}");
+
+    assert_eq!(app.put("/products/2", r#"{"sell_price":1.5}"#).await.status, 200);
+    let res = app.request_with_headers("GET", "/products/index", None, &[("if-none-match", &etag)]).await;
+    assert_eq!(res.status, 200);
+    let new_etag = res.header("etag").unwrap().to_string();
+    assert_ne!(new_etag, etag);
+    assert_eq!(res.json::<Vec<SearchIndexEntry>>()[1].price, Some(1.5));
+
+    // zmazaný produkt z indexu zmizne
+    assert_eq!(app.delete("/products/1").await.status, 204);
+    let res = app.get("/products/index").await;
+    assert_ne!(res.header("etag"), Some(new_etag.as_str()));
+    assert_eq!(res.json::<Vec<SearchIndexEntry>>().len(), 1);
+}
@@ -0,0 +1,54 @@
+use serde_json::Value;
+use store_manager::structs::ProductLookup;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, bar_code) in [("Kofola", 8586000000011_i64), ("Chlieb", 8586000000028), ("Rožok", 8586000000028)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":5,"status":true,"bar_code":{bar_code},"cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+async fn lookup(app: &TestApp, query: &str) -> u32 {
+    let res = app.get(&format!("/products/lookup?{query}")).await;
+    assert_eq!(res.status, 200, "{query}: {}", res.text());
+    let detail: Value = res.json();
+    detail["id"].as_u64().unwrap() as u32
+}
+
+#[tokio::test]
+async fn each_identifier_resolves_the_product() {
+    let app = spawn_with_products().await;
+
+    assert_eq!(lookup(&app, "id=2").await, 2);
+    assert_eq!(lookup(&app, "barcode=8586000000011").await, 1);
+    assert_eq!(lookup(&app, "name=kofola").await, 1);
+    assert_eq!(lookup(&app, "name=Ro%C5%BEok").await, 3);
+
+    let by_name = app.db.lookup_product(&ProductLookup { name: Some(" Chlieb ".into()), ..Default::default() }).await;
+    assert_eq!(by_name.unwrap().id, Some(2));
+
+    assert_eq!(app.get("/products/lookup?id=9").await.status, 404);
+    assert_eq!(app.get("/products/lookup?barcode=1").await.status, 404);
+    assert_eq!(app.get("/products/lookup?name=Mlieko").await.status, 404);
+}
+
+#[tokio::test]
+async fn missing_multiple_or_ambiguous_identifiers_are_rejected() {
+    let app = spawn_with_products().await;
+
+    assert_eq!(app.get("/products/lookup").await.status, 400);
+    assert_eq!(app.get("/products/lookup?name=%20").await.status, 400);
+    assert_eq!(app.get("/products/lookup?id=1&barcode=8586000000011").await.status, 400);
+    assert_eq!(app.get("/products/lookup?id=1&name=Kofola").await.status, 400);
+    assert_eq!(app.get("/products/lookup?barcode=abc").await.status, 400);
+
+    // čiarový kód zdieľajú dva produkty
+    let res = app.get("/products/lookup?barcode=8586000000028").await;
+    assert_eq!(res.status, 409);
+    assert!(res.text().contains("2, 3"), "{}", res.text());
+}
@@ -0,0 +1,46 @@
+use std::collections::HashSet;
+
+use store_manager::db::MAX_SAMPLE_SIZE;
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+
+fn product(name: &str) -> Product {
+    serde_json::from_str(&format!(
+        r#"{{"name":"{name}","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+    ))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn sample_returns_requested_count_of_distinct_products() {
+    let app = TestApp::spawn().await;
+    assert!(app.get("/products/sample").await.json::<Vec<Product>>().is_empty());
+
+    for i in 0..(MAX_SAMPLE_SIZE + 20) {
+        app.db.add_product_to_store_db(&product(&format!("Produkt {i}"))).await.unwrap();
+    }
+
+    let sample: Vec<Product> = app.get("/products/sample").await.json();
+    assert_eq!(sample.len(), 10);
+    let sample: Vec<Product> = app.get("/products/sample?n=25").await.json();
+    let ids: HashSet<_> = sample.iter().map(|p| p.id).collect();
+    assert_eq!(ids.len(), 25);
+
+    // veľká vzorka sa obmedzí
+    let sample: Vec<Product> = app.get("/products/sample?n=100000").await.json();
+    assert_eq!(sample.len(), MAX_SAMPLE_SIZE as usize);
+    assert_eq!(app.get("/products/sample?n=-1").await.status, 400);
+}
+
+#[tokio::test]
+async fn sample_of_small_store_returns_all_products() {
+    let app = TestApp::spawn().await;
+    for name in ["Chlieb", "Rožok", "Bageta"] {
+        app.db.add_product_to_store_db(&product(name)).await.unwrap();
+    }
+
+    let sample: Vec<Product> = app.get("/products/sample?n=10").await.json();
+    let mut names: Vec<_> = sample.into_iter().filter_map(|p| p.name).collect();
+    names.sort();
+    assert_eq!(names, ["Bageta", "Chlieb", "Rožok"]);
+}
@@ -0,0 +1,63 @@
+use chrono::{Days, Local};
+use store_manager::structs::EmployeeProfit;
+use store_manager::test_support::TestApp;
+
+async fn employee(app: &TestApp, name: &str) {
+    let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","department":"Predajňa","status":true}}"#);
+    assert_eq!(app.post("/employees", &body).await.status, 201);
+}
+
+async fn sell(app: &TestApp, employee_id: Option<u32>, product_id: u32, quantity: u32) {
+    let employee = employee_id.map(|id| format!(r#""employee_id":{id},"#)).unwrap_or_default();
+    let order = format!(r#"{{{employee}"items":[{{"product_id":{product_id},"quantity":{quantity}}}]}}"#);
+    let res = app.post("/orders", &order).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+}
+
+fn window() -> String {
+    let today = Local::now().date_naive();
+    format!("from={}&to={today}", today - Days::new(7))
+}
+
+#[tokio::test]
+async fn profit_is_attributed_to_the_seller() {
+    let app = TestApp::spawn().await;
+    employee(&app, "Jana").await;
+    employee(&app, "Eva").await;
+    employee(&app, "Mária").await;
+    // marža 1.0 / ks a 3.0 / ks; chlieb sa predáva za nákupnú cenu
+    let products = [
+        r#"{"name":"Kofola","category":"Nápoje","quantity":100,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#,
+        r#"{"name":"Víno","category":"Nápoje","quantity":100,"status":true,"bar_code":2,"cost_price":5.0,"sell_price":8.0}"#,
+        r#"{"name":"Chlieb","category":"Pečivo","quantity":100,"status":true,"bar_code":3,"cost_price":1.5,"sell_price":1.5}"#,
+    ];
+    for product in products {
+        assert_eq!(app.post("/products", product).await.status, 201);
+    }
+
+    sell(&app, Some(1), 1, 10).await; // Jana +10, vrátenie 4 ks → +6
+    sell(&app, Some(2), 2, 5).await; // Eva +15
+    sell(&app, Some(2), 3, 20).await; // Eva, bez marže
+    sell(&app, None, 2, 3).await; // nepripísaný predaj
+    let res = app.post("/orders/1/returns", r#"{"items":[{"product_id":1,"quantity":4}]}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+
+    let res = app.get(&format!("/stats/profit-by-employee?{}", window())).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let profits: Vec<EmployeeProfit> = res.json();
+    let rows: Vec<(u32, f64)> = profits.iter().map(|p| (p.employee_id, p.profit)).collect();
+    assert_eq!(rows, [(2, 15.0), (1, 6.0), (3, 0.0)]);
+    assert_eq!(profits[0].name.as_deref(), Some("Eva"));
+
+    let today = Local::now().date_naive();
+    let earlier = app.db.profit_by_employee(today - Days::new(30), today - Days::new(10)).await.unwrap();
+    assert!(earlier.iter().all(|(_, profit)| *profit == 0.0));
+    assert_eq!(earlier.len(), 3);
+}
+
+#[tokio::test]
+async fn invalid_range_is_rejected() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.get("/stats/profit-by-employee?from=2024-02-01&to=2024-01-01").await.status, 400);
+    assert_eq!(app.get("/stats/profit-by-employee").await.status, 400);
+}
@@ -0,0 +1,101 @@
+use store_manager::structs::{
+    Product, PurchaseOrder, PurchaseOrderDiscrepancy, PurchaseOrderReceiptResult, PurchaseOrderStatus,
+};
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, supplier: &str, quantity: u32) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Test","quantity":{quantity},"status":true,
+            "bar_code":1,"cost_price":1.5,"sell_price":2.0,"supplier":"{supplier}"}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn cancelled_purchase_order_cannot_be_received() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Mlieko", "Rajo", 2).await;
+
+    let res = app
+        .post("/purchase-orders", r#"{"supplier":"Rajo","items":[{"product_id":1,"quantity":10}]}"#)
+        .await;
+    assert_eq!(res.status, 201);
+    let id: u32 = res.json();
+
+    let po: PurchaseOrder = app.get(&format!("/purchase-orders/{id}")).await.json();
+    assert_eq!(po.status, Some(PurchaseOrderStatus::Draft));
+    assert_eq!(po.items.unwrap()[0].unit_cost, Some(1.5));
+
+    let res = app.put(&format!("/purchase-orders/{id}"), r#"{"status":"cancelled"}"#).await;
+    assert_eq!(res.status, 200);
+
+    let res = app
+        .post(&format!("/purchase-orders/{id}/receive"), r#"{"items":[{"product_id":1,"received_quantity":10}]}"#)
+        .await;
+    assert_eq!(res.status, 409);
+}
+
+#[tokio::test]
+async fn repeated_partial_receipts_only_stock_the_delta() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Mlieko", "Rajo", 2).await;
+
+    let id: u32 = app
+        .post("/purchase-orders", r#"{"supplier":"Rajo","items":[{"product_id":1,"quantity":10}]}"#)
+        .await
+        .json();
+    assert_eq!(app.put(&format!("/purchase-orders/{id}"), r#"{"status":"sent"}"#).await.status, 200);
+
+    let receive = |qty: u32| format!(r#"{{"items":[{{"product_id":1,"received_quantity":{qty}}}]}}"#);
+
+    let result: PurchaseOrderReceiptResult = app
+        .post(&format!("/purchase-orders/{id}/receive"), &receive(4))
+        .await
+        .json();
+    assert_eq!(result.status, PurchaseOrderStatus::PartiallyReceived);
+
+    let result: PurchaseOrderReceiptResult = app
+        .post(&format!("/purchase-orders/{id}/receive"), &receive(4))
+        .await
+        .json();
+    assert_eq!(result.lines[0].received, 4.0);
+
+    let res = app.post(&format!("/purchase-orders/{id}/receive"), &receive(12)).await;
+    assert_eq!(res.status, 409);
+
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.quantity, Some(6.0));
+
+    let result: PurchaseOrderReceiptResult = app
+        .post(&format!("/purchase-orders/{id}/receive?allow_over_delivery=true"), &receive(12))
+        .await
+        .json();
+    assert_eq!(result.status, PurchaseOrderStatus::Received);
+
+    let lines: Vec<PurchaseOrderDiscrepancy> =
+        app.get(&format!("/purchase-orders/{id}/discrepancies")).await.json();
+    assert_eq!(lines[0].difference, 2.0);
+    assert!(lines[0].over_delivered);
+
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.quantity, Some(14.0));
+}
+
+#[tokio::test]
+async fn suggestions_create_one_draft_per_supplier() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Mlieko", "Rajo", 1).await;
+    add_product(&app, "Jogurt", "Rajo", 0).await;
+    add_product(&app, "Chlieb", "Penam", 3).await;
+    add_product(&app, "Rožok", "Penam", 100).await;
+
+    let res = app.post("/purchase-orders/from-suggestions", "").await;
+    assert_eq!(res.status, 201);
+    let created: Vec<PurchaseOrder> = res.json();
+    assert_eq!(created.len(), 2);
+
+    let rajo = created.iter().find(|po| po.supplier.as_deref() == Some("Rajo")).unwrap();
+    assert_eq!(rajo.items.as_ref().unwrap().len(), 2);
+    let penam = created.iter().find(|po| po.supplier.as_deref() == Some("Penam")).unwrap();
+    assert_eq!(penam.items.as_ref().unwrap()[0].quantity, 17.0);
+}
@@ -0,0 +1,54 @@
+use std::io::Cursor;
+
+use store_manager::test_support::TestApp;
+
+/// Rozmery a pixely (odtiene sivej) PNG obrázka.
+fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+    let mut reader = png::Decoder::new(Cursor::new(png)).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!(info.color_type, png::ColorType::Grayscale);
+    (info.width, info.height, pixels)
+}
+
+#[tokio::test]
+async fn qr_png_is_a_centered_code_with_finder_patterns() {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Kofola","category":"Nápoje","quantity":1,"bar_code":0,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+
+    let res = app.get("/products/1/qr.png").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("image/png"));
+    let (width, height, pixels) = decode(&res.body);
+    assert_eq!((width, height), (256, 256));
+
+    // ID "1" = verzia 1 (21 modulov), s tichou zónou 29 → modul 8 px, okraj 44 px
+    let at = |x: u32, y: u32| pixels[(y * width + x) as usize];
+    assert_eq!(at(0, 0), 255);
+    assert_eq!(at(44, 44), 0);
+    // vyhľadávací vzor: tmavý rám, svetlý prstenec, tmavý stred
+    assert_eq!(at(44 + 8 + 4, 44 + 8 + 4), 255);
+    assert_eq!(at(44 + 3 * 8 + 4, 44 + 3 * 8 + 4), 0);
+    assert_eq!(at(255 - 44 - 4, 44 + 4), 0);
+
+    // odkaz je dlhší než ID, kód má viac modulov
+    let res = app.get("/products/1/qr.png?url=https://obchod.sk/produkty/%7Bid%7D?zdroj=tabula&size=128").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let (width, _, other) = decode(&res.body);
+    assert_eq!(width, 128);
+    assert_ne!(other, pixels);
+}
+
+#[tokio::test]
+async fn qr_png_rejects_unknown_products_and_bad_sizes() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.get("/products/1/qr.png").await.status, 404);
+
+    let body = r#"{"name":"Kofola","category":"Nápoje","quantity":1,"bar_code":0,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    assert_eq!(app.get("/products/1/qr.png?size=32").await.status, 400);
+    assert_eq!(app.get("/products/1/qr.png?size=5000").await.status, 400);
+    let long = "x".repeat(3000);
+    assert_eq!(app.get(&format!("/products/1/qr.png?url={long}")).await.status, 400);
+}
@@ -0,0 +1,54 @@
+use store_manager::structs::{LowStockReport, Product};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_stock() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, quantity) in [("Kofola", 0), ("Vinea", 1), ("Rajec", 5), ("Semtex", 6), ("Birell", 20)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+fn names(products: &[Product]) -> Vec<&str> {
+    products.iter().map(|p| p.name.as_deref().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn closed_and_open_ended_quantity_ranges() {
+    let app = spawn_with_stock().await;
+
+    let products: Vec<Product> = app.get("/products?quantity_min=1&quantity_max=5").await.json();
+    assert_eq!(names(&products), ["Vinea", "Rajec"]);
+
+    let products: Vec<Product> = app.get("/products?quantity_min=6").await.json();
+    assert_eq!(names(&products), ["Semtex", "Birell"]);
+
+    let products: Vec<Product> = app.post("/products/search", r#"{"quantity_max":1}"#).await.json();
+    assert_eq!(names(&products), ["Kofola", "Vinea"]);
+    assert!(products.iter().all(|p| p.quantity_min.is_none()));
+
+    let products: Vec<Product> = app.post("/products/search", r#"{"quantity_min":5,"quantity_max":1}"#).await.json();
+    assert!(products.is_empty());
+
+    // presné množstvo funguje ako doteraz
+    let products: Vec<Product> = app.get("/products?quantity=5").await.json();
+    assert_eq!(names(&products), ["Rajec"]);
+}
+
+#[tokio::test]
+async fn range_up_to_threshold_matches_low_stock_report() {
+    let app = spawn_with_stock().await;
+    let report: LowStockReport = app.get("/reports/low-stock").await.json();
+
+    let products: Vec<Product> =
+        app.get(&format!("/products?quantity_max={}&status=true", report.threshold)).await.json();
+    let mut expected: Vec<&str> = report.lines.iter().map(|l| l.name.as_deref().unwrap()).collect();
+    let mut found = names(&products);
+    expected.sort();
+    found.sort();
+    assert_eq!(found, expected);
+    assert_eq!(found, ["Kofola", "Rajec", "Vinea"]);
+}
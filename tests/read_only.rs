@@ -0,0 +1,88 @@
+use store_manager::config::Config;
+use store_manager::db::{self, StoreDB};
+use store_manager::structs::{Product, ReadOnlyStatus};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+const PRODUCT: &str = r#"{"name":"Kofola","category":"Nápoje","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":1.5}"#;
+
+async fn send(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+async fn set_read_only(app: &TestApp, enabled: bool) -> ReadOnlyStatus {
+    let res = send(app, "POST", "/admin/read-only", Some(&format!(r#"{{"enabled":{enabled}}}"#))).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("read-only-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path.to_str().unwrap().into()
+}
+
+#[tokio::test]
+async fn runtime_toggle_rejects_writes_but_keeps_reads() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    assert_eq!(send(&app, "POST", "/products", Some(PRODUCT)).await.status, 201);
+    assert!(!send(&app, "GET", "/admin/read-only", None).await.json::<ReadOnlyStatus>().read_only);
+
+    assert!(set_read_only(&app, true).await.read_only);
+    for (method, path, body) in [
+        ("POST", "/products", Some(PRODUCT)),
+        ("PUT", "/products/1", Some(r#"{"sell_price":2.0}"#)),
+        ("DELETE", "/products/1", None),
+        ("POST", "/admin/import-ndjson", Some("")),
+        ("POST", "/admin/stats/snapshot", None),
+    ] {
+        let res = send(&app, method, path, body).await;
+        assert_eq!(res.status, 503, "{method} {path}");
+        assert!(res.text().contains("len na čítanie"), "{}", res.text());
+    }
+
+    // čítanie, vyhľadávanie, export a health checky fungujú ďalej
+    assert_eq!(send(&app, "GET", "/products", None).await.json::<Vec<Product>>().len(), 1);
+    assert_eq!(send(&app, "POST", "/products/search", Some(r#"{"name":"Kof"}"#)).await.status, 200);
+    assert_eq!(send(&app, "GET", "/admin/export", None).await.status, 200);
+    assert_eq!(app.get("/livez").await.status, 200);
+    assert_eq!(app.get("/readyz").await.status, 200);
+
+    // mutácia, ktorú HTTP vrstva nezastaví, zlyhá v databáze
+    let mutation = r#"{"query":"mutation { deleteProduct(id: 1) }"}"#;
+    let res = send(&app, "POST", "/graphql", Some(mutation)).await;
+    assert!(res.text().contains("errors"), "{}", res.text());
+    let err = app.db.record_audit("test", None, None).await.unwrap_err();
+    assert!(db::is_read_only_error(&err), "{err}");
+
+    assert!(!set_read_only(&app, false).await.read_only);
+    assert_eq!(send(&app, "PUT", "/products/1", Some(r#"{"sell_price":2.0}"#)).await.status, 200);
+    let audit = app.db.get_audit_log().await.unwrap();
+    let toggles: Vec<_> = audit.iter().filter(|e| e.action == "read_only").map(|e| e.details.as_deref().unwrap()).collect();
+    assert_eq!(toggles, ["enabled=false", "enabled=true"]);
+}
+
+#[tokio::test]
+async fn read_only_config_opens_existing_database_read_only() {
+    let path = temp_path("snapshot.db");
+    let writable = StoreDB::open(&path).await.unwrap();
+    writable.record_audit("setup", None, None).await.unwrap();
+
+    let config = Config { db_path: path.clone(), read_only: true, ..Config::default() };
+    let db = StoreDB::new(&config).await.unwrap();
+    assert!(db.is_read_only());
+    assert_eq!(db.get_audit_log().await.unwrap().len(), 1);
+    assert!(db::is_read_only_error(&db.record_audit("zápis", None, None).await.unwrap_err()));
+
+    // prepnutie za behu otvorí nové spojenia na zápis
+    db.set_read_only(false);
+    db.record_audit("zápis", None, None).await.unwrap();
+    db.set_read_only(true);
+    assert!(db.record_audit("zápis", None, None).await.is_err());
+    assert_eq!(db.get_audit_log().await.unwrap().len(), 2);
+
+    // chýbajúci súbor sa v režime len na čítanie nevytvorí
+    let missing = temp_path("missing.db");
+    assert!(StoreDB::new(&Config { db_path: missing.clone(), ..config }).await.is_err());
+    assert!(!std::path::Path::new(&missing).exists());
+}
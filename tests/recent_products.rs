@@ -0,0 +1,43 @@
+use store_manager::db::MAX_RECENT_PRODUCTS;
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+
+async fn add(app: &TestApp, name: &str, bar_code: u32, date_added: Option<&str>) {
+    let date = date_added.map(|d| format!(r#","date_added":"{d}""#)).unwrap_or_default();
+    let body = format!(r#"{{"name":"{name}","category":"Potraviny","quantity":1,"status":true,"bar_code":{bar_code},"cost_price":0.05,"sell_price":1.0{date}}}"#);
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+async fn recent(app: &TestApp, query: &str) -> Vec<String> {
+    let res = app.get(&format!("/products/recent{query}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let products: Vec<Product> = res.json();
+    products.into_iter().map(|p| p.name.unwrap()).collect()
+}
+
+#[tokio::test]
+async fn newest_products_come_first() {
+    let app = TestApp::spawn().await;
+    add(&app, "Chlieb", 1, Some("2024-03-01")).await;
+    add(&app, "Mlieko", 2, Some("2024-05-10")).await;
+    add(&app, "Bez dátumu", 3, None).await;
+    add(&app, "Maslo", 4, Some("2024-03-01")).await;
+
+    // rovnaký dátum rozhodne vyššie ID, produkt bez dátumu sa vynechá
+    assert_eq!(recent(&app, "").await, ["Mlieko", "Maslo", "Chlieb"]);
+    assert_eq!(recent(&app, "?limit=2").await, ["Mlieko", "Maslo"]);
+    assert!(recent(&app, "?limit=0").await.is_empty());
+}
+
+#[tokio::test]
+async fn limit_is_capped() {
+    let app = TestApp::spawn().await;
+    for i in 1..=MAX_RECENT_PRODUCTS + 5 {
+        add(&app, &format!("Produkt {i}"), i, Some("2024-01-01")).await;
+    }
+    assert_eq!(recent(&app, "").await.len(), 10);
+    assert_eq!(recent(&app, "?limit=1000").await.len(), MAX_RECENT_PRODUCTS as usize);
+    let products = app.db.recently_added_products(u32::MAX).await.unwrap();
+    assert_eq!(products.len(), MAX_RECENT_PRODUCTS as usize);
+    assert_eq!(products[0].name, Some(format!("Produkt {}", MAX_RECENT_PRODUCTS + 5)));
+}
@@ -0,0 +1,67 @@
+use store_manager::structs::{CategoryLimit, CategoryRemap};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (bar_code, category) in [(1, "Drinks"), (2, "Drinks"), (3, "Nápoje"), (4, "Beverages"), (5, "Snacks")] {
+        let body = format!(r#"{{"name":"Produkt {bar_code}","category":"{category}","quantity":5,"status":true,"bar_code":{bar_code},"cost_price":0.5,"sell_price":1.0}}"#);
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+async fn category(app: &TestApp, id: u32) -> Option<String> {
+    app.db.get_product(id).await.unwrap().unwrap().category
+}
+
+#[tokio::test]
+async fn merges_two_source_categories_into_existing_target() {
+    let app = spawn_with_products().await;
+    assert_eq!(app.put("/categories/Drinks/limit", r#"{"max_total_quantity":100}"#).await.status, 200);
+    assert_eq!(app.put("/categories/Beverages/limit", r#"{"max_total_quantity":50}"#).await.status, 200);
+
+    let res = app.post("/products/remap-categories", r#"{"Drinks":"Beverages","Nápoje":"Beverages"}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: Vec<CategoryRemap> = res.json();
+    assert_eq!(
+        report,
+        [
+            CategoryRemap { from: "Drinks".into(), to: "Beverages".into(), products: 2 },
+            CategoryRemap { from: "Nápoje".into(), to: "Beverages".into(), products: 1 },
+        ]
+    );
+    for id in 1..=4 {
+        assert_eq!(category(&app, id).await.as_deref(), Some("Beverages"));
+    }
+    assert_eq!(category(&app, 5).await.as_deref(), Some("Snacks"));
+
+    // cieľ si ponechá vlastný limit, limit zdroja zanikne
+    let limit: CategoryLimit = app.get("/categories/Beverages/limit").await.json();
+    assert_eq!((limit.max_total_quantity, limit.total_quantity), (Some(50.0), 20.0));
+    let limit: CategoryLimit = app.get("/categories/Drinks/limit").await.json();
+    assert_eq!((limit.max_total_quantity, limit.total_quantity), (None, 0.0));
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    let details: Vec<_> = audit.iter().filter(|e| e.action == "remap_category").filter_map(|e| e.details.as_deref()).collect();
+    assert_eq!(
+        details,
+        ["from=Nápoje, to=Beverages, products=1", "from=Drinks, to=Beverages, products=2"]
+    );
+}
+
+#[tokio::test]
+async fn renames_are_applied_together() {
+    let app = spawn_with_products().await;
+    assert_eq!(app.put("/categories/Snacks/limit", r#"{"max_total_quantity":10}"#).await.status, 200);
+
+    // výmena názvov: produkty sa vyberajú podľa pôvodných kategórií
+    let report: Vec<CategoryRemap> = app.post("/products/remap-categories", r#"{"Drinks":"Snacks","Snacks":"Drinks"}"#).await.json();
+    assert_eq!(report.iter().map(|r| r.products).collect::<Vec<_>>(), [2, 1]);
+    assert_eq!(category(&app, 1).await.as_deref(), Some("Snacks"));
+    assert_eq!(category(&app, 5).await.as_deref(), Some("Drinks"));
+    let limit: CategoryLimit = app.get("/categories/Drinks/limit").await.json();
+    assert_eq!(limit.max_total_quantity, Some(10.0));
+
+    assert_eq!(app.post("/products/remap-categories", "{}").await.status, 400);
+    assert_eq!(app.post("/products/remap-categories", r#"{"Drinks":" "}"#).await.status, 400);
+}
@@ -0,0 +1,60 @@
+use chrono::Local;
+use store_manager::structs::{Employee, Product};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_employees() -> TestApp {
+    let app = TestApp::spawn().await;
+    for name in ["Jana", "Eva"] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","status":true}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    let product = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0,"employee_id":2}"#;
+    assert_eq!(app.post("/products", product).await.status, 201);
+    app
+}
+
+fn ids(employees: Vec<Employee>) -> Vec<u32> {
+    employees.into_iter().filter_map(|e| e.id).collect()
+}
+
+#[tokio::test]
+async fn soft_delete_hides_employee_and_keeps_references() {
+    let app = spawn_with_employees().await;
+
+    assert_eq!(app.delete("/employees/2?soft=true").await.status, 204);
+    assert_eq!(app.delete("/employees/9?soft=true").await.status, 404);
+
+    let res = app.get("/employees").await;
+    assert_eq!(res.header("x-total-count"), Some("1"));
+    assert_eq!(ids(res.json()), [1]);
+    assert_eq!(ids(app.post("/employees/search", "{}").await.json()), [1]);
+
+    let all: Vec<Employee> = app.get("/employees?include_removed=true").await.json();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[1].status, Some(false));
+    assert_eq!(all[1].date_remove, Some(Local::now().date_naive()));
+
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert_eq!(products[0].employee_id, Some(2));
+}
+
+#[tokio::test]
+async fn restore_returns_employee_to_listings() {
+    let app = spawn_with_employees().await;
+    assert_eq!(app.delete("/employees/2?soft=true").await.status, 204);
+
+    let res = app.post("/employees/2/restore", "").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let employee: Employee = res.json();
+    assert_eq!((employee.status, employee.date_remove), (Some(true), None));
+    assert_eq!(ids(app.get("/employees").await.json()), [1, 2]);
+
+    assert_eq!(app.post("/employees/9/restore", "").await.status, 404);
+}
+
+#[tokio::test]
+async fn delete_without_soft_still_removes_employee() {
+    let app = spawn_with_employees().await;
+    assert_eq!(app.delete("/employees/1").await.status, 204);
+    assert_eq!(ids(app.get("/employees?include_removed=true").await.json()), [2]);
+}
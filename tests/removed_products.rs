@@ -0,0 +1,68 @@
+use chrono::{Days, Local};
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    let today = Local::now().date_naive();
+    let removals = [None, today.checked_sub_days(Days::new(1)), today.checked_add_days(Days::new(1))];
+    for (i, date_remove) in removals.into_iter().enumerate() {
+        let date_remove = date_remove.map_or("null".to_string(), |d| format!("\"{d}\""));
+        let body = format!(
+            r#"{{"name":"Produkt {i}","category":"Nápoje","quantity":5,"status":true,"bar_code":{i},"cost_price":1.0,"sell_price":2.0,"date_remove":{date_remove}}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+fn ids(products: Vec<Product>) -> Vec<u32> {
+    products.into_iter().filter_map(|p| p.id).collect()
+}
+
+#[tokio::test]
+async fn v1_listings_hide_removed_products_by_default() {
+    let app = spawn_with_products().await;
+
+    let res = app.get("/api/v1/products").await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.header("x-removed-products"), Some("excluded"));
+    assert_eq!(res.header("x-total-count"), Some("2"));
+    assert_eq!(ids(res.json()), [1, 3]);
+
+    let res = app.get("/api/v1/products?include_removed=true").await;
+    assert_eq!(res.header("x-removed-products"), Some("included"));
+    assert_eq!(ids(res.json()), [1, 2, 3]);
+
+    let res = app.post("/api/v1/products/search", "{}").await;
+    assert_eq!(res.header("x-removed-products"), Some("excluded"));
+    assert_eq!(ids(res.json()), [1, 3]);
+    assert_eq!(ids(app.post("/api/v1/products/search?include_removed=true", "{}").await.json()), [1, 2, 3]);
+    assert_eq!(ids(app.post("/api/v1/products/search", r#"{"include_removed":true}"#).await.json()), [1, 2, 3]);
+
+    assert_eq!(app.get("/api/v1/products/export.ndjson").await.text().lines().count(), 2);
+
+    // odkazy na stránky zostávajú pod /api/v1
+    let res = app.get("/api/v1/products?limit=1").await;
+    assert!(res.header("link").unwrap().contains("/api/v1/products?"), "{:?}", res.header("link"));
+}
+
+#[tokio::test]
+async fn legacy_routes_keep_returning_removed_products() {
+    let app = spawn_with_products().await;
+
+    let res = app.get("/products").await;
+    assert_eq!(res.header("x-removed-products"), Some("included"));
+    assert_eq!(ids(res.json()), [1, 2, 3]);
+    assert_eq!(ids(app.post("/products/search", "{}").await.json()), [1, 2, 3]);
+    assert_eq!(app.get("/products/export.ndjson").await.text().lines().count(), 3);
+
+    // na pôvodných cestách sa dajú vynechať na požiadanie
+    let res = app.get("/products?include_removed=false").await;
+    assert_eq!(res.header("x-removed-products"), Some("excluded"));
+    assert_eq!(ids(res.json()), [1, 3]);
+
+    // ostatné trasy sú pod /api/v1 bez zmeny
+    assert_eq!(app.get("/api/v1/products/2").await.status, 200);
+    assert_eq!(app.get("/api/v10/products").await.status, 404);
+}
@@ -0,0 +1,36 @@
+use store_manager::structs::CategoryReorderBudget;
+use store_manager::test_support::TestApp;
+
+async fn add(app: &TestApp, bar_code: u32, category: &str, quantity: u32, min_stock: u32, cost: f64) {
+    let body = format!(
+        r#"{{"name":"Produkt {bar_code}","category":"{category}","quantity":{quantity},"min_stock":{min_stock},"status":true,"bar_code":{bar_code},"cost_price":{cost},"sell_price":1.0}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn budget_sums_reorder_costs_per_category() {
+    let app = TestApp::spawn().await;
+    // bez predaja je bod doobjednania min_stock a návrh ho dopĺňa
+    add(&app, 1, "Nápoje", 2, 10, 1.5).await;
+    add(&app, 2, "Nápoje", 0, 4, 2.0).await;
+    add(&app, 3, "Náradie", 1, 5, 10.0).await;
+    add(&app, 4, "Drogéria", 50, 5, 3.0).await;
+    // tovar s nulovou nákupnou cenou rozpočet nezvýši
+    add(&app, 5, "Potraviny", 0, 5, 0.0).await;
+
+    let res = app.get("/stats/reorder-budget").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let budgets: Vec<CategoryReorderBudget> = res.json();
+    let budgets: Vec<(&str, f64)> = budgets.iter().map(|b| (b.category.as_str(), b.budget)).collect();
+    assert_eq!(budgets, [("Náradie", 40.0), ("Nápoje", 20.0), ("Drogéria", 0.0), ("Potraviny", 0.0)]);
+
+    let total: f64 = app.db.reorder_list().await.unwrap().iter().filter_map(|s| s.estimated_cost).sum();
+    assert_eq!(total, 60.0);
+}
+
+#[tokio::test]
+async fn empty_catalog_has_no_budget() {
+    let app = TestApp::spawn().await;
+    assert!(app.db.reorder_budget_by_category().await.unwrap().is_empty());
+}
@@ -0,0 +1,70 @@
+use store_manager::structs::{ReorderSuggestion, SupplierOrderSheet};
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, quantity: u32, extra: &str) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.5,"sell_price":2.0{extra}}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn reorder_list_combines_min_stock_velocity_and_lead_time() {
+    let app = TestApp::spawn().await;
+    // 60 ks predaných za 30 dní = 2 ks denne, pri 30-dňovej lehote bod doobjednania 60
+    add_product(&app, "Kofola", 100, r#","lead_time_days":30"#).await;
+    add_product(&app, "Vinea", 2, r#","min_stock":10"#).await;
+    add_product(&app, "Rajec", 50, r#","min_stock":5"#).await;
+    add_product(&app, "Voda", 0, "").await;
+    add_product(&app, "Tonik", 0, r#","min_stock":4"#).await;
+    // rovnaký predaj, ale s predvolenou 7-dňovou lehotou (bod doobjednania 14)
+    add_product(&app, "Kinley", 100, "").await;
+    let res = app.post("/orders", r#"{"items":[{"product_id":1,"quantity":60},{"product_id":6,"quantity":60}]}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+
+    let res = app.get("/products/reorder-list").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let list: Vec<ReorderSuggestion> = res.json();
+    assert_eq!(list.iter().map(|s| s.product_id).collect::<Vec<_>>(), [5, 2, 1]);
+
+    let lines: Vec<_> = list.iter().map(|s| (s.suggested_quantity, s.estimated_cost, s.reorder_point)).collect();
+    assert_eq!(lines, [(4.0, Some(6.0), Some(4.0)), (8.0, Some(12.0), Some(10.0)), (80.0, Some(120.0), Some(60.0))]);
+    assert_eq!(list[2].daily_sales, Some(2.0));
+    assert_eq!(list[0].daily_sales, Some(0.0));
+}
+
+#[tokio::test]
+async fn reorder_list_skips_inactive_and_adequately_stocked_products() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Kofola", 20, r#","min_stock":5"#).await;
+    add_product(&app, "Vinea", 5, r#","min_stock":5"#).await;
+    assert!(app.get("/products/reorder-list").await.json::<Vec<ReorderSuggestion>>().is_empty());
+
+    add_product(&app, "Rajec", 1, r#","min_stock":5"#).await;
+    assert_eq!(app.put("/products/3", r#"{"status":false}"#).await.status, 200);
+    assert!(app.get("/products/reorder-list").await.json::<Vec<ReorderSuggestion>>().is_empty());
+}
+
+#[tokio::test]
+async fn supplier_order_sheet_lists_only_that_suppliers_products_to_reorder() {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Kofola", 2, r#","min_stock":10,"supplier":"Kofola a.s.""#).await;
+    add_product(&app, "Vinea", 20, r#","min_stock":10,"supplier":"Kofola a.s.""#).await;
+    add_product(&app, "Rajec", 0, r#","min_stock":3,"supplier":"Kofola a.s.""#).await;
+    add_product(&app, "Tonik", 0, r#","min_stock":4,"supplier":"Rauch""#).await;
+
+    let res = app.get("/suppliers/Kofola%20a.s./order-sheet").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let sheet: SupplierOrderSheet = res.json();
+    assert_eq!(sheet.supplier, "Kofola a.s.");
+    let lines: Vec<_> = sheet.lines.iter().map(|l| (l.product_id, l.suggested_quantity)).collect();
+    assert_eq!(lines, [(3, 3.0), (1, 8.0)]);
+    assert!((sheet.total_cost - 16.5).abs() < 1e-9, "{sheet:?}");
+    assert_eq!(sheet.uncosted_lines, 0);
+
+    let res = app.get("/suppliers/Nikto/order-sheet").await;
+    assert_eq!(res.status, 200);
+    let sheet: SupplierOrderSheet = res.json();
+    assert!(sheet.lines.is_empty());
+    assert_eq!(sheet.total_cost, 0.0);
+}
@@ -0,0 +1,153 @@
+use store_manager::structs::{CategoryMargin, DailyReport, HeadcountReport, ProfitReport, Scorecard};
+use store_manager::test_support::TestApp;
+
+#[tokio::test]
+async fn profit_report_sums_revenue_minus_cost_per_category() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 50.0, 1.0, 2.0).await;
+    app.add_product("Chlieb", "Pečivo", 50.0, 0.5, 1.5).await;
+
+    let res = app
+        .post(
+            "/orders",
+            r#"{"created_at":"2024-06-01T10:00:00",
+                "items":[{"product_id":1,"quantity":4},{"product_id":2,"quantity":2}]}"#,
+        )
+        .await;
+    assert_eq!(res.status, 201);
+
+    let report: ProfitReport = app.get("/stats/profit?from=2024-06-01&to=2024-06-30").await.json();
+    assert_eq!(report.units, 6.0);
+    assert!((report.revenue - 11.0).abs() < 1e-9);
+    assert!((report.cost - 5.0).abs() < 1e-9);
+    assert!((report.gross_profit - 6.0).abs() < 1e-9);
+    assert_eq!(report.categories.len(), 2);
+
+    let empty: ProfitReport = app.get("/stats/profit?from=2024-07-01&to=2024-07-31").await.json();
+    assert_eq!(empty.units, 0.0);
+    assert!(empty.margin.is_none());
+
+    assert_eq!(app.get("/stats/profit?from=2024-06-30&to=2024-06-01").await.status, 400);
+}
+
+#[tokio::test]
+async fn daily_report_without_sales_has_explicit_shape() {
+    let app = TestApp::spawn().await;
+
+    let res = app.get("/reports/daily?date=2024-06-01").await;
+    assert_eq!(res.status, 200);
+    let report: DailyReport = res.json();
+    assert!(report.no_sales);
+    assert_eq!(report.orders, 0);
+    assert!(report.top_products.is_empty());
+}
+
+#[tokio::test]
+async fn closed_day_rejects_backdated_sales_unless_overridden() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 50.0, 1.0, 2.0).await;
+    let order = r#"{"created_at":"2024-06-01T10:00:00","items":[{"product_id":1,"quantity":1}]}"#;
+    assert_eq!(app.post("/orders", order).await.status, 201);
+
+    let report: DailyReport = app.get("/reports/daily?date=2024-06-01").await.json();
+    assert!(!report.closed);
+    let res = app.get("/reports/daily?date=2024-06-01&close=true").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: DailyReport = res.json();
+    assert!(report.closed);
+    assert_eq!(report.orders, 1);
+
+    let res = app.post("/orders", order).await;
+    assert_eq!(res.status, 409, "{}", res.text());
+    assert_eq!(app.get("/reports/daily?date=2024-06-01").await.json::<DailyReport>().orders, 1);
+
+    // iný deň uzavretý nie je
+    let next_day = r#"{"created_at":"2024-06-02T09:00:00","items":[{"product_id":1,"quantity":1}]}"#;
+    assert_eq!(app.post("/orders", next_day).await.status, 201);
+
+    let res = app.post("/orders?override_closed=true", order).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let report: DailyReport = app.get("/reports/daily?date=2024-06-01").await.json();
+    assert_eq!((report.closed, report.orders), (true, 2));
+}
+
+#[tokio::test]
+async fn headcount_accumulates_hires_per_month() {
+    let app = TestApp::spawn().await;
+    for (hire_date, status) in [
+        (r#""2024-01-15""#, true),
+        (r#""2024-01-31""#, true),
+        (r#""2024-03-02""#, true),
+        (r#""2023-11-01""#, true),
+        (r#""2024-02-10""#, false),
+        ("null", true),
+    ] {
+        let body = format!(
+            r#"{{"name":"Jana","surname":"Nová","position":"Predavač","status":{status},"hire_date":{hire_date}}}"#
+        );
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+
+    let report: HeadcountReport = app.get("/stats/headcount?from=2024-01-10&to=2024-03-01").await.json();
+    assert_eq!(report.excluded_no_hire_date, 1);
+    assert_eq!(report.excluded_inactive, 1);
+
+    let months: Vec<(&str, u32, u32)> =
+        report.months.iter().map(|m| (m.month.as_str(), m.hired, m.headcount)).collect();
+    assert_eq!(months, [("2024-01", 2, 3), ("2024-02", 0, 3), ("2024-03", 0, 3)]);
+    assert_eq!(report.months[2].date.to_string(), "2024-03-01");
+
+    assert_eq!(app.get("/stats/headcount?from=2024-03-01&to=2024-01-01").await.status, 400);
+}
+
+#[tokio::test]
+async fn scorecard_combines_added_products_and_sales() {
+    let app = TestApp::spawn().await;
+    for name in ["Jana", "Ján"] {
+        let body = format!(r#"{{"name":"{name}","surname":"Test","position":"Predavač"}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    for (name, quantity, price) in [("Kofola", 10, 2.0), ("Chlieb", 4, 1.5)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":{quantity},"status":true,"bar_code":1,
+                "cost_price":1.0,"sell_price":{price},"employee_id":1}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    let res = app.post("/orders", r#"{"employee_id":1,"items":[{"product_id":1,"quantity":3},{"product_id":2,"quantity":1}]}"#).await;
+    assert_eq!(res.status, 201);
+
+    let card: Scorecard = app.get("/employees/1/scorecard").await.json();
+    assert_eq!((card.employee_id, card.products_added, card.orders), (1, 2, 1));
+    // zásoby po predaji: 7 × 2.0 + 3 × 1.5
+    assert!((card.catalog_value - 18.5).abs() < 1e-9);
+    assert!((card.revenue - 7.5).abs() < 1e-9);
+
+    let idle: Scorecard = app.get("/employees/2/scorecard").await.json();
+    assert_eq!((idle.products_added, idle.orders, idle.catalog_value, idle.revenue), (0, 0, 0.0, 0.0));
+
+    assert_eq!(app.get("/employees/99/scorecard").await.status, 404);
+}
+
+#[tokio::test]
+async fn category_margins_rank_active_products() {
+    let app = TestApp::spawn().await;
+    app.add_product("Jablko", "Ovocie", 50.0, 1.0, 1.5).await;
+    app.add_product("Hruška", "Ovocie", 50.0, 1.0, 2.5).await;
+    app.add_product("Syr", "Mliečne", 50.0, 2.0, 5.0).await;
+    // neaktívny produkt sa nezapočíta
+    let body = r#"{"name":"Starý","category":"Ovocie","quantity":50,"status":false,"bar_code":1,"cost_price":0.0,"sell_price":100.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+
+    let res = app.get("/stats/category-margins").await;
+    assert_eq!(res.status, 200);
+    let margins: Vec<CategoryMargin> = res.json();
+
+    let names: Vec<_> = margins.iter().map(|m| m.category.as_str()).collect();
+    assert_eq!(names, ["Mliečne", "Ovocie"]);
+    assert_eq!(margins[0].total_margin, 150.0);
+    assert_eq!(margins[1].products, 2);
+    assert_eq!(margins[1].excluded, 0);
+    assert_eq!(margins[1].avg_margin, Some(1.0));
+    assert_eq!(margins[1].total_margin, 100.0);
+}
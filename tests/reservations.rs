@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime};
+use store_manager::structs::{Order, Product, Reservation, ReservationStatus};
+use store_manager::test_support::{TestApp, TestResponse};
+
+async fn spawn_with_product(quantity: u32) -> TestApp {
+    let app = TestApp::spawn().await;
+    let body = format!(
+        r#"{{"name":"Torta","category":"Cukráreň","quantity":{quantity},"status":true,"bar_code":1,"cost_price":5.0,"sell_price":12.0}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+    app
+}
+
+fn in_hours(hours: i64) -> NaiveDateTime {
+    Local::now().naive_local() + chrono::Duration::hours(hours)
+}
+
+async fn reserve(app: &TestApp, quantity: f64, expires_at: NaiveDateTime) -> TestResponse {
+    let body = format!(
+        r#"{{"quantity":{quantity},"customer":"p. Nováková, 0900 123 456","expires_at":"{}"}}"#,
+        expires_at.format("%Y-%m-%dT%H:%M:%S")
+    );
+    app.post("/products/1/reserve", &body).await
+}
+
+async fn product(app: &TestApp) -> Product {
+    app.get("/products/1").await.json()
+}
+
+async fn sell(app: &TestApp, quantity: f64, reservation_id: Option<u32>) -> TestResponse {
+    let reservation = reservation_id.map(|id| format!(r#","reservation_id":{id}"#)).unwrap_or_default();
+    app.post("/orders", &format!(r#"{{"items":[{{"product_id":1,"quantity":{quantity}{reservation}}}]}}"#)).await
+}
+
+#[tokio::test]
+async fn reserved_items_cannot_be_sold_to_others() {
+    let app = spawn_with_product(5).await;
+
+    let res = reserve(&app, 3.0, in_hours(24)).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let reservation: Reservation = res.json();
+    assert_eq!(reservation.status, ReservationStatus::Active);
+
+    let p = product(&app).await;
+    assert_eq!((p.quantity, p.reserved_quantity, p.available_quantity), (Some(5.0), Some(3.0), Some(2.0)));
+
+    let res = sell(&app, 3.0, None).await;
+    assert_eq!(res.status, 409);
+    assert!(res.text().contains("rezervovaných"), "{}", res.text());
+    assert_eq!(reserve(&app, 3.0, in_hours(24)).await.status, 409);
+    assert_eq!(sell(&app, 2.0, None).await.status, 201);
+
+    // predaj s rezerváciou ju vyzdvihne
+    let res = sell(&app, 3.0, Some(reservation.id)).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let order_id: u32 = res.json();
+    let p = product(&app).await;
+    assert_eq!((p.quantity, p.reserved_quantity, p.available_quantity), (Some(0.0), Some(0.0), Some(0.0)));
+
+    let all: Vec<Reservation> = app.get("/reservations?product_id=1").await.json();
+    assert_eq!(all[0].status, ReservationStatus::Fulfilled);
+    assert_eq!(all[0].order_id, Some(order_id));
+    assert_eq!(sell(&app, 1.0, Some(reservation.id)).await.status, 409);
+}
+
+#[tokio::test]
+async fn cancel_and_fulfill_endpoints() {
+    let app = spawn_with_product(10).await;
+    let first: Reservation = reserve(&app, 4.0, in_hours(2)).await.json();
+    let second: Reservation = reserve(&app, 2.0, in_hours(2)).await.json();
+
+    let res = app.post(&format!("/reservations/{}/cancel", first.id), "").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<Reservation>().status, ReservationStatus::Cancelled);
+    assert_eq!(app.post(&format!("/reservations/{}/cancel", first.id), "").await.status, 409);
+    assert_eq!(app.post("/reservations/99/cancel", "").await.status, 404);
+    assert_eq!(product(&app).await.available_quantity, Some(8.0));
+
+    let res = app.post(&format!("/reservations/{}/fulfill", second.id), "").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let fulfilled: Reservation = res.json();
+    assert_eq!(fulfilled.status, ReservationStatus::Fulfilled);
+    let order: Order = app.get(&format!("/orders/{}", fulfilled.order_id.unwrap())).await.json();
+    assert_eq!(order.items[0].quantity, 2.0);
+
+    let p = product(&app).await;
+    assert_eq!((p.quantity, p.reserved_quantity), (Some(8.0), Some(0.0)));
+    let active: Vec<Reservation> = app.get("/reservations?active=true").await.json();
+    assert!(active.is_empty());
+}
+
+#[tokio::test]
+async fn invalid_reservations_are_rejected() {
+    let app = spawn_with_product(5).await;
+    assert_eq!(reserve(&app, 0.0, in_hours(1)).await.status, 400);
+    assert_eq!(reserve(&app, 1.0, in_hours(-1)).await.status, 400);
+    assert_eq!(reserve(&app, 6.0, in_hours(1)).await.status, 409);
+    let body = format!(r#"{{"quantity":1,"customer":" ","expires_at":"{}"}}"#, in_hours(1).format("%Y-%m-%dT%H:%M:%S"));
+    assert_eq!(app.post("/products/1/reserve", &body).await.status, 400);
+    assert_eq!(app.post("/products/9/reserve", &body.replace("\" \"", "\"Jana\"")).await.status, 404);
+}
+
+#[tokio::test]
+async fn expired_reservation_is_released_at_read_time() {
+    let app = spawn_with_product(5).await;
+    let expires_at = Local::now().naive_local() + chrono::Duration::seconds(1);
+    let reservation: Reservation = reserve(&app, 5.0, expires_at).await.json();
+    assert_eq!(product(&app).await.available_quantity, Some(0.0));
+
+    tokio::time::sleep(Duration::from_millis(2100)).await;
+
+    assert_eq!(product(&app).await.available_quantity, Some(5.0));
+    let all: Vec<Reservation> = app.get("/reservations").await.json();
+    assert_eq!(all[0].status, ReservationStatus::Expired);
+    assert_eq!(app.post(&format!("/reservations/{}/cancel", reservation.id), "").await.status, 409);
+    assert_eq!(sell(&app, 5.0, None).await.status, 201);
+}
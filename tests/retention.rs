@@ -0,0 +1,138 @@
+use chrono::{Days, Local, NaiveDate};
+use store_manager::config::Config;
+use store_manager::retention::{RetentionAction, RetentionPolicy};
+use store_manager::structs::{Employee, Product, RetentionReport};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+
+async fn admin(app: &TestApp, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", KEY)]).await
+}
+
+/// Jana pracuje, Peter a Eva odišli; Eva má dochádzku a je uvedená pri produkte.
+async fn spawn_app(policy: RetentionPolicy) -> TestApp {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), retention: policy, ..Config::default() })
+        .await;
+    for name in ["Jana", "Peter", "Eva"] {
+        let body = format!(
+            r#"{{"name":"{name}","surname":"Nová","position":"Predavač","status":true,"email":"{name}@obchod.sk","salary":1200}}"#
+        );
+        assert_eq!(admin(&app, "POST", "/employees", Some(&body)).await.status, 201);
+    }
+    let product = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0,"employee_id":3}"#;
+    assert_eq!(admin(&app, "POST", "/products", Some(product)).await.status, 201);
+    for id in [1, 3] {
+        assert_eq!(admin(&app, "POST", &format!("/employees/{id}/clock-in?at=2020-03-02T08:00:00"), None).await.status, 201);
+        assert_eq!(admin(&app, "POST", &format!("/employees/{id}/clock-out?at=2020-03-02T16:00:00"), None).await.status, 200);
+    }
+    for id in [2, 3] {
+        assert_eq!(admin(&app, "PUT", &format!("/employees/{id}"), Some(r#"{"status":false}"#)).await.status, 200);
+    }
+    app
+}
+
+fn in_years(years: u64) -> NaiveDate {
+    Local::now().date_naive().checked_add_days(Days::new(years * 366)).unwrap()
+}
+
+async fn employees(app: &TestApp) -> Vec<Employee> {
+    admin(app, "GET", "/employees", None).await.json()
+}
+
+#[tokio::test]
+async fn dry_run_lists_departed_employees_without_changes() {
+    let policy = RetentionPolicy { employee_years: Some(5), attendance_years: Some(3), ..RetentionPolicy::default() };
+    let app = spawn_app(policy).await;
+
+    // dnes ešte nikto neodišiel pred piatimi rokmi
+    let res = admin(&app, "GET", "/admin/retention", None).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: RetentionReport = res.json();
+    assert!(report.dry_run && report.employees.is_empty());
+    assert_eq!(report.attendance_entries, 2);
+    assert_eq!(app.get("/admin/retention").await.status, 401);
+
+    let report = app.db.purge_personal_data(&policy, in_years(6), true).await.unwrap();
+    let ids: Vec<u32> = report.employees.iter().map(|c| c.employee_id).collect();
+    assert_eq!(ids, [2, 3]);
+    assert_eq!(report.employees[0].departed_on, Local::now().date_naive());
+    assert_eq!(employees(&app).await[2].name.as_deref(), Some("Eva"));
+    assert!(app.db.get_audit_log().await.unwrap().iter().all(|e| e.action != "retention_purge"));
+}
+
+#[tokio::test]
+async fn anonymize_keeps_rows_but_erases_personal_data() {
+    let policy = RetentionPolicy { employee_years: Some(5), ..RetentionPolicy::default() };
+    let app = spawn_app(policy).await;
+
+    let report = app.db.purge_personal_data(&policy, in_years(6), false).await.unwrap();
+    assert_eq!((report.employees.len(), report.attendance_entries, report.references), (2, 0, 0));
+
+    let all = employees(&app).await;
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].email.as_deref(), Some("Jana@obchod.sk"));
+    let eva = &all[2];
+    assert_eq!((eva.name.as_deref(), eva.surname.as_deref()), (Some("Anonymizovaný"), Some("zamestnanec 3")));
+    assert!(eva.email.is_none() && eva.salary.is_none());
+    assert_eq!(eva.position.as_deref(), Some("Predavač"));
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "retention_purge");
+    assert!(audit[0].details.as_deref().unwrap().contains("action=anonymize, employees=2,3"), "{:?}", audit[0]);
+
+    // anonymizovaní sa druhýkrát neuvádzajú
+    let report = app.db.purge_personal_data(&policy, in_years(6), false).await.unwrap();
+    assert!(report.employees.is_empty());
+}
+
+#[tokio::test]
+async fn delete_nulls_references_and_removes_attendance() {
+    let policy = RetentionPolicy { employee_years: Some(5), attendance_years: Some(3), action: RetentionAction::Delete };
+    let app = spawn_app(policy).await;
+
+    let report = app.db.purge_personal_data(&policy, in_years(6), false).await.unwrap();
+    assert_eq!(report.employees.len(), 2);
+    // Evina dochádzka s ňou, Janina ako stará
+    assert_eq!((report.attendance_entries, report.references), (2, 1));
+
+    let all = employees(&app).await;
+    assert_eq!(all.iter().map(|e| e.id.unwrap()).collect::<Vec<_>>(), [1]);
+    let product: Product = admin(&app, "GET", "/products/1", None).await.json();
+    assert_eq!(product.employee_id, None);
+    let hours = admin(&app, "GET", "/employees/1/hours?from=2020-03-01&to=2020-03-31", None).await.text();
+    assert!(hours.contains(r#""entries":[]"#), "{hours}");
+}
+
+#[tokio::test]
+async fn reactivated_employee_is_not_purged() {
+    let policy = RetentionPolicy { employee_years: Some(1), ..RetentionPolicy::default() };
+    let app = spawn_app(policy).await;
+    assert_eq!(admin(&app, "PUT", "/employees/2", Some(r#"{"status":true}"#)).await.status, 200);
+
+    let report = app.db.purge_personal_data(&policy, in_years(2), true).await.unwrap();
+    assert_eq!(report.employees.iter().map(|c| c.employee_id).collect::<Vec<_>>(), [3]);
+}
+
+#[test]
+fn retention_policy_is_configured_per_data_class() {
+    let config = Config::from_toml("[retention]\nemployee_years = 3\naction = \"delete\"\n").unwrap();
+    assert_eq!(config.retention.employee_years, Some(3));
+    assert_eq!((config.retention.attendance_years, config.retention.action), (None, RetentionAction::Delete));
+    assert!(Config::default().retention.is_empty());
+
+    let mut config = Config::default();
+    let env = |key: &str| match key {
+        "STORE_RETENTION_ATTENDANCE_YEARS" => Some("2".to_string()),
+        "STORE_RETENTION_ACTION" => Some("anonymize".to_string()),
+        _ => None,
+    };
+    config.apply_overrides(env).unwrap();
+    assert_eq!(config.retention.attendance_years, Some(2));
+    assert!(Config::from_toml("[retention]\naction = \"shred\"\n").is_err());
+    assert!(Config::from_toml("[retention]\nemployee_years = 0\n").unwrap().validate().is_err());
+
+    let policy = RetentionPolicy { employee_years: Some(2), ..RetentionPolicy::default() };
+    let leap = NaiveDate::from_ymd_opt(2028, 2, 29).unwrap();
+    assert_eq!(policy.employee_cutoff(leap), NaiveDate::from_ymd_opt(2026, 2, 28));
+}
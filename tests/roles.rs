@@ -0,0 +1,90 @@
+use axum::http::Method;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use store_manager::auth::{required_role, Claims, Role};
+use store_manager::config::Config;
+use store_manager::test_support::{TestApp, TestResponse};
+
+const SECRET: &str = "jwt-tajomstvo";
+const PRODUCT: &str = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+
+async fn spawn_app() -> TestApp {
+    TestApp::spawn_with_config(Config {
+        admin_api_key: Some("admin-kluc".into()),
+        editor_api_key: Some("editor-kluc".into()),
+        viewer_api_key: Some("viewer-kluc".into()),
+        jwt_secret: Some(SECRET.into()),
+        ..Config::default()
+    })
+    .await
+}
+
+async fn with_key(app: &TestApp, method: &str, path: &str, body: Option<&str>, key: &str) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", key)]).await
+}
+
+#[tokio::test]
+async fn viewer_can_read_but_not_write() {
+    let app = spawn_app().await;
+    assert_eq!(with_key(&app, "POST", "/products", Some(PRODUCT), "editor-kluc").await.status, 201);
+
+    assert_eq!(with_key(&app, "GET", "/products", None, "viewer-kluc").await.status, 200);
+    assert_eq!(with_key(&app, "GET", "/products/1", None, "viewer-kluc").await.status, 200);
+    assert_eq!(with_key(&app, "POST", "/products/search", Some("{}"), "viewer-kluc").await.status, 200);
+
+    let res = with_key(&app, "POST", "/products", Some(PRODUCT), "viewer-kluc").await;
+    assert_eq!(res.status, 403);
+    assert!(res.text().contains("editor"), "{}", res.text());
+    assert_eq!(with_key(&app, "PUT", "/products/1", Some(r#"{"sell_price":3.0}"#), "viewer-kluc").await.status, 403);
+}
+
+#[tokio::test]
+async fn editor_can_write_but_only_admin_deletes() {
+    let app = spawn_app().await;
+    assert_eq!(with_key(&app, "POST", "/products", Some(PRODUCT), "editor-kluc").await.status, 201);
+    assert_eq!(with_key(&app, "PUT", "/products/1", Some(r#"{"sell_price":3.0}"#), "editor-kluc").await.status, 200);
+
+    assert_eq!(with_key(&app, "DELETE", "/products/1", None, "editor-kluc").await.status, 403);
+    assert_eq!(with_key(&app, "GET", "/admin/maintenance", None, "editor-kluc").await.status, 403);
+
+    assert_eq!(with_key(&app, "DELETE", "/products/1", None, "admin-kluc").await.status, 204);
+}
+
+#[tokio::test]
+async fn token_role_claim_decides_access() {
+    let app = spawn_app().await;
+    let bearer = |role: Option<&str>| {
+        let claims = Claims { sub: "jana".into(), role: role.map(Into::into), exp: u64::MAX / 2 };
+        format!("Bearer {}", encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap())
+    };
+    let app = &app;
+    let post = |auth: String| async move {
+        app.request_with_headers("POST", "/products", Some(PRODUCT), &[("authorization", &auth)]).await.status
+    };
+
+    // bez roly alebo s neznámou rolou je volajúci len čitateľ
+    assert_eq!(post(bearer(None)).await, 403);
+    assert_eq!(post(bearer(Some("superuser"))).await, 403);
+    assert_eq!(post(bearer(Some("editor"))).await, 201);
+
+    let auth = bearer(Some("viewer"));
+    assert_eq!(app.request_with_headers("GET", "/products", None, &[("authorization", &auth)]).await.status, 200);
+}
+
+#[tokio::test]
+async fn unknown_api_key_is_rejected() {
+    let app = spawn_app().await;
+    assert_eq!(with_key(&app, "GET", "/products", None, "zly-kluc").await.status, 401);
+    assert_eq!(app.post("/products", PRODUCT).await.status, 401);
+    assert_eq!(app.get("/products").await.status, 200);
+}
+
+#[test]
+fn route_roles_are_mapped_centrally() {
+    assert_eq!(required_role(&Method::GET, "/products"), Role::Viewer);
+    assert_eq!(required_role(&Method::POST, "/employees/search"), Role::Viewer);
+    assert_eq!(required_role(&Method::POST, "/orders"), Role::Editor);
+    assert_eq!(required_role(&Method::PUT, "/employees/1"), Role::Editor);
+    assert_eq!(required_role(&Method::DELETE, "/employees/1"), Role::Admin);
+    assert_eq!(required_role(&Method::GET, "/admin/maintenance"), Role::Admin);
+    assert!(Role::Viewer < Role::Editor && Role::Editor < Role::Admin);
+}
@@ -0,0 +1,22 @@
+use store_manager::structs::RouteInfo;
+use store_manager::test_support::TestApp;
+
+#[tokio::test]
+async fn registered_routes_are_listed() {
+    let app = TestApp::spawn().await;
+
+    let res = app.get("/routes").await;
+    assert_eq!(res.status, 200);
+    let routes: Vec<RouteInfo> = res.json();
+
+    let find = |path: &str| routes.iter().find(|r| r.path == path).unwrap_or_else(|| panic!("chýba {path}"));
+    assert_eq!(find("/products").methods, ["GET", "POST"]);
+    assert_eq!(find("/products/{id}").methods, ["GET", "DELETE", "PUT"]);
+    assert_eq!(find("/admin/export").methods, ["GET"]);
+    assert_eq!(find("/tx/{id}/commit").methods, ["POST"]);
+    assert_eq!(find("/routes").methods, ["GET"]);
+    assert!(routes.windows(2).all(|w| w[0].path <= w[1].path));
+
+    // zoznam je dostupný aj pod verziou API
+    assert_eq!(app.get("/api/v1/routes").await.json::<Vec<RouteInfo>>(), routes);
+}
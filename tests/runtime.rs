@@ -0,0 +1,78 @@
+use std::time::{Duration, Instant};
+
+use axum::{routing::get, Router};
+use store_manager::config::Config;
+use store_manager::server::{build_runtime, serve_with_shutdown};
+use store_manager::test_support::TestApp;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+#[test]
+fn app_starts_on_single_worker_runtime() {
+    let config = Config { worker_threads: Some(1), max_blocking_threads: 1, ..Config::default() };
+    let runtime = build_runtime(&config).expect("runtime s jedným vláknom");
+
+    runtime.block_on(async {
+        let app = TestApp::spawn_with_config(config).await;
+        assert_eq!(app.get("/livez").await.status, 200);
+        let body = r#"{"name":"Jablko","category":"Ovocie","quantity":1,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+        assert_eq!(app.post("/products", body).await.status, 201);
+        assert_eq!(app.get("/products").await.status, 200);
+    });
+}
+
+#[tokio::test]
+async fn shutdown_completes_despite_stuck_request() {
+    let app = Router::new().route(
+        "/slow",
+        get(|| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            "neskoro"
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let timeout = Duration::from_millis(300);
+    let server = tokio::spawn(serve_with_shutdown(
+        listener,
+        app,
+        async {
+            shutdown_rx.await.ok();
+        },
+        timeout,
+    ));
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let started = Instant::now();
+    shutdown_tx.send(()).unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(5), server).await.expect("server sa nevypol včas");
+    assert!(result.unwrap().is_ok());
+    let elapsed = started.elapsed();
+    assert!(elapsed >= timeout, "server nečakal na rozbehnuté požiadavky: {elapsed:?}");
+    assert!(elapsed < Duration::from_secs(2), "vypínanie trvalo {elapsed:?}");
+}
+
+#[tokio::test]
+async fn idle_server_shuts_down_without_waiting_for_timeout() {
+    let app = Router::new().route("/", get(|| async { "ok" }));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    let server = tokio::spawn(serve_with_shutdown(
+        listener,
+        app,
+        async {
+            shutdown_rx.await.ok();
+        },
+        Duration::from_secs(30),
+    ));
+
+    let started = Instant::now();
+    shutdown_tx.send(()).unwrap();
+    tokio::time::timeout(Duration::from_secs(5), server).await.expect("server sa nevypol").unwrap().unwrap();
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
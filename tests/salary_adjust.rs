@@ -0,0 +1,66 @@
+use store_manager::structs::{Employee, SalaryAdjustmentReport};
+use store_manager::test_support::TestApp;
+
+/// Sklad: Jana (1000), Eva (1500), Ivan bez platu, Peter (2000, neaktívny); Pokladňa: Mária (1000).
+async fn spawn_with_staff() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, department, salary, status) in [
+        ("Jana", "Sklad", Some(1000.0), true),
+        ("Eva", "Sklad", Some(1500.0), true),
+        ("Ivan", "Sklad", None, true),
+        ("Peter", "Sklad", Some(2000.0), false),
+        ("Mária", "Pokladňa", Some(1000.0), true),
+    ] {
+        let salary = salary.map(|s| format!(r#","salary":{s}"#)).unwrap_or_default();
+        let body = format!(
+            r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","department":"{department}","status":{status}{salary}}}"#
+        );
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    app
+}
+
+async fn salaries(app: &TestApp) -> Vec<Option<f64>> {
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    employees.iter().map(|e| e.salary).collect()
+}
+
+#[tokio::test]
+async fn percentage_raise_applies_to_active_department_staff() {
+    let app = spawn_with_staff().await;
+
+    let res = app.post("/employees/salary-adjust", r#"{"department":"Sklad","factor":1.035}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: SalaryAdjustmentReport = res.json();
+    assert_eq!((report.adjusted, report.skipped_without_salary), (2, vec![3]));
+    assert_eq!(salaries(&app).await, [Some(1035.0), Some(1552.5), None, Some(2000.0), Some(1000.0)]);
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "adjust_salaries");
+}
+
+#[tokio::test]
+async fn flat_adjustment_is_added_after_factor() {
+    let app = spawn_with_staff().await;
+
+    let res = app.post("/employees/salary-adjust", r#"{"department":"Sklad","flat":50}"#).await;
+    assert_eq!(res.json::<SalaryAdjustmentReport>().adjusted, 2);
+    let res = app.post("/employees/salary-adjust", r#"{"department":"Sklad","factor":1.1,"flat":-5.5}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(salaries(&app).await, [Some(1149.5), Some(1699.5), None, Some(2000.0), Some(1000.0)]);
+
+    let report: SalaryAdjustmentReport = app.post("/employees/salary-adjust", r#"{"department":"Neznáme","flat":10}"#).await.json();
+    assert_eq!((report.adjusted, report.skipped_without_salary.len()), (0, 0));
+}
+
+#[tokio::test]
+async fn negative_results_and_invalid_input_change_nothing() {
+    let app = spawn_with_staff().await;
+
+    let res = app.post("/employees/salary-adjust", r#"{"department":"Sklad","flat":-1200}"#).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("zamestnancov 1 by"), "{}", res.text());
+    assert_eq!(app.post("/employees/salary-adjust", r#"{"department":"Sklad","factor":-1}"#).await.status, 400);
+    assert_eq!(app.post("/employees/salary-adjust", r#"{"department":" ","flat":10}"#).await.status, 400);
+    assert_eq!(salaries(&app).await, [Some(1000.0), Some(1500.0), None, Some(2000.0), Some(1000.0)]);
+}
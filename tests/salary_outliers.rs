@@ -0,0 +1,52 @@
+use store_manager::structs::SalaryOutlier;
+use store_manager::test_support::TestApp;
+
+async fn employee(app: &TestApp, name: &str, position: &str, salary: Option<f64>) {
+    let salary = salary.map(|s| format!(r#","salary":{s}"#)).unwrap_or_default();
+    let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"{position}","department":"Predajňa","status":true{salary}}}"#);
+    assert_eq!(app.post("/employees", &body).await.status, 201);
+}
+
+async fn seed() -> TestApp {
+    let app = TestApp::spawn().await;
+    employee(&app, "Jana", "Predavačka", Some(1000.0)).await;
+    employee(&app, "Eva", "Predavačka", Some(1100.0)).await;
+    employee(&app, "Mária", "Predavačka", Some(1050.0)).await;
+    // jasný prípad: dvojnásobok mediánu
+    employee(&app, "Zuzana", "Predavačka", Some(2100.0)).await;
+    employee(&app, "Peter", "Skladník", Some(900.0)).await;
+    employee(&app, "Ivan", "Skladník", Some(1000.0)).await;
+    employee(&app, "Karol", "Skladník", Some(600.0)).await;
+    // bez platu sa neporovnáva
+    employee(&app, "Ján", "Skladník", None).await;
+    app
+}
+
+#[tokio::test]
+async fn clear_outliers_are_flagged() {
+    let app = seed().await;
+    let res = app.get("/stats/salary-outliers").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let outliers: Vec<SalaryOutlier> = res.json();
+
+    // Predavačka: medián (1050 + 1100) / 2 = 1075; Skladník: medián 900
+    let flagged: Vec<(&str, f64)> = outliers.iter().map(|o| (o.name.as_deref().unwrap(), o.median)).collect();
+    assert_eq!(flagged, [("Zuzana", 1075.0), ("Karol", 900.0)]);
+    assert_eq!(outliers[0].salary, 2100.0);
+    assert!((outliers[0].deviation - (2100.0 - 1075.0) / 1075.0).abs() < 1e-9);
+    assert!((outliers[1].deviation + 1.0 / 3.0).abs() < 1e-9, "{:?}", outliers[1]);
+    assert_eq!(outliers[1].position, "Skladník");
+}
+
+#[tokio::test]
+async fn deviation_threshold_is_configurable() {
+    let app = seed().await;
+    let outliers: Vec<SalaryOutlier> = app.get("/stats/salary-outliers?deviation=0.05").await.json();
+    let names: Vec<&str> = outliers.iter().map(|o| o.name.as_deref().unwrap()).collect();
+    // Jana: 1000 / 1075 → -7 %, Mária 1050 → -2.3 %, Ivan 1000 / 900 → +11 %
+    assert_eq!(names, ["Zuzana", "Karol", "Ivan", "Jana"]);
+
+    let outliers = app.db.salary_outliers(1.0).await.unwrap();
+    assert_eq!(outliers.len(), 0);
+    assert_eq!(app.get("/stats/salary-outliers?deviation=-0.1").await.status, 400);
+}
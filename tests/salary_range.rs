@@ -0,0 +1,41 @@
+use store_manager::structs::Employee;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_staff() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, salary) in [("Jana", "900"), ("Peter", "1200"), ("Eva", "1500"), ("Marek", "null"), ("Zuzana", "2000")] {
+        let body =
+            format!(r#"{{"name":"{name}","surname":"Nový","position":"Predavač","status":true,"salary":{salary}}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    app
+}
+
+async fn search(app: &TestApp, filter: &str) -> Vec<String> {
+    let employees: Vec<Employee> = app.post("/employees/search", filter).await.json();
+    assert!(employees.iter().all(|e| e.salary_min.is_none() && e.salary_max.is_none()));
+    employees.into_iter().map(|e| e.name.unwrap()).collect()
+}
+
+#[tokio::test]
+async fn closed_and_open_ended_salary_bands() {
+    let app = spawn_with_staff().await;
+
+    assert_eq!(search(&app, r#"{"salary_min":1200,"salary_max":1500}"#).await, ["Peter", "Eva"]);
+    assert_eq!(search(&app, r#"{"salary_min":1500}"#).await, ["Eva", "Zuzana"]);
+    assert_eq!(search(&app, r#"{"salary_max":1200}"#).await, ["Jana", "Peter"]);
+    assert!(search(&app, r#"{"salary_min":1600,"salary_max":1000}"#).await.is_empty());
+
+    // pásmo sa kombinuje s ostatnými poľami filtra
+    assert_eq!(search(&app, r#"{"salary_min":1000,"name":"Zuz"}"#).await, ["Zuzana"]);
+}
+
+#[tokio::test]
+async fn employees_without_salary_never_match_a_band() {
+    let app = spawn_with_staff().await;
+
+    assert_eq!(search(&app, "{}").await.len(), 5);
+    let everyone = search(&app, r#"{"salary_min":0}"#).await;
+    assert_eq!(everyone, ["Jana", "Peter", "Eva", "Zuzana"]);
+    assert!(!search(&app, r#"{"salary_max":100000}"#).await.contains(&"Marek".to_string()));
+}
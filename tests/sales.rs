@@ -0,0 +1,117 @@
+use chrono::{Days, Local, NaiveDate};
+use sqlx::sqlite::SqlitePool;
+use store_manager::config::Config;
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    for name in ["Kofola", "Vinea"] {
+        let body = format!(r#"{{"name":"{name}","category":"Nápoje","quantity":10,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#);
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+fn day(offset: i64) -> NaiveDate {
+    let today = Local::now().date_naive();
+    if offset >= 0 {
+        today.checked_add_days(Days::new(offset as u64)).unwrap()
+    } else {
+        today.checked_sub_days(Days::new(offset.unsigned_abs())).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn sale_ending_today_is_still_active() {
+    let app = spawn_with_products().await;
+    let body = format!(r#"{{"sale_price":1.5,"ends":"{}"}}"#, day(0));
+    let res = app.post("/products/1/sale", &body).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let product: Product = res.json();
+    assert_eq!((product.sale_price, product.sale_ends, product.effective_price), (Some(1.5), Some(day(0)), Some(1.5)));
+
+    let listed: Vec<Product> = app.get("/products").await.json();
+    let prices: Vec<_> = listed.iter().map(|p| (p.id.unwrap(), p.effective_price)).collect();
+    assert_eq!(prices, [(1, Some(1.5)), (2, Some(2.0))]);
+
+    let on_sale: Vec<Product> = app.get("/products/on-sale").await.json();
+    assert_eq!(on_sale.iter().map(|p| p.id.unwrap()).collect::<Vec<_>>(), [1]);
+
+    assert_eq!(app.db.products_on_sale(day(0)).await.unwrap().len(), 1);
+    let tomorrow = app.db.products_on_sale(day(1)).await.unwrap();
+    assert!(tomorrow.is_empty(), "{tomorrow:?}");
+}
+
+#[tokio::test]
+async fn finished_sale_falls_back_to_sell_price() {
+    let app = spawn_with_products().await;
+    let body = format!(r#"{{"sale_price":1.5,"ends":"{}"}}"#, day(3));
+    assert_eq!(app.post("/products/1/sale", &body).await.status, 200);
+
+    let pool = SqlitePool::connect(&format!("sqlite:{}", app.db_path().display())).await.unwrap();
+    sqlx::query("UPDATE products SET sale_ends = ? WHERE id = 1").bind(day(-1)).execute(&pool).await.unwrap();
+
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!((product.sale_price, product.effective_price), (Some(1.5), Some(2.0)));
+    let on_sale: Vec<Product> = app.get("/products/on-sale").await.json();
+    assert!(on_sale.is_empty());
+}
+
+#[tokio::test]
+async fn sale_is_validated_and_can_be_cleared() {
+    let app = spawn_with_products().await;
+    let ends = day(7);
+    for body in [
+        format!(r#"{{"sale_price":2.0,"ends":"{ends}"}}"#),
+        format!(r#"{{"sale_price":0,"ends":"{ends}"}}"#),
+        format!(r#"{{"sale_price":1.5,"ends":"{}"}}"#, day(-1)),
+        r#"{"sale_price":1.5}"#.to_string(),
+    ] {
+        assert_eq!(app.post("/products/1/sale", &body).await.status, 400, "{body}");
+    }
+    let body = format!(r#"{{"sale_price":1.5,"ends":"{ends}"}}"#);
+    assert_eq!(app.post("/products/9/sale", &body).await.status, 404);
+    assert_eq!(app.post("/products/1/sale", &body).await.status, 200);
+
+    let product: Product = app.post("/products/1/sale", r#"{"sale_price":null}"#).await.json();
+    assert_eq!((product.sale_price, product.sale_ends, product.effective_price), (None, None, Some(2.0)));
+    let on_sale: Vec<Product> = app.get("/products/on-sale").await.json();
+    assert!(on_sale.is_empty());
+}
+
+#[tokio::test]
+async fn expire_sales_clears_only_finished_sales() {
+    let app = spawn_with_products().await;
+    for id in [1, 2] {
+        let body = format!(r#"{{"sale_price":1.5,"ends":"{}"}}"#, day(0));
+        assert_eq!(app.post(&format!("/products/{id}/sale"), &body).await.status, 200);
+    }
+    let pool = SqlitePool::connect(&format!("sqlite:{}", app.db_path().display())).await.unwrap();
+    sqlx::query("UPDATE products SET sale_ends = ? WHERE id = 1").bind(day(-1)).execute(&pool).await.unwrap();
+
+    assert_eq!(app.post("/admin/expire-sales", "").await.status, 401);
+    let res = app.request_with_headers("POST", "/admin/expire-sales", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<u64>(), 1);
+
+    let expired: Product = app.get("/products/1").await.json();
+    assert_eq!((expired.sale_price, expired.sale_ends, expired.effective_price), (None, None, Some(2.0)));
+    let active: Product = app.get("/products/2").await.json();
+    assert_eq!((active.sale_price, active.sale_ends), (Some(1.5), Some(day(0))));
+
+    // posledný deň akcie sa ešte nezruší, nasledujúci áno
+    assert_eq!(app.db.expire_sales(day(0)).await.unwrap(), 0);
+    assert_eq!(app.db.expire_sales(day(1)).await.unwrap(), 1);
+}
+
+#[test]
+fn price_on_switches_after_last_sale_day() {
+    let ends = NaiveDate::from_ymd_opt(2024, 5, 31).unwrap();
+    let product = Product { sell_price: Some(2.0), sale_price: Some(1.5), sale_ends: Some(ends), ..Product::default() };
+    assert_eq!(product.price_on(ends), Some(1.5));
+    assert_eq!(product.price_on(ends.succ_opt().unwrap()), Some(2.0));
+    assert!(!Product { sale_ends: None, ..product }.on_sale(ends));
+}
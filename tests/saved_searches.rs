@@ -0,0 +1,94 @@
+use serde_json::json;
+use store_manager::structs::{Employee, Product, SavedSearch, SearchEntity};
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, quantity: u32, brand: &str) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0,"brand":"{brand}"}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+fn names(products: &[Product]) -> Vec<&str> {
+    products.iter().map(|p| p.name.as_deref().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn saved_product_search_runs_with_current_pagination() {
+    let app = TestApp::spawn().await;
+    for (name, quantity, brand) in [("Kofola", 3, "Akcia"), ("Vinea", 2, "Akcia"), ("Rajec", 1, "Bežné"), ("Kinley", 40, "Akcia")] {
+        add_product(&app, name, quantity, brand).await;
+    }
+
+    let body = r#"{"name":"akcia-dochadza","entity":"products","filter":{"brand":"Akcia","quantity_max":5}}"#;
+    let res = app.post("/saved-searches", body).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let saved: SavedSearch = res.json();
+    assert_eq!((saved.id, saved.entity), (Some(1), SearchEntity::Products));
+    assert!(saved.created_at.is_some());
+
+    let res = app.get("/products/saved/akcia-dochadza").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(names(&res.json::<Vec<Product>>()), ["Kofola", "Vinea"]);
+
+    let res = app.get("/products/saved/akcia-dochadza?limit=1&offset=1").await;
+    assert_eq!(res.header("x-total-count"), Some("2"));
+    assert_eq!(names(&res.json::<Vec<Product>>()), ["Vinea"]);
+
+    assert_eq!(app.get("/products/saved/neznáme").await.status, 404);
+    assert_eq!(app.post("/saved-searches", body).await.status, 409);
+}
+
+#[tokio::test]
+async fn saved_employee_search_and_listing() {
+    let app = TestApp::spawn().await;
+    for (name, department) in [("Jana", "Sklad"), ("Eva", "Pokladňa")] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavačka","department":"{department}","status":true}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    let res = app.post("/saved-searches", r#"{"name":"sklad","entity":"employees","filter":{"department":"Sklad"}}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let res = app.post("/saved-searches", r#"{"name":"sklad","entity":"products","filter":{"category":"Sklad"}}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+
+    let employees: Vec<Employee> = app.get("/employees/saved/sklad").await.json();
+    assert_eq!(employees.iter().map(|e| e.name.as_deref().unwrap()).collect::<Vec<_>>(), ["Jana"]);
+
+    let all: Vec<SavedSearch> = app.get("/saved-searches").await.json();
+    assert_eq!(all.iter().map(|s| s.entity).collect::<Vec<_>>(), [SearchEntity::Employees, SearchEntity::Products]);
+    let products: Vec<SavedSearch> = app.get("/saved-searches?entity=products").await.json();
+    assert_eq!(products.len(), 1);
+
+    assert_eq!(app.delete("/saved-searches/2").await.status, 204);
+    assert_eq!(app.delete("/saved-searches/2").await.status, 404);
+    assert_eq!(app.get("/products/saved/sklad").await.status, 404);
+}
+
+#[tokio::test]
+async fn saved_search_filter_is_validated_and_stale_filters_are_reported() {
+    let app = TestApp::spawn().await;
+    let res = app.post("/saved-searches", r#"{"name":"preklep","entity":"products","filter":{"sell_pricce":2}}"#).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("sell_pricce"), "{}", res.text());
+    let res = app.post("/saved-searches", r#"{"name":"typ","entity":"products","filter":{"quantity_max":"veľa"}}"#).await;
+    assert_eq!(res.status, 422);
+    assert_eq!(app.post("/saved-searches", r#"{"name":" ","entity":"products","filter":{}}"#).await.status, 400);
+
+    // filter uložený staršou verziou s poľom, ktoré aktuálna verzia nepozná
+    let old = SavedSearch {
+        id: None,
+        name: "stary".into(),
+        entity: SearchEntity::Products,
+        filter: json!({"discount_tier": 2}),
+        owner: None,
+        created_at: None,
+        stale: None,
+    };
+    app.db.add_saved_search(&old, None).await.unwrap();
+
+    let listed: Vec<SavedSearch> = app.get("/saved-searches").await.json();
+    assert!(listed[0].stale.as_deref().is_some_and(|s| s.contains("discount_tier")), "{listed:?}");
+    let res = app.get("/products/saved/stary").await;
+    assert_eq!(res.status, 409);
+    assert!(res.text().contains("zastarané"), "{}", res.text());
+}
@@ -0,0 +1,64 @@
+use store_manager::structs::{Employee, ShiftAssignment};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_employees() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, status) in [("Jana", true), ("Eva", true), ("Peter", false)] {
+        let body = format!(r#"{{"name":"{name}","surname":"Nová","position":"Predavač","shift":"morning","status":{status}}}"#);
+        assert_eq!(app.post("/employees", &body).await.status, 201);
+    }
+    app
+}
+
+async fn assign(app: &TestApp, employee_id: u32, date: &str, shift: &str) -> u16 {
+    let body = format!(r#"{{"employee_id":{employee_id},"date":"{date}","shift":"{shift}"}}"#);
+    app.post("/schedule", &body).await.status
+}
+
+async fn on_shift(app: &TestApp, query: &str) -> Vec<u32> {
+    let res = app.get(&format!("/schedule?{query}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json::<Vec<Employee>>().iter().filter_map(|e| e.id).collect()
+}
+
+#[tokio::test]
+async fn schedule_lists_assigned_active_employees() {
+    let app = spawn_with_employees().await;
+    let res = app.post("/schedule", r#"{"employee_id":2,"date":"2024-06-01","shift":" morning "}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let assignment: ShiftAssignment = res.json();
+    assert_eq!(assignment.shift, "morning");
+    assert_eq!(assign(&app, 1, "2024-06-01", "morning").await, 201);
+    assert_eq!(assign(&app, 1, "2024-06-02", "evening").await, 201);
+
+    assert_eq!(on_shift(&app, "date=2024-06-01&shift=morning").await, [1, 2]);
+    assert_eq!(on_shift(&app, "date=2024-06-02&shift=evening").await, [1]);
+    // statické pole `shift` zamestnanca sa do rozpisu nepočíta
+    assert!(on_shift(&app, "date=2024-06-03&shift=morning").await.is_empty());
+    assert_eq!(app.db.employees_on_shift("2024-06-01".parse().unwrap(), "evening").await.unwrap().len(), 0);
+
+    // vyradený zamestnanec zo zmeny vypadne
+    assert_eq!(app.delete("/employees/2?soft=true").await.status, 204);
+    assert_eq!(on_shift(&app, "date=2024-06-01&shift=morning").await, [1]);
+}
+
+#[tokio::test]
+async fn assignments_are_validated_and_can_be_removed() {
+    let app = spawn_with_employees().await;
+    assert_eq!(assign(&app, 1, "2024-06-01", "morning").await, 201);
+
+    assert_eq!(assign(&app, 1, "2024-06-01", "morning").await, 409);
+    assert_eq!(assign(&app, 3, "2024-06-01", "morning").await, 409);
+    assert_eq!(assign(&app, 9, "2024-06-01", "morning").await, 404);
+    assert_eq!(assign(&app, 1, "2024-06-01", " ").await, 400);
+
+    assert_eq!(app.delete("/schedule/1").await.status, 204);
+    assert!(on_shift(&app, "date=2024-06-01&shift=morning").await.is_empty());
+    assert_eq!(app.delete("/schedule/1").await.status, 404);
+    assert_eq!(app.get("/schedule?date=2024-06-01").await.status, 400);
+
+    // vymazaný zamestnanec odíde z rozpisu spolu s priradeniami
+    assert_eq!(assign(&app, 2, "2024-06-01", "morning").await, 201);
+    assert_eq!(app.delete("/employees/2").await.status, 204);
+    assert!(on_shift(&app, "date=2024-06-01&shift=morning").await.is_empty());
+}
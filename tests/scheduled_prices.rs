@@ -0,0 +1,79 @@
+use chrono::{Days, Local};
+use store_manager::structs::{Product, ScheduledPriceChange};
+use store_manager::test_support::TestApp;
+
+const ADMIN_KEY: &str = "tajny-kluc";
+
+async fn spawn_with_product() -> TestApp {
+    let app = TestApp::spawn_with_config(store_manager::config::Config {
+        admin_api_key: Some(ADMIN_KEY.into()),
+        ..Default::default()
+    })
+    .await;
+    let product = r#"{"name":"Kofola","category":"Nápoje","quantity":10,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", product).await.status, 201);
+    app
+}
+
+async fn schedule(app: &TestApp, price: f64, days_ahead: u64) -> ScheduledPriceChange {
+    let date = Local::now().date_naive() + Days::new(days_ahead);
+    let body = format!(r#"{{"new_price":{price},"effective_date":"{date}"}}"#);
+    let res = app.post("/products/1/schedule-price", &body).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    res.json()
+}
+
+async fn sell_price(app: &TestApp) -> Option<f64> {
+    let product: Product = app.get("/products/1").await.json();
+    product.sell_price
+}
+
+#[tokio::test]
+async fn due_change_is_applied_and_future_one_waits() {
+    let app = spawn_with_product().await;
+    let today = schedule(&app, 2.5, 0).await;
+    let later = schedule(&app, 3.0, 7).await;
+    assert!(today.applied_at.is_none() && today.old_price.is_none());
+
+    let res = app.request_with_headers("POST", "/admin/apply-price-changes", None, &[("x-api-key", ADMIN_KEY)]).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let applied: Vec<ScheduledPriceChange> = res.json();
+    assert_eq!(applied.len(), 1);
+    assert_eq!((applied[0].id, applied[0].old_price), (today.id, Some(2.0)));
+    assert!(applied[0].applied_at.is_some());
+    assert_eq!(sell_price(&app).await, Some(2.5));
+
+    let pending: Vec<ScheduledPriceChange> = app.get("/products/scheduled-changes?pending=true").await.json();
+    assert_eq!(pending.iter().map(|c| c.id).collect::<Vec<_>>(), [later.id]);
+    let history: Vec<ScheduledPriceChange> = app.get("/products/scheduled-changes?pending=false&product_id=1").await.json();
+    assert_eq!(history.iter().map(|c| c.id).collect::<Vec<_>>(), [today.id]);
+
+    // opakované spustenie už uplatnenú zmenu nezopakuje
+    let tomorrow = Local::now().date_naive() + Days::new(1);
+    assert!(app.db.apply_due_price_changes(tomorrow).await.unwrap().is_empty());
+    assert_eq!(sell_price(&app).await, Some(2.5));
+
+    let week = Local::now().date_naive() + Days::new(7);
+    let applied = app.db.apply_due_price_changes(week).await.unwrap();
+    assert_eq!((applied.len(), applied[0].old_price), (1, Some(2.5)));
+    assert_eq!(sell_price(&app).await, Some(3.0));
+    let all: Vec<ScheduledPriceChange> = app.get("/products/scheduled-changes").await.json();
+    assert!(all.iter().all(|c| c.applied_at.is_some()));
+}
+
+#[tokio::test]
+async fn invalid_schedules_are_rejected() {
+    let app = spawn_with_product().await;
+    let yesterday = Local::now().date_naive() - Days::new(1);
+    let today = Local::now().date_naive();
+
+    let past = format!(r#"{{"new_price":2.5,"effective_date":"{yesterday}"}}"#);
+    assert_eq!(app.post("/products/1/schedule-price", &past).await.status, 400);
+    let free = format!(r#"{{"new_price":0,"effective_date":"{today}"}}"#);
+    assert_eq!(app.post("/products/1/schedule-price", &free).await.status, 400);
+    let unknown = format!(r#"{{"new_price":2.5,"effective_date":"{today}"}}"#);
+    assert_eq!(app.post("/products/9/schedule-price", &unknown).await.status, 404);
+
+    let changes: Vec<ScheduledPriceChange> = app.get("/products/scheduled-changes").await.json();
+    assert!(changes.is_empty());
+}
@@ -0,0 +1,49 @@
+use store_manager::structs::Seasonality;
+use store_manager::test_support::TestApp;
+
+#[tokio::test]
+async fn averages_units_per_calendar_month_across_years() {
+    let app = TestApp::spawn().await;
+    app.add_product("Zmrzlina", "Mrazené", 1000.0, 1.0, 2.0).await;
+    app.add_product("Hranolky", "Mrazené", 1000.0, 1.0, 2.0).await;
+    // január 2022 až júl 2024: júl sa objaví trikrát, december dvakrát
+    app.sell_at(1, 2.0, Some("2022-01-10T10:00:00")).await;
+    app.sell_at(1, 30.0, Some("2022-07-05T10:00:00")).await;
+    app.sell_at(1, 10.0, Some("2022-07-20T10:00:00")).await;
+    app.sell_at(1, 50.0, Some("2023-07-15T10:00:00")).await;
+    app.sell_at(1, 4.0, Some("2023-12-01T10:00:00")).await;
+    app.sell_at(1, 30.0, Some("2024-07-31T10:00:00")).await;
+    app.sell_at(2, 6.0, Some("2023-12-24T10:00:00")).await;
+
+    let res = app.get("/products/1/seasonality").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: Seasonality = res.json();
+    assert!(report.note.is_none(), "{:?}", report.note);
+    assert_eq!(report.first_sale.unwrap().to_string(), "2022-01-10");
+    assert_eq!(report.last_sale.unwrap().to_string(), "2024-07-31");
+    assert_eq!(report.months.len(), 12);
+
+    let july = &report.months[6];
+    assert_eq!((july.month, july.years, july.units, july.average), (7, 3, 120.0, 40.0));
+    let december = &report.months[11];
+    assert_eq!((december.years, december.units, december.average), (2, 4.0, 2.0));
+    let january = &report.months[0];
+    assert_eq!((january.years, january.average), (3, 2.0 / 3.0));
+    assert!(july.index.unwrap() > 1.0 && december.index.unwrap() < 1.0);
+
+    let category: Seasonality = app.get("/stats/seasonality?category=Mrazené").await.json();
+    assert_eq!(category.months[11].units, 10.0);
+}
+
+#[tokio::test]
+async fn short_history_is_explained() {
+    let app = TestApp::spawn().await;
+    app.add_product("Zmrzlina", "Mrazené", 1000.0, 1.0, 2.0).await;
+    app.sell_at(1, 2.0, Some("2024-01-10T10:00:00")).await;
+    app.sell_at(1, 2.0, Some("2024-11-10T10:00:00")).await;
+
+    let report: Seasonality = app.get("/products/1/seasonality").await.json();
+    assert!(report.months.is_empty());
+    assert!(report.note.unwrap().contains("12 mesiacov"));
+    assert_eq!(app.get("/products/99/seasonality").await.status, 404);
+}
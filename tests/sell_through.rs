@@ -0,0 +1,64 @@
+use sqlx::sqlite::SqlitePool;
+use store_manager::structs::SellThroughReport;
+use store_manager::test_support::TestApp;
+
+async fn report(app: &TestApp, query: &str) -> SellThroughReport {
+    let res = app.get(&format!("/stats/sell-through{query}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn sell_through_divides_net_sales_by_received_units() {
+    let app = TestApp::spawn().await;
+    for body in [
+        r#"{"name":"Kofola","category":"Nápoje","quantity":0,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#,
+        r#"{"name":"Chlieb","category":"Pečivo","quantity":10,"status":true,"bar_code":2,"cost_price":0.5,"sell_price":1.0}"#,
+        r#"{"name":"Rajec","category":"Nápoje","quantity":0,"status":true,"bar_code":3,"cost_price":0.4,"sell_price":0.9}"#,
+    ] {
+        assert_eq!(app.post("/products", body).await.status, 201);
+    }
+
+    // príjem: úprava +20 ks Kofoly a príjemka 10 ks Rajecu
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":20}"#).await.status, 200);
+    assert_eq!(app.post("/products/3/adjust-stock", r#"{"delta":10}"#).await.status, 200);
+    let pool = SqlitePool::connect(&format!("sqlite:{}", app.db_path().display())).await.unwrap();
+    sqlx::query("UPDATE stock_movements SET reason = 'purchase', reference = 'PO-1' WHERE product_id = 3")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // výdaj: predaje, vrátenie a odpis, ktorý sa do miery nezapočíta
+    for order in [
+        r#"{"items":[{"product_id":1,"quantity":8}]}"#,
+        r#"{"items":[{"product_id":3,"quantity":4}]}"#,
+        r#"{"items":[{"product_id":2,"quantity":3}]}"#,
+        r#"{"created_at":"2020-01-10T10:00:00","items":[{"product_id":1,"quantity":5}]}"#,
+    ] {
+        let res = app.post("/orders", order).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+    assert_eq!(app.post("/orders/1/returns", r#"{"items":[{"product_id":1,"quantity":2}]}"#).await.status, 201);
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":-1}"#).await.status, 200);
+
+    let today = chrono::Local::now().date_naive();
+    let report = report(&app, &format!("?from={today}&to={today}")).await;
+    let lines: Vec<_> = report.categories.iter().map(|c| (c.category.as_str(), c.units_sold, c.units_received)).collect();
+    assert_eq!(lines, [("Nápoje", 10.0, 30.0), ("Pečivo", 3.0, 0.0)]);
+    assert!((report.categories[0].rate.unwrap() - 1.0 / 3.0).abs() < 1e-9, "{report:?}");
+    // kategória bez príjmu nemá definovanú mieru
+    assert_eq!(report.categories[1].rate, None);
+    assert_eq!((report.units_sold, report.units_received), (13.0, 30.0));
+    assert!((report.rate.unwrap() - 13.0 / 30.0).abs() < 1e-9, "{report:?}");
+}
+
+#[tokio::test]
+async fn empty_period_has_undefined_rate() {
+    let app = TestApp::spawn().await;
+
+    let report = report(&app, "?from=2024-01-01&to=2024-01-31").await;
+    assert!(report.categories.is_empty());
+    assert_eq!((report.units_sold, report.units_received, report.rate), (0.0, 0.0, None));
+
+    assert_eq!(app.get("/stats/sell-through?from=2024-02-01&to=2024-01-01").await.status, 400);
+}
@@ -0,0 +1,53 @@
+use store_manager::structs::ShiftCoverage;
+use store_manager::test_support::TestApp;
+
+async fn add_employee(app: &TestApp, department: Option<&str>, shift: Option<&str>, status: bool) {
+    let json = |v: Option<&str>| v.map_or("null".to_string(), |v| format!("\"{v}\""));
+    let body = format!(
+        r#"{{"name":"Jana","surname":"Nová","position":"Predavač","department":{},"shift":{},"status":{status}}}"#,
+        json(department),
+        json(shift)
+    );
+    assert_eq!(app.post("/employees", &body).await.status, 201);
+}
+
+#[tokio::test]
+async fn coverage_counts_active_employees_per_department_and_shift() {
+    let app = TestApp::spawn().await;
+    add_employee(&app, Some("Predajňa"), Some("ranná"), true).await;
+    add_employee(&app, Some("Predajňa"), Some("ranná"), true).await;
+    add_employee(&app, Some("Predajňa"), Some("poobedná"), true).await;
+    add_employee(&app, Some("Sklad"), Some("ranná"), true).await;
+    // neaktívny zamestnanec sa nepočíta, jeho zmena ale zostane v matici
+    add_employee(&app, Some("Sklad"), Some("nočná"), false).await;
+    add_employee(&app, Some("Sklad"), None, true).await;
+    add_employee(&app, None, Some("poobedná"), true).await;
+
+    let res = app.get("/stats/shift-coverage").await;
+    assert_eq!(res.status, 200);
+    let coverage: Vec<ShiftCoverage> = res.json();
+
+    let departments: Vec<_> = coverage.iter().map(|c| c.department.as_deref()).collect();
+    assert_eq!(departments, [Some("Predajňa"), Some("Sklad"), None]);
+    for line in &coverage {
+        let shifts: Vec<_> = line.shifts.keys().map(String::as_str).collect();
+        assert_eq!(shifts, ["nočná", "poobedná", "ranná"]);
+    }
+
+    let shop = &coverage[0];
+    assert_eq!((shop.shifts["ranná"], shop.shifts["poobedná"], shop.unassigned), (2, 1, 0));
+    assert_eq!(shop.uncovered, ["nočná"]);
+
+    let store = &coverage[1];
+    assert_eq!((store.shifts["ranná"], store.shifts["nočná"], store.unassigned), (1, 0, 1));
+    assert_eq!(store.uncovered, ["nočná", "poobedná"]);
+
+    assert_eq!(coverage[2].uncovered, ["nočná", "ranná"]);
+}
+
+#[tokio::test]
+async fn coverage_is_empty_without_employees() {
+    let app = TestApp::spawn().await;
+    let coverage: Vec<ShiftCoverage> = app.get("/stats/shift-coverage").await.json();
+    assert!(coverage.is_empty());
+}
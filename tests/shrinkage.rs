@@ -0,0 +1,53 @@
+use chrono::Local;
+use store_manager::structs::ShrinkageReport;
+use store_manager::test_support::TestApp;
+
+async fn adjust(app: &TestApp, product_id: u32, delta: f64) {
+    let res = app.post(&format!("/products/{product_id}/adjust-stock"), &format!(r#"{{"delta":{delta}}}"#)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+}
+
+async fn shrinkage(app: &TestApp) -> ShrinkageReport {
+    let today = Local::now().date_naive();
+    let res = app.get(&format!("/stats/shrinkage?from={today}&to={today}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn negative_adjustments_are_losses_and_sales_are_not() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 20.0, 2.0, 10.0).await;
+    app.add_product("Rožok", "Pečivo", 20.0, 0.5, 10.0).await;
+
+    // predaj znižuje zásoby, ale nie je stratou
+    let order = r#"{"items":[{"product_id":1,"quantity":6}]}"#;
+    assert_eq!(app.post("/orders", order).await.status, 201);
+    adjust(&app, 1, -2.0).await;
+    adjust(&app, 1, 5.0).await;
+    adjust(&app, 2, -4.0).await;
+
+    let report = shrinkage(&app).await;
+    assert_eq!((report.units_lost, report.value_lost, report.units_sold), (6.0, 6.0, 6.0));
+    assert_eq!((report.rate, report.uncosted_units), (0.5, 0.0));
+
+    let drinks = &report.categories[0];
+    assert_eq!((drinks.category.as_str(), drinks.units_lost, drinks.value_lost), ("Nápoje", 2.0, 4.0));
+    assert_eq!((drinks.units_sold, drinks.rate), (6.0, 0.25));
+    let bakery = &report.categories[1];
+    assert_eq!((bakery.units_lost, bakery.value_lost, bakery.rate), (4.0, 2.0, 1.0));
+}
+
+#[tokio::test]
+async fn period_without_losses_is_zero() {
+    let app = TestApp::spawn().await;
+    let report = shrinkage(&app).await;
+    assert_eq!((report.units_lost, report.value_lost, report.rate), (0.0, 0.0, 0.0));
+    assert!(report.categories.is_empty());
+
+    app.add_product("Kofola", "Nápoje", 20.0, 2.0, 10.0).await;
+    adjust(&app, 1, -2.0).await;
+    let report: ShrinkageReport = app.get("/stats/shrinkage?from=2024-01-01&to=2024-01-31").await.json();
+    assert_eq!(report.value_lost, 0.0);
+    assert_eq!(app.get("/stats/shrinkage?from=2024-02-01&to=2024-01-01").await.status, 400);
+}
@@ -0,0 +1,41 @@
+use store_manager::structs::Product;
+use store_manager::test_support::TestApp;
+
+fn product(name: &str, category: &str, bar_code: i64, sku: Option<&str>) -> String {
+    let sku = sku.map(|s| format!(r#","sku":"{s}""#)).unwrap_or_default();
+    format!(
+        r#"{{"name":"{name}","category":"{category}","quantity":0,"bar_code":{bar_code},"cost_price":1.0,"sell_price":2.0{sku}}}"#
+    )
+}
+
+#[tokio::test]
+async fn sku_is_generated_in_category_sequence() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Kofola", "Nápoje", 1, Some(" drk-0041 "))).await.status, 201);
+    assert_eq!(app.post("/products", &product("Voda", "Nápoje", 2, None)).await.status, 201);
+    assert_eq!(app.post("/products", &product("Rožok", "Pečivo", 3, None)).await.status, 201);
+
+    let skus: Vec<Option<String>> =
+        app.get("/products").await.json::<Vec<Product>>().into_iter().map(|p| p.sku).collect();
+    assert_eq!(skus, [Some("DRK-0041".into()), Some("DRK-0042".into()), Some("PEC-0001".into())]);
+
+    let res = app.get("/products/sku/drk-0042").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<Product>().name.as_deref(), Some("Voda"));
+    assert_eq!(app.get("/products/sku/DRK-9999").await.status, 404);
+
+    let found: Vec<Product> = app.get("/products?sku=drk").await.json();
+    assert_eq!(found.len(), 2);
+}
+
+#[tokio::test]
+async fn sku_rejects_bad_format_and_duplicates() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", &product("Kofola", "Nápoje", 1, Some("DRINK-1"))).await.status, 400);
+    assert_eq!(app.post("/products", &product("Kofola", "Nápoje", 1, Some("DRK-0001"))).await.status, 201);
+    assert_eq!(app.post("/products", &product("Voda", "Nápoje", 2, Some("DRK-0001"))).await.status, 409);
+    assert_eq!(app.post("/products", &product("Voda", "Nápoje", 2, None)).await.status, 201);
+    assert_eq!(app.put("/products/2", r#"{"sku":"DRK-0001"}"#).await.status, 409);
+    assert_eq!(app.put("/products/2", r#"{"sku":"DRK-0100"}"#).await.status, 200);
+    assert_eq!(app.db.get_product(2).await.unwrap().unwrap().sku.as_deref(), Some("DRK-0100"));
+}
@@ -0,0 +1,33 @@
+use std::time::Duration;
+
+use store_manager::config::{Config, DEFAULT_SLOW_QUERY_THRESHOLD};
+use store_manager::slow_query;
+use store_manager::test_support::TestApp;
+
+#[test]
+fn parameters_are_counted_outside_quotes() {
+    assert_eq!(slow_query::parameter_count("SELECT * FROM products WHERE id = ? AND name = ?"), 2);
+    assert_eq!(slow_query::parameter_count("SELECT '?' FROM \"a?b\" WHERE id = ?1"), 1);
+    assert_eq!(slow_query::parameter_count("SELECT 1"), 0);
+    assert_eq!(Config::default().slow_query_threshold, DEFAULT_SLOW_QUERY_THRESHOLD);
+    assert_eq!(DEFAULT_SLOW_QUERY_THRESHOLD, 200);
+}
+
+#[tokio::test]
+async fn slow_statements_are_counted_per_method() {
+    slow_query::init();
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Kofola","category":"Nápoje","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    assert!(!slow_query::counts().contains_key("get_product"));
+
+    // nulový prah označí každý príkaz za pomalý
+    app.db.set_slow_query_threshold(Duration::ZERO);
+    assert_eq!(app.get("/products/1").await.status, 200);
+    let counts = slow_query::counts();
+    assert!(counts["get_product"] >= 1, "{counts:?}");
+
+    let metrics = app.get("/metrics").await.text();
+    assert!(metrics.contains("# TYPE store_db_slow_queries_total counter"), "{metrics}");
+    assert!(metrics.contains("store_db_slow_queries_total{method=\"get_product\"}"), "{metrics}");
+}
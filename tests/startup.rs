@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use store_manager::config::Config;
+use store_manager::db::StoreDB;
+use store_manager::health::Readiness;
+use store_manager::startup;
+use store_manager::structs::Product;
+
+const DATA: &str = r#"{"employees":[],"products":[{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}]}"#;
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("startup-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+async fn fresh_db(name: &str) -> StoreDB {
+    StoreDB::open(temp_path(name).to_str().unwrap()).await.unwrap()
+}
+
+#[tokio::test]
+async fn disabled_json_sync_never_reads_or_writes_json() {
+    let db = fresh_db("off.db").await;
+    let json = temp_path("off.json");
+    std::fs::write(&json, DATA).unwrap();
+    let modified = std::fs::metadata(&json).unwrap().modified().unwrap();
+    let config = Config { json_sync: false, json_path: json.to_str().unwrap().into(), ..Config::default() };
+
+    let readiness = Readiness::starting();
+    startup::prepare(&db, &config, false, &readiness).await;
+    assert!(readiness.is_ready());
+    assert!(db.get_products(Product::new_empty()).await.unwrap().is_empty());
+
+    startup::shutdown(&db, &config).await;
+    assert_eq!(std::fs::read_to_string(&json).unwrap(), DATA);
+    assert_eq!(std::fs::metadata(&json).unwrap().modified().unwrap(), modified);
+
+    // neexistujúci súbor sa pri vypnutí nevytvorí
+    let missing = temp_path("missing.json");
+    let config = Config { json_path: missing.to_str().unwrap().into(), ..config };
+    startup::shutdown(&db, &config).await;
+    assert!(!missing.exists());
+}
+
+#[tokio::test]
+async fn enabled_json_sync_imports_new_database_and_saves_on_shutdown() {
+    let db = fresh_db("on.db").await;
+    let json = temp_path("on.json");
+    std::fs::write(&json, DATA).unwrap();
+    let config = Config { json_path: json.to_str().unwrap().into(), ..Config::default() };
+    assert!(config.json_sync);
+
+    startup::prepare(&db, &config, false, &Readiness::starting()).await;
+    assert_eq!(db.get_products(Product::new_empty()).await.unwrap().len(), 1);
+
+    std::fs::remove_file(&json).unwrap();
+    startup::shutdown(&db, &config).await;
+    assert!(std::fs::read_to_string(&json).unwrap().contains("Chlieb"));
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use store_manager::config::Config;
+use store_manager::scheduler;
+use store_manager::structs::StatsSnapshot;
+use store_manager::test_support::TestApp;
+
+const KEY: &str = "tajny-kluc";
+
+async fn snapshot(app: &TestApp) -> StatsSnapshot {
+    let res = app.request_with_headers("POST", "/admin/stats/snapshot", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn two_snapshots_yield_two_history_rows() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    app.add_product("Kofola", "Nápoje", 10.0, 2.0, 3.0).await;
+    let first = snapshot(&app).await;
+    assert_eq!((first.products, first.total_quantity, first.inventory_value), (1, 10.0, 20.0));
+
+    app.add_product("Rožok", "Pečivo", 5.0, 2.0, 3.0).await;
+    snapshot(&app).await;
+
+    let history: Vec<StatsSnapshot> = app.get("/stats/history").await.json();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].id, first.id);
+    assert_eq!(history[1].products, 2);
+    assert_eq!(history[1].inventory_value, 30.0);
+    assert_eq!(history[1].categories.get("Pečivo"), Some(&1));
+    assert!(history[0].taken_at <= history[1].taken_at);
+
+    let latest: Vec<StatsSnapshot> = app.get("/stats/history?limit=1").await.json();
+    assert_eq!(latest.len(), 1);
+    assert_eq!(latest[0].id, history[1].id);
+    assert_eq!(app.get("/stats/history?from=2024-02-01&to=2024-01-01").await.status, 400);
+}
+
+#[tokio::test]
+async fn scheduler_takes_snapshots_periodically() {
+    let app = TestApp::spawn().await;
+    let handle = scheduler::spawn_stats_snapshots(app.db.clone(), Duration::from_millis(20)).unwrap();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    handle.abort();
+
+    let history = app.db.stats_history(None, None, 100).await.unwrap();
+    assert!(history.len() >= 2, "{} snímok", history.len());
+    assert!(scheduler::spawn_stats_snapshots(app.db.clone(), Duration::ZERO).is_none());
+}
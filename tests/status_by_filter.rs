@@ -0,0 +1,58 @@
+use chrono::Local;
+use serde_json::json;
+use store_manager::structs::{Product, StatusChangeReport};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (bar_code, category) in [(1, "Sezónne"), (2, "Sezónne"), (3, "Potraviny")] {
+        let body = format!(r#"{{"name":"Produkt {bar_code}","category":"{category}","quantity":5,"status":true,"bar_code":{bar_code},"cost_price":0.05,"sell_price":1.0}}"#);
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+async fn product(app: &TestApp, id: u32) -> Product {
+    app.get(&format!("/products/{id}")).await.json()
+}
+
+#[tokio::test]
+async fn only_matching_products_change() {
+    let app = spawn_with_products().await;
+    let request = |active: bool| json!({"filter": {"category": "Sezónne"}, "active": active}).to_string();
+
+    let res = app.post("/products/set-status-by-filter", &request(false)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: StatusChangeReport = res.json();
+    assert_eq!(report, StatusChangeReport { matched: 2, changed: 2, ids: vec![1, 2] });
+    for id in [1, 2] {
+        let p = product(&app, id).await;
+        assert_eq!((p.status, p.date_remove), (Some(false), Some(Local::now().date_naive())));
+    }
+    let other = product(&app, 3).await;
+    assert_eq!((other.status, other.date_remove), (Some(true), None));
+
+    // opakované vyradenie nič nemení
+    let report: StatusChangeReport = app.post("/products/set-status-by-filter", &request(false)).await.json();
+    assert_eq!((report.matched, report.changed), (2, 0));
+
+    let report: StatusChangeReport = app.post("/products/set-status-by-filter", &request(true)).await.json();
+    assert_eq!(report.ids, [1, 2]);
+    let restored = product(&app, 1).await;
+    assert_eq!((restored.status, restored.date_remove), (Some(true), None));
+
+    let audit = app.db.get_audit_log().await.unwrap();
+    assert_eq!(audit[0].action, "set_status_by_filter");
+    assert_eq!(audit[0].details.as_deref(), Some("active=true, changed=2, ids=1,2"));
+    assert_eq!(audit.iter().filter(|e| e.action == "set_status_by_filter").count(), 3);
+}
+
+#[tokio::test]
+async fn empty_filter_is_rejected() {
+    let app = spawn_with_products().await;
+    let res = app.post("/products/set-status-by-filter", r#"{"active":false}"#).await;
+    assert_eq!(res.status, 400);
+    let res = app.post("/products/set-status-by-filter", r#"{"filter":{},"active":false}"#).await;
+    assert_eq!(res.status, 400);
+    assert_eq!(product(&app, 1).await.status, Some(true));
+}
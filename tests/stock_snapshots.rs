@@ -0,0 +1,49 @@
+use store_manager::config::Config;
+use store_manager::structs::{StockDeltaReport, StockSnapshot};
+use store_manager::test_support::TestApp;
+
+const KEY: &str = "tajny-kluc";
+
+async fn add_product(app: &TestApp, name: &str, bar_code: u64, quantity: u32) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":{bar_code},"cost_price":2.0,"sell_price":3.0}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+async fn snapshot(app: &TestApp, label: &str) -> StockSnapshot {
+    let uri = format!("/admin/stock-snapshot?label={label}");
+    let res = app.request_with_headers("POST", &uri, None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn delta_between_snapshots_reports_changed_products() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    add_product(&app, "Kofola", 1, 10).await;
+    add_product(&app, "Vinea", 2, 4).await;
+    let before = snapshot(&app, "pred").await;
+    assert_eq!((before.label.as_str(), before.products), ("pred", 2));
+
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":-3}"#).await.status, 200);
+    add_product(&app, "Rajec", 3, 6).await;
+    let after = snapshot(&app, "po").await;
+
+    let uri = format!("/stats/stock-delta?from_snapshot={}&to_snapshot={}", before.id, after.id);
+    let res = app.get(&uri).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: StockDeltaReport = res.json();
+    assert_eq!((report.from, report.to), (before, after));
+    let changes: Vec<_> = report.products.iter().map(|p| (p.product_id, p.from_quantity, p.to_quantity, p.change)).collect();
+    assert_eq!(changes, vec![(1, Some(10.0), Some(7.0), -3.0), (3, None, Some(6.0), 6.0)]);
+}
+
+#[tokio::test]
+async fn unknown_snapshot_is_not_found() {
+    let app = TestApp::spawn().await;
+    let snapshot = app.db.snapshot_stock(None).await.unwrap();
+    assert!(!snapshot.label.is_empty());
+    assert!(app.db.stock_delta(snapshot.id, snapshot.id).await.unwrap().products.is_empty());
+    assert_eq!(app.get(&format!("/stats/stock-delta?from_snapshot={}&to_snapshot=99", snapshot.id)).await.status, 404);
+}
@@ -0,0 +1,31 @@
+use store_manager::structs::{Product, StockStatus};
+
+const THRESHOLD: u32 = 5;
+
+fn product(quantity: f64, status: Option<bool>) -> Product {
+    Product { quantity: Some(quantity), status, ..Product::new_empty() }
+}
+
+#[test]
+fn zero_quantity_is_out_of_stock() {
+    assert_eq!(product(0.0, Some(true)).stock_status(THRESHOLD), StockStatus::OutOfStock);
+    assert_eq!(Product { quantity: None, ..product(0.0, Some(true)) }.stock_status(THRESHOLD), StockStatus::OutOfStock);
+}
+
+#[test]
+fn quantity_at_threshold_is_low_stock() {
+    assert_eq!(product(f64::from(THRESHOLD), Some(true)).stock_status(THRESHOLD), StockStatus::LowStock);
+    assert_eq!(product(1.0, None).stock_status(THRESHOLD), StockStatus::LowStock);
+}
+
+#[test]
+fn quantity_above_threshold_is_in_stock() {
+    assert_eq!(product(f64::from(THRESHOLD + 1), Some(true)).stock_status(THRESHOLD), StockStatus::InStock);
+}
+
+#[test]
+fn inactive_product_is_discontinued_regardless_of_quantity() {
+    for quantity in [0.0, 1.0, 100.0] {
+        assert_eq!(product(quantity, Some(false)).stock_status(THRESHOLD), StockStatus::Discontinued);
+    }
+}
@@ -0,0 +1,81 @@
+use store_manager::structs::{Product, ShrinkageReport, StockMovement, StocktakeReport};
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for body in [
+        r#"{"name":"Kofola","category":"Nápoje","quantity":10,"status":true,"bar_code":1,"cost_price":1.5,"sell_price":2.0}"#,
+        r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":2,"cost_price":0.8,"sell_price":1.2}"#,
+        r#"{"name":"Jablká","category":"Ovocie","quantity":4,"unit":"kg","status":true,"bar_code":3,"cost_price":1.0,"sell_price":1.9}"#,
+    ] {
+        assert_eq!(app.post("/products", body).await.status, 201);
+    }
+    app
+}
+
+async fn reconcile(app: &TestApp, query: &str, body: &str) -> StocktakeReport {
+    let res = app.post(&format!("/products/reconcile{query}"), body).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+async fn quantity(app: &TestApp, id: u32) -> Option<f64> {
+    let product: Product = app.get(&format!("/products/{id}")).await.json();
+    product.quantity
+}
+
+#[tokio::test]
+async fn matching_counts_report_no_discrepancies() {
+    let app = spawn_with_products().await;
+
+    let report = reconcile(&app, "?apply=true", r#"[{"id":1,"counted":10},{"id":2,"counted":5}]"#).await;
+    assert_eq!((report.counted, report.matched), (2, 2));
+    assert!(report.discrepancies.is_empty());
+    assert_eq!((report.shrinkage_units, report.shrinkage_value), (0.0, 0.0));
+
+    let movements: Vec<StockMovement> = app.get("/products/1/movements").await.json();
+    assert!(movements.is_empty());
+}
+
+#[tokio::test]
+async fn mismatching_counts_report_shrinkage_and_adjust_when_applied() {
+    let app = spawn_with_products().await;
+    let counts = r#"[{"id":1,"counted":8},{"id":2,"counted":6},{"id":3,"counted":3.5}]"#;
+
+    // bez `apply` sa len vypočíta správa
+    let preview = reconcile(&app, "", counts).await;
+    assert!(!preview.applied);
+    assert_eq!(quantity(&app, 1).await, Some(10.0));
+
+    let report = reconcile(&app, "?apply=true", counts).await;
+    assert!(report.applied);
+    assert_eq!(report.matched, 0);
+    let lines: Vec<_> = report.discrepancies.iter().map(|d| (d.product_id, d.recorded, d.counted, d.difference)).collect();
+    assert_eq!(lines, [(1, 10.0, 8.0, -2.0), (2, 5.0, 6.0, 1.0), (3, 4.0, 3.5, -0.5)]);
+    assert_eq!(report.discrepancies[1].value, Some(0.8));
+    assert_eq!(report.discrepancies[2].value, Some(-0.5));
+    assert!((report.shrinkage_units - 2.5).abs() < 1e-9, "{report:?}");
+    assert!((report.shrinkage_value - 3.5).abs() < 1e-9, "{report:?}");
+    assert_eq!(report.uncosted_lines, 0);
+
+    assert_eq!((quantity(&app, 1).await, quantity(&app, 2).await), (Some(8.0), Some(6.0)));
+    let movements: Vec<StockMovement> = app.get("/products/1/movements").await.json();
+    assert_eq!(movements.len(), 1);
+    assert_eq!((movements[0].reason.as_str(), movements[0].quantity), ("stocktake", -2.0));
+
+    let today = chrono::Local::now().date_naive();
+    let shrinkage: ShrinkageReport = app.get(&format!("/stats/shrinkage?from={today}&to={today}")).await.json();
+    assert!((shrinkage.value_lost - 3.5).abs() < 1e-9, "{shrinkage:?}");
+}
+
+#[tokio::test]
+async fn invalid_counts_change_nothing() {
+    let app = spawn_with_products().await;
+
+    assert_eq!(app.post("/products/reconcile?apply=true", "[]").await.status, 400);
+    assert_eq!(app.post("/products/reconcile?apply=true", r#"[{"id":1,"counted":8},{"id":9,"counted":1}]"#).await.status, 404);
+    assert_eq!(app.post("/products/reconcile?apply=true", r#"[{"id":1,"counted":8},{"id":1,"counted":7}]"#).await.status, 400);
+    assert_eq!(app.post("/products/reconcile?apply=true", r#"[{"id":1,"counted":8},{"id":2,"counted":-1}]"#).await.status, 400);
+    assert_eq!(app.post("/products/reconcile?apply=true", r#"[{"id":1,"counted":8},{"id":2,"counted":1.5}]"#).await.status, 400);
+    assert_eq!(quantity(&app, 1).await, Some(10.0));
+}
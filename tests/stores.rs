@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use store_manager::config::Config;
+use store_manager::structs::Product;
+use store_manager::test_support::{TestApp, TestResponse};
+
+const ADMIN: &str = "admin-kluc";
+const EDITOR: &str = "editor-kluc";
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("stores-{name}-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path.to_str().unwrap().into()
+}
+
+async fn spawn_two_stores(name: &str) -> TestApp {
+    let stores = BTreeMap::from([("pobocka".to_string(), temp_path(name))]);
+    let config = Config {
+        admin_api_key: Some(ADMIN.into()),
+        editor_api_key: Some(EDITOR.into()),
+        stores,
+        ..Config::default()
+    };
+    TestApp::spawn_with_config(config).await
+}
+
+async fn send(app: &TestApp, key: &str, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-api-key", key)]).await
+}
+
+fn product(name: &str) -> String {
+    format!(r#"{{"name":"{name}","category":"Nápoje","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":1.5}}"#)
+}
+
+fn names(res: TestResponse) -> Vec<String> {
+    res.json::<Vec<Product>>().into_iter().map(|p| p.name.unwrap()).collect()
+}
+
+#[tokio::test]
+async fn each_store_has_its_own_database() {
+    let app = spawn_two_stores("data").await;
+    assert_eq!(send(&app, ADMIN, "GET", "/stores", None).await.json::<Vec<String>>(), ["pobocka"]);
+
+    assert_eq!(send(&app, EDITOR, "POST", "/products", Some(&product("Kofola"))).await.status, 201);
+    for name in ["Vinea", "Rajec"] {
+        let res = send(&app, EDITOR, "POST", "/stores/pobocka/products", Some(&product(name))).await;
+        assert_eq!(res.status, 201, "{}", res.text());
+    }
+
+    assert_eq!(names(send(&app, EDITOR, "GET", "/products", None).await), ["Kofola"]);
+    assert_eq!(names(send(&app, EDITOR, "GET", "/stores/pobocka/products", None).await), ["Vinea", "Rajec"]);
+    assert_eq!(names(send(&app, EDITOR, "GET", "/stores/pobocka/products?name=Raj", None).await), ["Rajec"]);
+    assert_eq!(send(&app, EDITOR, "GET", "/stores/pobocka/products/1", None).await.json::<Product>().name.unwrap(), "Vinea");
+
+    // stránkovanie odkazuje na cesty obchodu
+    let res = send(&app, EDITOR, "GET", "/stores/pobocka/products?limit=1", None).await;
+    assert!(res.header("link").unwrap().contains("/stores/pobocka/products?"), "{:?}", res.header("link"));
+
+    let res = send(&app, EDITOR, "GET", "/stores/iny/products", None).await;
+    assert_eq!(res.status, 404);
+    assert!(res.text().contains("iny"), "{}", res.text());
+}
+
+#[tokio::test]
+async fn store_routes_keep_authorization_and_modes() {
+    let app = spawn_two_stores("auth").await;
+
+    assert_eq!(send(&app, "zly", "GET", "/stores/pobocka/products", None).await.status, 401);
+    assert_eq!(send(&app, EDITOR, "POST", "/stores/pobocka/admin/stats/snapshot", None).await.status, 403);
+    assert_eq!(send(&app, ADMIN, "POST", "/stores/pobocka/admin/stats/snapshot", None).await.status, 201);
+
+    // režim len na čítanie sa prepína pre každý obchod zvlášť
+    let res = send(&app, ADMIN, "POST", "/stores/pobocka/admin/read-only", Some(r#"{"enabled":true}"#)).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(send(&app, EDITOR, "POST", "/stores/pobocka/products", Some(&product("Vinea"))).await.status, 503);
+    assert_eq!(send(&app, EDITOR, "POST", "/products", Some(&product("Kofola"))).await.status, 201);
+    assert!(!app.db.is_read_only());
+}
+
+#[test]
+fn store_config_is_validated() {
+    let config = |stores: &[(&str, &str)]| Config {
+        stores: stores.iter().map(|(id, path)| (id.to_string(), path.to_string())).collect(),
+        ..Config::default()
+    };
+    assert!(config(&[("pobocka1", "a.db"), ("pobocka_2", "b.db")]).validate().is_ok());
+    assert!(config(&[("po/bocka", "a.db")]).validate().is_err());
+    assert!(config(&[("pobocka", " ")]).validate().is_err());
+    assert!(config(&[("a", "x.db"), ("b", "x.db")]).validate().is_err());
+    assert!(config(&[("hlavny", "store.db")]).validate().is_err());
+
+    let mut env = Config::default();
+    env.apply_overrides(|key| (key == "STORE_STORES").then(|| "a=a.db, b=b.db".to_string())).unwrap();
+    assert_eq!(env.stores["b"], "b.db");
+}
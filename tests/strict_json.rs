@@ -0,0 +1,75 @@
+use serde_json::json;
+use store_manager::config::Config;
+use store_manager::structs::{JsonBodyError, Product};
+use store_manager::test_support::TestApp;
+
+const PRODUCT: &str = r#"{"name":"Chlieb","category":"Pečivo","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}"#;
+
+#[tokio::test]
+async fn unknown_field_is_rejected() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", PRODUCT).await.status, 201);
+
+    let res = app.put("/products/1", r#"{"sell_pricce":3.5,"colour":"red"}"#).await;
+    assert_eq!(res.status, 422);
+    let error: JsonBodyError = res.json();
+    assert_eq!(error.error, "unknown_field");
+    assert_eq!(error.unknown_fields, ["colour", "sell_pricce"]);
+    assert!(error.message.contains("`sell_pricce`"), "{}", error.message);
+
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.sell_price, Some(2.0));
+}
+
+#[tokio::test]
+async fn type_mismatch_names_field_expected_type_and_value() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", PRODUCT).await.status, 201);
+
+    let res = app.put("/products/1", r#"{"sell_price":"drahé"}"#).await;
+    assert_eq!(res.status, 422);
+    let error: JsonBodyError = res.json();
+    assert_eq!(error.error, "invalid_value");
+    assert_eq!(error.field.as_deref(), Some("sell_price"));
+    assert_eq!(error.expected.as_deref(), Some("f64"));
+    assert_eq!(error.received, Some(json!("drahé")));
+    assert!(error.message.contains("sell_price"), "{}", error.message);
+}
+
+#[tokio::test]
+async fn nested_and_missing_fields_are_reported_with_path() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", PRODUCT).await.status, 201);
+
+    let res = app.put("/products/1/components", r#"[{"component_product_id":1,"quantity":"dva"}]"#).await;
+    assert_eq!(res.status, 422);
+    let error: JsonBodyError = res.json();
+    assert_eq!(error.field.as_deref(), Some("[0].quantity"));
+    assert_eq!(error.received, Some(json!("dva")));
+
+    let res = app.post("/products/1/adjust-stock", r#"{"unit":"kg"}"#).await;
+    assert_eq!(res.status, 422);
+    let error: JsonBodyError = res.json();
+    assert_eq!(error.field.as_deref(), Some("delta"));
+    assert_eq!(error.received, None);
+}
+
+#[tokio::test]
+async fn malformed_json_is_still_bad_request() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", r#"{"name":"#).await.status, 400);
+}
+
+#[tokio::test]
+async fn lenient_mode_ignores_unknown_fields() {
+    let app = TestApp::spawn_with_config(Config { strict_json: false, ..Config::default() }).await;
+    assert_eq!(app.post("/products", PRODUCT).await.status, 201);
+
+    let res = app.put("/products/1", r#"{"sell_price":3.5,"legacy_flag":true}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.sell_price, Some(3.5));
+
+    // zlý typ je chybou aj v tolerantnom režime
+    assert_eq!(app.put("/products/1", r#"{"sell_price":"x"}"#).await.status, 422);
+}
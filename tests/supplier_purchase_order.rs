@@ -0,0 +1,50 @@
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, quantity: u32, supplier: &str) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":{quantity},"status":true,"bar_code":1,"cost_price":1.5,"sell_price":2.0,"min_stock":10,"supplier":"{supplier}"}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+async fn seed() -> TestApp {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Kofola", 2, "Kofola a.s.").await;
+    add_product(&app, "Vinea, biela", 0, "Kofola a.s.").await;
+    add_product(&app, "Rajec", 20, "Kofola a.s.").await;
+    add_product(&app, "Tonik", 30, "Rauch").await;
+    app
+}
+
+#[tokio::test]
+async fn pdf_purchase_order_for_supplier_with_low_stock() {
+    let app = seed().await;
+    let res = app.post("/suppliers/Kofola%20a.s./purchase-order", "").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("application/pdf"));
+    let disposition = res.header("content-disposition").unwrap();
+    assert!(disposition.starts_with("attachment; filename=\"purchase-order-Kofola-a-s--"), "{disposition}");
+    assert!(res.body.starts_with(b"%PDF"));
+    assert!(res.body.len() > 500);
+}
+
+#[tokio::test]
+async fn csv_purchase_order_lists_lines_and_totals() {
+    let app = seed().await;
+    let res = app.post("/suppliers/Kofola%20a.s./purchase-order?format=csv", "").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.header("content-type"), Some("text/csv; charset=utf-8"));
+    assert!(res.header("content-disposition").unwrap().ends_with(".csv\""));
+    assert_eq!(
+        res.text(),
+        "product_id,name,quantity,unit,unit_cost,total\r\n2,\"Vinea, biela\",10,piece,1.50,15.00\r\n1,Kofola,8,piece,1.50,12.00\r\n"
+    );
+}
+
+#[tokio::test]
+async fn nothing_to_reorder_is_unprocessable() {
+    let app = seed().await;
+    assert_eq!(app.post("/suppliers/Rauch/purchase-order", "").await.status, 422);
+    assert_eq!(app.post("/suppliers/Nikto/purchase-order?format=csv", "").await.status, 422);
+    assert_eq!(app.post("/suppliers/Rauch/purchase-order?format=xml", "").await.status, 400);
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use store_manager::config::{Config, SupplierScoreWeights};
+use store_manager::structs::SupplierScore;
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, supplier: &str, quantity: u32) {
+    let body = format!(
+        r#"{{"name":"{name}","category":"Potraviny","quantity":{quantity},"status":true,
+            "bar_code":1,"cost_price":1.0,"sell_price":2.0,"supplier":"{supplier}"}}"#
+    );
+    assert_eq!(app.post("/products", &body).await.status, 201);
+}
+
+/// Vytvorí, odošle a celú prijme objednávku s 5 ks produktu.
+async fn deliver(app: &TestApp, supplier: &str, product_id: u32, unit_cost: f64, expected_date: &str) {
+    let po = format!(
+        r#"{{"supplier":"{supplier}","expected_date":"{expected_date}",
+            "items":[{{"product_id":{product_id},"quantity":5,"unit_cost":{unit_cost}}}]}}"#
+    );
+    let res = app.post("/purchase-orders", &po).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let id: u32 = res.json();
+    assert_eq!(app.put(&format!("/purchase-orders/{id}"), r#"{"status":"sent"}"#).await.status, 200);
+    let receipt = format!(r#"{{"items":[{{"product_id":{product_id},"received_quantity":5}}]}}"#);
+    let res = app.post(&format!("/purchase-orders/{id}/receive"), &receipt).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+}
+
+async fn sell(app: &TestApp, product_id: u32, quantity: u32) {
+    let body = format!(r#"{{"items":[{{"product_id":{product_id},"quantity":{quantity}}}]}}"#);
+    let res = app.post("/orders", &body).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+}
+
+/// Rajo dodáva načas za stálu cenu a tovar nedôjde; Tatra mešká, cena
+/// kolíše medzi 1 a 2 (variačný koeficient 1/3) a maslo sa vypredá.
+async fn spawn_with_history() -> TestApp {
+    let app = TestApp::spawn().await;
+    add_product(&app, "Mlieko", "Rajo", 10).await;
+    add_product(&app, "Maslo", "Tatra", 10).await;
+    for _ in 0..2 {
+        deliver(&app, "Rajo", 1, 1.0, "2999-01-01").await;
+    }
+    deliver(&app, "Tatra", 2, 1.0, "2000-01-01").await;
+    deliver(&app, "Tatra", 2, 2.0, "2000-01-01").await;
+    sell(&app, 1, 5).await;
+    sell(&app, 2, 5).await;
+    sell(&app, 2, 15).await;
+    app
+}
+
+fn close(actual: Option<f64>, expected: f64) -> bool {
+    actual.is_some_and(|v| (v - expected).abs() < 1e-9)
+}
+
+#[tokio::test]
+async fn scores_combine_punctuality_price_stability_and_availability() {
+    let app = spawn_with_history().await;
+
+    let res = app.get("/stats/supplier-scores").await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let scores: Vec<SupplierScore> = res.json();
+    let names: Vec<_> = scores.iter().map(|s| s.supplier.as_str()).collect();
+    assert_eq!(names, ["Rajo", "Tatra"]);
+
+    let rajo = &scores[0];
+    assert_eq!((rajo.deliveries, rajo.on_time_deliveries), (2, 2));
+    assert_eq!((rajo.outgoing, rajo.stock_outs), (1, 0));
+    assert!(close(rajo.on_time_rate, 1.0) && close(rajo.price_stability, 1.0) && close(rajo.availability, 1.0));
+    assert!(close(rajo.score, 1.0), "{rajo:?}");
+
+    let tatra = &scores[1];
+    assert_eq!((tatra.deliveries, tatra.on_time_deliveries), (2, 0));
+    assert_eq!((tatra.outgoing, tatra.stock_outs), (2, 1));
+    assert!(close(tatra.on_time_rate, 0.0), "{tatra:?}");
+    assert!(close(tatra.price_stability, 2.0 / 3.0), "{tatra:?}");
+    assert!(close(tatra.availability, 0.5), "{tatra:?}");
+    // 0.4 · 0 + 0.3 · 2/3 + 0.3 · 0.5
+    assert!(close(tatra.score, 0.35), "{tatra:?}");
+}
+
+#[tokio::test]
+async fn weights_change_the_score_and_missing_parts_are_skipped() {
+    let app = spawn_with_history().await;
+    add_product(&app, "Jogurt", "Zvolenská", 3).await;
+    sell(&app, 3, 1).await;
+
+    let prices_only = SupplierScoreWeights { on_time: 0.0, price_stability: 1.0, availability: 0.0 };
+    let scores = app.db.supplier_scores(&prices_only).await.unwrap();
+    let ranked: Vec<_> = scores.iter().map(|s| (s.supplier.as_str(), s.score)).collect();
+    assert!(close(ranked[0].1, 1.0) && close(ranked[1].1, 2.0 / 3.0), "{ranked:?}");
+    // Zvolenská nemá objednávky, takže jej chýba zložka s nenulovou váhou
+    assert_eq!(ranked[2], ("Zvolenská", None));
+
+    let scores = app.db.supplier_scores(&SupplierScoreWeights::default()).await.unwrap();
+    let zvolenska = scores.iter().find(|s| s.supplier == "Zvolenská").unwrap();
+    assert_eq!((zvolenska.deliveries, zvolenska.on_time_rate, zvolenska.price_stability), (0, None, None));
+    assert!(close(zvolenska.availability, 1.0) && close(zvolenska.score, 1.0), "{zvolenska:?}");
+}
+
+#[test]
+fn weights_come_from_file_and_env_and_are_validated() {
+    let mut config = Config::from_toml("[supplier_score_weights]\non_time = 2.0").expect("platná konfigurácia");
+    assert_eq!(
+        config.supplier_score_weights,
+        SupplierScoreWeights { on_time: 2.0, ..SupplierScoreWeights::default() }
+    );
+
+    let env: HashMap<&str, &str> = HashMap::from([("STORE_SUPPLIER_WEIGHT_AVAILABILITY", "0")]);
+    config.apply_overrides(|key| env.get(key).map(|v| v.to_string())).expect("platné premenné");
+    assert_eq!(config.supplier_score_weights.availability, 0.0);
+
+    config.validate().expect("platné váhy");
+
+    let negative = Config::from_toml("[supplier_score_weights]\nprice_stability = -1.0").unwrap();
+    let err = negative.validate().unwrap_err().to_string();
+    assert!(err.contains("supplier_score_weights.price_stability"), "{err}");
+    let zero = Config::from_toml("[supplier_score_weights]\non_time = 0\nprice_stability = 0\navailability = 0").unwrap();
+    let err = zero.validate().unwrap_err().to_string();
+    assert!(err.contains("aspoň jedna váha"), "{err}");
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use store_manager::config::Config;
+use store_manager::structs::{Employee, Order, Product, SyncReport};
+use store_manager::sync;
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "kluc-pobocky";
+
+/// Pobočka s vlastnou inštanciou chránenou API kľúčom.
+async fn spawn_shop() -> TestApp {
+    let shop = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    for body in [
+        r#"{"name":"Jana","surname":"Nová","position":"Predavač","status":true,"email":"jana@shop2.sk"}"#,
+        r#"{"name":"Peter","surname":"Bez emailu","position":"Skladník","status":true}"#,
+    ] {
+        assert_eq!(send(&shop, "POST", "/employees", body).await.status, 201);
+    }
+    for (name, bar_code) in [("Kofola", 100), ("Chlieb", 200)] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":5,"status":true,"bar_code":{bar_code},"cost_price":1.0,"sell_price":1.5}}"#
+        );
+        assert_eq!(send(&shop, "POST", "/products", &body).await.status, 201);
+    }
+    let sale = r#"{"employee_id":1,"items":[{"product_id":1,"quantity":2}]}"#;
+    assert_eq!(send(&shop, "POST", "/orders", sale).await.status, 201);
+    shop
+}
+
+async fn send(app: &TestApp, method: &str, path: &str, body: &str) -> TestResponse {
+    app.request_with_headers(method, path, Some(body), &[("x-api-key", KEY)]).await
+}
+
+async fn pull(office: &TestApp, shop: &TestApp) -> SyncReport {
+    sync::pull(&office.db, &format!("http://{}/", shop.addr), Some(KEY)).await.unwrap()
+}
+
+async fn product_by_code(office: &TestApp, bar_code: i64, source: bool) -> Product {
+    let products: Vec<Product> = office.get(&format!("/products?bar_code={bar_code}")).await.json();
+    // miestny produkt bol pridaný skôr, má nižšie ID
+    let mut products = products.into_iter();
+    if source {
+        products.next_back().unwrap()
+    } else {
+        products.next().unwrap()
+    }
+}
+
+#[tokio::test]
+async fn first_sync_copies_records_under_source_and_logs_high_water_mark() {
+    let shop = spawn_shop().await;
+    let office = TestApp::spawn().await;
+    let own = r#"{"name":"Kofola centrála","category":"Potraviny","quantity":9,"status":true,"bar_code":100,"cost_price":1.0,"sell_price":1.8}"#;
+    assert_eq!(office.post("/products", own).await.status, 201);
+
+    let report = pull(&office, &shop).await;
+    assert_eq!(report.source, format!("http://{}", shop.addr));
+    assert_eq!((report.employees.created, report.employees.skipped), (1, 1));
+    assert_eq!((report.products.created, report.products.updated), (2, 0));
+    assert_eq!(report.orders.created, 1);
+    assert!(report.products.high_water_mark.is_some());
+
+    // rovnaký čiarový kód z pobočky nezmení miestny produkt
+    assert_eq!(office.get("/products").await.json::<Vec<Product>>().len(), 3);
+    assert_eq!(product_by_code(&office, 100, false).await.quantity, Some(9.0));
+    let synced = product_by_code(&office, 100, true).await;
+    assert_eq!(synced.quantity, Some(3.0));
+
+    // položky predaja ukazujú na prevzaté záznamy
+    let orders: Vec<Order> = office.get("/orders").await.json();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].items[0].product_id, synced.id.unwrap());
+    let employees: Vec<Employee> = office.get("/employees").await.json();
+    assert_eq!(orders[0].employee_id, employees[0].id);
+
+    // bez zmien v pobočke sa nič neprevezme a značka zostane
+    let again = pull(&office, &shop).await;
+    assert_eq!((again.products.created, again.products.updated, again.orders.created), (0, 0, 0));
+    assert_eq!(again.products.high_water_mark, report.products.high_water_mark);
+
+    let log = office.db.get_sync_log().await.unwrap();
+    assert_eq!(log.len(), 6);
+    assert_eq!((log[0].entity.as_str(), log[5].entity.as_str()), ("orders", "employees"));
+    assert_eq!(log[4].counts.created, 2);
+}
+
+#[tokio::test]
+async fn changes_on_both_sides_are_reported_not_overwritten() {
+    let shop = spawn_shop().await;
+    let office = TestApp::spawn().await;
+    pull(&office, &shop).await;
+
+    // updated_at má rozlíšenie na milisekundy
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    let chlieb = product_by_code(&office, 200, true).await;
+    let res = office.put(&format!("/products/{}", chlieb.id.unwrap()), r#"{"sell_price":1.9}"#).await;
+    assert_eq!(res.status, 200);
+    for id in [1, 2] {
+        assert_eq!(send(&shop, "PUT", &format!("/products/{id}"), r#"{"sell_price":2.5}"#).await.status, 200);
+    }
+
+    let report = pull(&office, &shop).await;
+    assert_eq!((report.products.created, report.products.updated), (0, 1));
+    assert_eq!(report.products.conflicts.len(), 1);
+    assert_eq!(report.products.conflicts[0].key, "200");
+
+    assert_eq!(product_by_code(&office, 100, true).await.sell_price, Some(2.5));
+    assert_eq!(product_by_code(&office, 200, true).await.sell_price, Some(1.9));
+    let log = office.db.get_sync_log().await.unwrap();
+    let products = log.iter().find(|entry| entry.entity == "products").unwrap();
+    assert_eq!(products.counts.conflicts[0].key, "200");
+}
+
+#[tokio::test]
+async fn wrong_api_key_fails_without_logging() {
+    let shop = spawn_shop().await;
+    let office = TestApp::spawn().await;
+    let err = sync::pull(&office.db, &format!("http://{}", shop.addr), Some("zly")).await.unwrap_err();
+    assert!(err.to_string().contains("401"), "{err}");
+    assert!(office.db.get_sync_log().await.unwrap().is_empty());
+}
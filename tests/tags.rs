@@ -0,0 +1,45 @@
+use store_manager::structs::TaggingReport;
+use store_manager::test_support::TestApp;
+
+async fn spawn_with_products() -> TestApp {
+    let app = TestApp::spawn().await;
+    for (name, category) in [("Kofola", "Nápoje"), ("Vinea", "Nápoje"), ("Horalky", "Sladkosti")] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"{category}","quantity":5,"bar_code":0,"cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+    app
+}
+
+#[tokio::test]
+async fn whole_category_is_tagged_at_once() {
+    let app = spawn_with_products().await;
+
+    let res = app.post("/products/tag-by-filter", r#"{"filter":{"category":"Nápoje"},"tag":" Clearance "}"#).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    assert_eq!(res.json::<TaggingReport>(), TaggingReport { tag: "clearance".into(), tagged: 2, added: 2 });
+
+    assert_eq!(app.db.list_product_tags(1).await.unwrap(), ["clearance"]);
+    assert_eq!(app.db.list_product_tags(2).await.unwrap(), ["clearance"]);
+    assert!(app.db.list_product_tags(3).await.unwrap().is_empty());
+    assert_eq!(app.get("/products/1/tags").await.json::<Vec<String>>(), ["clearance"]);
+
+    // opakované označenie nepridá duplicitné väzby
+    let res = app.post("/products/tag-by-filter", r#"{"filter":{},"tag":"clearance"}"#).await;
+    assert_eq!(res.json::<TaggingReport>(), TaggingReport { tag: "clearance".into(), tagged: 3, added: 1 });
+
+    // zmazaný produkt stratí aj štítky
+    assert_eq!(app.delete("/products/1").await.status, 204);
+    assert_eq!(app.get("/products/1/tags").await.status, 404);
+    assert!(app.db.list_product_tags(1).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn empty_tag_is_rejected() {
+    let app = spawn_with_products().await;
+    let res = app.post("/products/tag-by-filter", r#"{"filter":{"category":"Nápoje"},"tag":"  "}"#).await;
+    assert_eq!(res.status, 400);
+    let res = app.post("/products/tag-by-filter", r#"{"filter":{"category":"Pečivo"},"tag":"novinka"}"#).await;
+    assert_eq!(res.json::<TaggingReport>().tagged, 0);
+}
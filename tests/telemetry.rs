@@ -0,0 +1,54 @@
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use store_manager::telemetry;
+use store_manager::test_support::TestApp;
+use tracing_subscriber::layer::SubscriberExt;
+
+fn attribute(span: &SpanData, key: &str) -> Option<String> {
+    span.attributes.iter().find(|kv| kv.key.as_str() == key).map(|kv| kv.value.as_str().into_owned())
+}
+
+#[tokio::test]
+async fn requests_become_traces_with_db_child_spans() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+    let subscriber = tracing_subscriber::registry().with(telemetry::layer(&provider));
+    // globálne, lebo spany databázy sa zatvárajú aj vo vláknach sqlx – s odberateľom len
+    // pre vlákno testu by sa span požiadavky nikdy nezavrel
+    tracing::subscriber::set_global_default(subscriber).unwrap();
+
+    let app = TestApp::spawn().await;
+    let res = app
+        .post("/employees", r#"{"name":"Jana","surname":"Nová","position":"Predavačka","phone_number":"0900123456"}"#)
+        .await;
+    assert_eq!(res.status, 201);
+    assert_eq!(app.get("/employees?phone_number=0900123456").await.status, 200);
+
+    // span požiadavky sa zavrie až so spanmi databázy, niekedy po odoslaní odpovede
+    let mut spans = Vec::new();
+    for _ in 0..100 {
+        provider.force_flush().unwrap();
+        spans = exporter.get_finished_spans().unwrap();
+        if spans.iter().any(|s| s.name == "GET /employees") {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let request = spans.iter().find(|s| s.name == "GET /employees").expect("span požiadavky");
+    assert_eq!(attribute(request, "http.route").as_deref(), Some("/employees"));
+    assert_eq!(attribute(request, "http.response.status_code").as_deref(), Some("200"));
+
+    let page = spans.iter().find(|s| s.name == "get_employees_page").expect("span databázy");
+    assert_eq!(page.parent_span_id, request.span_context.span_id());
+    assert_eq!(page.span_context.trace_id(), request.span_context.trace_id());
+    assert_eq!(attribute(page, "db.rows").as_deref(), Some("1"));
+    assert_eq!(attribute(page, "db.operation.name").as_deref(), Some("get_employees_page"));
+
+    // osobné údaje sa do atribútov nedostanú
+    for span in &spans {
+        for kv in &span.attributes {
+            let value = kv.value.as_str();
+            assert!(!value.contains("0900123456") && !value.contains("421900123456") && !value.contains("Jana"), "{kv:?}");
+        }
+    }
+}
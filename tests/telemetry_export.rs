@@ -0,0 +1,22 @@
+use store_manager::telemetry;
+use store_manager::test_support::TestApp;
+use tracing_subscriber::layer::SubscriberExt;
+
+#[test]
+fn unreachable_exporter_does_not_break_requests() {
+    // exportér sa vytvára mimo async kontextu, rovnako ako v `main`
+    let provider = telemetry::otlp_provider(Some("http://127.0.0.1:9/v1/traces")).unwrap();
+    let subscriber = tracing_subscriber::registry().with(telemetry::layer(&provider));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+    runtime.block_on(async {
+        let app = TestApp::spawn().await;
+        assert_eq!(app.get("/products").await.status, 200);
+        assert_eq!(app.get("/livez").await.status, 200);
+    });
+    drop(runtime);
+
+    assert!(provider.force_flush().is_err());
+    let _ = provider.shutdown();
+}
@@ -0,0 +1,89 @@
+use store_manager::config::Config;
+use store_manager::structs::{Employee, ImportReport, Product};
+use store_manager::test_support::TestApp;
+
+const KEY: &str = "tajny-kluc";
+
+async fn spawn_with_limits() -> TestApp {
+    TestApp::spawn_with_config(Config {
+        admin_api_key: Some(KEY.into()),
+        max_text_length: 10,
+        max_long_text_length: 20,
+        ..Config::default()
+    })
+    .await
+}
+
+fn product(name: &str, brand: &str, description: &str) -> String {
+    format!(
+        r#"{{"name":"{name}","category":"Nápoje","quantity":1,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0,"brand":"{brand}","description":"{description}"}}"#
+    )
+}
+
+#[tokio::test]
+async fn over_length_fields_are_rejected_with_field_and_limit() {
+    let app = spawn_with_limits().await;
+
+    let res = app.post("/products", &product("Kofola", "Kofolaaaaaa", "")).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("`brand`") && res.text().contains("10 znakov"), "{}", res.text());
+
+    // dĺžka sa počíta v znakoch, nie v bajtoch
+    assert_eq!(app.post("/products", &product("Kofola", "Čučoriedka", "")).await.status, 201);
+    let res = app.post("/products", &product("Kofola", "", &"x".repeat(21))).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("`description`") && res.text().contains("20 znakov"), "{}", res.text());
+
+    let res = app.put("/products/1", r#"{"supplier":"Dodávateľ s.r.o."}"#).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("`supplier`"), "{}", res.text());
+    let res = app.post("/products/bulk-update", r#"{"ids":[1],"set":{"category":"Nealkoholické"}}"#).await;
+    assert_eq!(res.status, 422);
+
+    let res = app.post("/employees", r#"{"name":"Jana","surname":"Nová","email":"jana.nova@obchod.sk"}"#).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("`email`"), "{}", res.text());
+
+    let line = format!(r#"{{"type":"product",{}"#, &product("Kofola", "", &"x".repeat(30))[1..]);
+    let res = app
+        .request_with_headers("POST", "/admin/import-ndjson?lenient=true", Some(&line), &[("x-api-key", KEY)])
+        .await;
+    let report: ImportReport = res.json();
+    assert_eq!((report.products, report.skipped), (0, 1));
+
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert_eq!(products.len(), 1);
+}
+
+#[tokio::test]
+async fn surrounding_whitespace_is_trimmed_on_write() {
+    let app = spawn_with_limits().await;
+
+    // bez okrajových medzier sa zmestí do limitu
+    assert_eq!(app.post("/products", &product("  Kofola  ", "   Kofola   ", " Sýtený nápoj ")).await.status, 201);
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.name.as_deref(), Some("Kofola"));
+    assert_eq!(product.brand.as_deref(), Some("Kofola"));
+    assert_eq!(product.description.as_deref(), Some("Sýtený nápoj"));
+
+    assert_eq!(app.put("/products/1", r#"{"category":"\tPečivo\n"}"#).await.status, 200);
+    let products: Vec<Product> = app.get("/products?category=Pe%C4%8Divo").await.json();
+    assert_eq!(products.len(), 1);
+
+    let body = r#"{"name":" Jana ","surname":"Nová ","position":"  Predavač","note":"  nový  "}"#;
+    assert_eq!(app.post("/employees", body).await.status, 201);
+    let employees: Vec<Employee> = app.get("/employees").await.json();
+    let employee = &employees[0];
+    assert_eq!(employee.name.as_deref(), Some("Jana"));
+    assert_eq!(employee.surname.as_deref(), Some("Nová"));
+    assert_eq!(employee.position.as_deref(), Some("Predavač"));
+    assert_eq!(employee.note.as_deref(), Some("nový"));
+}
+
+#[tokio::test]
+async fn default_limits_reject_pathological_input() {
+    let app = TestApp::spawn().await;
+    let res = app.post("/products", &product("Kofola", &"b".repeat(100_000), "")).await;
+    assert_eq!(res.status, 422);
+    assert!(res.text().contains("255 znakov"), "{}", res.text());
+}
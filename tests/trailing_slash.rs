@@ -0,0 +1,35 @@
+use store_manager::test_support::TestApp;
+
+const PRODUCT: &str = r#"{"name":"Kofola","category":"Nápoje","quantity":10,"status":true,"bar_code":1,"cost_price":0.05,"sell_price":2.0}"#;
+
+#[tokio::test]
+async fn trailing_slash_is_ignored() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products/", PRODUCT).await.status, 201);
+
+    for (plain, slashed) in [
+        ("/products", "/products/"),
+        ("/products/1", "/products/1/"),
+        ("/products?category=Nápoje", "/products/?category=Nápoje"),
+        ("/api/v1/products/1", "/api/v1/products/1/"),
+        ("/stats/profit?from=2024-01-01&to=2024-01-31", "/stats/profit/?from=2024-01-01&to=2024-01-31"),
+    ] {
+        let (plain_res, slashed_res) = (app.get(plain).await, app.get(slashed).await);
+        assert_eq!(plain_res.status, 200, "{plain}: {}", plain_res.text());
+        assert_eq!((slashed_res.status, slashed_res.text()), (plain_res.status, plain_res.text()), "{slashed}");
+    }
+    assert_eq!(app.get("/products//").await.status, 200);
+    assert_eq!(app.put("/products/1/", r#"{"quantity":5}"#).await.status, 200);
+    assert_eq!(app.delete("/products/1/").await.status, 204);
+    assert_eq!(app.get("/products/1").await.status, 404);
+}
+
+#[tokio::test]
+async fn paths_are_case_sensitive() {
+    let app = TestApp::spawn().await;
+    assert_eq!(app.post("/products", PRODUCT).await.status, 201);
+    for path in ["/Products", "/Products/", "/PRODUCTS/1", "/api/v1/Products"] {
+        assert_eq!(app.get(path).await.status, 404, "{path}");
+    }
+    assert_eq!(app.get("/").await.status, app.get("//").await.status);
+}
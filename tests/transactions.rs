@@ -0,0 +1,91 @@
+use store_manager::config::Config;
+use store_manager::structs::{Employee, Product, TxSessionInfo, TxStarted};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const EMPLOYEE: &str = r#"{"name":"Jana","surname":"Nová","position":"Predavač","status":true}"#;
+const PRODUCT: &str = r#"{"name":"Kofola","category":"Nápoje","quantity":5,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":1.5}"#;
+
+async fn in_tx(app: &TestApp, tx: &str, method: &str, path: &str, body: Option<&str>) -> TestResponse {
+    app.request_with_headers(method, path, body, &[("x-transaction-id", tx)]).await
+}
+
+/// Začne transakciu a v nej pridá zamestnanca a produkt.
+async fn two_step_flow(app: &TestApp) -> String {
+    let res = app.post("/tx", "").await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let tx = res.json::<TxStarted>().id;
+
+    assert_eq!(in_tx(app, &tx, "POST", "/employees", Some(EMPLOYEE)).await.status, 201);
+    assert_eq!(in_tx(app, &tx, "POST", "/products", Some(PRODUCT)).await.status, 201);
+    assert_eq!(in_tx(app, &tx, "GET", "/products", None).await.json::<Vec<Product>>().len(), 1);
+    tx
+}
+
+async fn counts(app: &TestApp) -> (usize, usize) {
+    let employees = app.get("/employees").await.json::<Vec<Employee>>().len();
+    let products = app.get("/products").await.json::<Vec<Product>>().len();
+    (employees, products)
+}
+
+#[tokio::test]
+async fn committed_flow_becomes_visible() {
+    let app = TestApp::spawn().await;
+    let tx = two_step_flow(&app).await;
+
+    // nepotvrdené zmeny mimo transakcie nevidno
+    assert_eq!(counts(&app).await, (0, 0));
+
+    assert_eq!(app.post(&format!("/tx/{tx}/commit"), "").await.status, 204);
+    assert_eq!(counts(&app).await, (1, 1));
+
+    // po ukončení sa token už nedá použiť
+    assert_eq!(app.post(&format!("/tx/{tx}/commit"), "").await.status, 404);
+    assert_eq!(in_tx(&app, &tx, "GET", "/products", None).await.status, 404);
+}
+
+#[tokio::test]
+async fn rolled_back_flow_leaves_no_trace() {
+    let app = TestApp::spawn().await;
+    let tx = two_step_flow(&app).await;
+
+    assert_eq!(app.post(&format!("/tx/{tx}/rollback"), "").await.status, 204);
+    assert_eq!(counts(&app).await, (0, 0));
+    assert_eq!(app.post(&format!("/tx/{tx}/rollback"), "").await.status, 404);
+
+    // po ukončení sa dá začať ďalšia transakcia a zápisy mimo nej fungujú
+    assert_eq!(app.post("/employees", EMPLOYEE).await.status, 201);
+    let tx = app.post("/tx", "").await.json::<TxStarted>().id;
+    assert_eq!(app.post(&format!("/tx/{tx}/rollback"), "").await.status, 204);
+}
+
+const KEY: &str = "tajny-kluc";
+
+async fn sessions(app: &TestApp) -> Vec<TxSessionInfo> {
+    let res = app.request_with_headers("GET", "/admin/tx-sessions", None, &[("x-api-key", KEY)]).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn idle_transaction_is_reaped_after_timeout() {
+    let app = TestApp::spawn_with_config(Config { tx_timeout: 1, admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+
+    let tx = app.post("/tx", "").await.json::<TxStarted>().id;
+    assert_eq!(in_tx(&app, &tx, "POST", "/employees", Some(EMPLOYEE)).await.status, 201);
+    let open = sessions(&app).await;
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].id, tx);
+    assert!(open[0].expires_in_secs <= 1.0, "{open:?}");
+    assert!(app.get("/metrics").await.text().contains("store_tx_sessions_open 1"));
+
+    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
+    assert!(sessions(&app).await.is_empty());
+    let metrics = app.get("/metrics").await.text();
+    assert!(metrics.contains("store_tx_sessions_open 0"), "{metrics}");
+    assert!(metrics.contains("store_tx_sessions_expired_total 1"), "{metrics}");
+
+    // zmeny sa vrátili späť a zámok zápisu je uvoľnený
+    assert_eq!(in_tx(&app, &tx, "GET", "/employees", None).await.status, 404);
+    assert_eq!(counts(&app).await, (0, 0));
+    assert_eq!(app.post("/employees", EMPLOYEE).await.status, 201);
+}
@@ -0,0 +1,110 @@
+use store_manager::db::StoreDB;
+use store_manager::db_filler::{DBFiller, LoadMode};
+use store_manager::structs::{Product, ProductTranslation};
+use store_manager::test_support::TestApp;
+
+/// Chlieb (1) s maďarským prekladom, mlieko (2) bez prekladu.
+async fn spawn_with_translation() -> TestApp {
+    let app = TestApp::spawn().await;
+    for name in ["Chlieb", "Mlieko"] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":5,"status":true,"bar_code":1,
+                "cost_price":1.0,"sell_price":2.0,"description":"Čerstvé"}}"#
+        );
+        assert_eq!(app.post("/products", &body).await.status, 201);
+    }
+
+    let res = app.put("/products/1/translations/hu", r#"{"name":"Kenyér","description":"Friss"}"#).await;
+    assert_eq!(res.status, 200);
+    let saved: ProductTranslation = res.json();
+    assert_eq!((saved.product_id, saved.lang.as_str()), (1, "hu"));
+    app
+}
+
+async fn get_localized<T: serde::de::DeserializeOwned>(app: &TestApp, path: &str, lang: &str) -> T {
+    app.request_with_headers("GET", path, None, &[("Accept-Language", lang)]).await.json()
+}
+
+#[tokio::test]
+async fn accept_language_overlays_translation() {
+    let app = spawn_with_translation().await;
+
+    let product: Product = get_localized(&app, "/products/1", "hu-HU,hu;q=0.9,sk;q=0.8").await;
+    assert_eq!(product.name.as_deref(), Some("Kenyér"));
+    assert_eq!(product.description.as_deref(), Some("Friss"));
+
+    // bez hlavičky alebo bez prekladu ostáva základné znenie
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.name.as_deref(), Some("Chlieb"));
+    let product: Product = get_localized(&app, "/products/1", "de, sk;q=0.5").await;
+    assert_eq!(product.name.as_deref(), Some("Chlieb"));
+
+    let products: Vec<Product> = get_localized(&app, "/products", "hu").await;
+    let names: Vec<_> = products.iter().map(|p| p.name.as_deref().unwrap()).collect();
+    assert_eq!(names, ["Kenyér", "Mlieko"]);
+
+    // preklad len názvu ponechá základný popis
+    assert_eq!(app.put("/products/2/translations/hu", r#"{"name":"Tej"}"#).await.status, 200);
+    let product: Product = get_localized(&app, "/products/2", "hu").await;
+    assert_eq!(product.name.as_deref(), Some("Tej"));
+    assert_eq!(product.description.as_deref(), Some("Čerstvé"));
+}
+
+#[tokio::test]
+async fn translations_can_be_updated_and_listed() {
+    let app = spawn_with_translation().await;
+
+    assert_eq!(app.put("/products/1/translations/HU", r#"{"name":"Fehér kenyér"}"#).await.status, 200);
+    assert_eq!(app.put("/products/1/translations/sk", r#"{"name":"Chlebík"}"#).await.status, 200);
+
+    let list: Vec<ProductTranslation> = app.get("/products/1/translations").await.json();
+    let langs: Vec<_> = list.iter().map(|t| (t.lang.as_str(), t.name.as_deref())).collect();
+    assert_eq!(langs, [("hu", Some("Fehér kenyér")), ("sk", Some("Chlebík"))]);
+    assert_eq!(list[0].description, None);
+
+    let hu: ProductTranslation = app.get("/products/1/translations/hu").await.json();
+    assert_eq!(hu.name.as_deref(), Some("Fehér kenyér"));
+    assert_eq!(app.get("/products/1/translations/de").await.status, 404);
+    assert_eq!(app.get("/products/9/translations").await.status, 404);
+
+    assert_eq!(app.put("/products/9/translations/hu", r#"{"name":"X"}"#).await.status, 404);
+    assert_eq!(app.put("/products/1/translations/h_u", r#"{"name":"X"}"#).await.status, 400);
+    assert_eq!(app.put("/products/1/translations/hu", r#"{"name":" "}"#).await.status, 400);
+}
+
+#[tokio::test]
+async fn search_optionally_matches_translations() {
+    let app = spawn_with_translation().await;
+
+    let found: Vec<Product> = app.post("/products/search", r#"{"name":"kenyér"}"#).await.json();
+    assert!(found.is_empty());
+
+    let found: Vec<Product> = app.post("/products/search?translations=true", r#"{"name":"Kenyér"}"#).await.json();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name.as_deref(), Some("Chlieb"));
+
+    let found: Vec<Product> = app.get("/products?translations=true&description=Friss").await.json();
+    assert_eq!(found.len(), 1);
+    let found: Vec<Product> = app.get("/products?translations=true&name=Mlieko").await.json();
+    assert_eq!(found.len(), 1);
+}
+
+#[tokio::test]
+async fn export_carries_translations() {
+    let app = spawn_with_translation().await;
+    let dir = std::env::temp_dir();
+    let json = dir.join(format!("store_manager_translations_{}.json", std::process::id()));
+    let copy = dir.join(format!("store_manager_translations_{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&copy);
+
+    DBFiller::save_to_json(&app.db, json.to_str().unwrap()).await.unwrap();
+    let db = StoreDB::open(copy.to_str().unwrap()).await.unwrap();
+    DBFiller::load_from_json(&db, json.to_str().unwrap(), LoadMode::Sequential).await.unwrap();
+
+    let translations = db.get_product_translations(None).await.unwrap();
+    assert_eq!(translations.len(), 1);
+    assert_eq!(translations[0].name.as_deref(), Some("Kenyér"));
+
+    let _ = std::fs::remove_file(&json);
+    let _ = std::fs::remove_file(&copy);
+}
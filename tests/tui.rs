@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Terminal;
+use store_manager::config::Config;
+use store_manager::test_support::TestApp;
+use store_manager::tui::{App, Tab};
+
+async fn press(app: &mut App, keys: &[KeyCode]) {
+    for key in keys {
+        app.handle_key(KeyEvent::from(*key)).await.unwrap();
+    }
+}
+
+async fn type_text(app: &mut App, text: &str) {
+    for c in text.chars() {
+        press(app, &[KeyCode::Char(c)]).await;
+    }
+}
+
+fn screen(terminal: &mut Terminal<TestBackend>, app: &mut App) -> String {
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let width = buffer.area.width as usize;
+    let symbols: Vec<&str> = buffer.content().iter().map(|c| c.symbol()).collect();
+    symbols.chunks(width).map(|line| line.concat()).collect::<Vec<_>>().join("\n")
+}
+
+async fn spawn_console() -> (TestApp, App, Terminal<TestBackend>) {
+    let server = TestApp::spawn().await;
+    for name in ["Kofola", "Rožok", "Mlieko"] {
+        let body = format!(
+            r#"{{"name":"{name}","category":"Potraviny","quantity":10,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+        );
+        assert_eq!(server.post("/products", &body).await.status, 201);
+    }
+    let body = r#"{"name":"Jana","surname":"Nová","position":"Predavačka","salary":1200}"#;
+    assert_eq!(server.post("/employees", body).await.status, 201);
+
+    let mut app = App::new(server.db.clone(), Arc::new(Config::default()));
+    app.refresh().await.unwrap();
+    (server, app, Terminal::new(TestBackend::new(120, 30)).unwrap())
+}
+
+#[tokio::test]
+async fn search_and_adjust_quantity() {
+    let (server, mut app, mut terminal) = spawn_console().await;
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("Kofola") && text.contains("Mlieko"), "{text}");
+    assert!(text.contains("strana 1/1, spolu 3"));
+
+    press(&mut app, &[KeyCode::Char('/')]).await;
+    type_text(&mut app, "Mlie").await;
+    press(&mut app, &[KeyCode::Enter]).await;
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("Mlieko") && !text.contains("Kofola"), "{text}");
+
+    // absolútne množstvo, potom zmena o -2
+    press(&mut app, &[KeyCode::Enter]).await;
+    type_text(&mut app, "7").await;
+    press(&mut app, &[KeyCode::Enter]).await;
+    press(&mut app, &[KeyCode::Enter]).await;
+    type_text(&mut app, "-2").await;
+    press(&mut app, &[KeyCode::Enter]).await;
+    assert_eq!(server.db.get_product(3).await.unwrap().unwrap().quantity, Some(5.0));
+
+    // odpis nad stav zásob sa zobrazí ako chyba, konzola beží ďalej
+    press(&mut app, &[KeyCode::Enter]).await;
+    type_text(&mut app, "-50").await;
+    press(&mut app, &[KeyCode::Enter]).await;
+    assert!(!app.should_quit());
+    assert_eq!(server.db.get_product(3).await.unwrap().unwrap().quantity, Some(5.0));
+}
+
+#[tokio::test]
+async fn delete_requires_confirmation() {
+    let (server, mut app, mut terminal) = spawn_console().await;
+
+    press(&mut app, &[KeyCode::Down, KeyCode::Char('d'), KeyCode::Char('n')]).await;
+    assert!(server.db.get_product(2).await.unwrap().is_some());
+
+    press(&mut app, &[KeyCode::Char('d')]).await;
+    assert!(screen(&mut terminal, &mut app).contains("Vymazať produkt Rožok? (y/n)"));
+    press(&mut app, &[KeyCode::Char('y')]).await;
+    assert!(server.db.get_product(2).await.unwrap().is_none());
+    assert!(screen(&mut terminal, &mut app).contains("spolu 2"));
+}
+
+#[tokio::test]
+async fn tabs_show_employees_and_dashboard() {
+    let (_server, mut app, mut terminal) = spawn_console().await;
+
+    press(&mut app, &[KeyCode::Tab]).await;
+    assert_eq!(app.tab(), Tab::Employees);
+    assert!(screen(&mut terminal, &mut app).contains("Nová"));
+
+    press(&mut app, &[KeyCode::Tab]).await;
+    assert_eq!(app.tab(), Tab::Dashboard);
+    let text = screen(&mut terminal, &mut app);
+    assert!(text.contains("Produkty: 3 (aktívne 3)") && text.contains("Zamestnanci: 1"), "{text}");
+    assert!(text.contains("Potraviny"));
+
+    press(&mut app, &[KeyCode::Char('q')]).await;
+    assert!(app.should_quit());
+}
@@ -0,0 +1,48 @@
+use chrono::Local;
+use store_manager::structs::InventoryTurnover;
+use store_manager::test_support::TestApp;
+
+async fn turnover(app: &TestApp) -> InventoryTurnover {
+    let today = Local::now().date_naive();
+    let res = app.get(&format!("/stats/turnover?from={today}&to={today}")).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    res.json()
+}
+
+#[tokio::test]
+async fn turnover_averages_snapshots_per_category() {
+    let app = TestApp::spawn().await;
+    app.add_product("Kofola", "Nápoje", 10.0, 2.0, 10.0).await;
+    app.add_product("Rožok", "Pečivo", 10.0, 1.0, 10.0).await;
+    app.db.snapshot_stats().await.unwrap();
+
+    app.sell(1, 5.0).await;
+    app.sell(2, 2.0).await;
+    app.db.snapshot_stats().await.unwrap();
+
+    let report = turnover(&app).await;
+    assert_eq!(report.snapshots, 2);
+    // zásoby 30 a 18 → priemer 24, náklady 5 × 2 + 2 × 1 = 12
+    assert_eq!((report.cogs, report.average_inventory), (12.0, 24.0));
+    assert_eq!(report.turnover, Some(0.5));
+
+    let drinks = &report.categories[0];
+    assert_eq!((drinks.category.as_str(), drinks.cogs, drinks.average_inventory), ("Nápoje", 10.0, 15.0));
+    assert!((drinks.turnover.unwrap() - 10.0 / 15.0).abs() < 1e-9);
+    assert_eq!(report.categories[1].average_inventory, 9.0);
+}
+
+#[tokio::test]
+async fn no_inventory_yields_no_ratio() {
+    let app = TestApp::spawn().await;
+    let report = turnover(&app).await;
+    assert_eq!((report.cogs, report.average_inventory, report.turnover), (0.0, 0.0, None));
+    assert_eq!(report.snapshots, 0);
+
+    // bez snímok sa použijú aktuálne zásoby
+    app.add_product("Kofola", "Nápoje", 4.0, 2.5, 10.0).await;
+    let report = turnover(&app).await;
+    assert_eq!((report.average_inventory, report.turnover), (10.0, Some(0.0)));
+
+    assert_eq!(app.get("/stats/turnover?from=2024-02-01&to=2024-01-01").await.status, 400);
+}
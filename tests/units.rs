@@ -0,0 +1,97 @@
+use store_manager::structs::{Order, Product, Unit};
+use store_manager::test_support::TestApp;
+
+async fn add_product(app: &TestApp, name: &str, quantity: f64, unit: &str, pack_size: Option<u32>) -> u16 {
+    let pack_size = pack_size.map(|p| p.to_string()).unwrap_or("null".into());
+    app.post(
+        "/products",
+        &format!(
+            r#"{{"name":"{name}","category":"Test","quantity":{quantity},"unit":"{unit}","pack_size":{pack_size},
+                "status":true,"bar_code":1,"cost_price":1.0,"sell_price":2.0}}"#
+        ),
+    )
+        .await
+        .status
+}
+
+#[tokio::test]
+async fn selling_grams_of_kg_product_converts_quantity() {
+    let app = TestApp::spawn().await;
+    assert_eq!(add_product(&app, "Syr", 2.0, "kg", None).await, 201);
+
+    let res = app.post("/orders", r#"{"items":[{"product_id":1,"quantity":250,"unit":"g"}]}"#).await;
+    assert_eq!(res.status, 201);
+
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.unit, Some(Unit::Kg));
+    assert_eq!(product.quantity, Some(1.75));
+
+    let order: Order = app.get("/orders/1").await.json();
+    assert_eq!(order.items[0].quantity, 0.25);
+    assert_eq!(order.items[0].unit, Some(Unit::Kg));
+
+    // hmotnosť nemožno prepočítať na objem
+    let res = app.post("/orders", r#"{"items":[{"product_id":1,"quantity":1,"unit":"l"}]}"#).await;
+    assert_eq!(res.status, 400);
+}
+
+#[tokio::test]
+async fn fractional_quantities_only_for_weight_and_volume() {
+    let app = TestApp::spawn().await;
+    assert_eq!(add_product(&app, "Rožok", 2.5, "piece", None).await, 400);
+    assert_eq!(add_product(&app, "Rožok", 10.0, "piece", None).await, 201);
+    assert_eq!(add_product(&app, "Mlieko", 1.5, "l", None).await, 201);
+
+    let res = app.post("/orders", r#"{"items":[{"product_id":1,"quantity":0.5}]}"#).await;
+    assert_eq!(res.status, 400);
+    assert_eq!(app.put("/products/1", r#"{"quantity":3.5}"#).await.status, 400);
+    assert_eq!(app.put("/products/2", r#"{"quantity":0.75}"#).await.status, 200);
+}
+
+#[tokio::test]
+async fn pack_products_convert_from_pieces_by_pack_size() {
+    let app = TestApp::spawn().await;
+    assert_eq!(add_product(&app, "Vajcia", 5.0, "pack", Some(10)).await, 201);
+
+    // 20 kusov = 2 balenia, 3 kusy nie sú celé balenie
+    let res = app.post("/orders", r#"{"items":[{"product_id":1,"quantity":20,"unit":"piece"}]}"#).await;
+    assert_eq!(res.status, 201);
+    let res = app.post("/orders", r#"{"items":[{"product_id":1,"quantity":3,"unit":"piece"}]}"#).await;
+    assert_eq!(res.status, 400);
+
+    let res = app.post("/products/1/batches", r#"{"lot_code":"V1","quantity":10,"unit":"piece"}"#).await;
+    assert_eq!(res.status, 201);
+
+    let product: Product = app.get("/products/1").await.json();
+    assert_eq!(product.quantity, Some(4.0));
+}
+
+#[tokio::test]
+async fn returns_validate_and_convert_units_like_sales() {
+    let app = TestApp::spawn().await;
+    assert_eq!(add_product(&app, "Rožok", 10.0, "piece", None).await, 201);
+    assert_eq!(add_product(&app, "Syr", 2.0, "kg", None).await, 201);
+    let res = app
+        .post("/orders", r#"{"items":[{"product_id":1,"quantity":4},{"product_id":2,"quantity":1,"unit_price":8.0}]}"#)
+        .await;
+    assert_eq!(res.status, 201);
+
+    // pol kusa sa nedá predať ani vrátiť
+    let res = app.post("/orders/1/returns", r#"{"items":[{"product_id":1,"quantity":0.5}]}"#).await;
+    assert_eq!(res.status, 400, "{}", res.text());
+    let res = app.post("/orders/1/returns", r#"{"items":[{"product_id":2,"quantity":1,"unit":"l"}]}"#).await;
+    assert_eq!(res.status, 400, "{}", res.text());
+
+    // 250 g syra je 0,25 kg za cenu z objednávky
+    let res = app.post("/orders/1/returns", r#"{"items":[{"product_id":2,"quantity":250,"unit":"g"}]}"#).await;
+    assert_eq!(res.status, 201, "{}", res.text());
+    let product: Product = app.get("/products/2").await.json();
+    assert_eq!(product.quantity, Some(1.25));
+    let order: Order = app.get("/orders/1").await.json();
+    let item = &order.returns[0].items[0];
+    assert_eq!((item.quantity, item.unit, item.unit_price), (0.25, None, Some(8.0)));
+
+    // prepočítané množstvo sa porovnáva s predaným
+    let res = app.post("/orders/1/returns", r#"{"items":[{"product_id":2,"quantity":800,"unit":"g"}]}"#).await;
+    assert_eq!(res.status, 400, "{}", res.text());
+}
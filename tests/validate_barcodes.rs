@@ -0,0 +1,69 @@
+use store_manager::config::Config;
+use store_manager::structs::{BarcodeValidationReport, Product};
+use store_manager::test_support::{TestApp, TestResponse};
+
+const KEY: &str = "tajny-kluc";
+const BOUNDARY: &str = "hranica-suboru";
+
+async fn upload(app: &TestApp, file_name: &str, content: &str) -> TestResponse {
+    let body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n{content}\r\n--{BOUNDARY}--\r\n"
+    );
+    let content_type = format!("multipart/form-data; boundary={BOUNDARY}");
+    app.request_with_headers(
+        "POST",
+        "/import/validate-barcodes",
+        Some(&body),
+        &[("x-api-key", KEY), ("content-type", &content_type)],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn reports_checksum_errors_duplicates_and_collisions() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    let existing = r#"{"name":"Horalky","category":"Sladkosti","quantity":3,"status":true,"bar_code":96385074,"cost_price":0.3,"sell_price":0.5}"#;
+    assert_eq!(app.post("/products", existing).await.status, 201);
+
+    let csv = "name,bar_code,quantity\n\
+               Kofola,4006381333931,1\n\
+               Vinea,4006381333932,1\n\
+               Rajec,5901234123457,1\n\
+               Rajec 2,5901234123457,1\n\
+               Tatranka,36000291452,1\n\
+               Horalky,96385074,1\n\
+               Bez kódu,,1\n";
+    let res = upload(&app, "dodavatel.csv", csv).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: BarcodeValidationReport = res.json();
+    assert!(!report.valid);
+    assert_eq!((report.rows, report.invalid, report.duplicates, report.collisions), (7, 2, 2, 1));
+
+    let line = |n: u64| report.lines.iter().find(|l| l.line == n).unwrap();
+    assert!(line(2).error.is_none() && line(2).duplicate_lines.is_empty());
+    assert!(line(3).error.as_deref().unwrap().contains("kontrolnú číslicu"));
+    assert_eq!((line(4).duplicate_lines.clone(), line(5).duplicate_lines.clone()), (vec![5], vec![4]));
+    assert!(line(6).error.is_none(), "UPC-A: {:?}", line(6).error);
+    assert_eq!(line(7).existing_products, vec![1]);
+    assert!(line(8).error.is_some());
+
+    // nič sa nezapísalo
+    let products: Vec<Product> = app.get("/products").await.json();
+    assert_eq!(products.len(), 1);
+}
+
+#[tokio::test]
+async fn valid_ndjson_passes_and_other_formats_are_rejected() {
+    let app = TestApp::spawn_with_config(Config { admin_api_key: Some(KEY.into()), ..Config::default() }).await;
+    let ndjson = "{\"type\":\"product\",\"name\":\"Kofola\",\"bar_code\":4006381333931}\n\
+                  {\"type\":\"employee\",\"name\":\"Jana\"}\n\
+                  {\"type\":\"product\",\"name\":\"Rajec\",\"bar_code\":5901234123457}\n";
+    let res = upload(&app, "dodavatel.ndjson", ndjson).await;
+    assert_eq!(res.status, 200, "{}", res.text());
+    let report: BarcodeValidationReport = res.json();
+    assert!(report.valid);
+    assert_eq!(report.lines.iter().map(|l| l.line).collect::<Vec<_>>(), vec![1, 3]);
+
+    assert_eq!(upload(&app, "store_data.json", "{}").await.status, 415);
+}
@@ -0,0 +1,64 @@
+use store_manager::structs::{ValuationMethod, ValuationReport};
+use store_manager::test_support::TestApp;
+
+async fn receive(app: &TestApp, product_id: u32, quantity: u32, unit_cost: f64) {
+    let body = format!(r#"{{"supplier":"Rajo","items":[{{"product_id":{product_id},"quantity":{quantity},"unit_cost":{unit_cost}}}]}}"#);
+    let id: u32 = app.post("/purchase-orders", &body).await.json();
+    assert_eq!(app.put(&format!("/purchase-orders/{id}"), r#"{"status":"sent"}"#).await.status, 200);
+    let body = format!(r#"{{"items":[{{"product_id":{product_id},"received_quantity":{quantity}}}]}}"#);
+    assert_eq!(app.post(&format!("/purchase-orders/{id}/receive"), &body).await.status, 200);
+}
+
+async fn valuation(app: &TestApp, query: &str) -> ValuationReport {
+    let res = app.get(&format!("/stats/valuation{query}")).await;
+    assert_eq!(res.status, 200);
+    res.json()
+}
+
+#[tokio::test]
+async fn fifo_keeps_the_newest_lots_and_average_blends_them() {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Káva","category":"Nápoje","quantity":0,"status":true,"bar_code":1,"cost_price":1.0,"sell_price":5.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+
+    receive(&app, 1, 10, 2.0).await;
+    receive(&app, 1, 10, 3.0).await;
+    // naskladnenie bez dokladu sa ocení aktuálnou nákupnou cenou
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":5}"#).await.status, 200);
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":-21}"#).await.status, 200);
+
+    // na sklade 4 ks: FIFO = 4 × 1.0 (najnovšia dávka), priemer = 4 × 55 / 25
+    let fifo = valuation(&app, "?method=fifo").await;
+    assert_eq!(fifo.method, ValuationMethod::Fifo);
+    assert_eq!(fifo.lines[0].quantity, 4.0);
+    assert!((fifo.total_value - 4.0).abs() < 1e-9);
+
+    let average = valuation(&app, "").await;
+    assert_eq!(average.method, ValuationMethod::Average);
+    assert!((average.total_value - 8.8).abs() < 1e-9);
+    assert!((average.lines[0].unit_cost.unwrap() - 2.2).abs() < 1e-9);
+
+    // FIFO berie dávky od najnovšej, kým nepokryje stav zásob
+    assert_eq!(app.post("/products/1/adjust-stock", r#"{"delta":3}"#).await.status, 200);
+    receive(&app, 1, 2, 4.0).await;
+    let fifo = valuation(&app, "?method=fifo").await;
+    // 9 ks: 2 × 4.0 + 3 × 1.0 + 4 × 1.0
+    assert!((fifo.total_value - 15.0).abs() < 1e-9);
+
+    assert_eq!(app.get("/stats/valuation?method=lifo").await.status, 400);
+}
+
+#[tokio::test]
+async fn stock_older_than_lots_is_valued_at_cost_price() {
+    let app = TestApp::spawn().await;
+    let body = r#"{"name":"Čaj","category":"Nápoje","quantity":4,"status":true,"bar_code":2,"cost_price":1.0,"sell_price":3.0}"#;
+    assert_eq!(app.post("/products", body).await.status, 201);
+    receive(&app, 1, 6, 2.0).await;
+
+    for method in ["fifo", "average"] {
+        let report = valuation(&app, &format!("?method={method}")).await;
+        let line = &report.lines[0];
+        assert_eq!((line.quantity, line.unlotted_quantity), (10.0, 4.0));
+        assert!((line.value - 16.0).abs() < 1e-9, "{method}: {}", line.value);
+    }
+}
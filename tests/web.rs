@@ -0,0 +1,52 @@
+use std::fs::{self, File};
+use std::time::{Duration, SystemTime};
+
+use store_manager::config::Config;
+use store_manager::test_support::TestApp;
+use store_manager::web::{INDEX_HTML, RELOAD_SCRIPT};
+
+#[tokio::test]
+async fn production_serves_embedded_page() {
+    let app = TestApp::spawn().await;
+
+    let res = app.get("/").await;
+    assert_eq!(res.status, 200);
+    assert_eq!(res.text(), INDEX_HTML);
+    assert_eq!(app.get("/__reload").await.status, 404);
+    assert_eq!(app.get("/web/index.html").await.status, 404);
+}
+
+#[tokio::test]
+async fn dev_mode_reads_files_from_disk_and_reloads() {
+    let dir = std::env::temp_dir().join(format!("store_manager_web_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("index.html"), "<html><body><h1>Verzia 1</h1></body></html>").unwrap();
+    fs::write(dir.join("style.css"), "h1 { color: red; }").unwrap();
+
+    let config = Config { dev_mode: true, web_dir: dir.to_string_lossy().into(), ..Config::default() };
+    let app = TestApp::spawn_with_config(config).await;
+
+    let page = app.get("/").await.text();
+    assert!(page.contains("Verzia 1"));
+    assert!(page.contains(RELOAD_SCRIPT.trim()));
+    assert!(page.find("/__reload").unwrap() < page.find("</body>").unwrap());
+
+    let css = app.get("/web/style.css").await;
+    assert_eq!(css.status, 200);
+    assert_eq!(css.header("content-type"), Some("text/css; charset=utf-8"));
+    assert_eq!(app.get("/web/..%2Fsecret").await.status, 404);
+
+    let before = app.get("/__reload").await.text();
+    fs::write(dir.join("index.html"), "<html><body><h1>Verzia 2</h1></body></html>").unwrap();
+    File::options()
+        .write(true)
+        .open(dir.join("index.html"))
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(5))
+        .unwrap();
+    let after = app.get("/__reload").await.text();
+    assert_ne!(before, after);
+    assert!(app.get("/").await.text().contains("Verzia 2"));
+
+    let _ = fs::remove_dir_all(&dir);
+}